@@ -1,13 +1,18 @@
 use axum::{
-    extract::State,
+    extract::{State, Extension, Path},
     response::Json as ResponseJson,
 };
+use diesel::prelude::*;
 use crate::{
     AppServices,
-    models::{User, Category, Post, Comment, Media, Page},
-    middleware::errors::AppError,
+    models::{User, Category, Post, Comment, Media, Page, Setting, Template},
+    models::navigation::{UpdateMenuArea, UpdateComponentTemplate},
+    middleware::{auth::AuthenticatedUser, errors::AppError},
+    services::theme_presets::DEFAULT_TEMPLATE_NAME,
+    services::component_templates::deactivate_superseded,
 };
-use sysinfo::{System, SystemExt, CpuExt, DiskExt, NetworksExt, NetworkExt};
+use tracing::info;
+use sysinfo::{System, SystemExt, CpuExt, DiskExt, NetworksExt, NetworkExt, ProcessExt};
 
 /// Get system statistics (admin only)
 /// 
@@ -50,25 +55,44 @@ pub async fn get_stats(
 /// Get performance metrics (admin only)
 /// 
 /// Returns comprehensive performance metrics for admin dashboard.
-/// Includes backend, frontend, and system metrics.
+/// Backend latencies come from the request metrics middleware and are
+/// cumulative since startup; frontend metrics are merged in client-side.
 /// Requires admin authentication.
 pub async fn get_performance_metrics(
     State(services): State<AppServices>
 ) -> Result<ResponseJson<serde_json::Value>, AppError> {
-    let mut system = System::new_all();
-    system.refresh_all();
+    // Only refresh what is reported below; a full refresh walks every process
+    let mut system = System::new();
+    system.refresh_memory();
+    system.refresh_cpu();
+    system.refresh_disks_list();
+    system.refresh_networks_list();
+    let process_memory_mb = sysinfo::get_current_pid().ok()
+        .filter(|pid| system.refresh_process(*pid))
+        .and_then(|pid| system.process(pid))
+        .map(|process| process.memory() as f64 / 1024.0 / 1024.0)
+        .unwrap_or(0.0);
+
+    let requests = services.metrics.request_latency();
+    let db_queries = services.metrics.db_query_latency();
+    let pool_state = services.db_pool.state();
     
-    // Backend metrics (mock data for now - in production, these would come from actual monitoring)
     let backend_metrics = serde_json::json!({
-        "avg_request_time": 120.5,
-        "max_request_time": 450.2,
-        "min_request_time": 45.1,
-        "total_requests": 15420_u64,
-        "error_rate": 1.2,
-        "db_query_avg_time": 35.8,
-        "db_connection_pool_active": 8_u32,
-        "db_connection_pool_idle": 12_u32,
-        "memory_usage_mb": 256.7,
+        "avg_request_time": requests.avg_ms,
+        "max_request_time": requests.max_ms,
+        "min_request_time": requests.min_ms,
+        "p50_request_time": requests.p50_ms,
+        "p95_request_time": requests.p95_ms,
+        "p99_request_time": requests.p99_ms,
+        "total_requests": requests.count,
+        "error_rate": services.metrics.error_rate(),
+        "db_query_avg_time": db_queries.avg_ms,
+        "db_query_p95_time": db_queries.p95_ms,
+        "db_connection_pool_active": pool_state.connections - pool_state.idle_connections,
+        "db_connection_pool_idle": pool_state.idle_connections,
+        "db_connection_pool_max": services.db_pool.max_size(),
+        "cache_hit_rate": services.metrics.cache_hit_rate(),
+        "memory_usage_mb": process_memory_mb,
         "active_sessions": services.session_manager.get_session_statistics().await?.active_sessions,
         "session_avg_duration": 1800.0
     });
@@ -169,6 +193,145 @@ pub async fn get_templates(
     Ok(ResponseJson(templates))
 }
 
+/// Reset the site theme to the shipped Default template (admin only)
+/// 
+/// Re-applies the seeded "Default" master template's menu areas, component
+/// templates, and container settings in one transaction, overwriting any
+/// customizations. Safe to call repeatedly.
+/// Requires admin authentication.
+pub async fn reset_default_template(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+) -> Result<ResponseJson<serde_json::Value>, AppError> {
+    let template = services.db_service.execute_optional(|conn| {
+        Template::find_by_name(conn, DEFAULT_TEMPLATE_NAME)
+    }).await?
+        .ok_or_else(|| AppError::NotFound(format!("Master template '{}' not found", DEFAULT_TEMPLATE_NAME)))?;
+
+    let response = apply_master_template(&services, template, &auth_user).await?;
+    Ok(ResponseJson(response))
+}
+
+/// Apply a master template to the site theme (admin only)
+/// 
+/// Writes the template's menu areas, component templates, and container
+/// settings in one transaction, so a failure leaves the current theme
+/// untouched. Used for the built-in presets and user-created templates alike.
+/// Requires admin authentication.
+pub async fn apply_template(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+    Path(id): Path<i32>,
+) -> Result<ResponseJson<serde_json::Value>, AppError> {
+    let template = services.db_service.execute_optional(move |conn| {
+        Template::find_by_id(conn, id)
+    }).await?
+        .ok_or_else(|| AppError::NotFound(format!("Master template {} not found", id)))?;
+
+    let response = apply_master_template(&services, template, &auth_user).await?;
+    Ok(ResponseJson(response))
+}
+
+/// Parse a master template's layout and apply it in a transaction, returning
+/// the JSON body shared by the reset and apply endpoints
+async fn apply_master_template(
+    services: &AppServices,
+    template: Template,
+    auth_user: &AuthenticatedUser,
+) -> Result<serde_json::Value, AppError> {
+    let layout: serde_json::Value = serde_json::from_str(&template.layout)
+        .map_err(|e| AppError::InternalError(format!("Invalid layout for template '{}': {}", template.name, e)))?;
+
+    let (menu_areas_reset, component_templates_reset, container_settings_reset) = services.db_service.execute(move |conn| {
+        conn.transaction(|conn| apply_template_layout(conn, &layout))
+    }).await?;
+    services.bootstrap_cache.invalidate();
+
+    info!(
+        "Theme set to '{}' by {}: {} menu areas, {} component templates, {} container settings",
+        template.name, auth_user.username, menu_areas_reset, component_templates_reset, container_settings_reset
+    );
+
+    Ok(serde_json::json!({
+        "success": true,
+        "message": format!("Theme set to the {} template", template.name),
+        "template_id": template.id,
+        "menu_areas_reset": menu_areas_reset,
+        "component_templates_reset": component_templates_reset,
+        "container_settings_reset": container_settings_reset
+    }))
+}
+
+/// Apply a master template layout (`menu_areas`, `component_templates`,
+/// `container_settings`) to the live configuration, returning how many of
+/// each were written. Entries for areas or component types that don't exist
+/// are skipped, matching how the template manager applies templates.
+fn apply_template_layout(conn: &mut PgConnection, layout: &serde_json::Value) -> QueryResult<(usize, usize, usize)> {
+    use crate::schema::{menu_areas, component_templates};
+
+    let now = chrono::Utc::now().naive_utc();
+    let mut menu_areas_reset = 0;
+    let mut component_templates_reset = 0;
+    let mut container_settings_reset = 0;
+
+    for area in layout.get("menu_areas").and_then(|v| v.as_array()).into_iter().flatten() {
+        let Some(area_name) = area.get("area_name").and_then(|v| v.as_str()) else { continue };
+        let update = UpdateMenuArea {
+            display_name: area.get("display_name").and_then(|v| v.as_str()).map(String::from),
+            template_id: None,
+            settings: area.get("settings").cloned(),
+            mobile_behavior: area.get("mobile_behavior").and_then(|v| v.as_str()).map(|v| Some(v.to_string())),
+            hamburger_icon: area.get("hamburger_icon").and_then(|v| v.as_str()).map(|v| Some(v.to_string())),
+            is_active: area.get("is_active").and_then(|v| v.as_bool()),
+            updated_at: Some(now),
+        };
+        menu_areas_reset += diesel::update(menu_areas::table.filter(menu_areas::area_name.eq(area_name)))
+            .set(update)
+            .execute(conn)?;
+    }
+
+    for component in layout.get("component_templates").and_then(|v| v.as_array()).into_iter().flatten() {
+        let Some(component_type) = component.get("component_type").and_then(|v| v.as_str()) else { continue };
+
+        // Prefer the default template for the type, otherwise the oldest one
+        let target_id = component_templates::table
+            .filter(component_templates::component_type.eq(component_type))
+            .order((component_templates::is_default.desc(), component_templates::id.asc()))
+            .select(component_templates::id)
+            .first::<i32>(conn)
+            .optional()?;
+        let Some(target_id) = target_id else { continue };
+        let is_active = component.get("is_active").and_then(|v| v.as_bool());
+        if is_active == Some(true) {
+            deactivate_superseded(conn, component_type, Some(target_id))?;
+        }
+
+        let update = UpdateComponentTemplate {
+            name: None,
+            template_data: component.get("template_data").cloned(),
+            breakpoints: component.get("breakpoints").cloned(),
+            width_setting: component.get("width_setting").and_then(|v| v.as_str()).map(|v| Some(v.to_string())),
+            max_width: component.get("max_width").and_then(|v| v.as_str()).map(|v| Some(v.to_string())),
+            is_default: None,
+            is_active,
+            updated_at: Some(now),
+        };
+        component_templates_reset += diesel::update(component_templates::table.find(target_id))
+            .set(update)
+            .execute(conn)?;
+    }
+
+    if let Some(container) = layout.get("container_settings").and_then(|v| v.as_object()) {
+        for (key, value) in container {
+            let setting_value = value.as_str().map(String::from).unwrap_or_else(|| value.to_string());
+            Setting::upsert(conn, &format!("container_{}", key), &setting_value, "container", None)?;
+            container_settings_reset += 1;
+        }
+    }
+
+    Ok((menu_areas_reset, component_templates_reset, container_settings_reset))
+}
+
 /// Get all components (admin only)
 /// 
 /// Returns available page builder components.
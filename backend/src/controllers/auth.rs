@@ -1,24 +1,37 @@
 use axum::{
-    extract::{State, Json},
+    extract::{State, Json, Extension},
     response::Json as ResponseJson,
-    http::HeaderMap,
+    http::{header, HeaderMap, Method},
 };
 use serde::{Deserialize, Serialize};
+use diesel::Connection;
 use chrono::{Utc, Duration};
 use crate::{
     AppServices,
-    models::{User, NewUser},
+    models::{Invite, User, NewUser, ProfileUpdate},
     middleware::{
-        auth::{get_authenticated_user, AuthenticatedUser},
-        validation::{validate_username, validate_email, validate_password},
+        auth::AuthenticatedUser,
+        validation::{validate_username, validate_email, validate_password, validate_text_content},
         errors::AppError,
     },
     services::{
+        SessionManager,
+        client_ip::ClientIp,
+        verification_tokens::{check_verification_token, resend_allowed, VERIFICATION_TOKEN_HOURS},
+        signup_policy::{check_bot_signals, check_invite, issue_form_token, load_min_fill_seconds, SignupMode},
+        email_domains::EmailDomainPolicy,
+        password_hashing::PasswordHashing,
+        user_uniqueness::{ensure_available, map_unique_violation},
+        captcha::{CaptchaConfig, LoginFailureTracker},
+        session_cookie::{clear_session_cookie, request_token, session_cookie},
+        permissions::{permissions_for_role, Permission},
         // Temporarily disabled for Docker build
         // email_service::{MockEmailService, generate_verification_token},
     },
 };
 
+const MAX_BIO_LENGTH: usize = 500;
+
 // Temporary replacement for email service function
 fn generate_verification_token() -> String {
     use rand::Rng;
@@ -27,11 +40,24 @@ fn generate_verification_token() -> String {
     hex::encode(token)
 }
 
+/// Send a verification email in the background so the response isn't held up
+fn send_verification_email(username: String, email: String, _token: String) {
+    // Temporarily disabled email functionality for Docker build. The token is
+    // a credential, so it is never logged.
+    tokio::spawn(async move {
+        tracing::info!("Email verification disabled for Docker build");
+        tracing::info!("User {} would receive verification email at {}", username, email);
+    });
+}
+
 // Authentication request/response structures
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
     pub username: String,
     pub password: String,
+    /// Required once there have been too many failed logins, when CAPTCHA is on
+    #[serde(default)]
+    pub captcha_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,6 +65,18 @@ pub struct SignupRequest {
     pub username: String,
     pub email: String,
     pub password: String,
+    /// Required when signup is invite-only
+    #[serde(default)]
+    pub invite_token: Option<String>,
+    /// Honeypot field hidden from people; bots tend to fill it in
+    #[serde(default)]
+    pub website: String,
+    /// The `form_token` from the signup policy, for timing the form
+    #[serde(default)]
+    pub form_token: Option<String>,
+    /// Required when CAPTCHA is on
+    #[serde(default)]
+    pub captcha_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -46,6 +84,20 @@ pub struct VerifyEmailRequest {
     pub token: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ResendVerificationRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+    /// Sign out every other session once the password has changed
+    #[serde(default)]
+    pub logout_other_sessions: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct LoginResponse {
     pub user: UserProfile,
@@ -59,16 +111,57 @@ pub struct UserProfile {
     pub email: String,
     pub role: String,
     pub status: String,
+    pub email_verified: bool,
+    /// Set by an admin; the user has to pick a new password before anything
+    /// else is allowed
+    pub must_change_password: bool,
+    pub bio: Option<String>,
+    /// New address waiting for confirmation
+    pub pending_email: Option<String>,
+    pub last_login_at: Option<chrono::NaiveDateTime>,
+    /// The login before the latest, i.e. before the current session's, which
+    /// is what the profile shows as the last login
+    pub previous_login_at: Option<chrono::NaiveDateTime>,
+    /// What the role allows in the admin area
+    pub permissions: &'static [Permission],
+}
+
+impl From<User> for UserProfile {
+    fn from(user: User) -> Self {
+        Self {
+            permissions: permissions_for_role(&user.role),
+            id: user.id,
+            username: user.username,
+            email: user.email.unwrap_or_default(),
+            role: user.role,
+            status: user.status,
+            email_verified: user.email_verified,
+            must_change_password: user.must_change_password,
+            bio: user.bio,
+            pending_email: user.pending_email,
+            last_login_at: user.last_login_at,
+            previous_login_at: user.previous_login_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProfileRequest {
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub bio: Option<String>,
 }
 
 /// User login endpoint
 /// 
 /// Validates user credentials and creates a session using the session manager.
 /// Implements rate limiting, input validation, and secure session creation.
+/// With cookie auth on, the session is also set in an HttpOnly cookie.
 pub async fn login(
     State(services): State<AppServices>, 
+    ClientIp(client_ip): ClientIp,
     Json(login_req): Json<LoginRequest>
-) -> Result<ResponseJson<LoginResponse>, AppError> {
+) -> Result<(HeaderMap, ResponseJson<LoginResponse>), AppError> {
     // Validate input
     validate_username(&login_req.username)?;
     if login_req.password.is_empty() {
@@ -77,10 +170,24 @@ pub async fn login(
 
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    // Repeated failures for this username or address call for a CAPTCHA
+    let client_ip = Some(client_ip.to_string());
+    let captcha = CaptchaConfig::load(&mut conn)?;
+    let failure_keys = LoginFailureTracker::keys(&login_req.username, client_ip.as_deref());
+    if services.login_failures.requires_captcha(&failure_keys, &captcha) {
+        // Don't keep a pooled connection checked out while the provider answers
+        drop(conn);
+        captcha.verify(login_req.captcha_token.as_deref(), client_ip.as_deref()).await?;
+        conn = services.db_pool.get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    }
     
     // Find user by username
-    let user = User::find_by_username(&mut conn, &login_req.username)?
-        .ok_or(AppError::Unauthorized)?;
+    let Some(user) = User::find_by_username(&mut conn, &login_req.username)? else {
+        services.login_failures.record_failure(&failure_keys);
+        return Err(AppError::Unauthorized);
+    };
     
     // Check if user is active
     if user.status != "active" {
@@ -91,24 +198,51 @@ pub async fn login(
     }
     
     // Verify password
-    match bcrypt::verify(&login_req.password, &user.password) {
+    match PasswordHashing::verify(&login_req.password, &user.password) {
         Ok(true) => {
+            // Upgrade hashes made under an older algorithm or cost; a failure
+            // here shouldn't stop the login
+            if services.password_hashing.needs_rehash(&user.password) {
+                match services.password_hashing.hash(&login_req.password) {
+                    // Only replaces the hash that was just verified, so a
+                    // concurrent password change isn't overwritten
+                    Ok(rehashed) => match User::replace_password_hash(&mut conn, user.id, &user.password, &rehashed) {
+                        Ok(true) => tracing::info!("Upgraded password hash for user {}", user.id),
+                        Ok(false) => tracing::info!("Skipped password hash upgrade for user {}: password changed meanwhile", user.id),
+                        Err(e) => tracing::warn!("Failed to store upgraded password hash for user {}: {}", user.id, e),
+                    },
+                    Err(e) => tracing::warn!("Failed to upgrade password hash for user {}: {}", user.id, e),
+                }
+            }
+
+            let user = User::record_login(&mut conn, user.id, Utc::now().naive_utc())?;
+            services.login_failures.clear_user(&failure_keys);
+
             // Password is correct, create session using session manager
-            let session = services.session_manager.create_session(user.id).await?;
+            let session = services.session_manager.create_session(user.id, client_ip).await?;
+
+            let mut response_headers = HeaderMap::new();
+            let config = services.session_manager.config();
+            if config.cookie_auth {
+                let max_age = session.expires_at
+                    .map(|expires_at| (expires_at - Utc::now().naive_utc()).num_seconds())
+                    .unwrap_or(config.session_duration_hours * 3600);
+                response_headers.insert(
+                    header::SET_COOKIE,
+                    session_cookie(&session.session_token, max_age, config.secure_cookies),
+                );
+            }
             
-            Ok(ResponseJson(LoginResponse {
+            Ok((response_headers, ResponseJson(LoginResponse {
                 token: session.session_token,
-                user: UserProfile {
-                    id: user.id,
-                    username: user.username,
-                    email: user.email.unwrap_or_default(),
-                    role: user.role,
-                    status: user.status,
-                },
-            }))
+                user: user.into(),
+            })))
+        }
+        Ok(false) => {
+            services.login_failures.record_failure(&failure_keys);
+            Err(AppError::Unauthorized)
         }
-        Ok(false) => Err(AppError::Unauthorized),
-        Err(_) => Err(AppError::InternalError("Password verification failed".to_string())),
+        Err(e) => Err(e),
     }
 }
 
@@ -117,49 +251,179 @@ pub async fn login(
 /// Returns the profile of the currently authenticated user.
 /// Requires valid session token in Authorization header.
 pub async fn get_current_user(
-    req: axum::extract::Request,
+    State(services): State<AppServices>,
+    Extension(auth_user): Extension<AuthenticatedUser>,
 ) -> Result<ResponseJson<UserProfile>, AppError> {
-    let auth_user: &AuthenticatedUser = get_authenticated_user(&req)?;
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let user = User::find_by_id(&mut conn, auth_user.id)?
+        .ok_or(AppError::Unauthorized)?;
     
-    Ok(ResponseJson(UserProfile {
-        id: auth_user.id,
-        username: auth_user.username.clone(),
-        email: auth_user.email.clone(),
-        role: auth_user.role.clone(),
-        status: auth_user.status.clone(),
-    }))
+    Ok(ResponseJson(user.into()))
 }
 
-/// Logout current session
+/// Update own profile endpoint
 /// 
-/// Invalidates the current session token.
+/// Lets the signed-in user change their username, bio and email address;
+/// role and status stay admin-only. A new email address is held as pending
+/// and only replaces the current one once confirmed through the link sent
+/// to it, so a typo can't lock the user out.
 /// Requires valid session token in Authorization header.
-pub async fn logout(
-    headers: HeaderMap,
+pub async fn update_current_user(
+    State(services): State<AppServices>,
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Json(profile_req): Json<UpdateProfileRequest>,
+) -> Result<ResponseJson<UserProfile>, AppError> {
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let mut profile = ProfileUpdate::default();
+
+    if let Some(username) = profile_req.username.map(|u| u.trim().to_string()) {
+        if username != auth_user.username {
+            validate_username(&username)?;
+            ensure_available(&mut conn, Some(&username), None, Some(auth_user.id))?;
+            profile.username = Some(username);
+        }
+    }
+
+    if let Some(bio) = profile_req.bio {
+        let bio = bio.trim().to_string();
+        validate_text_content(&bio, MAX_BIO_LENGTH)?;
+        profile.bio = Some(bio);
+    }
+
+    let mut confirmation = None;
+    if let Some(email) = profile_req.email.map(|e| e.trim().to_string()) {
+        if !email.eq_ignore_ascii_case(&auth_user.email) {
+            validate_email(&email)?;
+            EmailDomainPolicy::load(&mut conn)?.check(&email)?;
+            ensure_available(&mut conn, None, Some(&email), Some(auth_user.id))?;
+            let token = generate_verification_token();
+            profile.pending_email = Some(email.clone());
+            profile.email_verification_token = Some(token.clone());
+            profile.email_verification_expires_at = Some(Utc::now().naive_utc() + Duration::hours(VERIFICATION_TOKEN_HOURS));
+            confirmation = Some((email, token));
+        }
+    }
+
+    let updated = User::update_profile(&mut conn, auth_user.id, profile).map_err(map_unique_violation)?;
+    if let Some((email, token)) = confirmation {
+        send_verification_email(updated.username.clone(), email, token);
+    }
+    tracing::info!("User {} updated their profile", updated.id);
+
+    Ok(ResponseJson(updated.into()))
+}
+
+/// Change password endpoint
+/// 
+/// Replaces the caller's password after checking the current one, and clears
+/// an admin-set "must change password" flag. Other sessions stay signed in
+/// unless `logout_other_sessions` is set.
+/// Requires valid session token in Authorization header.
+pub async fn change_password(
     State(services): State<AppServices>,
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    method: Method,
+    headers: HeaderMap,
+    Json(change_req): Json<ChangePasswordRequest>,
 ) -> Result<ResponseJson<serde_json::Value>, AppError> {
-    // Extract token from authorization header
-    let auth_header = headers
-        .get("authorization")
-        .and_then(|h| h.to_str().ok())
-        .and_then(|h| h.strip_prefix("Bearer "))
-        .ok_or(AppError::MissingAuthHeader)?;
-    
-    // Use session manager to logout
-    services.session_manager.logout_session(auth_header).await?;
-    
+    validate_password(&change_req.new_password)?;
+    if change_req.new_password == change_req.current_password {
+        return Err(AppError::ValidationError("The new password must be different from the current one".to_string()));
+    }
+
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let user = User::find_by_id(&mut conn, auth_user.id)?
+        .ok_or(AppError::Unauthorized)?;
+    if !PasswordHashing::verify(&change_req.current_password, &user.password)? {
+        return Err(AppError::ValidationError("Current password is incorrect".to_string()));
+    }
+
+    let update_user = crate::models::UpdateUser {
+        username: None,
+        password: Some(services.password_hashing.hash(&change_req.new_password)?),
+        email: None,
+        role: None,
+        status: None,
+        email_verified: None,
+        email_verification_token: None,
+        email_verification_expires_at: None,
+        must_change_password: Some(false),
+    };
+    User::update(&mut conn, user.id, update_user)?;
+    tracing::info!("User {} changed their password", user.id);
+
+    let mut sessions_logged_out = 0;
+    if change_req.logout_other_sessions {
+        let (token, _) = request_token(&headers, &method, services.session_manager.config().cookie_auth)?;
+        sessions_logged_out = services.session_manager.logout_other_user_sessions(user.id, token).await?;
+    }
+
     Ok(ResponseJson(serde_json::json!({
         "success": true,
-        "message": "Logout successful"
+        "message": "Password changed",
+        "sessions_logged_out": sessions_logged_out
     })))
 }
 
+/// Logout current session
+/// 
+/// Invalidates the current session token and clears the session cookie.
+/// Requires valid session token in Authorization header or session cookie.
+pub async fn logout(
+    method: Method,
+    headers: HeaderMap,
+    State(services): State<AppServices>,
+) -> Result<(HeaderMap, ResponseJson<serde_json::Value>), AppError> {
+    let (token, _) = request_token(&headers, &method, services.session_manager.config().cookie_auth)?;
+    end_session(&services.session_manager, token).await
+}
+
+/// Delete the session behind `token` and clear the session cookie.
+/// A token whose session is already gone still gets its cookie cleared,
+/// so a stale cookie can't keep the browser looking logged in.
+async fn end_session(
+    session_manager: &SessionManager,
+    token: &str,
+) -> Result<(HeaderMap, ResponseJson<serde_json::Value>), AppError> {
+    match session_manager.logout_session(token).await {
+        Ok(()) | Err(AppError::NotFound(_) | AppError::InvalidToken) => {}
+        Err(e) => return Err(e),
+    }
+
+    // Cleared even when the request used a bearer token, so a cookie left
+    // from the same login doesn't outlive it
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(header::SET_COOKIE, clear_session_cookie(session_manager.config().secure_cookies));
+
+    Ok((response_headers, ResponseJson(serde_json::json!({
+        "success": true,
+        "message": "Logout successful"
+    }))))
+}
+
+/// Signup policy endpoint
+/// 
+/// Tells the signup page whether signup is open, invite-only or closed, and
+/// hands out the signed form token the signup request must return.
+pub async fn get_signup_policy(
+    State(services): State<AppServices>,
+) -> Result<ResponseJson<serde_json::Value>, AppError> {
+    let mode = services.db_service.execute(SignupMode::load).await?;
+    let form_token = issue_form_token(&services.signup_form_signer, Utc::now().timestamp_millis())?;
+    Ok(ResponseJson(serde_json::json!({ "mode": mode, "form_token": form_token })))
+}
+
 /// User signup endpoint
 /// 
 /// Creates a new user account with email verification required.
 /// Sends verification email and sets account to unverified status.
+/// Returns 403 when signup is closed, or invite-only and no invite is given.
 pub async fn signup(
     State(services): State<AppServices>, 
+    ClientIp(client_ip): ClientIp,
     Json(signup_req): Json<SignupRequest>
 ) -> Result<ResponseJson<serde_json::Value>, AppError> {
     // Validate input
@@ -170,23 +434,47 @@ pub async fn signup(
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
-    // Check if username already exists
-    if User::find_by_username(&mut conn, &signup_req.username)?.is_some() {
-        return Err(AppError::ConflictError("Username already exists".to_string()));
+    if let Err(e) = check_bot_signals(
+        &signup_req.website,
+        signup_req.form_token.as_deref(),
+        &services.signup_form_signer,
+        Utc::now().timestamp_millis(),
+        load_min_fill_seconds(&mut conn)?,
+    ) {
+        tracing::info!("Rejected likely automated signup for {}", signup_req.username);
+        return Err(e);
     }
+    let client_ip = Some(client_ip.to_string());
+    let captcha = CaptchaConfig::load(&mut conn)?;
+    // Don't keep a pooled connection checked out while the provider answers
+    drop(conn);
+    captcha.verify(signup_req.captcha_token.as_deref(), client_ip.as_deref()).await?;
+    conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    EmailDomainPolicy::load(&mut conn)?.check(&signup_req.email)?;
     
-    // Check if email already exists
-    if User::find_by_email(&mut conn, &signup_req.email)?.is_some() {
-        return Err(AppError::ConflictError("Email already exists".to_string()));
-    }
+    let now = Utc::now().naive_utc();
+    let invite = match SignupMode::load(&mut conn)? {
+        SignupMode::Open => None,
+        SignupMode::Closed => return Err(AppError::Forbidden),
+        SignupMode::InviteOnly => {
+            let token = signup_req.invite_token.as_deref().filter(|t| !t.is_empty())
+                .ok_or(AppError::Forbidden)?;
+            let invite = Invite::find_by_token(&mut conn, token)?;
+            check_invite(invite.as_ref(), &signup_req.email, now)?;
+            invite
+        }
+    };
+    
+    // Check the username and email aren't taken
+    ensure_available(&mut conn, Some(&signup_req.username), Some(&signup_req.email), None)?;
     
     // Hash password
-    let hashed_password = bcrypt::hash(&signup_req.password, bcrypt::DEFAULT_COST)
-        .map_err(|e| AppError::InternalError(format!("Password hashing failed: {}", e)))?;
+    let hashed_password = services.password_hashing.hash(&signup_req.password)?;
     
     // Generate verification token
     let verification_token = generate_verification_token();
-    let expires_at = Utc::now().naive_utc() + Duration::hours(24);
+    let expires_at = now + Duration::hours(VERIFICATION_TOKEN_HOURS);
     
     let new_user = NewUser {
         username: signup_req.username.clone(),
@@ -199,22 +487,19 @@ pub async fn signup(
         email_verification_expires_at: Some(expires_at),
     };
     
-    let created_user = User::create(&mut conn, new_user)?;
-    
-    // Send verification email asynchronously to avoid blocking the response
-    // Check if real email service should be used (via environment variable)
-    let use_real_email = std::env::var("USE_REAL_EMAIL").unwrap_or_else(|_| "false".to_string()) == "true";
+    // Claim the invite in the same transaction so it can only be used once
+    let created_user = conn.transaction::<_, AppError, _>(|conn| {
+        // A concurrent signup can take the name after the check above
+        let created_user = User::create(conn, new_user).map_err(map_unique_violation)?;
+        if let Some(invite) = &invite {
+            if !Invite::claim(conn, invite.id, created_user.id, now)? {
+                return Err(AppError::ValidationError("This invite is invalid or has expired".to_string()));
+            }
+        }
+        Ok(created_user)
+    })?;
     
-    let email = signup_req.email.clone();
-    let username = signup_req.username.clone();
-    let token = verification_token.clone();
-    
-    // Temporarily disabled email functionality for Docker build
-    tokio::spawn(async move {
-        tracing::info!("Email verification disabled for Docker build");
-        tracing::info!("User {} would receive verification email at {}", username, email);
-        tracing::info!("Verification token: {}", token);
-    });
+    send_verification_email(signup_req.username.clone(), signup_req.email.clone(), verification_token);
     
     Ok(ResponseJson(serde_json::json!({
         "success": true,
@@ -232,22 +517,38 @@ pub async fn verify_email(
     State(services): State<AppServices>, 
     Json(verify_req): Json<VerifyEmailRequest>
 ) -> Result<ResponseJson<serde_json::Value>, AppError> {
+    // Used tokens are stored as empty strings, so never look one up
+    if verify_req.token.is_empty() {
+        return Err(AppError::ValidationError("Invalid verification token".to_string()));
+    }
+
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
     // Find user by verification token
     let user = User::find_by_verification_token(&mut conn, &verify_req.token)?
-        .ok_or_else(|| AppError::ValidationError("Invalid or expired verification token".to_string()))?;
+        .ok_or_else(|| AppError::ValidationError("Invalid verification token".to_string()))?;
     
-    // Check if token is expired
-    if let Some(expires_at) = user.email_verification_expires_at {
-        if Utc::now().naive_utc() > expires_at {
-            return Err(AppError::ValidationError("Verification token has expired".to_string()));
+    // Expired tokens get their own message so the user can ask for a new one
+    check_verification_token(
+        user.email_verification_token.as_deref(),
+        user.email_verification_expires_at,
+        &verify_req.token,
+        Utc::now().naive_utc(),
+    )?;
+    
+    // Confirming a changed address swaps it in; the account was already active
+    if let Some(pending_email) = user.pending_email {
+        if User::find_by_email(&mut conn, &pending_email)?.is_some_and(|other| other.id != user.id) {
+            return Err(AppError::ValidationError("That email address is now used by another account".to_string()));
         }
-    } else {
-        return Err(AppError::ValidationError("Invalid verification token".to_string()));
+        User::confirm_pending_email(&mut conn, user.id, &pending_email)?;
+        return Ok(ResponseJson(serde_json::json!({
+            "success": true,
+            "message": "Your new email address is confirmed."
+        })));
     }
-    
+
     // Update user to verified status
     let update_user = crate::models::UpdateUser {
         username: None,
@@ -258,6 +559,7 @@ pub async fn verify_email(
         email_verified: Some(true),
         email_verification_token: Some(String::new()), // Clear the token
         email_verification_expires_at: None,
+        must_change_password: None,
     };
     
     User::update(&mut conn, user.id, update_user)?;
@@ -266,4 +568,93 @@ pub async fn verify_email(
         "success": true,
         "message": "Email verified successfully. Your account is now active."
     })))
-}
\ No newline at end of file
+}
+
+/// Resend verification email endpoint
+/// 
+/// Issues a fresh verification token to an unverified account and emails it.
+/// Always responds with success so the endpoint can't be used to find out
+/// which addresses have accounts. One resend per account every few minutes.
+pub async fn resend_verification(
+    State(services): State<AppServices>, 
+    Json(resend_req): Json<ResendVerificationRequest>
+) -> Result<ResponseJson<serde_json::Value>, AppError> {
+    let response = ResponseJson(serde_json::json!({
+        "success": true,
+        "message": "If an unverified account uses that address, a new verification email is on its way."
+    }));
+
+    let email = resend_req.email.trim();
+    if validate_email(email).is_err() {
+        return Ok(response);
+    }
+
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let Some(user) = User::find_by_email(&mut conn, email)? else {
+        return Ok(response);
+    };
+    if user.email_verified || user.status != "pending_verification" {
+        return Ok(response);
+    }
+
+    let now = Utc::now().naive_utc();
+    if !resend_allowed(user.email_verification_expires_at, now) {
+        tracing::info!("Skipping verification resend for user {}: sent too recently", user.id);
+        return Ok(response);
+    }
+
+    let verification_token = generate_verification_token();
+    let update_user = crate::models::UpdateUser {
+        username: None,
+        password: None,
+        email: None,
+        role: None,
+        status: None,
+        email_verified: None,
+        email_verification_token: Some(verification_token.clone()),
+        email_verification_expires_at: Some(now + Duration::hours(VERIFICATION_TOKEN_HOURS)),
+        must_change_password: None,
+    };
+    User::update(&mut conn, user.id, update_user)?;
+
+    send_verification_email(user.username, email.to_string(), verification_token);
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Session;
+    use crate::services::{SessionConfig, session_cookie::SESSION_COOKIE_NAME};
+
+    /// Runs against a scratch database:
+    /// `TEST_DATABASE_URL=postgres://... cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore = "needs a database in TEST_DATABASE_URL"]
+    async fn test_logout_ends_signed_session_and_clears_cookie() {
+        let _sessions = crate::test_support::lock_sessions().await;
+        let (pool, user) = crate::test_support::db_user("logout", "user");
+        let mut conn = pool.get().unwrap();
+
+        let config = SessionConfig { enable_token_signing: true, cookie_auth: true, ..SessionConfig::default() };
+        let manager = SessionManager::new_with_signing(pool.clone(), config, "test-session-secret");
+        let session = manager.create_session(user.id, None).await.unwrap();
+        assert!(crate::services::SessionSigner::is_signed_token(&session.session_token));
+
+        let (headers, _) = end_session(&manager, &session.session_token).await.unwrap();
+        let cookie = headers.get(header::SET_COOKIE).unwrap().to_str().unwrap();
+        assert!(cookie.starts_with(&format!("{}=;", SESSION_COOKIE_NAME)));
+        assert!(cookie.contains("Max-Age=0"));
+        assert!(manager.validate_session(&session.session_token, None).await.is_err());
+        assert_eq!(Session::count_active_sessions_for_user(&mut conn, user.id).unwrap(), 0);
+
+        // Logging out again finds no session but still clears the cookie
+        let (headers, _) = end_session(&manager, &session.session_token).await.unwrap();
+        assert!(headers.contains_key(header::SET_COOKIE));
+
+        User::delete(&mut conn, user.id).unwrap();
+    }
+}
@@ -0,0 +1,74 @@
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use std::time::Instant;
+use crate::{
+    AppServices,
+    controllers::navigation::{active_component_templates, public_area_items, FrontendComponentTemplate, FrontendNavigationItem},
+    middleware::errors::AppError,
+    models::Setting,
+    services::branding::Branding,
+};
+
+/// Everything the public layout needs before it can render
+#[derive(Debug, serde::Serialize)]
+pub struct PublicBootstrap {
+    pub header_navigation: Vec<FrontendNavigationItem>,
+    pub footer_navigation: Vec<FrontendNavigationItem>,
+    pub component_templates: Vec<FrontendComponentTemplate>,
+    pub site_settings: Vec<Setting>,
+    pub container_settings: Vec<Setting>,
+    /// Site logo and favicon URLs
+    pub branding: Branding,
+}
+
+/// Get the public layout's navigation, templates and settings (public endpoint)
+///
+/// Returns the header and footer navigation, the active component templates,
+/// the site and container settings and the logo and favicon URLs in one
+/// payload, the same data as their individual endpoints. The body is cached
+/// in memory and carries an ETag, so a browser revalidating it gets a 304.
+/// No authentication required.
+pub async fn get_public_bootstrap(
+    State(services): State<AppServices>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let cached = match services.bootstrap_cache.get(Instant::now()) {
+        Ok(cached) => cached,
+        Err(generation) => {
+            let mut conn = services.db_pool.get()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let bootstrap = PublicBootstrap {
+                header_navigation: public_area_items(&mut conn, "header")?,
+                footer_navigation: public_area_items(&mut conn, "footer")?,
+                component_templates: active_component_templates(&mut conn)?,
+                site_settings: Setting::list_by_type(&mut conn, "site")?,
+                container_settings: Setting::list_by_type(&mut conn, "container")?,
+                branding: Branding::load(&mut conn)?,
+            };
+            drop(conn);
+            let body = serde_json::to_string(&bootstrap)
+                .map_err(|e| AppError::InternalError(format!("Failed to serialize bootstrap: {}", e)))?;
+            services.bootstrap_cache.store(generation, body, Instant::now())
+        }
+    };
+
+    let not_modified = headers.get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| cached.matches(value));
+    let cache_headers = [
+        (header::ETAG, cached.etag.clone()),
+        // Revalidate every load so admin changes show up straight away
+        (header::CACHE_CONTROL, "no-cache".to_string()),
+    ];
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+    Ok((
+        cache_headers,
+        [(header::CONTENT_TYPE, "application/json")],
+        cached.body.as_str().to_owned(),
+    ).into_response())
+}
@@ -0,0 +1,118 @@
+use axum::{
+    extract::{State, Path, Json, Extension},
+    response::Json as ResponseJson,
+};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    AppServices,
+    middleware::{auth::AuthenticatedUser, errors::AppError},
+    services::color_schemes::{self, ColorSchemeList, StoredColorScheme},
+};
+
+/// Body for saving a custom scheme; either half may be omitted to keep it
+#[derive(Debug, Deserialize)]
+pub struct SaveColorSchemeRequest {
+    pub admin: Option<serde_json::Value>,
+    pub public: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RenameColorSchemeRequest {
+    pub new_name: String,
+}
+
+/// List design-system color schemes (admin only)
+/// 
+/// Returns the built-in presets followed by custom schemes in saved order,
+/// plus the active admin scheme. A stored active scheme that no longer exists
+/// is reported as the default preset.
+/// Requires admin authentication.
+pub async fn list_color_schemes(
+    State(services): State<AppServices>,
+) -> Result<ResponseJson<ColorSchemeList>, AppError> {
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
+    Ok(ResponseJson(color_schemes::list_schemes(&mut conn)?))
+}
+
+/// Get a custom color scheme (admin only)
+/// 
+/// Returns the stored admin and public colors; either may be null.
+/// Requires admin authentication.
+pub async fn get_color_scheme(
+    State(services): State<AppServices>,
+    Path(name): Path<String>,
+) -> Result<ResponseJson<StoredColorScheme>, AppError> {
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
+    Ok(ResponseJson(color_schemes::get_scheme(&mut conn, &name)?))
+}
+
+/// Create or update a custom color scheme (admin only)
+/// 
+/// Built-in preset names are reserved.
+/// Requires admin authentication.
+pub async fn save_color_scheme(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+    Path(name): Path<String>,
+    Json(request): Json<SaveColorSchemeRequest>,
+) -> Result<ResponseJson<StoredColorScheme>, AppError> {
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
+    let scheme = color_schemes::save_scheme(&mut conn, &name, request.admin, request.public)?;
+    info!("Color scheme '{}' saved by {}", scheme.name, auth_user.username);
+    Ok(ResponseJson(scheme))
+}
+
+/// Rename a custom color scheme (admin only)
+/// 
+/// Fails if the new name is taken. The active scheme stays active.
+/// Requires admin authentication.
+pub async fn rename_color_scheme(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+    Path(name): Path<String>,
+    Json(request): Json<RenameColorSchemeRequest>,
+) -> Result<ResponseJson<StoredColorScheme>, AppError> {
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
+    let scheme = color_schemes::rename_scheme(&mut conn, &name, &request.new_name)?;
+    info!("Color scheme '{}' renamed to '{}' by {}", name, scheme.name, auth_user.username);
+    Ok(ResponseJson(scheme))
+}
+
+/// Delete a custom color scheme (admin only)
+/// 
+/// The active admin scheme can't be deleted until another one is activated.
+/// Requires admin authentication.
+pub async fn delete_color_scheme(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+    Path(name): Path<String>,
+) -> Result<ResponseJson<serde_json::Value>, AppError> {
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
+    color_schemes::delete_scheme(&mut conn, &name)?;
+    info!("Color scheme '{}' deleted by {}", name, auth_user.username);
+    Ok(ResponseJson(serde_json::json!({
+        "success": true,
+        "message": format!("Color scheme '{}' deleted", name)
+    })))
+}
+
+/// Make a color scheme the active admin scheme (admin only)
+/// 
+/// Accepts built-in presets and custom schemes that have admin colors.
+/// Requires admin authentication.
+pub async fn activate_color_scheme(
+    State(services): State<AppServices>,
+    Path(name): Path<String>,
+) -> Result<ResponseJson<ColorSchemeList>, AppError> {
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
+    color_schemes::activate_scheme(&mut conn, &name)?;
+    Ok(ResponseJson(color_schemes::list_schemes(&mut conn)?))
+}
@@ -1,16 +1,26 @@
 use axum::{
     extract::{State, Path, Json, Query},
     response::Json as ResponseJson,
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, Uri},
+    Extension,
 };
+use diesel::Connection;
 use serde::{Deserialize, Serialize};
-use md5;
 use crate::{
     AppServices,
-    models::{Comment, NewComment, UpdateComment, User},
-    middleware::{
-        validation::validate_text_content,
-        errors::AppError,
+    models::{Comment, NewComment, ThreadComment, UpdateComment, User},
+    middleware::{auth::AuthenticatedUser, errors::AppError},
+    services::{
+        captcha::CaptchaConfig,
+        client_ip::ClientIp,
+        comment_limits::CommentLimits,
+        comment_moderation::{bulk_ids, ModerationAction, APPROVED},
+        comment_rate_limit::CommentRateLimits,
+        comment_sort::CommentSort,
+        cursor::{parse_cursor_param, split_page, Cursor},
+        gravatar::GravatarConfig,
+        pagination,
+        word_filter::WordFilter,
     },
 };
 
@@ -19,6 +29,12 @@ pub struct CommentQueryParams {
     pub post_id: Option<i32>,
     pub page_id: Option<i32>,
     pub user_id: Option<i32>,
+    /// Opt into cursor pagination; empty for the first page
+    pub cursor: Option<String>,
+    /// Comments per page in cursor mode
+    pub limit: Option<i64>,
+    /// `oldest` or `newest`; the site default when omitted
+    pub sort: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,10 +42,13 @@ pub struct PublicCommentRequest {
     pub content: String,
     pub post_id: Option<i32>,
     pub page_id: Option<i32>,
-    pub user_id: i32,
+    /// Required when CAPTCHA is on, except for admins and editors
+    #[serde(default)]
+    pub captcha_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct CommentWithGravatar {
     pub id: i32,
     pub post_id: Option<i32>,
@@ -41,12 +60,52 @@ pub struct CommentWithGravatar {
     pub author_username: Option<String>,
     pub author_email: Option<String>,
     pub gravatar_url: String,
+    /// `pending` when the comment is held for moderation
+    pub status: String,
 }
 
-fn generate_gravatar_url(email: &str, size: u32) -> String {
-    let trimmed_email = email.trim().to_lowercase();
-    let hash = format!("{:x}", md5::compute(trimmed_email.as_bytes()));
-    format!("https://www.gravatar.com/avatar/{}?s={}&d=identicon&r=pg", hash, size)
+/// A whole thread, or one cursor page of it
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum CommentThread {
+    All(Vec<CommentWithGravatar>),
+    Page {
+        comments: Vec<CommentWithGravatar>,
+        /// `null` on the last page
+        next_cursor: Option<String>,
+    },
+}
+
+impl CommentWithGravatar {
+    pub fn new(comment: ThreadComment, gravatar: &GravatarConfig) -> Self {
+        let gravatar_url = gravatar.url(comment.author_email.as_deref(), 80);
+
+        CommentWithGravatar {
+            id: comment.id,
+            post_id: comment.post_id,
+            page_id: comment.page_id,
+            user_id: comment.user_id,
+            content: comment.content,
+            created_at: comment.created_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            updated_at: comment.updated_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            author_username: comment.author_username,
+            author_email: comment.author_email,
+            gravatar_url,
+            status: APPROVED.to_string(),
+        }
+    }
+}
+
+/// Get comment limits
+/// 
+/// Returns the maximum comment length and number of links so the comment
+/// form can show a live counter, and the default sort order of the thread.
+/// No authentication required for public access.
+pub async fn get_comment_limits(
+    State(services): State<AppServices>,
+) -> Result<ResponseJson<CommentLimits>, AppError> {
+    let limits = services.db_service.execute(CommentLimits::load).await?;
+    Ok(ResponseJson(limits))
 }
 
 /// Get all comments (admin only)
@@ -64,88 +123,50 @@ pub async fn get_comments(
 
 /// Get comments for a post or page (public endpoint)
 /// 
-/// Returns comments for a specific post or page with Gravatar URLs.
+/// Returns comments for a specific post or page with Gravatar URLs, in the
+/// order given by `sort` (`oldest` or `newest`, defaulting to the site's
+/// `comments_default_sort` setting). Passing `cursor` (empty for the first page) switches to cursor
+/// pagination: the response is one page of `limit` comments plus a
+/// `next_cursor` for the page after it, which stays stable while comments
+/// are added or removed, and is also sent as a `Link: rel="next"` header.
 /// No authentication required.
 pub async fn get_post_comments(
     State(services): State<AppServices>,
+    uri: Uri,
     Query(params): Query<CommentQueryParams>
-) -> Result<ResponseJson<Vec<CommentWithGravatar>>, AppError> {
+) -> Result<(HeaderMap, ResponseJson<CommentThread>), AppError> {
+    let cursor = parse_cursor_param(params.cursor.as_deref())?;
+    let requested_sort = params.sort.as_deref().map(CommentSort::parse).transpose()?;
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    
-    let comments_with_relations = if let Some(post_id) = params.post_id {
-        // Get comments for specific post with user details
-        use crate::schema::{comments, users, posts};
-        use diesel::prelude::*;
-        
-        comments::table
-            .left_join(users::table.on(comments::user_id.eq(users::id.nullable())))
-            .left_join(posts::table.on(comments::post_id.eq(posts::id.nullable())))
-            .filter(comments::post_id.eq(post_id))
-            .order(comments::created_at.asc())
-            .select((
-                comments::id,
-                comments::post_id,
-                comments::page_id,
-                comments::user_id,
-                comments::content,
-                comments::created_at,
-                comments::updated_at,
-                users::username.nullable(),
-                users::email.nullable(),
-            ))
-            .load::<(i32, Option<i32>, Option<i32>, Option<i32>, String, Option<chrono::NaiveDateTime>, Option<chrono::NaiveDateTime>, Option<String>, Option<String>)>(&mut conn)?
-    } else if let Some(page_id) = params.page_id {
-        // Get comments for specific page with user details
-        use crate::schema::{comments, users, pages};
-        use diesel::prelude::*;
-        
-        comments::table
-            .left_join(users::table.on(comments::user_id.eq(users::id.nullable())))
-            .left_join(pages::table.on(comments::page_id.eq(pages::id.nullable())))
-            .filter(comments::page_id.eq(page_id))
-            .order(comments::created_at.asc())
-            .select((
-                comments::id,
-                comments::post_id,
-                comments::page_id,
-                comments::user_id,
-                comments::content,
-                comments::created_at,
-                comments::updated_at,
-                users::username.nullable(),
-                users::email.nullable(),
-            ))
-            .load::<(i32, Option<i32>, Option<i32>, Option<i32>, String, Option<chrono::NaiveDateTime>, Option<chrono::NaiveDateTime>, Option<String>, Option<String>)>(&mut conn)?
-    } else {
-        // If no post_id or page_id specified, return empty vec for public endpoint
-        vec![]
+    let newest_first = match requested_sort {
+        Some(sort) => sort,
+        None => CommentSort::load_default(&mut conn)?,
+    } == CommentSort::Newest;
+    let gravatar = GravatarConfig::load(&mut conn)?;
+
+    // If no post_id or page_id is given the thread is empty
+    let Some(after) = cursor else {
+        let comments = Comment::list_thread_after(&mut conn, params.post_id, params.page_id, None, None, newest_first)?;
+        return Ok((HeaderMap::new(), ResponseJson(CommentThread::All(comments.into_iter().map(|comment| CommentWithGravatar::new(comment, &gravatar)).collect()))));
     };
-    
-    let comments_with_gravatar: Vec<CommentWithGravatar> = comments_with_relations
-        .into_iter()
-        .map(|(id, post_id, page_id, user_id, content, created_at, updated_at, username, email)| {
-            let gravatar_url = email
-                .as_ref()
-                .map(|e| generate_gravatar_url(e, 80))
-                .unwrap_or_else(|| generate_gravatar_url("default@example.com", 80));
-            
-            CommentWithGravatar {
-                id,
-                post_id,
-                page_id,
-                user_id,
-                content,
-                created_at: created_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-                updated_at: updated_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-                author_username: username,
-                author_email: email,
-                gravatar_url,
-            }
-        })
-        .collect();
-    
-    Ok(ResponseJson(comments_with_gravatar))
+
+    let limit = services.pagination.per_page(params.limit)?;
+    let rows = Comment::list_thread_after(
+        &mut conn,
+        params.post_id,
+        params.page_id,
+        after.map(|cursor| (cursor.created_at, cursor.id)),
+        Some(limit + 1),
+        newest_first,
+    )?;
+    let (rows, next_cursor) = split_page(rows, limit as usize, |row| Cursor::new(row.created_at, row.id));
+
+    let headers = pagination::cursor_headers(&uri, None, next_cursor.as_deref());
+    Ok((headers, ResponseJson(CommentThread::Page {
+        comments: rows.into_iter().map(|comment| CommentWithGravatar::new(comment, &gravatar)).collect(),
+        next_cursor,
+    })))
 }
 
 /// Create a new comment (admin only)
@@ -168,11 +189,11 @@ pub async fn create_comment(
         return Err(AppError::ValidationError("Content cannot be empty".to_string()));
     }
     
-    validate_text_content(&content, 2000)?;
-    
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
+    CommentLimits::load(&mut conn)?.validate(&content)?;
+    
     let page_id = comment_data["page_id"].as_i64().map(|id| id as i32);
     
     let new_comment = NewComment {
@@ -180,6 +201,7 @@ pub async fn create_comment(
         page_id,
         user_id,
         content: content.trim().to_string(),
+        status: APPROVED.to_string(),
     };
     
     let created_comment = Comment::create(&mut conn, new_comment)?;
@@ -196,10 +218,13 @@ pub async fn create_comment(
 /// Create a new comment (public endpoint, requires authentication)
 /// 
 /// Creates a new comment from authenticated users.
-/// Content is sanitized and validated for security.
+/// Content is sanitized and validated for security, and submissions are
+/// rate limited per user and per client IP.
 /// Requires user authentication but not admin.
 pub async fn create_public_comment(
     State(services): State<AppServices>, 
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    ClientIp(client_ip): ClientIp,
     Json(comment_request): Json<PublicCommentRequest>
 ) -> Result<(StatusCode, ResponseJson<CommentWithGravatar>), AppError> {
     // Validate content
@@ -214,29 +239,43 @@ pub async fn create_public_comment(
         _ => {} // Valid: exactly one is specified
     }
     
-    validate_text_content(&comment_request.content, 2000)?;
-    
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
-    // Verify user exists
-    let user = User::find_by_id(&mut conn, comment_request.user_id)?
-        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let limits = CommentLimits::load(&mut conn)?;
+    let author = User::find_by_id(&mut conn, auth_user.id)?
+        .ok_or(AppError::Unauthorized)?;
+    limits.check_author(&author.role, author.email_verified)?;
+    let status = limits.initial_status(&author.role, Comment::has_approved_by_user(&mut conn, author.id)?);
+    limits.validate(&comment_request.content)?;
+    let content = WordFilter::load(&mut conn)?.apply(comment_request.content.trim())?;
+    let client_ip = client_ip.to_string();
+
+    // Checked after validation so rejected comments don't use up the
+    // allowance, but before the CAPTCHA so a flood never reaches the provider
+    let rate_keys = vec![format!("user:{}", auth_user.id), format!("ip:{}", client_ip)];
+    services.comment_rate_limiter.check(&rate_keys, CommentRateLimits::load(&mut conn)?)?;
+
+    if !matches!(author.role.as_str(), "admin" | "editor") {
+        let captcha = CaptchaConfig::load(&mut conn)?;
+        // Don't keep a pooled connection checked out while the provider answers
+        drop(conn);
+        captcha.verify(comment_request.captcha_token.as_deref(), Some(&client_ip)).await?;
+        conn = services.db_pool.get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    }
     
     let new_comment = NewComment {
         post_id: comment_request.post_id,
         page_id: comment_request.page_id,
-        user_id: Some(comment_request.user_id),
-        content: comment_request.content.trim().to_string(),
+        user_id: Some(author.id),
+        content,
+        status: status.to_string(),
     };
     
     let created_comment = Comment::create(&mut conn, new_comment)?;
     
-    // Generate gravatar URL
-    let gravatar_url = user.email
-        .as_ref()
-        .map(|e| generate_gravatar_url(e, 80))
-        .unwrap_or_else(|| generate_gravatar_url("default@example.com", 80));
+    let gravatar_url = GravatarConfig::load(&mut conn)?.url(author.email.as_deref(), 80);
     
     let comment_with_gravatar = CommentWithGravatar {
         id: created_comment.id,
@@ -246,9 +285,10 @@ pub async fn create_public_comment(
         content: created_comment.content,
         created_at: created_comment.created_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
         updated_at: created_comment.updated_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-        author_username: Some(user.username),
-        author_email: user.email,
+        author_username: Some(author.username),
+        author_email: author.email,
         gravatar_url,
+        status: created_comment.status,
     };
     
     Ok((StatusCode::CREATED, ResponseJson(comment_with_gravatar)))
@@ -265,16 +305,16 @@ pub async fn update_comment(
 ) -> Result<ResponseJson<serde_json::Value>, AppError> {
     let content = comment_data["content"].as_str().map(|s| s.to_string());
     
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    
     if let Some(ref content_str) = content {
         if content_str.trim().is_empty() {
             return Err(AppError::ValidationError("Content cannot be empty".to_string()));
         }
-        validate_text_content(content_str, 2000)?;
+        CommentLimits::load(&mut conn)?.validate(content_str)?;
     }
     
-    let mut conn = services.db_pool.get()
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    
     // Check if comment exists
     let _existing_comment = Comment::find_by_id(&mut conn, id)?
         .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?;
@@ -317,4 +357,72 @@ pub async fn delete_comment(
         "success": true,
         "message": "Comment deleted successfully"
     })))
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkModerationRequest {
+    pub action: ModerationAction,
+    pub ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModeratedComment {
+    pub id: i32,
+    pub success: bool,
+    /// The comment's status afterwards; `None` once deleted
+    pub status: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkModerationReport {
+    pub action: ModerationAction,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<ModeratedComment>,
+}
+
+/// Moderate many comments at once (admin only)
+/// 
+/// Approves, rejects, marks as spam or deletes every listed comment in one
+/// transaction. Comments that no longer exist, e.g. deleted by another
+/// moderator meanwhile, are reported as failures without affecting the
+/// rest; a database error rolls the whole batch back.
+/// Requires admin authentication.
+pub async fn bulk_moderate_comments(
+    State(services): State<AppServices>,
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Json(request): Json<BulkModerationRequest>,
+) -> Result<ResponseJson<BulkModerationReport>, AppError> {
+    let ids = bulk_ids(&request.ids)?;
+    let action = request.action;
+
+    let results = services.db_service.execute(move |conn| {
+        conn.transaction(|conn| {
+            ids.into_iter().map(|id| {
+                let found = match action.status() {
+                    Some(status) => Comment::set_status(conn, id, status)?.map(|comment| Some(comment.status)),
+                    None => (Comment::delete(conn, id)? > 0).then_some(None),
+                };
+                Ok(match found {
+                    Some(status) => ModeratedComment { id, success: true, status, error: None },
+                    None => ModeratedComment {
+                        id,
+                        success: false,
+                        status: None,
+                        error: Some("Comment not found; it may already have been deleted".to_string()),
+                    },
+                })
+            }).collect::<Result<Vec<_>, diesel::result::Error>>()
+        })
+    }).await?;
+
+    let succeeded = results.iter().filter(|result| result.success).count();
+    tracing::info!("User {} applied {:?} to {} comments", auth_user.id, action, succeeded);
+    Ok(ResponseJson(BulkModerationReport {
+        action,
+        succeeded,
+        failed: results.len() - succeeded,
+        results,
+    }))
+}
@@ -0,0 +1,44 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use crate::{
+    AppServices,
+    middleware::errors::AppError,
+    models::{Post, Setting},
+    services::{canonical_urls::public_base_url, feed::{render_rss, FeedChannel, FeedConfig}},
+};
+
+/// Get the RSS feed of published posts (public endpoint)
+/// 
+/// Returns the newest published posts as RSS 2.0, as many as the
+/// `feed_item_count` setting allows, with full content when
+/// `feed_full_content` is on. Links point at the site's `site_url` setting,
+/// falling back to `BASE_URL`.
+/// No authentication required.
+pub async fn get_feed(
+    State(services): State<AppServices>,
+) -> Result<Response, AppError> {
+    let (channel, posts, config) = services.db_service.execute(|conn| {
+        let config = FeedConfig::load(conn)?;
+        let mut setting = |key: &str| -> Result<Option<String>, diesel::result::Error> {
+            Ok(Setting::find_by_key(conn, key)?
+                .and_then(|s| s.setting_value)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty()))
+        };
+        let channel = FeedChannel {
+            title: setting("site_title")?.unwrap_or_else(|| "My Rust CMS".to_string()),
+            description: setting("site_description")?.unwrap_or_default(),
+            link: public_base_url(conn)?,
+        };
+        let posts = Post::list_published(conn, config.item_count as i64)?;
+        Ok((channel, posts, config))
+    }).await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+        render_rss(&channel, &posts, &config),
+    ).into_response())
+}
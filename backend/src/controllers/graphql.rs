@@ -0,0 +1,149 @@
+//! Read-only GraphQL endpoint, built with the `graphql` feature
+//!
+//! Lets a client fetch what a public page needs (posts, pages, navigation,
+//! settings and comments) in one request instead of several REST calls.
+//! Resolvers go through the same models and visibility rules as the public
+//! REST endpoints and return the same shapes. There are no mutations yet.
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema};
+use axum::{
+    extract::{Json, State},
+    response::Json as ResponseJson,
+};
+use chrono::Utc;
+use crate::{
+    AppServices,
+    controllers::{
+        comments::CommentWithGravatar,
+        navigation::FrontendNavigationItem,
+        pages::FrontendPage,
+        posts::{with_comment_counts, FrontendPost},
+    },
+    middleware::errors::AppError,
+    models::{Comment, Navigation, Page, Post, PostFilter, Setting},
+    services::{gravatar::GravatarConfig, pagination::PaginationParams, post_schedule, slug_redirects::{self, SlugLookup}, visibility_window::is_visible_at},
+};
+
+pub type CmsSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Setting types the public may read, as on `/api/public/system/settings`
+const PUBLIC_SETTING_TYPES: [&str; 3] = ["site", "container", "theme"];
+const MAX_QUERY_DEPTH: usize = 8;
+const MAX_QUERY_COMPLEXITY: usize = 500;
+
+fn services<'a>(ctx: &Context<'a>) -> &'a AppServices {
+    ctx.data_unchecked::<AppServices>()
+}
+
+/// Resolve `page`/`perPage` arguments the same way the REST lists do
+fn paging(ctx: &Context<'_>, page: Option<i32>, per_page: Option<i32>) -> Result<PaginationParams, AppError> {
+    PaginationParams::new(page.map(i64::from), per_page.map(i64::from), &services(ctx).pagination)
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Published posts, newest first
+    async fn posts(&self, ctx: &Context<'_>, page: Option<i32>, per_page: Option<i32>) -> Result<Vec<FrontendPost>, AppError> {
+        let paging = paging(ctx, page, per_page)?;
+        let filter = PostFilter {
+            status: Some("published".to_string()),
+            live_at: Some(Utc::now().naive_utc()),
+            ..PostFilter::default()
+        };
+        services(ctx).db_service.execute(move |conn| {
+            let (posts, _) = Post::list_filtered(conn, &filter, paging.per_page, paging.offset())?;
+            with_comment_counts(conn, posts)
+        }).await
+    }
+
+    /// A published post by ID
+    async fn post(&self, ctx: &Context<'_>, id: i32) -> Result<Option<FrontendPost>, AppError> {
+        let post = services(ctx).db_service.execute_optional(move |conn| Post::find_with_relations(conn, id)).await?;
+        let now = Utc::now().naive_utc();
+        Ok(post
+            .filter(|post| post_schedule::is_publicly_visible(&post.status, post.published_at, now))
+            .map(FrontendPost::from))
+    }
+
+    /// Published pages
+    async fn pages(&self, ctx: &Context<'_>, page: Option<i32>, per_page: Option<i32>) -> Result<Vec<FrontendPage>, AppError> {
+        let paging = paging(ctx, page, per_page)?;
+        let (pages, _) = services(ctx).db_service.execute(move |conn| {
+            Page::list_paginated(conn, true, paging.per_page, paging.offset())
+        }).await?;
+        Ok(pages.into_iter().map(FrontendPage::from).collect())
+    }
+
+    /// A published page by slug; an old slug of a renamed page finds the
+    /// page under its current one
+    async fn page(&self, ctx: &Context<'_>, slug: String) -> Result<Option<FrontendPage>, AppError> {
+        let slug = slug.trim().to_lowercase();
+        let page = services(ctx).db_service.execute(move |conn| {
+            Ok(match slug_redirects::lookup_page(conn, &slug, Utc::now().naive_utc())? {
+                SlugLookup::Found(page) => Some(page),
+                SlugLookup::Redirect(current) => Page::find_by_slug(conn, &current)?,
+                SlugLookup::NotFound => None,
+            })
+        }).await?;
+        Ok(page.map(FrontendPage::from))
+    }
+
+    /// Active navigation items that are currently visible, optionally for
+    /// one menu area
+    async fn navigation(&self, ctx: &Context<'_>, menu_area: Option<String>) -> Result<Vec<FrontendNavigationItem>, AppError> {
+        let items = services(ctx).db_service.execute(Navigation::list_active).await?;
+        let now = Utc::now().naive_utc();
+        Ok(items.into_iter()
+            .filter(|nav| is_visible_at(nav.visible_from, nav.visible_until, now))
+            .filter(|nav| menu_area.as_deref().is_none_or(|area| nav.menu_area == area))
+            .map(FrontendNavigationItem::from)
+            .collect())
+    }
+
+    /// Public settings, optionally of one type (`site`, `container` or `theme`)
+    async fn settings(&self, ctx: &Context<'_>, setting_type: Option<String>) -> Result<Vec<Setting>, AppError> {
+        let types: Vec<String> = match setting_type {
+            Some(setting_type) if PUBLIC_SETTING_TYPES.contains(&setting_type.as_str()) => vec![setting_type],
+            Some(setting_type) => return Err(AppError::ValidationError(format!("Setting type {} is not public", setting_type))),
+            None => PUBLIC_SETTING_TYPES.iter().map(|t| t.to_string()).collect(),
+        };
+        services(ctx).db_service.execute(move |conn| {
+            let mut settings = Vec::new();
+            for setting_type in &types {
+                settings.extend(Setting::list_by_type(conn, setting_type)?);
+            }
+            Ok(settings)
+        }).await
+    }
+
+    /// Comments on a post or page, oldest first
+    async fn comments(&self, ctx: &Context<'_>, post_id: Option<i32>, page_id: Option<i32>) -> Result<Vec<CommentWithGravatar>, AppError> {
+        let (comments, gravatar) = services(ctx).db_service.execute(move |conn| {
+            Ok((Comment::list_thread_after(conn, post_id, page_id, None, None, false)?, GravatarConfig::load(conn)?))
+        }).await?;
+        Ok(comments.into_iter().map(|comment| CommentWithGravatar::new(comment, &gravatar)).collect())
+    }
+}
+
+/// Build the schema once at startup
+pub fn build_schema(services: AppServices) -> CmsSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(services)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Run a GraphQL query (public endpoint)
+///
+/// Accepts the standard `{ query, variables, operationName }` JSON body and
+/// answers with `{ data, errors }`. Read-only.
+/// No authentication required.
+pub async fn graphql_handler(
+    State(schema): State<CmsSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> ResponseJson<async_graphql::Response> {
+    ResponseJson(schema.execute(request).await)
+}
@@ -0,0 +1,170 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json as ResponseJson,
+};
+use diesel::prelude::*;
+use diesel_migrations::MigrationHarness;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use crate::{AppServices, database::MIGRATIONS};
+
+/// Upper bound on any single readiness check so probes stay fast
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Up,
+    Down,
+    NotConfigured,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    /// Optional dependencies only degrade readiness when down
+    pub required: bool,
+    pub latency_ms: u128,
+    /// A fixed reason such as "unreachable"; the underlying error is only logged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<&'static str>,
+}
+
+/// A failed check: the reason reported publicly and the error that is logged
+struct CheckFailure {
+    reason: &'static str,
+    error: String,
+}
+
+impl CheckFailure {
+    fn unreachable(error: impl ToString) -> Self {
+        CheckFailure { reason: "unreachable", error: error.to_string() }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessReport {
+    /// "ready", "degraded" or "not_ready"
+    pub status: &'static str,
+    pub checks: Vec<DependencyCheck>,
+    pub timestamp: String,
+}
+
+async fn timed_check<F>(name: &'static str, required: bool, check: F) -> DependencyCheck
+where
+    F: std::future::Future<Output = Result<CheckStatus, CheckFailure>>,
+{
+    let started = Instant::now();
+    let (status, detail) = match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(Ok(status)) => (status, None),
+        Ok(Err(failure)) => {
+            tracing::warn!("Readiness check {} failed: {}", name, failure.error);
+            (CheckStatus::Down, Some(failure.reason))
+        }
+        Err(_) => {
+            tracing::warn!("Readiness check {} timed out after {}s", name, CHECK_TIMEOUT.as_secs());
+            (CheckStatus::Down, Some("timed out"))
+        }
+    };
+    DependencyCheck { name, status, required, latency_ms: started.elapsed().as_millis(), detail }
+}
+
+async fn check_database(services: &AppServices) -> Result<CheckStatus, CheckFailure> {
+    services.db_service.execute(|conn| {
+        diesel::sql_query("SELECT 1").execute(conn)
+    }).await.map_err(CheckFailure::unreachable)?;
+    Ok(CheckStatus::Up)
+}
+
+async fn check_migrations(services: &AppServices) -> Result<CheckStatus, CheckFailure> {
+    let pool = services.db_pool.clone();
+    let pending = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(CheckFailure::unreachable)?;
+        conn.has_pending_migration(MIGRATIONS).map_err(CheckFailure::unreachable)
+    }).await.map_err(CheckFailure::unreachable)??;
+
+    if pending {
+        Err(CheckFailure {
+            reason: "pending migrations",
+            error: "pending migrations have not been applied".to_string(),
+        })
+    } else {
+        Ok(CheckStatus::Up)
+    }
+}
+
+fn check_background_tasks(services: &AppServices) -> Result<CheckStatus, CheckFailure> {
+    let stopped: Vec<&str> = services.background_tasks.iter()
+        .filter(|(_, handle)| handle.is_finished())
+        .map(|(name, _)| *name)
+        .collect();
+
+    if stopped.is_empty() {
+        Ok(CheckStatus::Up)
+    } else {
+        Err(CheckFailure { reason: "stopped", error: format!("stopped: {}", stopped.join(", ")) })
+    }
+}
+
+/// SMTP is optional: it is only probed when a server is configured
+async fn check_smtp() -> Result<CheckStatus, CheckFailure> {
+    let Some(host) = std::env::var("SMTP_SERVER").or_else(|_| std::env::var("SMTP_HOST")).ok()
+        .filter(|host| !host.trim().is_empty())
+    else {
+        return Ok(CheckStatus::NotConfigured);
+    };
+    let port = std::env::var("SMTP_PORT").ok()
+        .and_then(|port| port.parse::<u16>().ok())
+        .unwrap_or(587);
+
+    tokio::net::TcpStream::connect((host.trim(), port)).await
+        .map(|_| CheckStatus::Up)
+        .map_err(|e| CheckFailure::unreachable(format!("cannot reach {}:{}: {}", host.trim(), port, e)))
+}
+
+/// Liveness probe
+///
+/// Reports that the process is up and serving requests. Performs no
+/// dependency checks, so a failure means the process should be restarted.
+/// No authentication required for public access.
+pub async fn liveness() -> ResponseJson<serde_json::Value> {
+    ResponseJson(serde_json::json!({
+        "status": "ok",
+        "timestamp": chrono::Utc::now().to_rfc3339()
+    }))
+}
+
+/// Readiness probe
+///
+/// Checks the database, applied migrations, background tasks and SMTP.
+/// Returns 503 when a required dependency is down, and 200 with a
+/// "degraded" status when only an optional dependency is.
+/// No authentication required for public access.
+pub async fn readiness(
+    State(services): State<AppServices>,
+) -> (StatusCode, ResponseJson<ReadinessReport>) {
+    let (database, migrations, background_tasks, smtp) = tokio::join!(
+        timed_check("database", true, check_database(&services)),
+        timed_check("migrations", true, check_migrations(&services)),
+        timed_check("background_tasks", true, async { check_background_tasks(&services) }),
+        timed_check("smtp", false, check_smtp()),
+    );
+    let checks = vec![database, migrations, background_tasks, smtp];
+
+    let is_down = |check: &&DependencyCheck| check.status == CheckStatus::Down;
+    let (status_code, status) = if checks.iter().filter(is_down).any(|check| check.required) {
+        (StatusCode::SERVICE_UNAVAILABLE, "not_ready")
+    } else if checks.iter().any(|check| is_down(&check)) {
+        (StatusCode::OK, "degraded")
+    } else {
+        (StatusCode::OK, "ready")
+    };
+
+    (status_code, ResponseJson(ReadinessReport {
+        status,
+        checks,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    }))
+}
@@ -0,0 +1,68 @@
+use axum::{
+    extract::{State, Json, Extension},
+    response::Json as ResponseJson,
+    http::StatusCode,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    AppServices,
+    models::{Invite, NewInvite},
+    middleware::{auth::AuthenticatedUser, errors::AppError, validation::validate_email},
+    services::signup_policy::{generate_invite_token, DEFAULT_INVITE_DAYS, MAX_INVITE_DAYS},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInviteRequest {
+    /// Restrict the invite to this address
+    pub email: Option<String>,
+    pub expires_in_days: Option<i64>,
+}
+
+/// Create a signup invite (admin only)
+///
+/// Returns the invite with its single-use token; the signup link is
+/// `/signup?invite=<token>`. Invites last a week unless told otherwise.
+/// Requires admin authentication.
+pub async fn create_invite(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+    Json(request): Json<CreateInviteRequest>,
+) -> Result<(StatusCode, ResponseJson<Invite>), AppError> {
+    let email = request.email
+        .map(|email| email.trim().to_string())
+        .filter(|email| !email.is_empty());
+    if let Some(email) = &email {
+        validate_email(email)?;
+    }
+
+    let days = request.expires_in_days.unwrap_or(DEFAULT_INVITE_DAYS);
+    if !(1..=MAX_INVITE_DAYS).contains(&days) {
+        return Err(AppError::ValidationError(format!(
+            "Invites can last between 1 and {} days", MAX_INVITE_DAYS
+        )));
+    }
+
+    let new_invite = NewInvite {
+        token: generate_invite_token(),
+        email,
+        created_by: Some(auth_user.id),
+        expires_at: Utc::now().naive_utc() + Duration::days(days),
+    };
+    let invite = services.db_service.execute(move |conn| Invite::create(conn, new_invite)).await?;
+    info!("User {} created signup invite {}", auth_user.username, invite.id);
+
+    Ok((StatusCode::CREATED, ResponseJson(invite)))
+}
+
+/// List signup invites (admin only)
+///
+/// Newest first, including used and expired ones.
+/// Requires admin authentication.
+pub async fn list_invites(
+    State(services): State<AppServices>,
+) -> Result<ResponseJson<Vec<Invite>>, AppError> {
+    Ok(ResponseJson(services.db_service.execute(Invite::list).await?))
+}
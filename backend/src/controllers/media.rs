@@ -1,14 +1,18 @@
 use axum::{
-    extract::{State, Path, Multipart, Extension},
+    extract::{State, Path, Multipart, Extension, Json},
     response::Json as ResponseJson,
     http::StatusCode,
 };
-use std::path::Path as StdPath;
+use serde::{Deserialize, Serialize};
+use diesel::Connection;
+use std::collections::HashSet;
+use std::path::{Path as StdPath, PathBuf};
+use std::time::Duration;
 use tokio::fs;
-use uuid::Uuid;
 use crate::{
     AppServices,
     models::{Media, NewMedia},
+    services::file_security::{display_file_name, store_upload},
     middleware::{
         validation::validate_file_upload,
         errors::AppError,
@@ -16,6 +20,35 @@ use crate::{
     },
 };
 
+/// Upper bound on ids accepted by one bulk delete request
+const MAX_BULK_DELETE: usize = 500;
+
+/// Files this recent may belong to an upload whose row is not inserted yet,
+/// so cleanup never treats them as orphaned
+const ORPHAN_GRACE_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// Location on disk of a media item stored in the upload directory.
+///
+/// Returns `None` for URLs outside `/uploads/` and for names that are not a
+/// plain file name, so a crafted URL can never point outside the directory.
+fn stored_file_path(upload_dir: &StdPath, url: &str) -> Option<PathBuf> {
+    let filename = url.strip_prefix("/uploads/")?;
+    let is_plain_name = StdPath::new(filename).file_name().and_then(|n| n.to_str()) == Some(filename);
+    is_plain_name.then(|| upload_dir.join(filename))
+}
+
+/// Remove a stored file, treating an already-missing file as removed
+async fn remove_stored_file(path: &StdPath) -> Result<(), String> {
+    match fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => {
+            tracing::warn!("Failed to delete file {}: {}", path.display(), e);
+            Err(format!("{}: {}", path.display(), e))
+        }
+    }
+}
+
 /// Get all media files (admin only)
 /// 
 /// Returns a list of all uploaded media files.
@@ -40,10 +73,10 @@ pub async fn upload_media(
     State(services): State<AppServices>, 
     mut multipart: Multipart
 ) -> Result<(StatusCode, ResponseJson<serde_json::Value>), AppError> {
-    // Create upload directory if it doesn't exist
-    let upload_dir = "uploads";
-    if !StdPath::new(upload_dir).exists() {
-        fs::create_dir_all(upload_dir).await
+    // Recreate the upload directory if it was removed after startup
+    let upload_dir = services.upload_dir.clone();
+    if !upload_dir.exists() {
+        fs::create_dir_all(&upload_dir).await
             .map_err(|e| {
                 tracing::error!(error = %e, "Failed to create upload directory");
                 AppError::InternalError(format!("Failed to create upload directory: {}", e))
@@ -56,9 +89,11 @@ pub async fn upload_media(
         let name = field.name().unwrap_or("").to_string();
         
         if name == "file" {
+            // Keep only the last path component of the client's name; it is
+            // shown in the library but never used as a path
             let file_name = field.file_name()
-                .ok_or_else(|| AppError::ValidationError("Missing filename".to_string()))?
-                .to_string();
+                .map(display_file_name)
+                .ok_or_else(|| AppError::ValidationError("Missing filename".to_string()))?;
             let content_type = field.content_type()
                 .unwrap_or("application/octet-stream")
                 .to_string();
@@ -70,19 +105,16 @@ pub async fn upload_media(
             
             // Validate file upload
             validate_file_upload(&file_name, &content_type, data.len())?;
+            let size = data.len();
             
-            // Generate unique filename
-            let file_extension = StdPath::new(&file_name)
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .unwrap_or("");
-            let unique_filename = format!("{}.{}", Uuid::new_v4(), file_extension);
-            let file_path = format!("{}/{}", upload_dir, unique_filename);
-            
-            // Save file
-            fs::write(&file_path, &data).await
+            // Store under a fresh random name inside the upload directory
+            let dir = upload_dir.clone();
+            let original_name = file_name.clone();
+            let unique_filename = tokio::task::spawn_blocking(move || store_upload(&dir, &original_name, &data))
+                .await
+                .map_err(|e| AppError::InternalError(format!("Task join error: {}", e)))?
                 .map_err(|e| {
-                    tracing::error!(error = %e, path = %file_path, "Failed to save uploaded file");
+                    tracing::error!(error = %e, dir = %upload_dir.display(), "Failed to save uploaded file");
                     AppError::InternalError(format!("Failed to save file: {}", e))
                 })?;
             
@@ -110,7 +142,7 @@ pub async fn upload_media(
                     "id": created_media.id,
                     "name": file_name,
                     "type_": content_type,
-                    "size": format!("{} bytes", data.len()),
+                    "size": format!("{} bytes", size),
                     "url": format!("/uploads/{}", unique_filename),
                     "created_at": created_media.uploaded_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
                 }
@@ -138,17 +170,213 @@ pub async fn delete_media(
     
     // Delete from database
     Media::delete(&mut conn, id)?;
+    // The site logo or favicon may have been this file
+    services.bootstrap_cache.invalidate();
     
     // Delete actual file from disk
-    // Extract filename from URL and delete physical file
-    if let Some(filename) = media.url.strip_prefix("/uploads/") {
-        let file_path = format!("uploads/{}", filename);
-        if StdPath::new(&file_path).exists() {
-            if let Err(e) = fs::remove_file(&file_path).await {
-                tracing::warn!("Failed to delete file {}: {}", file_path, e);
-            }
-        }
+    if let Some(file_path) = stored_file_path(&services.upload_dir, &media.url) {
+        let _ = remove_stored_file(&file_path).await;
     }
     
     Ok(StatusCode::NO_CONTENT)
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkDeleteMediaRequest {
+    pub ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkDeleteMediaSummary {
+    pub deleted: usize,
+    /// Requested ids that did not match any media item
+    pub not_found: Vec<i32>,
+    pub files_removed: usize,
+    /// Files whose rows were deleted but which could not be removed from disk
+    pub file_errors: Vec<String>,
+}
+
+/// Delete several media files at once (admin only)
+/// 
+/// Deletes all matching rows in one transaction, then removes their files
+/// from disk. Unknown ids are reported rather than failing the request.
+/// Requires admin authentication.
+pub async fn bulk_delete_media(
+    State(services): State<AppServices>,
+    Json(request): Json<BulkDeleteMediaRequest>,
+) -> Result<ResponseJson<BulkDeleteMediaSummary>, AppError> {
+    let ids: Vec<i32> = request.ids.into_iter()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    if ids.is_empty() {
+        return Err(AppError::ValidationError("No media ids provided".to_string()));
+    }
+    if ids.len() > MAX_BULK_DELETE {
+        return Err(AppError::ValidationError(format!("Cannot delete more than {} media items at once", MAX_BULK_DELETE)));
+    }
+
+    let deleted_media = services.db_service.execute(move |conn| {
+        conn.transaction(|conn| {
+            let found = Media::find_by_ids(conn, &ids)?;
+            let found_ids: Vec<i32> = found.iter().map(|m| m.id).collect();
+            Media::delete_many(conn, &found_ids)?;
+            let not_found = ids.into_iter().filter(|id| !found_ids.contains(id)).collect::<Vec<_>>();
+            Ok((found, not_found))
+        })
+    }).await?;
+    let (deleted, mut not_found) = deleted_media;
+    not_found.sort_unstable();
+    services.bootstrap_cache.invalidate();
+
+    // Files go only after the rows are gone, so a failed transaction never
+    // leaves rows pointing at deleted files
+    let mut files_removed = 0;
+    let mut file_errors = Vec::new();
+    for media in &deleted {
+        if let Some(file_path) = stored_file_path(&services.upload_dir, &media.url) {
+            match remove_stored_file(&file_path).await {
+                Ok(()) => files_removed += 1,
+                Err(e) => file_errors.push(e),
+            }
+        }
+    }
+
+    tracing::info!("Bulk deleted {} media items ({} files removed)", deleted.len(), files_removed);
+
+    Ok(ResponseJson(BulkDeleteMediaSummary {
+        deleted: deleted.len(),
+        not_found,
+        files_removed,
+        file_errors,
+    }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct MediaCleanupRequest {
+    /// Only report what would be cleaned; defaults to true
+    pub dry_run: Option<bool>,
+    /// Required to remove rows when the upload directory is missing or
+    /// every media row would be removed
+    pub confirm_remove_all: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MissingMediaFile {
+    pub id: i32,
+    pub file_name: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MediaCleanupSummary {
+    pub dry_run: bool,
+    /// Files in the upload directory with no media row
+    pub orphaned_files: Vec<String>,
+    pub orphaned_bytes: u64,
+    /// Media rows whose file is missing from the upload directory
+    pub missing_files: Vec<MissingMediaFile>,
+    pub files_removed: usize,
+    pub rows_removed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Reconcile the media table with the upload directory (admin only)
+/// 
+/// Finds files on disk without a media row and rows whose file is missing.
+/// Nothing is removed unless `dry_run` is explicitly set to false. Files
+/// modified within `ORPHAN_GRACE_PERIOD` are never orphaned, and removing
+/// every row (or any row while the upload directory is missing) needs
+/// `confirm_remove_all`, since a misconfigured `UPLOAD_DIR` looks the same.
+/// Requires admin authentication.
+pub async fn cleanup_media(
+    State(services): State<AppServices>,
+    request: Option<Json<MediaCleanupRequest>>,
+) -> Result<ResponseJson<MediaCleanupSummary>, AppError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let dry_run = request.dry_run.unwrap_or(true);
+    let confirm_remove_all = request.confirm_remove_all.unwrap_or(false);
+
+    let media = services.db_service.execute(Media::list).await?;
+    let known_files: HashSet<String> = media.iter()
+        .filter_map(|m| stored_file_path(&services.upload_dir, &m.url))
+        .filter_map(|path| path.file_name().and_then(|n| n.to_str()).map(str::to_string))
+        .collect();
+
+    let mut orphaned_files = Vec::new();
+    let mut orphaned_bytes = 0;
+    let mut files_on_disk = HashSet::new();
+    let mut upload_dir_missing = false;
+    match fs::read_dir(&services.upload_dir).await {
+        Ok(mut entries) => {
+            while let Some(entry) = entries.next_entry().await
+                .map_err(|e| AppError::InternalError(format!("Failed to read upload directory: {}", e)))? {
+                let Ok(metadata) = entry.metadata().await else { continue };
+                let Some(name) = entry.file_name().to_str().map(str::to_string) else { continue };
+                // Skip directories and dotfiles such as .gitkeep
+                if !metadata.is_file() || name.starts_with('.') {
+                    continue;
+                }
+                let recently_modified = metadata.modified().ok()
+                    .is_some_and(|modified| modified.elapsed().map_or(true, |age| age < ORPHAN_GRACE_PERIOD));
+                if !known_files.contains(&name) && !recently_modified {
+                    orphaned_bytes += metadata.len();
+                    orphaned_files.push(name.clone());
+                }
+                files_on_disk.insert(name);
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => upload_dir_missing = true,
+        Err(e) => return Err(AppError::InternalError(format!("Failed to read upload directory: {}", e))),
+    }
+    orphaned_files.sort();
+
+    let media_count = media.len();
+    let missing_files: Vec<MissingMediaFile> = media.into_iter()
+        .filter(|m| stored_file_path(&services.upload_dir, &m.url)
+            .and_then(|path| path.file_name().and_then(|n| n.to_str()).map(str::to_string))
+            .is_some_and(|name| !files_on_disk.contains(&name)))
+        .map(|m| MissingMediaFile { id: m.id, file_name: m.file_name, url: m.url })
+        .collect();
+
+    let mut files_removed = 0;
+    let mut rows_removed = 0;
+    let mut errors = Vec::new();
+    if !dry_run {
+        let suspicious = upload_dir_missing || missing_files.len() == media_count;
+        if suspicious && !missing_files.is_empty() && !confirm_remove_all {
+            return Err(AppError::ValidationError(format!(
+                "Cleanup would remove {} of {} media rows{}; check UPLOAD_DIR, or pass confirm_remove_all to remove them anyway",
+                missing_files.len(),
+                media_count,
+                if upload_dir_missing { " because the upload directory is missing" } else { "" },
+            )));
+        }
+
+        for name in &orphaned_files {
+            match remove_stored_file(&services.upload_dir.join(name)).await {
+                Ok(()) => files_removed += 1,
+                Err(e) => errors.push(e),
+            }
+        }
+
+        let missing_ids: Vec<i32> = missing_files.iter().map(|m| m.id).collect();
+        if !missing_ids.is_empty() {
+            rows_removed = services.db_service.execute(move |conn| {
+                Media::delete_many(conn, &missing_ids)
+            }).await?;
+        }
+
+        tracing::info!("Media cleanup removed {} orphaned files and {} rows with missing files", files_removed, rows_removed);
+    }
+
+    Ok(ResponseJson(MediaCleanupSummary {
+        dry_run,
+        orphaned_files,
+        orphaned_bytes,
+        missing_files,
+        files_removed,
+        rows_removed,
+        errors,
+    }))
+}
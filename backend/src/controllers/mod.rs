@@ -8,6 +8,18 @@ pub mod navigation;
 pub mod sessions;
 pub mod admin;
 pub mod system;
+pub mod color_schemes;
+pub mod invites;
+pub mod preview;
+pub mod health;
+pub mod search;
+pub mod feed;
+pub mod sitemap;
+pub mod bootstrap;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "ssr")]
+pub mod ssr;
 
 // Export controller modules for direct access
 // Individual functions are accessed via module::function syntax
\ No newline at end of file
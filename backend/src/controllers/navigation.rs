@@ -5,15 +5,27 @@ use axum::{
 };
 use crate::{
     AppServices,
-    models::{Navigation, NewNavigation, UpdateNavigation, MenuArea, MenuTemplate, ComponentTemplate, NewMenuTemplate, NewComponentTemplate, UpdateMenuArea, UpdateComponentTemplate},
+    models::{Navigation, NewNavigation, Page, Setting, UpdateNavigation, MenuArea, MenuTemplate, ComponentTemplate, NewMenuTemplate, NewComponentTemplate, UpdateMenuArea, UpdateComponentTemplate},
     middleware::{
         validation::validate_text_content,
         errors::AppError,
     },
+    services::navigation_urls::{validate_nav_url, sanitize_target, sanitize_css_class},
+    services::visibility_window::{is_visible_at, validate_window},
+    services::component_templates::deactivate_superseded,
+    services::template_schema::{self, validate_template_data, Field, UnknownKeys},
 };
+use chrono::{DateTime, Utc};
+
+/// Setting that fills an empty header with Home and the published pages
+pub const DEFAULT_HEADER_NAV_SETTING: &str = "default_header_navigation";
+
+/// Keep the generated header from overflowing on sites with many pages
+const MAX_DEFAULT_NAV_PAGES: usize = 8;
 
 // Frontend-compatible Navigation structure (enhanced)
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct FrontendNavigationItem {
     pub id: i32,
     pub title: String,
@@ -27,6 +39,11 @@ pub struct FrontendNavigationItem {
     pub target: Option<String>,
     pub mobile_visible: bool,
     pub description: Option<String>,
+    /// Optional schedule; items outside it are hidden from the public site
+    #[serde(default)]
+    pub visible_from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub visible_until: Option<DateTime<Utc>>,
     pub children: Option<Vec<FrontendNavigationItem>>,
 }
 
@@ -45,6 +62,8 @@ impl From<Navigation> for FrontendNavigationItem {
             target: nav.target,
             mobile_visible: nav.mobile_visible,
             description: nav.description,
+            visible_from: nav.visible_from.map(|t| t.and_utc()),
+            visible_until: nav.visible_until.map(|t| t.and_utc()),
             children: None, // Will be populated separately for hierarchical structures
         }
     }
@@ -114,6 +133,10 @@ pub struct FrontendComponentTemplate {
     pub max_width: Option<String>,
     pub is_default: bool,
     pub is_active: bool,
+    /// Problems with `template_data` that didn't stop the save, e.g.
+    /// unknown keys; only sent back from create and update
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 impl From<ComponentTemplate> for FrontendComponentTemplate {
@@ -128,6 +151,7 @@ impl From<ComponentTemplate> for FrontendComponentTemplate {
             max_width: template.max_width,
             is_default: template.is_default,
             is_active: template.is_active,
+            warnings: Vec::new(),
         }
     }
 }
@@ -135,15 +159,17 @@ impl From<ComponentTemplate> for FrontendComponentTemplate {
 /// Get all active navigation items (public endpoint)
 /// 
 /// Returns navigation items for public site display.
-/// Only returns active items, ordered by position.
+/// Only returns active items inside their visibility window, ordered by position.
 /// No authentication required for public access.
 pub async fn get_navigation(
     State(services): State<AppServices>
 ) -> Result<ResponseJson<Vec<FrontendNavigationItem>>, AppError> {
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let now = Utc::now().naive_utc();
     let nav_items = Navigation::list_active(&mut conn)?;
     let frontend_nav_items: Vec<FrontendNavigationItem> = nav_items.into_iter()
+        .filter(|nav| is_visible_at(nav.visible_from, nav.visible_until, now))
         .map(FrontendNavigationItem::from)
         .collect();
     Ok(ResponseJson(frontend_nav_items))
@@ -163,36 +189,38 @@ pub async fn create_navigation_item(
         return Err(AppError::ValidationError("Title cannot be empty".to_string()));
     }
     
-    if nav_item.url.trim().is_empty() {
-        return Err(AppError::ValidationError("URL cannot be empty".to_string()));
-    }
-    
     validate_text_content(&nav_item.title, 100)?;
     validate_text_content(&nav_item.url, 200)?;
     
-    // Basic URL validation
-    if !nav_item.url.starts_with('/') && !nav_item.url.starts_with("http") {
-        return Err(AppError::ValidationError("URL must start with '/' or 'http'".to_string()));
-    }
+    // Only site-relative paths and http(s) links can reach the public href
+    let url = validate_nav_url(&nav_item.url)?;
+    let target = sanitize_target(nav_item.target)?;
+    let css_class = sanitize_css_class(nav_item.css_class);
+    let visible_from = nav_item.visible_from.map(|t| t.naive_utc());
+    let visible_until = nav_item.visible_until.map(|t| t.naive_utc());
+    validate_window(visible_from, visible_until)?;
     
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
     let new_nav = NewNavigation {
         title: nav_item.title.trim().to_string(),
-        url: nav_item.url.trim().to_string(),
+        url,
         order_position: nav_item.order,
         is_active: nav_item.is_active,
         menu_area: nav_item.menu_area,
         parent_id: nav_item.parent_id,
         icon: nav_item.icon,
-        css_class: nav_item.css_class,
-        target: nav_item.target.or(Some("_self".to_string())),
+        css_class,
+        target: target.or(Some("_self".to_string())),
         mobile_visible: nav_item.mobile_visible,
         description: nav_item.description,
+        visible_from,
+        visible_until,
     };
     
     let created_nav = Navigation::create(&mut conn, new_nav)?;
+    services.bootstrap_cache.invalidate();
     let response = FrontendNavigationItem::from(created_nav);
     
     Ok((StatusCode::CREATED, ResponseJson(response)))
@@ -213,17 +241,16 @@ pub async fn update_navigation_item(
         return Err(AppError::ValidationError("Title cannot be empty".to_string()));
     }
     
-    if nav_item.url.trim().is_empty() {
-        return Err(AppError::ValidationError("URL cannot be empty".to_string()));
-    }
-    
     validate_text_content(&nav_item.title, 100)?;
     validate_text_content(&nav_item.url, 200)?;
     
-    // Basic URL validation
-    if !nav_item.url.starts_with('/') && !nav_item.url.starts_with("http") {
-        return Err(AppError::ValidationError("URL must start with '/' or 'http'".to_string()));
-    }
+    // Only site-relative paths and http(s) links can reach the public href
+    let url = validate_nav_url(&nav_item.url)?;
+    let target = sanitize_target(nav_item.target)?;
+    let css_class = sanitize_css_class(nav_item.css_class);
+    let visible_from = nav_item.visible_from.map(|t| t.naive_utc());
+    let visible_until = nav_item.visible_until.map(|t| t.naive_utc());
+    validate_window(visible_from, visible_until)?;
     
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -234,20 +261,23 @@ pub async fn update_navigation_item(
     
     let update_nav = UpdateNavigation {
         title: Some(nav_item.title.trim().to_string()),
-        url: Some(nav_item.url.trim().to_string()),
+        url: Some(url),
         order_position: Some(nav_item.order),
         is_active: Some(nav_item.is_active),
         updated_at: None, // Will be set in the model
         menu_area: Some(nav_item.menu_area),
         parent_id: Some(nav_item.parent_id),
         icon: Some(nav_item.icon),
-        css_class: Some(nav_item.css_class),
-        target: Some(nav_item.target),
+        css_class: Some(css_class),
+        target: Some(target),
         mobile_visible: Some(nav_item.mobile_visible),
         description: Some(nav_item.description),
+        visible_from: Some(visible_from),
+        visible_until: Some(visible_until),
     };
     
     let updated_nav = Navigation::update(&mut conn, id, update_nav)?;
+    services.bootstrap_cache.invalidate();
     let response = FrontendNavigationItem::from(updated_nav);
     
     Ok(ResponseJson(response))
@@ -269,6 +299,7 @@ pub async fn delete_navigation_item(
         .ok_or_else(|| AppError::NotFound("Navigation item not found".to_string()))?;
     
     Navigation::delete(&mut conn, id)?;
+    services.bootstrap_cache.invalidate();
     
     Ok(ResponseJson(serde_json::json!({
         "success": true,
@@ -276,10 +307,70 @@ pub async fn delete_navigation_item(
     })))
 }
 
+/// Generated header links: Home followed by published pages, one per URL.
+/// These are not stored, so they carry negative ids.
+fn default_header_items(conn: &mut diesel::PgConnection) -> Result<Vec<FrontendNavigationItem>, AppError> {
+    let enabled = Setting::find_by_key(conn, DEFAULT_HEADER_NAV_SETTING)?
+        .and_then(|s| s.setting_value)
+        .map(|value| value.trim() == "true")
+        .unwrap_or(true);
+    if !enabled {
+        return Ok(Vec::new());
+    }
+
+    let link = |id: i32, title: String, url: String| FrontendNavigationItem {
+        id,
+        title,
+        url,
+        order: -id,
+        is_active: true,
+        menu_area: "header".to_string(),
+        parent_id: None,
+        icon: None,
+        css_class: None,
+        target: Some("_self".to_string()),
+        mobile_visible: true,
+        description: None,
+        visible_from: None,
+        visible_until: None,
+        children: None,
+    };
+
+    let mut items = vec![link(-1, "Home".to_string(), "/".to_string())];
+    for page in Page::list_published(conn)? {
+        if items.len() > MAX_DEFAULT_NAV_PAGES {
+            break;
+        }
+        let url = format!("/page/{}", page.slug);
+        if !items.iter().any(|item| item.url == url) {
+            items.push(link(-(items.len() as i32) - 1, page.title, url));
+        }
+    }
+    Ok(items)
+}
+
+/// Nest child items under their parents, keeping root items in order
+fn build_hierarchy(nav_items: Vec<Navigation>) -> Vec<FrontendNavigationItem> {
+    let (roots, children): (Vec<_>, Vec<_>) = nav_items.into_iter()
+        .map(FrontendNavigationItem::from)
+        .partition(|item| item.parent_id.is_none());
+    let mut root_items = roots;
+
+    for item in children {
+        // Children of hidden or missing parents are dropped with them
+        if let Some(parent) = root_items.iter_mut().find(|i| Some(i.id) == item.parent_id) {
+            parent.children.get_or_insert_with(Vec::new).push(item);
+        }
+    }
+    root_items
+}
+
 /// Get navigation items by menu area (public endpoint)
 /// 
 /// Returns navigation items for a specific menu area with hierarchical structure.
-/// Only returns active items, ordered by position.
+/// Only returns active items inside their visibility window, ordered by position.
+/// A header with no visible items falls back to Home plus published pages
+/// unless that is turned off.
 /// No authentication required for public access.
 pub async fn get_navigation_by_area(
     State(services): State<AppServices>,
@@ -288,35 +379,39 @@ pub async fn get_navigation_by_area(
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
-    let nav_items = Navigation::list_by_area_with_children(&mut conn, &area)?;
-    
-    // Build hierarchical structure
-    let mut items_map: std::collections::HashMap<i32, FrontendNavigationItem> = std::collections::HashMap::new();
-    let mut root_items: Vec<FrontendNavigationItem> = Vec::new();
-    
-    // First pass: convert all items and collect them
-    for nav in nav_items {
-        let item = FrontendNavigationItem::from(nav);
-        items_map.insert(item.id, item);
-    }
+    Ok(ResponseJson(public_area_items(&mut conn, &area)?))
+}
+
+/// The visible items of a menu area as a tree, with the generated header
+/// links standing in for an empty header
+pub fn public_area_items(conn: &mut diesel::PgConnection, area: &str) -> Result<Vec<FrontendNavigationItem>, AppError> {
+    let now = Utc::now().naive_utc();
+    let nav_items: Vec<Navigation> = Navigation::list_by_area_with_children(conn, area)?
+        .into_iter()
+        .filter(|nav| is_visible_at(nav.visible_from, nav.visible_until, now))
+        .collect();
     
-    // Second pass: build hierarchy
-    for (_, item) in items_map.drain() {
-        if let Some(parent_id) = item.parent_id {
-            // This is a child item, find its parent
-            if let Some(parent) = root_items.iter_mut().find(|i| i.id == parent_id) {
-                if parent.children.is_none() {
-                    parent.children = Some(Vec::new());
-                }
-                parent.children.as_mut().unwrap().push(item);
-            }
-        } else {
-            // This is a root item
-            root_items.push(item);
-        }
+    let mut root_items = build_hierarchy(nav_items);
+    if area == "header" && root_items.is_empty() {
+        root_items = default_header_items(conn)?;
     }
+    Ok(root_items)
+}
+
+/// Get all navigation items in a menu area (admin only)
+/// 
+/// Returns every active item, including ones scheduled outside their
+/// visibility window, so editors can manage them. No fallback items.
+/// Requires admin authentication.
+pub async fn get_admin_navigation_by_area(
+    State(services): State<AppServices>,
+    Path(area): Path<String>
+) -> Result<ResponseJson<Vec<FrontendNavigationItem>>, AppError> {
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
-    Ok(ResponseJson(root_items))
+    let nav_items = Navigation::list_by_area_with_children(&mut conn, &area)?;
+    Ok(ResponseJson(build_hierarchy(nav_items)))
 }
 
 /// Get all menu areas (admin endpoint)
@@ -471,18 +566,21 @@ pub async fn get_component_templates(
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
+    Ok(ResponseJson(active_component_templates(&mut conn)?))
+}
+
+/// Component templates currently in use on the public site
+pub fn active_component_templates(conn: &mut diesel::PgConnection) -> Result<Vec<FrontendComponentTemplate>, AppError> {
     use diesel::prelude::*;
     use crate::schema::component_templates;
     
     let templates = component_templates::table
         .filter(component_templates::is_active.eq(true))
-        .load::<ComponentTemplate>(&mut conn)?;
+        .load::<ComponentTemplate>(conn)?;
     
-    let frontend_templates: Vec<FrontendComponentTemplate> = templates.into_iter()
+    Ok(templates.into_iter()
         .map(FrontendComponentTemplate::from)
-        .collect();
-    
-    Ok(ResponseJson(frontend_templates))
+        .collect())
 }
 
 /// Get all component templates for admin (including inactive ones)
@@ -505,6 +603,26 @@ pub async fn get_all_component_templates_admin(
     Ok(ResponseJson(frontend_templates))
 }
 
+/// Allowed `template_data` keys per component type (admin endpoint), for
+/// the template editor; see `services::template_schema`
+pub async fn get_component_template_schema(
+    State(services): State<AppServices>
+) -> Result<ResponseJson<ComponentTemplateSchema>, AppError> {
+    let mut conn = services.db_pool.get()
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(ResponseJson(ComponentTemplateSchema {
+        unknown_keys: UnknownKeys::load(&mut conn)?,
+        component_types: template_schema::schema(),
+    }))
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct ComponentTemplateSchema {
+    pub unknown_keys: UnknownKeys,
+    pub component_types: std::collections::BTreeMap<&'static str, Vec<Field>>,
+}
+
 /// Create component template (admin endpoint)
 pub async fn create_component_template(
     State(services): State<AppServices>,
@@ -515,6 +633,7 @@ pub async fn create_component_template(
     
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let warnings = validate_template_data(&template_data.component_type, &template_data.template_data, UnknownKeys::load(&mut conn)?)?;
     
     let new_template = NewComponentTemplate {
         name: template_data.name,
@@ -530,11 +649,18 @@ pub async fn create_component_template(
     use diesel::prelude::*;
     use crate::schema::component_templates;
     
-    let created_template = diesel::insert_into(component_templates::table)
-        .values(&new_template)
-        .get_result::<ComponentTemplate>(&mut conn)?;
+    // Only one template per type is active; see `services::component_templates`
+    let created_template = conn.transaction(|conn| {
+        if new_template.is_active {
+            deactivate_superseded(conn, &new_template.component_type, None)?;
+        }
+        diesel::insert_into(component_templates::table)
+            .values(&new_template)
+            .get_result::<ComponentTemplate>(conn)
+    })?;
+    services.bootstrap_cache.invalidate();
     
-    Ok((StatusCode::CREATED, ResponseJson(FrontendComponentTemplate::from(created_template))))
+    Ok((StatusCode::CREATED, ResponseJson(FrontendComponentTemplate { warnings, ..created_template.into() })))
 }
 
 /// Update component template (admin endpoint)
@@ -552,11 +678,12 @@ pub async fn update_component_template(
     use crate::schema::component_templates;
     
     // Check if template exists
-    let _existing_template = component_templates::table
+    let existing_template = component_templates::table
         .find(id)
         .first::<ComponentTemplate>(&mut conn)
         .optional()?
         .ok_or_else(|| AppError::NotFound("Component template not found".to_string()))?;
+    let warnings = validate_template_data(&existing_template.component_type, &template_data.template_data, UnknownKeys::load(&mut conn)?)?;
     
     let update_data = UpdateComponentTemplate {
         name: Some(template_data.name),
@@ -569,14 +696,24 @@ pub async fn update_component_template(
         updated_at: Some(chrono::Utc::now().naive_utc()),
     };
     
-    let updated_template = diesel::update(component_templates::table.find(id))
-        .set(update_data)
-        .get_result::<ComponentTemplate>(&mut conn)?;
+    let updated_template = conn.transaction(|conn| {
+        if template_data.is_active {
+            deactivate_superseded(conn, &existing_template.component_type, Some(id))?;
+        }
+        diesel::update(component_templates::table.find(id))
+            .set(update_data)
+            .get_result::<ComponentTemplate>(conn)
+    })?;
+    services.bootstrap_cache.invalidate();
     
-    Ok(ResponseJson(FrontendComponentTemplate::from(updated_template)))
+    Ok(ResponseJson(FrontendComponentTemplate { warnings, ..updated_template.into() }))
 }
 
-// Toggle component template active state
+/// Toggle component template active state (admin endpoint)
+///
+/// Activating a template deactivates the others of its type, as in
+/// `services::component_templates`. Returns the toggled template; clients
+/// apply the same rule to the rest.
 pub async fn toggle_component_template(
     State(services): State<AppServices>,
     Path(id): Path<i32>
@@ -607,9 +744,15 @@ pub async fn toggle_component_template(
         updated_at: Some(chrono::Utc::now().naive_utc()),
     };
     
-    let updated_template = diesel::update(component_templates::table.find(id))
-        .set(update_data)
-        .get_result::<ComponentTemplate>(&mut conn)?;
+    let updated_template = conn.transaction(|conn| {
+        if new_active_state {
+            deactivate_superseded(conn, &current_template.component_type, Some(id))?;
+        }
+        diesel::update(component_templates::table.find(id))
+            .set(update_data)
+            .get_result::<ComponentTemplate>(conn)
+    })?;
+    services.bootstrap_cache.invalidate();
     
     Ok(ResponseJson(FrontendComponentTemplate::from(updated_template)))
 }
\ No newline at end of file
@@ -1,11 +1,12 @@
 use axum::{
     extract::{State, Path, Json, Extension},
-    response::Json as ResponseJson,
-    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson, Response},
+    http::{header, HeaderMap, StatusCode, Uri},
 };
 use crate::{
     AppServices,
-    models::{Page, NewPage, UpdatePage},
+    models::{Page, NewPage, UpdatePage, SlugRedirect},
+    services::{image_alt, page_blocks::validate_page_blocks, pagination::{self, PaginationParams}, homepage::{resolve_homepage, HomepageConfig, HomepageMode}, slug_redirects::{self, SlugLookup, PAGE_RESOURCE}, slugs::{slugify, slug_from_title, unique_slug}},
     middleware::{
         validation::validate_text_content,
         errors::AppError,
@@ -15,6 +16,7 @@ use crate::{
 
 // Frontend-compatible Page structure
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct FrontendPage {
     pub id: Option<i32>,
     pub title: String,
@@ -39,23 +41,97 @@ impl From<Page> for FrontendPage {
     }
 }
 
-/// Get all pages (public endpoint)
+#[derive(Debug, serde::Serialize)]
+pub struct PaginatedPages {
+    pub pages: Vec<FrontendPage>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+}
+
+/// Resolve the slug to store for a page.
+///
+/// An explicit slug is normalized and must not belong to another page; an
+/// omitted slug is generated from the title and suffixed until it is free.
+fn resolve_page_slug(conn: &mut diesel::PgConnection, title: &str, requested: &str, page_id: Option<i32>) -> Result<String, AppError> {
+    let mut is_taken = |candidate: &str| {
+        Page::find_by_slug(conn, candidate).map(|existing| existing.is_some_and(|p| Some(p.id) != page_id))
+    };
+
+    if requested.trim().is_empty() {
+        return Ok(unique_slug(&slug_from_title(title), is_taken)?);
+    }
+
+    let slug = slugify(requested);
+    if slug.is_empty() {
+        return Err(AppError::ValidationError("Slug must contain letters or numbers".to_string()));
+    }
+    if is_taken(&slug)? {
+        return Err(AppError::ValidationError(format!("Slug '{}' is already used by another page", slug)));
+    }
+    Ok(slug)
+}
+
+/// Map a unique index violation on `pages.slug` (a concurrent insert won the
+/// race) to a validation error instead of a generic database failure
+fn map_slug_conflict(err: diesel::result::Error) -> AppError {
+    match err {
+        diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+            AppError::ValidationError("Slug is already used by another page".to_string())
+        }
+        other => other.into(),
+    }
+}
+
+async fn list_pages_page(services: &AppServices, uri: &Uri, paging: PaginationParams, published_only: bool) -> Result<(HeaderMap, ResponseJson<PaginatedPages>), AppError> {
+    let PaginationParams { page, per_page } = paging;
+    let offset = paging.offset();
+
+    let (pages, total) = services.db_service.execute(move |conn| {
+        Page::list_paginated(conn, published_only, per_page, offset)
+    }).await?;
+
+    Ok((pagination::page_headers(uri, page, per_page, total), ResponseJson(PaginatedPages {
+        pages: pages.into_iter().map(FrontendPage::from).collect(),
+        total,
+        page,
+        per_page,
+        total_pages: paging.total_pages(total),
+    })))
+}
+
+/// Get published pages (public endpoint)
 /// 
-/// Returns a list of all published pages.
+/// Returns one page of published pages along with the total count.
+/// Accepts `page` and `per_page` query parameters; the total and
+/// neighbouring pages are also sent as `X-Total-Count` and `Link` headers.
 /// No authentication required for public access.
 pub async fn get_pages(
-    State(services): State<AppServices>
-) -> Result<ResponseJson<Vec<FrontendPage>>, AppError> {
-    let mut conn = services.db_pool.get()
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let pages = Page::list(&mut conn)?;
-    let frontend_pages: Vec<FrontendPage> = pages.into_iter().map(FrontendPage::from).collect();
-    Ok(ResponseJson(frontend_pages))
+    State(services): State<AppServices>,
+    uri: Uri,
+    paging: PaginationParams,
+) -> Result<(HeaderMap, ResponseJson<PaginatedPages>), AppError> {
+    list_pages_page(&services, &uri, paging, true).await
+}
+
+/// Get all pages including drafts (admin only)
+/// 
+/// Returns one page of pages in any status along with the total count.
+/// Accepts `page` and `per_page` query parameters; the total and
+/// neighbouring pages are also sent as `X-Total-Count` and `Link` headers.
+/// Requires admin authentication.
+pub async fn get_admin_pages(
+    State(services): State<AppServices>,
+    uri: Uri,
+    paging: PaginationParams,
+) -> Result<(HeaderMap, ResponseJson<PaginatedPages>), AppError> {
+    list_pages_page(&services, &uri, paging, false).await
 }
 
 /// Get a specific page by ID (public endpoint)
 /// 
-/// Returns a single page by its ID.
+/// Returns a single published page by its ID.
 /// No authentication required for public access.
 pub async fn get_page(
     State(services): State<AppServices>, 
@@ -65,6 +141,7 @@ pub async fn get_page(
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
     let page = Page::find_by_id(&mut conn, id)?
+        .filter(|page| page.status == "published")
         .ok_or_else(|| AppError::NotFound("Page not found".to_string()))?;
     
     Ok(ResponseJson(FrontendPage::from(page)))
@@ -72,20 +149,70 @@ pub async fn get_page(
 
 /// Get a page by slug (public endpoint)
 /// 
-/// Returns a page by its URL slug.
-/// Generates slug from title since database doesn't store slugs.
+/// Returns a published page by its URL slug. A slug the page had before
+/// it was renamed answers with a 301 to the current one. Unpublished pages
+/// are only reachable through a signed preview link.
 /// No authentication required for public access.
 pub async fn get_page_by_slug(
     State(services): State<AppServices>, 
     Path(slug): Path<String>
-) -> Result<ResponseJson<FrontendPage>, AppError> {
+) -> Result<Response, AppError> {
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
     let normalized_slug = slug.trim().to_lowercase();
-    let page = Page::find_by_slug(&mut conn, &normalized_slug)?
-        .ok_or_else(|| AppError::NotFound("Page not found".to_string()))?;
-    Ok(ResponseJson(FrontendPage::from(page)))
+    match slug_redirects::lookup_page(&mut conn, &normalized_slug, chrono::Utc::now().naive_utc())? {
+        SlugLookup::Found(page) => Ok(ResponseJson(FrontendPage::from(page)).into_response()),
+        SlugLookup::Redirect(current_slug) => Ok((
+            StatusCode::MOVED_PERMANENTLY,
+            [(header::LOCATION, format!("/api/pages/slug/{}", current_slug))],
+        ).into_response()),
+        SlugLookup::NotFound => Err(AppError::NotFound("Page not found".to_string())),
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct HomepageResponse {
+    /// `posts` or `page`, after falling back from a missing or unpublished page
+    pub mode: String,
+    pub page: Option<FrontendPage>,
+}
+
+/// Get what the public home shows (public endpoint)
+/// 
+/// Returns the page configured as the homepage, or mode `posts` when the
+/// home is the post list or the configured page is no longer published.
+/// No authentication required for public access.
+pub async fn get_homepage(
+    State(services): State<AppServices>,
+) -> Result<ResponseJson<HomepageResponse>, AppError> {
+    let page = services.db_service.execute(|conn| {
+        let config = HomepageConfig::load(conn)?;
+        let page = match config.page_id {
+            Some(page_id) if config.mode == HomepageMode::Page => Page::find_by_id(conn, page_id)?,
+            _ => None,
+        };
+        Ok(resolve_homepage(&config, page))
+    }).await?;
+
+    let mode = if page.is_some() { HomepageMode::Page } else { HomepageMode::Posts };
+    Ok(ResponseJson(HomepageResponse {
+        mode: mode.as_str().to_string(),
+        page: page.map(FrontendPage::from),
+    }))
+}
+
+/// Enforce the `enforce_image_alt` setting when a page is being published.
+/// Drafts can always be saved without alt text.
+fn check_publishable(conn: &mut diesel::PgConnection, status: &str, content: &str) -> Result<(), AppError> {
+    if status != "published" {
+        return Ok(());
+    }
+    let missing = image_alt::page_images_missing_alt(content);
+    if !missing.is_empty() && image_alt::enforcement_enabled(conn)? {
+        image_alt::ensure_alt_text(&missing)?;
+    }
+    Ok(())
 }
 
 /// Create a new page (admin only)
@@ -108,23 +235,13 @@ pub async fn create_page(
     
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    validate_page_blocks(&mut conn, &page.content)?;
     
-    // Generate slug if missing and normalize
-    let mut slug_value = if page.slug.trim().is_empty() {
-        page.title.trim().to_lowercase().replace(' ', "-")
-    } else {
-        page.slug.trim().to_lowercase().replace(' ', "-")
-    };
-    // collapse consecutive dashes
-    while slug_value.contains("--") { slug_value = slug_value.replace("--", "-"); }
+    let slug_value = resolve_page_slug(&mut conn, &page.title, &page.slug, None)?;
 
     // Default status
     let status_value = if page.status.trim().is_empty() { "draft".to_string() } else { page.status.trim().to_string() };
-
-    // Pre-check unique slug
-    if let Ok(Some(_)) = Page::find_by_slug(&mut conn, &slug_value) {
-        return Err(AppError::ConflictError("Slug already exists".to_string()));
-    }
+    check_publishable(&mut conn, &status_value, &page.content)?;
 
     let new_page = NewPage {
         title: page.title.trim().to_string(),
@@ -134,7 +251,11 @@ pub async fn create_page(
         status: status_value,
     };
     
-    let created_page = Page::create(&mut conn, new_page)?;
+    let created_page = Page::create(&mut conn, new_page).map_err(map_slug_conflict)?;
+    // A new page taking over a renamed page's old slug replaces the redirect
+    SlugRedirect::delete_slug(&mut conn, PAGE_RESOURCE, &created_page.slug)?;
+    // Published pages feed the generated header navigation
+    services.bootstrap_cache.invalidate();
     let response = FrontendPage::from(created_page);
     
     Ok((StatusCode::CREATED, ResponseJson(response)))
@@ -159,24 +280,16 @@ pub async fn update_page(
     
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    validate_page_blocks(&mut conn, &page.content)?;
     
     // Check if page exists
-    let _existing_page = Page::find_by_id(&mut conn, id)?
+    let existing_page = Page::find_by_id(&mut conn, id)?
         .ok_or_else(|| AppError::NotFound("Page not found".to_string()))?;
     
-    // Normalize slug and default status
-    let mut slug_value = if page.slug.trim().is_empty() {
-        page.title.trim().to_lowercase().replace(' ', "-")
-    } else {
-        page.slug.trim().to_lowercase().replace(' ', "-")
-    };
-    while slug_value.contains("--") { slug_value = slug_value.replace("--", "-"); }
+    // Normalize slug (unique among other pages) and default status
+    let slug_value = resolve_page_slug(&mut conn, &page.title, &page.slug, Some(id))?;
     let status_value = if page.status.trim().is_empty() { "draft".to_string() } else { page.status.trim().to_string() };
-
-    // If slug changed, ensure uniqueness (excluding this id)
-    if let Ok(Some(existing)) = Page::find_by_slug(&mut conn, &slug_value) {
-        if existing.id != id { return Err(AppError::ConflictError("Slug already exists".to_string())); }
-    }
+    check_publishable(&mut conn, &status_value, &page.content)?;
 
     let update_page = UpdatePage {
         title: Some(page.title.trim().to_string()),
@@ -187,7 +300,10 @@ pub async fn update_page(
         status: Some(status_value),
     };
     
-    let updated_page = Page::update(&mut conn, id, update_page)?;
+    let updated_page = Page::update(&mut conn, id, update_page).map_err(map_slug_conflict)?;
+    // Keep links to the old slug working
+    slug_redirects::record_rename(&mut conn, PAGE_RESOURCE, id, &existing_page.slug, &updated_page.slug, chrono::Utc::now().naive_utc())?;
+    services.bootstrap_cache.invalidate();
     Ok(ResponseJson(FrontendPage::from(updated_page)))
 }
 
@@ -207,6 +323,8 @@ pub async fn delete_page(
         .ok_or_else(|| AppError::NotFound("Page not found".to_string()))?;
     
     Page::delete(&mut conn, id)?;
+    SlugRedirect::delete_for_resource(&mut conn, PAGE_RESOURCE, id)?;
+    services.bootstrap_cache.invalidate();
     
     Ok(ResponseJson(serde_json::json!({
         "success": true,
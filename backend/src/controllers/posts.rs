@@ -1,12 +1,15 @@
+use chrono::{DateTime, Utc};
+use diesel::Connection;
 use axum::{
-    extract::{State, Path, Json, Extension},
-    response::Json as ResponseJson,
-    http::StatusCode,
+    extract::{State, Path, Json, Extension, Multipart, Query},
+    response::{IntoResponse, Json as ResponseJson, Response},
+    http::{header, HeaderMap, StatusCode, Uri},
 };
 
 use crate::{
     AppServices,
-    models::{Post, NewPost, UpdatePost},
+    models::{Comment, Post, NewPost, UpdatePost, PostWithRelations, PostFilter, Category, NewCategory},
+    services::{excerpts::{excerpt_of, ExcerptConfig}, image_alt, markdown_export, markdown_import, pagination::{self, PaginationParams}, structured_data, post_schedule, slugs::slug_from_title, cursor::{parse_cursor_param, split_page, Cursor}},
     middleware::{
         validation::validate_text_content,
         errors::AppError,
@@ -16,6 +19,7 @@ use crate::{
 
 // Frontend-compatible Post structure
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct FrontendPost {
     pub id: Option<i32>,
     pub title: String,
@@ -23,7 +27,40 @@ pub struct FrontendPost {
     pub author: String,
     pub status: String,
     pub category_id: Option<i32>,
+    #[serde(default)]
+    pub category_name: Option<String>,
     pub created_at: Option<String>,
+    /// When the post went (or is scheduled to go) live
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+    /// Comments on the post; only filled in on public post lists
+    #[serde(default)]
+    pub comment_count: Option<i64>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Teaser cut to the `excerpt_length` setting; only on public post lists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    /// JSON-LD `Article` for search engines; only on the public single post
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub structured_data: Option<serde_json::Value>,
+}
+
+/// Category assigned to posts created without one (seeded at startup)
+const DEFAULT_CATEGORY_NAME: &str = "General";
+
+/// Publishing states a post can be in
+const POST_STATUSES: &[&str] = &["draft", "published", "scheduled", "trash"];
+
+
+fn validate_post_status(status: &str) -> Result<String, AppError> {
+    let status = status.trim().to_lowercase();
+    if POST_STATUSES.contains(&status.as_str()) {
+        Ok(status)
+    } else {
+        Err(AppError::ValidationError(format!("Invalid post status: {}", status)))
+    }
 }
 
 impl From<Post> for FrontendPost {
@@ -33,42 +70,311 @@ impl From<Post> for FrontendPost {
             title: post.title,
             content: post.content,
             author: "Admin".to_string(), // Default for now
-            status: "published".to_string(), // Default for now
+            status: post.status,
             category_id: post.category_id,
+            category_name: None,
             created_at: post.created_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            published_at: post.published_at.map(|dt| dt.and_utc()),
+            comment_count: None,
+            updated_at: post.updated_at.map(|dt| dt.and_utc()),
+            excerpt: None,
+            structured_data: None,
         }
     }
 }
 
+impl From<PostWithRelations> for FrontendPost {
+    fn from(post: PostWithRelations) -> Self {
+        FrontendPost {
+            id: Some(post.id),
+            title: post.title,
+            content: post.content,
+            author: post.author_username.unwrap_or_else(|| "Admin".to_string()),
+            status: post.status,
+            category_id: post.category_id,
+            category_name: post.category_name,
+            created_at: post.created_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            published_at: post.published_at.map(|dt| dt.and_utc()),
+            comment_count: None,
+            updated_at: post.updated_at.map(|dt| dt.and_utc()),
+            excerpt: None,
+            structured_data: None,
+        }
+    }
+}
+
+/// Validate the requested category, falling back to the default category
+/// when none was chosen.
+async fn resolve_category_id(services: &AppServices, category_id: Option<i32>) -> Result<Option<i32>, AppError> {
+    let category = services.db_service.execute(move |conn| match category_id {
+        Some(id) => Category::find_by_id(conn, id),
+        None => Category::find_by_name(conn, DEFAULT_CATEGORY_NAME),
+    }).await?;
+
+    match (category_id, category) {
+        (Some(id), None) => Err(AppError::ValidationError(format!("Category {} does not exist", id))),
+        (_, category) => Ok(category.map(|c| c.id)),
+    }
+}
+
+/// Convert posts for a public list, with their excerpts and their comment
+/// counts looked up in one query for the whole list
+pub(crate) fn with_comment_counts(conn: &mut diesel::PgConnection, posts: Vec<PostWithRelations>) -> Result<Vec<FrontendPost>, diesel::result::Error> {
+    let excerpt_length = ExcerptConfig::load(conn)?.length;
+    let ids: Vec<i32> = posts.iter().map(|post| post.id).collect();
+    let counts: std::collections::HashMap<i32, i64> = Comment::count_by_posts(conn, &ids)?
+        .into_iter()
+        .filter_map(|(post_id, count)| post_id.map(|id| (id, count)))
+        .collect();
+    Ok(posts.into_iter().map(|post| {
+        let comment_count = counts.get(&post.id).copied().unwrap_or(0);
+        let excerpt = excerpt_of(&post.content, excerpt_length);
+        FrontendPost { comment_count: Some(comment_count), excerpt: Some(excerpt), ..FrontendPost::from(post) }
+    }).collect())
+}
+
+/// Load a post together with its category and author for responses
+pub(crate) async fn load_post_with_relations(services: &AppServices, id: i32) -> Result<PostWithRelations, AppError> {
+    services.db_service.execute_optional(move |conn| {
+        Post::find_with_relations(conn, id)
+    }).await?
+        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PublicPostQuery {
+    pub category_id: Option<i32>,
+    /// Most posts to return; more than the largest page size returns that many
+    pub limit: Option<i64>,
+}
+
 /// Get all posts (public endpoint)
 /// 
-/// Returns a list of all published posts.
+/// Returns a list of all published posts with their comment counts.
+/// With `category_id` or `limit`, e.g. for a page's Posts List block, only
+/// the newest published posts of that category, up to `limit`.
 /// No authentication required for public access.
 pub async fn get_posts(
-    State(services): State<AppServices>
+    State(services): State<AppServices>,
+    Query(query): Query<PublicPostQuery>,
 ) -> Result<ResponseJson<Vec<FrontendPost>>, AppError> {
-    let posts = services.db_service.execute(|conn| {
-        Post::list(conn)
+    if query.limit.is_some_and(|limit| limit < 1) {
+        return Err(AppError::ValidationError("limit must be at least 1".to_string()));
+    }
+    let max = services.pagination.max_per_page;
+    let frontend_posts = services.db_service.execute(move |conn| {
+        let filter = PostFilter {
+            status: Some("published".to_string()),
+            search: None,
+            category_id: query.category_id,
+            live_at: Some(Utc::now().naive_utc()),
+        };
+        let limit = query.limit.map_or(i64::MAX, |limit| limit.min(max));
+        let (posts, _) = Post::list_filtered(conn, &filter, limit, 0)?;
+        with_comment_counts(conn, posts)
     }).await?;
     
-    let frontend_posts: Vec<FrontendPost> = posts.into_iter().map(FrontendPost::from).collect();
     Ok(ResponseJson(frontend_posts))
 }
 
+#[derive(Debug, serde::Deserialize)]
+pub struct PostListQuery {
+    /// One of the post statuses; omitted or `all` lists everything but trash
+    pub status: Option<String>,
+    pub search: Option<String>,
+    /// Opt into cursor pagination, which ignores `page`; empty for the first
+    /// page, then the previous response's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+/// Number of posts per status tab. `all` excludes trashed posts.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct PostStatusCounts {
+    pub all: i64,
+    pub published: i64,
+    pub draft: i64,
+    pub scheduled: i64,
+    pub trash: i64,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct PaginatedPosts {
+    pub posts: Vec<FrontendPost>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+    pub counts: PostStatusCounts,
+    /// Cursor for the next page in cursor mode; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl PostListQuery {
+    fn to_filter(&self) -> Result<PostFilter, AppError> {
+        let status = match self.status.as_deref().map(str::trim) {
+            None | Some("") | Some("all") => None,
+            Some(status) => Some(validate_post_status(status)?),
+        };
+        let search = self.search.as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from);
+        Ok(PostFilter { status, search, category_id: None, live_at: None })
+    }
+}
+
+/// Get posts for the admin post list (admin only)
+/// 
+/// Returns one page of posts filtered by status and search text, along with
+/// per-status counts for the status tabs. Pages are by offset unless a
+/// `cursor` is given, in which case they stay stable while posts are added
+/// or removed. The total and neighbouring pages are also sent as
+/// `X-Total-Count` and `Link` headers.
+/// Requires admin authentication.
+pub async fn get_admin_posts(
+    State(services): State<AppServices>,
+    uri: Uri,
+    paging: PaginationParams,
+    Query(query): Query<PostListQuery>,
+) -> Result<(HeaderMap, ResponseJson<PaginatedPosts>), AppError> {
+    let filter = query.to_filter()?;
+    let cursor = parse_cursor_param(query.cursor.as_deref())?;
+    let PaginationParams { page, per_page } = match cursor {
+        Some(_) => PaginationParams { page: 1, ..paging },
+        None => paging,
+    };
+    let offset = (page - 1) * per_page;
+
+    let ((posts, total, next_cursor), status_counts) = services.db_service.execute(move |conn| {
+        let listing = match cursor {
+            None => {
+                let (posts, total) = Post::list_filtered(conn, &filter, per_page, offset)?;
+                (posts, total, None)
+            }
+            Some(after) => {
+                let rows = Post::list_filtered_after(conn, &filter, after.map(|c| (c.created_at, c.id)), per_page + 1)?;
+                let (posts, next_cursor) = split_page(rows, per_page as usize, |post| Cursor::new(post.created_at, post.id));
+                (posts, Post::count_filtered(conn, &filter)?, next_cursor)
+            }
+        };
+        Ok((listing, Post::count_by_status(conn)?))
+    }).await?;
+
+    let mut counts = PostStatusCounts::default();
+    for (status, count) in status_counts {
+        match status.as_str() {
+            "published" => counts.published += count,
+            "draft" => counts.draft += count,
+            "scheduled" => counts.scheduled += count,
+            "trash" => counts.trash += count,
+            _ => {}
+        }
+        if status != "trash" {
+            counts.all += count;
+        }
+    }
+
+    let headers = match cursor {
+        None => pagination::page_headers(&uri, page, per_page, total),
+        Some(_) => pagination::cursor_headers(&uri, Some(total), next_cursor.as_deref()),
+    };
+    Ok((headers, ResponseJson(PaginatedPosts {
+        posts: posts.into_iter().map(FrontendPost::from).collect(),
+        total,
+        page,
+        per_page,
+        total_pages: (total + per_page - 1) / per_page,
+        counts,
+        next_cursor,
+    })))
+}
+
+/// Get a post in any status by ID (admin only)
+/// 
+/// Returns the full post, including drafts, scheduled and trashed posts.
+/// Requires admin authentication.
+pub async fn get_admin_post(
+    State(services): State<AppServices>,
+    Path(id): Path<i32>
+) -> Result<ResponseJson<FrontendPost>, AppError> {
+    let post = load_post_with_relations(&services, id).await?;
+    Ok(ResponseJson(FrontendPost::from(post)))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PostCalendarQuery {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+/// A post plotted on the admin calendar
+#[derive(Debug, serde::Serialize)]
+pub struct CalendarPost {
+    pub id: i32,
+    pub title: String,
+    pub status: String,
+    pub published_at: DateTime<Utc>,
+}
+
+/// Get posts for the publishing calendar (admin only)
+/// 
+/// Returns published and scheduled posts whose publish date falls in
+/// `[from, to)`. Both bounds are RFC 3339 timestamps; results are in UTC.
+/// Requires admin authentication.
+pub async fn get_post_calendar(
+    State(services): State<AppServices>,
+    Query(query): Query<PostCalendarQuery>,
+) -> Result<ResponseJson<Vec<CalendarPost>>, AppError> {
+    let from = query.from.naive_utc();
+    let to = query.to.naive_utc();
+    post_schedule::validate_calendar_range(from, to)?;
+
+    let posts = services.db_service.execute(move |conn| {
+        Post::list_dated_between(conn, from, to)
+    }).await?;
+
+    Ok(ResponseJson(posts.into_iter().filter_map(|post| Some(CalendarPost {
+        published_at: post.published_at?.and_utc(),
+        id: post.id,
+        title: post.title,
+        status: post.status,
+    })).collect()))
+}
+
 /// Get a specific post by ID (public endpoint)
 /// 
-/// Returns a single post by its ID.
+/// Returns a single published post by its ID. Unpublished posts are only
+/// reachable through a signed preview link.
 /// No authentication required for public access.
 pub async fn get_post(
     State(services): State<AppServices>, 
     Path(id): Path<i32>
 ) -> Result<ResponseJson<FrontendPost>, AppError> {
-    let post = services.db_service.execute_optional(move |conn| {
-        Ok(Post::find_by_id(conn, id)?)
-    }).await?
-        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+    let post = load_post_with_relations(&services, id).await?;
+    if !post_schedule::is_publicly_visible(&post.status, post.published_at, Utc::now().naive_utc()) {
+        return Err(AppError::NotFound("Post not found".to_string()));
+    }
+    let publisher = services.db_service.execute(structured_data::Publisher::load).await?;
+    let article = structured_data::article(&post, &publisher);
     
-    Ok(ResponseJson(FrontendPost::from(post)))
+    Ok(ResponseJson(FrontendPost { structured_data: Some(article), ..FrontendPost::from(post) }))
+}
+
+/// Enforce the `enforce_image_alt` setting when a post is being published.
+/// Drafts and other statuses are always allowed through.
+async fn check_publishable(services: &AppServices, status: &str, content: &str) -> Result<(), AppError> {
+    if status != "published" {
+        return Ok(());
+    }
+    let missing = image_alt::markdown_images_missing_alt(content);
+    if missing.is_empty() {
+        return Ok(());
+    }
+    if services.db_service.execute(image_alt::enforcement_enabled).await? {
+        image_alt::ensure_alt_text(&missing)?;
+    }
+    Ok(())
 }
 
 /// Create a new post (admin only)
@@ -89,24 +395,32 @@ pub async fn create_post(
     validate_text_content(&frontend_post.title, 200)?;
     validate_text_content(&frontend_post.content, 50000)?;
     
+    let status = validate_post_status(&frontend_post.status)?;
+    check_publishable(&services, &status, &frontend_post.content).await?;
+    let category_id = resolve_category_id(&services, frontend_post.category_id).await?;
+    let published_at = post_schedule::resolve_published_at(
+        &status,
+        frontend_post.published_at.map(|dt| dt.naive_utc()),
+        None,
+        Utc::now().naive_utc(),
+    )?;
+    
     let new_post = NewPost {
         title: frontend_post.title.trim().to_string(),
         content: frontend_post.content.trim().to_string(),
-        category_id: frontend_post.category_id,
+        category_id,
         user_id: Some(auth_user.id),
+        status,
+        published_at,
     };
     
     let created_post = services.db_service.execute(move |conn| {
         Post::create(conn, new_post)
     }).await?;
+    let created_post = load_post_with_relations(&services, created_post.id).await?;
     let response = FrontendPost {
-        id: Some(created_post.id),
-        title: created_post.title,
-        content: created_post.content,
         author: frontend_post.author,
-        status: frontend_post.status,
-        category_id: created_post.category_id,
-        created_at: created_post.created_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+        ..FrontendPost::from(created_post)
     };
     
     Ok((StatusCode::CREATED, ResponseJson(response)))
@@ -129,29 +443,228 @@ pub async fn update_post(
     validate_text_content(&frontend_post.title, 200)?;
     validate_text_content(&frontend_post.content, 50000)?;
     
-    // Check if post exists and update in one operation
+    let status = validate_post_status(&frontend_post.status)?;
+    check_publishable(&services, &status, &frontend_post.content).await?;
+    
+    let existing_post = services.db_service.execute_optional(move |conn| {
+        Post::find_by_id(conn, id)
+    }).await?
+        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+    // Only new posts fall back to the default category; an update without
+    // one keeps the post where it is
+    let category_id = match frontend_post.category_id {
+        Some(_) => resolve_category_id(&services, frontend_post.category_id).await?,
+        None => existing_post.category_id,
+    };
+    let published_at = post_schedule::resolve_published_at(
+        &status,
+        frontend_post.published_at.map(|dt| dt.naive_utc()),
+        existing_post.published_at,
+        Utc::now().naive_utc(),
+    )?;
+    
     let update_post = UpdatePost {
         title: Some(frontend_post.title.trim().to_string()),
         content: Some(frontend_post.content.trim().to_string()),
-        category_id: frontend_post.category_id,
+        category_id,
         user_id: None,
         updated_at: Some(chrono::Utc::now().naive_utc()),
+        status: Some(status),
+        published_at: Some(published_at),
     };
     
     let updated_post = services.db_service.execute(move |conn| {
-        // Check if post exists
-        let _existing_post = Post::find_by_id(conn, id)?
-            .ok_or_else(|| diesel::result::Error::NotFound)?;
-        
         Post::update(conn, id, update_post)
-    }).await.map_err(|e| match e {
-        AppError::DatabaseError(msg) if msg.contains("NotFound") => 
-            AppError::NotFound("Post not found".to_string()),
-        other => other,
-    })?;
+    }).await?;
+    let updated_post = load_post_with_relations(&services, updated_post.id).await?;
     Ok(ResponseJson(FrontendPost::from(updated_post)))
 }
 
+/// Duplicate a post (admin only)
+/// 
+/// Copies the title, content and category of an existing post into a new
+/// draft owned by the requesting user. The original post is left untouched.
+/// Requires admin authentication.
+pub async fn duplicate_post(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+    Path(id): Path<i32>
+) -> Result<(StatusCode, ResponseJson<FrontendPost>), AppError> {
+    let original = services.db_service.execute_optional(move |conn| {
+        Post::find_by_id(conn, id)
+    }).await?
+        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))?;
+
+    let new_post = NewPost {
+        title: format!("{} (copy)", original.title),
+        content: original.content,
+        category_id: original.category_id,
+        user_id: Some(auth_user.id),
+        status: "draft".to_string(),
+        published_at: None,
+    };
+
+    let duplicated = services.db_service.execute(move |conn| {
+        Post::create(conn, new_post)
+    }).await?;
+    let duplicated = load_post_with_relations(&services, duplicated.id).await?;
+
+    Ok((StatusCode::CREATED, ResponseJson(FrontendPost::from(duplicated))))
+}
+
+/// Export a post as Markdown (admin only)
+/// 
+/// Returns the post as a Markdown file with YAML front matter for static-site
+/// generators, sent as a download named after the post's slug. Posts in any
+/// status can be exported; unpublished ones are marked `draft: true`.
+/// Requires admin authentication.
+pub async fn export_post_markdown(
+    State(services): State<AppServices>,
+    Path(id): Path<i32>
+) -> Result<Response, AppError> {
+    let post = load_post_with_relations(&services, id).await?;
+    let slug = match slug_from_title(&post.title) {
+        slug if slug.is_empty() => format!("post-{}", post.id),
+        slug => slug,
+    };
+    let markdown = markdown_export::post_markdown(&markdown_export::ExportedPost {
+        title: &post.title,
+        slug: &slug,
+        author: post.author_username.as_deref().unwrap_or("Admin"),
+        category: post.category_name.as_deref(),
+        status: &post.status,
+        date: post.published_at.or(post.created_at),
+        content: &post.content,
+    });
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/markdown; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}.md\"", slug)),
+        ],
+        markdown,
+    ).into_response())
+}
+
+/// Most files one import request may carry
+const MAX_IMPORT_FILES: usize = 50;
+
+/// Outcome of importing one file
+#[derive(Debug, serde::Serialize)]
+pub struct ImportedFile {
+    pub file: String,
+    pub success: bool,
+    pub post_id: Option<i32>,
+    pub title: Option<String>,
+    pub error: Option<String>,
+    /// Front matter that was skipped, e.g. tags
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub failed: usize,
+    pub results: Vec<ImportedFile>,
+}
+
+/// Create a draft post from one Markdown file, creating its category if
+/// there is none by that name yet
+async fn import_markdown_file(
+    services: &AppServices,
+    user_id: i32,
+    file_name: &str,
+    data: &[u8],
+) -> Result<(Post, Vec<String>), AppError> {
+    let text = std::str::from_utf8(data)
+        .map_err(|_| AppError::ValidationError("File is not UTF-8 text".to_string()))?;
+    let imported = markdown_import::parse_markdown_file(file_name, text)?;
+
+    validate_text_content(&imported.title, 200)?;
+    validate_text_content(&imported.content, 50000)?;
+    if let Some(ref category) = imported.category {
+        validate_text_content(category, 100)?;
+    }
+
+    let category = imported.category;
+    let new_post = NewPost {
+        title: imported.title,
+        content: imported.content,
+        category_id: None,
+        user_id: Some(user_id),
+        status: "draft".to_string(),
+        // Kept so the post goes out under its original date when published
+        published_at: imported.date,
+    };
+    let post = services.db_service.execute(move |conn| {
+        conn.transaction(|conn| {
+            let category = match category {
+                Some(name) => match Category::find_by_name(conn, &name)? {
+                    Some(category) => Some(category),
+                    None => Some(Category::create(conn, NewCategory { name })?),
+                },
+                None => Category::find_by_name(conn, DEFAULT_CATEGORY_NAME)?,
+            };
+            Post::create(conn, NewPost { category_id: category.map(|c| c.id), ..new_post })
+        })
+    }).await?;
+    Ok((post, imported.warnings))
+}
+
+/// Import Markdown files as draft posts (admin only)
+/// 
+/// Accepts one or more `files` fields of multipart form data, each a
+/// Markdown file with optional YAML front matter as written by Jekyll, Hugo
+/// or `export_post_markdown`. Every file is imported on its own and the
+/// report says which succeeded; one bad file doesn't stop the rest.
+/// Requires admin authentication.
+pub async fn import_posts_markdown(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+    mut multipart: Multipart
+) -> Result<ResponseJson<ImportReport>, AppError> {
+    let mut results = Vec::new();
+    while let Some(field) = multipart.next_field().await
+        .map_err(|e| AppError::ValidationError(format!("Invalid multipart data: {}", e)))? {
+        if !matches!(field.name(), Some("files" | "file")) {
+            continue;
+        }
+        let file = field.file_name().unwrap_or("untitled.md").to_string();
+        let data = field.bytes().await
+            .map_err(|e| AppError::ValidationError(format!("Failed to read file data: {}", e)))?;
+
+        let outcome = if results.len() >= MAX_IMPORT_FILES {
+            Err(AppError::ValidationError(format!("Only {} files can be imported at once", MAX_IMPORT_FILES)))
+        } else {
+            import_markdown_file(&services, auth_user.id, &file, &data).await
+        };
+        results.push(match outcome {
+            Ok((post, warnings)) => ImportedFile {
+                file,
+                success: true,
+                post_id: Some(post.id),
+                title: Some(post.title),
+                error: None,
+                warnings,
+            },
+            Err(e) => {
+                tracing::warn!(file = %file, error = %e, "Markdown import failed");
+                let error = match e {
+                    AppError::ValidationError(message) => message,
+                    other => other.to_string(),
+                };
+                ImportedFile { file, success: false, post_id: None, title: None, error: Some(error), warnings: Vec::new() }
+            }
+        });
+    }
+
+    if results.is_empty() {
+        return Err(AppError::ValidationError("No files to import".to_string()));
+    }
+    let imported = results.iter().filter(|r| r.success).count();
+    Ok(ResponseJson(ImportReport { imported, failed: results.len() - imported, results }))
+}
+
 /// Delete a post (admin only)
 /// 
 /// Permanently deletes a post and associated data.
@@ -0,0 +1,194 @@
+use axum::{
+    extract::{State, Path, Query, Json, Extension},
+    response::Json as ResponseJson,
+    http::StatusCode,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use crate::{
+    AppServices,
+    controllers::{pages::FrontendPage, posts::{FrontendPost, load_post_with_relations}},
+    middleware::{auth::AuthenticatedUser, errors::AppError},
+    models::{NewPostShareLink, Page, PostShareLink},
+    services::{
+        preview_links::{PreviewTarget, PREVIEW_LINK_TTL_HOURS},
+        share_previews::{check_share_link, is_shareable, validate_share_request, SHARE_PARAM},
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewLinkRequest {
+    /// `post` or `page`
+    pub content_type: String,
+    pub id: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreviewLinkResponse {
+    pub token: String,
+    pub expires_at: String,
+    /// Public path including the preview token, e.g. `/post/12?preview=...`
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewQuery {
+    pub token: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SharePreviewRequest {
+    pub expires_in_hours: Option<i64>,
+    pub max_views: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SharePreviewResponse {
+    pub token: String,
+    pub expires_at: String,
+    pub max_views: i32,
+    /// Public path including the share token, e.g. `/post/12?share=...`
+    pub path: String,
+}
+
+/// Create a signed preview link (admin and editor)
+/// 
+/// Issues a time-limited link that shows an unpublished post or page on
+/// the public site without making it visible to everyone.
+/// Requires content management permission.
+pub async fn create_preview_link(
+    State(services): State<AppServices>,
+    Json(request): Json<PreviewLinkRequest>,
+) -> Result<ResponseJson<PreviewLinkResponse>, AppError> {
+    let id = request.id;
+    let (target, public_path) = match request.content_type.as_str() {
+        "post" => {
+            load_post_with_relations(&services, id).await?;
+            (PreviewTarget::Post(id), format!("/post/{}", id))
+        }
+        "page" => {
+            let page = services.db_service.execute_optional(move |conn| Page::find_by_id(conn, id)).await?
+                .ok_or_else(|| AppError::NotFound("Page not found".to_string()))?;
+            (PreviewTarget::Page(id), format!("/page/{}", page.slug))
+        }
+        other => return Err(AppError::ValidationError(format!("Unsupported preview content type: {}", other))),
+    };
+
+    let expires_at = chrono::Utc::now() + chrono::Duration::hours(PREVIEW_LINK_TTL_HOURS);
+    let token = services.preview_signer.create_token(target, expires_at.timestamp());
+
+    Ok(ResponseJson(PreviewLinkResponse {
+        path: format!("{}?preview={}", public_path, token),
+        token,
+        expires_at: expires_at.naive_utc().format("%Y-%m-%d %H:%M:%S").to_string(),
+    }))
+}
+
+/// Get a post through a preview link (public endpoint)
+/// 
+/// Returns the post in any status when the preview token is valid for it.
+/// No authentication required beyond the token.
+pub async fn get_post_preview(
+    State(services): State<AppServices>,
+    Path(id): Path<i32>,
+    Query(query): Query<PreviewQuery>,
+) -> Result<ResponseJson<FrontendPost>, AppError> {
+    let now = chrono::Utc::now().timestamp();
+    // Answer like a missing post so bad tokens don't reveal drafts exist
+    if !services.preview_signer.verify(&query.token, PreviewTarget::Post(id), now) {
+        return Err(AppError::NotFound("Post not found".to_string()));
+    }
+
+    let post = load_post_with_relations(&services, id).await?;
+    Ok(ResponseJson(FrontendPost::from(post)))
+}
+
+/// Get a page through a preview link (public endpoint)
+/// 
+/// Returns the page with this slug in any status when the preview token is
+/// valid for it.
+/// No authentication required beyond the token.
+pub async fn get_page_preview(
+    State(services): State<AppServices>,
+    Path(slug): Path<String>,
+    Query(query): Query<PreviewQuery>,
+) -> Result<ResponseJson<FrontendPage>, AppError> {
+    let normalized_slug = slug.trim().to_lowercase();
+    let page = services.db_service.execute_optional(move |conn| Page::find_by_slug(conn, &normalized_slug)).await?
+        .ok_or_else(|| AppError::NotFound("Page not found".to_string()))?;
+
+    let now = chrono::Utc::now().timestamp();
+    if !services.preview_signer.verify(&query.token, PreviewTarget::Page(page.id), now) {
+        return Err(AppError::NotFound("Page not found".to_string()));
+    }
+
+    Ok(ResponseJson(FrontendPage::from(page)))
+}
+
+/// Create a share link for a draft post (admin and editor)
+///
+/// Issues a link that shows a draft or scheduled post to anyone who has it,
+/// for a limited time (`expires_in_hours`, 72 by default) and number of
+/// views (`max_views`, 50 by default).
+/// Requires content management permission.
+pub async fn create_share_preview(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+    Path(id): Path<i32>,
+    request: Option<Json<SharePreviewRequest>>,
+) -> Result<(StatusCode, ResponseJson<SharePreviewResponse>), AppError> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let (hours, max_views) = validate_share_request(request.expires_in_hours, request.max_views)?;
+
+    let post = load_post_with_relations(&services, id).await?;
+    if !is_shareable(&post.status) {
+        return Err(AppError::ValidationError("Only draft and scheduled posts can be shared for preview".to_string()));
+    }
+
+    let token_id = uuid::Uuid::new_v4().to_string();
+    let token = services.share_signer.create_signed_token_from_uuid(&token_id)
+        .map_err(|e| AppError::InternalError(format!("Could not sign share link: {}", e)))?;
+    let new_link = NewPostShareLink {
+        post_id: id,
+        token_id,
+        created_by: Some(auth_user.id),
+        expires_at: Utc::now().naive_utc() + Duration::hours(hours),
+        max_views,
+    };
+    let link = services.db_service.execute(move |conn| PostShareLink::create(conn, new_link)).await?;
+    info!("User {} created share link {} for post {}", auth_user.username, link.id, id);
+
+    Ok((StatusCode::CREATED, ResponseJson(SharePreviewResponse {
+        path: format!("/post/{}?{}={}", id, SHARE_PARAM, token),
+        token,
+        expires_at: link.expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        max_views: link.max_views,
+    })))
+}
+
+/// Get a draft post through a share link (public endpoint)
+///
+/// Returns the post while the link is valid for it, counting the view.
+/// No authentication required beyond the token.
+pub async fn get_shared_post_preview(
+    State(services): State<AppServices>,
+    Path(id): Path<i32>,
+    Query(query): Query<PreviewQuery>,
+) -> Result<ResponseJson<FrontendPost>, AppError> {
+    // Answer like a missing post so bad tokens don't reveal drafts exist
+    let not_found = || AppError::NotFound("Post not found".to_string());
+    let token_id = services.share_signer.verify_signed_token(&query.token).ok_or_else(not_found)?;
+    let link = services.db_service.execute_optional(move |conn| PostShareLink::find_by_token_id(conn, &token_id)).await?
+        .ok_or_else(not_found)?;
+
+    let post = load_post_with_relations(&services, id).await?;
+    let now = Utc::now().naive_utc();
+    check_share_link(&link, id, &post.status, now)?;
+    let link_id = link.id;
+    if !services.db_service.execute(move |conn| PostShareLink::record_view(conn, link_id, now)).await? {
+        return Err(AppError::NotFound("This preview link is no longer valid".to_string()));
+    }
+
+    Ok(ResponseJson(FrontendPost::from(post)))
+}
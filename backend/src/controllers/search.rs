@@ -0,0 +1,101 @@
+use axum::{
+    extract::{State, Query},
+    response::Json as ResponseJson,
+    Extension,
+};
+use serde::{Deserialize, Serialize};
+use crate::{
+    AppServices,
+    middleware::{auth::AuthenticatedUser, errors::AppError},
+    models::{Comment, Page, Post},
+    services::{
+        pagination::PaginationParams,
+        search::{self, SearchResult, SearchResultKind},
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub query: String,
+    pub results: Vec<SearchResult>,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+/// Search posts, pages and comments (public endpoint)
+/// 
+/// Matches the `q` parameter against published posts and pages; admins
+/// also get comments. Results are ranked across types, title matches first,
+/// and carry a short snippet around the match. Accepts `page` and
+/// `per_page` query parameters.
+/// No authentication required; a valid session is used when present.
+pub async fn search(
+    State(services): State<AppServices>,
+    auth_user: Option<Extension<AuthenticatedUser>>,
+    Query(params): Query<SearchQuery>,
+    paging: PaginationParams,
+) -> Result<ResponseJson<SearchResponse>, AppError> {
+    let query = search::normalize_query(&params.q)?;
+    let include_comments = auth_user.is_some_and(|Extension(user)| user.is_admin());
+
+    let pattern = search::like_pattern(&query);
+    // The ranking is merged across types, so each type has to supply every
+    // match up to the end of the requested page
+    let per_type = paging.offset() + paging.per_page;
+    let (posts, pages, comments) = services.db_service.execute(move |conn| {
+        let posts = Post::search_published(conn, &pattern, per_type)?;
+        let pages = Page::search_published(conn, &pattern, per_type)?;
+        let comments = if include_comments {
+            Comment::search(conn, &pattern, per_type)?
+        } else {
+            Vec::new()
+        };
+        Ok((posts, pages, comments))
+    }).await?;
+
+    let post_results = posts.into_iter().map(|post| search::build_result(
+        SearchResultKind::Post,
+        post.id,
+        post.title,
+        &post.content,
+        format!("/post/{}", post.id),
+        post.published_at.or(post.created_at),
+        &query,
+    )).collect();
+
+    let page_results = pages.into_iter().map(|page| search::build_result(
+        SearchResultKind::Page,
+        page.id,
+        page.title,
+        &page.content,
+        format!("/page/{}", page.slug),
+        page.updated_at,
+        &query,
+    )).collect();
+
+    let comment_results = comments.into_iter().map(|comment| {
+        let (on, url) = match (comment.post_id, comment.page_slug) {
+            (Some(post_id), _) => (comment.post_title, format!("/post/{}", post_id)),
+            (None, Some(slug)) => (comment.page_title, format!("/page/{}", slug)),
+            (None, None) => (None, "/admin/comments".to_string()),
+        };
+        let title = format!(
+            "{} on {}",
+            comment.author_username.as_deref().unwrap_or("Anonymous"),
+            on.as_deref().unwrap_or("deleted content"),
+        );
+        search::build_result(SearchResultKind::Comment, comment.id, title, &comment.content, url, comment.created_at, &query)
+    }).collect();
+
+    let results = search::merge_results(
+        vec![post_results, page_results, comment_results],
+        paging.offset() as usize,
+        paging.per_page as usize,
+    );
+    Ok(ResponseJson(SearchResponse { query, results, page: paging.page, per_page: paging.per_page }))
+}
@@ -1,17 +1,96 @@
 use axum::{
-    extract::{State, Path, Extension},
+    extract::{State, Path, Query, Extension, Json},
     response::Json as ResponseJson,
+    http::{HeaderMap, Method, Uri},
 };
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
 use crate::{
     AppServices,
     middleware::{
         auth::AuthenticatedUser,
         errors::AppError,
     },
-    models::session::SessionInfo,
+    models::session::{Session, SessionInfo, SessionFilter, SessionWithUser},
+    services::{pagination::{self, PaginationParams}, session_cookie::request_token},
 
 };
 
+/// Filters for admin session listings
+#[derive(Debug, Deserialize)]
+pub struct SessionListQuery {
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    /// "active" or "expired"
+    pub status: Option<String>,
+    /// Inclusive creation date range, `YYYY-MM-DD`
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaginatedSessions {
+    pub sessions: Vec<SessionWithUser>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+}
+
+fn parse_date_param(name: &str, value: &str) -> Result<NaiveDateTime, AppError> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default())
+        .map_err(|_| AppError::ValidationError(format!("{} must be a date in YYYY-MM-DD format", name)))
+}
+
+impl SessionListQuery {
+    fn to_filter(&self) -> Result<SessionFilter, AppError> {
+        let active = match self.status.as_deref().map(str::trim) {
+            None | Some("") | Some("all") => None,
+            Some("active") => Some(true),
+            Some("expired") => Some(false),
+            Some(other) => return Err(AppError::ValidationError(format!("Invalid session status: {}", other))),
+        };
+
+        let created_from = self.from.as_deref()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| parse_date_param("from", v))
+            .transpose()?;
+        // `to` is inclusive, so filter on the start of the following day
+        let created_before = self.to.as_deref()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| parse_date_param("to", v).map(|date| date + Duration::days(1)))
+            .transpose()?;
+
+        Ok(SessionFilter {
+            user_id: self.user_id,
+            username: self.username.as_ref()
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty()),
+            active,
+            created_from,
+            created_before,
+        })
+    }
+}
+
+async fn list_sessions_page(services: &AppServices, paging: PaginationParams, filter: SessionFilter) -> Result<PaginatedSessions, AppError> {
+    let PaginationParams { page, per_page } = paging;
+    let offset = paging.offset();
+
+    let (sessions, total) = services.db_service.execute(move |conn| {
+        Session::list_filtered(conn, &filter, per_page, offset)
+    }).await?;
+
+    Ok(PaginatedSessions {
+        sessions,
+        total,
+        page,
+        per_page,
+        total_pages: paging.total_pages(total),
+    })
+}
+
 /// Get current user's active sessions
 /// 
 /// Returns a list of all active sessions for the authenticated user.
@@ -43,18 +122,33 @@ pub async fn logout_all_sessions(
 
 /// Get system-wide session statistics (admin only)
 /// 
-/// Returns comprehensive session statistics for monitoring.
-/// Includes total sessions, active sessions, cleanup stats, etc.
+/// Returns comprehensive session statistics for monitoring, along with
+/// one page of sessions filtered by user, username, status and date range.
+/// The page's total and neighbours are also sent as `X-Total-Count` and
+/// `Link` headers.
+/// Requires admin authentication.
 pub async fn get_all_session_stats(
     State(services): State<AppServices>,
-) -> Result<ResponseJson<serde_json::Value>, AppError> {
+    uri: Uri,
+    paging: PaginationParams,
+    Query(query): Query<SessionListQuery>,
+) -> Result<(HeaderMap, ResponseJson<serde_json::Value>), AppError> {
+    let filter = query.to_filter()?;
     let stats = services.session_manager.get_session_statistics().await?;
-    Ok(ResponseJson(serde_json::json!({
+    let page = list_sessions_page(&services, paging, filter).await?;
+
+    let headers = pagination::page_headers(&uri, page.page, page.per_page, page.total);
+    Ok((headers, ResponseJson(serde_json::json!({
         "total_sessions": stats.total_sessions,
         "active_sessions": stats.active_sessions,
         "expired_cleaned": stats.expired_cleaned,
-        "last_cleanup": stats.last_cleanup
-    })))
+        "last_cleanup": stats.last_cleanup,
+        "sessions": page.sessions,
+        "total": page.total,
+        "page": page.page,
+        "per_page": page.per_page,
+        "total_pages": page.total_pages
+    }))))
 }
 
 /// Manually trigger session cleanup (admin only)
@@ -75,14 +169,21 @@ pub async fn manual_session_cleanup(
 
 /// Get sessions for a specific user (admin only)
 /// 
-/// Returns all active sessions for the specified user.
+/// Returns a page of the specified user's sessions, accepting the same
+/// status and date range filters as the system-wide listing.
 /// Useful for admin monitoring and support.
 pub async fn get_admin_user_sessions(
     State(services): State<AppServices>,
     Path(user_id): Path<i32>,
-) -> Result<ResponseJson<Vec<SessionInfo>>, AppError> {
-    let sessions = services.session_manager.get_user_sessions(user_id).await?;
-    Ok(ResponseJson(sessions))
+    paging: PaginationParams,
+    Query(query): Query<SessionListQuery>,
+) -> Result<ResponseJson<PaginatedSessions>, AppError> {
+    let filter = SessionFilter {
+        user_id: Some(user_id),
+        ..query.to_filter()?
+    };
+    let page = list_sessions_page(&services, paging, filter).await?;
+    Ok(ResponseJson(page))
 }
 
 /// Force logout a user (admin only)
@@ -102,4 +203,41 @@ pub async fn force_logout_user(
         "message": format!("Force logged out user {}", user_id),
         "sessions_expired": count
     })))
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ForceLogoutAllRequest {
+    /// Leave the requesting admin's own session active
+    #[serde(default)]
+    pub keep_current_session: bool,
+}
+
+/// Force logout every user (admin only)
+/// 
+/// Expires all sessions across all users at once, for use during a
+/// security incident, and records who did it in the audit log. The
+/// requesting admin can optionally keep their session.
+/// Requires admin authentication.
+pub async fn force_logout_all_sessions(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    State(services): State<AppServices>,
+    method: Method,
+    headers: HeaderMap,
+    Json(request): Json<ForceLogoutAllRequest>,
+) -> Result<ResponseJson<serde_json::Value>, AppError> {
+    let current_token = request_token(&headers, &method, services.session_manager.config().cookie_auth)
+        .ok()
+        .map(|(token, _)| token);
+    let keep_token = if request.keep_current_session { current_token } else { None };
+
+    let count = services.session_manager
+        .force_expire_all_sessions(keep_token, auth_user.id, &auth_user.username)
+        .await?;
+
+    Ok(ResponseJson(serde_json::json!({
+        "success": true,
+        "message": format!("Force logged out {} sessions", count),
+        "sessions_expired": count,
+        "kept_current_session": request.keep_current_session
+    })))
+}
@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use crate::{
+    AppServices,
+    middleware::errors::AppError,
+    services::sitemap::{child_document, parse_child_number, root_document, SitemapKind},
+};
+
+fn xml_response(xml: String) -> Response {
+    ([(header::CONTENT_TYPE, "application/xml; charset=utf-8")], xml).into_response()
+}
+
+/// Get the sitemap (public endpoint)
+///
+/// Lists the home page, the post list and every published post and page,
+/// or, once they no longer fit one file, indexes the numbered post and page
+/// sitemaps. URLs are built from the `site_url` setting.
+/// No authentication required.
+pub async fn get_sitemap(
+    State(services): State<AppServices>,
+) -> Result<Response, AppError> {
+    Ok(xml_response(services.db_service.execute(root_document).await?))
+}
+
+async fn child_sitemap(services: &AppServices, kind: SitemapKind, tail: &str) -> Result<Response, AppError> {
+    let not_found = || AppError::NotFound("Sitemap not found".to_string());
+    let number = parse_child_number(tail).ok_or_else(not_found)?;
+    services.db_service.execute_optional(move |conn| child_document(conn, kind, number)).await?
+        .map(xml_response)
+        .ok_or_else(not_found)
+}
+
+/// Get a numbered posts sitemap of a large site (public endpoint)
+///
+/// `/sitemap-posts-N.xml`, listed in the sitemap index.
+/// No authentication required.
+pub async fn get_posts_sitemap(
+    State(services): State<AppServices>,
+    Path(tail): Path<String>,
+) -> Result<Response, AppError> {
+    child_sitemap(&services, SitemapKind::Posts, &tail).await
+}
+
+/// Get a numbered pages sitemap of a large site (public endpoint)
+///
+/// `/sitemap-pages-N.xml`, listed in the sitemap index; the first also
+/// lists the home page and post list.
+/// No authentication required.
+pub async fn get_pages_sitemap(
+    State(services): State<AppServices>,
+    Path(tail): Path<String>,
+) -> Result<Response, AppError> {
+    child_sitemap(&services, SitemapKind::Pages, &tail).await
+}
@@ -0,0 +1,176 @@
+//! Server-rendered public posts and pages, built with the `ssr` feature
+//!
+//! Serves the app shell filled in by `services::ssr` for `/post/:id` and
+//! `/page/:slug`, so the reverse proxy can send those paths here instead of
+//! to the static `index.html`. Content that isn't public gets the plain
+//! shell with a 404 status; the app still loads and shows its own page, so
+//! preview links keep working.
+
+use std::{path::Path as FilePath, sync::Arc};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse, Redirect, Response},
+    Extension,
+};
+use chrono::Utc;
+use diesel::prelude::*;
+use shared::component_style::{component_style, header_logo, logo_style, HeaderLogo};
+use tracing::{info, warn};
+use crate::{
+    AppServices,
+    controllers::posts::load_post_with_relations,
+    middleware::{errors::AppError, security_headers::CspNonce},
+    models::ComponentTemplate,
+    services::{
+        branding::Branding,
+        text_direction::TextDirection,
+        canonical_urls::canonical_path,
+        feed::{excerpt, render_html},
+        post_schedule,
+        slug_redirects::{self, SlugLookup},
+        ssr::{page_document, render, with_csp_nonce, SsrDocument, SsrLogo, SsrSite},
+        structured_data::{self, Publisher},
+    },
+};
+
+#[derive(Clone)]
+pub struct SsrState {
+    pub services: AppServices,
+    /// The built `index.html`
+    pub shell: Arc<String>,
+}
+
+/// Read the app shell, or `None` (with a warning) if it isn't there
+pub fn load_shell(path: &FilePath) -> Option<Arc<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(shell) => {
+            info!("Server-side rendering public posts and pages with {}", path.display());
+            Some(Arc::new(shell))
+        }
+        Err(e) => {
+            warn!("Server-side rendering is off: cannot read {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn active_data(conn: &mut PgConnection, component_type: &str) -> QueryResult<Option<serde_json::Value>> {
+    Ok(ComponentTemplate::find_by_type(conn, component_type)?
+        .into_iter()
+        .find(|t| t.is_active)
+        .map(|t| t.template_data))
+}
+
+fn active_style(conn: &mut PgConnection, component_type: &str) -> QueryResult<String> {
+    Ok(active_data(conn, component_type)?
+        .map(|data| component_style(component_type, &data))
+        .unwrap_or_default())
+}
+
+/// The header logo, shown the way the active header template asks
+fn logo(conn: &mut PgConnection, logo_url: Option<String>) -> QueryResult<Option<SsrLogo>> {
+    let data = active_data(conn, "header")?.unwrap_or_default();
+    let beside_title = match header_logo(&data, logo_url.is_some()) {
+        HeaderLogo::Title => return Ok(None),
+        HeaderLogo::Logo => false,
+        HeaderLogo::LogoAndTitle => true,
+    };
+    Ok(logo_url.map(|url| SsrLogo { url, style: logo_style(&data), beside_title }))
+}
+
+async fn load_site(services: &AppServices) -> Result<(Publisher, SsrSite), AppError> {
+    services.db_service.execute(|conn| {
+        let publisher = Publisher::load(conn)?;
+        let branding = Branding::load(conn)?;
+        let site = SsrSite {
+            name: publisher.name.clone(),
+            header_style: active_style(conn, "header")?,
+            footer_style: active_style(conn, "footer")?,
+            logo: logo(conn, branding.logo_url)?,
+            favicon_url: branding.favicon_url,
+            text_direction: TextDirection::load(conn)?,
+        };
+        Ok((publisher, site))
+    }).await
+}
+
+fn html_response(status: StatusCode, html: String) -> Response {
+    (status, [(header::CACHE_CONTROL, "no-cache")], Html(html)).into_response()
+}
+
+/// The unfilled shell, for content the public can't see
+fn not_found(state: &SsrState, nonce: &CspNonce) -> Response {
+    html_response(StatusCode::NOT_FOUND, with_csp_nonce(&state.shell, &nonce.0))
+}
+
+/// Server-rendered post (public endpoint)
+///
+/// Published posts get their title, excerpt, canonical URL, Open Graph tags,
+/// JSON-LD and body in the app shell.
+/// No authentication required.
+pub async fn render_post(
+    State(state): State<SsrState>,
+    Extension(nonce): Extension<CspNonce>,
+    Path(id): Path<String>,
+) -> Result<Response, AppError> {
+    let Ok(id) = id.parse::<i32>() else {
+        return Ok(not_found(&state, &nonce));
+    };
+    let post = match load_post_with_relations(&state.services, id).await {
+        Ok(post) if post_schedule::is_publicly_visible(&post.status, post.published_at, Utc::now().naive_utc()) => post,
+        Ok(_) | Err(AppError::NotFound(_)) => return Ok(not_found(&state, &nonce)),
+        Err(e) => return Err(e),
+    };
+    let (publisher, site) = load_site(&state.services).await?;
+
+    let path = canonical_path(&format!("/post/{}", post.id), publisher.trailing_slash);
+    let document = SsrDocument {
+        title: post.title.clone(),
+        description: excerpt(&post.content),
+        canonical_url: publisher.absolute(&path),
+        og_type: "article",
+        structured_data: Some(structured_data::article(&post, &publisher)),
+        content_html: render_html(&post.content),
+    };
+    Ok(html_response(StatusCode::OK, render(&state.shell, &document, &site, &nonce.0)))
+}
+
+/// Server-rendered page (public endpoint)
+///
+/// Published pages get their title, a summary, canonical URL, Open Graph
+/// tags and text content in the app shell. An old slug of a renamed page
+/// redirects to the current one.
+/// No authentication required.
+pub async fn render_page(
+    State(state): State<SsrState>,
+    Extension(nonce): Extension<CspNonce>,
+    Path(slug): Path<String>,
+) -> Result<Response, AppError> {
+    let slug = slug.trim().to_lowercase();
+    let lookup = state.services.db_service.execute(move |conn| {
+        slug_redirects::lookup_page(conn, &slug, Utc::now().naive_utc())
+    }).await?;
+    let (publisher, site) = load_site(&state.services).await?;
+
+    let page = match lookup {
+        SlugLookup::Found(page) => page,
+        SlugLookup::Redirect(current) => {
+            let path = canonical_path(&format!("/page/{}", current), publisher.trailing_slash);
+            return Ok(Redirect::permanent(&path).into_response());
+        }
+        SlugLookup::NotFound => return Ok(not_found(&state, &nonce)),
+    };
+
+    let (description, content_html) = page_document(&page.content);
+    let path = canonical_path(&format!("/page/{}", page.slug), publisher.trailing_slash);
+    let document = SsrDocument {
+        title: page.title,
+        description,
+        canonical_url: publisher.absolute(&path),
+        og_type: "website",
+        structured_data: None,
+        content_html,
+    };
+    Ok(html_response(StatusCode::OK, render(&state.shell, &document, &site, &nonce.0)))
+}
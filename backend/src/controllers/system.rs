@@ -9,7 +9,7 @@ use diesel::prelude::*;
 use crate::{
     models::{Setting, SystemInfo, BackupInfo, DataSnapshot},
     middleware::errors::AppError,
-    services::BackupService,
+    services::{BackupService, SessionConfig, SESSION_SETTING_KEYS, comment_limits::{CommentLimits, COMMENT_LIMIT_SETTING_KEYS}, comment_rate_limit::{CommentRateLimits, COMMENT_RATE_SETTING_KEYS}, comment_sort::{CommentSort, COMMENT_SORT_SETTING}, email_domains::{parse_domain_list, EMAIL_DOMAIN_SETTING_KEYS}, signup_policy::{validate_min_fill_seconds, SIGNUP_MIN_FILL_SECONDS_SETTING}, timezones, homepage::{HomepageConfig, HomepageMode, HOMEPAGE_MODE_SETTING, HOMEPAGE_PAGE_ID_SETTING, HOMEPAGE_SETTING_KEYS}, captcha::{CaptchaConfig, CAPTCHA_SETTING_KEYS}, feed::{FeedConfig, FEED_SETTING_KEYS}, excerpts::{ExcerptConfig, EXCERPT_SETTING_KEYS}, branding::{self, BRANDING_SETTING_KEYS}, text_direction::{TextDirection, TEXT_DIRECTION_SETTING}, gravatar::{GravatarConfig, GRAVATAR_SETTING_KEYS}, canonical_urls::{validate_site_url, TrailingSlash, CANONICAL_TRAILING_SLASH_SETTING, SITE_URL_SETTING}, slow_query_log::{validate_threshold, SLOW_QUERY_THRESHOLD_SETTING}, data_retention::{self, RetentionPolicy, RetentionReport, RETENTION_SETTING_KEYS}, word_filter::{parse_word_list, WordFilterMode, COMMENT_BLOCKED_WORDS_SETTING, COMMENT_WORD_FILTER_MODE_SETTING}, template_schema::{UnknownKeys, UNKNOWN_KEYS_SETTING}},
     AppServices,
 };
 
@@ -98,14 +98,70 @@ pub async fn get_setting(
     Ok(ResponseJson(setting))
 }
 
+/// Checks a submitted setting value, returning the value to store
+type SettingValidator = fn(&mut PgConnection, &str, &str) -> Result<String, AppError>;
+
+/// Validators for the settings that have rules, by the keys they cover
+const SETTING_VALIDATORS: &[(&[&str], SettingValidator)] = &[
+    (&SESSION_SETTING_KEYS, |_, key, value| SessionConfig::validate_setting(key, value).map(|_| value.to_string())),
+    (&COMMENT_LIMIT_SETTING_KEYS, |_, key, value| CommentLimits::validate_setting(key, value).map(|_| value.to_string())),
+    (&COMMENT_RATE_SETTING_KEYS, |_, key, value| CommentRateLimits::validate_setting(key, value).map(|_| value.to_string())),
+    (&GRAVATAR_SETTING_KEYS, |_, key, value| Ok(GravatarConfig::validate_setting(key, value)?.to_string())),
+    (&[CANONICAL_TRAILING_SLASH_SETTING], |_, _, value| Ok(TrailingSlash::parse(value)?.as_str().to_string())),
+    (&[SITE_URL_SETTING], |_, _, value| validate_site_url(value)),
+    (&RETENTION_SETTING_KEYS, |_, key, value| Ok(RetentionPolicy::validate_setting(key, value)?.to_string())),
+    (&[SLOW_QUERY_THRESHOLD_SETTING], |_, _, value| Ok(validate_threshold(value)?.to_string())),
+    (&[TEXT_DIRECTION_SETTING], |_, _, value| Ok(TextDirection::parse(value)?.as_str().to_string())),
+    (&[COMMENT_SORT_SETTING], |_, _, value| Ok(CommentSort::parse(value)?.as_str().to_string())),
+    (&[COMMENT_BLOCKED_WORDS_SETTING], |_, _, value| Ok(parse_word_list(value)?.join(", "))),
+    (&[COMMENT_WORD_FILTER_MODE_SETTING], |_, _, value| Ok(WordFilterMode::parse(value)?.as_str().to_string())),
+    (&[UNKNOWN_KEYS_SETTING], |_, _, value| Ok(UnknownKeys::parse(value)?.as_str().to_string())),
+    (&EMAIL_DOMAIN_SETTING_KEYS, |_, _, value| Ok(parse_domain_list(value)?.join(", "))),
+    (&[SIGNUP_MIN_FILL_SECONDS_SETTING], |_, _, value| validate_min_fill_seconds(value).map(|_| value.to_string())),
+    (&[timezones::TIMEZONE_SETTING], |_, _, value| timezones::validate_timezone_setting(value)),
+    (&FEED_SETTING_KEYS, |_, key, value| FeedConfig::validate_setting(key, value).map(|_| value.trim().to_string())),
+    (&EXCERPT_SETTING_KEYS, |_, key, value| ExcerptConfig::validate_setting(key, value)),
+    (&BRANDING_SETTING_KEYS, branding::validate_setting),
+    (&CAPTCHA_SETTING_KEYS, |_, key, value| CaptchaConfig::validate_setting(key, value).map(|_| value.trim().to_string())),
+    (&HOMEPAGE_SETTING_KEYS, |_, key, value| HomepageConfig::validate_setting(key, value).map(|_| value.trim().to_lowercase())),
+];
+
 // Update multiple settings
 pub async fn update_settings(
     State(services): State<AppServices>,
-    Json(request): Json<SettingsRequest>
+    Json(mut request): Json<SettingsRequest>
 ) -> Result<ResponseJson<Vec<Setting>>, AppError> {
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
 
+    // Validate and normalize every value before writing anything
+    for setting_data in &mut request.settings {
+        for (keys, validate) in SETTING_VALIDATORS {
+            if keys.contains(&setting_data.key.as_str()) {
+                setting_data.value = validate(&mut conn, &setting_data.key, &setting_data.value)?;
+            }
+        }
+    }
+    if request.settings.iter().any(|s| HOMEPAGE_SETTING_KEYS.contains(&s.key.as_str())) {
+        // The mode and page may be saved separately, so check them together
+        let stored = HomepageConfig::load(&mut conn)?;
+        let value_of = |key: &str| request.settings.iter().rev().find(|s| s.key == key).map(|s| s.value.as_str());
+        let homepage = HomepageConfig {
+            mode: value_of(HOMEPAGE_MODE_SETTING).and_then(HomepageMode::parse).unwrap_or(stored.mode),
+            page_id: match value_of(HOMEPAGE_PAGE_ID_SETTING) {
+                Some(value) => HomepageConfig::from_values(None, Some(value)).page_id,
+                None => stored.page_id,
+            },
+        };
+        homepage.validate(&mut conn)?;
+    }
+    let session_policy_changed = request.settings.iter()
+        .any(|s| SESSION_SETTING_KEYS.contains(&s.key.as_str()));
+    let slow_query_threshold = request.settings.iter()
+        .rev()
+        .find(|s| s.key == SLOW_QUERY_THRESHOLD_SETTING)
+        .and_then(|s| s.value.parse::<u64>().ok());
+
     let mut updated_settings = Vec::new();
 
     for setting_data in request.settings {
@@ -121,9 +177,32 @@ pub async fn update_settings(
         updated_settings.push(setting);
     }
 
+    if session_policy_changed {
+        services.session_manager.reload_config()?;
+    }
+    if let Some(ms) = slow_query_threshold {
+        services.slow_query_threshold.set_millis(ms);
+    }
+    services.bootstrap_cache.invalidate();
+
     Ok(ResponseJson(updated_settings))
 }
 
+/// Run the data retention purge now (admin only)
+/// 
+/// Removes expired sessions, stale tokens and old audit log entries past
+/// their retention period, as the hourly background job does, and returns
+/// what was removed.
+pub async fn run_data_retention(
+    State(services): State<AppServices>,
+) -> Result<ResponseJson<RetentionReport>, AppError> {
+    let report = services.db_service.execute(|conn| {
+        let policy = RetentionPolicy::load(conn)?;
+        data_retention::run(conn, policy, chrono::Utc::now().naive_utc())
+    }).await?;
+    Ok(ResponseJson(report))
+}
+
 // Get system information
 pub async fn get_system_info(
     State(services): State<AppServices>
@@ -160,6 +239,13 @@ pub async fn get_system_info(
         .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
         .map(|dt| dt.with_timezone(&Utc));
 
+    let site_timezone = timezones::load_site_timezone(&mut conn)
+        .map_err(|e| AppError::DatabaseQuery(e.to_string()))?;
+    let site_time = timezones::format_in_zone(
+        Utc::now().naive_utc(),
+        timezones::display_zone(&site_timezone),
+    );
+
     // System info (simplified for now)
     let system_info = SystemInfo {
         rust_version: env!("CARGO_PKG_VERSION").to_string(),
@@ -173,6 +259,7 @@ pub async fn get_system_info(
         total_users,
         total_media,
         last_backup,
+        site_time,
     };
 
     Ok(ResponseJson(system_info))
@@ -192,7 +279,8 @@ pub async fn create_backup(
     let database_url = std::env::var("DATABASE_URL")
         .map_err(|_| AppError::Configuration("DATABASE_URL not set".to_string()))?;
     
-    let backup_service = BackupService::new(backup_dir, database_url);
+    let backup_service = BackupService::new(backup_dir, database_url)
+        .with_upload_dir(services.upload_dir.clone());
 
     // Create backup based on type
     let backup_info = match request.backup_type.as_str() {
@@ -270,6 +358,7 @@ pub async fn restore_backup(
     // Perform database restore
     let result = backup_service.restore_database(&backup_filename).await
         .map_err(|e| AppError::InternalServerError(format!("Database restore failed: {}", e)))?;
+    services.bootstrap_cache.invalidate();
     
     Ok(ResponseJson(result))
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use axum::{
     extract::{State, Path, Json, Extension},
+    http::{HeaderMap, Uri},
     response::Json as ResponseJson,
 
 };
@@ -12,6 +13,10 @@ use crate::{
         validation::{validate_username, validate_email, validate_password},
         errors::AppError,
     },
+    services::{
+        pagination::{self, PaginationParams},
+        user_uniqueness::{ensure_available, map_unique_violation},
+    },
 };
 
 #[derive(Deserialize)]
@@ -29,6 +34,8 @@ pub struct UpdateUserRequest {
     pub password: Option<String>,
     pub role: Option<String>,
     pub status: Option<String>,
+    /// Make the user pick a new password at their next request
+    pub must_change_password: Option<bool>,
 }
 
 #[derive(Deserialize)]
@@ -44,19 +51,37 @@ pub struct UserResponse {
     pub role: String,
     pub status: String,
     pub email_verified: bool,
+    pub must_change_password: bool,
+    pub last_login_at: Option<chrono::NaiveDateTime>,
     pub created_at: Option<chrono::NaiveDateTime>,
 }
 
-/// Get all users (admin only)
+#[derive(Serialize)]
+pub struct PaginatedUsers {
+    pub users: Vec<UserResponse>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+}
+
+/// Get users (admin only)
 /// 
-/// Returns a list of all users in the system.
+/// Returns one page of users, newest first, along with the total count.
+/// Accepts `page` and `per_page` query parameters; the total and
+/// neighbouring pages are also sent as `X-Total-Count` and `Link` headers.
 /// Requires admin authentication.
 pub async fn get_users(
-    State(services): State<AppServices>
-) -> Result<ResponseJson<Vec<UserResponse>>, AppError> {
-    let mut conn = services.db_pool.get()
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let users = User::list(&mut conn)?;
+    State(services): State<AppServices>,
+    uri: Uri,
+    paging: PaginationParams,
+) -> Result<(HeaderMap, ResponseJson<PaginatedUsers>), AppError> {
+    let PaginationParams { page, per_page } = paging;
+    let offset = paging.offset();
+
+    let (users, total) = services.db_service.execute(move |conn| {
+        User::list_paginated(conn, per_page, offset)
+    }).await?;
     
     let user_responses: Vec<UserResponse> = users.into_iter().map(|user| UserResponse {
         id: user.id,
@@ -65,16 +90,24 @@ pub async fn get_users(
         role: user.role,
         status: user.status,
         email_verified: user.email_verified,
+        must_change_password: user.must_change_password,
+        last_login_at: user.last_login_at,
         created_at: user.created_at,
     }).collect();
-    
-    Ok(ResponseJson(user_responses))
+
+    Ok((pagination::page_headers(&uri, page, per_page, total), ResponseJson(PaginatedUsers {
+        users: user_responses,
+        total,
+        page,
+        per_page,
+        total_pages: paging.total_pages(total),
+    })))
 }
 
 /// Create a new user (admin only)
 /// 
 /// Creates a new user with validation and duplicate checking.
-/// Passwords are automatically hashed using bcrypt.
+/// Passwords are hashed with the configured algorithm (bcrypt by default).
 /// Requires admin authentication.
 pub async fn create_user(
     Extension(_auth_user): Extension<AuthenticatedUser>,
@@ -93,21 +126,11 @@ pub async fn create_user(
     let mut conn = services.db_pool.get()
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
     
-    // Check if username already exists
-    if User::find_by_username(&mut conn, &user_req.username)?.is_some() {
-        return Err(AppError::ConflictError("Username already exists".to_string()));
-    }
-    
-    // Check if email already exists (if provided)
-    if let Some(ref email) = user_req.email {
-        if User::find_by_email(&mut conn, email)?.is_some() {
-            return Err(AppError::ConflictError("Email already exists".to_string()));
-        }
-    }
+    // Check the username and email (if provided) aren't taken
+    ensure_available(&mut conn, Some(&user_req.username), user_req.email.as_deref(), None)?;
     
     // Hash password
-    let hashed_password = bcrypt::hash(&user_req.password, bcrypt::DEFAULT_COST)
-        .map_err(|e| AppError::InternalError(format!("Password hashing failed: {}", e)))?;
+    let hashed_password = services.password_hashing.hash(&user_req.password)?;
     
     let new_user = NewUser {
         username: user_req.username,
@@ -120,7 +143,7 @@ pub async fn create_user(
         email_verification_expires_at: None,
     };
     
-    let created_user = User::create(&mut conn, new_user)?;
+    let created_user = User::create(&mut conn, new_user).map_err(map_unique_violation)?;
     
     Ok(ResponseJson(serde_json::json!({
         "id": created_user.id,
@@ -162,14 +185,12 @@ pub async fn update_user(
     // Check if user exists
     let _existing_user = User::find_by_id(&mut conn, id)?
         .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    ensure_available(&mut conn, user_req.username.as_deref(), user_req.email.as_deref(), Some(id))?;
     
     // Hash password if provided
-    let hashed_password = if let Some(password) = user_req.password {
-        Some(bcrypt::hash(&password, bcrypt::DEFAULT_COST)
-            .map_err(|e| AppError::InternalError(format!("Password hashing failed: {}", e)))?)
-    } else {
-        None
-    };
+    let hashed_password = user_req.password
+        .map(|password| services.password_hashing.hash(&password))
+        .transpose()?;
     
     let update_user = UpdateUser {
         username: user_req.username,
@@ -180,16 +201,18 @@ pub async fn update_user(
         email_verified: None, // Don't change verification status in regular updates
         email_verification_token: None,
         email_verification_expires_at: None,
+        must_change_password: user_req.must_change_password,
     };
     
-    let updated_user = User::update(&mut conn, id, update_user)?;
+    let updated_user = User::update(&mut conn, id, update_user).map_err(map_unique_violation)?;
     
     Ok(ResponseJson(serde_json::json!({
         "id": updated_user.id,
         "username": updated_user.username,
         "email": updated_user.email,
         "role": updated_user.role,
-        "status": updated_user.status
+        "status": updated_user.status,
+        "must_change_password": updated_user.must_change_password
     })))
 }
 
@@ -271,6 +294,7 @@ pub async fn promote_user(
         email_verified: None,
         email_verification_token: None,
         email_verification_expires_at: None,
+        must_change_password: None,
     };
     
     let updated_user = User::update(&mut conn, id, update_user)?;
@@ -287,6 +311,8 @@ pub async fn promote_user(
             role: updated_user.role,
             status: updated_user.status,
             email_verified: updated_user.email_verified,
+            must_change_password: updated_user.must_change_password,
+            last_login_at: updated_user.last_login_at,
             created_at: updated_user.created_at,
         }
     })))
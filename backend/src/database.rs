@@ -1,6 +1,7 @@
 use diesel::pg::PgConnection;
 use diesel::r2d2::{self, ConnectionManager};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use crate::services::slow_query_log::{SlowQueryLog, SlowQueryThreshold};
 
 
 pub type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
@@ -8,9 +9,11 @@ pub type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 
-pub fn establish_connection_pool(database_url: &str) -> Result<DbPool, Box<dyn std::error::Error>> {
+/// Connections log statements slower than `slow_queries`
+pub fn establish_connection_pool(database_url: &str, slow_queries: SlowQueryThreshold) -> Result<DbPool, Box<dyn std::error::Error>> {
     let manager = ConnectionManager::<PgConnection>::new(database_url);
     let pool = r2d2::Pool::builder()
+        .connection_customizer(Box::new(SlowQueryLog(slow_queries)))
         .build(manager)
         .expect("Failed to create pool");
 
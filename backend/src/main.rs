@@ -7,8 +7,11 @@ mod models;
 mod middleware;
 mod services;
 mod controllers;
+#[cfg(test)]
+mod test_support;
 
 use axum::{
+    handler::Handler,
     routing::{get, post, put, delete},
     http::StatusCode,
     response::IntoResponse,
@@ -16,18 +19,44 @@ use axum::{
     middleware as axum_middleware,
 };
 use std::net::SocketAddr;
-use tracing::info;
+use tracing::{info, warn};
 use dotenvy::dotenv;
 use tower_http::cors::CorsLayer;
 use config::Config;
 use database::{DbPool, establish_connection_pool};
 use models::*;
-use middleware::auth::{auth_middleware_with_services, admin_auth_middleware_with_services};
+use middleware::auth::{auth_middleware_with_services, admin_auth_middleware_with_services, content_auth_middleware_with_services, optional_auth_middleware_with_services};
 // Rate limiting temporarily disabled due to API changes
 // use middleware::rate_limiting::{create_auth_rate_limiter, create_upload_rate_limiter};
 use middleware::security_headers::security_headers_middleware;
+use middleware::request_metrics::request_metrics_middleware;
+use middleware::upload_guard::upload_guard_middleware;
+use middleware::canonical_redirect::canonical_redirect_middleware;
+use middleware::idempotency::idempotency_middleware;
 
-use services::{SessionManager, SessionConfig};
+use services::image_alt::ENFORCE_IMAGE_ALT_SETTING;
+use controllers::navigation::DEFAULT_HEADER_NAV_SETTING;
+use services::comment_limits::{CommentLimits, COMMENT_MAX_LENGTH_SETTING, COMMENT_MAX_LINKS_SETTING, COMMENTS_REQUIRE_VERIFIED_EMAIL_SETTING, HOLD_FIRST_COMMENT_SETTING};
+use services::comment_sort::COMMENT_SORT_SETTING;
+use services::gravatar::{GRAVATAR_DEFAULT_SETTING, GRAVATAR_RATING_SETTING};
+use services::comment_rate_limit::{CommentRateLimits, COMMENT_RATE_PER_MINUTE_SETTING, COMMENT_RATE_PER_HOUR_SETTING};
+use services::timezones::{TIMEZONE_SETTING, DEFAULT_TIMEZONE};
+use services::homepage::{HomepageMode, HOMEPAGE_MODE_SETTING, HOMEPAGE_PAGE_ID_SETTING};
+use services::canonical_urls::{TrailingSlash, CANONICAL_TRAILING_SLASH_SETTING};
+use services::text_direction::{TextDirection, TEXT_DIRECTION_SETTING};
+use services::slow_query_log::SLOW_QUERY_THRESHOLD_SETTING;
+use services::data_retention::{RetentionPolicy, AUDIT_LOG_RETENTION_SETTING, SESSION_RETENTION_SETTING, TOKEN_RETENTION_SETTING};
+use services::word_filter::{WordFilterMode, COMMENT_BLOCKED_WORDS_SETTING, COMMENT_WORD_FILTER_MODE_SETTING};
+use services::template_schema::{UnknownKeys, UNKNOWN_KEYS_SETTING};
+use services::feed::{DEFAULT_FEED_ITEMS, FEED_ITEM_COUNT_SETTING, FEED_FULL_CONTENT_SETTING};
+use services::branding::{FAVICON_SETTING, SITE_LOGO_SETTING};
+use services::excerpts::{DEFAULT_EXCERPT_LENGTH, DEFAULT_READ_MORE_TEXT, EXCERPT_LENGTH_SETTING, READ_MORE_TEXT_SETTING};
+use services::captcha::{CAPTCHA_PROVIDER_SETTING, CAPTCHA_SITE_KEY_SETTING, CAPTCHA_SECRET_KEY_SETTING, CAPTCHA_LOGIN_FAILURES_SETTING, DEFAULT_LOGIN_FAILURES};
+use services::theme_presets::built_in_presets;
+use services::password_hashing::is_password_hash;
+use services::signup_policy::{ALLOW_PUBLIC_SIGNUP_SETTING, SIGNUP_INVITE_ONLY_SETTING, SIGNUP_MIN_FILL_SECONDS_SETTING, DEFAULT_MIN_FILL_SECONDS};
+use services::email_domains::{ALLOWED_EMAIL_DOMAINS_SETTING, BLOCKED_EMAIL_DOMAINS_SETTING};
+use services::{SessionManager, SessionConfig, SESSION_DURATION_SETTING, MAX_SESSIONS_SETTING, REFRESH_THRESHOLD_SETTING, BIND_TO_IP_SETTING, IDLE_TIMEOUT_SETTING, COOKIE_AUTH_SETTING};
 
 
 // Database connection pool state
@@ -40,6 +69,30 @@ pub struct AppServices {
     pub db_pool: Arc<DbPool>,
     pub session_manager: SessionManager,
     pub db_service: services::DbService,
+    pub preview_signer: services::PreviewSigner,
+    /// Signs draft share links; see `services::share_previews`
+    pub share_signer: services::SessionSigner,
+    /// Signs signup form tokens; see `services::signup_policy`
+    pub signup_form_signer: services::SessionSigner,
+    pub metrics: services::MetricsCollector,
+    /// Recent comment submissions, for per-user and per-IP rate limits
+    pub comment_rate_limiter: services::comment_rate_limit::CommentRateLimiter,
+    /// Recent failed logins, for deciding when login needs a CAPTCHA
+    pub login_failures: services::captcha::LoginFailureTracker,
+    /// Serialized `/api/public/bootstrap` payload, shared by public page loads
+    pub bootstrap_cache: services::bootstrap_cache::BootstrapCache,
+    /// Directory uploaded media is stored in, served as `/uploads`
+    pub upload_dir: std::path::PathBuf,
+    /// How new password hashes are made
+    pub password_hashing: services::password_hashing::PasswordHashing,
+    /// Default and largest page size of list endpoints
+    pub pagination: services::pagination::PaginationConfig,
+    /// Proxies whose forwarded-for headers give the client IP
+    pub trusted_proxies: services::client_ip::TrustedProxies,
+    /// Statements at least this slow are logged; shared with every pooled connection
+    pub slow_query_threshold: services::slow_query_log::SlowQueryThreshold,
+    /// Long-running tasks reported by the readiness check
+    pub background_tasks: Arc<Vec<(&'static str, tokio::task::JoinHandle<()>)>>,
 }
 
 // Re-export controller types for convenience
@@ -57,21 +110,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     std::env::set_var("RUST_LOG", &config.rust_log);
     tracing_subscriber::fmt::init();
 
+    // Make sure the upload directory exists before it is served
+    std::fs::create_dir_all(&config.upload_dir)
+        .map_err(|e| format!("Failed to create upload directory {}: {}", config.upload_dir.display(), e))?;
+    info!("Serving uploads from {}", config.upload_dir.display());
+
     // Initialize database connection pool
-    let pool = establish_connection_pool(&config.database_url)?;
+    // Off until the slow query setting is loaded below
+    let slow_query_threshold = services::slow_query_log::SlowQueryThreshold::default();
+    let pool = establish_connection_pool(&config.database_url, slow_query_threshold.clone())?;
     info!("Database connection pool established");
     
     // Store pool in state and initialize services
     let db_pool = Arc::new(pool);
     
     // Initialize session manager with custom config
+    // (duration, per-user limit and refresh threshold can be overridden via settings)
     let session_config = SessionConfig {
         session_duration_hours: 24,
-        cleanup_interval_minutes: 10, // More frequent cleanup for demo
         max_sessions_per_user: 3,
         enable_session_refresh: true,
         refresh_threshold_minutes: 30,
         enable_token_signing: true, // Enable HMAC-SHA256 token signing
+        idle_timeout_minutes: 0, // Disabled unless set in settings
+        bind_to_ip: false,
+        cookie_auth: false, // Enabled via settings
+        secure_cookies: !config.is_development(),
     };
     
     let session_manager = SessionManager::new_with_signing(
@@ -80,16 +144,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         &config.session_secret
     );
     
-    // Start background session cleanup
-    let _cleanup_task = session_manager.clone().start_background_cleanup().await;
-    info!("Session cleanup background task started");
+    // Start the background purge of expired sessions and stale tokens
+    let retention_task = services::data_retention::start_background_job(db_pool.clone());
+    // and the publishing of scheduled posts once they are due
+    let publish_task = services::post_schedule::start_background_job(db_pool.clone());
     
-    let db_service = services::DbService::new(db_pool.clone());
+    let metrics = services::MetricsCollector::new();
+    let db_service = services::DbService::new(db_pool.clone(), metrics.clone());
     
     let app_services = AppServices {
         db_pool: db_pool.clone(),
         session_manager,
         db_service,
+        preview_signer: services::PreviewSigner::new(&config.session_secret),
+        share_signer: services::share_previews::share_signer(&config.session_secret),
+        signup_form_signer: services::signup_policy::form_signer(&config.session_secret),
+        metrics: metrics.clone(),
+        comment_rate_limiter: services::comment_rate_limit::CommentRateLimiter::new(),
+        login_failures: services::captcha::LoginFailureTracker::default(),
+        bootstrap_cache: services::bootstrap_cache::BootstrapCache::new(
+            services::bootstrap_cache::BOOTSTRAP_CACHE_TTL,
+            metrics.clone(),
+        ),
+        upload_dir: config.upload_dir.clone(),
+        password_hashing: config.password_hashing.clone(),
+        pagination: config.pagination,
+        trusted_proxies: config.trusted_proxies.clone(),
+        slow_query_threshold,
+        background_tasks: Arc::new(vec![
+            ("data_retention", retention_task),
+            ("post_schedule", publish_task),
+        ]),
     };
     
     // Initialize with demo data in database
@@ -99,8 +184,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Create demo user if none exists, or update existing admin user password
         let users = User::list(&mut conn)?;
         if users.is_empty() {
-            let hashed_password = bcrypt::hash("admin", bcrypt::DEFAULT_COST)
-                .map_err(|e| format!("Password hashing failed: {}", e))?;
+            let hashed_password = config.password_hashing.hash(config.initial_admin_password()).map_err(|e| e.to_string())?;
             let demo_user = NewUser {
                 username: "admin".to_string(),
                 password: hashed_password,
@@ -112,12 +196,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 email_verification_expires_at: None,
             };
             let _user = User::create(&mut conn, demo_user)?;
-            info!("Created demo user: admin with password 'admin'");
+            if config.admin_password.is_some() {
+                info!("Created admin user with the password from ADMIN_PASSWORD");
+            } else {
+                info!("Created demo user: admin with password 'admin'");
+            }
         } else if let Some(admin_user) = users.iter().find(|u| u.username == "admin") {
-            // Check if password is already hashed (bcrypt hashes start with $2a$, $2b$, or $2y$)
-            if !admin_user.password.starts_with("$2") {
-                let hashed_password = bcrypt::hash("admin", bcrypt::DEFAULT_COST)
-                    .map_err(|e| format!("Password hashing failed: {}", e))?;
+            // Hash the password if it was stored in plain text
+            if !is_password_hash(&admin_user.password) {
+                let hashed_password = config.password_hashing.hash(config.initial_admin_password()).map_err(|e| e.to_string())?;
                 let update_user = UpdateUser {
                     username: None,
                     password: Some(hashed_password),
@@ -127,6 +214,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     email_verified: None,
                     email_verification_token: None,
                     email_verification_expires_at: None,
+                    must_change_password: None,
                 };
                 let _updated_user = User::update(&mut conn, admin_user.id, update_user)?;
                 info!("Updated admin user password to properly hashed version");
@@ -156,6 +244,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 target: Some("_self".to_string()),
                 mobile_visible: true,
                 description: Some("Homepage link".to_string()),
+                visible_from: None,
+                visible_until: None,
             };
             let _home = Navigation::create(&mut conn, home_nav)?;
             info!("Created default navigation item: Home");
@@ -172,24 +262,72 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 target: Some("_self".to_string()),
                 mobile_visible: true,
                 description: Some("View all posts".to_string()),
+                visible_from: None,
+                visible_until: None,
             };
             let _posts = Navigation::create(&mut conn, posts_nav)?;
             info!("Created default navigation item: Posts");
         }
         
-        // Create default site settings if they don't exist
-        use crate::models::setting::*;
-        
-        // Admin button visibility setting
-        if Setting::find_by_key(&mut conn, "admin_button_visible")?.is_none() {
-            let admin_button_setting = NewSetting {
-                setting_key: "admin_button_visible".to_string(),
-                setting_value: Some("true".to_string()),
-                setting_type: "site".to_string(),
-                description: Some("Show admin button in public navigation".to_string()),
-            };
-            let _setting = Setting::create(&mut conn, admin_button_setting)?;
-            info!("Created default setting: admin_button_visible = true");
+        // Create default site settings if they don't exist. The site key of
+        // the CAPTCHA is public so the frontend can render the widget; its
+        // secret is a system setting, which the public settings endpoint
+        // never returns. The comment word filter and the signup email domain
+        // lists are too, so spammers can't read which words or domains to avoid
+        let comment_defaults = CommentLimits::default();
+        let comment_rate_defaults = CommentRateLimits::default();
+        let retention_defaults = RetentionPolicy::default();
+        let session_defaults = app_services.session_manager.config();
+        let default_settings: Vec<(&str, String, &str, &str)> = vec![
+            ("admin_button_visible", "true".to_string(), "site", "Show admin button in public navigation"),
+            (ENFORCE_IMAGE_ALT_SETTING, "false".to_string(), "site", "Require alt text on every image before a post or page can be published"),
+            (ALLOW_PUBLIC_SIGNUP_SETTING, "true".to_string(), "site", "Let visitors create accounts from the signup page"),
+            (SIGNUP_INVITE_ONLY_SETTING, "false".to_string(), "site", "Require an admin-issued invite to sign up"),
+            (ALLOWED_EMAIL_DOMAINS_SETTING, String::new(), "system", "Only these email domains can sign up (comma-separated; *.example.com matches subdomains; empty allows all)"),
+            (BLOCKED_EMAIL_DOMAINS_SETTING, String::new(), "system", "Email domains that cannot sign up (comma-separated; *.example.com matches subdomains)"),
+            (SIGNUP_MIN_FILL_SECONDS_SETTING, DEFAULT_MIN_FILL_SECONDS.to_string(), "site", "Reject signups submitted faster than this many seconds after the form opened (0 disables)"),
+            (DEFAULT_HEADER_NAV_SETTING, "true".to_string(), "site", "Show Home and published pages in the header when it has no menu items"),
+            ("footer_powered_by_visible", "true".to_string(), "site", "Show the footer template's additional (\"powered by\") line"),
+            (TIMEZONE_SETTING, DEFAULT_TIMEZONE.to_string(), "site", "Time zone for displayed dates (an IANA name like Europe/Berlin, or \"viewer\" for each visitor's own)"),
+            (HOMEPAGE_MODE_SETTING, HomepageMode::default().as_str().to_string(), "site", "What the public home shows: \"posts\" for the post list or \"page\" for a single page"),
+            (HOMEPAGE_PAGE_ID_SETTING, String::new(), "site", "ID of the published page shown as the home when the homepage mode is \"page\""),
+            (CANONICAL_TRAILING_SLASH_SETTING, TrailingSlash::default().as_str().to_string(), "site", "Whether public post and page URLs end in a slash: \"remove\" or \"add\"; other spellings redirect"),
+            (SLOW_QUERY_THRESHOLD_SETTING, "0".to_string(), "system", "Log database statements taking at least this many milliseconds (0 disables)"),
+            (SESSION_RETENTION_SETTING, retention_defaults.expired_sessions_days.to_string(), "system", "Days expired sessions are kept before being purged (0 purges them on the next run)"),
+            (TOKEN_RETENTION_SETTING, retention_defaults.stale_tokens_days.to_string(), "system", "Days used or expired verification tokens and invites are kept before being purged"),
+            (AUDIT_LOG_RETENTION_SETTING, retention_defaults.audit_log_days.to_string(), "system", "Days audit log entries are kept before being purged"),
+            (UNKNOWN_KEYS_SETTING, UnknownKeys::default().as_str().to_string(), "system", "What to do with component template data keys not in the schema: warn or reject"),
+            (CAPTCHA_PROVIDER_SETTING, "none".to_string(), "site", "CAPTCHA provider for signup, login and comments: none, recaptcha or hcaptcha"),
+            (CAPTCHA_SITE_KEY_SETTING, String::new(), "site", "Public site key from the CAPTCHA provider"),
+            (CAPTCHA_SECRET_KEY_SETTING, String::new(), "system", "Secret key from the CAPTCHA provider, used to verify tokens"),
+            (CAPTCHA_LOGIN_FAILURES_SETTING, DEFAULT_LOGIN_FAILURES.to_string(), "site", "Failed logins from a username or IP address before login needs a CAPTCHA (0 always requires it)"),
+            (FEED_ITEM_COUNT_SETTING, DEFAULT_FEED_ITEMS.to_string(), "site", "Number of newest posts in the RSS feed"),
+            (FEED_FULL_CONTENT_SETTING, "false".to_string(), "site", "Include each post's full content in the RSS feed, not just an excerpt"),
+            (EXCERPT_LENGTH_SETTING, DEFAULT_EXCERPT_LENGTH.to_string(), "site", "Characters of text in post excerpts on post lists"),
+            (READ_MORE_TEXT_SETTING, DEFAULT_READ_MORE_TEXT.to_string(), "site", "Label of the link from a post excerpt to the full post"),
+            (SITE_LOGO_SETTING, String::new(), "site", "Media id of the image shown in the header instead of the site title; empty for the title"),
+            (FAVICON_SETTING, String::new(), "site", "Media id of the site's favicon; empty for none"),
+            (TEXT_DIRECTION_SETTING, TextDirection::default().as_str().to_string(), "site", "Reading direction of the public site and admin: ltr, rtl or auto"),
+            (COMMENT_MAX_LENGTH_SETTING, comment_defaults.max_length.to_string(), "site", "Maximum comment length in characters"),
+            (COMMENT_MAX_LINKS_SETTING, comment_defaults.max_links.to_string(), "site", "Maximum number of links allowed in a comment (0 disallows links)"),
+            (COMMENTS_REQUIRE_VERIFIED_EMAIL_SETTING, comment_defaults.require_verified_email.to_string(), "site", "Only users with a verified email address can comment (admins and editors are exempt)"),
+            (HOLD_FIRST_COMMENT_SETTING, comment_defaults.hold_first_comment.to_string(), "site", "Hold comments for moderation until the author has one approved comment (admins and editors are exempt)"),
+            (COMMENT_SORT_SETTING, comment_defaults.default_sort.as_str().to_string(), "site", "Default order of comment threads: oldest or newest first"),
+            (GRAVATAR_DEFAULT_SETTING, comment_defaults.gravatar.default_image.to_string(), "site", "Gravatar image for commenters without one: mp, identicon, monsterid, wavatar, retro, robohash, blank or 404"),
+            (GRAVATAR_RATING_SETTING, comment_defaults.gravatar.rating.to_string(), "site", "Most explicit Gravatar rating to show: g, pg, r or x"),
+            (COMMENT_RATE_PER_MINUTE_SETTING, comment_rate_defaults.per_minute.to_string(), "site", "Maximum comments per minute from one user or IP address (0 disables)"),
+            (COMMENT_RATE_PER_HOUR_SETTING, comment_rate_defaults.per_hour.to_string(), "site", "Maximum comments per hour from one user or IP address (0 disables)"),
+            (COMMENT_BLOCKED_WORDS_SETTING, String::new(), "system", "Words or phrases not allowed in comments (comma-separated, whole words, case-insensitive)"),
+            (COMMENT_WORD_FILTER_MODE_SETTING, WordFilterMode::default().as_str().to_string(), "system", "What to do with comments containing a blocked word: reject or mask"),
+            (SESSION_DURATION_SETTING, session_defaults.session_duration_hours.to_string(), "system", "How long a session lasts before it expires (hours)"),
+            (MAX_SESSIONS_SETTING, session_defaults.max_sessions_per_user.to_string(), "system", "Maximum concurrent sessions per user"),
+            (REFRESH_THRESHOLD_SETTING, session_defaults.refresh_threshold_minutes.to_string(), "system", "Refresh a session when less than this many minutes remain"),
+            (IDLE_TIMEOUT_SETTING, session_defaults.idle_timeout_minutes.to_string(), "system", "Log out sessions with no activity for this many minutes (0 disables)"),
+            (BIND_TO_IP_SETTING, session_defaults.bind_to_ip.to_string(), "system", "Only accept a session from the IP it was created on (logs out users whose network changes, e.g. on mobile)"),
+            (COOKIE_AUTH_SETTING, session_defaults.cookie_auth.to_string(), "system", "Also set the session in an HttpOnly cookie at login and accept it in place of a bearer token"),
+        ];
+        for (key, value, setting_type, description) in &default_settings {
+            seed_default(&mut conn, key, value, setting_type, description)?;
         }
 
         // Create default menu areas if they don't exist
@@ -295,7 +433,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         "padding": "3rem 0",
                         "navigation_layout": "horizontal",
                         "copyright_position": "center",
-                        "copyright_text": "© 2024 My Rust CMS",
+                        "copyright_text": "© {year} {site_title}",
                         "additional_text": "Built with Rust & Yew"
                     }),
                     serde_json::json!({
@@ -391,93 +529,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
-        // Create master templates (templates table) if none exist
-        {
-            use crate::schema::templates;
-            let existing_master_templates = templates::table.load::<Template>(&mut conn)?;
-            if existing_master_templates.is_empty() {
-                // Build Default template layout JSON (aligned with current defaults)
-                let default_layout = serde_json::json!({
-                    "menu_areas": [
-                        {"area_name": "header", "display_name": "Header Menu", "is_active": true, "settings": {"layout": "horizontal", "background": "#ffffff"}},
-                        {"area_name": "footer", "display_name": "Footer Menu", "is_active": true, "settings": {"style": "simple", "background": "#111111", "text_color": "#cccccc"}},
-                        {"area_name": "floating", "display_name": "Floating Menu", "is_active": false, "settings": {"position": "fixed-right"}}
-                    ],
-                    "component_templates": [
-                        {"component_type": "header", "template_data": {"position": "sticky", "height": "110px", "background_color": "#000000", "text_color": "#ffffff", "text_hover_color": "#f7fafc", "nav_hover_color": "#f7fafc", "nav_underline_color": "#ffffff", "nav_underline_thickness": "2px", "nav_underline_animation": "none", "navigation_layout": "horizontal", "logo_type": "text", "logo_size": "1.85rem", "mobile_menu": "hamburger", "mobile_breakpoint": "768px"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "contained", "max_width": "1200px", "is_active": true},
-                        {"component_type": "footer", "template_data": {"style": "simple", "padding": "3rem 0", "navigation_layout": "horizontal", "copyright_position": "center", "copyright_text": "© 2024 My Rust CMS", "additional_text": "Built with Rust & Yew"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "full", "is_active": true},
-                        {"component_type": "sidebar", "template_data": {"position": "right", "width": "300px", "sticky": true, "mobile_display": "hidden", "mobile_breakpoint": "768px", "sections": ["navigation", "recent_posts"]}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "fixed", "max_width": "300px", "is_active": false},
-                        {"component_type": "modal", "template_data": {"backdrop": "blur", "position": "center", "animation": "fade", "max_width": "600px", "z_index": 1000}, "breakpoints": {"mobile": "95%", "tablet": "80%", "desktop": "600px"}, "width_setting": "responsive", "max_width": "600px", "is_active": true},
-                        {"component_type": "main_container", "template_data": {"width_type": "fixed", "max_width": "1200px", "padding": "1rem", "grid_system": "css_grid", "responsive": true}, "breakpoints": {"mobile": "100%", "tablet": "90%", "desktop": "1200px"}, "width_setting": "fixed", "max_width": "1200px", "is_active": true}
-                    ],
-                    "container_settings": {
-                        "background_type": "none",
-                        "background_color": "#ffffff",
-                        "gradient_from": "#ffffff",
-                        "gradient_to": "#ffffff",
-                        "gradient_angle": "180deg",
-                        "overlay_color": "#000000",
-                        "overlay_opacity": "0.3",
-                        "border_radius": "0px",
-                        "border_width": "0px",
-                        "border_color": "#000000",
-                        "box_shadow": "none",
-                        "animation": "none",
-                        "width_type": "fixed",
-                        "max_width": "1200px",
-                        "horizontal_padding": "1rem"
-                    }
-                }).to_string();
-
-                // Build Acid Mode template layout JSON
-                let acid_layout = serde_json::json!({
-                    "menu_areas": [
-                        {"area_name": "header", "display_name": "Header Menu", "is_active": true, "settings": {"layout": "centered", "background": "linear-gradient(135deg, #ff00cc 0%, #3333ff 100%)", "text_color": "#ffffff"}},
-                        {"area_name": "footer", "display_name": "Footer Menu", "is_active": true, "settings": {"style": "multi-column", "background": "linear-gradient(180deg, #111111 0%, #000000 100%)", "text_color": "#66ffcc"}},
-                        {"area_name": "floating", "display_name": "Floating Menu", "is_active": true, "settings": {"position": "fixed-right"}}
-                    ],
-                    "component_templates": [
-                        {"component_type": "header", "template_data": {"position": "sticky", "height": "88px", "background": "linear-gradient(90deg, #ff0066, #ffcc00, #33ff99)", "navigation_layout": "split", "logo_type": "icon", "logo_size": "2rem", "hover_effect": "scale", "neon_glow": "0 0 20px #33ff99", "text_color": "#ffffff", "text_hover_color": "#e2e8f0", "nav_hover_color": "#a5b4fc", "nav_underline_color": "linear-gradient(90deg, #ff0066, #ffcc00, #33ff99)", "nav_underline_thickness": "3px", "nav_underline_animation": "underlineShimmer 3s linear infinite"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1400px"}, "width_setting": "fluid", "is_active": true},
-                        {"component_type": "footer", "template_data": {"style": "multi-column", "padding": "4rem 0", "navigation_layout": "grid", "link_spacing": "1rem", "separator": "dot", "background": "linear-gradient(45deg, #111111, #222244)", "text_glow": "0 0 10px #66ffcc", "text_color": "#ffffff"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1400px"}, "width_setting": "full", "is_active": true},
-                        {"component_type": "sidebar", "template_data": {"position": "both", "width": "320px", "sticky": true, "mobile_display": "drawer", "sections": ["navigation", "recent_posts", "categories", "archives"], "background": "linear-gradient(180deg, rgba(255,0,102,0.1), rgba(51,255,153,0.1))", "border": "2px solid", "border_image": "linear-gradient(90deg, #ff0066, #33ff99) 1"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1400px"}, "width_setting": "fixed", "max_width": "320px", "is_active": true},
-                        {"component_type": "modal", "template_data": {"backdrop": "blur", "position": "center", "animation": "scale", "max_width": "800px", "z_index": 1200, "backdrop_opacity": 70, "backdrop_gradient": "radial-gradient(circle at center, rgba(255,0,102,0.4), rgba(0,0,0,0.8))"}, "breakpoints": {"mobile": "95%", "tablet": "85%", "desktop": "800px"}, "width_setting": "responsive", "max_width": "800px", "is_active": true},
-                        {"component_type": "main_container", "template_data": {"width_type": "hybrid", "max_width": "1280px", "padding": "1.5rem", "responsive": true, "background_type": "gradient", "gradient_from": "#0f0f3d", "gradient_to": "#000000", "gradient_angle": "135deg", "gradient_animate": true, "background_animation": "gradientShift 20s ease infinite", "card_background": "#0f1629", "grid_gap": "24px", "card_radius": "12px", "card_shadow": "0 2px 12px rgba(0,0,0,0.35)", "title_color": "#e2e8f0", "meta_color": "#94a3b8", "link_color": "#22d3ee"}, "breakpoints": {"mobile": "100%", "tablet": "95%", "desktop": "1280px"}, "width_setting": "hybrid", "max_width": "1280px", "is_active": true}
-                    ],
-                    "container_settings": {
-                        "background_type": "gradient",
-                        "background_color": "#0b0b0b",
-                        "gradient_from": "#0f0f3d",
-                        "gradient_to": "#000000",
-                        "gradient_angle": "135deg",
-                        "overlay_color": "#00ffcc",
-                        "overlay_opacity": "0.12",
-                        "border_radius": "16px",
-                        "border_width": "2px",
-                        "border_color": "#33ff99",
-                        "box_shadow": "0 10px 40px rgba(51,255,153,0.25)",
-                        "animation": "fade-in",
-                        "width_type": "hybrid",
-                        "max_width": "1280px",
-                        "horizontal_padding": "2rem"
-                    }
-                }).to_string();
-
-                let new_default = NewTemplate { name: "Default".to_string(), layout: default_layout };
-                let new_acid = NewTemplate { name: "Acid Mode".to_string(), layout: acid_layout };
-
-                diesel::insert_into(templates::table)
-                    .values(&new_default)
-                    .execute(&mut conn)?;
-                diesel::insert_into(templates::table)
-                    .values(&new_acid)
-                    .execute(&mut conn)?;
-                info!("Seeded master templates: Default, Acid Mode");
+        // Seed any built-in master templates (theme presets) that are missing
+        for (name, layout) in built_in_presets() {
+            if Template::find_by_name(&mut conn, name)?.is_none() {
+                let new_template = NewTemplate { name: name.to_string(), layout: layout.to_string() };
+                Template::create(&mut conn, new_template)?;
+                info!("Seeded master template: {}", name);
             }
         }
     }
 
+    // Apply any session policy overrides stored in settings
+    if let Err(e) = app_services.session_manager.reload_config() {
+        warn!("Failed to load session settings, using defaults: {}", e);
+    }
+    let slow_query_setting = app_services.db_pool.get()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| app_services.slow_query_threshold.load(&mut conn).map_err(|e| e.to_string()));
+    if let Err(e) = slow_query_setting {
+        warn!("Failed to load the slow query threshold, leaving the log off: {}", e);
+    }
+
     // Configure CORS with proper security
-    let cors = if config.is_development() {
+    let cors = if config.cors_allowed_origins.is_empty() {
         // Development CORS - more permissive
         CorsLayer::new()
             .allow_origin([
@@ -487,13 +561,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "http://127.0.0.1:8080".parse().unwrap(),
             ])
     } else {
-        // Production CORS - strict origins only
-        // Replace with your actual production frontend domain
-        CorsLayer::new()
-            .allow_origin([
-                "https://yourdomain.com".parse().unwrap(),
-                "https://www.yourdomain.com".parse().unwrap(),
-            ])
+        // Strict origins from CORS_ALLOWED_ORIGINS (validated when config loads)
+        let origins = config.cors_allowed_origins.iter()
+            .map(|origin| origin.parse::<axum::http::HeaderValue>())
+            .collect::<Result<Vec<_>, _>>()?;
+        info!("CORS allowed origins: {}", config.cors_allowed_origins.join(", "));
+        CorsLayer::new().allow_origin(origins)
     }
         .allow_methods([
             axum::http::Method::GET,
@@ -506,18 +579,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             axum::http::header::AUTHORIZATION,
             axum::http::header::CONTENT_TYPE,
             axum::http::header::ACCEPT,
+            axum::http::HeaderName::from_static(services::session_cookie::CSRF_HEADER),
+            axum::http::HeaderName::from_static(services::idempotency::IDEMPOTENCY_KEY_HEADER),
+        ])
+        // Pagination headers on list endpoints, the wait after a 429 and
+        // the file name of downloads and replayed idempotent responses
+        .expose_headers([
+            axum::http::HeaderName::from_static(services::pagination::TOTAL_COUNT_HEADER),
+            axum::http::header::LINK,
+            axum::http::header::RETRY_AFTER,
+            axum::http::header::CONTENT_DISPOSITION,
+            axum::http::HeaderName::from_static(services::idempotency::REPLAYED_HEADER),
         ])
         .allow_credentials(true);
 
     // Public routes (no authentication required)
     let public_routes = Router::new()
         .route("/", get(root))
-        .route("/health", get(health))
+        .route("/health", get(controllers::health::liveness))
+        .route("/health/live", get(controllers::health::liveness))
+        .route("/health/ready", get(controllers::health::readiness))
         .route("/api/public/system/settings", get(controllers::system::get_public_settings))
+        .route("/api/public/bootstrap", get(controllers::bootstrap::get_public_bootstrap))
         .route("/api/posts", get(controllers::posts::get_posts))
         .route("/api/auth/login", post(controllers::auth::login))
         .route("/api/auth/signup", post(controllers::auth::signup))
+        .route("/api/auth/signup-policy", get(controllers::auth::get_signup_policy))
         .route("/api/auth/verify-email", post(controllers::auth::verify_email))
+        .route("/api/auth/resend-verification", post(controllers::auth::resend_verification))
         // TODO: Re-enable rate limiting when API is stabilized
         // .layer(create_auth_rate_limiter())
         .route("/api/posts/:id", get(controllers::posts::get_post))
@@ -529,38 +618,81 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/pages", get(controllers::pages::get_pages))
         .route("/api/pages/:id", get(controllers::pages::get_page))
         .route("/api/pages/slug/:slug", get(controllers::pages::get_page_by_slug))
+        .route("/api/homepage", get(controllers::pages::get_homepage))
+        .route("/api/preview/posts/:id", get(controllers::preview::get_post_preview))
+        .route("/api/preview/pages/:slug", get(controllers::preview::get_page_preview))
+        .route("/api/preview/posts/:id/shared", get(controllers::preview::get_shared_post_preview))
         .route("/api/comments/public", get(controllers::comments::get_post_comments))
+        .route("/api/comments/limits", get(controllers::comments::get_comment_limits))
+        .route("/api/feed.xml", get(controllers::feed::get_feed))
+        .route("/sitemap.xml", get(controllers::sitemap::get_sitemap))
+        .route("/sitemap-posts-:number", get(controllers::sitemap::get_posts_sitemap))
+        .route("/sitemap-pages-:number", get(controllers::sitemap::get_pages_sitemap))
         .route("/api/test", get(test_endpoint));
 
+    // Public routes whose results depend on who is signed in
+    let optional_auth_routes = Router::new()
+        .route("/api/search", get(controllers::search::search))
+        .layer(axum_middleware::from_fn_with_state(app_services.clone(), optional_auth_middleware_with_services));
+
+    // Create endpoints that honor an Idempotency-Key header; layered on the
+    // handler so it runs after the route's authentication
+    let idempotent = || axum_middleware::from_fn_with_state(app_services.clone(), idempotency_middleware);
+
     // Authenticated routes (requires valid session)
     let auth_routes = Router::new()
         .route("/api/auth/logout", post(controllers::auth::logout))
-        .route("/api/auth/me", get(controllers::auth::get_current_user))
+        .route("/api/auth/me", get(controllers::auth::get_current_user).put(controllers::auth::update_current_user))
+        .route("/api/auth/change-password", post(controllers::auth::change_password))
         .route("/api/auth/sessions", get(controllers::sessions::get_user_sessions))
         .route("/api/auth/sessions/logout-all", post(controllers::sessions::logout_all_sessions))
-        .route("/api/comments/create", post(controllers::comments::create_public_comment))
+        .route("/api/comments/create", post(controllers::comments::create_public_comment.layer(idempotent())))
         .layer(axum_middleware::from_fn_with_state(app_services.clone(), auth_middleware_with_services));
 
-    // Admin-only routes (requires admin role)
-    let admin_routes = Router::new()
-        .route("/api/users", get(controllers::users::get_users).post(controllers::users::create_user))
-        .route("/api/users/:id", put(controllers::users::update_user).delete(controllers::users::delete_user))
-        .route("/api/users/:id/promote", put(controllers::users::promote_user))
-        .route("/api/posts", post(controllers::posts::create_post))
+    // Content routes (requires the manage_content permission: admins and editors)
+    let content_routes = Router::new()
+        .route("/api/posts", post(controllers::posts::create_post.layer(idempotent())))
         .route("/api/posts/:id", put(controllers::posts::update_post).delete(controllers::posts::delete_post))
-        .route("/api/comments", get(controllers::comments::get_comments).post(controllers::comments::create_comment))
+        .route("/api/posts/:id/duplicate", post(controllers::posts::duplicate_post))
+        .route("/api/posts/:id/export.md", get(controllers::posts::export_post_markdown))
+        .route("/api/posts/import", post(controllers::posts::import_posts_markdown))
+        .route("/api/admin/posts", get(controllers::posts::get_admin_posts))
+        .route("/api/admin/posts/:id", get(controllers::posts::get_admin_post))
+        .route("/api/posts/calendar", get(controllers::posts::get_post_calendar))
+        .route("/api/preview-links", post(controllers::preview::create_preview_link))
+        .route("/api/posts/:id/share-preview", post(controllers::preview::create_share_preview))
+        .route("/api/comments", get(controllers::comments::get_comments).post(controllers::comments::create_comment.layer(idempotent())))
+        .route("/api/comments/bulk", post(controllers::comments::bulk_moderate_comments))
         .route("/api/comments/:id", put(controllers::comments::update_comment).delete(controllers::comments::delete_comment))
         .route("/api/media", get(controllers::media::get_media))
         .route("/api/media/upload", post(controllers::media::upload_media))
         // TODO: Re-enable upload rate limiting when API is stabilized
         // .layer(create_upload_rate_limiter())
         .route("/api/media/:id", delete(controllers::media::delete_media))
+        .route("/api/media/bulk-delete", post(controllers::media::bulk_delete_media))
+        .route("/api/pages", post(controllers::pages::create_page))
+        .route("/api/admin/pages", get(controllers::pages::get_admin_pages))
+        .route("/api/pages/:id", put(controllers::pages::update_page).delete(controllers::pages::delete_page))
+        .layer(axum_middleware::from_fn_with_state(app_services.clone(), content_auth_middleware_with_services));
+
+    // Admin-only routes (requires admin role)
+    let admin_routes = Router::new()
+        .route("/api/users", get(controllers::users::get_users).post(controllers::users::create_user.layer(idempotent())))
+        .route("/api/users/:id", put(controllers::users::update_user).delete(controllers::users::delete_user))
+        .route("/api/users/:id/promote", put(controllers::users::promote_user))
         .route("/api/sessions", get(controllers::admin::get_sessions))
         .route("/api/settings", get(controllers::admin::get_settings))
         .route("/api/templates", get(controllers::admin::get_templates))
+        .route("/api/templates/reset-default", post(controllers::admin::reset_default_template))
+        .route("/api/templates/:id/apply", post(controllers::admin::apply_template))
+        .route("/api/color-schemes", get(controllers::color_schemes::list_color_schemes))
+        .route("/api/color-schemes/:name", get(controllers::color_schemes::get_color_scheme).put(controllers::color_schemes::save_color_scheme).delete(controllers::color_schemes::delete_color_scheme))
+        .route("/api/color-schemes/:name/rename", post(controllers::color_schemes::rename_color_scheme))
+        .route("/api/color-schemes/:name/activate", post(controllers::color_schemes::activate_color_scheme))
         .route("/api/components", get(controllers::admin::get_components))
         .route("/api/navigation", post(controllers::navigation::create_navigation_item))
         .route("/api/navigation/:id", put(controllers::navigation::update_navigation_item).delete(controllers::navigation::delete_navigation_item))
+        .route("/api/navigation/area/:area/all", get(controllers::navigation::get_admin_navigation_by_area))
         // Enhanced navigation management routes
         .route("/api/menu-areas", get(controllers::navigation::get_menu_areas))
         .route("/api/menu-areas/:name", put(controllers::navigation::update_menu_area))
@@ -568,15 +700,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/menu-templates/type/:template_type", get(controllers::navigation::get_menu_templates_by_type))
         .route("/api/component-templates", post(controllers::navigation::create_component_template))
         .route("/api/component-templates/admin", get(controllers::navigation::get_all_component_templates_admin))
+        .route("/api/component-templates/schema", get(controllers::navigation::get_component_template_schema))
         .route("/api/component-templates/:id", put(controllers::navigation::update_component_template))
         .route("/api/component-templates/:id/toggle", post(controllers::navigation::toggle_component_template))
         .route("/api/component-templates/type/:component_type", get(controllers::navigation::get_component_templates_by_type))
-        .route("/api/pages", post(controllers::pages::create_page))
-        .route("/api/pages/:id", put(controllers::pages::update_page).delete(controllers::pages::delete_page))
         .route("/api/stats", get(controllers::admin::get_stats))
         .route("/api/performance", get(controllers::admin::get_performance_metrics))
         .route("/api/admin/sessions", get(controllers::sessions::get_all_session_stats))
+        .route("/api/admin/invites", get(controllers::invites::list_invites).post(controllers::invites::create_invite))
         .route("/api/admin/sessions/cleanup", post(controllers::sessions::manual_session_cleanup))
+        .route("/api/admin/sessions/force-logout-all", post(controllers::sessions::force_logout_all_sessions))
         .route("/api/admin/users/:id/sessions", get(controllers::sessions::get_admin_user_sessions))
         .route("/api/admin/users/:id/force-logout", post(controllers::sessions::force_logout_user))
         // System management routes
@@ -587,19 +720,63 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/system/backups", get(controllers::system::list_backups))
         .route("/api/system/backup/:id/restore", post(controllers::system::restore_backup))
         .route("/api/system/snapshot", get(controllers::system::get_data_snapshot))
+        .route("/api/system/media/cleanup", post(controllers::media::cleanup_media))
+        .route("/api/system/retention/run", post(controllers::system::run_data_retention))
         .layer(axum_middleware::from_fn_with_state(app_services.clone(), admin_auth_middleware_with_services));
 
+    // Uploaded files, behind a guard that only lets stored file names through
+    let upload_routes = Router::new()
+        .nest_service("/uploads", tower_http::services::ServeDir::new(&config.upload_dir))
+        .route_layer(axum_middleware::from_fn_with_state(
+            config.upload_dir.clone(),
+            upload_guard_middleware
+        ));
+
+    // Read-only GraphQL, when built with the `graphql` feature
+    #[cfg(feature = "graphql")]
+    let public_routes = public_routes.merge(
+        Router::new()
+            .route("/api/graphql", post(controllers::graphql::graphql_handler))
+            .with_state(controllers::graphql::build_schema(app_services.clone()))
+    );
+
+    // Server-rendered public posts and pages, when built with the `ssr`
+    // feature and the frontend build is there to render into
+    #[cfg(feature = "ssr")]
+    let public_routes = match controllers::ssr::load_shell(&config.ssr_index_html) {
+        Some(shell) => public_routes.merge(
+            Router::new()
+                .route("/post/:id", get(controllers::ssr::render_post))
+                .route("/post/:id/", get(controllers::ssr::render_post))
+                .route("/page/:slug", get(controllers::ssr::render_page))
+                .route("/page/:slug/", get(controllers::ssr::render_page))
+                .with_state(controllers::ssr::SsrState { services: app_services.clone(), shell })
+        ),
+        None => public_routes,
+    };
+
     // Combine all routes
     let app = Router::new()
         .merge(public_routes)
+        .merge(optional_auth_routes)
         .merge(auth_routes)
+        .merge(content_routes)
         .merge(admin_routes)
-        .nest_service("/uploads", tower_http::services::ServeDir::new("uploads"))
+        .merge(upload_routes)
         .with_state(app_services.clone())
+        // Ahead of routing, so non-canonical content paths never reach a 404
+        .layer(axum_middleware::from_fn_with_state(
+            app_services.clone(),
+            canonical_redirect_middleware
+        ))
         .layer(cors)
         .layer(axum_middleware::from_fn_with_state(
             config.clone(),
             security_headers_middleware
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            metrics,
+            request_metrics_middleware
         ));
 
     // Run the server
@@ -609,17 +786,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Environment: {}", config.rust_env);
     
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // Peer addresses are needed to resolve client IPs for session binding
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
     
     Ok(())
 }
 
-async fn root() -> impl IntoResponse {
-    "My Rust CMS Backend is running!"
+/// Create a setting with its default value unless it already exists
+fn seed_default(conn: &mut diesel::PgConnection, key: &str, value: &str, setting_type: &str, description: &str) -> Result<(), diesel::result::Error> {
+    if Setting::find_by_key(conn, key)?.is_none() {
+        Setting::create(conn, NewSetting {
+            setting_key: key.to_string(),
+            setting_value: Some(value.to_string()),
+            setting_type: setting_type.to_string(),
+            description: Some(description.to_string()),
+        })?;
+        info!("Created default setting: {} = {}", key, value);
+    }
+    Ok(())
 }
 
-async fn health() -> impl IntoResponse {
-    (StatusCode::OK, "OK")
+async fn root() -> impl IntoResponse {
+    "My Rust CMS Backend is running!"
 }
 
 
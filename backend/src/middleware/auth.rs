@@ -1,5 +1,6 @@
 use axum::{
     extract::{Request, State},
+    http::{header, Method},
     middleware::Next,
     response::Response,
 };
@@ -7,7 +8,8 @@ use axum::{
 use crate::{
     database::DbPool,
     models::{Session, User},
-    middleware::errors::{AppError, ApiResult},
+    middleware::errors::AppError,
+    services::{client_ip::ClientIp, permissions::{role_has_permission, Permission}, session_cookie::{request_token, session_cookie, sets_session_cookie, TokenSource}},
     AppServices,
 };
 use std::sync::Arc;
@@ -137,92 +139,171 @@ pub async fn admin_auth_middleware(
     Ok(next.run(req).await)
 }
 
-// Helper function to extract authenticated user from request
-pub fn get_authenticated_user(req: &Request) -> ApiResult<&AuthenticatedUser> {
-    req.extensions()
-        .get::<AuthenticatedUser>()
-        .ok_or(AppError::Unauthorized)
+/// Session token of a request: the bearer token, or the session cookie
+/// when cookie auth is on
+fn request_session_token(services: &AppServices, req: &Request) -> Result<(String, TokenSource), AppError> {
+    let cookie_auth = services.session_manager.config().cookie_auth;
+    request_token(req.headers(), req.method(), cookie_auth)
+        .map(|(token, source)| (token.to_string(), source))
 }
 
-// New middleware that works with AppServices and uses SessionManager
-pub async fn auth_middleware_with_services(
-    State(services): State<AppServices>,
+/// Keep a cookie session's cookie alive as long as the session, which
+/// validation may just have refreshed; responses that set or clear the
+/// cookie themselves (login, logout) are left alone
+fn renew_session_cookie(services: &AppServices, session: &Session, source: TokenSource, mut response: Response) -> Response {
+    if source != TokenSource::Cookie || sets_session_cookie(response.headers()) {
+        return response;
+    }
+    let config = services.session_manager.config();
+    let max_age = session.expires_at
+        .map(|expires_at| (expires_at - chrono::Utc::now().naive_utc()).num_seconds())
+        .unwrap_or(config.session_duration_hours * 3600);
+    response.headers_mut().append(
+        header::SET_COOKIE,
+        session_cookie(&session.session_token, max_age, config.secure_cookies),
+    );
+    response
+}
+
+// Client IP of the request as seen by the session manager
+fn request_client_ip(services: &AppServices, req: &Request) -> Option<String> {
+    ClientIp::from_parts(req.headers(), req.extensions(), &services.trusted_proxies)
+        .map(|ClientIp(ip)| ip.to_string())
+}
+
+/// Requests a user who must change their password can still make: reading
+/// their profile, changing the password and logging out
+const PASSWORD_CHANGE_ALLOWED_REQUESTS: [(Method, &str); 3] = [
+    (Method::GET, "/api/auth/me"),
+    (Method::POST, "/api/auth/change-password"),
+    (Method::POST, "/api/auth/logout"),
+];
+
+/// Refuse everything but the password change while one is required
+fn ensure_password_change_allowed(must_change_password: bool, method: &Method, path: &str) -> Result<(), AppError> {
+    let allowed = PASSWORD_CHANGE_ALLOWED_REQUESTS.iter()
+        .any(|(allowed_method, allowed_path)| allowed_method == method && *allowed_path == path);
+    if must_change_password && !allowed {
+        return Err(AppError::PasswordChangeRequired);
+    }
+    Ok(())
+}
+
+/// Validate the request's session, check the user is active and that
+/// `role_allowed` accepts their role, then run the rest of the stack with the
+/// `AuthenticatedUser` attached
+async fn authenticate(
+    services: AppServices,
     mut req: Request,
     next: Next,
+    role_allowed: fn(&str) -> bool,
 ) -> Result<Response, AppError> {
-    let auth_header = req
-        .headers()
-        .get("authorization")
-        .and_then(|h| h.to_str().ok())
-        .and_then(|h| h.strip_prefix("Bearer "))
-        .ok_or(AppError::MissingAuthHeader)?;
+    let (token, source) = request_session_token(&services, &req)?;
 
     // Use session manager to validate session
-    let session = services.session_manager.validate_session(auth_header).await?;
-    
+    let client_ip = request_client_ip(&services, &req);
+    let session = services.session_manager.validate_session(&token, client_ip.as_deref()).await?;
+
     // Get user from session
     let user_id = session.user_id.ok_or(AppError::InvalidToken)?;
     let mut conn = services.db_pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
     let user = User::find_by_id(&mut conn, user_id)?
         .ok_or(AppError::InvalidToken)?;
-    
-    // Check if user is active
+    drop(conn);
+
     if user.status != "active" {
         return Err(AppError::Forbidden);
     }
-    
-    // Create authenticated user and add to request extensions
-    let auth_user = AuthenticatedUser {
+    if !role_allowed(&user.role) {
+        return Err(AppError::InsufficientPermissions);
+    }
+    ensure_password_change_allowed(user.must_change_password, req.method(), req.uri().path())?;
+
+    req.extensions_mut().insert(AuthenticatedUser {
         id: user.id,
         username: user.username,
         email: user.email.unwrap_or_default(),
         role: user.role,
         status: user.status,
-    };
-    
-    req.extensions_mut().insert(auth_user);
-    
-    Ok(next.run(req).await)
+    });
+
+    Ok(renew_session_cookie(&services, &session, source, next.run(req).await))
+}
+
+// New middleware that works with AppServices and uses SessionManager
+pub async fn auth_middleware_with_services(
+    State(services): State<AppServices>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    authenticate(services, req, next, |_| true).await
 }
 
 pub async fn admin_auth_middleware_with_services(
     State(services): State<AppServices>,
-    mut req: Request,
+    req: Request,
     next: Next,
 ) -> Result<Response, AppError> {
-    let auth_header = req
-        .headers()
-        .get("authorization")
-        .and_then(|h| h.to_str().ok())
-        .and_then(|h| h.strip_prefix("Bearer "))
-        .ok_or(AppError::MissingAuthHeader)?;
+    authenticate(services, req, next, |role| role == "admin").await
+}
 
-    // Use session manager to validate session
-    let session = services.session_manager.validate_session(auth_header).await?;
-    
-    let user_id = session.user_id.ok_or(AppError::InvalidToken)?;
-    let mut conn = services.db_pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-    let user = User::find_by_id(&mut conn, user_id)?
-        .ok_or(AppError::InvalidToken)?;
-    
-    if user.status != "active" {
-        return Err(AppError::Forbidden);
+/// For content routes: lets through active users whose role grants
+/// `Permission::ManageContent`, i.e. admins and editors
+pub async fn content_auth_middleware_with_services(
+    State(services): State<AppServices>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    authenticate(services, req, next, |role| role_has_permission(role, Permission::ManageContent)).await
+}
+
+/// For public routes whose results depend on who is asking: attaches the
+/// `AuthenticatedUser` when the request carries a valid session, and lets it
+/// through anonymously otherwise (including with an expired token)
+pub async fn optional_auth_middleware_with_services(
+    State(services): State<AppServices>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    // A cookie write without the CSRF header is treated as anonymous too
+    if let Ok((token, source)) = request_session_token(&services, &req) {
+        let client_ip = request_client_ip(&services, &req);
+        if let Ok(session) = services.session_manager.validate_session(&token, client_ip.as_deref()).await {
+            let user = services.db_pool.get().ok().and_then(|mut conn| {
+                User::find_by_id(&mut conn, session.user_id?).ok().flatten()
+            });
+            if let Some(user) = user.filter(|user| user.status == "active" && !user.must_change_password) {
+                req.extensions_mut().insert(AuthenticatedUser {
+                    id: user.id,
+                    username: user.username,
+                    email: user.email.unwrap_or_default(),
+                    role: user.role,
+                    status: user.status,
+                });
+                return renew_session_cookie(&services, &session, source, next.run(req).await);
+            }
+        }
     }
-    
-    // Additional check for admin role
-    if user.role != "admin" {
-        return Err(AppError::InsufficientPermissions);
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_change_allows_only_listed_requests() {
+        assert!(ensure_password_change_allowed(true, &Method::GET, "/api/auth/me").is_ok());
+        assert!(ensure_password_change_allowed(true, &Method::POST, "/api/auth/change-password").is_ok());
+        assert!(ensure_password_change_allowed(true, &Method::POST, "/api/auth/logout").is_ok());
+
+        // The profile can be read but not edited before the password changes
+        assert!(matches!(
+            ensure_password_change_allowed(true, &Method::PUT, "/api/auth/me"),
+            Err(AppError::PasswordChangeRequired)
+        ));
+        assert!(ensure_password_change_allowed(true, &Method::GET, "/api/posts").is_err());
+        assert!(ensure_password_change_allowed(false, &Method::PUT, "/api/auth/me").is_ok());
     }
-    
-    let auth_user = AuthenticatedUser {
-        id: user.id,
-        username: user.username,
-        email: user.email.unwrap_or_default(),
-        role: user.role,
-        status: user.status,
-    };
-    
-    req.extensions_mut().insert(auth_user);
-    
-    Ok(next.run(req).await)
-}
\ No newline at end of file
+}
@@ -0,0 +1,36 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use crate::AppServices;
+use crate::services::canonical_urls::{is_content_path, redirect_target, TrailingSlash};
+
+/// Middleware answering GET and HEAD requests for public content paths that
+/// aren't spelled canonically with a 301 to the canonical path
+///
+/// Only `/`, `/posts`, `/post/..` and `/page/..` are considered, so API and
+/// upload routes pass straight through without a settings lookup.
+pub async fn canonical_redirect_middleware(
+    State(services): State<AppServices>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let path = req.uri().path();
+    if !matches!(*req.method(), Method::GET | Method::HEAD) || !is_content_path(path) {
+        return next.run(req).await;
+    }
+
+    let policy = match services.db_service.execute(TrailingSlash::load).await {
+        Ok(policy) => policy,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load trailing slash policy");
+            TrailingSlash::default()
+        }
+    };
+    match redirect_target(path, req.uri().query(), policy) {
+        Some(location) => (StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, location)]).into_response(),
+        None => next.run(req).await,
+    }
+}
@@ -1,5 +1,5 @@
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -24,10 +24,15 @@ pub enum AppError {
     // Authorization errors
     Forbidden,
     InsufficientPermissions,
+    PasswordChangeRequired,
     
     // Validation errors
     ValidationError(String),
     InvalidInput(String),
+    /// A validation error tied to one request field, so forms can show it there
+    FieldValidationError { field: String, message: String },
+    /// A CAPTCHA token was missing or rejected by the provider
+    CaptchaFailed(String),
     
     // Database errors
     DatabaseError(String),
@@ -38,6 +43,8 @@ pub enum AppError {
     // Business logic errors
     ConflictError(String),
     BadRequest(String),
+    /// Refused by a rate limiter; sent with a `Retry-After` header
+    TooManyRequests { message: String, retry_after_secs: u64 },
     
     // System errors
     InternalError(String),
@@ -55,14 +62,18 @@ impl fmt::Display for AppError {
             AppError::MissingAuthHeader => write!(f, "Missing authorization header"),
             AppError::Forbidden => write!(f, "Access denied"),
             AppError::InsufficientPermissions => write!(f, "Insufficient permissions"),
+            AppError::PasswordChangeRequired => write!(f, "Password change required"),
             AppError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
             AppError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            AppError::FieldValidationError { field, message } => write!(f, "Validation error: {}: {}", field, message),
+            AppError::CaptchaFailed(msg) => write!(f, "CAPTCHA failed: {}", msg),
             AppError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
             AppError::DatabaseConnection(msg) => write!(f, "Database connection error: {}", msg),
             AppError::DatabaseQuery(msg) => write!(f, "Database query error: {}", msg),
             AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
             AppError::ConflictError(msg) => write!(f, "Conflict: {}", msg),
             AppError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
+            AppError::TooManyRequests { message, .. } => write!(f, "Too many requests: {}", message),
             AppError::InternalError(msg) => write!(f, "Internal error: {}", msg),
             AppError::InternalServerError(msg) => write!(f, "Internal server error: {}", msg),
             AppError::ExternalServiceError(msg) => write!(f, "External service error: {}", msg),
@@ -80,8 +91,11 @@ impl IntoResponse for AppError {
             AppError::MissingAuthHeader => (StatusCode::UNAUTHORIZED, "MISSING_AUTH_HEADER", "Missing authorization header"),
             AppError::Forbidden => (StatusCode::FORBIDDEN, "FORBIDDEN", "Access denied"),
             AppError::InsufficientPermissions => (StatusCode::FORBIDDEN, "INSUFFICIENT_PERMISSIONS", "Insufficient permissions"),
+            AppError::PasswordChangeRequired => (StatusCode::FORBIDDEN, "PASSWORD_CHANGE_REQUIRED", "You must change your password before continuing"),
             AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.as_str()),
             AppError::InvalidInput(msg) => (StatusCode::BAD_REQUEST, "INVALID_INPUT", msg.as_str()),
+            AppError::FieldValidationError { message, .. } => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", message.as_str()),
+            AppError::CaptchaFailed(msg) => (StatusCode::BAD_REQUEST, "CAPTCHA_FAILED", msg.as_str()),
             AppError::DatabaseError(msg) => {
                 // Detect unique violation to surface 409 instead of 500
                 if msg.contains("unique") || msg.contains("UNIQUE") || msg.contains("duplicate key value violates unique constraint") {
@@ -95,6 +109,7 @@ impl IntoResponse for AppError {
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "NOT_FOUND", msg.as_str()),
             AppError::ConflictError(msg) => (StatusCode::CONFLICT, "CONFLICT", msg.as_str()),
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, "BAD_REQUEST", msg.as_str()),
+            AppError::TooManyRequests { message, .. } => (StatusCode::TOO_MANY_REQUESTS, "TOO_MANY_REQUESTS", message.as_str()),
             AppError::InternalError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", "Internal server error"),
             AppError::InternalServerError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_SERVER_ERROR", "Internal server error"),
             AppError::ExternalServiceError(_) => (StatusCode::SERVICE_UNAVAILABLE, "EXTERNAL_SERVICE_ERROR", "External service unavailable"),
@@ -105,9 +120,15 @@ impl IntoResponse for AppError {
             code: error_code.to_string(),
             message: message.to_string(),
             details: match &self {
-                AppError::ValidationError(msg) | AppError::InvalidInput(msg) | AppError::ConflictError(msg) | AppError::NotFound(msg) | AppError::BadRequest(msg) => {
+                AppError::ValidationError(msg) | AppError::InvalidInput(msg) | AppError::CaptchaFailed(msg) | AppError::ConflictError(msg) | AppError::NotFound(msg) | AppError::BadRequest(msg) => {
                     Some(serde_json::json!({ "error": msg }))
                 }
+                AppError::TooManyRequests { message, retry_after_secs } => {
+                    Some(serde_json::json!({ "error": message, "retry_after": retry_after_secs }))
+                }
+                AppError::FieldValidationError { field, message } => {
+                    Some(serde_json::json!({ "error": message, "field": field }))
+                }
                 AppError::InternalError(msg) | AppError::InternalServerError(msg) | AppError::DatabaseError(msg) | AppError::Configuration(msg) => {
                     Some(serde_json::json!({ "error": msg }))
                 }
@@ -115,6 +136,9 @@ impl IntoResponse for AppError {
             },
         };
 
+        if let AppError::TooManyRequests { retry_after_secs, .. } = &self {
+            return (status, [(header::RETRY_AFTER, retry_after_secs.to_string())], Json(api_error)).into_response();
+        }
         (status, Json(api_error)).into_response()
     }
 }
@@ -156,4 +180,13 @@ impl<T> ResponseJson<T> {
             message: Some(message),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Message of the validation or not-found error a test expects
+#[cfg(test)]
+pub fn error_message(result: Result<(), AppError>) -> String {
+    match result {
+        Err(AppError::ValidationError(message) | AppError::NotFound(message)) => message,
+        other => panic!("expected a validation or not-found error, got {:?}", other),
+    }
+}
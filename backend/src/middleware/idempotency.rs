@@ -0,0 +1,88 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use crate::AppServices;
+use crate::middleware::{auth::AuthenticatedUser, errors::AppError};
+use crate::models::IdempotencyKey;
+use crate::services::idempotency::{claim, fingerprint, validate_key, Decision, IDEMPOTENCY_KEY_HEADER, REPLAYED_HEADER};
+
+/// Largest request body buffered for fingerprinting, matching axum's
+/// default JSON body limit
+const MAX_REQUEST_BYTES: usize = 2 * 1024 * 1024;
+
+/// Middleware for create endpoints honoring an `Idempotency-Key` header
+///
+/// Must run after authentication, since keys belong to a user. Requests
+/// without the header pass straight through; see `services::idempotency`
+/// for what happens to the rest.
+pub async fn idempotency_middleware(
+    State(services): State<AppServices>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(req).await);
+    };
+    let key = validate_key(key.to_str().unwrap_or_default())?.to_string();
+    let user_id = req.extensions()
+        .get::<AuthenticatedUser>()
+        .map(|user| user.id)
+        .ok_or(AppError::Unauthorized)?;
+
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, MAX_REQUEST_BYTES).await
+        .map_err(|_| AppError::BadRequest("Request body is too large".to_string()))?;
+    let request_fingerprint = fingerprint(parts.method.as_str(), parts.uri.path(), &body);
+
+    let claim_key = key.clone();
+    let decision = services.db_service.execute(move |conn| {
+        claim(conn, user_id, &claim_key, &request_fingerprint, chrono::Utc::now().naive_utc())
+    }).await?;
+
+    let record_id = match decision {
+        Decision::Proceed(id) => id,
+        Decision::Replay { status, body } => {
+            let status = StatusCode::from_u16(status).unwrap_or(StatusCode::OK);
+            let mut response = (status, [(header::CONTENT_TYPE, "application/json")], body).into_response();
+            response.headers_mut().insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+            return Ok(response);
+        }
+        Decision::InProgress => {
+            return Err(AppError::ConflictError("A request with this Idempotency-Key is still being processed".to_string()));
+        }
+        Decision::Mismatch => {
+            return Err(AppError::ValidationError("This Idempotency-Key was already used for a different request".to_string()));
+        }
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!("Failed to read response for Idempotency-Key {}: {}", key, e);
+            let _ = services.db_service.execute(move |conn| IdempotencyKey::delete(conn, record_id)).await;
+            return Err(AppError::InternalError("Failed to read response".to_string()));
+        }
+    };
+
+    // Only successes are replayed; after a failure the key is free to retry
+    let status = parts.status;
+    let stored_body = String::from_utf8_lossy(&body).into_owned();
+    let stored = services.db_service.execute(move |conn| {
+        if status.is_success() {
+            IdempotencyKey::complete(conn, record_id, i32::from(status.as_u16()), &stored_body)
+        } else {
+            IdempotencyKey::delete(conn, record_id)
+        }
+    }).await;
+    if let Err(e) = stored {
+        tracing::warn!("Failed to store response for Idempotency-Key {}: {}", key, e);
+    }
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
@@ -3,6 +3,10 @@ pub mod validation;
 pub mod errors;
 pub mod rate_limiting;
 pub mod security_headers;
+pub mod request_metrics;
+pub mod upload_guard;
+pub mod canonical_redirect;
+pub mod idempotency;
 
 // Export middleware modules for direct access
 // Individual functions are accessed via module::function syntax
\ No newline at end of file
@@ -0,0 +1,19 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+use crate::services::MetricsCollector;
+
+/// Middleware to time every request for the admin performance monitor
+pub async fn request_metrics_middleware(
+    State(metrics): State<MetricsCollector>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let response = next.run(req).await;
+    metrics.record_request(started.elapsed(), response.status().is_server_error());
+    response
+}
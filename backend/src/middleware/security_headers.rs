@@ -4,14 +4,77 @@ use axum::{
     response::Response,
     http::{header, HeaderValue},
 };
+use base64::{Engine as _, engine::general_purpose};
+use rand::RngCore;
 use crate::config::Config;
 
+/// Response header carrying the per-response CSP nonce, for whatever serves
+/// the frontend shell to copy into its `<meta name="csp-nonce">` tag
+pub const CSP_NONCE_HEADER: &str = "x-csp-nonce";
+
+/// Per-response nonce that `<style>` elements must carry under the production
+/// CSP. Available to handlers as a request extension.
+#[derive(Clone, Debug)]
+pub struct CspNonce(pub String);
+
+impl CspNonce {
+    fn generate() -> Self {
+        let mut bytes = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(general_purpose::STANDARD.encode(bytes))
+    }
+}
+
+/// Hosts the reCAPTCHA and hCaptcha widgets load scripts and frames from
+const CAPTCHA_SOURCES: &str = "https://www.google.com/recaptcha/ https://www.gstatic.com/recaptcha/ https://hcaptcha.com https://*.hcaptcha.com";
+
+/// Build the Content-Security-Policy header value
+///
+/// Production allows `<style>` elements only with the response's nonce, so
+/// styles injected by the frontend (theme and container overrides) must be
+/// tagged with it. Inline `style` attributes, which components set for live
+/// previews, stay allowed through `style-src-attr` since they can't carry a
+/// nonce. Development keeps `'unsafe-inline'` and omits the nonce, as browsers
+/// ignore `'unsafe-inline'` once a nonce is present. Both allow the CAPTCHA
+/// providers' scripts and frames.
+pub fn content_security_policy(development: bool, nonce: &str) -> String {
+    if development {
+        format!(
+            "default-src 'self' 'unsafe-inline' 'unsafe-eval' http://localhost:* http://127.0.0.1:* {captcha}; \
+             img-src 'self' data: http://localhost:* http://127.0.0.1:*; \
+             font-src 'self' data:; \
+             connect-src 'self' http://localhost:* http://127.0.0.1:* {captcha}",
+            captcha = CAPTCHA_SOURCES
+        )
+    } else {
+        format!(
+            "default-src 'self'; \
+             script-src 'self' 'wasm-unsafe-eval' {captcha}; \
+             frame-src {captcha}; \
+             style-src 'self' 'nonce-{nonce}'; \
+             style-src-elem 'self' 'nonce-{nonce}'; \
+             style-src-attr 'unsafe-inline'; \
+             img-src 'self' data:; \
+             font-src 'self' data:; \
+             connect-src 'self' {captcha}; \
+             frame-ancestors 'none'; \
+             base-uri 'self'; \
+             form-action 'self'",
+            nonce = nonce,
+            captcha = CAPTCHA_SOURCES
+        )
+    }
+}
+
 /// Middleware to add security headers for production deployment
 pub async fn security_headers_middleware(
     axum::extract::State(config): axum::extract::State<Config>,
-    req: Request,
+    mut req: Request,
     next: Next,
 ) -> Response {
+    let nonce = CspNonce::generate();
+    req.extensions_mut().insert(nonce.clone());
+
     let mut response = next.run(req).await;
     let headers = response.headers_mut();
 
@@ -53,29 +116,16 @@ pub async fn security_headers_middleware(
     );
 
     // Content-Security-Policy: Prevent XSS and data injection
-    let csp = if config.is_development() {
-        // More permissive CSP for development
-        "default-src 'self' 'unsafe-inline' 'unsafe-eval' http://localhost:* http://127.0.0.1:*; \
-         img-src 'self' data: http://localhost:* http://127.0.0.1:*; \
-         font-src 'self' data:; \
-         connect-src 'self' http://localhost:* http://127.0.0.1:*"
-    } else {
-        // Strict CSP for production
-        "default-src 'self'; \
-         script-src 'self' 'wasm-unsafe-eval'; \
-         style-src 'self' 'unsafe-inline'; \
-         img-src 'self' data:; \
-         font-src 'self' data:; \
-         connect-src 'self'; \
-         frame-ancestors 'none'; \
-         base-uri 'self'; \
-         form-action 'self'"
-    };
-    
+    let csp = content_security_policy(config.is_development(), &nonce.0);
     headers.insert(
         header::HeaderName::from_static("content-security-policy"),
-        HeaderValue::from_str(csp).unwrap_or_else(|_| HeaderValue::from_static("default-src 'self'"))
+        HeaderValue::from_str(&csp).unwrap_or_else(|_| HeaderValue::from_static("default-src 'self'"))
     );
+    if !config.is_development() {
+        if let Ok(value) = HeaderValue::from_str(&nonce.0) {
+            headers.insert(header::HeaderName::from_static(CSP_NONCE_HEADER), value);
+        }
+    }
 
     // Permissions-Policy: Control browser features
     headers.insert(
@@ -103,4 +153,48 @@ pub async fn https_redirect_middleware(
     }
     
     next.run(req).await
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directive<'a>(policy: &'a str, name: &str) -> Option<&'a str> {
+        policy.split(';')
+            .map(str::trim)
+            .find(|directive| directive.split_whitespace().next() == Some(name))
+    }
+
+    #[test]
+    fn test_development_policy_allows_inline_styles_without_nonce() {
+        let policy = content_security_policy(true, "abc123");
+        assert!(directive(&policy, "default-src").unwrap().contains("'unsafe-inline'"));
+        assert!(!policy.contains("nonce-"));
+        assert!(directive(&policy, "frame-ancestors").is_none());
+    }
+
+    #[test]
+    fn test_production_policy_requires_the_nonce_for_style_elements() {
+        let policy = content_security_policy(false, "abc123");
+        assert_eq!(directive(&policy, "style-src"), Some("style-src 'self' 'nonce-abc123'"));
+        assert_eq!(directive(&policy, "style-src-elem"), Some("style-src-elem 'self' 'nonce-abc123'"));
+        assert_eq!(directive(&policy, "style-src-attr"), Some("style-src-attr 'unsafe-inline'"));
+        assert_eq!(directive(&policy, "default-src"), Some("default-src 'self'"));
+        assert_eq!(directive(&policy, "frame-ancestors"), Some("frame-ancestors 'none'"));
+        assert!(directive(&policy, "script-src").unwrap().contains("https://hcaptcha.com"));
+    }
+
+    #[test]
+    fn test_nonce_differs_per_response() {
+        // The middleware generates one nonce per request
+        let first = CspNonce::generate();
+        let second = CspNonce::generate();
+        assert_ne!(first.0, second.0);
+        assert_eq!(general_purpose::STANDARD.decode(&first.0).unwrap().len(), 16);
+        assert!(HeaderValue::from_str(&first.0).is_ok());
+
+        let policies = [&first, &second].map(|nonce| content_security_policy(false, &nonce.0));
+        assert_ne!(policies[0], policies[1]);
+        assert!(policies[1].contains(&format!("'nonce-{}'", second.0)));
+    }
+}
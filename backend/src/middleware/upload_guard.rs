@@ -0,0 +1,23 @@
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::path::PathBuf;
+use crate::services::file_security::served_upload_path;
+
+/// Middleware in front of the `/uploads` file server: only plain stored file
+/// names directly inside the upload directory get through, so encoded
+/// traversal, nested paths, dotfiles and symlinks all come back as 404
+pub async fn upload_guard_middleware(
+    State(upload_dir): State<PathBuf>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let name = req.uri().path().strip_prefix("/uploads/").unwrap_or_default();
+    if served_upload_path(&upload_dir, name).is_none() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    next.run(req).await
+}
@@ -150,10 +150,8 @@ pub fn validate_file_upload(filename: &str, content_type: &str, size: usize) ->
         return Err(AppError::ValidationError("Filename too long".to_string()));
     }
     
-    // Check for dangerous filename patterns
-    if filename.contains("..") || filename.contains("/") || filename.contains("\\") {
-        return Err(AppError::ValidationError("Invalid filename".to_string()));
-    }
+    // No path checks here: callers pass the name through
+    // `file_security::display_file_name`, and it is never used as a path
     
     // Validate allowed file types
     let allowed_types = [
@@ -209,8 +207,13 @@ pub async fn rate_limit_middleware(
     
     // Allow 100 requests per minute per IP
     if *count > 100 {
+        let retry_after = 60 - now.duration_since(*timestamp).as_secs();
         drop(limiter); // Release the lock
-        return (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded").into_response();
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(axum::http::header::RETRY_AFTER, retry_after.max(1).to_string())],
+            "Rate limit exceeded",
+        ).into_response();
     }
     
     drop(limiter); // Release the lock
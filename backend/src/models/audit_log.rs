@@ -0,0 +1,39 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+use crate::schema::audit_log;
+
+/// Action recorded when an admin expires every session at once
+pub const FORCE_LOGOUT_ALL_ACTION: &str = "force_logout_all";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = audit_log)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuditLogEntry {
+    pub id: i32,
+    pub action: String,
+    /// `None` once the acting user has been deleted
+    pub actor_id: Option<i32>,
+    pub actor_username: String,
+    /// Action-specific details, e.g. how many sessions were expired
+    pub details: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = audit_log)]
+pub struct NewAuditLogEntry {
+    pub action: String,
+    pub actor_id: Option<i32>,
+    pub actor_username: String,
+    pub details: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
+
+impl AuditLogEntry {
+    pub fn record(conn: &mut PgConnection, new_entry: NewAuditLogEntry) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(audit_log::table)
+            .values(&new_entry)
+            .get_result(conn)
+    }
+}
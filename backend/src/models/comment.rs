@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use chrono::NaiveDateTime;
 use crate::schema::{comments, posts, users, pages};
 use super::{Post, User, Page};
+use crate::services::comment_moderation::APPROVED;
 
 #[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Associations)]
 #[diesel(table_name = comments)]
@@ -18,6 +19,8 @@ pub struct Comment {
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
     pub page_id: Option<i32>,
+    /// `approved`, `pending`, `rejected` or `spam`; see `services::comment_moderation`
+    pub status: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -27,6 +30,7 @@ pub struct NewComment {
     pub user_id: Option<i32>,
     pub content: String,
     pub page_id: Option<i32>,
+    pub status: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, AsChangeset)]
@@ -48,6 +52,35 @@ pub struct CommentWithRelations {
     pub post_title: Option<String>,
     pub page_id: Option<i32>,
     pub page_title: Option<String>,
+    pub status: String,
+}
+
+/// A comment in a post or page thread with its author's name and email
+#[derive(Debug, Queryable)]
+pub struct ThreadComment {
+    pub id: i32,
+    pub post_id: Option<i32>,
+    pub page_id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub content: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    pub author_username: Option<String>,
+    pub author_email: Option<String>,
+}
+
+/// A comment matched by search, with what it was left on
+#[derive(Debug, Queryable)]
+pub struct CommentSearchRow {
+    pub id: i32,
+    pub post_id: Option<i32>,
+    pub page_id: Option<i32>,
+    pub content: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub author_username: Option<String>,
+    pub post_title: Option<String>,
+    pub page_title: Option<String>,
+    pub page_slug: Option<String>,
 }
 
 impl Comment {
@@ -75,6 +108,7 @@ impl Comment {
                 posts::title.nullable(),
                 comments::page_id,
                 pages::title.nullable(),
+                comments::status,
             ))
             .first::<CommentWithRelations>(conn)
             .optional()
@@ -121,10 +155,107 @@ impl Comment {
                 posts::title.nullable(),
                 comments::page_id,
                 pages::title.nullable(),
+                comments::status,
             ))
             .load::<CommentWithRelations>(conn)
     }
 
+    /// Comments whose content matches an `ILIKE` pattern, newest first
+    pub fn search(conn: &mut PgConnection, pattern: &str, limit: i64) -> Result<Vec<CommentSearchRow>, diesel::result::Error> {
+        comments::table
+            .left_join(users::table.on(comments::user_id.eq(users::id.nullable())))
+            .left_join(posts::table.on(comments::post_id.eq(posts::id.nullable())))
+            .left_join(pages::table.on(comments::page_id.eq(pages::id.nullable())))
+            .filter(comments::content.ilike(pattern))
+            .order((comments::created_at.desc().nulls_last(), comments::id.desc()))
+            .limit(limit)
+            .select((
+                comments::id,
+                comments::post_id,
+                comments::page_id,
+                comments::content,
+                comments::created_at,
+                users::username.nullable(),
+                posts::title.nullable(),
+                pages::title.nullable(),
+                pages::slug.nullable(),
+            ))
+            .load::<CommentSearchRow>(conn)
+    }
+
+    /// Approved comments on a post or page that sort after `after`, a
+    /// `(created_at, id)` key, oldest first (or newest first with
+    /// `newest_first`), up to `limit` if given. Undated comments count as
+    /// the oldest: last oldest first, first newest first.
+    pub fn list_thread_after(
+        conn: &mut PgConnection,
+        post_id: Option<i32>,
+        page_id: Option<i32>,
+        after: Option<(Option<NaiveDateTime>, i32)>,
+        limit: Option<i64>,
+        newest_first: bool,
+    ) -> Result<Vec<ThreadComment>, diesel::result::Error> {
+        let mut query = comments::table
+            .left_join(users::table.on(comments::user_id.eq(users::id.nullable())))
+            .filter(comments::status.eq(APPROVED))
+            .into_boxed();
+        query = match (post_id, page_id) {
+            (Some(post_id), _) => query.filter(comments::post_id.eq(post_id)),
+            (None, Some(page_id)) => query.filter(comments::page_id.eq(page_id)),
+            (None, None) => return Ok(Vec::new()),
+        };
+        query = match (after, newest_first) {
+            (Some((Some(created_at), id)), false) => query.filter(
+                comments::created_at.gt(created_at)
+                    .or(comments::created_at.eq(created_at).and(comments::id.gt(id)))
+                    .or(comments::created_at.is_null())
+            ),
+            (Some((None, id)), false) => query.filter(comments::created_at.is_null().and(comments::id.gt(id))),
+            (Some((Some(created_at), id)), true) => query.filter(
+                comments::created_at.lt(created_at)
+                    .or(comments::created_at.eq(created_at).and(comments::id.lt(id)))
+            ),
+            (Some((None, id)), true) => query.filter(
+                comments::created_at.is_not_null()
+                    .or(comments::created_at.is_null().and(comments::id.lt(id)))
+            ),
+            (None, _) => query,
+        };
+
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+        query = if newest_first {
+            query.order((comments::created_at.desc().nulls_first(), comments::id.desc()))
+        } else {
+            query.order((comments::created_at.asc().nulls_last(), comments::id.asc()))
+        };
+        query
+            .select((
+                comments::id,
+                comments::post_id,
+                comments::page_id,
+                comments::user_id,
+                comments::content,
+                comments::created_at,
+                comments::updated_at,
+                users::username.nullable(),
+                users::email.nullable(),
+            ))
+            .load::<ThreadComment>(conn)
+    }
+
+    /// Number of approved comments on each of `post_ids`, in one grouped
+    /// query. Posts without comments are left out.
+    pub fn count_by_posts(conn: &mut PgConnection, post_ids: &[i32]) -> Result<Vec<(Option<i32>, i64)>, diesel::result::Error> {
+        comments::table
+            .filter(comments::post_id.eq_any(post_ids))
+            .filter(comments::status.eq(APPROVED))
+            .group_by(comments::post_id)
+            .select((comments::post_id, diesel::dsl::count_star()))
+            .load::<(Option<i32>, i64)>(conn)
+    }
+
     pub fn find_by_post(conn: &mut PgConnection, post_id: i32) -> Result<Vec<Self>, diesel::result::Error> {
         comments::table
             .filter(comments::post_id.eq(post_id))
@@ -139,6 +270,27 @@ impl Comment {
             .load::<Comment>(conn)
     }
 
+    /// Set the moderation status of a comment; `None` if it no longer exists
+    pub fn set_status(conn: &mut PgConnection, comment_id: i32, status: &str) -> Result<Option<Self>, diesel::result::Error> {
+        diesel::update(comments::table.find(comment_id))
+            .set((
+                comments::status.eq(status),
+                comments::updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .get_result(conn)
+            .optional()
+    }
+
+    /// Whether a user has at least one approved comment
+    pub fn has_approved_by_user(conn: &mut PgConnection, user_id: i32) -> Result<bool, diesel::result::Error> {
+        diesel::select(diesel::dsl::exists(
+            comments::table
+                .filter(comments::user_id.eq(user_id))
+                .filter(comments::status.eq(APPROVED))
+        ))
+        .get_result(conn)
+    }
+
     pub fn find_by_user(conn: &mut PgConnection, user_id: i32) -> Result<Vec<Self>, diesel::result::Error> {
         comments::table
             .filter(comments::user_id.eq(user_id))
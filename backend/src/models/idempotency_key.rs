@@ -0,0 +1,69 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+use crate::schema::idempotency_keys;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = idempotency_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IdempotencyKey {
+    pub id: i32,
+    pub user_id: i32,
+    pub idempotency_key: String,
+    /// Hash of the method, path and body the key was first used with
+    pub request_fingerprint: String,
+    /// `None` while the first request is still being handled
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = idempotency_keys)]
+pub struct NewIdempotencyKey {
+    pub user_id: i32,
+    pub idempotency_key: String,
+    pub request_fingerprint: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl IdempotencyKey {
+    pub fn find(conn: &mut PgConnection, user_id: i32, key: &str) -> Result<Option<Self>, diesel::result::Error> {
+        idempotency_keys::table
+            .filter(idempotency_keys::user_id.eq(user_id))
+            .filter(idempotency_keys::idempotency_key.eq(key))
+            .first::<IdempotencyKey>(conn)
+            .optional()
+    }
+
+    /// Claim a key for a request about to be handled; `None` if another
+    /// request claimed it first
+    pub fn reserve(conn: &mut PgConnection, new_key: NewIdempotencyKey) -> Result<Option<Self>, diesel::result::Error> {
+        diesel::insert_into(idempotency_keys::table)
+            .values(&new_key)
+            .on_conflict_do_nothing()
+            .get_result(conn)
+            .optional()
+    }
+
+    /// Store the response of the request that claimed the key
+    pub fn complete(conn: &mut PgConnection, id: i32, status: i32, body: &str) -> Result<usize, diesel::result::Error> {
+        diesel::update(idempotency_keys::table.find(id))
+            .set((
+                idempotency_keys::response_status.eq(status),
+                idempotency_keys::response_body.eq(body),
+            ))
+            .execute(conn)
+    }
+
+    /// Give a key up, e.g. when its request failed and may be retried
+    pub fn delete(conn: &mut PgConnection, id: i32) -> Result<usize, diesel::result::Error> {
+        diesel::delete(idempotency_keys::table.find(id))
+            .execute(conn)
+    }
+
+    pub fn delete_created_before(conn: &mut PgConnection, cutoff: NaiveDateTime) -> Result<usize, diesel::result::Error> {
+        diesel::delete(idempotency_keys::table.filter(idempotency_keys::created_at.lt(cutoff)))
+            .execute(conn)
+    }
+}
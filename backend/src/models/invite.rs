@@ -0,0 +1,58 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+use crate::schema::invites;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = invites)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Invite {
+    pub id: i32,
+    pub token: String,
+    /// When set, only this address can sign up with the invite
+    pub email: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub used_by: Option<i32>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = invites)]
+pub struct NewInvite {
+    pub token: String,
+    pub email: Option<String>,
+    pub created_by: Option<i32>,
+    pub expires_at: NaiveDateTime,
+}
+
+impl Invite {
+    pub fn create(conn: &mut PgConnection, new_invite: NewInvite) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(invites::table)
+            .values(&new_invite)
+            .get_result(conn)
+    }
+
+    pub fn list(conn: &mut PgConnection) -> Result<Vec<Self>, diesel::result::Error> {
+        invites::table
+            .order(invites::created_at.desc())
+            .load::<Invite>(conn)
+    }
+
+    pub fn find_by_token(conn: &mut PgConnection, token: &str) -> Result<Option<Self>, diesel::result::Error> {
+        invites::table
+            .filter(invites::token.eq(token))
+            .first::<Invite>(conn)
+            .optional()
+    }
+
+    /// Mark an invite used, unless it already was. Returns false when another
+    /// signup claimed it first.
+    pub fn claim(conn: &mut PgConnection, invite_id: i32, user_id: i32, now: NaiveDateTime) -> Result<bool, diesel::result::Error> {
+        let claimed = diesel::update(invites::table.find(invite_id).filter(invites::used_at.is_null()))
+            .set((invites::used_at.eq(now), invites::used_by.eq(user_id)))
+            .execute(conn)?;
+        Ok(claimed == 1)
+    }
+}
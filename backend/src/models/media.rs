@@ -59,6 +59,17 @@ impl Media {
             .execute(conn)
     }
 
+    pub fn find_by_ids(conn: &mut PgConnection, media_ids: &[i32]) -> Result<Vec<Self>, diesel::result::Error> {
+        media::table
+            .filter(media::id.eq_any(media_ids))
+            .load::<Media>(conn)
+    }
+
+    pub fn delete_many(conn: &mut PgConnection, media_ids: &[i32]) -> Result<usize, diesel::result::Error> {
+        diesel::delete(media::table.filter(media::id.eq_any(media_ids)))
+            .execute(conn)
+    }
+
     pub fn list(conn: &mut PgConnection) -> Result<Vec<Self>, diesel::result::Error> {
         media::table
             .order(media::uploaded_at.desc())
@@ -9,6 +9,11 @@ pub mod setting;
 pub mod template;
 pub mod component;
 pub mod navigation;
+pub mod invite;
+pub mod slug_redirect;
+pub mod idempotency_key;
+pub mod post_share_link;
+pub mod audit_log;
 
 pub use user::*;
 pub use post::*;
@@ -20,4 +25,9 @@ pub use session::*;
 pub use setting::*;
 pub use template::*;
 pub use component::*;
-pub use navigation::*; 
\ No newline at end of file
+pub use navigation::*;
+pub use invite::*;
+pub use slug_redirect::*;
+pub use idempotency_key::*;
+pub use post_share_link::*;
+pub use audit_log::*; 
\ No newline at end of file
@@ -21,6 +21,8 @@ pub struct Navigation {
     pub target: Option<String>,
     pub mobile_visible: bool,
     pub description: Option<String>,
+    pub visible_from: Option<NaiveDateTime>,
+    pub visible_until: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -37,6 +39,8 @@ pub struct NewNavigation {
     pub target: Option<String>,
     pub mobile_visible: bool,
     pub description: Option<String>,
+    pub visible_from: Option<NaiveDateTime>,
+    pub visible_until: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Serialize, Deserialize, AsChangeset)]
@@ -54,6 +58,8 @@ pub struct UpdateNavigation {
     pub target: Option<Option<String>>,
     pub mobile_visible: Option<bool>,
     pub description: Option<Option<String>>,
+    pub visible_from: Option<Option<NaiveDateTime>>,
+    pub visible_until: Option<Option<NaiveDateTime>>,
 }
 
 impl Navigation {
@@ -272,4 +278,14 @@ impl ComponentTemplate {
             .first::<ComponentTemplate>(conn)
             .optional()
     }
+
+    /// Mark templates inactive; returns the rows changed
+    pub fn deactivate(conn: &mut PgConnection, ids: &[i32]) -> Result<usize, diesel::result::Error> {
+        diesel::update(component_templates::table.filter(component_templates::id.eq_any(ids)))
+            .set((
+                component_templates::is_active.eq(false),
+                component_templates::updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .execute(conn)
+    }
 }
\ No newline at end of file
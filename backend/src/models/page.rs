@@ -73,12 +73,69 @@ impl Page {
             .execute(conn)
     }
 
+    /// Published pages whose title or content matches an `ILIKE` pattern,
+    /// most recently updated first
+    pub fn search_published(conn: &mut PgConnection, pattern: &str, limit: i64) -> Result<Vec<Self>, diesel::result::Error> {
+        pages::table
+            .filter(pages::status.eq("published"))
+            .filter(pages::title.ilike(pattern).or(pages::content.ilike(pattern)))
+            .order((pages::updated_at.desc().nulls_last(), pages::id.desc()))
+            .limit(limit)
+            .load::<Page>(conn)
+    }
+
     pub fn list(conn: &mut PgConnection) -> Result<Vec<Self>, diesel::result::Error> {
         pages::table
             .order(pages::updated_at.desc())
             .load::<Page>(conn)
     }
 
+    /// Published pages, oldest first
+    pub fn list_published(conn: &mut PgConnection) -> Result<Vec<Self>, diesel::result::Error> {
+        pages::table
+            .filter(pages::status.eq("published"))
+            .order((pages::created_at.asc(), pages::id.asc()))
+            .load::<Page>(conn)
+    }
+
+    pub fn count_published(conn: &mut PgConnection) -> Result<i64, diesel::result::Error> {
+        pages::table
+            .filter(pages::status.eq("published"))
+            .count()
+            .get_result(conn)
+    }
+
+    /// Slugs and last-modified times of published pages, a window at a time
+    /// in id order, for the sitemap
+    pub fn list_published_for_sitemap(conn: &mut PgConnection, limit: i64, offset: i64) -> Result<Vec<(String, Option<NaiveDateTime>)>, diesel::result::Error> {
+        pages::table
+            .filter(pages::status.eq("published"))
+            .select((pages::slug, pages::updated_at))
+            .order(pages::id.asc())
+            .limit(limit)
+            .offset(offset)
+            .load(conn)
+    }
+
+    /// One page of results, most recently updated first, with the total
+    /// number of matching pages. Drafts are excluded when `published_only`.
+    pub fn list_paginated(conn: &mut PgConnection, published_only: bool, limit: i64, offset: i64) -> Result<(Vec<Self>, i64), diesel::result::Error> {
+        let mut count_query = pages::table.into_boxed();
+        let mut page_query = pages::table.into_boxed();
+        if published_only {
+            count_query = count_query.filter(pages::status.eq("published"));
+            page_query = page_query.filter(pages::status.eq("published"));
+        }
+
+        let total = count_query.count().get_result::<i64>(conn)?;
+        let items = page_query
+            .order((pages::updated_at.desc(), pages::id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .load::<Page>(conn)?;
+        Ok((items, total))
+    }
+
     pub fn find_by_user(conn: &mut PgConnection, user_id: i32) -> Result<Vec<Self>, diesel::result::Error> {
         pages::table
             .filter(pages::user_id.eq(user_id))
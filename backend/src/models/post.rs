@@ -17,6 +17,8 @@ pub struct Post {
     pub user_id: Option<i32>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub status: String,
+    pub published_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -26,6 +28,8 @@ pub struct NewPost {
     pub content: String,
     pub category_id: Option<i32>,
     pub user_id: Option<i32>,
+    pub status: String,
+    pub published_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Serialize, Deserialize, AsChangeset)]
@@ -36,6 +40,8 @@ pub struct UpdatePost {
     pub category_id: Option<i32>,
     pub user_id: Option<i32>,
     pub updated_at: Option<NaiveDateTime>,
+    pub status: Option<String>,
+    pub published_at: Option<Option<NaiveDateTime>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Queryable)]
@@ -47,10 +53,55 @@ pub struct PostWithRelations {
     pub user_id: Option<i32>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub status: String,
+    pub published_at: Option<NaiveDateTime>,
     pub category_name: Option<String>,
     pub author_username: Option<String>,
 }
 
+/// Criteria for the paginated post listings
+#[derive(Debug, Clone, Default)]
+pub struct PostFilter {
+    /// Exact status match; when unset, trashed posts are left out
+    pub status: Option<String>,
+    /// Case-insensitive substring match on title or content
+    pub search: Option<String>,
+    pub category_id: Option<i32>,
+    /// Only posts whose publish date has come by this time, for public listings
+    pub live_at: Option<NaiveDateTime>,
+}
+
+fn filtered_posts(filter: &PostFilter) -> posts::BoxedQuery<'static, diesel::pg::Pg> {
+    let mut query = posts::table.into_boxed();
+    if let Some(now) = filter.live_at {
+        query = query.filter(posts::published_at.is_null().or(posts::published_at.le(now)));
+    }
+
+    match filter.status {
+        Some(ref status) => query = query.filter(posts::status.eq(status.clone())),
+        None => query = query.filter(posts::status.ne("trash")),
+    }
+    if let Some(ref search) = filter.search {
+        let escaped = search.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern = format!("%{}%", escaped);
+        query = query.filter(posts::title.ilike(pattern.clone()).or(posts::content.ilike(pattern)));
+    }
+    if let Some(category_id) = filter.category_id {
+        query = query.filter(posts::category_id.eq(category_id));
+    }
+
+    query
+}
+
+/// Published posts whose publish date has come, i.e. what the public site shows
+fn live_posts() -> posts::BoxedQuery<'static, diesel::pg::Pg> {
+    filtered_posts(&PostFilter {
+        status: Some("published".to_string()),
+        live_at: Some(chrono::Utc::now().naive_utc()),
+        ..PostFilter::default()
+    })
+}
+
 impl Post {
     pub fn find_by_id(conn: &mut PgConnection, post_id: i32) -> Result<Option<Self>, diesel::result::Error> {
         posts::table
@@ -72,6 +123,8 @@ impl Post {
                 posts::user_id,
                 posts::created_at,
                 posts::updated_at,
+                posts::status,
+                posts::published_at,
                 categories::name.nullable(),
                 users::username.nullable(),
             ))
@@ -116,12 +169,163 @@ impl Post {
                 posts::user_id,
                 posts::created_at,
                 posts::updated_at,
+                posts::status,
+                posts::published_at,
                 categories::name.nullable(),
                 users::username.nullable(),
             ))
             .load::<PostWithRelations>(conn)
     }
 
+    /// One page of posts matching `filter`, newest first, with the total
+    /// number of matches
+    pub fn list_filtered(conn: &mut PgConnection, filter: &PostFilter, limit: i64, offset: i64) -> Result<(Vec<PostWithRelations>, i64), diesel::result::Error> {
+        let total = Self::count_filtered(conn, filter)?;
+
+        let ids = filtered_posts(filter)
+            .order((posts::created_at.desc(), posts::id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .select(posts::id)
+            .load::<i32>(conn)?;
+
+        let items = posts::table
+            .left_join(categories::table.on(posts::category_id.eq(categories::id.nullable())))
+            .left_join(users::table.on(posts::user_id.eq(users::id.nullable())))
+            .filter(posts::id.eq_any(ids))
+            .order((posts::created_at.desc(), posts::id.desc()))
+            .select((
+                posts::id,
+                posts::title,
+                posts::content,
+                posts::category_id,
+                posts::user_id,
+                posts::created_at,
+                posts::updated_at,
+                posts::status,
+                posts::published_at,
+                categories::name.nullable(),
+                users::username.nullable(),
+            ))
+            .load::<PostWithRelations>(conn)?;
+
+        Ok((items, total))
+    }
+
+    pub fn count_filtered(conn: &mut PgConnection, filter: &PostFilter) -> Result<i64, diesel::result::Error> {
+        filtered_posts(filter)
+            .count()
+            .get_result(conn)
+    }
+
+    /// Up to `limit` posts matching `filter` that sort after `after`, a
+    /// `(created_at, id)` key, newest first. Undated posts come last.
+    pub fn list_filtered_after(conn: &mut PgConnection, filter: &PostFilter, after: Option<(Option<NaiveDateTime>, i32)>, limit: i64) -> Result<Vec<PostWithRelations>, diesel::result::Error> {
+        let mut query = filtered_posts(filter);
+        match after {
+            Some((Some(created_at), id)) => {
+                query = query.filter(
+                    posts::created_at.lt(created_at)
+                        .or(posts::created_at.eq(created_at).and(posts::id.lt(id)))
+                        .or(posts::created_at.is_null())
+                );
+            }
+            Some((None, id)) => query = query.filter(posts::created_at.is_null().and(posts::id.lt(id))),
+            None => {}
+        }
+
+        let ids = query
+            .order((posts::created_at.desc().nulls_last(), posts::id.desc()))
+            .limit(limit)
+            .select(posts::id)
+            .load::<i32>(conn)?;
+
+        posts::table
+            .left_join(categories::table.on(posts::category_id.eq(categories::id.nullable())))
+            .left_join(users::table.on(posts::user_id.eq(users::id.nullable())))
+            .filter(posts::id.eq_any(ids))
+            .order((posts::created_at.desc().nulls_last(), posts::id.desc()))
+            .select((
+                posts::id,
+                posts::title,
+                posts::content,
+                posts::category_id,
+                posts::user_id,
+                posts::created_at,
+                posts::updated_at,
+                posts::status,
+                posts::published_at,
+                categories::name.nullable(),
+                users::username.nullable(),
+            ))
+            .load::<PostWithRelations>(conn)
+    }
+
+    /// Posts with a publish date in `[from, to)`, earliest first, excluding trash
+    pub fn list_dated_between(conn: &mut PgConnection, from: NaiveDateTime, to: NaiveDateTime) -> Result<Vec<Self>, diesel::result::Error> {
+        posts::table
+            .filter(posts::published_at.ge(from))
+            .filter(posts::published_at.lt(to))
+            .filter(posts::status.ne("trash"))
+            .order((posts::published_at.asc(), posts::id.asc()))
+            .load::<Post>(conn)
+    }
+
+    /// The newest published posts, by publish date
+    pub fn list_published(conn: &mut PgConnection, limit: i64) -> Result<Vec<Self>, diesel::result::Error> {
+        live_posts()
+            .order((posts::published_at.desc().nulls_last(), posts::created_at.desc().nulls_last(), posts::id.desc()))
+            .limit(limit)
+            .load::<Post>(conn)
+    }
+
+    pub fn count_published(conn: &mut PgConnection) -> Result<i64, diesel::result::Error> {
+        live_posts()
+            .count()
+            .get_result(conn)
+    }
+
+    /// Ids and last-modified times of published posts, a window at a time
+    /// in id order, for the sitemap
+    pub fn list_published_for_sitemap(conn: &mut PgConnection, limit: i64, offset: i64) -> Result<Vec<(i32, Option<NaiveDateTime>)>, diesel::result::Error> {
+        let rows: Vec<(i32, Option<NaiveDateTime>, Option<NaiveDateTime>)> = live_posts()
+            .select((posts::id, posts::updated_at, posts::published_at))
+            .order(posts::id.asc())
+            .limit(limit)
+            .offset(offset)
+            .load(conn)?;
+        Ok(rows.into_iter().map(|(id, updated_at, published_at)| (id, updated_at.or(published_at))).collect())
+    }
+
+    /// Published posts whose title or content matches an `ILIKE` pattern,
+    /// newest first
+    pub fn search_published(conn: &mut PgConnection, pattern: &str, limit: i64) -> Result<Vec<Self>, diesel::result::Error> {
+        live_posts()
+            .filter(posts::title.ilike(pattern).or(posts::content.ilike(pattern)))
+            .order((posts::published_at.desc().nulls_last(), posts::id.desc()))
+            .limit(limit)
+            .load::<Post>(conn)
+    }
+
+    /// Publish scheduled posts whose publish date has come
+    pub fn publish_due(conn: &mut PgConnection, now: NaiveDateTime) -> Result<usize, diesel::result::Error> {
+        diesel::update(
+            posts::table
+                .filter(posts::status.eq("scheduled"))
+                .filter(posts::published_at.le(now))
+        )
+        .set((posts::status.eq("published"), posts::updated_at.eq(now)))
+        .execute(conn)
+    }
+
+    /// Number of posts in each status
+    pub fn count_by_status(conn: &mut PgConnection) -> Result<Vec<(String, i64)>, diesel::result::Error> {
+        posts::table
+            .group_by(posts::status)
+            .select((posts::status, diesel::dsl::count_star()))
+            .load::<(String, i64)>(conn)
+    }
+
     pub fn find_by_category(conn: &mut PgConnection, category_id: i32) -> Result<Vec<Self>, diesel::result::Error> {
         posts::table
             .filter(posts::category_id.eq(category_id))
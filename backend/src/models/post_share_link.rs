@@ -0,0 +1,58 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+use crate::schema::post_share_links;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = post_share_links)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PostShareLink {
+    pub id: i32,
+    pub post_id: i32,
+    /// Id inside the signed token handed out with the link
+    pub token_id: String,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub max_views: i32,
+    pub view_count: i32,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = post_share_links)]
+pub struct NewPostShareLink {
+    pub post_id: i32,
+    pub token_id: String,
+    pub created_by: Option<i32>,
+    pub expires_at: NaiveDateTime,
+    pub max_views: i32,
+}
+
+impl PostShareLink {
+    pub fn create(conn: &mut PgConnection, new_link: NewPostShareLink) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(post_share_links::table)
+            .values(&new_link)
+            .get_result(conn)
+    }
+
+    pub fn find_by_token_id(conn: &mut PgConnection, token_id: &str) -> Result<Option<Self>, diesel::result::Error> {
+        post_share_links::table
+            .filter(post_share_links::token_id.eq(token_id))
+            .first::<PostShareLink>(conn)
+            .optional()
+    }
+
+    /// Count a view of the link, unless it has expired or run out of views.
+    /// Returns false when it could not be used.
+    pub fn record_view(conn: &mut PgConnection, link_id: i32, now: NaiveDateTime) -> Result<bool, diesel::result::Error> {
+        let counted = diesel::update(
+            post_share_links::table
+                .find(link_id)
+                .filter(post_share_links::expires_at.gt(now))
+                .filter(post_share_links::view_count.lt(post_share_links::max_views))
+        )
+            .set(post_share_links::view_count.eq(post_share_links::view_count + 1))
+            .execute(conn)?;
+        Ok(counted == 1)
+    }
+}
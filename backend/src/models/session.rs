@@ -1,7 +1,7 @@
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use chrono::{NaiveDateTime, Duration};
-use crate::schema::sessions;
+use crate::schema::{sessions, users};
 use super::User;
 
 #[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Associations)]
@@ -14,6 +14,8 @@ pub struct Session {
     pub session_token: String,
     pub created_at: Option<NaiveDateTime>,
     pub expires_at: Option<NaiveDateTime>,
+    pub ip_address: Option<String>,
+    pub last_seen_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -25,6 +27,8 @@ pub struct SessionInfo {
     pub expires_at: NaiveDateTime,
     pub is_expired: bool,
     pub time_remaining: Option<Duration>,
+    pub ip_address: Option<String>,
+    pub last_seen_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -33,6 +37,59 @@ pub struct NewSession {
     pub user_id: Option<i32>,
     pub session_token: String,
     pub expires_at: Option<NaiveDateTime>,
+    pub ip_address: Option<String>,
+    pub last_seen_at: Option<NaiveDateTime>,
+}
+
+/// Filters for admin session listings
+#[derive(Debug, Default)]
+pub struct SessionFilter {
+    pub user_id: Option<i32>,
+    /// Case-insensitive substring match on the owner's username
+    pub username: Option<String>,
+    /// `Some(true)` for unexpired sessions, `Some(false)` for expired ones
+    pub active: Option<bool>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_before: Option<NaiveDateTime>,
+}
+
+/// Session row joined with its owner for admin listings
+#[derive(Debug, Serialize, Deserialize, Queryable)]
+pub struct SessionWithUser {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_seen_at: Option<NaiveDateTime>,
+    pub ip_address: Option<String>,
+}
+
+type FilteredSessions<'a> = diesel::dsl::IntoBoxed<'a, diesel::dsl::LeftJoin<sessions::table, users::table>, diesel::pg::Pg>;
+
+fn filtered_sessions(filter: &SessionFilter, now: NaiveDateTime) -> FilteredSessions<'static> {
+    let mut query = sessions::table.left_join(users::table).into_boxed();
+
+    if let Some(user_id) = filter.user_id {
+        query = query.filter(sessions::user_id.eq(user_id));
+    }
+    if let Some(ref username) = filter.username {
+        let escaped = username.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        query = query.filter(users::username.nullable().ilike(format!("%{}%", escaped)));
+    }
+    match filter.active {
+        Some(true) => query = query.filter(sessions::expires_at.gt(now)),
+        Some(false) => query = query.filter(sessions::expires_at.le(now).or(sessions::expires_at.is_null())),
+        None => {}
+    }
+    if let Some(from) = filter.created_from {
+        query = query.filter(sessions::created_at.ge(from));
+    }
+    if let Some(before) = filter.created_before {
+        query = query.filter(sessions::created_at.lt(before));
+    }
+
+    query
 }
 
 impl Session {
@@ -82,6 +139,13 @@ impl Session {
             .execute(conn)
     }
 
+    pub fn delete_other_user_sessions(conn: &mut PgConnection, user_id: i32, keep_session_id: i32) -> Result<usize, diesel::result::Error> {
+        diesel::delete(sessions::table
+            .filter(sessions::user_id.eq(user_id))
+            .filter(sessions::id.ne(keep_session_id)))
+            .execute(conn)
+    }
+
     pub fn delete_old_sessions_for_user(conn: &mut PgConnection, user_id: i32, keep_latest: usize) -> Result<usize, diesel::result::Error> {
         // Get all sessions for user, ordered by creation date (newest first)
         let sessions = sessions::table
@@ -107,6 +171,38 @@ impl Session {
             .get_result(conn)
     }
 
+    /// One page of sessions matching `filter`, newest first, with the total match count
+    pub fn list_filtered(conn: &mut PgConnection, filter: &SessionFilter, limit: i64, offset: i64) -> Result<(Vec<SessionWithUser>, i64), diesel::result::Error> {
+        let now = chrono::Utc::now().naive_utc();
+
+        let total = filtered_sessions(filter, now)
+            .count()
+            .get_result(conn)?;
+
+        let sessions = filtered_sessions(filter, now)
+            .order((sessions::created_at.desc(), sessions::id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .select((
+                sessions::id,
+                sessions::user_id,
+                users::username.nullable(),
+                sessions::created_at,
+                sessions::expires_at,
+                sessions::last_seen_at,
+                sessions::ip_address,
+            ))
+            .load::<SessionWithUser>(conn)?;
+
+        Ok((sessions, total))
+    }
+
+    pub fn touch(conn: &mut PgConnection, session_id: i32, seen_at: NaiveDateTime) -> Result<Self, diesel::result::Error> {
+        diesel::update(sessions::table.find(session_id))
+            .set(sessions::last_seen_at.eq(seen_at))
+            .get_result(conn)
+    }
+
     pub fn get_session_info(&self) -> SessionInfo {
         let now = chrono::Utc::now().naive_utc();
         let expires_at = self.expires_at.unwrap_or(now);
@@ -125,6 +221,8 @@ impl Session {
             expires_at,
             is_expired,
             time_remaining,
+            ip_address: self.ip_address.clone(),
+            last_seen_at: self.last_seen_at,
         }
     }
 
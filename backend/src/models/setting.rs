@@ -5,6 +5,7 @@ use chrono::{DateTime, Utc};
 use crate::schema::settings;
 
 #[derive(Queryable, Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct Setting {
     pub id: i32,
     pub setting_key: String,
@@ -107,6 +108,8 @@ pub struct SystemInfo {
     pub total_users: i64,
     pub total_media: i64,
     pub last_backup: Option<DateTime<Utc>>,
+    /// Current time in the site time zone, to check the `timezone` setting
+    pub site_time: String,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
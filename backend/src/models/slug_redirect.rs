@@ -0,0 +1,82 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+use crate::schema::slug_redirects;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = slug_redirects)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SlugRedirect {
+    pub id: i32,
+    /// Kind of content the slug belonged to, e.g. `page`
+    pub resource_type: String,
+    pub resource_id: i32,
+    pub old_slug: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = slug_redirects)]
+pub struct NewSlugRedirect {
+    pub resource_type: String,
+    pub resource_id: i32,
+    pub old_slug: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl SlugRedirect {
+    /// Point an old slug at a resource, taking it over if another resource
+    /// of the same type used to have it
+    pub fn record(conn: &mut PgConnection, new_redirect: NewSlugRedirect) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(slug_redirects::table)
+            .values(&new_redirect)
+            .on_conflict((slug_redirects::resource_type, slug_redirects::old_slug))
+            .do_update()
+            .set((
+                slug_redirects::resource_id.eq(new_redirect.resource_id),
+                slug_redirects::created_at.eq(new_redirect.created_at),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn find(conn: &mut PgConnection, resource_type: &str, old_slug: &str) -> Result<Option<Self>, diesel::result::Error> {
+        slug_redirects::table
+            .filter(slug_redirects::resource_type.eq(resource_type))
+            .filter(slug_redirects::old_slug.eq(old_slug))
+            .first::<SlugRedirect>(conn)
+            .optional()
+    }
+
+    /// A resource's redirects, newest first
+    pub fn list_for_resource(conn: &mut PgConnection, resource_type: &str, resource_id: i32) -> Result<Vec<Self>, diesel::result::Error> {
+        slug_redirects::table
+            .filter(slug_redirects::resource_type.eq(resource_type))
+            .filter(slug_redirects::resource_id.eq(resource_id))
+            .order((slug_redirects::created_at.desc(), slug_redirects::id.desc()))
+            .load::<SlugRedirect>(conn)
+    }
+
+    pub fn delete_ids(conn: &mut PgConnection, ids: &[i32]) -> Result<usize, diesel::result::Error> {
+        diesel::delete(slug_redirects::table.filter(slug_redirects::id.eq_any(ids)))
+            .execute(conn)
+    }
+
+    /// Drop the redirect for a slug that is in use again
+    pub fn delete_slug(conn: &mut PgConnection, resource_type: &str, slug: &str) -> Result<usize, diesel::result::Error> {
+        diesel::delete(
+            slug_redirects::table
+                .filter(slug_redirects::resource_type.eq(resource_type))
+                .filter(slug_redirects::old_slug.eq(slug))
+        )
+        .execute(conn)
+    }
+
+    pub fn delete_for_resource(conn: &mut PgConnection, resource_type: &str, resource_id: i32) -> Result<usize, diesel::result::Error> {
+        diesel::delete(
+            slug_redirects::table
+                .filter(slug_redirects::resource_type.eq(resource_type))
+                .filter(slug_redirects::resource_id.eq(resource_id))
+        )
+        .execute(conn)
+    }
+}
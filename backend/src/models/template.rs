@@ -37,6 +37,13 @@ impl Template {
             .optional()
     }
 
+    pub fn find_by_name(conn: &mut PgConnection, template_name: &str) -> Result<Option<Self>, diesel::result::Error> {
+        templates::table
+            .filter(templates::name.eq(template_name))
+            .first::<Template>(conn)
+            .optional()
+    }
+
     pub fn create(conn: &mut PgConnection, new_template: NewTemplate) -> Result<Self, diesel::result::Error> {
         diesel::insert_into(templates::table)
             .values(&new_template)
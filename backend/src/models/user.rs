@@ -17,6 +17,16 @@ pub struct User {
     pub email_verified: bool,
     pub email_verification_token: Option<String>,
     pub email_verification_expires_at: Option<NaiveDateTime>,
+    /// Set by an admin to make the user pick a new password at next login
+    pub must_change_password: bool,
+    pub bio: Option<String>,
+    /// New address the user asked to switch to; `email` stays in use until
+    /// this one is confirmed
+    pub pending_email: Option<String>,
+    /// Last successful password login; session refreshes don't count
+    pub last_login_at: Option<NaiveDateTime>,
+    /// The password login before `last_login_at`
+    pub previous_login_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -43,6 +53,18 @@ pub struct UpdateUser {
     pub email_verified: Option<bool>,
     pub email_verification_token: Option<String>,
     pub email_verification_expires_at: Option<NaiveDateTime>,
+    pub must_change_password: Option<bool>,
+}
+
+/// Changes a user can make to their own account
+#[derive(Debug, Default, AsChangeset)]
+#[diesel(table_name = users)]
+pub struct ProfileUpdate {
+    pub username: Option<String>,
+    pub bio: Option<String>,
+    pub pending_email: Option<String>,
+    pub email_verification_token: Option<String>,
+    pub email_verification_expires_at: Option<NaiveDateTime>,
 }
 
 impl User {
@@ -86,6 +108,44 @@ impl User {
             .get_result(conn)
     }
 
+    pub fn update_profile(conn: &mut PgConnection, user_id: i32, profile: ProfileUpdate) -> Result<Self, diesel::result::Error> {
+        diesel::update(users::table.find(user_id))
+            .set(profile)
+            .get_result(conn)
+    }
+
+    /// Make the pending email address the account's address
+    pub fn confirm_pending_email(conn: &mut PgConnection, user_id: i32, email: &str) -> Result<Self, diesel::result::Error> {
+        diesel::update(users::table.find(user_id))
+            .set((
+                users::email.eq(email),
+                users::pending_email.eq(None::<String>),
+                users::email_verified.eq(true),
+                users::email_verification_token.eq(""),
+            ))
+            .get_result(conn)
+    }
+
+    /// Record a password login at `at`, keeping the one before it in
+    /// `previous_login_at`. Session refreshes don't call this.
+    pub fn record_login(conn: &mut PgConnection, user_id: i32, at: NaiveDateTime) -> Result<Self, diesel::result::Error> {
+        diesel::update(users::table.find(user_id))
+            .set((
+                users::previous_login_at.eq(users::last_login_at),
+                users::last_login_at.eq(at),
+            ))
+            .get_result(conn)
+    }
+
+    /// Swap in a new password hash, provided the stored one is still
+    /// `current_hash`. Returns false when the password changed meanwhile.
+    pub fn replace_password_hash(conn: &mut PgConnection, user_id: i32, current_hash: &str, new_hash: &str) -> Result<bool, diesel::result::Error> {
+        let updated = diesel::update(users::table.find(user_id).filter(users::password.eq(current_hash)))
+            .set(users::password.eq(new_hash))
+            .execute(conn)?;
+        Ok(updated == 1)
+    }
+
     pub fn delete(conn: &mut PgConnection, user_id: i32) -> Result<usize, diesel::result::Error> {
         diesel::delete(users::table.find(user_id))
             .execute(conn)
@@ -96,4 +156,14 @@ impl User {
             .order(users::created_at.desc())
             .load::<User>(conn)
     }
+
+    pub fn list_paginated(conn: &mut PgConnection, limit: i64, offset: i64) -> Result<(Vec<Self>, i64), diesel::result::Error> {
+        let total = users::table.count().get_result::<i64>(conn)?;
+        let items = users::table
+            .order((users::created_at.desc(), users::id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .load::<User>(conn)?;
+        Ok((items, total))
+    }
 } 
\ No newline at end of file
@@ -1,5 +1,16 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    audit_log (id) {
+        id -> Int4,
+        action -> Varchar,
+        actor_id -> Nullable<Int4>,
+        actor_username -> Varchar,
+        details -> Jsonb,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     builder_components (id) {
         id -> Int4,
@@ -27,6 +38,7 @@ diesel::table! {
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
         page_id -> Nullable<Int4>,
+        status -> Varchar,
     }
 }
 
@@ -77,6 +89,31 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    idempotency_keys (id) {
+        id -> Int4,
+        user_id -> Int4,
+        idempotency_key -> Varchar,
+        request_fingerprint -> Varchar,
+        response_status -> Nullable<Int4>,
+        response_body -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    invites (id) {
+        id -> Int4,
+        token -> Varchar,
+        email -> Nullable<Varchar>,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        used_by -> Nullable<Int4>,
+    }
+}
+
 diesel::table! {
     media (id) {
         id -> Int4,
@@ -132,6 +169,8 @@ diesel::table! {
         target -> Nullable<Varchar>,
         mobile_visible -> Bool,
         description -> Nullable<Text>,
+        visible_from -> Nullable<Timestamp>,
+        visible_until -> Nullable<Timestamp>,
     }
 }
 
@@ -169,6 +208,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    post_share_links (id) {
+        id -> Int4,
+        post_id -> Int4,
+        token_id -> Varchar,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        max_views -> Int4,
+        view_count -> Int4,
+    }
+}
+
 diesel::table! {
     posts (id) {
         id -> Int4,
@@ -178,6 +230,8 @@ diesel::table! {
         user_id -> Nullable<Int4>,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        status -> Varchar,
+        published_at -> Nullable<Timestamp>,
     }
 }
 
@@ -188,6 +242,8 @@ diesel::table! {
         session_token -> Varchar,
         created_at -> Nullable<Timestamp>,
         expires_at -> Nullable<Timestamp>,
+        ip_address -> Nullable<Varchar>,
+        last_seen_at -> Nullable<Timestamp>,
     }
 }
 
@@ -203,6 +259,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    slug_redirects (id) {
+        id -> Int4,
+        resource_type -> Varchar,
+        resource_id -> Int4,
+        old_slug -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     templates (id) {
         id -> Int4,
@@ -225,9 +291,15 @@ diesel::table! {
         email_verified -> Bool,
         email_verification_token -> Nullable<Varchar>,
         email_verification_expires_at -> Nullable<Timestamp>,
+        must_change_password -> Bool,
+        bio -> Nullable<Text>,
+        pending_email -> Nullable<Varchar>,
+        last_login_at -> Nullable<Timestamp>,
+        previous_login_at -> Nullable<Timestamp>,
     }
 }
 
+diesel::joinable!(audit_log -> users (actor_id));
 diesel::joinable!(builder_components -> templates (template_id));
 diesel::joinable!(comments -> pages (page_id));
 diesel::joinable!(comments -> posts (post_id));
@@ -235,17 +307,20 @@ diesel::joinable!(comments -> users (user_id));
 diesel::joinable!(component_events -> components (component_id));
 diesel::joinable!(component_styles -> components (component_id));
 diesel::joinable!(components -> templates (template_id));
+diesel::joinable!(idempotency_keys -> users (user_id));
 diesel::joinable!(media -> users (user_id));
 diesel::joinable!(menu_areas -> menu_templates (template_id));
 diesel::joinable!(page_components -> components (component_id));
 diesel::joinable!(page_components -> pages (page_id));
 diesel::joinable!(page_sections -> pages (page_id));
 diesel::joinable!(pages -> users (user_id));
+diesel::joinable!(post_share_links -> posts (post_id));
 diesel::joinable!(posts -> categories (category_id));
 diesel::joinable!(posts -> users (user_id));
 diesel::joinable!(sessions -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    audit_log,
     builder_components,
     categories,
     comments,
@@ -253,6 +328,8 @@ diesel::allow_tables_to_appear_in_same_query!(
     component_styles,
     component_templates,
     components,
+    idempotency_keys,
+    invites,
     media,
     menu_areas,
     menu_templates,
@@ -260,9 +337,11 @@ diesel::allow_tables_to_appear_in_same_query!(
     page_components,
     page_sections,
     pages,
+    post_share_links,
     posts,
     sessions,
     settings,
+    slug_redirects,
     templates,
     users,
 );
@@ -1,5 +1,5 @@
 use std::process::Command;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fs;
 use chrono::{DateTime, Utc};
 use sha2::{Sha256, Digest};
@@ -19,6 +19,8 @@ pub struct BackupService {
     pub backup_dir: String,
     pub database_url: String,
     pub encryption_key: Option<[u8; 32]>,
+    /// Directory media backups archive
+    pub upload_dir: PathBuf,
 }
 
 #[derive(Debug)]
@@ -53,6 +55,7 @@ impl BackupService {
             backup_dir,
             database_url,
             encryption_key: None,
+            upload_dir: PathBuf::from("uploads"),
         }
     }
     
@@ -78,8 +81,15 @@ impl BackupService {
             backup_dir,
             database_url,
             encryption_key: Some(encryption_key),
+            upload_dir: PathBuf::from("uploads"),
         }
     }
+
+    /// Archive media from `upload_dir` instead of the default `uploads`
+    pub fn with_upload_dir(mut self, upload_dir: impl Into<PathBuf>) -> Self {
+        self.upload_dir = upload_dir.into();
+        self
+    }
     
     /// Encrypt backup file data
     fn encrypt_backup_data(&self, data: &[u8]) -> Result<Vec<u8>, BackupError> {
@@ -215,14 +225,18 @@ impl BackupService {
         );
         let backup_path = Path::new(&self.backup_dir).join(&filename);
 
-        // Create tar.gz archive of uploads directory
+        // Create tar.gz archive of the upload directory, stored under its own name
+        let upload_parent = self.upload_dir.parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        let upload_name = self.upload_dir.file_name()
+            .ok_or_else(|| BackupError::ValidationError(format!("Invalid upload directory: {}", self.upload_dir.display())))?;
         let output = Command::new("tar")
             .arg("-czf")
             .arg(&backup_path)
             .arg("-C")
-            .arg(".")
-            .arg("uploads")
-            .arg("backend/uploads")
+            .arg(upload_parent)
+            .arg(upload_name)
             .output()
             .map_err(|e| BackupError::ProcessError(format!("Failed to execute tar: {}", e)))?;
 
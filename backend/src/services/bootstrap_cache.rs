@@ -0,0 +1,165 @@
+//! Cached body of `/api/public/bootstrap`
+//!
+//! Every public page load asks for the bootstrap payload, so the serialized
+//! JSON is kept in memory for `BOOTSTRAP_CACHE_TTL` and handed out with an
+//! ETag. Admin changes to navigation, component templates, pages or settings
+//! call `invalidate`; the TTL bounds anything else, such as a scheduled
+//! navigation item coming into its visibility window.
+//!
+//! A body built from data read before an invalidation is not stored, so a
+//! request that races an admin save can't put stale data back in the cache.
+//!
+//! Every lookup is recorded as a hit or miss in the shared metrics, including
+//! the ones answered with a 304, for the admin cache hit rate.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::services::MetricsCollector;
+
+pub const BOOTSTRAP_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// A serialized bootstrap payload and its ETag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedBody {
+    pub body: Arc<String>,
+    pub etag: String,
+}
+
+impl CachedBody {
+    pub fn new(body: String) -> Self {
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        Self {
+            etag: format!("\"{:016x}\"", hasher.finish()),
+            body: Arc::new(body),
+        }
+    }
+
+    /// Whether an `If-None-Match` header value names this body
+    pub fn matches(&self, if_none_match: &str) -> bool {
+        if_none_match.split(',').any(|tag| {
+            let tag = tag.trim();
+            tag == "*" || tag.trim_start_matches("W/") == self.etag
+        })
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    entry: Option<(Instant, CachedBody)>,
+    /// Bumped by every invalidation
+    generation: u64,
+}
+
+/// Shared cache of the bootstrap payload
+#[derive(Clone)]
+pub struct BootstrapCache {
+    ttl: Duration,
+    state: Arc<Mutex<CacheState>>,
+    metrics: MetricsCollector,
+}
+
+impl BootstrapCache {
+    pub fn new(ttl: Duration, metrics: MetricsCollector) -> Self {
+        Self {
+            ttl,
+            state: Arc::new(Mutex::new(CacheState::default())),
+            metrics,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// The cached body if it is still fresh; otherwise the generation to pass
+    /// to `store` once a new body is built
+    pub fn get(&self, now: Instant) -> Result<CachedBody, u64> {
+        let state = self.lock();
+        let lookup = match &state.entry {
+            Some((stored_at, body)) if now.saturating_duration_since(*stored_at) < self.ttl => Ok(body.clone()),
+            _ => Err(state.generation),
+        };
+        self.metrics.record_cache_lookup(lookup.is_ok());
+        lookup
+    }
+
+    /// Cache a body built after `get` returned `generation`, unless the cache
+    /// was invalidated in the meantime
+    pub fn store(&self, generation: u64, body: String, now: Instant) -> CachedBody {
+        let body = CachedBody::new(body);
+        let mut state = self.lock();
+        if state.generation == generation {
+            state.entry = Some((now, body.clone()));
+        }
+        body
+    }
+
+    /// Drop the cached body after a change to anything it includes
+    pub fn invalidate(&self) {
+        let mut state = self.lock();
+        state.entry = None;
+        state.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_body_is_served_until_ttl() {
+        let cache = BootstrapCache::new(Duration::from_secs(30), MetricsCollector::new());
+        let start = Instant::now();
+        let generation = cache.get(start).unwrap_err();
+        let stored = cache.store(generation, "{}".to_string(), start);
+
+        assert_eq!(cache.get(start + Duration::from_secs(29)), Ok(stored));
+        assert!(cache.get(start + Duration::from_secs(30)).is_err());
+    }
+
+    #[test]
+    fn test_invalidate_drops_body_and_rejects_racing_store() {
+        let cache = BootstrapCache::new(Duration::from_secs(30), MetricsCollector::new());
+        let start = Instant::now();
+        let generation = cache.get(start).unwrap_err();
+        cache.store(generation, "{\"a\":1}".to_string(), start);
+        cache.invalidate();
+        assert!(cache.get(start).is_err());
+
+        // Built from data read before the invalidation: returned, not cached
+        let stale = cache.store(generation, "{\"a\":1}".to_string(), start);
+        assert_eq!(stale.body.as_str(), "{\"a\":1}");
+        assert!(cache.get(start).is_err());
+
+        let generation = cache.get(start).unwrap_err();
+        cache.store(generation, "{\"a\":2}".to_string(), start);
+        assert_eq!(cache.get(start).unwrap().body.as_str(), "{\"a\":2}");
+    }
+
+    #[test]
+    fn test_lookups_are_recorded_as_hits_and_misses() {
+        let metrics = MetricsCollector::new();
+        let cache = BootstrapCache::new(Duration::from_secs(30), metrics.clone());
+        let start = Instant::now();
+        let generation = cache.get(start).unwrap_err();
+        cache.store(generation, "{}".to_string(), start);
+        cache.get(start).unwrap();
+        cache.get(start).unwrap();
+        cache.get(start).unwrap();
+
+        assert_eq!(metrics.cache_hit_rate(), 75.0);
+    }
+
+    #[test]
+    fn test_etag_matching() {
+        let body = CachedBody::new("{\"a\":1}".to_string());
+        assert_ne!(body.etag, CachedBody::new("{\"a\":2}".to_string()).etag);
+        assert!(body.matches(&body.etag));
+        assert!(body.matches(&format!("\"other\", W/{}", body.etag)));
+        assert!(body.matches("*"));
+        assert!(!body.matches("\"other\""));
+    }
+}
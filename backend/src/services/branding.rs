@@ -0,0 +1,86 @@
+//! Site logo and favicon
+//!
+//! `site_logo_media_id` and `favicon_media_id` name images in the media
+//! library; empty means none, and the header shows the site title as text.
+//! The public bootstrap carries their URLs. A setting whose media has since
+//! been deleted is treated as unset.
+
+use diesel::prelude::*;
+use serde::Serialize;
+use crate::middleware::errors::AppError;
+use crate::models::{Media, Setting};
+
+pub const SITE_LOGO_SETTING: &str = "site_logo_media_id";
+pub const FAVICON_SETTING: &str = "favicon_media_id";
+pub const BRANDING_SETTING_KEYS: [&str; 2] = [SITE_LOGO_SETTING, FAVICON_SETTING];
+
+/// URLs of the site's logo and favicon, when set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Branding {
+    pub logo_url: Option<String>,
+    pub favicon_url: Option<String>,
+}
+
+/// The media id in a branding setting value; `None` when it is empty
+pub fn parse_media_id(key: &str, value: &str) -> Result<Option<i32>, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value.parse::<i32>()
+        .ok()
+        .filter(|id| *id > 0)
+        .map(Some)
+        .ok_or_else(|| AppError::ValidationError(format!("{} must be the id of an image in the media library", key)))
+}
+
+fn is_image(media: &Media) -> bool {
+    media.media_type.as_deref().is_some_and(|media_type| media_type.starts_with("image/"))
+}
+
+/// Validate a branding setting value, returning it normalized: the setting
+/// must be empty or name an image that exists
+pub fn validate_setting(conn: &mut PgConnection, key: &str, value: &str) -> Result<String, AppError> {
+    let Some(id) = parse_media_id(key, value)? else {
+        return Ok(String::new());
+    };
+    match Media::find_by_id(conn, id)? {
+        Some(media) if is_image(&media) => Ok(id.to_string()),
+        Some(_) => Err(AppError::ValidationError(format!("{}: media {} is not an image", key, id))),
+        None => Err(AppError::ValidationError(format!("{}: media {} does not exist", key, id))),
+    }
+}
+
+fn image_url(conn: &mut PgConnection, key: &str) -> QueryResult<Option<String>> {
+    let Some(value) = Setting::find_by_key(conn, key)?.and_then(|s| s.setting_value) else {
+        return Ok(None);
+    };
+    let Ok(Some(id)) = parse_media_id(key, &value) else {
+        return Ok(None);
+    };
+    Ok(Media::find_by_id(conn, id)?.filter(is_image).map(|media| media.url))
+}
+
+impl Branding {
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        Ok(Self {
+            logo_url: image_url(conn, SITE_LOGO_SETTING)?,
+            favicon_url: image_url(conn, FAVICON_SETTING)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_media_id() {
+        assert_eq!(parse_media_id(SITE_LOGO_SETTING, "").unwrap(), None);
+        assert_eq!(parse_media_id(SITE_LOGO_SETTING, "  ").unwrap(), None);
+        assert_eq!(parse_media_id(FAVICON_SETTING, " 12 ").unwrap(), Some(12));
+        assert!(parse_media_id(FAVICON_SETTING, "0").is_err());
+        assert!(parse_media_id(FAVICON_SETTING, "-3").is_err());
+        assert!(parse_media_id(SITE_LOGO_SETTING, "/uploads/logo.png").is_err());
+    }
+}
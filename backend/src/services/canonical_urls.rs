@@ -0,0 +1,177 @@
+//! Canonical URLs for public content
+//!
+//! Public content lives at `/`, `/posts`, `/post/:id` and `/page/:slug`.
+//! The `canonical_trailing_slash` setting decides whether those paths end
+//! in a slash; any other spelling (the other slash form, doubled slashes)
+//! is redirected to the canonical one. API, upload and admin paths are never
+//! touched. The public layout names the canonical URL, that path on
+//! `site_url`, in a `<link rel="canonical">`, which settles `http://` versus
+//! `https://` and `www.` variants that a path redirect can't see.
+
+use diesel::prelude::*;
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const CANONICAL_TRAILING_SLASH_SETTING: &str = "canonical_trailing_slash";
+pub const SITE_URL_SETTING: &str = "site_url";
+
+/// Prefixes of public content paths; `/` itself is matched exactly
+const CONTENT_PREFIXES: [&str; 3] = ["/posts", "/post/", "/page/"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// `/page/about`
+    #[default]
+    Remove,
+    /// `/page/about/`
+    Add,
+}
+
+impl TrailingSlash {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "remove" => Ok(Self::Remove),
+            "add" => Ok(Self::Add),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown trailing slash policy '{}'; use remove or add", other
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Remove => "remove",
+            Self::Add => "add",
+        }
+    }
+
+    /// The configured policy; a missing or invalid setting means remove
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let value = Setting::find_by_key(conn, CANONICAL_TRAILING_SLASH_SETTING)?.and_then(|s| s.setting_value);
+        Ok(match value.as_deref().map(Self::parse) {
+            Some(Ok(policy)) => policy,
+            Some(Err(e)) => {
+                warn!("Ignoring setting {}: {}", CANONICAL_TRAILING_SLASH_SETTING, e);
+                Self::default()
+            }
+            None => Self::default(),
+        })
+    }
+}
+
+/// Absolute URL of the public site without a trailing slash, for links
+/// leaving the site (feeds, sitemaps): `site_url`, else `BASE_URL`, else the
+/// development frontend
+pub fn public_base_url(conn: &mut PgConnection) -> QueryResult<String> {
+    let site_url = Setting::find_by_key(conn, SITE_URL_SETTING)?
+        .and_then(|s| s.setting_value)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let url = site_url
+        .or_else(|| std::env::var("BASE_URL").ok())
+        .unwrap_or_else(|| "http://localhost:3000".to_string());
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+/// Normalize a `site_url` value: empty, or an http(s) URL without a
+/// trailing slash
+pub fn validate_site_url(value: &str) -> Result<String, AppError> {
+    let value = value.trim().trim_end_matches('/');
+    let host = value.strip_prefix("https://").or_else(|| value.strip_prefix("http://"));
+    match host {
+        _ if value.is_empty() => Ok(String::new()),
+        Some(host) if !host.is_empty() && !host.contains(char::is_whitespace) => Ok(value.to_string()),
+        _ => Err(AppError::ValidationError(
+            "Site URL must start with http:// or https://, e.g. https://example.com".to_string()
+        )),
+    }
+}
+
+/// Whether a request path is public content the policy applies to
+pub fn is_content_path(path: &str) -> bool {
+    let collapsed = collapse_slashes(path);
+    collapsed == "/" || CONTENT_PREFIXES.iter().any(|prefix| {
+        collapsed.strip_prefix(prefix.trim_end_matches('/'))
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+fn collapse_slashes(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c != '/' || !out.ends_with('/') {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// A content path spelled the canonical way
+pub fn canonical_path(path: &str, policy: TrailingSlash) -> String {
+    let trimmed = collapse_slashes(path).trim_end_matches('/').to_string();
+    match policy {
+        _ if trimmed.is_empty() => "/".to_string(),
+        TrailingSlash::Remove => trimmed,
+        TrailingSlash::Add => trimmed + "/",
+    }
+}
+
+/// Where to redirect a request, if its path is content spelled some other
+/// way; the query string is kept
+pub fn redirect_target(path: &str, query: Option<&str>, policy: TrailingSlash) -> Option<String> {
+    if !is_content_path(path) {
+        return None;
+    }
+    let canonical = canonical_path(path, policy);
+    if canonical == path {
+        return None;
+    }
+    Some(match query {
+        Some(query) if !query.is_empty() => format!("{}?{}", canonical, query),
+        _ => canonical,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redirects_follow_policy() {
+        assert_eq!(redirect_target("/page/about/", None, TrailingSlash::Remove).as_deref(), Some("/page/about"));
+        assert_eq!(redirect_target("/page/about", None, TrailingSlash::Remove), None);
+        assert_eq!(redirect_target("/page/about", None, TrailingSlash::Add).as_deref(), Some("/page/about/"));
+        assert_eq!(redirect_target("/page/about/", None, TrailingSlash::Add), None);
+        assert_eq!(redirect_target("/posts//", Some("page=2"), TrailingSlash::Remove).as_deref(), Some("/posts?page=2"));
+        assert_eq!(redirect_target("//post//4", None, TrailingSlash::Remove).as_deref(), Some("/post/4"));
+    }
+
+    #[test]
+    fn test_root_and_other_routes_are_left_alone() {
+        for policy in [TrailingSlash::Remove, TrailingSlash::Add] {
+            assert_eq!(redirect_target("/", None, policy), None);
+            assert_eq!(redirect_target("/api/posts/", None, policy), None);
+            assert_eq!(redirect_target("/uploads/a.png", None, policy), None);
+            assert_eq!(redirect_target("/admin/posts/", None, policy), None);
+            assert_eq!(redirect_target("/postscript", None, policy), None);
+            assert_eq!(redirect_target("/login/", None, policy), None);
+        }
+        assert_eq!(redirect_target("//", None, TrailingSlash::Add).as_deref(), Some("/"));
+    }
+
+    #[test]
+    fn test_validate_site_url() {
+        assert_eq!(validate_site_url(" https://example.com/ ").unwrap(), "https://example.com");
+        assert_eq!(validate_site_url("").unwrap(), "");
+        assert!(validate_site_url("example.com").is_err());
+        assert!(validate_site_url("https://").is_err());
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        assert_eq!(TrailingSlash::parse(" Add ").unwrap(), TrailingSlash::Add);
+        assert_eq!(TrailingSlash::parse(TrailingSlash::Remove.as_str()).unwrap(), TrailingSlash::Remove);
+        assert!(TrailingSlash::parse("keep").is_err());
+    }
+}
@@ -0,0 +1,310 @@
+//! Optional CAPTCHA checks for signup, login and comments
+//!
+//! With `captcha_provider` set to `recaptcha` or `hcaptcha` and a secret key
+//! configured, the token the frontend widget produced is sent to the
+//! provider's `siteverify` endpoint before the request is handled. Signup
+//! and public comments always need a token; login only does once a username
+//! or IP address has `captcha_login_failures` recent failed attempts, which
+//! are counted in memory like the comment rate limits. The site key is a
+//! public setting so the frontend can render the widget; the secret is not.
+
+use diesel::prelude::*;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const CAPTCHA_PROVIDER_SETTING: &str = "captcha_provider";
+pub const CAPTCHA_SITE_KEY_SETTING: &str = "captcha_site_key";
+pub const CAPTCHA_SECRET_KEY_SETTING: &str = "captcha_secret_key";
+pub const CAPTCHA_LOGIN_FAILURES_SETTING: &str = "captcha_login_failures";
+pub const CAPTCHA_SETTING_KEYS: [&str; 4] = [
+    CAPTCHA_PROVIDER_SETTING,
+    CAPTCHA_SITE_KEY_SETTING,
+    CAPTCHA_SECRET_KEY_SETTING,
+    CAPTCHA_LOGIN_FAILURES_SETTING,
+];
+
+pub const DEFAULT_LOGIN_FAILURES: u32 = 3;
+const MAX_LOGIN_FAILURES: u32 = 100;
+/// Failed logins older than this no longer count towards the threshold
+const LOGIN_FAILURE_WINDOW: Duration = Duration::from_secs(15 * 60);
+/// Limit on a whole verification request, from connecting to the last byte
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Client shared by all verifications so connections to providers are reused
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(VERIFY_TIMEOUT)
+        .build()
+        .expect("CAPTCHA HTTP client configuration is valid")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptchaProvider {
+    Recaptcha,
+    Hcaptcha,
+}
+
+impl CaptchaProvider {
+    /// `None` for the `none` setting value (CAPTCHA disabled)
+    pub fn parse(value: &str) -> Result<Option<Self>, AppError> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "none" => Ok(None),
+            "recaptcha" => Ok(Some(Self::Recaptcha)),
+            "hcaptcha" => Ok(Some(Self::Hcaptcha)),
+            other => Err(AppError::ValidationError(format!(
+                "{} must be none, recaptcha or hcaptcha, got '{}'", CAPTCHA_PROVIDER_SETTING, other
+            ))),
+        }
+    }
+
+    /// The provider's token verification endpoint
+    fn verify_url(self) -> &'static str {
+        match self {
+            Self::Recaptcha => "https://www.google.com/recaptcha/api/siteverify",
+            Self::Hcaptcha => "https://api.hcaptcha.com/siteverify",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptchaConfig {
+    pub provider: Option<CaptchaProvider>,
+    pub secret_key: String,
+    /// Failed logins before a CAPTCHA is required; 0 requires it on every login
+    pub login_failures: u32,
+}
+
+impl Default for CaptchaConfig {
+    fn default() -> Self {
+        Self {
+            provider: None,
+            secret_key: String::new(),
+            login_failures: DEFAULT_LOGIN_FAILURES,
+        }
+    }
+}
+
+impl CaptchaConfig {
+    /// Validate a CAPTCHA setting value before it is stored
+    pub fn validate_setting(key: &str, value: &str) -> Result<(), AppError> {
+        match key {
+            CAPTCHA_PROVIDER_SETTING => CaptchaProvider::parse(value).map(|_| ()),
+            CAPTCHA_LOGIN_FAILURES_SETTING => match value.trim().parse::<u32>() {
+                Ok(failures) if failures <= MAX_LOGIN_FAILURES => Ok(()),
+                _ => Err(AppError::ValidationError(format!(
+                    "{} must be a whole number between 0 and {}", CAPTCHA_LOGIN_FAILURES_SETTING, MAX_LOGIN_FAILURES
+                ))),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Current configuration from settings; invalid values leave CAPTCHA off
+    /// or at the default threshold
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut value = |key: &str| -> QueryResult<String> {
+            Ok(Setting::find_by_key(conn, key)?.and_then(|s| s.setting_value).unwrap_or_default())
+        };
+        let provider = value(CAPTCHA_PROVIDER_SETTING)?;
+        let secret_key = value(CAPTCHA_SECRET_KEY_SETTING)?.trim().to_string();
+        let login_failures = value(CAPTCHA_LOGIN_FAILURES_SETTING)?;
+
+        Ok(Self {
+            provider: CaptchaProvider::parse(&provider).unwrap_or_else(|e| {
+                warn!("Ignoring CAPTCHA provider setting: {}", e);
+                None
+            }),
+            secret_key,
+            login_failures: login_failures.trim().parse().unwrap_or(DEFAULT_LOGIN_FAILURES),
+        })
+    }
+
+    /// Whether verification runs at all; a provider without a secret can't
+    /// verify anything, so it counts as off
+    pub fn enabled(&self) -> bool {
+        self.provider.is_some() && !self.secret_key.is_empty()
+    }
+
+    /// Check a token from the frontend with the provider. Does nothing when
+    /// CAPTCHA is disabled.
+    pub async fn verify(&self, token: Option<&str>, remote_ip: Option<&str>) -> Result<(), AppError> {
+        let Some(provider) = self.provider.filter(|_| self.enabled()) else {
+            return Ok(());
+        };
+        let token = token.map(str::trim).filter(|token| !token.is_empty())
+            .ok_or_else(|| AppError::CaptchaFailed("Please complete the CAPTCHA".to_string()))?;
+
+        let mut form = vec![("secret", self.secret_key.as_str()), ("response", token)];
+        if let Some(ip) = remote_ip {
+            form.push(("remoteip", ip));
+        }
+
+        let url = provider.verify_url();
+        let request = async {
+            HTTP_CLIENT.post(url).form(&form).send().await?
+                .error_for_status()?
+                .text().await
+        };
+        let response = request.await.map_err(|e| {
+            warn!("CAPTCHA verification request to {} failed: {}", url, e);
+            AppError::ExternalServiceError(format!("CAPTCHA verification failed: {}", e))
+        })?;
+
+        if parse_verify_response(&response)? {
+            Ok(())
+        } else {
+            Err(AppError::CaptchaFailed("CAPTCHA verification failed, please try again".to_string()))
+        }
+    }
+}
+
+/// Whether the provider accepted the token
+fn parse_verify_response(body: &str) -> Result<bool, AppError> {
+    let json: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| AppError::ExternalServiceError(format!("Unreadable CAPTCHA verification response: {}", e)))?;
+    if json["success"].as_bool() != Some(true) {
+        warn!("CAPTCHA token rejected: {}", json["error-codes"]);
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Recent failed logins per username and IP address
+#[derive(Clone, Default)]
+pub struct LoginFailureTracker {
+    recent: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+}
+
+impl LoginFailureTracker {
+    /// Keys a login attempt is counted under
+    pub fn keys(username: &str, ip: Option<&str>) -> Vec<String> {
+        let mut keys = vec![format!("user:{}", username.trim().to_lowercase())];
+        if let Some(ip) = ip {
+            keys.push(format!("ip:{}", ip));
+        }
+        keys
+    }
+
+    fn failures_at(&self, keys: &[String], now: Instant) -> u32 {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        keys.iter()
+            .map(|key| {
+                let Some(times) = recent.get_mut(key) else { return 0 };
+                while times.front().is_some_and(|t| now.duration_since(*t) >= LOGIN_FAILURE_WINDOW) {
+                    times.pop_front();
+                }
+                times.len() as u32
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn record_failure_at(&self, keys: &[String], now: Instant) {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        for key in keys {
+            recent.entry(key.clone()).or_default().push_back(now);
+        }
+        // Forget keys whose failures have all expired
+        recent.retain(|_, times| times.back().is_some_and(|t| now.duration_since(*t) < LOGIN_FAILURE_WINDOW));
+    }
+
+    /// Whether the next login under these keys needs a CAPTCHA
+    pub fn requires_captcha(&self, keys: &[String], config: &CaptchaConfig) -> bool {
+        config.enabled() && self.failures_at(keys, Instant::now()) >= config.login_failures
+    }
+
+    pub fn record_failure(&self, keys: &[String]) {
+        self.record_failure_at(keys, Instant::now());
+    }
+
+    /// Forget the username's failures after a successful login; the IP's
+    /// stay, since other accounts may be under attack from it
+    pub fn clear_user(&self, keys: &[String]) {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        for key in keys.iter().filter(|key| key.starts_with("user:")) {
+            recent.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled(login_failures: u32) -> CaptchaConfig {
+        CaptchaConfig {
+            provider: Some(CaptchaProvider::Hcaptcha),
+            secret_key: "secret".to_string(),
+            login_failures,
+        }
+    }
+
+    #[test]
+    fn test_provider_and_threshold_settings() {
+        assert_eq!(CaptchaProvider::parse("none").unwrap(), None);
+        assert_eq!(CaptchaProvider::parse(" reCAPTCHA ").unwrap(), Some(CaptchaProvider::Recaptcha));
+        assert!(CaptchaProvider::parse("turnstile").is_err());
+
+        assert!(CaptchaConfig::validate_setting(CAPTCHA_LOGIN_FAILURES_SETTING, "0").is_ok());
+        assert!(CaptchaConfig::validate_setting(CAPTCHA_LOGIN_FAILURES_SETTING, "-1").is_err());
+        assert!(CaptchaConfig::validate_setting(CAPTCHA_LOGIN_FAILURES_SETTING, "101").is_err());
+    }
+
+    #[test]
+    fn test_provider_without_secret_is_disabled() {
+        assert!(!CaptchaConfig::default().enabled());
+        assert!(!CaptchaConfig { secret_key: String::new(), ..enabled(3) }.enabled());
+        assert!(enabled(3).enabled());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_captcha_skips_verification() {
+        assert!(CaptchaConfig::default().verify(None, None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_rejected_when_enabled() {
+        assert!(matches!(enabled(3).verify(Some("  "), None).await, Err(AppError::CaptchaFailed(_))));
+    }
+
+    #[test]
+    fn test_parse_provider_responses() {
+        assert!(parse_verify_response(r#"{"success": true}"#).unwrap());
+        assert!(!parse_verify_response(r#"{"success": false, "error-codes": ["invalid-input-response"]}"#).unwrap());
+        assert!(parse_verify_response("<html>").is_err());
+    }
+
+    #[test]
+    fn test_login_failures_trigger_captcha() {
+        let tracker = LoginFailureTracker::default();
+        let config = enabled(2);
+        let keys = LoginFailureTracker::keys("Alice", Some("203.0.113.9"));
+        let start = Instant::now();
+
+        tracker.record_failure_at(&keys, start);
+        assert_eq!(tracker.failures_at(&keys, start), 1);
+        tracker.record_failure_at(&keys, start);
+        assert!(tracker.requires_captcha(&keys, &config));
+        // The same IP trying another account also needs one
+        assert!(tracker.requires_captcha(&LoginFailureTracker::keys("bob", Some("203.0.113.9")), &config));
+        assert!(!tracker.requires_captcha(&keys, &CaptchaConfig::default()));
+
+        assert_eq!(tracker.failures_at(&keys, start + LOGIN_FAILURE_WINDOW), 0);
+    }
+
+    #[test]
+    fn test_successful_login_clears_user_failures_only() {
+        let tracker = LoginFailureTracker::default();
+        let keys = LoginFailureTracker::keys("alice", Some("203.0.113.9"));
+        tracker.record_failure(&keys);
+        tracker.clear_user(&keys);
+
+        assert_eq!(tracker.failures_at(&keys[..1], Instant::now()), 0);
+        assert_eq!(tracker.failures_at(&keys[1..], Instant::now()), 1);
+    }
+}
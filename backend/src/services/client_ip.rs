@@ -0,0 +1,190 @@
+//! The client IP address of a request
+//!
+//! Behind a reverse proxy the socket peer is the proxy, so the client's
+//! address has to come from `X-Forwarded-For` or `X-Real-IP`. Anyone can send
+//! those headers, so they are only read when the peer is one of the proxies
+//! listed in `TRUSTED_PROXIES` (addresses or CIDR ranges such as
+//! `10.0.0.0/8`). `X-Forwarded-For` is walked from the right, skipping
+//! trusted proxies, since entries further left were written by the client.
+//!
+//! Everything that depends on the client IP (session IP binding, comment
+//! rate limits, login CAPTCHA tracking, CAPTCHA verification) goes through
+//! `TrustedProxies::client_ip` or the `ClientIp` extractor, so they all agree.
+
+use std::net::{IpAddr, SocketAddr};
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{request::Parts, HeaderMap},
+};
+use crate::{middleware::errors::AppError, AppServices};
+
+/// An address range like `10.0.0.0/8`; a bare address is a range of one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpRange {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || format!("'{}' is not an IP address or CIDR range", value);
+        let (address, prefix_len) = match value.split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len)),
+            None => (value, None),
+        };
+        let network: IpAddr = address.parse().map_err(|_| invalid())?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(len) => len.parse::<u8>().ok().filter(|len| *len <= max_len).ok_or_else(invalid)?,
+            None => max_len,
+        };
+        Ok(Self { network, prefix_len })
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        // A dual-stack listener reports IPv4 peers as `::ffff:a.b.c.d`
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.prefix_len)).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(self.prefix_len)).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Proxies allowed to report the client IP, from `TRUSTED_PROXIES`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies(Vec<IpRange>);
+
+impl TrustedProxies {
+    /// Parse a comma-separated list of addresses and CIDR ranges
+    pub fn parse(value: &str) -> Result<Self, String> {
+        value.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| IpRange::parse(entry).map_err(|e| format!("Invalid entry in TRUSTED_PROXIES: {}", e)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.0.iter().any(|range| range.contains(ip))
+    }
+
+    /// Client IP of a request received from `peer`
+    pub fn client_ip(&self, headers: &HeaderMap, peer: IpAddr) -> IpAddr {
+        if !self.contains(peer) {
+            return peer;
+        }
+
+        let forwarded: Vec<IpAddr> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|h| h.to_str().ok())
+            .flat_map(|h| h.split(','))
+            .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+            .collect();
+        if !forwarded.is_empty() {
+            // Every hop was a trusted proxy: the leftmost is the closest to a client
+            return forwarded.iter().rev().copied().find(|ip| !self.contains(*ip))
+                .unwrap_or(forwarded[0]);
+        }
+
+        headers
+            .get("x-real-ip")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|ip| ip.trim().parse().ok())
+            .unwrap_or(peer)
+    }
+}
+
+/// Client IP of the request being handled
+///
+/// Needs the server to run with connect info, as `main` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+impl ClientIp {
+    /// Resolve from request headers and extensions, if the peer is known
+    pub fn from_parts(headers: &HeaderMap, extensions: &axum::http::Extensions, trusted_proxies: &TrustedProxies) -> Option<Self> {
+        let ConnectInfo(peer) = extensions.get::<ConnectInfo<SocketAddr>>()?;
+        Some(Self(trusted_proxies.client_ip(headers, peer.ip())))
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppServices> for ClientIp {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, services: &AppServices) -> Result<Self, Self::Rejection> {
+        Self::from_parts(&parts.headers, &parts.extensions, &services.trusted_proxies)
+            .ok_or_else(|| AppError::InternalError("Client address is unavailable".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_spoofed_headers_from_untrusted_peer_are_ignored() {
+        let proxies = TrustedProxies::parse("10.0.0.0/8").unwrap();
+        let spoofed = headers(&[("x-forwarded-for", "203.0.113.7"), ("x-real-ip", "203.0.113.8")]);
+        assert_eq!(proxies.client_ip(&spoofed, ip("198.51.100.1")), ip("198.51.100.1"));
+        assert_eq!(TrustedProxies::default().client_ip(&spoofed, ip("127.0.0.1")), ip("127.0.0.1"));
+    }
+
+    #[test]
+    fn test_forwarded_header_from_trusted_proxy() {
+        let proxies = TrustedProxies::parse("127.0.0.1, 10.0.0.0/8").unwrap();
+        // Leftmost entry is client-controlled; the proxies append the real client
+        let forwarded = headers(&[("x-forwarded-for", "192.0.2.99, 203.0.113.7, 10.1.2.3")]);
+        assert_eq!(proxies.client_ip(&forwarded, ip("127.0.0.1")), ip("203.0.113.7"));
+
+        // Only trusted hops: the leftmost is the best guess
+        let internal = headers(&[("x-forwarded-for", "10.0.0.5, 10.0.0.6")]);
+        assert_eq!(proxies.client_ip(&internal, ip("10.0.0.7")), ip("10.0.0.5"));
+    }
+
+    #[test]
+    fn test_real_ip_header_and_fallback_to_peer() {
+        let proxies = TrustedProxies::parse("::1, 127.0.0.1").unwrap();
+        let real_ip = headers(&[("x-real-ip", "203.0.113.7")]);
+        assert_eq!(proxies.client_ip(&real_ip, ip("::1")), ip("203.0.113.7"));
+        assert_eq!(proxies.client_ip(&HeaderMap::new(), ip("127.0.0.1")), ip("127.0.0.1"));
+        assert_eq!(proxies.client_ip(&headers(&[("x-real-ip", "garbage")]), ip("127.0.0.1")), ip("127.0.0.1"));
+    }
+
+    #[test]
+    fn test_ranges() {
+        let range = IpRange::parse("172.16.0.0/12").unwrap();
+        assert!(range.contains(ip("172.31.255.255")));
+        assert!(!range.contains(ip("172.32.0.0")));
+        assert!(range.contains(ip("::ffff:172.16.0.1")));
+        assert!(IpRange::parse("0.0.0.0/0").unwrap().contains(ip("8.8.8.8")));
+        assert!(IpRange::parse("fd00::/8").unwrap().contains(ip("fd12::1")));
+
+        assert!(IpRange::parse("10.0.0.0/33").is_err());
+        assert!(IpRange::parse("proxy.local").is_err());
+        assert!(TrustedProxies::parse("127.0.0.1, nope").is_err());
+        assert_eq!(TrustedProxies::parse(" ").unwrap(), TrustedProxies::default());
+    }
+}
@@ -0,0 +1,311 @@
+//! Custom design-system color schemes
+//!
+//! Schemes are stored as `theme`-type settings: `theme_admin_<name>` and
+//! `theme_public_<name>` hold each half as JSON, `theme_custom_list` keeps the
+//! names in the order they were saved, and `theme_current_admin` names the
+//! active admin scheme. The scheme JSON itself is owned by the frontend's
+//! design system and passed through untouched.
+
+use std::collections::BTreeSet;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const THEME_SETTING_TYPE: &str = "theme";
+pub const CURRENT_ADMIN_THEME_SETTING: &str = "theme_current_admin";
+pub const CUSTOM_THEME_LIST_SETTING: &str = "theme_custom_list";
+const ADMIN_SCHEME_PREFIX: &str = "theme_admin_";
+const PUBLIC_SCHEME_PREFIX: &str = "theme_public_";
+
+/// Presets defined in the frontend; they can be activated but not edited
+pub const BUILT_IN_SCHEMES: [&str; 2] = ["Light Preset", "Dark Preset"];
+/// Scheme used when nothing is active or the active one has been lost
+pub const DEFAULT_SCHEME: &str = "Light Preset";
+const MAX_SCHEME_NAME_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ColorSchemeSummary {
+    pub name: String,
+    pub built_in: bool,
+    pub has_admin: bool,
+    pub has_public: bool,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ColorSchemeList {
+    pub schemes: Vec<ColorSchemeSummary>,
+    /// Active admin scheme, falling back to the default if the stored one is gone
+    pub current_admin: String,
+}
+
+/// Both halves of a custom scheme as stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredColorScheme {
+    pub name: String,
+    pub admin: Option<serde_json::Value>,
+    pub public: Option<serde_json::Value>,
+}
+
+fn admin_key(name: &str) -> String {
+    format!("{}{}", ADMIN_SCHEME_PREFIX, name)
+}
+
+fn public_key(name: &str) -> String {
+    format!("{}{}", PUBLIC_SCHEME_PREFIX, name)
+}
+
+fn is_built_in(name: &str) -> bool {
+    BUILT_IN_SCHEMES.contains(&name)
+}
+
+/// Validate a custom scheme name, returning it trimmed
+pub fn validate_scheme_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::ValidationError("Scheme name is required".to_string()));
+    }
+    if name.chars().count() > MAX_SCHEME_NAME_LENGTH {
+        return Err(AppError::ValidationError(format!("Scheme name must be at most {} characters", MAX_SCHEME_NAME_LENGTH)));
+    }
+    if name.chars().any(|c| c.is_control() || c == '/') {
+        return Err(AppError::ValidationError("Scheme name can't contain '/' or control characters".to_string()));
+    }
+    if is_built_in(name) {
+        return Err(AppError::ValidationError(format!("'{}' is a built-in scheme", name)));
+    }
+    Ok(name.to_string())
+}
+
+/// Custom scheme names in saved order: the stored list first, then any
+/// schemes with data but missing from the list (e.g. saved by older
+/// versions), skipping built-ins and duplicates
+fn custom_scheme_names(list_json: Option<&str>, settings: &[Setting]) -> Vec<String> {
+    let listed: Vec<String> = list_json
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default();
+    let stored = settings.iter().filter_map(|s| {
+        s.setting_key.strip_prefix(ADMIN_SCHEME_PREFIX)
+            .or_else(|| s.setting_key.strip_prefix(PUBLIC_SCHEME_PREFIX))
+            .map(str::to_string)
+    });
+
+    let with_data: BTreeSet<String> = stored.clone().collect();
+    let mut seen = BTreeSet::new();
+    listed.into_iter()
+        .filter(|name| with_data.contains(name))
+        .chain(stored)
+        .filter(|name| !is_built_in(name) && seen.insert(name.clone()))
+        .collect()
+}
+
+/// The active admin scheme if it still exists, otherwise the default
+fn resolve_current(current: Option<&str>, custom_names: &[String]) -> String {
+    match current {
+        Some(name) if is_built_in(name) || custom_names.iter().any(|n| n == name) => name.to_string(),
+        _ => DEFAULT_SCHEME.to_string(),
+    }
+}
+
+fn setting_value<'a>(settings: &'a [Setting], key: &str) -> Option<&'a str> {
+    settings.iter()
+        .find(|s| s.setting_key == key)
+        .and_then(|s| s.setting_value.as_deref())
+}
+
+fn save_name_list(conn: &mut PgConnection, names: &[String]) -> QueryResult<Setting> {
+    let json = serde_json::to_string(names).unwrap_or_else(|_| "[]".to_string());
+    Setting::upsert(conn, CUSTOM_THEME_LIST_SETTING, &json, THEME_SETTING_TYPE, Some("List of custom saved themes".to_string()))
+}
+
+/// Built-in and custom schemes with the active admin scheme
+pub fn list_schemes(conn: &mut PgConnection) -> Result<ColorSchemeList, AppError> {
+    let settings = Setting::list_by_type(conn, THEME_SETTING_TYPE)?;
+    let custom = custom_scheme_names(setting_value(&settings, CUSTOM_THEME_LIST_SETTING), &settings);
+    let current_admin = resolve_current(setting_value(&settings, CURRENT_ADMIN_THEME_SETTING), &custom);
+
+    let built_in = BUILT_IN_SCHEMES.iter().map(|name| ColorSchemeSummary {
+        name: name.to_string(),
+        built_in: true,
+        has_admin: true,
+        has_public: true,
+        active: *name == current_admin,
+    });
+    let custom = custom.iter().map(|name| ColorSchemeSummary {
+        name: name.clone(),
+        built_in: false,
+        has_admin: setting_value(&settings, &admin_key(name)).is_some(),
+        has_public: setting_value(&settings, &public_key(name)).is_some(),
+        active: *name == current_admin,
+    });
+
+    Ok(ColorSchemeList { schemes: built_in.chain(custom).collect(), current_admin })
+}
+
+/// Load a custom scheme; unreadable halves are treated as missing
+pub fn get_scheme(conn: &mut PgConnection, name: &str) -> Result<StoredColorScheme, AppError> {
+    let load = |conn: &mut PgConnection, key: String| -> QueryResult<Option<serde_json::Value>> {
+        Ok(Setting::find_by_key(conn, &key)?
+            .and_then(|s| s.setting_value)
+            .and_then(|json| serde_json::from_str(&json).ok()))
+    };
+    let admin = load(conn, admin_key(name))?;
+    let public = load(conn, public_key(name))?;
+    if admin.is_none() && public.is_none() {
+        return Err(AppError::NotFound(format!("Color scheme '{}' not found", name)));
+    }
+    Ok(StoredColorScheme { name: name.to_string(), admin, public })
+}
+
+/// Create or update a custom scheme; halves that aren't given are kept
+pub fn save_scheme(
+    conn: &mut PgConnection,
+    name: &str,
+    admin: Option<serde_json::Value>,
+    public: Option<serde_json::Value>,
+) -> Result<StoredColorScheme, AppError> {
+    let name = validate_scheme_name(name)?;
+    if admin.is_none() && public.is_none() {
+        return Err(AppError::ValidationError("Provide an admin or public scheme to save".to_string()));
+    }
+
+    conn.transaction::<_, AppError, _>(|conn| {
+        if let Some(admin) = &admin {
+            Setting::upsert(conn, &admin_key(&name), &admin.to_string(), THEME_SETTING_TYPE, Some(format!("Admin theme data for {}", name)))?;
+        }
+        if let Some(public) = &public {
+            Setting::upsert(conn, &public_key(&name), &public.to_string(), THEME_SETTING_TYPE, Some(format!("Public theme data for {}", name)))?;
+        }
+
+        let settings = Setting::list_by_type(conn, THEME_SETTING_TYPE)?;
+        let names = custom_scheme_names(setting_value(&settings, CUSTOM_THEME_LIST_SETTING), &settings);
+        save_name_list(conn, &names)?;
+        get_scheme(conn, &name)
+    })
+}
+
+/// Rename a custom scheme, keeping it active if it was
+pub fn rename_scheme(conn: &mut PgConnection, from: &str, to: &str) -> Result<StoredColorScheme, AppError> {
+    let to = validate_scheme_name(to)?;
+    if is_built_in(from) {
+        return Err(AppError::ValidationError(format!("'{}' is a built-in scheme and can't be renamed", from)));
+    }
+
+    conn.transaction::<_, AppError, _>(|conn| {
+        let scheme = get_scheme(conn, from)?;
+        if to == from {
+            return Ok(scheme);
+        }
+        if get_scheme(conn, &to).is_ok() {
+            return Err(AppError::ConflictError(format!("A color scheme named '{}' already exists", to)));
+        }
+
+        Setting::delete(conn, &admin_key(from))?;
+        Setting::delete(conn, &public_key(from))?;
+        if let Some(admin) = &scheme.admin {
+            Setting::upsert(conn, &admin_key(&to), &admin.to_string(), THEME_SETTING_TYPE, Some(format!("Admin theme data for {}", to)))?;
+        }
+        if let Some(public) = &scheme.public {
+            Setting::upsert(conn, &public_key(&to), &public.to_string(), THEME_SETTING_TYPE, Some(format!("Public theme data for {}", to)))?;
+        }
+
+        let settings = Setting::list_by_type(conn, THEME_SETTING_TYPE)?;
+        let mut listed: Vec<String> = setting_value(&settings, CUSTOM_THEME_LIST_SETTING)
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+        // Keep the renamed scheme in its old position
+        for name in listed.iter_mut().filter(|name| *name == from) {
+            *name = to.clone();
+        }
+        let names = custom_scheme_names(serde_json::to_string(&listed).ok().as_deref(), &settings);
+        save_name_list(conn, &names)?;
+
+        if setting_value(&settings, CURRENT_ADMIN_THEME_SETTING) == Some(from) {
+            Setting::upsert(conn, CURRENT_ADMIN_THEME_SETTING, &to, THEME_SETTING_TYPE, Some("Current active admin theme".to_string()))?;
+        }
+        Ok(StoredColorScheme { name: to.clone(), ..scheme })
+    })
+}
+
+/// Delete a custom scheme; the active scheme must be switched away from first
+pub fn delete_scheme(conn: &mut PgConnection, name: &str) -> Result<(), AppError> {
+    if is_built_in(name) {
+        return Err(AppError::ValidationError(format!("'{}' is a built-in scheme and can't be deleted", name)));
+    }
+
+    conn.transaction::<_, AppError, _>(|conn| {
+        get_scheme(conn, name)?;
+        let current = Setting::find_by_key(conn, CURRENT_ADMIN_THEME_SETTING)?.and_then(|s| s.setting_value);
+        if current.as_deref() == Some(name) {
+            return Err(AppError::ConflictError(format!(
+                "'{}' is the active admin scheme; switch to another scheme before deleting it", name
+            )));
+        }
+
+        Setting::delete(conn, &admin_key(name))?;
+        Setting::delete(conn, &public_key(name))?;
+        let settings = Setting::list_by_type(conn, THEME_SETTING_TYPE)?;
+        let names = custom_scheme_names(setting_value(&settings, CUSTOM_THEME_LIST_SETTING), &settings);
+        save_name_list(conn, &names)?;
+        Ok(())
+    })
+}
+
+/// Make a built-in or custom scheme with admin colors the active admin scheme
+pub fn activate_scheme(conn: &mut PgConnection, name: &str) -> Result<(), AppError> {
+    if !is_built_in(name) && get_scheme(conn, name)?.admin.is_none() {
+        return Err(AppError::ValidationError(format!("Color scheme '{}' has no admin colors", name)));
+    }
+    Setting::upsert(conn, CURRENT_ADMIN_THEME_SETTING, name, THEME_SETTING_TYPE, Some("Current active admin theme".to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setting(key: &str, value: &str) -> Setting {
+        Setting {
+            id: 0,
+            setting_key: key.to_string(),
+            setting_value: Some(value.to_string()),
+            created_at: None,
+            setting_type: THEME_SETTING_TYPE.to_string(),
+            description: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_scheme_name() {
+        assert_eq!(validate_scheme_name("  Ocean ").unwrap(), "Ocean");
+        assert!(validate_scheme_name("   ").is_err());
+        assert!(validate_scheme_name("Light Preset").is_err());
+        assert!(validate_scheme_name("a/b").is_err());
+        assert!(validate_scheme_name(&"x".repeat(MAX_SCHEME_NAME_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_custom_scheme_names_keeps_order_and_adds_unlisted() {
+        let settings = vec![
+            setting("theme_admin_Ocean", "{}"),
+            setting("theme_public_Forest", "{}"),
+            setting("theme_admin_Forest", "{}"),
+            setting("theme_admin_Legacy", "{}"),
+        ];
+        // Built-ins and names without data are dropped from the stored list
+        let list = r#"["Light Preset", "Dark Preset", "Forest", "Gone", "Ocean"]"#;
+        assert_eq!(custom_scheme_names(Some(list), &settings), vec!["Forest", "Ocean", "Legacy"]);
+        assert_eq!(custom_scheme_names(Some("not json"), &settings), vec!["Ocean", "Forest", "Legacy"]);
+    }
+
+    #[test]
+    fn test_resolve_current_falls_back_when_missing() {
+        let custom = vec!["Ocean".to_string()];
+        assert_eq!(resolve_current(Some("Ocean"), &custom), "Ocean");
+        assert_eq!(resolve_current(Some("Dark Preset"), &custom), "Dark Preset");
+        assert_eq!(resolve_current(Some("Deleted"), &custom), DEFAULT_SCHEME);
+        assert_eq!(resolve_current(None, &custom), DEFAULT_SCHEME);
+    }
+}
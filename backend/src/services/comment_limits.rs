@@ -0,0 +1,215 @@
+//! Admin-configurable limits on comment length and links
+//!
+//! Length is counted in characters and links are counted as occurrences of
+//! `http://`, `https://` or a bare `www.`, which is how the comment form's
+//! live counter measures them too.
+//!
+//! `comments_require_verified_email` additionally keeps users with an
+//! unverified email address from commenting; admins and editors are exempt.
+//! With `comments_hold_first_for_approval` on, a user's comments are held as
+//! pending until a moderator has approved one of them; from then on they
+//! post immediately. Admins and editors are exempt from this too.
+//! The thread's default sort order and the Gravatar settings travel with the
+//! limits so the comment section can use them without another request.
+
+use diesel::prelude::*;
+use serde::Serialize;
+use tracing::warn;
+use crate::middleware::{errors::AppError, validation::validate_text_content};
+use crate::models::Setting;
+use crate::services::{comment_moderation::{APPROVED, PENDING}, comment_sort::CommentSort, gravatar::GravatarConfig};
+
+pub const COMMENT_MAX_LENGTH_SETTING: &str = "comment_max_length";
+pub const COMMENT_MAX_LINKS_SETTING: &str = "comment_max_links";
+pub const COMMENT_LIMIT_SETTING_KEYS: [&str; 2] = [COMMENT_MAX_LENGTH_SETTING, COMMENT_MAX_LINKS_SETTING];
+pub const COMMENTS_REQUIRE_VERIFIED_EMAIL_SETTING: &str = "comments_require_verified_email";
+pub const HOLD_FIRST_COMMENT_SETTING: &str = "comments_hold_first_for_approval";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CommentLimits {
+    pub max_length: usize,
+    /// 0 disallows links entirely
+    pub max_links: usize,
+    pub require_verified_email: bool,
+    /// Hold comments until the author has one approved comment
+    pub hold_first_comment: bool,
+    pub default_sort: CommentSort,
+    pub gravatar: GravatarConfig,
+}
+
+impl Default for CommentLimits {
+    fn default() -> Self {
+        Self {
+            max_length: 2000,
+            max_links: 3,
+            require_verified_email: false,
+            hold_first_comment: false,
+            default_sort: CommentSort::default(),
+            gravatar: GravatarConfig::default(),
+        }
+    }
+}
+
+/// Count links the same way the comment form does
+pub fn count_links(content: &str) -> usize {
+    let lower = content.to_lowercase();
+    let schemes = lower.matches("http://").count() + lower.matches("https://").count();
+    // `www.` directly after a scheme belongs to a link already counted
+    let bare_www = lower.match_indices("www.")
+        .filter(|(index, _)| !lower[..*index].ends_with("://"))
+        .count();
+    schemes + bare_www
+}
+
+impl CommentLimits {
+    /// Validate a comment limit setting value before it is stored
+    pub fn validate_setting(key: &str, value: &str) -> Result<usize, AppError> {
+        let (min, max) = match key {
+            COMMENT_MAX_LENGTH_SETTING => (1, 20_000),
+            COMMENT_MAX_LINKS_SETTING => (0, 50),
+            _ => return Err(AppError::ValidationError(format!("Unknown comment setting: {}", key))),
+        };
+
+        let parsed = value.trim().parse::<usize>()
+            .map_err(|_| AppError::ValidationError(format!("{} must be a whole number", key)))?;
+        if parsed < min || parsed > max {
+            return Err(AppError::ValidationError(format!("{} must be between {} and {}", key, min, max)));
+        }
+        Ok(parsed)
+    }
+
+    /// Current limits from settings; missing or invalid values use the defaults
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut limits = Self::default();
+        for key in COMMENT_LIMIT_SETTING_KEYS {
+            let Some(value) = Setting::find_by_key(conn, key)?.and_then(|s| s.setting_value) else {
+                continue;
+            };
+            match Self::validate_setting(key, &value) {
+                Ok(parsed) if key == COMMENT_MAX_LENGTH_SETTING => limits.max_length = parsed,
+                Ok(parsed) => limits.max_links = parsed,
+                Err(e) => warn!("Ignoring comment setting {}: {}", key, e),
+            }
+        }
+        limits.require_verified_email = Setting::find_by_key(conn, COMMENTS_REQUIRE_VERIFIED_EMAIL_SETTING)?
+            .and_then(|s| s.setting_value)
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"));
+        limits.hold_first_comment = Setting::find_by_key(conn, HOLD_FIRST_COMMENT_SETTING)?
+            .and_then(|s| s.setting_value)
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"));
+        limits.default_sort = CommentSort::load_default(conn)?;
+        limits.gravatar = GravatarConfig::load(conn)?;
+        Ok(limits)
+    }
+
+    /// Check that a user may comment at all
+    pub fn check_author(&self, role: &str, email_verified: bool) -> Result<(), AppError> {
+        let exempt = role == "admin" || role == "editor";
+        if self.require_verified_email && !email_verified && !exempt {
+            return Err(AppError::ValidationError(
+                "Please verify your email address before commenting".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Status of a new comment by a user who has or hasn't had one approved
+    pub fn initial_status(&self, role: &str, has_approved_comment: bool) -> &'static str {
+        let exempt = role == "admin" || role == "editor";
+        if self.hold_first_comment && !has_approved_comment && !exempt {
+            PENDING
+        } else {
+            APPROVED
+        }
+    }
+
+    /// Check a comment against these limits and the general content rules
+    pub fn validate(&self, content: &str) -> Result<(), AppError> {
+        let length = content.trim().chars().count();
+        if length > self.max_length {
+            return Err(AppError::ValidationError(format!(
+                "Comment is {} characters long; the maximum is {}",
+                length, self.max_length
+            )));
+        }
+
+        let links = count_links(content);
+        if links > self.max_links {
+            return Err(AppError::ValidationError(match self.max_links {
+                0 => "Comments cannot contain links".to_string(),
+                1 => format!("Comment contains {} links; at most 1 is allowed", links),
+                max => format!("Comment contains {} links; at most {} are allowed", links, max),
+            }));
+        }
+
+        // Length is already enforced in characters above
+        validate_text_content(content, content.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::errors::error_message;
+
+    #[test]
+    fn test_count_links() {
+        assert_eq!(count_links("no links here"), 0);
+        assert_eq!(count_links("see https://example.com and http://foo.org"), 2);
+        assert_eq!(count_links("visit https://www.example.com or WWW.other.net"), 2);
+    }
+
+    #[test]
+    fn test_length_is_counted_in_characters() {
+        let limits = CommentLimits { max_length: 5, max_links: 3, ..CommentLimits::default() };
+        assert!(limits.validate("héllo").is_ok());
+        assert_eq!(error_message(limits.validate("héllo!")), "Comment is 6 characters long; the maximum is 5");
+    }
+
+    #[test]
+    fn test_link_limit_messages() {
+        let limits = CommentLimits { max_length: 2000, max_links: 1, ..CommentLimits::default() };
+        assert!(limits.validate("one link: https://example.com").is_ok());
+        assert_eq!(
+            error_message(limits.validate("https://a.com https://b.com")),
+            "Comment contains 2 links; at most 1 is allowed"
+        );
+
+        let no_links = CommentLimits { max_length: 2000, max_links: 0, ..CommentLimits::default() };
+        assert_eq!(error_message(no_links.validate("www.spam.example")), "Comments cannot contain links");
+    }
+
+    #[test]
+    fn test_verified_email_requirement() {
+        let open = CommentLimits::default();
+        assert!(open.check_author("user", false).is_ok());
+
+        let strict = CommentLimits { require_verified_email: true, ..CommentLimits::default() };
+        assert!(strict.check_author("user", true).is_ok());
+        assert_eq!(error_message(strict.check_author("user", false)), "Please verify your email address before commenting");
+        assert!(strict.check_author("editor", false).is_ok());
+        assert!(strict.check_author("admin", false).is_ok());
+    }
+
+    #[test]
+    fn test_first_comment_is_held() {
+        let open = CommentLimits::default();
+        assert_eq!(open.initial_status("user", false), APPROVED);
+
+        let held = CommentLimits { hold_first_comment: true, ..CommentLimits::default() };
+        // First-time commenter waits for a moderator
+        assert_eq!(held.initial_status("user", false), PENDING);
+        // Returning commenter with an approved comment posts immediately
+        assert_eq!(held.initial_status("user", true), APPROVED);
+        assert_eq!(held.initial_status("editor", false), APPROVED);
+        assert_eq!(held.initial_status("admin", false), APPROVED);
+    }
+
+    #[test]
+    fn test_validate_setting_ranges() {
+        assert_eq!(CommentLimits::validate_setting(COMMENT_MAX_LINKS_SETTING, "0").unwrap(), 0);
+        assert!(CommentLimits::validate_setting(COMMENT_MAX_LENGTH_SETTING, "0").is_err());
+        assert!(CommentLimits::validate_setting(COMMENT_MAX_LENGTH_SETTING, "abc").is_err());
+        assert!(CommentLimits::validate_setting(COMMENT_MAX_LINKS_SETTING, "51").is_err());
+    }
+}
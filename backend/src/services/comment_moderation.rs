@@ -0,0 +1,80 @@
+//! Comment moderation actions
+//!
+//! A comment is `approved` (shown publicly, the default for new comments),
+//! `pending` (awaiting a moderator), `rejected` or `spam` (all three hidden
+//! from public threads and counts). Spam is kept apart from rejected so a
+//! spam wave can be reviewed and purged on its own. Deleting removes the
+//! comment outright.
+
+use serde::{Deserialize, Serialize};
+use crate::middleware::errors::AppError;
+
+pub const APPROVED: &str = "approved";
+pub const PENDING: &str = "pending";
+pub const REJECTED: &str = "rejected";
+pub const SPAM: &str = "spam";
+
+/// Most comments one bulk request may act on
+pub const MAX_BULK_COMMENTS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationAction {
+    Approve,
+    Reject,
+    Spam,
+    Delete,
+}
+
+impl ModerationAction {
+    /// The status the action sets; `None` for delete
+    pub fn status(self) -> Option<&'static str> {
+        match self {
+            Self::Approve => Some(APPROVED),
+            Self::Reject => Some(REJECTED),
+            Self::Spam => Some(SPAM),
+            Self::Delete => None,
+        }
+    }
+}
+
+/// The ids of a bulk request with duplicates dropped, in request order
+pub fn bulk_ids(ids: &[i32]) -> Result<Vec<i32>, AppError> {
+    let mut unique = Vec::with_capacity(ids.len());
+    for &id in ids {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+    if unique.is_empty() {
+        return Err(AppError::ValidationError("Select at least one comment".to_string()));
+    }
+    if unique.len() > MAX_BULK_COMMENTS {
+        return Err(AppError::ValidationError(format!(
+            "At most {} comments can be moderated at once", MAX_BULK_COMMENTS
+        )));
+    }
+    Ok(unique)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_actions_map_to_statuses() {
+        let action: ModerationAction = serde_json::from_str("\"spam\"").unwrap();
+        assert_eq!(action.status(), Some(SPAM));
+        assert_eq!(ModerationAction::Approve.status(), Some(APPROVED));
+        assert_eq!(ModerationAction::Delete.status(), None);
+        assert!(serde_json::from_str::<ModerationAction>("\"archive\"").is_err());
+    }
+
+    #[test]
+    fn test_bulk_ids() {
+        assert_eq!(bulk_ids(&[3, 1, 3, 2, 1]).unwrap(), vec![3, 1, 2]);
+        assert!(bulk_ids(&[]).is_err());
+        let too_many: Vec<i32> = (0..=MAX_BULK_COMMENTS as i32).collect();
+        assert!(bulk_ids(&too_many).is_err());
+    }
+}
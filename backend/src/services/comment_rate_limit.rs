@@ -0,0 +1,192 @@
+//! Per-user and per-IP limits on how fast comments can be posted
+//!
+//! Recent submissions are kept in memory as timestamps per key (`user:<id>`
+//! and `ip:<addr>`), so limits reset on restart and are not shared between
+//! instances. A submission counts against every key it was checked under.
+
+use diesel::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const COMMENT_RATE_PER_MINUTE_SETTING: &str = "comment_rate_per_minute";
+pub const COMMENT_RATE_PER_HOUR_SETTING: &str = "comment_rate_per_hour";
+pub const COMMENT_RATE_SETTING_KEYS: [&str; 2] = [COMMENT_RATE_PER_MINUTE_SETTING, COMMENT_RATE_PER_HOUR_SETTING];
+
+const MINUTE: Duration = Duration::from_secs(60);
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentRateLimits {
+    /// 0 disables the per-minute limit
+    pub per_minute: usize,
+    /// 0 disables the per-hour limit
+    pub per_hour: usize,
+}
+
+impl Default for CommentRateLimits {
+    fn default() -> Self {
+        Self {
+            per_minute: 3,
+            per_hour: 20,
+        }
+    }
+}
+
+impl CommentRateLimits {
+    /// Validate a comment rate setting value before it is stored
+    pub fn validate_setting(key: &str, value: &str) -> Result<usize, AppError> {
+        let max = match key {
+            COMMENT_RATE_PER_MINUTE_SETTING => 100,
+            COMMENT_RATE_PER_HOUR_SETTING => 1_000,
+            _ => return Err(AppError::ValidationError(format!("Unknown comment rate setting: {}", key))),
+        };
+
+        let parsed = value.trim().parse::<usize>()
+            .map_err(|_| AppError::ValidationError(format!("{} must be a whole number", key)))?;
+        if parsed > max {
+            return Err(AppError::ValidationError(format!("{} must be between 0 and {}", key, max)));
+        }
+        Ok(parsed)
+    }
+
+    /// Current limits from settings; missing or invalid values use the defaults
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut limits = Self::default();
+        for key in COMMENT_RATE_SETTING_KEYS {
+            let Some(value) = Setting::find_by_key(conn, key)?.and_then(|s| s.setting_value) else {
+                continue;
+            };
+            match Self::validate_setting(key, &value) {
+                Ok(parsed) if key == COMMENT_RATE_PER_MINUTE_SETTING => limits.per_minute = parsed,
+                Ok(parsed) => limits.per_hour = parsed,
+                Err(e) => warn!("Ignoring comment rate setting {}: {}", key, e),
+            }
+        }
+        Ok(limits)
+    }
+
+    fn windows(&self) -> [(usize, Duration); 2] {
+        [(self.per_minute, MINUTE), (self.per_hour, HOUR)]
+    }
+}
+
+/// Shared record of recent comment submissions
+#[derive(Clone, Default)]
+pub struct CommentRateLimiter {
+    recent: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+}
+
+impl CommentRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a submission under `keys`, or refuse it with 429 if any key is
+    /// already at one of its limits. Refused submissions are not recorded.
+    pub fn check(&self, keys: &[String], limits: CommentRateLimits) -> Result<(), AppError> {
+        self.check_at(keys, limits, Instant::now())
+            .map_err(|wait| {
+                // Round up so a retry at the advertised time is let through
+                let retry_after_secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+                AppError::TooManyRequests {
+                    message: format!("You're commenting too quickly; try again in {} seconds", retry_after_secs.max(1)),
+                    retry_after_secs: retry_after_secs.max(1),
+                }
+            })
+    }
+
+    /// Returns how long to wait when the submission is refused
+    fn check_at(&self, keys: &[String], limits: CommentRateLimits, now: Instant) -> Result<(), Duration> {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Forget anything older than the longest window
+        recent.retain(|_, times| {
+            while times.front().is_some_and(|t| now.duration_since(*t) >= HOUR) {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+
+        let wait = keys.iter()
+            .filter_map(|key| recent.get(key))
+            .flat_map(|times| limits.windows().into_iter().filter_map(move |(limit, window)| {
+                let in_window: Vec<&Instant> = times.iter()
+                    .filter(|t| now.duration_since(**t) < window)
+                    .collect();
+                // The oldest submission that must expire before there is room again
+                (limit > 0 && in_window.len() >= limit)
+                    .then(|| window - now.duration_since(*in_window[in_window.len() - limit]))
+            }))
+            .max();
+        if let Some(wait) = wait {
+            return Err(wait);
+        }
+
+        for key in keys {
+            recent.entry(key.clone()).or_default().push_back(now);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_per_minute_limit_resets_after_window() {
+        let limiter = CommentRateLimiter::new();
+        let limits = CommentRateLimits { per_minute: 2, per_hour: 0 };
+        let user = keys(&["user:1"]);
+        let start = Instant::now();
+
+        assert!(limiter.check_at(&user, limits, start).is_ok());
+        assert!(limiter.check_at(&user, limits, start + Duration::from_secs(10)).is_ok());
+        let wait = limiter.check_at(&user, limits, start + Duration::from_secs(20)).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(40));
+
+        assert!(limiter.check_at(&user, limits, start + MINUTE).is_ok());
+    }
+
+    #[test]
+    fn test_any_key_at_its_limit_refuses_without_recording() {
+        let limiter = CommentRateLimiter::new();
+        let limits = CommentRateLimits { per_minute: 0, per_hour: 1 };
+        let start = Instant::now();
+
+        assert!(limiter.check_at(&keys(&["user:1", "ip:10.0.0.1"]), limits, start).is_ok());
+        // A different user behind the same address is still limited
+        assert!(limiter.check_at(&keys(&["user:2", "ip:10.0.0.1"]), limits, start).is_err());
+        // ...and the refused attempt didn't count against user 2
+        assert!(limiter.check_at(&keys(&["user:2", "ip:10.0.0.2"]), limits, start).is_ok());
+    }
+
+    #[test]
+    fn test_refusal_carries_retry_after() {
+        let limiter = CommentRateLimiter::new();
+        let limits = CommentRateLimits { per_minute: 1, per_hour: 0 };
+        let user = keys(&["user:1"]);
+
+        assert!(limiter.check(&user, limits).is_ok());
+        match limiter.check(&user, limits) {
+            Err(AppError::TooManyRequests { retry_after_secs, .. }) => assert!((1..=60).contains(&retry_after_secs)),
+            other => panic!("expected a rate limit error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_setting_ranges() {
+        assert_eq!(CommentRateLimits::validate_setting(COMMENT_RATE_PER_MINUTE_SETTING, "0").unwrap(), 0);
+        assert!(CommentRateLimits::validate_setting(COMMENT_RATE_PER_MINUTE_SETTING, "101").is_err());
+        assert!(CommentRateLimits::validate_setting(COMMENT_RATE_PER_HOUR_SETTING, "-1").is_err());
+        assert!(CommentRateLimits::validate_setting("comment_max_length", "5").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Order of public comment threads
+//!
+//! `GET /api/comments/public` takes a `sort` parameter; without one the
+//! `comments_default_sort` setting decides. Comments have no replies yet, so
+//! the whole thread is sorted as one list. `top` is reserved for when
+//! comments get reactions to rank them by.
+
+use diesel::prelude::*;
+use serde::Serialize;
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const COMMENT_SORT_SETTING: &str = "comments_default_sort";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentSort {
+    #[default]
+    Oldest,
+    Newest,
+}
+
+impl CommentSort {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "oldest" => Ok(Self::Oldest),
+            "newest" => Ok(Self::Newest),
+            "top" => Err(AppError::ValidationError(
+                "Sorting by top comments needs comment reactions, which aren't available yet".to_string()
+            )),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown comment sort '{}'; use oldest or newest", other
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Oldest => "oldest",
+            Self::Newest => "newest",
+        }
+    }
+
+    /// The site's default order; a missing or invalid setting means oldest
+    pub fn load_default(conn: &mut PgConnection) -> QueryResult<Self> {
+        let value = Setting::find_by_key(conn, COMMENT_SORT_SETTING)?.and_then(|s| s.setting_value);
+        Ok(Self::from_setting(value.as_deref()))
+    }
+
+    fn from_setting(value: Option<&str>) -> Self {
+        match value.map(Self::parse) {
+            Some(Ok(sort)) => sort,
+            Some(Err(e)) => {
+                warn!("Ignoring setting {}: {}", COMMENT_SORT_SETTING, e);
+                Self::default()
+            }
+            None => Self::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sort_modes() {
+        assert_eq!(CommentSort::parse("oldest").unwrap(), CommentSort::Oldest);
+        assert_eq!(CommentSort::parse(" Newest ").unwrap(), CommentSort::Newest);
+        assert!(CommentSort::parse("random").is_err());
+
+        match CommentSort::parse("top") {
+            Err(AppError::ValidationError(message)) => assert!(message.contains("reactions")),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_through_setting_value() {
+        for sort in [CommentSort::Oldest, CommentSort::Newest] {
+            assert_eq!(CommentSort::parse(sort.as_str()).unwrap(), sort);
+            assert_eq!(CommentSort::from_setting(Some(sort.as_str())), sort);
+        }
+    }
+
+    #[test]
+    fn test_default_falls_back_to_oldest() {
+        assert_eq!(CommentSort::from_setting(None), CommentSort::Oldest);
+        assert_eq!(CommentSort::from_setting(Some("top")), CommentSort::Oldest);
+        assert_eq!(CommentSort::from_setting(Some("")), CommentSort::Oldest);
+    }
+}
@@ -0,0 +1,155 @@
+//! One active component template per type
+//!
+//! The public layout renders the active template of each component type
+//! (`find(|t| t.component_type == X && t.is_active)`), so two active headers
+//! would leave it to chance which one shows. Creating, updating, toggling or
+//! applying a layout that leaves a template active first deactivates the
+//! other active templates of its type, in the same transaction. A partial
+//! unique index on `component_templates (component_type) WHERE is_active`
+//! backs this up in the database, which is why the others go first.
+
+use diesel::prelude::*;
+use crate::models::ComponentTemplate;
+
+/// Active templates of `component_type` that have to give way to `keep_id`
+/// (`None` for a template not stored yet)
+pub fn superseded(active: &[ComponentTemplate], component_type: &str, keep_id: Option<i32>) -> Vec<i32> {
+    active.iter()
+        .filter(|t| t.is_active && t.component_type == component_type && Some(t.id) != keep_id)
+        .map(|t| t.id)
+        .collect()
+}
+
+/// Deactivate the other active templates of a type before one is saved
+/// active; returns how many were deactivated
+pub fn deactivate_superseded(conn: &mut PgConnection, component_type: &str, keep_id: Option<i32>) -> QueryResult<usize> {
+    let active = ComponentTemplate::find_by_type(conn, component_type)?;
+    let ids = superseded(&active, component_type, keep_id);
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    ComponentTemplate::deactivate(conn, &ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(id: i32, component_type: &str, is_active: bool) -> ComponentTemplate {
+        ComponentTemplate {
+            id,
+            name: format!("Template {}", id),
+            component_type: component_type.to_string(),
+            template_data: serde_json::json!({}),
+            breakpoints: serde_json::json!({}),
+            width_setting: None,
+            max_width: None,
+            is_default: false,
+            is_active,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    // Save `saved` the way the controllers do: supersede, then store
+    fn save(templates: &mut Vec<ComponentTemplate>, saved: ComponentTemplate) {
+        if saved.is_active {
+            let ids = superseded(templates, &saved.component_type, Some(saved.id));
+            for t in templates.iter_mut().filter(|t| ids.contains(&t.id)) {
+                t.is_active = false;
+            }
+        }
+        templates.retain(|t| t.id != saved.id);
+        templates.push(saved);
+    }
+
+    fn active_ids(templates: &[ComponentTemplate], component_type: &str) -> Vec<i32> {
+        templates.iter().filter(|t| t.is_active && t.component_type == component_type).map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn test_second_active_header_replaces_the_first() {
+        let mut templates = vec![template(9, "footer", true)];
+        save(&mut templates, template(1, "header", true));
+        save(&mut templates, template(2, "header", true));
+        assert_eq!(active_ids(&templates, "header"), vec![2]);
+        assert_eq!(active_ids(&templates, "footer"), vec![9]);
+
+        // Reactivating the first hands the slot back
+        save(&mut templates, template(1, "header", true));
+        assert_eq!(active_ids(&templates, "header"), vec![1]);
+    }
+
+    #[test]
+    fn test_inactive_saves_and_other_types_are_left_alone() {
+        let active = vec![template(1, "header", true), template(2, "footer", true)];
+        assert_eq!(superseded(&active, "header", Some(1)), Vec::<i32>::new());
+        assert_eq!(superseded(&active, "header", None), vec![1]);
+        assert_eq!(superseded(&active, "sidebar", None), Vec::<i32>::new());
+
+        let mut templates = active;
+        save(&mut templates, template(3, "header", false));
+        assert_eq!(active_ids(&templates, "header"), vec![1]);
+    }
+
+    /// Runs against a scratch database:
+    /// `TEST_DATABASE_URL=postgres://... cargo test -- --ignored`
+    #[test]
+    #[ignore = "needs a database in TEST_DATABASE_URL"]
+    fn test_single_active_template_in_database() {
+        use crate::models::NewComponentTemplate;
+        use crate::schema::component_templates;
+
+        let pool = crate::test_support::db_pool();
+        let mut conn = pool.get().unwrap();
+        // A type of its own, so the layout's real templates are untouched
+        let component_type = format!("test_{}", uuid::Uuid::new_v4().simple());
+        let new_template = |name: &str| NewComponentTemplate {
+            name: name.to_string(),
+            component_type: component_type.clone(),
+            template_data: serde_json::json!({}),
+            breakpoints: serde_json::json!({}),
+            width_setting: None,
+            max_width: None,
+            is_default: false,
+            is_active: true,
+        };
+        // What create_component_template does
+        let create = |conn: &mut PgConnection, name: &str| conn.transaction(|conn| {
+            deactivate_superseded(conn, &component_type, None)?;
+            diesel::insert_into(component_templates::table)
+                .values(&new_template(name))
+                .get_result::<ComponentTemplate>(conn)
+        });
+        let active = |conn: &mut PgConnection| -> Vec<i32> {
+            ComponentTemplate::find_by_type(conn, &component_type).unwrap().iter().map(|t| t.id).collect()
+        };
+
+        let first = create(&mut conn, "first").unwrap();
+        let second = create(&mut conn, "second").unwrap();
+        assert_eq!(active(&mut conn), vec![second.id]);
+
+        // Skipping the deactivation trips the partial unique index
+        let clash = diesel::insert_into(component_templates::table)
+            .values(&new_template("clash"))
+            .execute(&mut conn);
+        assert!(matches!(
+            clash,
+            Err(diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _))
+        ));
+        assert_eq!(active(&mut conn), vec![second.id]);
+
+        // Reactivating the first, as toggling it does, hands the slot back
+        conn.transaction(|conn| {
+            deactivate_superseded(conn, &component_type, Some(first.id))?;
+            diesel::update(component_templates::table.find(first.id))
+                .set(component_templates::is_active.eq(true))
+                .execute(conn)
+        }).unwrap();
+        assert_eq!(active(&mut conn), vec![first.id]);
+
+        diesel::delete(component_templates::table.filter(component_templates::component_type.eq(&component_type)))
+            .execute(&mut conn)
+            .unwrap();
+    }
+}
@@ -0,0 +1,197 @@
+//! Cursor pagination for lists that change while they're being paged
+//!
+//! Offset pages shift when rows are added or removed between requests, so a
+//! client can see an item twice or never. A cursor instead names the last
+//! row a client has seen by its `(created_at, id)` sort key, and the next
+//! page is everything that sorts after it. The token is opaque to clients:
+//! base64 of the key, with no meaning beyond "continue from here".
+//!
+//! Lists are ordered by `created_at` then ID, both in the list's direction.
+//! Rows without a `created_at` sort after all dated rows, ordered by ID, so
+//! they are still reached exactly once.
+
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, NaiveDateTime};
+use crate::middleware::errors::AppError;
+
+/// Sort key of the last row on a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: Option<NaiveDateTime>,
+    pub id: i32,
+}
+
+impl Cursor {
+    pub fn new(created_at: Option<NaiveDateTime>, id: i32) -> Self {
+        Self { created_at, id }
+    }
+
+    /// Opaque token for the `cursor` query parameter
+    pub fn encode(&self) -> String {
+        let timestamp = self.created_at
+            .map(|at| at.and_utc().timestamp_micros().to_string())
+            .unwrap_or_else(|| "-".to_string());
+        general_purpose::URL_SAFE_NO_PAD.encode(format!("{}:{}", timestamp, self.id))
+    }
+
+    pub fn decode(token: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::ValidationError("Invalid pagination cursor".to_string());
+        let bytes = general_purpose::URL_SAFE_NO_PAD.decode(token.trim()).map_err(|_| invalid())?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (timestamp, id) = text.split_once(':').ok_or_else(invalid)?;
+        let created_at = match timestamp {
+            "-" => None,
+            micros => Some(
+                micros.parse::<i64>().ok()
+                    .and_then(DateTime::from_timestamp_micros)
+                    .ok_or_else(invalid)?
+                    .naive_utc(),
+            ),
+        };
+        Ok(Self { created_at, id: id.parse().map_err(|_| invalid())? })
+    }
+}
+
+/// The `cursor` query parameter: absent means offset paging, empty means the
+/// first cursor page
+pub fn parse_cursor_param(param: Option<&str>) -> Result<Option<Option<Cursor>>, AppError> {
+    match param.map(str::trim) {
+        None => Ok(None),
+        Some("") => Ok(Some(None)),
+        Some(token) => Cursor::decode(token).map(|cursor| Some(Some(cursor))),
+    }
+}
+
+/// Trim rows fetched with a limit of `page_size + 1` down to one page, and
+/// return the cursor for the next page if there is one
+pub fn split_page<T>(mut rows: Vec<T>, page_size: usize, key: impl Fn(&T) -> Cursor) -> (Vec<T>, Option<String>) {
+    if rows.len() <= page_size {
+        return (rows, None);
+    }
+    rows.truncate(page_size);
+    let next_cursor = rows.last().map(|row| key(row).encode());
+    (rows, next_cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Order of a cursor-paged list; ties on `created_at` follow the same
+    /// direction by ID
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum SortDirection {
+        NewestFirst,
+        OldestFirst,
+    }
+
+    impl Cursor {
+        /// Whether a row with this sort key belongs on a page after the cursor;
+        /// the rule the list queries' `WHERE` clauses express
+        fn precedes(&self, direction: SortDirection, created_at: Option<NaiveDateTime>, id: i32) -> bool {
+            let later_id = match direction {
+                SortDirection::NewestFirst => id < self.id,
+                SortDirection::OldestFirst => id > self.id,
+            };
+            match (self.created_at, created_at) {
+                (Some(cursor_at), Some(row_at)) if row_at == cursor_at => later_id,
+                (Some(cursor_at), Some(row_at)) => match direction {
+                    SortDirection::NewestFirst => row_at < cursor_at,
+                    SortDirection::OldestFirst => row_at > cursor_at,
+                },
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => later_id,
+            }
+        }
+    }
+
+    fn at(s: &str) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok()
+    }
+
+    /// One page the way the list queries build it: sort, skip to the cursor,
+    /// fetch one extra row
+    fn page(rows: &[Cursor], direction: SortDirection, cursor: Option<Cursor>, size: usize) -> (Vec<Cursor>, Option<String>) {
+        let mut sorted = rows.to_vec();
+        sorted.sort_by(|a, b| {
+            let by_date = match (a.created_at, b.created_at) {
+                (Some(x), Some(y)) => match direction {
+                    SortDirection::NewestFirst => y.cmp(&x),
+                    SortDirection::OldestFirst => x.cmp(&y),
+                },
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            };
+            by_date.then(match direction {
+                SortDirection::NewestFirst => b.id.cmp(&a.id),
+                SortDirection::OldestFirst => a.id.cmp(&b.id),
+            })
+        });
+        let fetched: Vec<Cursor> = sorted.into_iter()
+            .filter(|row| cursor.is_none_or(|c| c.precedes(direction, row.created_at, row.id)))
+            .take(size + 1)
+            .collect();
+        split_page(fetched, size, |row| *row)
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let cursor = Cursor::new(at("2025-08-15 12:30:45"), 42);
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+
+        let undated = Cursor::new(None, 7);
+        assert_eq!(Cursor::decode(&undated.encode()).unwrap(), undated);
+
+        assert!(Cursor::decode("not a cursor!").is_err());
+        assert!(Cursor::decode(&general_purpose::URL_SAFE_NO_PAD.encode("12:abc")).is_err());
+    }
+
+    #[test]
+    fn test_parse_cursor_param() {
+        assert_eq!(parse_cursor_param(None).unwrap(), None);
+        assert_eq!(parse_cursor_param(Some("")).unwrap(), Some(None));
+        let cursor = Cursor::new(at("2025-08-15 12:00:00"), 3);
+        assert_eq!(parse_cursor_param(Some(&cursor.encode())).unwrap(), Some(Some(cursor)));
+        assert!(parse_cursor_param(Some("@@")).is_err());
+    }
+
+    #[test]
+    fn test_page_boundaries_break_ties_by_id() {
+        // Three rows share a timestamp across the page boundary
+        let rows = vec![
+            Cursor::new(at("2025-08-15 10:00:00"), 1),
+            Cursor::new(at("2025-08-15 11:00:00"), 2),
+            Cursor::new(at("2025-08-15 11:00:00"), 3),
+            Cursor::new(at("2025-08-15 11:00:00"), 4),
+            Cursor::new(None, 5),
+        ];
+        let (first, next) = page(&rows, SortDirection::NewestFirst, None, 2);
+        assert_eq!(first.iter().map(|r| r.id).collect::<Vec<_>>(), vec![4, 3]);
+        let (second, next) = page(&rows, SortDirection::NewestFirst, Some(Cursor::decode(&next.unwrap()).unwrap()), 2);
+        assert_eq!(second.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2, 1]);
+        let (third, next) = page(&rows, SortDirection::NewestFirst, Some(Cursor::decode(&next.unwrap()).unwrap()), 2);
+        assert_eq!(third.iter().map(|r| r.id).collect::<Vec<_>>(), vec![5]);
+        assert!(next.is_none());
+
+        let (oldest, _) = page(&rows, SortDirection::OldestFirst, None, 5);
+        assert_eq!(oldest.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_pages_stay_stable_when_rows_change() {
+        let mut rows: Vec<Cursor> = (1..=6)
+            .map(|id| Cursor::new(at(&format!("2025-08-15 10:0{}:00", id)), id))
+            .collect();
+        let (first, next) = page(&rows, SortDirection::NewestFirst, None, 3);
+        assert_eq!(first.iter().map(|r| r.id).collect::<Vec<_>>(), vec![6, 5, 4]);
+
+        // A new row arrives and one already seen is deleted before page two
+        rows.push(Cursor::new(at("2025-08-15 10:09:00"), 7));
+        rows.retain(|row| row.id != 5);
+        let (second, next) = page(&rows, SortDirection::NewestFirst, Some(Cursor::decode(&next.unwrap()).unwrap()), 3);
+        assert_eq!(second.iter().map(|r| r.id).collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert!(next.is_none());
+    }
+}
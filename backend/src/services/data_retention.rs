@@ -0,0 +1,280 @@
+//! Data retention: purging old sessions and stale tokens
+//!
+//! A background job runs every `RETENTION_INTERVAL_MINUTES` and removes
+//!
+//! - sessions that expired more than `retention_expired_sessions_days` ago,
+//!   so admins can still review recently ended sessions;
+//! - verification tokens and invites that were used or expired more than
+//!   `retention_stale_tokens_days` ago, as well as draft share links;
+//! - idempotency keys past their replay window;
+//! - audit log entries older than `retention_audit_log_days`.
+//!
+//! Deletes run in batches of `PURGE_BATCH_SIZE` rows, each its own statement,
+//! so a large backlog never holds locks on a whole table. Admins can also
+//! run the job on demand.
+
+use std::sync::Arc;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use tracing::{error, info, warn};
+use crate::database::DbPool;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+use crate::schema::{audit_log, idempotency_keys, invites, post_share_links, sessions, users};
+use crate::services::idempotency::KEY_TTL_HOURS;
+
+pub const SESSION_RETENTION_SETTING: &str = "retention_expired_sessions_days";
+pub const TOKEN_RETENTION_SETTING: &str = "retention_stale_tokens_days";
+pub const AUDIT_LOG_RETENTION_SETTING: &str = "retention_audit_log_days";
+pub const RETENTION_SETTING_KEYS: [&str; 3] = [SESSION_RETENTION_SETTING, TOKEN_RETENTION_SETTING, AUDIT_LOG_RETENTION_SETTING];
+
+/// Longest retention period accepted, about ten years
+pub const MAX_RETENTION_DAYS: i64 = 3650;
+/// Rows removed per statement
+pub const PURGE_BATCH_SIZE: i64 = 1000;
+/// How often the background job runs
+const RETENTION_INTERVAL_MINUTES: u64 = 60;
+
+/// How long expired data is kept, in days; 0 purges it on the next run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub expired_sessions_days: i64,
+    pub stale_tokens_days: i64,
+    pub audit_log_days: i64,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            expired_sessions_days: 7,
+            // Long enough for the verify page to still say "expired" rather than "invalid"
+            stale_tokens_days: 7,
+            audit_log_days: 365,
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Validate a retention setting value before it is stored
+    pub fn validate_setting(key: &str, value: &str) -> Result<i64, AppError> {
+        if !RETENTION_SETTING_KEYS.contains(&key) {
+            return Err(AppError::ValidationError(format!("Unknown retention setting: {}", key)));
+        }
+        value.trim().parse::<i64>()
+            .ok()
+            .filter(|days| (0..=MAX_RETENTION_DAYS).contains(days))
+            .ok_or_else(|| AppError::ValidationError(format!(
+                "{} must be a whole number of days from 0 to {}", key, MAX_RETENTION_DAYS
+            )))
+    }
+
+    /// Current policy from settings; missing or invalid values use the defaults
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut policy = Self::default();
+        for key in RETENTION_SETTING_KEYS {
+            let Some(value) = Setting::find_by_key(conn, key)?.and_then(|s| s.setting_value) else {
+                continue;
+            };
+            match Self::validate_setting(key, &value) {
+                Ok(days) if key == SESSION_RETENTION_SETTING => policy.expired_sessions_days = days,
+                Ok(days) if key == AUDIT_LOG_RETENTION_SETTING => policy.audit_log_days = days,
+                Ok(days) => policy.stale_tokens_days = days,
+                Err(e) => warn!("Ignoring retention setting {}: {}", key, e),
+            }
+        }
+        Ok(policy)
+    }
+}
+
+/// Rows removed by one run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RetentionReport {
+    pub expired_sessions: usize,
+    pub verification_tokens: usize,
+    pub invites: usize,
+    pub idempotency_keys: usize,
+    pub share_links: usize,
+    pub audit_log_entries: usize,
+}
+
+impl RetentionReport {
+    pub fn total(&self) -> usize {
+        self.expired_sessions + self.verification_tokens + self.invites + self.idempotency_keys + self.share_links
+            + self.audit_log_entries
+    }
+}
+
+/// Repeat a batch until it comes back short; returns the rows affected
+fn in_batches(mut batch: impl FnMut() -> QueryResult<usize>) -> QueryResult<usize> {
+    let mut total = 0;
+    loop {
+        let affected = batch()?;
+        total += affected;
+        if (affected as i64) < PURGE_BATCH_SIZE {
+            return Ok(total);
+        }
+    }
+}
+
+fn purge_expired_sessions(conn: &mut PgConnection, cutoff: NaiveDateTime) -> QueryResult<usize> {
+    in_batches(|| {
+        let ids: Vec<i32> = sessions::table
+            .select(sessions::id)
+            .filter(sessions::expires_at.lt(cutoff))
+            .limit(PURGE_BATCH_SIZE)
+            .load(conn)?;
+        diesel::delete(sessions::table.filter(sessions::id.eq_any(ids))).execute(conn)
+    })
+}
+
+// A used token is left as an empty string; see `services::verification_tokens`
+fn purge_verification_tokens(conn: &mut PgConnection, cutoff: NaiveDateTime) -> QueryResult<usize> {
+    in_batches(|| {
+        let ids: Vec<i32> = users::table
+            .select(users::id)
+            .filter(
+                users::email_verification_expires_at.lt(cutoff)
+                    .or(users::email_verification_token.eq(""))
+            )
+            .limit(PURGE_BATCH_SIZE)
+            .load(conn)?;
+        diesel::update(users::table.filter(users::id.eq_any(ids)))
+            .set((
+                users::email_verification_token.eq(None::<String>),
+                users::email_verification_expires_at.eq(None::<NaiveDateTime>),
+            ))
+            .execute(conn)
+    })
+}
+
+fn purge_invites(conn: &mut PgConnection, cutoff: NaiveDateTime) -> QueryResult<usize> {
+    in_batches(|| {
+        let ids: Vec<i32> = invites::table
+            .select(invites::id)
+            .filter(invites::expires_at.lt(cutoff).or(invites::used_at.lt(cutoff)))
+            .limit(PURGE_BATCH_SIZE)
+            .load(conn)?;
+        diesel::delete(invites::table.filter(invites::id.eq_any(ids))).execute(conn)
+    })
+}
+
+fn purge_share_links(conn: &mut PgConnection, cutoff: NaiveDateTime) -> QueryResult<usize> {
+    in_batches(|| {
+        let ids: Vec<i32> = post_share_links::table
+            .select(post_share_links::id)
+            .filter(post_share_links::expires_at.lt(cutoff))
+            .limit(PURGE_BATCH_SIZE)
+            .load(conn)?;
+        diesel::delete(post_share_links::table.filter(post_share_links::id.eq_any(ids))).execute(conn)
+    })
+}
+
+fn purge_idempotency_keys(conn: &mut PgConnection, cutoff: NaiveDateTime) -> QueryResult<usize> {
+    in_batches(|| {
+        let ids: Vec<i32> = idempotency_keys::table
+            .select(idempotency_keys::id)
+            .filter(idempotency_keys::created_at.lt(cutoff))
+            .limit(PURGE_BATCH_SIZE)
+            .load(conn)?;
+        diesel::delete(idempotency_keys::table.filter(idempotency_keys::id.eq_any(ids))).execute(conn)
+    })
+}
+
+fn purge_audit_log(conn: &mut PgConnection, cutoff: NaiveDateTime) -> QueryResult<usize> {
+    in_batches(|| {
+        let ids: Vec<i32> = audit_log::table
+            .select(audit_log::id)
+            .filter(audit_log::created_at.lt(cutoff))
+            .limit(PURGE_BATCH_SIZE)
+            .load(conn)?;
+        diesel::delete(audit_log::table.filter(audit_log::id.eq_any(ids))).execute(conn)
+    })
+}
+
+/// Purge everything past its retention period
+pub fn run(conn: &mut PgConnection, policy: RetentionPolicy, now: NaiveDateTime) -> QueryResult<RetentionReport> {
+    let token_cutoff = now - Duration::days(policy.stale_tokens_days);
+    let report = RetentionReport {
+        expired_sessions: purge_expired_sessions(conn, now - Duration::days(policy.expired_sessions_days))?,
+        verification_tokens: purge_verification_tokens(conn, token_cutoff)?,
+        invites: purge_invites(conn, token_cutoff)?,
+        share_links: purge_share_links(conn, token_cutoff)?,
+        idempotency_keys: purge_idempotency_keys(conn, now - Duration::hours(KEY_TTL_HOURS))?,
+        audit_log_entries: purge_audit_log(conn, now - Duration::days(policy.audit_log_days))?,
+    };
+    if report.total() > 0 {
+        info!(
+            "Data retention: removed {} expired sessions, {} verification tokens, {} invites, {} share links, {} idempotency keys and {} audit log entries",
+            report.expired_sessions, report.verification_tokens, report.invites, report.share_links, report.idempotency_keys,
+            report.audit_log_entries
+        );
+    }
+    Ok(report)
+}
+
+/// Run the purge periodically with the current policy
+pub fn start_background_job(pool: Arc<DbPool>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        info!("Starting data retention background task (interval: {} minutes)", RETENTION_INTERVAL_MINUTES);
+        let mut timer = tokio::time::interval(std::time::Duration::from_secs(RETENTION_INTERVAL_MINUTES * 60));
+
+        loop {
+            timer.tick().await;
+
+            let pool = pool.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let mut conn = pool.get().map_err(|e| e.to_string())?;
+                let policy = RetentionPolicy::load(&mut conn).map_err(|e| e.to_string())?;
+                run(&mut conn, policy, Utc::now().naive_utc()).map_err(|e| e.to_string())
+            }).await;
+
+            match result {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => error!("Data retention run failed: {}", e),
+                Err(e) => error!("Data retention task panicked: {}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batches_repeat_until_short() {
+        let mut remaining = 2 * PURGE_BATCH_SIZE as usize + 17;
+        let mut statements = 0;
+        let total = in_batches(|| {
+            statements += 1;
+            let batch = remaining.min(PURGE_BATCH_SIZE as usize);
+            remaining -= batch;
+            Ok(batch)
+        }).unwrap();
+        assert_eq!(total, 2 * PURGE_BATCH_SIZE as usize + 17);
+        assert_eq!(statements, 3);
+
+        // An exact multiple needs one more, empty, batch to notice the end
+        let mut batches = vec![0, PURGE_BATCH_SIZE as usize];
+        assert_eq!(in_batches(|| Ok(batches.pop().unwrap())).unwrap(), PURGE_BATCH_SIZE as usize);
+    }
+
+    #[test]
+    fn test_validate_setting() {
+        assert_eq!(RetentionPolicy::validate_setting(SESSION_RETENTION_SETTING, " 30 ").unwrap(), 30);
+        assert_eq!(RetentionPolicy::validate_setting(TOKEN_RETENTION_SETTING, "0").unwrap(), 0);
+        assert_eq!(RetentionPolicy::validate_setting(AUDIT_LOG_RETENTION_SETTING, "730").unwrap(), 730);
+        assert!(RetentionPolicy::validate_setting(SESSION_RETENTION_SETTING, "-1").is_err());
+        assert!(RetentionPolicy::validate_setting(SESSION_RETENTION_SETTING, "3651").is_err());
+        assert!(RetentionPolicy::validate_setting(TOKEN_RETENTION_SETTING, "a week").is_err());
+        assert!(RetentionPolicy::validate_setting("retention_unknown_days", "5").is_err());
+    }
+
+    #[test]
+    fn test_report_total() {
+        let report = RetentionReport { expired_sessions: 3, verification_tokens: 1, invites: 2, idempotency_keys: 4, share_links: 5, audit_log_entries: 6 };
+        assert_eq!(report.total(), 21);
+        assert_eq!(RetentionReport::default().total(), 0);
+    }
+}
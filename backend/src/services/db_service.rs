@@ -4,18 +4,21 @@
 //! using tokio::task::spawn_blocking to avoid blocking the async runtime.
 
 use std::sync::Arc;
+use std::time::Instant;
 use crate::database::DbPool;
 use crate::middleware::errors::AppError;
+use crate::services::MetricsCollector;
 use tokio::task;
 
 /// Async database service that wraps blocking operations
 pub struct DbService {
     pool: Arc<DbPool>,
+    metrics: MetricsCollector,
 }
 
 impl DbService {
-    pub fn new(pool: Arc<DbPool>) -> Self {
-        Self { pool }
+    pub fn new(pool: Arc<DbPool>, metrics: MetricsCollector) -> Self {
+        Self { pool, metrics }
     }
 
     /// Execute a blocking database operation asynchronously
@@ -25,13 +28,16 @@ impl DbService {
         R: Send + 'static,
     {
         let pool = self.pool.clone();
+        let metrics = self.metrics.clone();
         
         task::spawn_blocking(move || {
             let mut conn = pool.get()
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?;
             
-            operation(&mut conn)
-                .map_err(|e| AppError::DatabaseError(e.to_string()))
+            let started = Instant::now();
+            let result = operation(&mut conn);
+            metrics.record_db_query(started.elapsed());
+            result.map_err(|e| AppError::DatabaseError(e.to_string()))
         })
         .await
         .map_err(|e| AppError::InternalError(format!("Task join error: {}", e)))?
@@ -44,13 +50,16 @@ impl DbService {
         R: Send + 'static,
     {
         let pool = self.pool.clone();
+        let metrics = self.metrics.clone();
         
         task::spawn_blocking(move || {
             let mut conn = pool.get()
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?;
             
-            operation(&mut conn)
-                .map_err(|e| AppError::DatabaseError(e.to_string()))
+            let started = Instant::now();
+            let result = operation(&mut conn);
+            metrics.record_db_query(started.elapsed());
+            result.map_err(|e| AppError::DatabaseError(e.to_string()))
         })
         .await
         .map_err(|e| AppError::InternalError(format!("Task join error: {}", e)))?
@@ -61,6 +70,7 @@ impl Clone for DbService {
     fn clone(&self) -> Self {
         Self {
             pool: self.pool.clone(),
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -0,0 +1,148 @@
+//! Allowed and blocked email domains for signup
+//!
+//! Both lists are settings holding comma- or newline-separated patterns. A
+//! pattern is either a domain (`example.com`, matching only that domain) or
+//! `*.` followed by a suffix (`*.ru`, matching any domain under it). Blocked
+//! patterns win; when the allow list is non-empty, only matching domains can
+//! sign up.
+
+use diesel::prelude::*;
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const ALLOWED_EMAIL_DOMAINS_SETTING: &str = "signup_allowed_email_domains";
+pub const BLOCKED_EMAIL_DOMAINS_SETTING: &str = "signup_blocked_email_domains";
+pub const EMAIL_DOMAIN_SETTING_KEYS: [&str; 2] = [ALLOWED_EMAIL_DOMAINS_SETTING, BLOCKED_EMAIL_DOMAINS_SETTING];
+
+const MAX_PATTERNS: usize = 200;
+
+/// Parse and normalize a domain pattern list, rejecting malformed entries
+pub fn parse_domain_list(value: &str) -> Result<Vec<String>, AppError> {
+    let patterns: Vec<String> = value
+        .split([',', '\n'])
+        .map(|pattern| pattern.trim().trim_end_matches('.').to_lowercase())
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+
+    if patterns.len() > MAX_PATTERNS {
+        return Err(AppError::ValidationError(format!(
+            "Domain lists can hold at most {} entries", MAX_PATTERNS
+        )));
+    }
+    for pattern in &patterns {
+        let domain = pattern.strip_prefix("*.").unwrap_or(pattern);
+        let valid = !domain.is_empty()
+            && domain.split('.').all(|label| {
+                !label.is_empty()
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+        if !valid {
+            return Err(AppError::ValidationError(format!(
+                "\"{}\" is not a domain or a *.suffix pattern", pattern
+            )));
+        }
+    }
+    Ok(patterns)
+}
+
+fn matches(pattern: &str, domain: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => domain.strip_suffix(suffix).is_some_and(|rest| rest.ends_with('.')),
+        None => domain == pattern,
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmailDomainPolicy {
+    pub allowed: Vec<String>,
+    pub blocked: Vec<String>,
+}
+
+impl EmailDomainPolicy {
+    /// Current lists from settings; an invalid stored list is ignored
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut policy = Self::default();
+        for key in EMAIL_DOMAIN_SETTING_KEYS {
+            let Some(value) = Setting::find_by_key(conn, key)?.and_then(|s| s.setting_value) else {
+                continue;
+            };
+            match parse_domain_list(&value) {
+                Ok(patterns) if key == ALLOWED_EMAIL_DOMAINS_SETTING => policy.allowed = patterns,
+                Ok(patterns) => policy.blocked = patterns,
+                Err(e) => warn!("Ignoring email domain setting {}: {}", key, e),
+            }
+        }
+        Ok(policy)
+    }
+
+    /// Reject an address whose domain is blocked or not on the allow list
+    pub fn check(&self, email: &str) -> Result<(), AppError> {
+        let domain = email
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.trim().trim_end_matches('.').to_lowercase())
+            .unwrap_or_default();
+
+        let rejected = || AppError::ValidationError(format!(
+            "Signups from {} email addresses are not allowed", domain
+        ));
+        if self.blocked.iter().any(|pattern| matches(pattern, &domain)) {
+            return Err(rejected());
+        }
+        if !self.allowed.is_empty() && !self.allowed.iter().any(|pattern| matches(pattern, &domain)) {
+            return Err(rejected());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &str, blocked: &str) -> EmailDomainPolicy {
+        EmailDomainPolicy {
+            allowed: parse_domain_list(allowed).unwrap(),
+            blocked: parse_domain_list(blocked).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_neutral_domains_pass_with_no_lists() {
+        assert!(policy("", "").check("someone@example.com").is_ok());
+        assert!(policy("", "*.ru").check("someone@example.com").is_ok());
+    }
+
+    #[test]
+    fn test_blocked_suffix_and_exact_domains() {
+        let policy = policy("", "*.ru, spam.example\nMailinator.com.");
+        assert!(policy.check("a@mail.ru").is_err());
+        assert!(policy.check("a@deep.sub.RU").is_err());
+        assert!(policy.check("a@spam.example").is_err());
+        assert!(policy.check("a@mailinator.com").is_err());
+        // Exact patterns don't cover subdomains, and suffixes need a dot
+        assert!(policy.check("a@eggs.spam.example").is_ok());
+        assert!(policy.check("a@guru").is_ok());
+    }
+
+    #[test]
+    fn test_allow_list_restricts_and_block_list_wins() {
+        let policy = policy("corp.example, *.corp.example", "contractors.corp.example");
+        assert!(policy.check("a@corp.example").is_ok());
+        assert!(policy.check("a@eu.corp.example").is_ok());
+        assert!(policy.check("a@gmail.com").is_err());
+        assert!(policy.check("a@contractors.corp.example").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_patterns() {
+        assert_eq!(parse_domain_list(" Example.COM ,\n*.ru ").unwrap(), vec!["example.com", "*.ru"]);
+        assert!(parse_domain_list("*").is_err());
+        assert!(parse_domain_list("exa mple.com").is_err());
+        assert!(parse_domain_list("user@example.com").is_err());
+        assert!(parse_domain_list("*.*.ru").is_err());
+        assert!(parse_domain_list("-bad.com").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! Post teasers for post cards
+//!
+//! Posts have no excerpt field of their own; the public post list derives
+//! one from each post's text, cut at a word after `excerpt_length`
+//! characters. Since it is derived on every request, changing the setting
+//! re-derives every teaser. `read_more_text` labels the link under each
+//! card. Feed items and meta descriptions keep their own fixed length
+//! (`feed::excerpt`), which suits search snippets and readers.
+
+use diesel::prelude::*;
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+use crate::services::search::plain_text;
+
+pub const EXCERPT_LENGTH_SETTING: &str = "excerpt_length";
+pub const READ_MORE_TEXT_SETTING: &str = "read_more_text";
+pub const EXCERPT_SETTING_KEYS: [&str; 2] = [EXCERPT_LENGTH_SETTING, READ_MORE_TEXT_SETTING];
+
+pub const DEFAULT_EXCERPT_LENGTH: usize = 200;
+pub const MAX_EXCERPT_LENGTH: usize = 2000;
+pub const DEFAULT_READ_MORE_TEXT: &str = "Read Article";
+pub const MAX_READ_MORE_CHARS: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExcerptConfig {
+    pub length: usize,
+    pub read_more_text: String,
+}
+
+impl Default for ExcerptConfig {
+    fn default() -> Self {
+        Self {
+            length: DEFAULT_EXCERPT_LENGTH,
+            read_more_text: DEFAULT_READ_MORE_TEXT.to_string(),
+        }
+    }
+}
+
+impl ExcerptConfig {
+    /// Validate an excerpt setting value, returning it normalized
+    pub fn validate_setting(key: &str, value: &str) -> Result<String, AppError> {
+        let value = value.trim();
+        match key {
+            EXCERPT_LENGTH_SETTING => value.parse::<usize>()
+                .ok()
+                .filter(|length| (1..=MAX_EXCERPT_LENGTH).contains(length))
+                .map(|length| length.to_string())
+                .ok_or_else(|| AppError::ValidationError(format!(
+                    "{} must be a whole number of characters from 1 to {}", EXCERPT_LENGTH_SETTING, MAX_EXCERPT_LENGTH
+                ))),
+            READ_MORE_TEXT_SETTING if value.is_empty() || value.chars().count() > MAX_READ_MORE_CHARS => {
+                Err(AppError::ValidationError(format!(
+                    "{} must be 1 to {} characters long", READ_MORE_TEXT_SETTING, MAX_READ_MORE_CHARS
+                )))
+            }
+            READ_MORE_TEXT_SETTING => Ok(value.to_string()),
+            _ => Err(AppError::ValidationError(format!("Unknown excerpt setting: {}", key))),
+        }
+    }
+
+    /// Current settings; missing or invalid values use the defaults
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut config = Self::default();
+        for key in EXCERPT_SETTING_KEYS {
+            let Some(value) = Setting::find_by_key(conn, key)?.and_then(|s| s.setting_value) else {
+                continue;
+            };
+            match Self::validate_setting(key, &value) {
+                Ok(value) if key == EXCERPT_LENGTH_SETTING => config.length = value.parse().unwrap_or(DEFAULT_EXCERPT_LENGTH),
+                Ok(value) => config.read_more_text = value,
+                Err(e) => warn!("Ignoring setting {}: {}", key, e),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// The first `length` characters of a post's text, cut at a word
+pub fn excerpt_of(content: &str, length: usize) -> String {
+    let text = plain_text(content);
+    if text.chars().count() <= length {
+        return text;
+    }
+    let cut: String = text.chars().take(length).collect();
+    let cut = match cut.rfind(char::is_whitespace) {
+        Some(at) => &cut[..at],
+        None => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excerpt_follows_length() {
+        let content = "# Title\n\nThe quick **brown** fox jumps over the lazy dog.";
+        assert_eq!(excerpt_of(content, 20), "Title The quick…");
+        assert_eq!(excerpt_of(content, 1000), "Title The quick brown fox jumps over the lazy dog.");
+        // A single long word is cut mid-word rather than dropped
+        assert_eq!(excerpt_of("Supercalifragilistic", 5), "Super…");
+    }
+
+    #[test]
+    fn test_validate_setting() {
+        assert_eq!(ExcerptConfig::validate_setting(EXCERPT_LENGTH_SETTING, " 120 ").unwrap(), "120");
+        assert!(ExcerptConfig::validate_setting(EXCERPT_LENGTH_SETTING, "0").is_err());
+        assert!(ExcerptConfig::validate_setting(EXCERPT_LENGTH_SETTING, "-5").is_err());
+        assert!(ExcerptConfig::validate_setting(EXCERPT_LENGTH_SETTING, &(MAX_EXCERPT_LENGTH + 1).to_string()).is_err());
+        assert!(ExcerptConfig::validate_setting(EXCERPT_LENGTH_SETTING, "long").is_err());
+
+        assert_eq!(ExcerptConfig::validate_setting(READ_MORE_TEXT_SETTING, " Continue → ").unwrap(), "Continue →");
+        assert!(ExcerptConfig::validate_setting(READ_MORE_TEXT_SETTING, "  ").is_err());
+        assert!(ExcerptConfig::validate_setting(READ_MORE_TEXT_SETTING, &"x".repeat(MAX_READ_MORE_CHARS + 1)).is_err());
+        assert!(ExcerptConfig::validate_setting("excerpt_style", "short").is_err());
+    }
+}
@@ -0,0 +1,224 @@
+//! RSS 2.0 feed of published posts
+//!
+//! `feed_item_count` sets how many of the newest posts the feed carries, up
+//! to `MAX_FEED_ITEMS`. With `feed_full_content` off each item has only a
+//! plain-text excerpt as its `<description>`; turned on, items also carry
+//! the post rendered to HTML in `<content:encoded>`, so readers can show the
+//! whole post without a click through.
+
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use diesel::prelude::*;
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::{Post, Setting};
+use crate::services::excerpts::excerpt_of;
+
+pub const FEED_ITEM_COUNT_SETTING: &str = "feed_item_count";
+pub const FEED_FULL_CONTENT_SETTING: &str = "feed_full_content";
+pub const FEED_SETTING_KEYS: [&str; 2] = [FEED_ITEM_COUNT_SETTING, FEED_FULL_CONTENT_SETTING];
+pub const DEFAULT_FEED_ITEMS: usize = 20;
+pub const MAX_FEED_ITEMS: usize = 100;
+/// Characters of plain text kept in an item's excerpt
+const EXCERPT_LENGTH: usize = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedConfig {
+    pub item_count: usize,
+    pub full_content: bool,
+}
+
+impl Default for FeedConfig {
+    fn default() -> Self {
+        Self {
+            item_count: DEFAULT_FEED_ITEMS,
+            full_content: false,
+        }
+    }
+}
+
+impl FeedConfig {
+    /// Check the format of a single feed setting value
+    pub fn validate_setting(key: &str, value: &str) -> Result<(), AppError> {
+        match key {
+            FEED_ITEM_COUNT_SETTING => match value.trim().parse::<usize>() {
+                Ok(count) if (1..=MAX_FEED_ITEMS).contains(&count) => Ok(()),
+                _ => Err(AppError::ValidationError(format!(
+                    "{} must be a whole number between 1 and {}", FEED_ITEM_COUNT_SETTING, MAX_FEED_ITEMS
+                ))),
+            },
+            FEED_FULL_CONTENT_SETTING => match value.trim() {
+                "true" | "false" => Ok(()),
+                _ => Err(AppError::ValidationError(format!("{} must be true or false", FEED_FULL_CONTENT_SETTING))),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Current feed settings; missing or invalid values use the defaults
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut config = Self::default();
+        if let Some(value) = Setting::find_by_key(conn, FEED_ITEM_COUNT_SETTING)?.and_then(|s| s.setting_value) {
+            match value.trim().parse::<usize>() {
+                Ok(count) => config.item_count = count.clamp(1, MAX_FEED_ITEMS),
+                Err(_) => warn!("Ignoring feed setting {}: {:?} is not a number", FEED_ITEM_COUNT_SETTING, value),
+            }
+        }
+        config.full_content = Setting::find_by_key(conn, FEED_FULL_CONTENT_SETTING)?
+            .and_then(|s| s.setting_value)
+            .is_some_and(|value| value.trim() == "true");
+        Ok(config)
+    }
+}
+
+/// What the feed describes: the site's title, description and home URL
+#[derive(Debug, Clone)]
+pub struct FeedChannel {
+    pub title: String,
+    pub description: String,
+    /// Absolute URL of the public site, without a trailing slash
+    pub link: String,
+}
+
+/// Text safe to place in an XML element or attribute
+pub(crate) fn xml_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab and newlines aren't valid XML
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// A CDATA section holding `html`; a `]]>` inside is split across sections
+fn cdata(html: &str) -> String {
+    format!("<![CDATA[{}]]>", html.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// The first `EXCERPT_LENGTH` characters of a post's text, cut at a word
+pub fn excerpt(content: &str) -> String {
+    excerpt_of(content, EXCERPT_LENGTH)
+}
+
+/// A post's Markdown as sanitized HTML
+pub fn render_html(content: &str) -> String {
+    let parser = pulldown_cmark::Parser::new_ext(content, pulldown_cmark::Options::all());
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    ammonia::clean(&html)
+}
+
+fn rfc2822(at: NaiveDateTime) -> String {
+    Utc.from_utc_datetime(&at).to_rfc2822()
+}
+
+/// The feed document for `posts`, which should be newest first
+pub fn render_rss(channel: &FeedChannel, posts: &[Post], config: &FeedConfig) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\">\n<channel>\n");
+    xml.push_str(&format!("<title>{}</title>\n", xml_escape(&channel.title)));
+    xml.push_str(&format!("<link>{}/</link>\n", xml_escape(&channel.link)));
+    xml.push_str(&format!("<description>{}</description>\n", xml_escape(&channel.description)));
+    if let Some(latest) = posts.iter().filter_map(|post| post.published_at.or(post.created_at)).max() {
+        xml.push_str(&format!("<lastBuildDate>{}</lastBuildDate>\n", rfc2822(latest)));
+    }
+
+    for post in posts.iter().take(config.item_count) {
+        let link = format!("{}/post/{}", channel.link, post.id);
+        xml.push_str("<item>\n");
+        xml.push_str(&format!("<title>{}</title>\n", xml_escape(&post.title)));
+        xml.push_str(&format!("<link>{}</link>\n", xml_escape(&link)));
+        xml.push_str(&format!("<guid isPermaLink=\"true\">{}</guid>\n", xml_escape(&link)));
+        if let Some(date) = post.published_at.or(post.created_at) {
+            xml.push_str(&format!("<pubDate>{}</pubDate>\n", rfc2822(date)));
+        }
+        xml.push_str(&format!("<description>{}</description>\n", xml_escape(&excerpt(&post.content))));
+        if config.full_content {
+            xml.push_str(&format!("<content:encoded>{}</content:encoded>\n", cdata(&render_html(&post.content))));
+        }
+        xml.push_str("</item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel() -> FeedChannel {
+        FeedChannel {
+            title: "Rust & CMS".to_string(),
+            description: "Notes".to_string(),
+            link: "https://example.com".to_string(),
+        }
+    }
+
+    fn post(id: i32, title: &str, content: &str) -> Post {
+        Post {
+            id,
+            title: title.to_string(),
+            content: content.to_string(),
+            category_id: None,
+            user_id: None,
+            created_at: NaiveDateTime::parse_from_str("2025-08-15 09:30:00", "%Y-%m-%d %H:%M:%S").ok(),
+            updated_at: None,
+            status: "published".to_string(),
+            published_at: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_setting() {
+        assert!(FeedConfig::validate_setting(FEED_ITEM_COUNT_SETTING, "20").is_ok());
+        assert!(FeedConfig::validate_setting(FEED_ITEM_COUNT_SETTING, "0").is_err());
+        assert!(FeedConfig::validate_setting(FEED_ITEM_COUNT_SETTING, &(MAX_FEED_ITEMS + 1).to_string()).is_err());
+        assert!(FeedConfig::validate_setting(FEED_ITEM_COUNT_SETTING, "lots").is_err());
+        assert!(FeedConfig::validate_setting(FEED_FULL_CONTENT_SETTING, "true").is_ok());
+        assert!(FeedConfig::validate_setting(FEED_FULL_CONTENT_SETTING, "yes").is_err());
+    }
+
+    #[test]
+    fn test_excerpt_mode_has_only_descriptions() {
+        let posts = vec![post(1, "First <post>", "Hello **world**"), post(2, "Second", "More")];
+        let config = FeedConfig { item_count: 1, full_content: false };
+        let xml = render_rss(&channel(), &posts, &config);
+
+        assert!(xml.contains("<title>Rust &amp; CMS</title>"));
+        assert!(xml.contains("<title>First &lt;post&gt;</title>"));
+        assert!(xml.contains("<link>https://example.com/post/1</link>"));
+        assert!(xml.contains("<description>Hello world</description>"));
+        assert!(xml.contains("<pubDate>Fri, 15 Aug 2025 09:30:00 +0000</pubDate>"));
+        assert!(!xml.contains("<content:encoded>"));
+        assert_eq!(xml.matches("<item>").count(), 1);
+    }
+
+    #[test]
+    fn test_full_content_mode_adds_rendered_html() {
+        let posts = vec![post(1, "First", "Hello **world** <script>alert(1)</script>")];
+        let config = FeedConfig { item_count: 10, full_content: true };
+        let xml = render_rss(&channel(), &posts, &config);
+
+        assert!(xml.contains("<description>"));
+        assert!(xml.contains("<content:encoded><![CDATA[<p>Hello <strong>world</strong>"));
+        assert!(!xml.contains("<script>"));
+        assert_eq!(cdata("a]]>b"), "<![CDATA[a]]]]><![CDATA[>b]]>");
+    }
+
+    #[test]
+    fn test_excerpt_cuts_at_a_word() {
+        let long = "word ".repeat(100);
+        let cut = excerpt(&long);
+        assert!(cut.ends_with("word…"));
+        assert!(cut.chars().count() <= EXCERPT_LENGTH + 1);
+        assert_eq!(excerpt("Short post"), "Short post");
+    }
+}
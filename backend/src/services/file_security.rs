@@ -1,4 +1,14 @@
 use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Longest original file name kept for display, in bytes
+const MAX_DISPLAY_NAME_LEN: usize = 255;
+/// Longest extension carried over to the stored name
+const MAX_EXTENSION_LEN: usize = 10;
+/// Fresh names tried before giving up on a colliding upload
+const MAX_NAME_ATTEMPTS: usize = 5;
 
 /// File type validation service for secure uploads
 pub struct FileSecurityService {
@@ -163,4 +173,140 @@ impl std::fmt::Display for FileSecurityError {
     }
 }
 
-impl std::error::Error for FileSecurityError {}
\ No newline at end of file
+impl std::error::Error for FileSecurityError {}
+
+/// Original file name reduced to something safe to store and show: the last
+/// path component, without control characters or leading dots
+pub fn display_file_name(raw: &str) -> String {
+    let last = raw.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = last.chars().filter(|c| !c.is_control()).collect();
+    let mut name = cleaned.trim().trim_start_matches('.').trim().to_string();
+    while name.len() > MAX_DISPLAY_NAME_LEN {
+        name.pop();
+    }
+    if name.is_empty() { "file".to_string() } else { name }
+}
+
+/// Lowercased extension of a file name, if it is short and alphanumeric
+fn safe_extension(name: &str) -> Option<String> {
+    let (_, extension) = name.rsplit_once('.')?;
+    let valid = !extension.is_empty()
+        && extension.len() <= MAX_EXTENSION_LEN
+        && extension.chars().all(|c| c.is_ascii_alphanumeric());
+    valid.then(|| extension.to_ascii_lowercase())
+}
+
+/// Random name an upload is stored under, keeping a safe extension
+fn random_stored_name(original: &str) -> String {
+    let id = uuid::Uuid::new_v4();
+    match safe_extension(&display_file_name(original)) {
+        Some(extension) => format!("{}.{}", id, extension),
+        None => id.to_string(),
+    }
+}
+
+/// Whether `name` can be used as a file directly inside the upload
+/// directory: a single, non-hidden component of `[A-Za-z0-9._-]`
+fn is_plain_stored_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        && Path::new(name).file_name().and_then(|n| n.to_str()) == Some(name)
+}
+
+/// Write an upload into `upload_dir` under a fresh random name, returning
+/// that name. The original name only contributes its extension, and the
+/// file is created exclusively so a collision never overwrites anything.
+pub fn store_upload(upload_dir: &Path, original_name: &str, data: &[u8]) -> std::io::Result<String> {
+    for _ in 0..MAX_NAME_ATTEMPTS {
+        let name = random_stored_name(original_name);
+        let mut file = match OpenOptions::new().write(true).create_new(true).open(upload_dir.join(&name)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        };
+        file.write_all(data)?;
+        return Ok(name);
+    }
+    Err(std::io::Error::new(std::io::ErrorKind::AlreadyExists, "could not pick an unused file name"))
+}
+
+/// File a request under `/uploads/` may be served from: a plain stored name
+/// that is a regular file directly in `upload_dir`, not a symlink. Anything
+/// else is treated as not found.
+pub fn served_upload_path(upload_dir: &Path, name: &str) -> Option<PathBuf> {
+    if !is_plain_stored_name(name) {
+        return None;
+    }
+    let path = upload_dir.join(name);
+    let metadata = std::fs::symlink_metadata(&path).ok()?;
+    metadata.file_type().is_file().then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty scratch directory under the system temp dir
+    fn scratch_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("file_security_{}_{}", label, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_display_file_name_strips_directories() {
+        assert_eq!(display_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(display_file_name("C:\\Users\\me\\photo.PNG"), "photo.PNG");
+        assert_eq!(display_file_name("uploads/../.env"), "env");
+        assert_eq!(display_file_name("../"), "file");
+        assert_eq!(display_file_name("re\u{0}port.pdf"), "report.pdf");
+    }
+
+    #[test]
+    fn test_store_upload_keeps_traversal_names_inside_upload_dir() {
+        let dir = scratch_dir("store");
+        for original in ["../../escape.png", "/etc/cron.d/job.txt", "..\\..\\win.ini", "a/b/../c.pdf", ".hidden"] {
+            let name = store_upload(&dir, original, b"data").unwrap();
+            assert!(is_plain_stored_name(&name), "{} -> {}", original, name);
+            let stored = dir.join(&name);
+            assert_eq!(stored.parent(), Some(dir.as_path()));
+            assert_eq!(std::fs::read(&stored).unwrap(), b"data");
+        }
+        assert!(!dir.parent().unwrap().join("escape.png").exists());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 5);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_store_upload_keeps_only_safe_extensions() {
+        let dir = scratch_dir("ext");
+        assert!(store_upload(&dir, "photo.JPG", b"x").unwrap().ends_with(".jpg"));
+        assert!(!store_upload(&dir, "shell.p h p", b"x").unwrap().contains(' '));
+        assert!(!store_upload(&dir, "noext", b"x").unwrap().contains('.'));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_served_upload_path_rejects_escapes() {
+        let dir = scratch_dir("serve");
+        std::fs::write(dir.join("ok.png"), b"x").unwrap();
+        std::fs::write(dir.join(".env"), b"secret").unwrap();
+        std::fs::create_dir(dir.join("sub")).unwrap();
+
+        assert_eq!(served_upload_path(&dir, "ok.png"), Some(dir.join("ok.png")));
+        assert_eq!(served_upload_path(&dir, "missing.png"), None);
+        assert_eq!(served_upload_path(&dir, ".env"), None);
+        assert_eq!(served_upload_path(&dir, "sub"), None);
+        assert_eq!(served_upload_path(&dir, "../ok.png"), None);
+        assert_eq!(served_upload_path(&dir, "sub/../ok.png"), None);
+        assert_eq!(served_upload_path(&dir, "..%2fok.png"), None);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("/etc/hostname", dir.join("link.png")).unwrap();
+            assert_eq!(served_upload_path(&dir, "link.png"), None);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,110 @@
+//! Gravatar avatar URLs for comment authors
+//!
+//! `gravatar_default` picks the image Gravatar shows for addresses without
+//! an avatar of their own and `gravatar_rating` the most explicit rating
+//! that may be shown. Both are limited to the values Gravatar accepts.
+
+use diesel::prelude::*;
+use serde::Serialize;
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const GRAVATAR_DEFAULT_SETTING: &str = "gravatar_default";
+pub const GRAVATAR_RATING_SETTING: &str = "gravatar_rating";
+pub const GRAVATAR_SETTING_KEYS: [&str; 2] = [GRAVATAR_DEFAULT_SETTING, GRAVATAR_RATING_SETTING];
+
+/// Built-in default images Gravatar can fall back to
+pub const GRAVATAR_DEFAULTS: [&str; 8] = ["mp", "identicon", "monsterid", "wavatar", "retro", "robohash", "blank", "404"];
+/// Ratings from most to least restrictive
+pub const GRAVATAR_RATINGS: [&str; 4] = ["g", "pg", "r", "x"];
+
+/// Hashed in place of a missing email, so every anonymous author gets the
+/// same generated image
+const ANONYMOUS_EMAIL: &str = "default@example.com";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GravatarConfig {
+    pub default_image: &'static str,
+    pub rating: &'static str,
+}
+
+impl Default for GravatarConfig {
+    fn default() -> Self {
+        Self {
+            default_image: "identicon",
+            rating: "pg",
+        }
+    }
+}
+
+impl GravatarConfig {
+    /// Normalize a Gravatar setting value, rejecting values Gravatar doesn't accept
+    pub fn validate_setting(key: &str, value: &str) -> Result<&'static str, AppError> {
+        let allowed: &[&'static str] = match key {
+            GRAVATAR_DEFAULT_SETTING => &GRAVATAR_DEFAULTS,
+            GRAVATAR_RATING_SETTING => &GRAVATAR_RATINGS,
+            _ => return Err(AppError::ValidationError(format!("Unknown Gravatar setting: {}", key))),
+        };
+        let value = value.trim();
+        allowed.iter()
+            .find(|allowed| allowed.eq_ignore_ascii_case(value))
+            .copied()
+            .ok_or_else(|| AppError::ValidationError(format!("{} must be one of: {}", key, allowed.join(", "))))
+    }
+
+    /// Current settings; missing or invalid values use the defaults
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut config = Self::default();
+        for key in GRAVATAR_SETTING_KEYS {
+            let Some(value) = Setting::find_by_key(conn, key)?.and_then(|s| s.setting_value) else {
+                continue;
+            };
+            match Self::validate_setting(key, &value) {
+                Ok(value) if key == GRAVATAR_DEFAULT_SETTING => config.default_image = value,
+                Ok(value) => config.rating = value,
+                Err(e) => warn!("Ignoring Gravatar setting {}: {}", key, e),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Avatar URL for an email address at `size` pixels square
+    pub fn url(&self, email: Option<&str>, size: u32) -> String {
+        let email = email.unwrap_or(ANONYMOUS_EMAIL).trim().to_lowercase();
+        let hash = format!("{:x}", md5::compute(email.as_bytes()));
+        format!(
+            "https://www.gravatar.com/avatar/{}?s={}&d={}&r={}",
+            hash, size, self.default_image, self.rating
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_url_matches_previous_format() {
+        let url = GravatarConfig::default().url(Some(" Someone@Example.com "), 80);
+        let hash = format!("{:x}", md5::compute("someone@example.com"));
+        assert_eq!(url, format!("https://www.gravatar.com/avatar/{}?s=80&d=identicon&r=pg", hash));
+    }
+
+    #[test]
+    fn test_configured_default_and_rating() {
+        let config = GravatarConfig { default_image: "retro", rating: "g" };
+        let url = config.url(None, 40);
+        assert!(url.ends_with("?s=40&d=retro&r=g"));
+        assert!(url.contains(&format!("{:x}", md5::compute(ANONYMOUS_EMAIL))));
+    }
+
+    #[test]
+    fn test_validate_setting() {
+        assert_eq!(GravatarConfig::validate_setting(GRAVATAR_DEFAULT_SETTING, " RoboHash ").unwrap(), "robohash");
+        assert_eq!(GravatarConfig::validate_setting(GRAVATAR_RATING_SETTING, "X").unwrap(), "x");
+        assert!(GravatarConfig::validate_setting(GRAVATAR_DEFAULT_SETTING, "https://example.com/a.png").is_err());
+        assert!(GravatarConfig::validate_setting(GRAVATAR_RATING_SETTING, "nc-17").is_err());
+        assert!(GravatarConfig::validate_setting("gravatar_size", "80").is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! What the public site shows at `/`
+//!
+//! `homepage_mode` is `posts` (the post list, the default) or `page`, in
+//! which case `homepage_page_id` names the page to show. Saving the settings
+//! checks that the page exists and is published; if it is later unpublished
+//! or deleted, the home falls back to the post list rather than erroring.
+
+use diesel::prelude::*;
+use crate::middleware::errors::AppError;
+use crate::models::{Page, Setting};
+
+pub const HOMEPAGE_MODE_SETTING: &str = "homepage_mode";
+pub const HOMEPAGE_PAGE_ID_SETTING: &str = "homepage_page_id";
+pub const HOMEPAGE_SETTING_KEYS: [&str; 2] = [HOMEPAGE_MODE_SETTING, HOMEPAGE_PAGE_ID_SETTING];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HomepageMode {
+    #[default]
+    Posts,
+    Page,
+}
+
+impl HomepageMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "posts" => Some(Self::Posts),
+            "page" => Some(Self::Page),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Posts => "posts",
+            Self::Page => "page",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HomepageConfig {
+    pub mode: HomepageMode,
+    pub page_id: Option<i32>,
+}
+
+fn parse_page_id(value: &str) -> Result<Option<i32>, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value.parse::<i32>()
+        .ok()
+        .filter(|id| *id > 0)
+        .map(Some)
+        .ok_or_else(|| AppError::ValidationError(format!("{} must be a page ID", HOMEPAGE_PAGE_ID_SETTING)))
+}
+
+impl HomepageConfig {
+    /// Build from stored values; anything unparseable means the post list
+    pub fn from_values(mode: Option<&str>, page_id: Option<&str>) -> Self {
+        Self {
+            mode: mode.and_then(HomepageMode::parse).unwrap_or_default(),
+            page_id: page_id.and_then(|value| parse_page_id(value).ok().flatten()),
+        }
+    }
+
+    /// Check the format of a single homepage setting value
+    pub fn validate_setting(key: &str, value: &str) -> Result<(), AppError> {
+        match key {
+            HOMEPAGE_MODE_SETTING => HomepageMode::parse(value).map(|_| ()).ok_or_else(|| {
+                AppError::ValidationError(format!("{} must be 'posts' or 'page'", HOMEPAGE_MODE_SETTING))
+            }),
+            HOMEPAGE_PAGE_ID_SETTING => parse_page_id(value).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// The stored homepage settings
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let value = |conn: &mut PgConnection, key: &str| -> QueryResult<Option<String>> {
+            Ok(Setting::find_by_key(conn, key)?.and_then(|setting| setting.setting_value))
+        };
+        let mode = value(conn, HOMEPAGE_MODE_SETTING)?;
+        let page_id = value(conn, HOMEPAGE_PAGE_ID_SETTING)?;
+        Ok(Self::from_values(mode.as_deref(), page_id.as_deref()))
+    }
+
+    /// Reject a page homepage that names no page, or one that isn't published
+    pub fn validate(&self, conn: &mut PgConnection) -> Result<(), AppError> {
+        if self.mode != HomepageMode::Page {
+            return Ok(());
+        }
+        let page_id = self.page_id.ok_or_else(|| {
+            AppError::ValidationError("Choose the page to show as the homepage".to_string())
+        })?;
+        match Page::find_by_id(conn, page_id)? {
+            Some(page) if page.status == "published" => Ok(()),
+            Some(_) => Err(AppError::ValidationError("The homepage must be a published page".to_string())),
+            None => Err(AppError::ValidationError(format!("Page {} does not exist", page_id))),
+        }
+    }
+}
+
+/// The page to show at `/`, if the config asks for one and it is still
+/// published; `None` means the post list
+pub fn resolve_homepage(config: &HomepageConfig, page: Option<Page>) -> Option<Page> {
+    if config.mode != HomepageMode::Page {
+        return None;
+    }
+    page.filter(|page| Some(page.id) == config.page_id && page.status == "published")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(id: i32, status: &str) -> Page {
+        Page {
+            id,
+            title: "Welcome".to_string(),
+            content: String::new(),
+            user_id: None,
+            created_at: None,
+            updated_at: None,
+            slug: "welcome".to_string(),
+            status: status.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_from_values_defaults_to_posts() {
+        assert_eq!(HomepageConfig::from_values(None, None), HomepageConfig::default());
+        assert_eq!(HomepageConfig::from_values(Some("bogus"), Some("abc")), HomepageConfig::default());
+        assert_eq!(
+            HomepageConfig::from_values(Some("Page"), Some(" 7 ")),
+            HomepageConfig { mode: HomepageMode::Page, page_id: Some(7) }
+        );
+    }
+
+    #[test]
+    fn test_validate_setting() {
+        assert!(HomepageConfig::validate_setting(HOMEPAGE_MODE_SETTING, "posts").is_ok());
+        assert!(HomepageConfig::validate_setting(HOMEPAGE_MODE_SETTING, "landing").is_err());
+        assert!(HomepageConfig::validate_setting(HOMEPAGE_PAGE_ID_SETTING, "").is_ok());
+        assert!(HomepageConfig::validate_setting(HOMEPAGE_PAGE_ID_SETTING, "12").is_ok());
+        assert!(HomepageConfig::validate_setting(HOMEPAGE_PAGE_ID_SETTING, "0").is_err());
+        assert!(HomepageConfig::validate_setting(HOMEPAGE_PAGE_ID_SETTING, "home").is_err());
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_posts() {
+        let config = HomepageConfig { mode: HomepageMode::Page, page_id: Some(3) };
+        assert_eq!(resolve_homepage(&config, Some(page(3, "published"))).map(|p| p.id), Some(3));
+        assert!(resolve_homepage(&config, Some(page(3, "draft"))).is_none());
+        assert!(resolve_homepage(&config, None).is_none());
+
+        let posts = HomepageConfig { mode: HomepageMode::Posts, page_id: Some(3) };
+        assert!(resolve_homepage(&posts, Some(page(3, "published"))).is_none());
+    }
+}
@@ -0,0 +1,151 @@
+//! Idempotency keys for create requests
+//!
+//! A client may send an `Idempotency-Key` header (any string up to 255
+//! visible ASCII characters, e.g. a UUID) with a create request. The first
+//! request with a key is handled normally and its successful response is
+//! kept for `KEY_TTL_HOURS`; repeats of the same request with the same key
+//! get that response back, marked `Idempotent-Replayed: true`, instead of
+//! creating a duplicate. Keys are per user. Reusing a key for a different
+//! request is refused, as is a repeat while the first is still running.
+//! Failed requests don't keep their key, so they can be retried with it.
+
+use chrono::{Duration, NaiveDateTime};
+use diesel::prelude::*;
+use sha2::{Digest, Sha256};
+use crate::middleware::errors::AppError;
+use crate::models::{IdempotencyKey, NewIdempotencyKey};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+/// How long a response is replayed for
+pub const KEY_TTL_HOURS: i64 = 24;
+pub const MAX_KEY_LENGTH: usize = 255;
+
+/// What to do with a request carrying a key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// Handle it; the key is claimed under this id
+    Proceed(i32),
+    /// Send back the response of the first request
+    Replay { status: u16, body: String },
+    /// The first request is still being handled
+    InProgress,
+    /// The key was used for a different request
+    Mismatch,
+}
+
+pub fn validate_key(key: &str) -> Result<&str, AppError> {
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH || !key.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err(AppError::ValidationError(format!(
+            "Idempotency-Key must be 1 to {} visible ASCII characters", MAX_KEY_LENGTH
+        )));
+    }
+    Ok(key)
+}
+
+/// Hash identifying a request, so a key can't be replayed for another one
+pub fn fingerprint(method: &str, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn is_expired(record: &IdempotencyKey, now: NaiveDateTime) -> bool {
+    record.created_at < now - Duration::hours(KEY_TTL_HOURS)
+}
+
+/// How a request relates to the live record already stored for its key
+fn compare(record: &IdempotencyKey, fingerprint: &str) -> Decision {
+    if record.request_fingerprint != fingerprint {
+        return Decision::Mismatch;
+    }
+    match (record.response_status, &record.response_body) {
+        (Some(status), Some(body)) => Decision::Replay {
+            status: u16::try_from(status).unwrap_or(200),
+            body: body.clone(),
+        },
+        _ => Decision::InProgress,
+    }
+}
+
+/// Look a key up and claim it if it is new or its record has expired.
+/// Expired records of every user are cleared along the way.
+pub fn claim(conn: &mut PgConnection, user_id: i32, key: &str, fingerprint: &str, now: NaiveDateTime) -> QueryResult<Decision> {
+    IdempotencyKey::delete_created_before(conn, now - Duration::hours(KEY_TTL_HOURS))?;
+    if let Some(record) = IdempotencyKey::find(conn, user_id, key)?.filter(|record| !is_expired(record, now)) {
+        return Ok(compare(&record, fingerprint));
+    }
+    let claimed = IdempotencyKey::reserve(conn, NewIdempotencyKey {
+        user_id,
+        idempotency_key: key.to_string(),
+        request_fingerprint: fingerprint.to_string(),
+        created_at: now,
+    })?;
+    // Losing the race to a concurrent request with the same key
+    Ok(claimed.map_or(Decision::InProgress, |record| Decision::Proceed(record.id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, 1).and_then(|d| d.and_hms_opt(12, 0, 0)).unwrap()
+    }
+
+    fn record(fingerprint: &str) -> IdempotencyKey {
+        IdempotencyKey {
+            id: 1,
+            user_id: 7,
+            idempotency_key: "4f1c2a".to_string(),
+            request_fingerprint: fingerprint.to_string(),
+            response_status: None,
+            response_body: None,
+            created_at: now(),
+        }
+    }
+
+    #[test]
+    fn test_replaying_a_key_returns_the_first_response() {
+        let body = br#"{"title":"Hello","content":"World"}"#;
+        let first = fingerprint("POST", "/api/posts", body);
+        let mut stored = record(&first);
+
+        // While the first request runs, a repeat has to wait
+        assert_eq!(compare(&stored, &first), Decision::InProgress);
+
+        stored.response_status = Some(201);
+        stored.response_body = Some(r#"{"id":42}"#.to_string());
+        let replay = fingerprint("POST", "/api/posts", body);
+        for _ in 0..2 {
+            assert_eq!(
+                compare(&stored, &replay),
+                Decision::Replay { status: 201, body: r#"{"id":42}"#.to_string() }
+            );
+        }
+    }
+
+    #[test]
+    fn test_key_reused_for_another_request() {
+        let stored = record(&fingerprint("POST", "/api/posts", b"{\"title\":\"A\"}"));
+        assert_eq!(compare(&stored, &fingerprint("POST", "/api/posts", b"{\"title\":\"B\"}")), Decision::Mismatch);
+        assert_eq!(compare(&stored, &fingerprint("POST", "/api/comments", b"{\"title\":\"A\"}")), Decision::Mismatch);
+    }
+
+    #[test]
+    fn test_expiry_and_key_format() {
+        let stored = record("abc");
+        assert!(!is_expired(&stored, now() + Duration::hours(KEY_TTL_HOURS)));
+        assert!(is_expired(&stored, now() + Duration::hours(KEY_TTL_HOURS) + Duration::seconds(1)));
+
+        assert!(validate_key("3f2b9c1e-7d4a-4e8b-9f0a-1c2d3e4f5a6b").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("has space").is_err());
+        assert!(validate_key(&"k".repeat(MAX_KEY_LENGTH + 1)).is_err());
+    }
+}
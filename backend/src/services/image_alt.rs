@@ -0,0 +1,224 @@
+//! Alt text checks for published content
+//!
+//! When the `enforce_image_alt` setting is on, posts and pages cannot be
+//! published while they contain images without alt text. Post content is
+//! Markdown (optionally with inline HTML); page content is the page builder's
+//! JSON component list, falling back to Markdown for plain-text pages.
+
+use diesel::prelude::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+use crate::services::page_blocks::NESTED_COMPONENT_FIELDS;
+
+/// Setting key that turns enforcement on
+pub const ENFORCE_IMAGE_ALT_SETTING: &str = "enforce_image_alt";
+
+static MARKDOWN_IMAGE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"!\[([^\]]*)\]\(\s*<?([^)\s>]*)").expect("valid markdown image regex")
+});
+static HTML_IMAGE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)<img\b[^>]*>").expect("valid img tag regex")
+});
+static HTML_ALT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)\salt\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).expect("valid alt attribute regex")
+});
+static HTML_SRC: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)\ssrc\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).expect("valid src attribute regex")
+});
+
+fn is_blank(text: &str) -> bool {
+    text.trim().is_empty()
+}
+
+fn attribute_value<'a>(pattern: &Regex, tag: &'a str) -> Option<&'a str> {
+    pattern.captures(tag)
+        .and_then(|c| c.get(1).or_else(|| c.get(2)).or_else(|| c.get(3)))
+        .map(|m| m.as_str())
+}
+
+fn describe(url: &str) -> String {
+    if is_blank(url) { "image with no source".to_string() } else { url.trim().to_string() }
+}
+
+/// Images in Markdown or inline HTML that have no alt text
+pub fn markdown_images_missing_alt(content: &str) -> Vec<String> {
+    let markdown = MARKDOWN_IMAGE.captures_iter(content)
+        .filter(|c| is_blank(&c[1]))
+        .map(|c| describe(&c[2]));
+
+    let html = HTML_IMAGE.find_iter(content)
+        .map(|m| m.as_str())
+        .filter(|tag| attribute_value(&HTML_ALT, tag).is_none_or(is_blank))
+        .map(|tag| describe(attribute_value(&HTML_SRC, tag).unwrap_or("")));
+
+    markdown.chain(html).collect()
+}
+
+/// Source of the first image in Markdown or inline HTML, if any
+pub fn first_markdown_image(content: &str) -> Option<String> {
+    let markdown = MARKDOWN_IMAGE.captures(content)
+        .map(|c| (c.get(0).map_or(0, |m| m.start()), c[2].to_string()));
+    let html = HTML_IMAGE.find(content)
+        .and_then(|m| attribute_value(&HTML_SRC, m.as_str()).map(|src| (m.start(), src.to_string())));
+    [markdown, html].into_iter()
+        .flatten()
+        .filter(|(_, url)| !is_blank(url))
+        .min_by_key(|(position, _)| *position)
+        .map(|(_, url)| url.trim().to_string())
+}
+
+fn collect_layout_images(components: &[Value], missing: &mut Vec<String>) {
+    for component in components {
+        let properties = &component["properties"];
+        let text = |value: &Value| value.as_str().unwrap_or("").to_string();
+
+        match component["component_type"].as_str() {
+            Some("Image") => {
+                let url = text(&properties["image_url"]);
+                if !is_blank(&url) && is_blank(&text(&properties["image_alt"])) {
+                    missing.push(url.trim().to_string());
+                }
+            }
+            Some("Gallery") => {
+                for image in properties["gallery_images"].as_array().into_iter().flatten() {
+                    let url = text(&image["url"]);
+                    if !is_blank(&url) && is_blank(&text(&image["alt"])) {
+                        missing.push(url.trim().to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Text blocks can embed Markdown images too
+        if let Some(content) = component["content"].as_str() {
+            missing.extend(markdown_images_missing_alt(content));
+        }
+
+        for field in NESTED_COMPONENT_FIELDS {
+            if let Some(children) = properties[field].as_array() {
+                collect_layout_images(children, missing);
+            }
+        }
+    }
+}
+
+/// Images in page content that have no alt text
+pub fn page_images_missing_alt(content: &str) -> Vec<String> {
+    match serde_json::from_str::<Value>(content) {
+        Ok(Value::Array(components)) => {
+            let mut missing = Vec::new();
+            collect_layout_images(&components, &mut missing);
+            missing
+        }
+        _ => markdown_images_missing_alt(content),
+    }
+}
+
+/// Whether alt text is currently required before publishing
+pub fn enforcement_enabled(conn: &mut PgConnection) -> QueryResult<bool> {
+    Ok(Setting::find_by_key(conn, ENFORCE_IMAGE_ALT_SETTING)?
+        .and_then(|s| s.setting_value)
+        .map(|value| value.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false))
+}
+
+/// Reject publishing when any images lack alt text, listing the offenders
+pub fn ensure_alt_text(missing: &[String]) -> Result<(), AppError> {
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::ValidationError(format!(
+        "Add alt text to every image before publishing. Missing alt text: {}",
+        missing.join(", ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_markdown_with_alt_text_passes() {
+        let content = "Intro\n\n![A rusty crab](/uploads/crab.png)\n\n<img src=\"/uploads/logo.svg\" alt=\"Site logo\">";
+        assert!(markdown_images_missing_alt(content).is_empty());
+    }
+
+    #[test]
+    fn test_markdown_without_alt_text_is_reported() {
+        let content = "![](/uploads/one.png) and ![  ](/uploads/two.png)\n<img src='/uploads/three.png'>\n<img alt=\"\" src=\"/uploads/four.png\" />";
+        assert_eq!(
+            markdown_images_missing_alt(content),
+            vec!["/uploads/one.png", "/uploads/two.png", "/uploads/three.png", "/uploads/four.png"]
+        );
+    }
+
+    #[test]
+    fn test_page_layout_images_are_checked_recursively() {
+        let layout = json!([
+            {
+                "component_type": "Image",
+                "content": "",
+                "properties": { "image_url": "/uploads/hero.png", "image_alt": "Hero banner" }
+            },
+            {
+                "component_type": "Container",
+                "content": "",
+                "properties": {
+                    "nested_components": [
+                        {
+                            "component_type": "Image",
+                            "content": "",
+                            "properties": { "image_url": "/uploads/nested.png", "image_alt": "" }
+                        },
+                        {
+                            "component_type": "Text",
+                            "content": "Inline ![](/uploads/inline.png)",
+                            "properties": {}
+                        }
+                    ],
+                    "column_2_components": [
+                        {
+                            "component_type": "Gallery",
+                            "content": "",
+                            "properties": {
+                                "gallery_images": [
+                                    { "url": "/uploads/g1.png", "alt": "First" },
+                                    { "url": "/uploads/g2.png", "alt": " " }
+                                ]
+                            }
+                        }
+                    ]
+                }
+            }
+        ]);
+
+        assert_eq!(
+            page_images_missing_alt(&layout.to_string()),
+            vec!["/uploads/nested.png", "/uploads/inline.png", "/uploads/g2.png"]
+        );
+    }
+
+    #[test]
+    fn test_page_layout_with_alt_text_passes() {
+        let layout = json!([
+            {
+                "component_type": "Image",
+                "content": "",
+                "properties": { "image_url": "/uploads/hero.png", "image_alt": "Hero banner" }
+            },
+            {
+                "component_type": "Image",
+                "content": "",
+                "properties": { "image_url": "", "image_alt": "" }
+            }
+        ]);
+
+        assert!(page_images_missing_alt(&layout.to_string()).is_empty());
+        assert!(page_images_missing_alt("Plain text page with ![a chart](/c.png)").is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+//! Export of a post as a Markdown file with YAML front matter
+//!
+//! The front matter uses the keys most static-site generators (Hugo, Jekyll,
+//! Eleventy, Astro) read: `title`, `slug`, `date`, `author`, `categories`,
+//! `excerpt` and `draft`. Post bodies are Markdown already and pass through
+//! unchanged. Page builder JSON and HTML bodies are converted best-effort:
+//! headings, paragraphs, emphasis, links, images and list items survive,
+//! anything else is reduced to its text.
+
+use chrono::NaiveDateTime;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde_json::Value;
+use crate::services::feed::excerpt;
+
+static HTML_HEADING: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<h([1-6])[^>]*>(.*?)</h[1-6]>").expect("valid heading regex"));
+static HTML_STRONG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<(?:strong|b)(?:\s[^>]*)?>(.*?)</(?:strong|b)>").expect("valid strong regex"));
+static HTML_EM: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<(?:em|i)(?:\s[^>]*)?>(.*?)</(?:em|i)>").expect("valid em regex"));
+static HTML_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?is)<a\s[^>]*href\s*=\s*["']([^"']*)["'][^>]*>(.*?)</a>"#).expect("valid link regex"));
+static HTML_IMAGE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?is)<img\s[^>]*src\s*=\s*["']([^"']*)["'][^>]*>"#).expect("valid image regex"));
+static HTML_ALT: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?is)alt\s*=\s*["']([^"']*)["']"#).expect("valid alt regex"));
+static HTML_LIST_ITEM: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<li[^>]*>(.*?)</li>").expect("valid list item regex"));
+static HTML_BREAK: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)<br\s*/?>").expect("valid break regex"));
+static HTML_BLOCK_END: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)</(?:p|div|ul|ol|blockquote|section)>").expect("valid block regex"));
+static HTML_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").expect("valid tag regex"));
+static BLANK_LINES: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n{3,}").expect("valid blank line regex"));
+
+/// What goes into an exported file
+pub struct ExportedPost<'a> {
+    pub title: &'a str,
+    pub slug: &'a str,
+    pub author: &'a str,
+    pub category: Option<&'a str>,
+    pub status: &'a str,
+    /// Publish date, or creation date for posts never published
+    pub date: Option<NaiveDateTime>,
+    pub content: &'a str,
+}
+
+/// Double-quoted YAML scalar
+fn yaml_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+fn html_to_markdown(html: &str) -> String {
+    let text = HTML_HEADING.replace_all(html, |caps: &Captures| {
+        let level: usize = caps[1].parse().unwrap_or(2);
+        format!("\n\n{} {}\n\n", "#".repeat(level), caps[2].trim())
+    });
+    let text = HTML_IMAGE.replace_all(&text, |caps: &Captures| {
+        let alt = HTML_ALT.captures(&caps[0]).map(|alt| alt[1].to_string()).unwrap_or_default();
+        format!("![{}]({})", alt, &caps[1])
+    });
+    let text = HTML_LINK.replace_all(&text, "[$2]($1)");
+    let text = HTML_STRONG.replace_all(&text, "**$1**");
+    let text = HTML_EM.replace_all(&text, "*$1*");
+    let text = HTML_LIST_ITEM.replace_all(&text, |caps: &Captures| format!("\n- {}", caps[1].trim()));
+    let text = HTML_BREAK.replace_all(&text, "  \n");
+    let text = HTML_BLOCK_END.replace_all(&text, "\n\n");
+    let text = HTML_TAG.replace_all(&text, "");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let lines: Vec<&str> = text.lines().map(str::trim_start).collect();
+    BLANK_LINES.replace_all(lines.join("\n").trim(), "\n\n").to_string()
+}
+
+/// Text of page builder components in order: titles become headings and
+/// content becomes paragraphs
+fn collect_layout(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| collect_layout(item, out)),
+        Value::Object(fields) => {
+            for key in ["title", "subtitle", "content", "text"] {
+                let Some(Value::String(text)) = fields.get(key) else { continue };
+                if text.trim().is_empty() {
+                    continue;
+                }
+                out.push(match key {
+                    "title" => format!("## {}", text.trim()),
+                    "subtitle" => format!("### {}", text.trim()),
+                    _ => html_to_markdown(text),
+                });
+            }
+            for (_, field) in fields {
+                if matches!(field, Value::Array(_) | Value::Object(_)) {
+                    collect_layout(field, out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A post body as Markdown
+pub fn body_markdown(content: &str) -> String {
+    if let Ok(layout @ Value::Array(_)) = serde_json::from_str::<Value>(content) {
+        let mut blocks = Vec::new();
+        collect_layout(&layout, &mut blocks);
+        return blocks.join("\n\n");
+    }
+    if content.trim_start().starts_with('<') {
+        return html_to_markdown(content);
+    }
+    content.trim().to_string()
+}
+
+/// The whole exported file: front matter followed by the body
+pub fn post_markdown(post: &ExportedPost) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("title: {}\n", yaml_string(post.title)));
+    out.push_str(&format!("slug: {}\n", yaml_string(post.slug)));
+    if let Some(date) = post.date {
+        out.push_str(&format!("date: {}\n", date.and_utc().to_rfc3339()));
+    }
+    out.push_str(&format!("author: {}\n", yaml_string(post.author)));
+    if let Some(category) = post.category {
+        out.push_str(&format!("categories: [{}]\n", yaml_string(category)));
+    }
+    let summary = excerpt(post.content);
+    if !summary.is_empty() {
+        out.push_str(&format!("excerpt: {}\n", yaml_string(&summary)));
+    }
+    out.push_str(&format!("draft: {}\n", post.status != "published"));
+    out.push_str("---\n\n");
+    out.push_str(&body_markdown(post.content));
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn post(content: &str) -> ExportedPost<'_> {
+        ExportedPost {
+            title: "Hello \"World\"",
+            slug: "hello-world",
+            author: "admin",
+            category: Some("General"),
+            status: "published",
+            date: NaiveDate::from_ymd_opt(2024, 5, 1).and_then(|d| d.and_hms_opt(9, 30, 0)),
+            content,
+        }
+    }
+
+    #[test]
+    fn test_front_matter_and_markdown_body() {
+        let exported = post_markdown(&post("# Intro\n\nSome *text* here."));
+        assert_eq!(exported, "---\n\
+            title: \"Hello \\\"World\\\"\"\n\
+            slug: \"hello-world\"\n\
+            date: 2024-05-01T09:30:00+00:00\n\
+            author: \"admin\"\n\
+            categories: [\"General\"]\n\
+            excerpt: \"Intro Some text here.\"\n\
+            draft: false\n\
+            ---\n\n\
+            # Intro\n\nSome *text* here.\n");
+    }
+
+    #[test]
+    fn test_unpublished_posts_are_drafts() {
+        let mut draft = post("Body");
+        draft.status = "draft";
+        draft.category = None;
+        let exported = post_markdown(&draft);
+        assert!(exported.contains("draft: true\n"));
+        assert!(!exported.contains("categories:"));
+    }
+
+    #[test]
+    fn test_html_body_is_converted() {
+        let html = "<h2>Title</h2><p>Read <a href=\"https://example.com\">this</a> <strong>now</strong>, <em>please</em>.</p>\
+            <ul><li>One</li><li>Two</li></ul><p><img src=\"/a.png\" alt=\"A cat\"></p>";
+        assert_eq!(
+            body_markdown(html),
+            "## Title\n\nRead [this](https://example.com) **now**, *please*.\n\n- One\n- Two\n\n![A cat](/a.png)"
+        );
+    }
+
+    #[test]
+    fn test_builder_layout_is_converted() {
+        let layout = r#"[{"type":"hero","properties":{"title":"Welcome","subtitle":"To the site"}},
+            {"type":"text","content":"<p>First &amp; foremost</p>"}]"#;
+        assert_eq!(body_markdown(layout), "## Welcome\n\n### To the site\n\nFirst & foremost");
+    }
+}
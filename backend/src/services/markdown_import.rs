@@ -0,0 +1,305 @@
+//! Import of Markdown files with YAML front matter as posts
+//!
+//! The counterpart of `markdown_export`, meant for moving posts over from
+//! static-site generators (Jekyll, Hugo, Eleventy). Only the simple subset
+//! of YAML those front matters use is understood: `key: value` lines with
+//! plain or quoted scalars, and lists written inline (`[a, b]`) or as
+//! `- item` lines. Nested maps are skipped.
+//!
+//! `title`, `date` and `categories` (or `category`) become post fields.
+//! Posts have one category, so only the first is used. There are no tags,
+//! so `tags` is reported back as skipped along with any other keys.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use crate::middleware::errors::AppError;
+
+/// File extensions accepted for import
+pub const MARKDOWN_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+/// A Markdown file parsed into post fields
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportedPost {
+    pub title: String,
+    pub content: String,
+    pub category: Option<String>,
+    pub date: Option<NaiveDateTime>,
+    /// Front matter that couldn't be carried over, for the import report
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum YamlValue {
+    Scalar(String),
+    List(Vec<String>),
+}
+
+fn invalid(message: String) -> AppError {
+    AppError::ValidationError(message)
+}
+
+/// A plain, single-quoted or double-quoted YAML scalar
+fn parse_scalar(raw: &str) -> String {
+    let raw = raw.trim();
+    if let Some(inner) = raw.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+        let mut out = String::new();
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        }
+        return out;
+    }
+    if let Some(inner) = raw.strip_prefix('\'').and_then(|rest| rest.strip_suffix('\'')) {
+        return inner.replace("''", "'");
+    }
+    // A ` #` starts a comment after a plain scalar
+    raw.split(" #").next().unwrap_or("").trim().to_string()
+}
+
+/// Items of an inline `[a, "b, c"]` list, splitting on commas outside quotes
+fn parse_inline_list(raw: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    for c in raw.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            (',', None) => {
+                items.push(parse_scalar(&current));
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(parse_scalar(&current));
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+fn parse_value(raw: &str) -> YamlValue {
+    let raw = raw.trim();
+    match raw.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+        Some(inner) => YamlValue::List(parse_inline_list(inner)),
+        None => YamlValue::Scalar(parse_scalar(raw)),
+    }
+}
+
+/// Keys and values of a front matter block, in order
+fn parse_front_matter(block: &str) -> Result<Vec<(String, YamlValue)>, AppError> {
+    let mut fields: Vec<(String, YamlValue)> = Vec::new();
+    for (number, line) in block.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix("- ").or((trimmed == "-").then_some("")) {
+            match fields.last_mut() {
+                Some((_, YamlValue::List(items))) => items.push(parse_scalar(item)),
+                Some((_, value)) if *value == YamlValue::Scalar(String::new()) => {
+                    *value = YamlValue::List(vec![parse_scalar(item)]);
+                }
+                _ => return Err(invalid(format!("Front matter line {}: list item without a key", number + 1))),
+            }
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            // Part of a nested map, which no post field uses
+            continue;
+        }
+        let (key, value) = trimmed.split_once(':')
+            .ok_or_else(|| invalid(format!("Front matter line {}: expected `key: value`", number + 1)))?;
+        fields.push((key.trim().to_lowercase(), parse_value(value)));
+    }
+    Ok(fields)
+}
+
+/// A front matter date: RFC 3339, `YYYY-MM-DD HH:MM[:SS] [±hhmm]` as
+/// Jekyll writes it, or a bare date. Offsets are converted to UTC.
+fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.naive_utc());
+    }
+    for format in ["%Y-%m-%d %H:%M:%S %z", "%Y-%m-%d %H:%M %z"] {
+        if let Ok(date) = DateTime::parse_from_str(value, format) {
+            return Some(date.naive_utc());
+        }
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(date);
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok().and_then(|date| date.and_hms_opt(0, 0, 0))
+}
+
+/// Title from the file name: `2024-05-01-hello-world.md` becomes "Hello world"
+fn title_from_file_name(file_name: &str) -> String {
+    let stem = file_name.rsplit_once('.').map_or(file_name, |(stem, _)| stem);
+    // Jekyll prefixes post file names with their date
+    let stem = stem.get(..10)
+        .filter(|prefix| NaiveDate::parse_from_str(prefix, "%Y-%m-%d").is_ok())
+        .and_then(|_| stem[10..].strip_prefix('-'))
+        .unwrap_or(stem);
+    let words = stem.replace(['-', '_'], " ");
+    let mut chars = words.trim().chars();
+    chars.next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Split a leading `# Heading` off a body without front matter
+fn take_heading(body: &str) -> Option<(String, String)> {
+    let body = body.trim_start();
+    let (first, rest) = body.split_once('\n').unwrap_or((body, ""));
+    let heading = first.strip_prefix("# ")?.trim();
+    (!heading.is_empty()).then(|| (heading.to_string(), rest.to_string()))
+}
+
+/// Parse one uploaded file into post fields
+///
+/// Fails when the front matter is malformed or a mapped field has the wrong
+/// shape; keys that have no post field are only reported as warnings.
+pub fn parse_markdown_file(file_name: &str, text: &str) -> Result<ImportedPost, AppError> {
+    let extension = file_name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase()).unwrap_or_default();
+    if !MARKDOWN_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(invalid(format!("Not a Markdown file; expected one of: .{}", MARKDOWN_EXTENSIONS.join(", ."))));
+    }
+
+    let text = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let (front_matter, body) = match text.strip_prefix("---\n") {
+        Some(rest) => {
+            let end = rest.find("\n---\n").map(|i| (i, i + 5))
+                .or_else(|| rest.find("\n...\n").map(|i| (i, i + 5)))
+                .or_else(|| rest.strip_suffix("\n---").map(|block| (block.len(), rest.len())))
+                .ok_or_else(|| invalid("Front matter is not closed with `---`".to_string()))?;
+            (parse_front_matter(&rest[..end.0])?, rest[end.1..].to_string())
+        }
+        None => (Vec::new(), text.clone()),
+    };
+
+    let mut post = ImportedPost::default();
+    let mut skipped = Vec::new();
+    for (key, value) in front_matter {
+        match (key.as_str(), value) {
+            ("title", YamlValue::Scalar(title)) => post.title = title,
+            ("date", YamlValue::Scalar(date)) if !date.is_empty() => {
+                post.date = Some(parse_date(&date)
+                    .ok_or_else(|| invalid(format!("Unrecognised date '{}'", date)))?);
+            }
+            ("categories" | "category", YamlValue::Scalar(name)) => post.category = Some(name).filter(|n| !n.is_empty()),
+            ("categories" | "category", YamlValue::List(names)) => {
+                let mut names = names.into_iter();
+                post.category = names.next();
+                let extra: Vec<String> = names.collect();
+                if !extra.is_empty() {
+                    post.warnings.push(format!("Posts have one category; also listed: {}", extra.join(", ")));
+                }
+            }
+            ("title" | "date", YamlValue::List(_)) => {
+                return Err(invalid(format!("Front matter `{}` must be a single value", key)));
+            }
+            ("tags", _) => post.warnings.push("Tags are not supported and were skipped".to_string()),
+            _ => skipped.push(key),
+        }
+    }
+    if !skipped.is_empty() {
+        post.warnings.push(format!("Ignored front matter: {}", skipped.join(", ")));
+    }
+
+    post.content = body.trim().to_string();
+    if post.title.trim().is_empty() {
+        post.title = match take_heading(&post.content) {
+            Some((heading, rest)) => {
+                post.content = rest.trim().to_string();
+                heading
+            }
+            None => title_from_file_name(file_name),
+        };
+    }
+    post.title = post.title.trim().to_string();
+    if post.title.is_empty() {
+        return Err(invalid("No title in the front matter, a heading or the file name".to_string()));
+    }
+    Ok(post)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::markdown_export::{post_markdown, ExportedPost};
+
+    #[test]
+    fn test_jekyll_front_matter() {
+        let file = "---\n\
+            layout: post\n\
+            title: 'It''s \"here\"'\n\
+            date: 2024-05-01 09:30:00 +0200\n\
+            categories: [News, \"Rust, mostly\"]\n\
+            tags:\n\
+            \x20 - rust\n\
+            \x20 - cms\n\
+            ---\n\
+            \n\
+            Body *text*.\n";
+        let post = parse_markdown_file("2024-05-01-its-here.md", file).unwrap();
+        assert_eq!(post.title, "It's \"here\"");
+        assert_eq!(post.content, "Body *text*.");
+        assert_eq!(post.category.as_deref(), Some("News"));
+        assert_eq!(post.date.unwrap().to_string(), "2024-05-01 07:30:00");
+        assert_eq!(post.warnings, vec![
+            "Posts have one category; also listed: Rust, mostly".to_string(),
+            "Tags are not supported and were skipped".to_string(),
+            "Ignored front matter: layout".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_round_trip_with_export() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).and_then(|d| d.and_hms_opt(9, 30, 0));
+        let exported = post_markdown(&ExportedPost {
+            title: "Hello: \"World\"",
+            slug: "hello-world",
+            author: "admin",
+            category: Some("General"),
+            status: "draft",
+            date,
+            content: "# Intro\n\nSome text.",
+        });
+        let post = parse_markdown_file("hello-world.md", &exported).unwrap();
+        assert_eq!(post.title, "Hello: \"World\"");
+        assert_eq!(post.content, "# Intro\n\nSome text.");
+        assert_eq!(post.category.as_deref(), Some("General"));
+        assert_eq!(post.date, date);
+    }
+
+    #[test]
+    fn test_title_without_front_matter() {
+        let post = parse_markdown_file("notes.markdown", "# First heading\n\nText").unwrap();
+        assert_eq!((post.title.as_str(), post.content.as_str()), ("First heading", "Text"));
+
+        let post = parse_markdown_file("2023-01-02-hugo_and-jekyll.md", "Just text").unwrap();
+        assert_eq!(post.title, "Hugo and jekyll");
+        assert_eq!(post.date, None);
+    }
+
+    #[test]
+    fn test_rejects_malformed_files() {
+        assert!(parse_markdown_file("post.html", "<p>Hi</p>").is_err());
+        assert!(parse_markdown_file("post.md", "---\ntitle: Open\n\nNo closing line").is_err());
+        assert!(parse_markdown_file("post.md", "---\ntitle: Dated\ndate: last tuesday\n---\nBody").is_err());
+        assert!(parse_markdown_file("post.md", "---\n- orphan\n---\nBody").is_err());
+        assert!(parse_markdown_file("post.md", "---\ntitle: [a, b]\n---\nBody").is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! In-process performance metrics
+//!
+//! Request and database latencies are recorded into fixed-bucket histograms
+//! backed by atomics, so recording is lock-free and costs a few increments.
+//! Percentiles are approximate: they report the upper bound of the bucket the
+//! requested rank falls into. Response cache lookups are counted the same
+//! way, for the cache hit rate.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds of the latency buckets, in microseconds. Anything slower than
+/// the last bound lands in an overflow bucket reported as the observed max.
+const BUCKET_BOUNDS_MICROS: [u64; 16] = [
+    500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000,
+    250_000, 500_000, 1_000_000, 2_500_000, 5_000_000, 10_000_000, 30_000_000, 60_000_000,
+];
+
+/// Lock-free latency histogram
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; BUCKET_BOUNDS_MICROS.len() + 1],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+    min_micros: AtomicU64,
+    max_micros: AtomicU64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            count: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+            min_micros: AtomicU64::new(u64::MAX),
+            max_micros: AtomicU64::new(0),
+        }
+    }
+}
+
+impl LatencyHistogram {
+    pub fn record(&self, duration: Duration) {
+        let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        let bucket = BUCKET_BOUNDS_MICROS
+            .iter()
+            .position(|&bound| micros <= bound)
+            .unwrap_or(BUCKET_BOUNDS_MICROS.len());
+
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+        self.min_micros.fetch_min(micros, Ordering::Relaxed);
+        self.max_micros.fetch_max(micros, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn snapshot(&self) -> LatencySnapshot {
+        let count = self.count();
+        if count == 0 {
+            return LatencySnapshot::default();
+        }
+
+        let counts: Vec<u64> = self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect();
+        let max_micros = self.max_micros.load(Ordering::Relaxed);
+        let percentile = |p: f64| micros_to_ms(bucket_percentile(&counts, max_micros, p));
+
+        LatencySnapshot {
+            count,
+            avg_ms: micros_to_ms(self.sum_micros.load(Ordering::Relaxed) / count),
+            min_ms: micros_to_ms(self.min_micros.load(Ordering::Relaxed)),
+            max_ms: micros_to_ms(max_micros),
+            p50_ms: percentile(0.50),
+            p95_ms: percentile(0.95),
+            p99_ms: percentile(0.99),
+        }
+    }
+}
+
+/// Upper bound, in microseconds, of the bucket holding the `p` quantile
+fn bucket_percentile(counts: &[u64], max_micros: u64, p: f64) -> u64 {
+    let total: u64 = counts.iter().sum();
+    if total == 0 {
+        return 0;
+    }
+
+    let rank = ((total as f64 * p).ceil() as u64).clamp(1, total);
+    let mut seen = 0;
+    for (index, count) in counts.iter().enumerate() {
+        seen += count;
+        if seen >= rank {
+            // Never report more than was actually observed
+            return BUCKET_BOUNDS_MICROS.get(index).copied().unwrap_or(max_micros).min(max_micros);
+        }
+    }
+    max_micros
+}
+
+fn micros_to_ms(micros: u64) -> f64 {
+    micros as f64 / 1000.0
+}
+
+/// Point-in-time summary of a latency histogram, in milliseconds
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencySnapshot {
+    pub count: u64,
+    pub avg_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+/// Shared collector for request and database timings
+#[derive(Clone, Default)]
+pub struct MetricsCollector {
+    inner: Arc<MetricsInner>,
+}
+
+#[derive(Default)]
+struct MetricsInner {
+    requests: LatencyHistogram,
+    server_errors: AtomicU64,
+    db_queries: LatencyHistogram,
+    cache_lookups: AtomicU64,
+    cache_hits: AtomicU64,
+}
+
+impl MetricsCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a completed HTTP request and whether it failed with a 5xx
+    pub fn record_request(&self, duration: Duration, server_error: bool) {
+        self.inner.requests.record(duration);
+        if server_error {
+            self.inner.server_errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record the time spent running one database operation
+    pub fn record_db_query(&self, duration: Duration) {
+        self.inner.db_queries.record(duration);
+    }
+
+    /// Record a lookup in a response cache and whether it was served from it
+    pub fn record_cache_lookup(&self, hit: bool) {
+        self.inner.cache_lookups.fetch_add(1, Ordering::Relaxed);
+        if hit {
+            self.inner.cache_hits.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn request_latency(&self) -> LatencySnapshot {
+        self.inner.requests.snapshot()
+    }
+
+    pub fn db_query_latency(&self) -> LatencySnapshot {
+        self.inner.db_queries.snapshot()
+    }
+
+    /// Percentage of recorded requests that ended in a server error
+    pub fn error_rate(&self) -> f64 {
+        let total = self.inner.requests.count();
+        if total == 0 {
+            return 0.0;
+        }
+        self.inner.server_errors.load(Ordering::Relaxed) as f64 / total as f64 * 100.0
+    }
+
+    /// Percentage of recorded cache lookups served from the cache
+    pub fn cache_hit_rate(&self) -> f64 {
+        let total = self.inner.cache_lookups.load(Ordering::Relaxed);
+        if total == 0 {
+            return 0.0;
+        }
+        self.inner.cache_hits.load(Ordering::Relaxed) as f64 / total as f64 * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_histogram_reports_zeroes() {
+        let histogram = LatencyHistogram::default();
+        assert_eq!(histogram.snapshot(), LatencySnapshot::default());
+    }
+
+    #[test]
+    fn test_percentiles_use_bucket_upper_bounds() {
+        let histogram = LatencyHistogram::default();
+        for _ in 0..90 {
+            histogram.record(Duration::from_millis(3));
+        }
+        for _ in 0..9 {
+            histogram.record(Duration::from_millis(40));
+        }
+        histogram.record(Duration::from_millis(400));
+
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.count, 100);
+        assert_eq!(snapshot.p50_ms, 5.0);
+        assert_eq!(snapshot.p95_ms, 50.0);
+        assert_eq!(snapshot.p99_ms, 50.0);
+        assert_eq!(snapshot.min_ms, 3.0);
+        assert_eq!(snapshot.max_ms, 400.0);
+    }
+
+    #[test]
+    fn test_percentiles_never_exceed_observed_max() {
+        let histogram = LatencyHistogram::default();
+        histogram.record(Duration::from_millis(120));
+        assert_eq!(histogram.snapshot().p99_ms, 120.0);
+
+        // Slower than the last bucket bound
+        histogram.record(Duration::from_secs(90));
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.p50_ms, 250.0);
+        assert_eq!(snapshot.p99_ms, 90_000.0);
+    }
+
+    #[test]
+    fn test_error_rate_counts_server_errors() {
+        let metrics = MetricsCollector::new();
+        assert_eq!(metrics.error_rate(), 0.0);
+
+        metrics.record_request(Duration::from_millis(10), false);
+        metrics.record_request(Duration::from_millis(10), false);
+        metrics.record_request(Duration::from_millis(10), false);
+        metrics.record_request(Duration::from_millis(10), true);
+
+        assert_eq!(metrics.error_rate(), 25.0);
+        assert_eq!(metrics.request_latency().count, 4);
+    }
+
+    #[test]
+    fn test_cache_hit_rate_counts_lookups() {
+        let metrics = MetricsCollector::new();
+        assert_eq!(metrics.cache_hit_rate(), 0.0);
+
+        metrics.record_cache_lookup(false);
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(true);
+
+        assert_eq!(metrics.cache_hit_rate(), 75.0);
+    }
+}
@@ -4,6 +4,55 @@ pub mod backup_service;
 pub mod input_sanitization;
 pub mod db_service;
 pub mod session_signing;
+pub mod slugs;
+pub mod structured_data;
+pub mod preview_links;
+pub mod share_previews;
+pub mod metrics;
+pub mod image_alt;
+pub mod comment_limits;
+pub mod comment_rate_limit;
+pub mod comment_sort;
+pub mod navigation_urls;
+pub mod visibility_window;
+pub mod post_schedule;
+pub mod timezones;
+pub mod theme_presets;
+pub mod color_schemes;
+pub mod verification_tokens;
+pub mod signup_policy;
+pub mod email_domains;
+pub mod password_hashing;
+pub mod user_uniqueness;
+pub mod search;
+pub mod homepage;
+pub mod slug_redirects;
+pub mod captcha;
+pub mod cursor;
+pub mod feed;
+pub mod excerpts;
+pub mod sitemap;
+pub mod gravatar;
+pub mod markdown_export;
+pub mod markdown_import;
+pub mod bootstrap_cache;
+pub mod canonical_urls;
+pub mod branding;
+pub mod text_direction;
+pub mod session_cookie;
+pub mod comment_moderation;
+pub mod slow_query_log;
+pub mod idempotency;
+pub mod word_filter;
+pub mod client_ip;
+pub mod data_retention;
+pub mod permissions;
+pub mod component_templates;
+pub mod template_schema;
+pub mod page_blocks;
+pub mod pagination;
+#[cfg(feature = "ssr")]
+pub mod ssr;
 // Temporarily disabled for Docker build
 // pub mod email_service;
 
@@ -11,5 +60,7 @@ pub use session_manager::*;
 pub use backup_service::*;
 pub use db_service::DbService;
 pub use session_signing::SessionSigner;
+pub use preview_links::PreviewSigner;
+pub use metrics::MetricsCollector;
 // Temporarily disabled for Docker build
 // pub use email_service::*;
\ No newline at end of file
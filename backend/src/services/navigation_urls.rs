@@ -0,0 +1,110 @@
+//! Validation for navigation item links
+//!
+//! Navigation URLs end up in `href` and `data-url` attributes on every public
+//! page, so only site-relative paths and absolute http(s) URLs are accepted.
+//! `target` and `css_class` are reduced to values that are safe to render.
+
+use url::Url;
+use crate::middleware::errors::AppError;
+
+/// Link targets the public layout knows how to render
+const ALLOWED_TARGETS: [&str; 4] = ["_self", "_blank", "_parent", "_top"];
+
+/// Validate a navigation URL, returning the trimmed value to store
+pub fn validate_nav_url(input: &str) -> Result<String, AppError> {
+    let url = input.trim();
+    if url.is_empty() {
+        return Err(AppError::ValidationError("URL cannot be empty".to_string()));
+    }
+    // Browsers drop tabs and newlines inside URLs, which can hide a scheme
+    if url.chars().any(|c| c.is_control() || c.is_whitespace()) {
+        return Err(AppError::ValidationError("URL cannot contain whitespace or control characters".to_string()));
+    }
+
+    if url.starts_with('/') {
+        // "//host" and "/\host" are protocol-relative links to another site
+        if url.starts_with("//") || url.starts_with("/\\") {
+            return Err(AppError::ValidationError(
+                "Relative URLs must be a path on this site; use http(s):// for external links".to_string()
+            ));
+        }
+        return Ok(url.to_string());
+    }
+
+    let parsed = Url::parse(url).map_err(|_| AppError::ValidationError(
+        "URL must be a relative path starting with '/' or an http(s) URL".to_string()
+    ))?;
+    match parsed.scheme() {
+        "http" | "https" if parsed.host_str().is_some() => Ok(url.to_string()),
+        "http" | "https" => Err(AppError::ValidationError("URL must include a host".to_string())),
+        scheme => Err(AppError::ValidationError(format!("URL scheme '{}:' is not allowed", scheme))),
+    }
+}
+
+/// Normalize a link target; blank means the default (same window)
+pub fn sanitize_target(target: Option<String>) -> Result<Option<String>, AppError> {
+    let Some(target) = target.map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()) else {
+        return Ok(None);
+    };
+    if ALLOWED_TARGETS.contains(&target.as_str()) {
+        Ok(Some(target))
+    } else {
+        Err(AppError::ValidationError(format!(
+            "Link target must be one of: {}",
+            ALLOWED_TARGETS.join(", ")
+        )))
+    }
+}
+
+/// Keep only class names made of letters, digits, '-' and '_'
+pub fn sanitize_css_class(css_class: Option<String>) -> Option<String> {
+    let classes: Vec<&str> = css_class.as_deref()?
+        .split_whitespace()
+        .filter(|class| class.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .collect();
+    (!classes.is_empty()).then(|| classes.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_and_http_urls_are_accepted() {
+        assert_eq!(validate_nav_url(" /blog ").unwrap(), "/blog");
+        assert!(validate_nav_url("/posts/1?tab=comments#top").is_ok());
+        assert!(validate_nav_url("https://example.com/docs").is_ok());
+        assert!(validate_nav_url("http://example.com").is_ok());
+    }
+
+    #[test]
+    fn test_dangerous_schemes_are_rejected() {
+        assert!(validate_nav_url("javascript:alert(1)").is_err());
+        assert!(validate_nav_url("JavaScript:alert(document.cookie)").is_err());
+        assert!(validate_nav_url("data:text/html,<script>alert(1)</script>").is_err());
+        assert!(validate_nav_url("vbscript:msgbox").is_err());
+        assert!(validate_nav_url("java\tscript:alert(1)").is_err());
+        // Passed the old `starts_with("http")` check
+        assert!(validate_nav_url("httpjavascript:alert(1)").is_err());
+    }
+
+    #[test]
+    fn test_protocol_relative_urls_are_rejected() {
+        assert!(validate_nav_url("//evil.example").is_err());
+        assert!(validate_nav_url("/\\evil.example").is_err());
+        assert!(validate_nav_url("https://").is_err());
+    }
+
+    #[test]
+    fn test_target_and_css_class_are_sanitized() {
+        assert_eq!(sanitize_target(Some(" _BLANK ".to_string())).unwrap(), Some("_blank".to_string()));
+        assert_eq!(sanitize_target(Some("".to_string())).unwrap(), None);
+        assert!(sanitize_target(Some("\" onclick=\"alert(1)".to_string())).is_err());
+
+        assert_eq!(
+            sanitize_css_class(Some("nav-link active \"><script> btn_primary".to_string())),
+            Some("nav-link active btn_primary".to_string())
+        );
+        assert_eq!(sanitize_css_class(Some("\"onmouseover=alert(1)".to_string())), None);
+    }
+}
@@ -0,0 +1,160 @@
+//! Checks on page builder blocks with settings the server acts on
+//!
+//! Page content is the page builder's JSON component list. Most blocks only
+//! carry presentation, but a Posts List block asks the public renderer to
+//! fetch posts: how many (`posts_list_count`), from which category
+//! (`posts_list_category_id`, null for all) and in which layout
+//! (`posts_list_layout`, `grid` with `posts_list_columns` or `list`). Saving
+//! a page checks those settings, including nested blocks, so a page can't
+//! ask for a category that doesn't exist. Plain-text pages have no blocks.
+
+use diesel::prelude::*;
+use serde_json::Value;
+use crate::middleware::errors::AppError;
+use crate::models::Category;
+
+/// Page builder properties holding child components
+pub const NESTED_COMPONENT_FIELDS: [&str; 4] = [
+    "nested_components",
+    "column_1_components",
+    "column_2_components",
+    "column_3_components",
+];
+
+pub const POSTS_LIST_BLOCK: &str = "PostsList";
+pub const MAX_POSTS_LIST_COUNT: i64 = 50;
+pub const MAX_POSTS_LIST_COLUMNS: i64 = 4;
+pub const POSTS_LIST_LAYOUTS: [&str; 2] = ["grid", "list"];
+
+/// What a Posts List block shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostsListBlock {
+    pub count: i64,
+    pub category_id: Option<i32>,
+    pub layout: String,
+}
+
+/// A whole number property within `range`; absent means the editor's default
+fn whole_number(properties: &Value, key: &str, range: std::ops::RangeInclusive<i64>, default: i64) -> Result<i64, String> {
+    match &properties[key] {
+        Value::Null => Ok(default),
+        value => value.as_i64()
+            .filter(|number| range.contains(number))
+            .ok_or_else(|| format!("{} must be a whole number from {} to {}", key, range.start(), range.end())),
+    }
+}
+
+/// Check a Posts List block's properties
+pub fn parse_posts_list(properties: &Value) -> Result<PostsListBlock, String> {
+    let count = whole_number(properties, "posts_list_count", 1..=MAX_POSTS_LIST_COUNT, 6)?;
+    whole_number(properties, "posts_list_columns", 1..=MAX_POSTS_LIST_COLUMNS, 3)?;
+    let category_id = match &properties["posts_list_category_id"] {
+        Value::Null => None,
+        value => Some(value.as_i64()
+            .filter(|id| *id > 0)
+            .and_then(|id| i32::try_from(id).ok())
+            .ok_or("posts_list_category_id must be a category id or null")?),
+    };
+    let layout = match &properties["posts_list_layout"] {
+        Value::Null => POSTS_LIST_LAYOUTS[0],
+        value => value.as_str()
+            .filter(|layout| POSTS_LIST_LAYOUTS.contains(layout))
+            .ok_or_else(|| format!("posts_list_layout must be one of {}", POSTS_LIST_LAYOUTS.join(", ")))?,
+    };
+    Ok(PostsListBlock { count, category_id, layout: layout.to_string() })
+}
+
+fn collect_posts_lists(components: &[Value], blocks: &mut Vec<PostsListBlock>) -> Result<(), String> {
+    for component in components {
+        let properties = &component["properties"];
+        if component["component_type"].as_str() == Some(POSTS_LIST_BLOCK) {
+            blocks.push(parse_posts_list(properties)?);
+        }
+        for field in NESTED_COMPONENT_FIELDS {
+            if let Some(children) = properties[field].as_array() {
+                collect_posts_lists(children, blocks)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The Posts List blocks in page content, checked; none for plain-text pages
+pub fn posts_list_blocks(content: &str) -> Result<Vec<PostsListBlock>, String> {
+    let mut blocks = Vec::new();
+    if let Ok(Value::Array(components)) = serde_json::from_str::<Value>(content) {
+        collect_posts_lists(&components, &mut blocks)?;
+    }
+    Ok(blocks)
+}
+
+/// Check the blocks of page content before it is saved
+pub fn validate_page_blocks(conn: &mut PgConnection, content: &str) -> Result<(), AppError> {
+    let invalid = |message: String| AppError::ValidationError(format!("Posts List block: {}", message));
+    for block in posts_list_blocks(content).map_err(invalid)? {
+        if let Some(category_id) = block.category_id {
+            if Category::find_by_id(conn, category_id)?.is_none() {
+                return Err(invalid(format!("category {} does not exist", category_id)));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn page(properties: Value) -> String {
+        json!([{ "component_type": POSTS_LIST_BLOCK, "properties": properties }]).to_string()
+    }
+
+    #[test]
+    fn test_posts_list_settings() {
+        let blocks = posts_list_blocks(&page(json!({
+            "posts_list_count": 3,
+            "posts_list_category_id": 7,
+            "posts_list_layout": "list",
+        }))).unwrap();
+        assert_eq!(blocks, vec![PostsListBlock { count: 3, category_id: Some(7), layout: "list".to_string() }]);
+
+        // Blocks saved before these settings existed get the defaults
+        assert_eq!(posts_list_blocks(&page(json!({}))).unwrap()[0], PostsListBlock {
+            count: 6,
+            category_id: None,
+            layout: "grid".to_string(),
+        });
+    }
+
+    #[test]
+    fn test_invalid_posts_list() {
+        for properties in [
+            json!({ "posts_list_count": 0 }),
+            json!({ "posts_list_count": MAX_POSTS_LIST_COUNT + 1 }),
+            json!({ "posts_list_count": "6" }),
+            json!({ "posts_list_columns": 5 }),
+            json!({ "posts_list_category_id": -1 }),
+            json!({ "posts_list_category_id": "news" }),
+            json!({ "posts_list_layout": "carousel" }),
+        ] {
+            assert!(posts_list_blocks(&page(properties.clone())).is_err(), "{} should be refused", properties);
+        }
+    }
+
+    #[test]
+    fn test_nested_blocks_and_plain_pages() {
+        let content = json!([{
+            "component_type": "TwoColumn",
+            "properties": {
+                "column_2_components": [{ "component_type": POSTS_LIST_BLOCK, "properties": { "posts_list_layout": "list" } }],
+            },
+        }]).to_string();
+        assert_eq!(posts_list_blocks(&content).unwrap()[0].layout, "list");
+        let nested_invalid = content.replace("\"list\"", "\"tiles\"");
+        assert!(posts_list_blocks(&nested_invalid).is_err());
+
+        assert!(posts_list_blocks("# About us\n\nPlain Markdown").unwrap().is_empty());
+        assert!(posts_list_blocks(&json!([{ "component_type": "Text", "properties": {} }]).to_string()).unwrap().is_empty());
+    }
+}
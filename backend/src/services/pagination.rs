@@ -0,0 +1,294 @@
+//! Page parameters and `X-Total-Count`/`Link` headers for paginated list
+//! endpoints
+//!
+//! `PaginationParams` reads `page` and `per_page` from the query string,
+//! filling in the configured default page size and clamping to the
+//! configured maximum (`PAGINATION_DEFAULT_PER_PAGE` and
+//! `PAGINATION_MAX_PER_PAGE`), so every list pages the same way.
+//!
+//! The JSON envelopes already carry the totals and cursors; the headers
+//! let generic clients page through a list without parsing the body. Link
+//! targets are the request's own path and query with only the page (or
+//! cursor) parameter replaced, so filters carry over. They are relative
+//! references, which RFC 8288 resolves against the request URL.
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap, HeaderName, HeaderValue, Uri},
+};
+use crate::{middleware::errors::AppError, AppServices};
+
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/// Highest page number accepted; past it the offset is meaningless
+pub const MAX_PAGE_NUMBER: i64 = 100_000;
+/// Page sizes above the configured maximum are clamped, but ones past this
+/// are refused as mistakes
+pub const MAX_REQUESTED_PER_PAGE: i64 = 10_000;
+
+/// Default and largest page size, from `Config`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaginationConfig {
+    pub default_per_page: i64,
+    pub max_per_page: i64,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            default_per_page: 20,
+            max_per_page: 100,
+        }
+    }
+}
+
+impl PaginationConfig {
+    /// Build from the raw environment values; empty values use the defaults
+    pub fn from_config_values(default_per_page: &str, max_per_page: &str) -> Result<Self, String> {
+        let defaults = Self::default();
+        let parse = |name: &str, raw: &str, fallback: i64| match raw.trim() {
+            "" => Ok(fallback),
+            raw => raw.parse::<i64>()
+                .ok()
+                .filter(|value| (1..=MAX_REQUESTED_PER_PAGE).contains(value))
+                .ok_or_else(|| format!("{} must be a whole number from 1 to {}", name, MAX_REQUESTED_PER_PAGE)),
+        };
+        let max_per_page = parse("PAGINATION_MAX_PER_PAGE", max_per_page, defaults.max_per_page)?;
+        let default_per_page = parse("PAGINATION_DEFAULT_PER_PAGE", default_per_page, defaults.default_per_page.min(max_per_page))?;
+        if default_per_page > max_per_page {
+            return Err("PAGINATION_DEFAULT_PER_PAGE must not exceed PAGINATION_MAX_PER_PAGE".to_string());
+        }
+        Ok(Self { default_per_page, max_per_page })
+    }
+
+    /// The page size to use for a requested one
+    pub fn per_page(&self, requested: Option<i64>) -> Result<i64, AppError> {
+        match requested {
+            None => Ok(self.default_per_page),
+            Some(per_page) if !(1..=MAX_REQUESTED_PER_PAGE).contains(&per_page) => Err(AppError::ValidationError(
+                format!("per_page must be between 1 and {}", MAX_REQUESTED_PER_PAGE)
+            )),
+            Some(per_page) => Ok(per_page.min(self.max_per_page)),
+        }
+    }
+}
+
+/// `page` and `per_page` of a list request, resolved against the
+/// configured defaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaginationParams {
+    pub page: i64,
+    pub per_page: i64,
+}
+
+impl PaginationParams {
+    /// Resolve a requested page and page size; missing ones take the defaults
+    pub fn new(page: Option<i64>, per_page: Option<i64>, config: &PaginationConfig) -> Result<Self, AppError> {
+        let page = page.unwrap_or(1);
+        if !(1..=MAX_PAGE_NUMBER).contains(&page) {
+            return Err(AppError::ValidationError(format!("page must be between 1 and {}", MAX_PAGE_NUMBER)));
+        }
+        Ok(Self { page, per_page: config.per_page(per_page)? })
+    }
+
+    /// Parse the parameters from a query string, ignoring any others
+    pub fn from_query(query: Option<&str>, config: &PaginationConfig) -> Result<Self, AppError> {
+        let mut page = None;
+        let mut per_page = None;
+        for (name, value) in query.unwrap_or("").split('&').filter_map(|pair| pair.split_once('=')) {
+            let slot = match name {
+                "page" => &mut page,
+                "per_page" => &mut per_page,
+                _ => continue,
+            };
+            if value.is_empty() {
+                continue;
+            }
+            *slot = Some(value.parse::<i64>()
+                .map_err(|_| AppError::ValidationError(format!("{} must be a whole number", name)))?);
+        }
+        Self::new(page, per_page, config)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page - 1) * self.per_page
+    }
+
+    pub fn total_pages(&self, total: i64) -> i64 {
+        (total + self.per_page - 1) / self.per_page
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppServices> for PaginationParams {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, services: &AppServices) -> Result<Self, Self::Rejection> {
+        Self::from_query(parts.uri.query(), &services.pagination)
+    }
+}
+
+/// The request URI with `param` set to `value` and every other query
+/// parameter kept as sent
+fn with_param(uri: &Uri, param: &str, value: &str) -> String {
+    let mut pairs: Vec<&str> = uri.query()
+        .unwrap_or("")
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(param))
+        .collect();
+    let replaced = format!("{}={}", param, value);
+    pairs.push(&replaced);
+    format!("{}?{}", uri.path(), pairs.join("&"))
+}
+
+fn link(uri: &Uri, param: &str, value: &str, rel: &str) -> String {
+    format!("<{}>; rel=\"{}\"", with_param(uri, param, value), rel)
+}
+
+fn insert(headers: &mut HeaderMap, name: HeaderName, value: String) {
+    // Only fails on control characters, which a request URI can't contain
+    if let Ok(value) = HeaderValue::from_str(&value) {
+        headers.insert(name, value);
+    }
+}
+
+/// Headers for one page of an offset-paged list: the total and `first`,
+/// `prev`, `next` and `last` links as far as they exist
+pub fn page_headers(uri: &Uri, page: i64, per_page: i64, total: i64) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    insert(&mut headers, HeaderName::from_static(TOTAL_COUNT_HEADER), total.to_string());
+
+    let last_page = ((total + per_page - 1) / per_page.max(1)).max(1);
+    let mut links = vec![link(uri, "page", "1", "first")];
+    if page > 1 {
+        links.push(link(uri, "page", &(page - 1).min(last_page).to_string(), "prev"));
+    }
+    if page < last_page {
+        links.push(link(uri, "page", &(page + 1).to_string(), "next"));
+    }
+    links.push(link(uri, "page", &last_page.to_string(), "last"));
+    insert(&mut headers, axum::http::header::LINK, links.join(", "));
+    headers
+}
+
+/// Headers for one page of a cursor-paged list: the total when it is known
+/// and a `next` link while more pages remain
+pub fn cursor_headers(uri: &Uri, total: Option<i64>, next_cursor: Option<&str>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(total) = total {
+        insert(&mut headers, HeaderName::from_static(TOTAL_COUNT_HEADER), total.to_string());
+    }
+    if let Some(cursor) = next_cursor {
+        insert(&mut headers, axum::http::header::LINK, link(uri, "cursor", cursor, "next"));
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+        headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    #[test]
+    fn test_middle_page_links_keep_filters() {
+        let uri: Uri = "/api/admin/posts?status=draft&page=2&per_page=10&search=rust%20cms".parse().unwrap();
+        let headers = page_headers(&uri, 2, 10, 35);
+
+        assert_eq!(header(&headers, "x-total-count"), Some("35"));
+        assert_eq!(
+            header(&headers, "link"),
+            Some("</api/admin/posts?status=draft&per_page=10&search=rust%20cms&page=1>; rel=\"first\", \
+                  </api/admin/posts?status=draft&per_page=10&search=rust%20cms&page=1>; rel=\"prev\", \
+                  </api/admin/posts?status=draft&per_page=10&search=rust%20cms&page=3>; rel=\"next\", \
+                  </api/admin/posts?status=draft&per_page=10&search=rust%20cms&page=4>; rel=\"last\"")
+        );
+    }
+
+    #[test]
+    fn test_first_and_last_pages_omit_missing_neighbours() {
+        let uri: Uri = "/api/pages".parse().unwrap();
+        let first = header(&page_headers(&uri, 1, 20, 45), "link").unwrap().to_string();
+        assert!(first.contains("</api/pages?page=2>; rel=\"next\""));
+        assert!(!first.contains("rel=\"prev\""));
+
+        let last = header(&page_headers(&uri, 3, 20, 45), "link").unwrap().to_string();
+        assert!(last.contains("</api/pages?page=2>; rel=\"prev\""));
+        assert!(!last.contains("rel=\"next\""));
+        assert!(last.contains("</api/pages?page=3>; rel=\"last\""));
+
+        // An empty list still has one (empty) page
+        let empty = page_headers(&uri, 1, 20, 0);
+        assert_eq!(header(&empty, "x-total-count"), Some("0"));
+        assert!(!header(&empty, "link").unwrap().contains("rel=\"next\""));
+    }
+
+    #[test]
+    fn test_params_default_and_clamp() {
+        let config = PaginationConfig::default();
+        assert_eq!(PaginationParams::from_query(None, &config).unwrap(), PaginationParams { page: 1, per_page: 20 });
+
+        let params = PaginationParams::from_query(Some("status=draft&page=3&per_page=500"), &config).unwrap();
+        assert_eq!(params, PaginationParams { page: 3, per_page: 100 });
+        assert_eq!(params.offset(), 200);
+        assert_eq!(params.total_pages(201), 3);
+
+        let empty = PaginationParams::from_query(Some("page=&per_page="), &config).unwrap();
+        assert_eq!(empty, PaginationParams { page: 1, per_page: 20 });
+    }
+
+    #[test]
+    fn test_params_reject_absurd_values() {
+        let config = PaginationConfig::default();
+        for query in ["page=0", "page=-2", "page=1000000", "per_page=0", "per_page=1000000", "page=two", "per_page=1.5"] {
+            assert!(
+                matches!(PaginationParams::from_query(Some(query), &config), Err(AppError::ValidationError(_))),
+                "{} should be rejected", query
+            );
+        }
+    }
+
+    #[test]
+    fn test_per_page_error_names_the_accepted_bound() {
+        let config = PaginationConfig::default();
+        assert_eq!(config.per_page(Some(MAX_REQUESTED_PER_PAGE)).unwrap(), config.max_per_page);
+        match config.per_page(Some(MAX_REQUESTED_PER_PAGE + 1)) {
+            Err(AppError::ValidationError(message)) => {
+                assert_eq!(message, format!("per_page must be between 1 and {}", MAX_REQUESTED_PER_PAGE));
+            }
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_config_values() {
+        assert_eq!(PaginationConfig::from_config_values("", "").unwrap(), PaginationConfig::default());
+        assert_eq!(
+            PaginationConfig::from_config_values(" 50 ", "250").unwrap(),
+            PaginationConfig { default_per_page: 50, max_per_page: 250 }
+        );
+        // A lower maximum pulls the default down with it
+        assert_eq!(
+            PaginationConfig::from_config_values("", "10").unwrap(),
+            PaginationConfig { default_per_page: 10, max_per_page: 10 }
+        );
+        assert!(PaginationConfig::from_config_values("200", "100").is_err());
+        assert!(PaginationConfig::from_config_values("0", "").is_err());
+        assert!(PaginationConfig::from_config_values("", "lots").is_err());
+    }
+
+    #[test]
+    fn test_cursor_links() {
+        let uri: Uri = "/api/comments/public?post_id=4&cursor=&limit=2".parse().unwrap();
+        let headers = cursor_headers(&uri, None, Some("MTIzOjQ"));
+        assert_eq!(header(&headers, "link"), Some("</api/comments/public?post_id=4&limit=2&cursor=MTIzOjQ>; rel=\"next\""));
+        assert!(headers.get("x-total-count").is_none());
+
+        let last = cursor_headers(&uri, Some(5), None);
+        assert_eq!(header(&last, "x-total-count"), Some("5"));
+        assert!(last.get("link").is_none());
+    }
+}
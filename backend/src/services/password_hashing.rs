@@ -0,0 +1,213 @@
+//! Password hashing with a configurable algorithm and cost
+//!
+//! New hashes use bcrypt or Argon2id depending on `PASSWORD_HASH_ALGORITHM`.
+//! Verification picks the algorithm from the stored hash's prefix (`$2a$`,
+//! `$2b$`, `$2y$` for bcrypt, `$argon2id$` and friends for Argon2), so hashes
+//! made under an older configuration keep working and can be upgraded the
+//! next time their owner logs in.
+
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
+use crate::middleware::errors::AppError;
+
+pub const MIN_BCRYPT_COST: u32 = 10;
+pub const MAX_BCRYPT_COST: u32 = 16;
+/// OWASP's minimum recommendation for Argon2id: 19 MiB, 2 iterations
+pub const DEFAULT_ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+pub const DEFAULT_ARGON2_ITERATIONS: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordAlgorithm {
+    Bcrypt,
+    Argon2id,
+}
+
+/// How new password hashes are made
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordHashing {
+    pub algorithm: PasswordAlgorithm,
+    pub bcrypt_cost: u32,
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+}
+
+impl Default for PasswordHashing {
+    fn default() -> Self {
+        Self {
+            algorithm: PasswordAlgorithm::Bcrypt,
+            bcrypt_cost: bcrypt::DEFAULT_COST,
+            argon2_memory_kib: DEFAULT_ARGON2_MEMORY_KIB,
+            argon2_iterations: DEFAULT_ARGON2_ITERATIONS,
+        }
+    }
+}
+
+fn is_bcrypt_hash(hash: &str) -> bool {
+    ["$2a$", "$2b$", "$2y$"].iter().any(|prefix| hash.starts_with(prefix))
+}
+
+fn is_argon2_hash(hash: &str) -> bool {
+    hash.starts_with("$argon2")
+}
+
+/// Whether `hash` is in a format `verify` understands; anything else would be
+/// a plaintext or corrupted password column
+pub fn is_password_hash(hash: &str) -> bool {
+    is_bcrypt_hash(hash) || is_argon2_hash(hash)
+}
+
+impl PasswordHashing {
+    /// Build from the `PASSWORD_HASH_ALGORITHM`, `BCRYPT_COST`,
+    /// `ARGON2_MEMORY_KIB` and `ARGON2_ITERATIONS` values; empty values use
+    /// the defaults
+    pub fn from_config_values(
+        algorithm: &str,
+        bcrypt_cost: &str,
+        argon2_memory_kib: &str,
+        argon2_iterations: &str,
+    ) -> Result<Self, String> {
+        let defaults = Self::default();
+        let number = |name: &str, raw: &str, default: u32| -> Result<u32, String> {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                return Ok(default);
+            }
+            raw.parse().map_err(|_| format!("{} must be a whole number, got '{}'", name, raw))
+        };
+
+        let algorithm = match algorithm.trim().to_lowercase().as_str() {
+            "" | "bcrypt" => PasswordAlgorithm::Bcrypt,
+            "argon2" | "argon2id" => PasswordAlgorithm::Argon2id,
+            other => return Err(format!("PASSWORD_HASH_ALGORITHM must be bcrypt or argon2id, got '{}'", other)),
+        };
+
+        let bcrypt_cost = number("BCRYPT_COST", bcrypt_cost, defaults.bcrypt_cost)?;
+        if !(MIN_BCRYPT_COST..=MAX_BCRYPT_COST).contains(&bcrypt_cost) {
+            return Err(format!("BCRYPT_COST must be between {} and {}", MIN_BCRYPT_COST, MAX_BCRYPT_COST));
+        }
+
+        let hashing = Self {
+            algorithm,
+            bcrypt_cost,
+            argon2_memory_kib: number("ARGON2_MEMORY_KIB", argon2_memory_kib, defaults.argon2_memory_kib)?,
+            argon2_iterations: number("ARGON2_ITERATIONS", argon2_iterations, defaults.argon2_iterations)?,
+        };
+        hashing.argon2().map_err(|e| format!("Invalid Argon2 parameters: {}", e))?;
+        Ok(hashing)
+    }
+
+    fn argon2(&self) -> Result<Argon2<'static>, argon2::Error> {
+        let params = Params::new(self.argon2_memory_kib, self.argon2_iterations, Params::DEFAULT_P_COST, None)?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+
+    /// Hash a password with the configured algorithm and cost
+    pub fn hash(&self, password: &str) -> Result<String, AppError> {
+        let failed = |e: String| AppError::InternalError(format!("Password hashing failed: {}", e));
+        match self.algorithm {
+            PasswordAlgorithm::Bcrypt => bcrypt::hash(password, self.bcrypt_cost).map_err(|e| failed(e.to_string())),
+            PasswordAlgorithm::Argon2id => {
+                let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>()).map_err(|e| failed(e.to_string()))?;
+                self.argon2()
+                    .map_err(|e| failed(e.to_string()))?
+                    .hash_password(password.as_bytes(), &salt)
+                    .map(|hash| hash.to_string())
+                    .map_err(|e| failed(e.to_string()))
+            }
+        }
+    }
+
+    /// Check a password against a stored hash of either algorithm
+    pub fn verify(password: &str, hash: &str) -> Result<bool, AppError> {
+        let failed = |e: String| AppError::InternalError(format!("Password verification failed: {}", e));
+        if is_bcrypt_hash(hash) {
+            return bcrypt::verify(password, hash).map_err(|e| failed(e.to_string()));
+        }
+        if is_argon2_hash(hash) {
+            let parsed = PasswordHash::new(hash).map_err(|e| failed(e.to_string()))?;
+            // The parameters come from the hash itself
+            return Ok(Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok());
+        }
+        Err(failed("unrecognized hash format".to_string()))
+    }
+
+    /// Whether a stored hash is weaker than, or a different algorithm from,
+    /// what would be made now
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        match self.algorithm {
+            PasswordAlgorithm::Bcrypt => !is_bcrypt_hash(hash)
+                || hash.get(4..6).and_then(|cost| cost.parse::<u32>().ok()).is_none_or(|cost| cost < self.bcrypt_cost),
+            PasswordAlgorithm::Argon2id => {
+                let Ok(parsed) = PasswordHash::new(hash) else {
+                    return true;
+                };
+                let Ok(params) = Params::try_from(&parsed) else {
+                    return true;
+                };
+                parsed.algorithm != Algorithm::Argon2id.ident()
+                    || params.m_cost() < self.argon2_memory_kib
+                    || params.t_cost() < self.argon2_iterations
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap settings so the tests don't spend seconds hashing
+    fn fast(algorithm: PasswordAlgorithm) -> PasswordHashing {
+        PasswordHashing {
+            algorithm,
+            bcrypt_cost: 4,
+            argon2_memory_kib: 64,
+            argon2_iterations: 1,
+        }
+    }
+
+    #[test]
+    fn test_verifies_hashes_from_either_algorithm() {
+        let bcrypt_hash = fast(PasswordAlgorithm::Bcrypt).hash("hunter2").unwrap();
+        let argon2_hash = fast(PasswordAlgorithm::Argon2id).hash("hunter2").unwrap();
+        assert!(bcrypt_hash.starts_with("$2"));
+        assert!(argon2_hash.starts_with("$argon2id$"));
+
+        for hash in [&bcrypt_hash, &argon2_hash] {
+            assert!(PasswordHashing::verify("hunter2", hash).unwrap());
+            assert!(!PasswordHashing::verify("hunter3", hash).unwrap());
+        }
+        assert!(PasswordHashing::verify("hunter2", "hunter2").is_err());
+    }
+
+    #[test]
+    fn test_needs_rehash_on_algorithm_or_cost_change() {
+        let bcrypt_hash = fast(PasswordAlgorithm::Bcrypt).hash("hunter2").unwrap();
+        let argon2_hash = fast(PasswordAlgorithm::Argon2id).hash("hunter2").unwrap();
+
+        let bcrypt = fast(PasswordAlgorithm::Bcrypt);
+        assert!(!bcrypt.needs_rehash(&bcrypt_hash));
+        assert!(bcrypt.needs_rehash(&argon2_hash));
+        assert!(PasswordHashing { bcrypt_cost: 5, ..bcrypt }.needs_rehash(&bcrypt_hash));
+
+        let argon2 = fast(PasswordAlgorithm::Argon2id);
+        assert!(!argon2.needs_rehash(&argon2_hash));
+        assert!(argon2.needs_rehash(&bcrypt_hash));
+        assert!(PasswordHashing { argon2_iterations: 2, ..argon2 }.needs_rehash(&argon2_hash));
+    }
+
+    #[test]
+    fn test_from_config_values() {
+        assert_eq!(PasswordHashing::from_config_values("", "", "", "").unwrap(), PasswordHashing::default());
+
+        let argon2 = PasswordHashing::from_config_values("Argon2id", "12", "65536", "3").unwrap();
+        assert_eq!(argon2.algorithm, PasswordAlgorithm::Argon2id);
+        assert_eq!((argon2.argon2_memory_kib, argon2.argon2_iterations), (65536, 3));
+
+        assert!(PasswordHashing::from_config_values("md5", "", "", "").is_err());
+        assert!(PasswordHashing::from_config_values("bcrypt", "4", "", "").is_err());
+        assert!(PasswordHashing::from_config_values("argon2id", "", "1", "").is_err());
+    }
+}
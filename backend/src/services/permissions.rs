@@ -0,0 +1,72 @@
+//! What each role may do in the admin area
+//!
+//! Routes are guarded by permission rather than by role name, and the same
+//! list is sent to the browser with the signed-in user (`/api/auth/me`), so
+//! the admin UI only offers what the API will allow:
+//!
+//! - admins have every permission;
+//! - editors manage content: posts, pages, media and comments;
+//! - everyone else has none and stays out of the admin area.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// Posts, pages, media and comment moderation
+    ManageContent,
+    /// Site statistics and performance metrics
+    ViewAnalytics,
+    /// User accounts, invites and sessions
+    ManageUsers,
+    /// Navigation, templates, design and system settings
+    ManageSite,
+}
+
+const ADMIN_PERMISSIONS: &[Permission] = &[
+    Permission::ManageContent,
+    Permission::ViewAnalytics,
+    Permission::ManageUsers,
+    Permission::ManageSite,
+];
+const EDITOR_PERMISSIONS: &[Permission] = &[Permission::ManageContent];
+
+/// Permissions granted to a role; unknown roles get none
+pub fn permissions_for_role(role: &str) -> &'static [Permission] {
+    match role {
+        "admin" => ADMIN_PERMISSIONS,
+        "editor" => EDITOR_PERMISSIONS,
+        _ => &[],
+    }
+}
+
+pub fn role_has_permission(role: &str, permission: Permission) -> bool {
+    permissions_for_role(role).contains(&permission)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editors_only_manage_content() {
+        assert!(role_has_permission("editor", Permission::ManageContent));
+        for permission in [Permission::ViewAnalytics, Permission::ManageUsers, Permission::ManageSite] {
+            assert!(!role_has_permission("editor", permission));
+            assert!(role_has_permission("admin", permission));
+        }
+    }
+
+    #[test]
+    fn test_other_roles_have_no_permissions() {
+        assert!(permissions_for_role("user").is_empty());
+        assert!(permissions_for_role("Admin").is_empty());
+        assert!(permissions_for_role("").is_empty());
+    }
+
+    #[test]
+    fn test_permission_names_sent_to_the_browser() {
+        let names = serde_json::to_value(permissions_for_role("admin")).unwrap();
+        assert_eq!(names, serde_json::json!(["manage_content", "view_analytics", "manage_users", "manage_site"]));
+    }
+}
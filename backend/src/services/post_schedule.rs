@@ -0,0 +1,144 @@
+//! Publish dates for posts
+//!
+//! `published_at` is when a published post went live, or when a scheduled
+//! post is planned to. Times are stored as naive UTC, like the rest of the
+//! schema; the API exchanges them as RFC 3339 so clients can convert to local
+//! time without guessing.
+//!
+//! The public site only shows published posts whose publish date has come.
+//! A background job runs every `PUBLISH_INTERVAL_SECONDS` and publishes
+//! scheduled posts that are due.
+
+use std::sync::Arc;
+use chrono::{Duration, NaiveDateTime, Utc};
+use tracing::{error, info};
+use crate::database::DbPool;
+use crate::middleware::errors::AppError;
+use crate::models::Post;
+
+/// Longest span the calendar endpoint will return in one request
+pub const MAX_CALENDAR_RANGE_DAYS: i64 = 62;
+
+/// How often due scheduled posts are published
+const PUBLISH_INTERVAL_SECONDS: u64 = 60;
+
+/// Whether the public site shows a post with this status and publish date
+/// at `now`. A published post dated in the future waits for its date, the
+/// same as a scheduled one.
+pub fn is_publicly_visible(status: &str, published_at: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+    status == "published" && published_at.is_none_or(|published_at| published_at <= now)
+}
+
+/// Decide the publish date to store for a post being saved
+///
+/// A requested date always wins. Otherwise the existing date is kept, and a
+/// post being published without one is stamped with `now`. Scheduled posts
+/// must end up with a date, since that is what they are scheduled for.
+pub fn resolve_published_at(
+    status: &str,
+    requested: Option<NaiveDateTime>,
+    existing: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> Result<Option<NaiveDateTime>, AppError> {
+    let published_at = requested.or(existing);
+    match status {
+        "published" => Ok(Some(published_at.unwrap_or(now))),
+        "scheduled" if published_at.is_none() => Err(AppError::ValidationError(
+            "Scheduled posts need a publish date".to_string()
+        )),
+        _ => Ok(published_at),
+    }
+}
+
+/// Validate a calendar query window: `from` must precede `to`, within the cap
+pub fn validate_calendar_range(from: NaiveDateTime, to: NaiveDateTime) -> Result<(), AppError> {
+    if to <= from {
+        return Err(AppError::ValidationError("Calendar range end must be after its start".to_string()));
+    }
+    if to - from > Duration::days(MAX_CALENDAR_RANGE_DAYS) {
+        return Err(AppError::ValidationError(format!(
+            "Calendar range cannot exceed {} days",
+            MAX_CALENDAR_RANGE_DAYS
+        )));
+    }
+    Ok(())
+}
+
+/// Publish due scheduled posts periodically
+pub fn start_background_job(pool: Arc<DbPool>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        info!("Starting scheduled post publishing task (interval: {} seconds)", PUBLISH_INTERVAL_SECONDS);
+        let mut timer = tokio::time::interval(std::time::Duration::from_secs(PUBLISH_INTERVAL_SECONDS));
+
+        loop {
+            timer.tick().await;
+
+            let pool = pool.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let mut conn = pool.get().map_err(|e| e.to_string())?;
+                Post::publish_due(&mut conn, Utc::now().naive_utc()).map_err(|e| e.to_string())
+            }).await;
+
+            match result {
+                Ok(Ok(0)) => {}
+                Ok(Ok(published)) => info!("Published {} scheduled posts", published),
+                Ok(Err(e)) => error!("Publishing scheduled posts failed: {}", e),
+                Err(e) => error!("Scheduled post task panicked: {}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_publishing_stamps_now_only_when_no_date() {
+        let now = at("2025-08-10 12:00:00");
+        let earlier = at("2025-08-01 09:00:00");
+        assert_eq!(resolve_published_at("published", None, None, now).unwrap(), Some(now));
+        assert_eq!(resolve_published_at("published", None, Some(earlier), now).unwrap(), Some(earlier));
+    }
+
+    #[test]
+    fn test_requested_date_overrides_existing() {
+        let now = at("2025-08-10 12:00:00");
+        let requested = at("2025-09-01 08:30:00");
+        assert_eq!(
+            resolve_published_at("scheduled", Some(requested), Some(now), now).unwrap(),
+            Some(requested)
+        );
+    }
+
+    #[test]
+    fn test_scheduled_requires_a_date() {
+        let now = at("2025-08-10 12:00:00");
+        assert!(resolve_published_at("scheduled", None, None, now).is_err());
+        assert_eq!(resolve_published_at("draft", None, None, now).unwrap(), None);
+    }
+
+    #[test]
+    fn test_future_publish_dates_stay_hidden() {
+        let now = at("2025-08-10 12:00:00");
+        assert!(is_publicly_visible("published", Some(at("2025-08-10 11:59:59")), now));
+        assert!(is_publicly_visible("published", Some(now), now));
+        assert!(is_publicly_visible("published", None, now));
+        assert!(!is_publicly_visible("published", Some(at("2025-08-10 12:00:01")), now));
+        assert!(!is_publicly_visible("scheduled", Some(at("2025-08-01 09:00:00")), now));
+        assert!(!is_publicly_visible("draft", None, now));
+    }
+
+    #[test]
+    fn test_calendar_range_limits() {
+        let from = at("2025-08-01 00:00:00");
+        assert!(validate_calendar_range(from, at("2025-09-01 00:00:00")).is_ok());
+        assert!(validate_calendar_range(from, from).is_err());
+        assert!(validate_calendar_range(from, at("2025-07-01 00:00:00")).is_err());
+        assert!(validate_calendar_range(from, at("2025-12-01 00:00:00")).is_err());
+    }
+}
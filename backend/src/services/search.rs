@@ -0,0 +1,271 @@
+//! Sitewide search over posts, pages and comments
+//!
+//! Each content type is matched with one case-insensitive substring query
+//! (see `like_pattern`); this module turns the matching rows into results
+//! with a plain-text snippet around the first hit and a relevance score,
+//! then merges the per-type lists into one ranking. Page content is the page
+//! builder's JSON, so its text is pulled out of the components first.
+
+use chrono::NaiveDateTime;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use crate::middleware::errors::AppError;
+
+pub const MIN_QUERY_LENGTH: usize = 2;
+pub const MAX_QUERY_LENGTH: usize = 100;
+/// Characters of context kept on each side of the first match
+const SNIPPET_CONTEXT: usize = 80;
+
+const TITLE_MATCH_SCORE: u32 = 10;
+const EXACT_TITLE_SCORE: u32 = 5;
+/// Body matches past this many stop adding to the score
+const MAX_COUNTED_BODY_MATCHES: u32 = 5;
+
+static HTML_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").expect("valid html tag regex"));
+static MARKDOWN_SYNTAX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"!?\[([^\]]*)\]\([^)]*\)|[#*_`>~]").expect("valid markdown syntax regex")
+});
+static WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").expect("valid whitespace regex"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchResultKind {
+    Post,
+    Page,
+    Comment,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub kind: SearchResultKind,
+    pub id: i32,
+    pub title: String,
+    pub snippet: String,
+    /// Public path of the result, or of the post or page a comment is on
+    pub url: String,
+    pub score: u32,
+    pub date: Option<NaiveDateTime>,
+}
+
+/// Trim and length-check the `q` parameter
+pub fn normalize_query(raw: &str) -> Result<String, AppError> {
+    let query = WHITESPACE.replace_all(raw.trim(), " ").to_string();
+    let length = query.chars().count();
+    if length < MIN_QUERY_LENGTH {
+        return Err(AppError::ValidationError(format!("Search terms must be at least {} characters", MIN_QUERY_LENGTH)));
+    }
+    if length > MAX_QUERY_LENGTH {
+        return Err(AppError::ValidationError(format!("Search terms must be at most {} characters", MAX_QUERY_LENGTH)));
+    }
+    Ok(query)
+}
+
+/// `ILIKE` pattern matching `query` anywhere, with wildcards in it escaped
+pub fn like_pattern(query: &str) -> String {
+    let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+fn collect_layout_text(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| collect_layout_text(item, out)),
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                match field {
+                    Value::String(text) if matches!(key.as_str(), "content" | "title" | "text" | "subtitle") => {
+                        out.push(text.clone());
+                    }
+                    Value::Array(_) | Value::Object(_) => collect_layout_text(field, out),
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Readable text of post, page or comment content: page builder JSON is
+/// reduced to its text fields, then HTML tags and Markdown syntax are dropped
+pub fn plain_text(content: &str) -> String {
+    let text = match serde_json::from_str::<Value>(content) {
+        Ok(layout @ Value::Array(_)) => {
+            let mut parts = Vec::new();
+            collect_layout_text(&layout, &mut parts);
+            parts.join(" ")
+        }
+        _ => content.to_string(),
+    };
+    let text = HTML_TAG.replace_all(&text, " ");
+    let text = MARKDOWN_SYNTAX.replace_all(&text, "$1");
+    WHITESPACE.replace_all(text.trim(), " ").to_string()
+}
+
+/// Char index of the first case-insensitive occurrence of `query` in `text`
+fn find_ignore_case(text: &[char], query: &[char]) -> Option<usize> {
+    if query.is_empty() || query.len() > text.len() {
+        return None;
+    }
+    (0..=text.len() - query.len()).find(|&start| {
+        text[start..start + query.len()]
+            .iter()
+            .zip(query)
+            .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+    })
+}
+
+fn count_ignore_case(text: &str, query: &str) -> u32 {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return 0;
+    }
+    text.to_lowercase().matches(&query).count() as u32
+}
+
+/// Up to `SNIPPET_CONTEXT` characters either side of the first match, or the
+/// start of the text when it only matched in the title
+pub fn snippet(text: &str, query: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let query: Vec<char> = query.chars().collect();
+    let (start, end) = match find_ignore_case(&chars, &query) {
+        Some(at) => (at.saturating_sub(SNIPPET_CONTEXT), (at + query.len() + SNIPPET_CONTEXT).min(chars.len())),
+        None => (0, (2 * SNIPPET_CONTEXT).min(chars.len())),
+    };
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    out.extend(&chars[start..end]);
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+/// Title matches outrank body matches; an exact title match ranks highest
+pub fn relevance(title: &str, body: &str, query: &str) -> u32 {
+    let mut score = 0;
+    if count_ignore_case(title, query) > 0 {
+        score += TITLE_MATCH_SCORE;
+        if title.trim().to_lowercase() == query.to_lowercase() {
+            score += EXACT_TITLE_SCORE;
+        }
+    }
+    score + count_ignore_case(body, query).min(MAX_COUNTED_BODY_MATCHES)
+}
+
+/// Build a result from a matching row's title and raw content
+pub fn build_result(
+    kind: SearchResultKind,
+    id: i32,
+    title: String,
+    content: &str,
+    url: String,
+    date: Option<NaiveDateTime>,
+    query: &str,
+) -> SearchResult {
+    let body = plain_text(content);
+    SearchResult {
+        kind,
+        id,
+        score: relevance(&title, &body, query),
+        snippet: snippet(&body, query),
+        title,
+        url,
+        date,
+    }
+}
+
+/// Merge the per-type result lists, best first and newest first among
+/// equals, skipping `offset` and keeping at most `limit`
+///
+/// Each list must hold at least its first `offset + limit` matches for the
+/// page to be right.
+pub fn merge_results(groups: Vec<Vec<SearchResult>>, offset: usize, limit: usize) -> Vec<SearchResult> {
+    let mut results: Vec<SearchResult> = groups.into_iter().flatten().collect();
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| b.date.cmp(&a.date)));
+    results.into_iter().skip(offset).take(limit).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(kind: SearchResultKind, id: i32, score: u32, date: &str) -> SearchResult {
+        SearchResult {
+            kind,
+            id,
+            title: String::new(),
+            snippet: String::new(),
+            url: String::new(),
+            score,
+            date: NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").ok(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_query() {
+        assert_eq!(normalize_query("  rust   cms ").unwrap(), "rust cms");
+        assert!(normalize_query(" a ").is_err());
+        assert!(normalize_query(&"x".repeat(MAX_QUERY_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("50%_off\\"), "%50\\%\\_off\\\\%");
+    }
+
+    #[test]
+    fn test_plain_text_strips_markup_and_layout_json() {
+        assert_eq!(plain_text("## Hello **world** <em>again</em> [link](/x)"), "Hello world again link");
+
+        let layout = r#"[{"component_type":"Text","content":"Welcome home","properties":{"image_url":"/a.png"}},
+            {"component_type":"Container","properties":{"nested_components":[{"component_type":"Hero","properties":{"title":"Big news"}}]}}]"#;
+        assert_eq!(plain_text(layout), "Welcome home Big news");
+    }
+
+    #[test]
+    fn test_snippet_centers_on_first_match() {
+        let text = format!("{}Needle{}", "a".repeat(200), "b".repeat(200));
+        let snip = snippet(&text, "needle");
+        assert!(snip.starts_with('…') && snip.ends_with('…'));
+        assert!(snip.contains("Needle"));
+        assert_eq!(snip.chars().count(), 2 * SNIPPET_CONTEXT + "Needle".len() + 2);
+
+        assert_eq!(snippet("short text", "missing"), "short text");
+    }
+
+    #[test]
+    fn test_relevance_prefers_title_matches() {
+        let title_hit = relevance("Rust tips", "nothing here", "rust");
+        let body_hit = relevance("Tips", "rust rust rust", "rust");
+        let exact = relevance("Rust", "", "rust");
+        assert!(exact > title_hit && title_hit > body_hit);
+        assert_eq!(relevance("Tips", &"rust ".repeat(20), "rust"), MAX_COUNTED_BODY_MATCHES);
+    }
+
+    #[test]
+    fn test_merge_results_orders_by_score_then_date() {
+        let merged = merge_results(vec![
+            vec![result(SearchResultKind::Post, 1, 3, "2025-01-01 00:00:00"), result(SearchResultKind::Post, 2, 12, "2025-01-01 00:00:00")],
+            vec![result(SearchResultKind::Page, 3, 3, "2025-03-01 00:00:00")],
+            vec![result(SearchResultKind::Comment, 4, 1, "2025-05-01 00:00:00")],
+        ], 0, 3);
+        let ids: Vec<i32> = merged.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_merge_results_pages_across_types() {
+        let groups = || vec![
+            vec![result(SearchResultKind::Post, 1, 3, "2025-01-01 00:00:00"), result(SearchResultKind::Post, 2, 12, "2025-01-01 00:00:00")],
+            vec![result(SearchResultKind::Page, 3, 3, "2025-03-01 00:00:00")],
+        ];
+        let ids = |merged: Vec<SearchResult>| merged.iter().map(|r| r.id).collect::<Vec<i32>>();
+        assert_eq!(ids(merge_results(groups(), 0, 2)), vec![2, 3]);
+        assert_eq!(ids(merge_results(groups(), 2, 2)), vec![1]);
+        assert!(merge_results(groups(), 4, 2).is_empty());
+    }
+}
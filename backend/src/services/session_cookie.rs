@@ -0,0 +1,138 @@
+//! Session cookies as an alternative to bearer tokens
+//!
+//! With the `session_cookie_auth` setting on, login also sets the session
+//! token in an HttpOnly cookie scoped to `/api`, which page scripts can't
+//! read. The auth middleware accepts it when no `Authorization` header is
+//! sent, so API clients keep using bearer tokens unchanged.
+//!
+//! A cookie rides along on requests the user never meant to make. Besides
+//! `SameSite=Strict`, a cookie-authenticated request that changes state must
+//! carry an `X-Requested-With` header: a cross-site form can't set one, and a
+//! cross-site script can't send one without a CORS preflight the API
+//! refuses. Bearer requests need no such check.
+
+use axum::http::{header, HeaderMap, HeaderValue, Method};
+use crate::middleware::errors::AppError;
+
+pub const SESSION_COOKIE_NAME: &str = "cms_session";
+/// Header a cookie-authenticated state-changing request must carry
+pub const CSRF_HEADER: &str = "x-requested-with";
+
+/// Where a request's session token came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSource {
+    Bearer,
+    Cookie,
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+}
+
+fn cookie_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|h| h.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, value)| *name == SESSION_COOKIE_NAME && !value.is_empty())
+        .map(|(_, value)| value)
+}
+
+/// The session token of a request; a bearer token wins over the cookie,
+/// which is only read when `cookie_auth` is on
+pub fn request_token<'a>(headers: &'a HeaderMap, method: &Method, cookie_auth: bool) -> Result<(&'a str, TokenSource), AppError> {
+    if let Some(token) = bearer_token(headers) {
+        return Ok((token, TokenSource::Bearer));
+    }
+    let token = cookie_token(headers)
+        .filter(|_| cookie_auth)
+        .ok_or(AppError::MissingAuthHeader)?;
+    let safe = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+    if !safe && !headers.contains_key(CSRF_HEADER) {
+        return Err(AppError::Forbidden);
+    }
+    Ok((token, TokenSource::Cookie))
+}
+
+/// `Set-Cookie` value carrying a session token for `max_age_seconds`
+pub fn session_cookie(token: &str, max_age_seconds: i64, secure: bool) -> HeaderValue {
+    cookie_header(token, max_age_seconds.max(0), secure)
+}
+
+/// `Set-Cookie` value that removes the session cookie
+pub fn clear_session_cookie(secure: bool) -> HeaderValue {
+    cookie_header("", 0, secure)
+}
+
+fn cookie_header(value: &str, max_age_seconds: i64, secure: bool) -> HeaderValue {
+    let mut cookie = format!(
+        "{}={}; Path=/api; HttpOnly; SameSite=Strict; Max-Age={}",
+        SESSION_COOKIE_NAME, value, max_age_seconds
+    );
+    if secure {
+        cookie.push_str("; Secure");
+    }
+    HeaderValue::from_str(&cookie).unwrap_or_else(|_| HeaderValue::from_static("cms_session=; Path=/api; Max-Age=0"))
+}
+
+/// Whether a response already sets or clears the session cookie
+pub fn sets_session_cookie(headers: &HeaderMap) -> bool {
+    let prefix = format!("{}=", SESSION_COOKIE_NAME);
+    headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|h| h.to_str().ok())
+        .any(|h| h.starts_with(&prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_bearer_wins_and_cookie_needs_the_setting() {
+        let both = headers(&[("authorization", "Bearer abc"), ("cookie", "theme=dark; cms_session=xyz")]);
+        assert_eq!(request_token(&both, &Method::POST, true).unwrap(), ("abc", TokenSource::Bearer));
+
+        let cookie_only = headers(&[("cookie", "theme=dark; cms_session=xyz")]);
+        assert_eq!(request_token(&cookie_only, &Method::GET, true).unwrap(), ("xyz", TokenSource::Cookie));
+        assert!(matches!(request_token(&cookie_only, &Method::GET, false), Err(AppError::MissingAuthHeader)));
+        assert!(matches!(request_token(&headers(&[("cookie", "cms_session=")]), &Method::GET, true), Err(AppError::MissingAuthHeader)));
+    }
+
+    #[test]
+    fn test_cookie_writes_need_the_csrf_header() {
+        let cookie_only = headers(&[("cookie", "cms_session=xyz")]);
+        assert!(matches!(request_token(&cookie_only, &Method::DELETE, true), Err(AppError::Forbidden)));
+
+        let with_header = headers(&[("cookie", "cms_session=xyz"), ("x-requested-with", "XMLHttpRequest")]);
+        assert_eq!(request_token(&with_header, &Method::DELETE, true).unwrap(), ("xyz", TokenSource::Cookie));
+    }
+
+    #[test]
+    fn test_cookie_attributes() {
+        assert_eq!(
+            session_cookie("xyz", 3600, true),
+            "cms_session=xyz; Path=/api; HttpOnly; SameSite=Strict; Max-Age=3600; Secure"
+        );
+        assert_eq!(clear_session_cookie(false), "cms_session=; Path=/api; HttpOnly; SameSite=Strict; Max-Age=0");
+
+        let mut response = HeaderMap::new();
+        assert!(!sets_session_cookie(&response));
+        response.append(header::SET_COOKIE, clear_session_cookie(true));
+        assert!(sets_session_cookie(&response));
+    }
+}
@@ -1,14 +1,12 @@
-use std::sync::Arc;
-use std::time::Duration as StdDuration;
-use tokio::time::{interval, sleep};
-use tracing::{info, warn, error};
+use std::sync::{Arc, RwLock};
+use tracing::{info, warn};
 use chrono::{Duration, NaiveDateTime, Utc};
 use uuid::Uuid;
 
 use diesel::prelude::*;
 use crate::{
     database::DbPool,
-    models::{Session, NewSession, User},
+    models::{Session, NewSession, User, Setting, AuditLogEntry, NewAuditLogEntry, FORCE_LOGOUT_ALL_ACTION},
     middleware::errors::{AppError, ApiResult},
     services::SessionSigner,
 };
@@ -16,30 +14,156 @@ use crate::{
 #[derive(Clone)]
 pub struct SessionConfig {
     pub session_duration_hours: i64,
-    pub cleanup_interval_minutes: u64,
     pub max_sessions_per_user: usize,
     pub enable_session_refresh: bool,
     pub refresh_threshold_minutes: i64,
+    /// Expire sessions with no activity for this many minutes (0 disables)
+    pub idle_timeout_minutes: i64,
     pub enable_token_signing: bool,
+    /// Reject a session used from a different IP than the one it was created on.
+    /// Off by default since clients switching networks (e.g. mobile) get logged out.
+    pub bind_to_ip: bool,
+    /// Also accept the session token from an HttpOnly cookie set at login
+    pub cookie_auth: bool,
+    /// Mark session cookies `Secure`; only off for plain-http development
+    pub secure_cookies: bool,
 }
 
 impl Default for SessionConfig {
     fn default() -> Self {
         Self {
             session_duration_hours: 24,        // 24 hour sessions
-            cleanup_interval_minutes: 15,      // Clean up every 15 minutes
             max_sessions_per_user: 5,         // Max 5 concurrent sessions per user
             enable_session_refresh: true,      // Allow automatic session refresh
             refresh_threshold_minutes: 60,     // Refresh if less than 1 hour remaining
+            idle_timeout_minutes: 0,           // No idle timeout, only absolute expiry
             enable_token_signing: true,        // Enable HMAC-SHA256 token signing
+            bind_to_ip: false,                 // Don't tie sessions to the client IP
+            cookie_auth: false,                // Bearer tokens only
+            secure_cookies: true,              // Cookies only travel over HTTPS
         }
     }
 }
 
+/// Setting keys that override the session policy at runtime
+pub const SESSION_DURATION_SETTING: &str = "session_duration_hours";
+pub const MAX_SESSIONS_SETTING: &str = "max_sessions_per_user";
+pub const REFRESH_THRESHOLD_SETTING: &str = "refresh_threshold_minutes";
+pub const BIND_TO_IP_SETTING: &str = "bind_session_to_ip";
+pub const IDLE_TIMEOUT_SETTING: &str = "session_idle_timeout_minutes";
+pub const COOKIE_AUTH_SETTING: &str = "session_cookie_auth";
+
+pub const SESSION_SETTING_KEYS: [&str; 6] = [
+    SESSION_DURATION_SETTING,
+    MAX_SESSIONS_SETTING,
+    REFRESH_THRESHOLD_SETTING,
+    BIND_TO_IP_SETTING,
+    IDLE_TIMEOUT_SETTING,
+    COOKIE_AUTH_SETTING,
+];
+
+/// Minimum time between `last_seen_at` writes for a session, so activity
+/// tracking doesn't cost a database write on every request
+const LAST_SEEN_UPDATE_INTERVAL_SECONDS: i64 = 60;
+
+/// Whether a session last active at `last_seen` has been idle for at least
+/// `idle_timeout_minutes` as of `now`. A timeout of 0 disables idle expiry.
+pub fn is_idle_expired(last_seen: Option<NaiveDateTime>, now: NaiveDateTime, idle_timeout_minutes: i64) -> bool {
+    if idle_timeout_minutes <= 0 {
+        return false;
+    }
+
+    match last_seen {
+        Some(last_seen) => now.signed_duration_since(last_seen) >= Duration::minutes(idle_timeout_minutes),
+        None => false,
+    }
+}
+
+/// Whether enough time has passed since `last_seen` to record new activity
+fn should_record_activity(last_seen: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+    match last_seen {
+        Some(last_seen) => now.signed_duration_since(last_seen) >= Duration::seconds(LAST_SEEN_UPDATE_INTERVAL_SECONDS),
+        None => true,
+    }
+}
+
+/// New expiry for a session expiring at `expires_at` when it is due a
+/// refresh as of `now`, or `None` while enough time remains.
+fn refreshed_expiry(config: &SessionConfig, expires_at: NaiveDateTime, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let time_remaining = expires_at.signed_duration_since(now);
+    (config.enable_session_refresh && time_remaining < Duration::minutes(config.refresh_threshold_minutes))
+        .then(|| now + Duration::hours(config.session_duration_hours))
+}
+
+impl SessionConfig {
+    /// Validate a session policy setting value.
+    /// Unknown keys are rejected so callers can check `SESSION_SETTING_KEYS` first.
+    pub fn validate_setting(key: &str, value: &str) -> Result<(), AppError> {
+        if key == BIND_TO_IP_SETTING || key == COOKIE_AUTH_SETTING {
+            Self::parse_flag_setting(key, value).map(|_| ())
+        } else {
+            Self::parse_numeric_setting(key, value).map(|_| ())
+        }
+    }
+
+    fn parse_flag_setting(key: &str, value: &str) -> Result<bool, AppError> {
+        value.trim().parse::<bool>()
+            .map_err(|_| AppError::ValidationError(format!("{} must be true or false", key)))
+    }
+
+    fn parse_numeric_setting(key: &str, value: &str) -> Result<i64, AppError> {
+        let (min, max) = match key {
+            SESSION_DURATION_SETTING => (1, 24 * 30),
+            MAX_SESSIONS_SETTING => (1, 50),
+            REFRESH_THRESHOLD_SETTING => (1, 24 * 60),
+            IDLE_TIMEOUT_SETTING => (0, 7 * 24 * 60),
+            _ => return Err(AppError::ValidationError(format!("Unknown session setting: {}", key))),
+        };
+
+        let parsed = value.trim().parse::<i64>()
+            .map_err(|_| AppError::ValidationError(format!("{} must be a whole number", key)))?;
+
+        if parsed < min || parsed > max {
+            return Err(AppError::ValidationError(format!("{} must be between {} and {}", key, min, max)));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Overlay values stored in settings onto this config.
+    /// Missing or invalid values keep the current value.
+    pub fn apply_settings(&mut self, conn: &mut PgConnection) -> QueryResult<()> {
+        for key in SESSION_SETTING_KEYS {
+            let Some(value) = Setting::find_by_key(conn, key)?.and_then(|s| s.setting_value) else {
+                continue;
+            };
+
+            let applied = match key {
+                BIND_TO_IP_SETTING => Self::parse_flag_setting(key, &value)
+                    .map(|flag| self.bind_to_ip = flag),
+                COOKIE_AUTH_SETTING => Self::parse_flag_setting(key, &value)
+                    .map(|flag| self.cookie_auth = flag),
+                _ => Self::parse_numeric_setting(key, &value).map(|parsed| match key {
+                    SESSION_DURATION_SETTING => self.session_duration_hours = parsed,
+                    MAX_SESSIONS_SETTING => self.max_sessions_per_user = parsed as usize,
+                    IDLE_TIMEOUT_SETTING => self.idle_timeout_minutes = parsed,
+                    _ => self.refresh_threshold_minutes = parsed,
+                }),
+            };
+
+            if let Err(e) = applied {
+                warn!("Ignoring session setting {}: {}", key, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 pub struct SessionManager {
     pool: Arc<DbPool>,
-    config: SessionConfig,
+    config: Arc<RwLock<SessionConfig>>,
     signer: Option<SessionSigner>,
 }
 
@@ -55,7 +179,7 @@ impl SessionManager {
     pub fn new(pool: Arc<DbPool>, config: SessionConfig) -> Self {
         Self { 
             pool, 
-            config,
+            config: Arc::new(RwLock::new(config)),
             signer: None,
         }
     }
@@ -69,7 +193,7 @@ impl SessionManager {
         
         Self {
             pool,
-            config,
+            config: Arc::new(RwLock::new(config)),
             signer,
         }
     }
@@ -78,9 +202,30 @@ impl SessionManager {
         Self::new(pool, SessionConfig::default())
     }
 
+    /// Snapshot of the current session policy
+    pub fn config(&self) -> SessionConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Re-read the session policy from settings so changes apply without a restart
+    pub fn reload_config(&self) -> ApiResult<SessionConfig> {
+        let mut conn = self.pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut config = self.config();
+        config.apply_settings(&mut conn)?;
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config.clone();
+
+        info!("Session policy loaded: {}h sessions, max {} per user, refresh under {} minutes, idle timeout {} minutes, IP binding {}",
+            config.session_duration_hours, config.max_sessions_per_user, config.refresh_threshold_minutes,
+            config.idle_timeout_minutes, if config.bind_to_ip { "on" } else { "off" });
+
+        Ok(config)
+    }
+
     /// Create a new session for a user with automatic cleanup of old sessions
-    pub async fn create_session(&self, user_id: i32) -> ApiResult<Session> {
+    pub async fn create_session(&self, user_id: i32, ip_address: Option<String>) -> ApiResult<Session> {
         let mut conn = self.pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let config = self.config();
         
         // Check if user exists
         User::find_by_id(&mut conn, user_id)?
@@ -89,20 +234,23 @@ impl SessionManager {
         // Clean up old sessions for this user if they exceed the limit
         let current_session_count = Session::count_active_sessions_for_user(&mut conn, user_id)?;
         
-        if current_session_count >= self.config.max_sessions_per_user as i64 {
-            let removed = Session::delete_old_sessions_for_user(&mut conn, user_id, self.config.max_sessions_per_user - 1)?;
+        if current_session_count >= config.max_sessions_per_user as i64 {
+            let removed = Session::delete_old_sessions_for_user(&mut conn, user_id, config.max_sessions_per_user - 1)?;
             info!("Removed {} old sessions for user {} to stay within limit", removed, user_id);
         }
 
         // Create new session token - always store UUID in database, return signed token if signing enabled
         let uuid_token = Uuid::new_v4().to_string();
         
-        let expires_at = Utc::now().naive_utc() + Duration::hours(self.config.session_duration_hours);
+        let now = Utc::now().naive_utc();
+        let expires_at = now + Duration::hours(config.session_duration_hours);
 
         let new_session = NewSession {
             user_id: Some(user_id),
             session_token: uuid_token.clone(),
             expires_at: Some(expires_at),
+            ip_address,
+            last_seen_at: Some(now),
         };
 
         let mut session = Session::create(&mut conn, new_session)?;
@@ -118,49 +266,67 @@ impl SessionManager {
         Ok(session)
     }
 
-    /// Validate and optionally refresh a session
-    pub async fn validate_session(&self, token: &str) -> ApiResult<Session> {
-        let mut conn = self.pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        
-        // Extract the actual token to look up in database
-        let lookup_token = if let Some(ref signer) = self.signer {
+    /// Extract the token stored in the database from a client token
+    fn lookup_token(&self, token: &str) -> ApiResult<String> {
+        if let Some(ref signer) = self.signer {
             // If we have a signer, check if this is a signed token
             if crate::services::SessionSigner::is_signed_token(token) {
                 // Verify signature and extract UUID
                 signer.verify_signed_token(token)
-                    .ok_or(AppError::InvalidToken)?
+                    .ok_or(AppError::InvalidToken)
             } else {
                 // Handle unsigned tokens (for backward compatibility)
-                token.to_string()
+                Ok(token.to_string())
             }
         } else {
             // No signing enabled, use token directly
-            token.to_string()
-        };
+            Ok(token.to_string())
+        }
+    }
+
+    /// Validate and optionally refresh a session.
+    /// `ip_address` is the requesting client's IP, checked when IP binding is enabled.
+    pub async fn validate_session(&self, token: &str, ip_address: Option<&str>) -> ApiResult<Session> {
+        let mut conn = self.pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        
+        let lookup_token = self.lookup_token(token)?;
         
         let session = Session::find_by_token(&mut conn, &lookup_token)?
             .ok_or(AppError::InvalidToken)?;
 
-        // Check if session is expired
-        if let Some(expires_at) = session.expires_at {
-            let now = Utc::now().naive_utc();
-            
-            if expires_at <= now {
-                // Clean up expired session
-                let _ = Session::delete(&mut conn, session.id);
-                return Err(AppError::ExpiredToken);
-            }
+        let config = self.config();
+        if config.bind_to_ip && (ip_address.is_none() || session.ip_address.as_deref() != ip_address) {
+            warn!("Rejected session {} used from {:?} (bound to {:?})",
+                session.id, ip_address, session.ip_address);
+            return Err(AppError::InvalidToken);
+        }
+
+        let now = Utc::now().naive_utc();
 
-            // Check if session should be refreshed
-            if self.config.enable_session_refresh {
-                let time_remaining = expires_at.signed_duration_since(now);
-                let refresh_threshold = Duration::minutes(self.config.refresh_threshold_minutes);
-                
-                if time_remaining < refresh_threshold {
-                    // Refresh the session
-                    let new_expires_at = now + Duration::hours(self.config.session_duration_hours);
-                    return Ok(Session::refresh_expiration(&mut conn, session.id, new_expires_at)?);
-                }
+        // Check if session is expired, before any activity is recorded on it
+        if session.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            // Clean up expired session
+            let _ = Session::delete(&mut conn, session.id);
+            return Err(AppError::ExpiredToken);
+        }
+
+        // Expire sessions idle longer than the configured window, independent of absolute expiry
+        if is_idle_expired(session.last_seen_at.or(session.created_at), now, config.idle_timeout_minutes) {
+            let _ = Session::delete(&mut conn, session.id);
+            return Err(AppError::ExpiredToken);
+        }
+
+        // Record activity (throttled)
+        let session = if should_record_activity(session.last_seen_at, now) {
+            Session::touch(&mut conn, session.id, now)?
+        } else {
+            session
+        };
+
+        if let Some(expires_at) = session.expires_at {
+            // Refresh the session; the user's last login stays as it was
+            if let Some(new_expires_at) = refreshed_expiry(&config, expires_at, now) {
+                return Ok(Session::refresh_expiration(&mut conn, session.id, new_expires_at)?);
             }
         }
 
@@ -168,8 +334,8 @@ impl SessionManager {
     }
 
     /// Get detailed session information
-    pub async fn get_session_info(&self, token: &str) -> ApiResult<crate::models::session::SessionInfo> {
-        let session = self.validate_session(token).await?;
+    pub async fn get_session_info(&self, token: &str, ip_address: Option<&str>) -> ApiResult<crate::models::session::SessionInfo> {
+        let session = self.validate_session(token, ip_address).await?;
         Ok(session.get_session_info())
     }
 
@@ -189,13 +355,14 @@ impl SessionManager {
     /// Logout a specific session
     pub async fn logout_session(&self, token: &str) -> ApiResult<()> {
         let mut conn = self.pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        
-        let deleted = Session::delete_by_token(&mut conn, token)?;
+
+        let lookup_token = self.lookup_token(token)?;
+        let deleted = Session::delete_by_token(&mut conn, &lookup_token)?;
         if deleted == 0 {
             return Err(AppError::NotFound("Session not found".to_string()));
         }
-        
-        info!("Logged out session: {}", token);
+
+        info!("Logged out a session");
         Ok(())
     }
 
@@ -209,6 +376,20 @@ impl SessionManager {
         Ok(deleted)
     }
 
+    /// Logout every session of a user except the one holding `keep_token`
+    pub async fn logout_other_user_sessions(&self, user_id: i32, keep_token: &str) -> ApiResult<usize> {
+        let mut conn = self.pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let lookup_token = self.lookup_token(keep_token)?;
+        let keep_session_id = Session::find_by_token(&mut conn, &lookup_token)?
+            .map(|s| s.id)
+            .unwrap_or(-1);
+        let deleted = Session::delete_other_user_sessions(&mut conn, user_id, keep_session_id)?;
+        info!("Logged out {} other sessions for user {}", deleted, user_id);
+
+        Ok(deleted)
+    }
+
     /// Manually trigger session cleanup
     pub async fn cleanup_expired_sessions(&self) -> ApiResult<SessionStats> {
         let mut conn = self.pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -230,35 +411,6 @@ impl SessionManager {
         Ok(stats)
     }
 
-    /// Start background session cleanup task
-    pub async fn start_background_cleanup(self) -> tokio::task::JoinHandle<()> {
-        let cleanup_interval = StdDuration::from_secs(self.config.cleanup_interval_minutes * 60);
-        
-        tokio::spawn(async move {
-            info!("Starting session cleanup background task (interval: {} minutes)", 
-                self.config.cleanup_interval_minutes);
-            
-            let mut cleanup_timer = interval(cleanup_interval);
-            
-            loop {
-                cleanup_timer.tick().await;
-                
-                match self.cleanup_expired_sessions().await {
-                    Ok(stats) => {
-                        if stats.expired_cleaned > 0 {
-                            info!("Background cleanup: removed {} expired sessions", stats.expired_cleaned);
-                        }
-                    }
-                    Err(e) => {
-                        error!("Background session cleanup failed: {}", e);
-                        // On error, wait a bit before trying again
-                        sleep(StdDuration::from_secs(60)).await;
-                    }
-                }
-            }
-        })
-    }
-
     /// Get session statistics
     pub async fn get_session_statistics(&self) -> ApiResult<SessionStats> {
         let mut conn = self.pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -295,4 +447,150 @@ impl SessionManager {
         
         Ok(updated)
     }
-}
\ No newline at end of file
+
+    /// Force expire every session across all users in a single query.
+    /// If `keep_token` is given, that session is left active. The reset is
+    /// recorded in the audit log, in the same transaction as the expiry.
+    pub async fn force_expire_all_sessions(&self, keep_token: Option<&str>, requested_by_id: i32, requested_by: &str) -> ApiResult<usize> {
+        let mut conn = self.pool.get().map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let keep_session_id = match keep_token {
+            Some(token) => {
+                let lookup_token = self.lookup_token(token)?;
+                Session::find_by_token(&mut conn, &lookup_token)?.map(|s| s.id)
+            }
+            None => None,
+        };
+
+        let now = Utc::now().naive_utc();
+        let updated = conn.transaction::<_, AppError, _>(|conn| {
+            let updated = diesel::update(crate::schema::sessions::table
+                .filter(crate::schema::sessions::expires_at.gt(now))
+                .filter(crate::schema::sessions::id.ne(keep_session_id.unwrap_or(-1))))
+                .set(crate::schema::sessions::expires_at.eq(now))
+                .execute(conn)?;
+
+            AuditLogEntry::record(conn, NewAuditLogEntry {
+                action: FORCE_LOGOUT_ALL_ACTION.to_string(),
+                actor_id: Some(requested_by_id),
+                actor_username: requested_by.to_string(),
+                details: serde_json::json!({
+                    "sessions_expired": updated,
+                    "kept_own_session": keep_session_id.is_some(),
+                }),
+                created_at: now,
+            })?;
+
+            Ok(updated)
+        })?;
+
+        warn!("Global session reset by {}: force expired {} sessions (kept own session: {})",
+            requested_by, updated, keep_session_id.is_some());
+
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(minutes: i64) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2025-01-01 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap() + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn test_idle_expiry_boundary() {
+        let last_seen = Some(at(0));
+        assert!(!is_idle_expired(last_seen, at(29) + Duration::seconds(59), 30));
+        assert!(is_idle_expired(last_seen, at(30), 30));
+        assert!(is_idle_expired(last_seen, at(31), 30));
+    }
+
+    #[test]
+    fn test_idle_expiry_disabled() {
+        assert!(!is_idle_expired(Some(at(0)), at(60 * 24 * 365), 0));
+        assert!(!is_idle_expired(None, at(60), 30));
+    }
+
+    #[test]
+    fn test_activity_recording_is_throttled() {
+        assert!(should_record_activity(None, at(0)));
+        assert!(!should_record_activity(Some(at(0)), at(0) + Duration::seconds(59)));
+        assert!(should_record_activity(Some(at(0)), at(1)));
+    }
+
+    #[test]
+    fn test_refresh_near_expiry() {
+        let config = SessionConfig { refresh_threshold_minutes: 30, session_duration_hours: 24, ..SessionConfig::default() };
+        assert_eq!(refreshed_expiry(&config, at(31), at(0)), None);
+        assert_eq!(refreshed_expiry(&config, at(30), at(0)), None);
+        assert_eq!(refreshed_expiry(&config, at(29), at(0)), Some(at(60 * 24)));
+
+        let disabled = SessionConfig { enable_session_refresh: false, ..config };
+        assert_eq!(refreshed_expiry(&disabled, at(1), at(0)), None);
+    }
+
+    /// Runs against a scratch database:
+    /// `TEST_DATABASE_URL=postgres://... cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore = "needs a database in TEST_DATABASE_URL"]
+    async fn test_refresh_leaves_last_login_alone() {
+        let _sessions = crate::test_support::lock_sessions().await;
+        let (pool, user) = crate::test_support::db_user("login", "user");
+        let mut conn = pool.get().unwrap();
+
+        // A login moves the one before it to previous_login_at
+        let first = User::record_login(&mut conn, user.id, at(0)).unwrap();
+        assert_eq!((first.last_login_at, first.previous_login_at), (Some(at(0)), None));
+        let second = User::record_login(&mut conn, user.id, at(60)).unwrap();
+        assert_eq!((second.last_login_at, second.previous_login_at), (Some(at(60)), Some(at(0))));
+
+        // Every validation refreshes under this threshold
+        let config = SessionConfig { refresh_threshold_minutes: 48 * 60, enable_token_signing: false, ..SessionConfig::default() };
+        let manager = SessionManager::new(pool.clone(), config);
+        let session = manager.create_session(user.id, None).await.unwrap();
+        let refreshed = manager.validate_session(&session.session_token, None).await.unwrap();
+        assert!(refreshed.expires_at > session.expires_at);
+
+        let after = User::find_by_id(&mut conn, user.id).unwrap().unwrap();
+        assert_eq!((after.last_login_at, after.previous_login_at), (Some(at(60)), Some(at(0))));
+
+        Session::delete_user_sessions(&mut conn, user.id).unwrap();
+        User::delete(&mut conn, user.id).unwrap();
+    }
+
+    /// Runs against a scratch database:
+    /// `TEST_DATABASE_URL=postgres://... cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore = "needs a database in TEST_DATABASE_URL"]
+    async fn test_force_expire_all_sessions_is_audited() {
+        use crate::schema::audit_log;
+
+        let _sessions = crate::test_support::lock_sessions().await;
+        let (pool, admin) = crate::test_support::db_user("reset", "admin");
+        let mut conn = pool.get().unwrap();
+
+        let manager = SessionManager::new_with_signing(pool.clone(), SessionConfig::default(), "test-session-secret");
+        let kept = manager.create_session(admin.id, None).await.unwrap();
+        let other = manager.create_session(admin.id, None).await.unwrap();
+
+        let expired = manager.force_expire_all_sessions(Some(&kept.session_token), admin.id, &admin.username).await.unwrap();
+        assert!(expired >= 1);
+        assert!(manager.validate_session(&kept.session_token, None).await.is_ok());
+        assert!(manager.validate_session(&other.session_token, None).await.is_err());
+
+        let entry = audit_log::table
+            .filter(audit_log::actor_id.eq(admin.id))
+            .first::<AuditLogEntry>(&mut conn)
+            .unwrap();
+        assert_eq!(entry.action, FORCE_LOGOUT_ALL_ACTION);
+        assert_eq!(entry.actor_username, admin.username);
+        assert_eq!(entry.details["sessions_expired"], expired);
+        assert_eq!(entry.details["kept_own_session"], true);
+
+        diesel::delete(audit_log::table.find(entry.id)).execute(&mut conn).unwrap();
+        Session::delete_user_sessions(&mut conn, admin.id).unwrap();
+        User::delete(&mut conn, admin.id).unwrap();
+    }
+}
@@ -24,6 +24,15 @@ impl SessionSigner {
         }
     }
 
+    /// Signer for another kind of token built on the session secret
+    ///
+    /// The key is derived from `purpose` as well as the secret, so these
+    /// tokens are keyed apart from session tokens and from each other: one
+    /// kind can't be passed off as another.
+    pub fn for_purpose(purpose: &str, secret: &str) -> Self {
+        Self::new(&format!("{}:{}", purpose, secret))
+    }
+
     /// Generate a new signed session token
     /// 
     /// Format: {uuid}.{base64_signature}
@@ -149,6 +158,18 @@ mod tests {
         assert!(signer1.verify_signed_token(&token2).is_none());
     }
 
+    #[test]
+    fn test_purpose_signers_are_keyed_apart() {
+        let sessions = SessionSigner::new("secret");
+        let shares = SessionSigner::for_purpose("post-share-preview", "secret");
+        let forms = SessionSigner::for_purpose("signup-form", "secret");
+
+        let token = shares.create_signed_token().unwrap();
+        assert!(shares.verify_signed_token(&token).is_some());
+        assert!(sessions.verify_signed_token(&token).is_none());
+        assert!(forms.verify_signed_token(&token).is_none());
+    }
+
     #[test]
     fn test_is_signed_token() {
         assert!(SessionSigner::is_signed_token("uuid.signature"));
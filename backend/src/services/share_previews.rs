@@ -0,0 +1,111 @@
+//! Draft preview links for reviewers without an account
+//!
+//! A share link is `/post/:id?share=<token>`, where the token is a random id
+//! signed with `SessionSigner::for_purpose`. The id is stored with the post
+//! it unlocks, its expiry and how many views it allows; every view is
+//! counted. A link stops working when it expires, runs out of views or its
+//! post stops being a draft or scheduled post, e.g. once it is published.
+
+use chrono::NaiveDateTime;
+use crate::middleware::errors::AppError;
+use crate::models::PostShareLink;
+use crate::services::SessionSigner;
+
+/// Query parameter carrying a share token on public URLs
+pub const SHARE_PARAM: &str = "share";
+pub const DEFAULT_SHARE_LINK_HOURS: i64 = 72;
+/// Two weeks
+pub const MAX_SHARE_LINK_HOURS: i64 = 336;
+pub const DEFAULT_SHARE_LINK_VIEWS: i32 = 50;
+pub const MAX_SHARE_LINK_VIEWS: i32 = 1000;
+/// Post statuses a share link shows
+const SHAREABLE_STATUSES: [&str; 2] = ["draft", "scheduled"];
+
+/// Signer for share tokens
+pub fn share_signer(secret: &str) -> SessionSigner {
+    SessionSigner::for_purpose("post-share-preview", secret)
+}
+
+/// Validate a requested lifetime in hours and view limit, filling in defaults
+pub fn validate_share_request(hours: Option<i64>, max_views: Option<i32>) -> Result<(i64, i32), AppError> {
+    let hours = hours.unwrap_or(DEFAULT_SHARE_LINK_HOURS);
+    if !(1..=MAX_SHARE_LINK_HOURS).contains(&hours) {
+        return Err(AppError::ValidationError(format!(
+            "Share links can last between 1 and {} hours", MAX_SHARE_LINK_HOURS
+        )));
+    }
+    let max_views = max_views.unwrap_or(DEFAULT_SHARE_LINK_VIEWS);
+    if !(1..=MAX_SHARE_LINK_VIEWS).contains(&max_views) {
+        return Err(AppError::ValidationError(format!(
+            "Share links can allow between 1 and {} views", MAX_SHARE_LINK_VIEWS
+        )));
+    }
+    Ok((hours, max_views))
+}
+
+/// Whether a post can still be previewed through a share link
+pub fn is_shareable(post_status: &str) -> bool {
+    SHAREABLE_STATUSES.contains(&post_status)
+}
+
+/// Check that `link` may show post `post_id`, which has `post_status`, at `now`
+///
+/// A link for another post is reported as missing so it never hints at other
+/// content; one that no longer works says so.
+pub fn check_share_link(link: &PostShareLink, post_id: i32, post_status: &str, now: NaiveDateTime) -> Result<(), AppError> {
+    if link.post_id != post_id {
+        return Err(AppError::NotFound("Post not found".to_string()));
+    }
+    if now >= link.expires_at || link.view_count >= link.max_views || !is_shareable(post_status) {
+        return Err(AppError::NotFound("This preview link is no longer valid".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::errors::error_message;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2025, 8, 19).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn link() -> PostShareLink {
+        PostShareLink {
+            id: 1,
+            post_id: 12,
+            token_id: "abc".to_string(),
+            created_by: Some(1),
+            created_at: at(9),
+            expires_at: at(12),
+            max_views: 3,
+            view_count: 2,
+        }
+    }
+
+    #[test]
+    fn test_link_shows_its_draft() {
+        assert!(check_share_link(&link(), 12, "draft", at(11)).is_ok());
+        assert!(check_share_link(&link(), 12, "scheduled", at(11)).is_ok());
+        assert_eq!(error_message(check_share_link(&link(), 13, "draft", at(11))), "Post not found");
+    }
+
+    #[test]
+    fn test_link_stops_working() {
+        let invalid = "This preview link is no longer valid";
+        assert_eq!(error_message(check_share_link(&link(), 12, "draft", at(12))), invalid);
+        assert_eq!(error_message(check_share_link(&PostShareLink { view_count: 3, ..link() }, 12, "draft", at(11))), invalid);
+        assert_eq!(error_message(check_share_link(&link(), 12, "published", at(11))), invalid);
+        assert_eq!(error_message(check_share_link(&link(), 12, "trash", at(11))), invalid);
+    }
+
+    #[test]
+    fn test_validate_share_request() {
+        assert_eq!(validate_share_request(None, None).unwrap(), (DEFAULT_SHARE_LINK_HOURS, DEFAULT_SHARE_LINK_VIEWS));
+        assert_eq!(validate_share_request(Some(1), Some(MAX_SHARE_LINK_VIEWS)).unwrap(), (1, MAX_SHARE_LINK_VIEWS));
+        assert!(validate_share_request(Some(0), None).is_err());
+        assert!(validate_share_request(Some(MAX_SHARE_LINK_HOURS + 1), None).is_err());
+        assert!(validate_share_request(None, Some(0)).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Who may create an account through public signup
+//!
+//! `allow_public_signup` turns signup off entirely, and `signup_invite_only`
+//! requires a single-use invite created by an admin. Invite-only wins when
+//! both are set, so a closed site can still let invited people in.
+//!
+//! Signups also pass a cheap bot filter: a hidden honeypot field that people
+//! never see must stay empty, and the form must have been open for at least
+//! `signup_min_fill_seconds`. The signup policy response carries a form token,
+//! the time it was issued signed with `SessionSigner::for_purpose`, and the
+//! server measures the time since then itself.
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Serialize;
+use crate::middleware::errors::AppError;
+use crate::models::{Invite, Setting};
+use crate::services::SessionSigner;
+
+pub const ALLOW_PUBLIC_SIGNUP_SETTING: &str = "allow_public_signup";
+pub const SIGNUP_INVITE_ONLY_SETTING: &str = "signup_invite_only";
+pub const SIGNUP_MIN_FILL_SECONDS_SETTING: &str = "signup_min_fill_seconds";
+pub const DEFAULT_MIN_FILL_SECONDS: u64 = 3;
+const MAX_MIN_FILL_SECONDS: u64 = 60;
+/// A signup form left open longer than this has to be reloaded
+const FORM_TOKEN_MAX_AGE_HOURS: i64 = 24;
+/// Invite lifetime when the admin doesn't pick one
+pub const DEFAULT_INVITE_DAYS: i64 = 7;
+pub const MAX_INVITE_DAYS: i64 = 90;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignupMode {
+    Open,
+    InviteOnly,
+    Closed,
+}
+
+impl SignupMode {
+    fn from_flags(allow_public_signup: bool, invite_only: bool) -> Self {
+        match (allow_public_signup, invite_only) {
+            (_, true) => SignupMode::InviteOnly,
+            (true, false) => SignupMode::Open,
+            (false, false) => SignupMode::Closed,
+        }
+    }
+
+    /// Current mode from settings; missing values leave signup open
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let flag = |conn: &mut PgConnection, key: &str, default: bool| -> QueryResult<bool> {
+            Ok(Setting::find_by_key(conn, key)?
+                .and_then(|s| s.setting_value)
+                .map(|value| value.trim().eq_ignore_ascii_case("true"))
+                .unwrap_or(default))
+        };
+        let allow_public_signup = flag(conn, ALLOW_PUBLIC_SIGNUP_SETTING, true)?;
+        let invite_only = flag(conn, SIGNUP_INVITE_ONLY_SETTING, false)?;
+        Ok(Self::from_flags(allow_public_signup, invite_only))
+    }
+}
+
+/// Validate the minimum form fill time setting before it is stored
+pub fn validate_min_fill_seconds(value: &str) -> Result<u64, AppError> {
+    value.trim().parse::<u64>()
+        .ok()
+        .filter(|seconds| *seconds <= MAX_MIN_FILL_SECONDS)
+        .ok_or_else(|| AppError::ValidationError(format!(
+            "{} must be a whole number between 0 and {}", SIGNUP_MIN_FILL_SECONDS_SETTING, MAX_MIN_FILL_SECONDS
+        )))
+}
+
+/// Minimum form fill time from settings; 0 disables the check
+pub fn load_min_fill_seconds(conn: &mut PgConnection) -> QueryResult<u64> {
+    Ok(Setting::find_by_key(conn, SIGNUP_MIN_FILL_SECONDS_SETTING)?
+        .and_then(|s| s.setting_value)
+        .and_then(|value| validate_min_fill_seconds(&value).ok())
+        .unwrap_or(DEFAULT_MIN_FILL_SECONDS))
+}
+
+/// Signer for signup form tokens
+pub fn form_signer(secret: &str) -> SessionSigner {
+    SessionSigner::for_purpose("signup-form", secret)
+}
+
+/// Form token recording that the signup form was handed out at `now_ms`
+pub fn issue_form_token(signer: &SessionSigner, now_ms: i64) -> Result<String, AppError> {
+    signer.create_signed_token_from_uuid(&now_ms.to_string())
+        .map_err(|e| AppError::InternalError(format!("Could not sign signup form token: {}", e)))
+}
+
+/// Reject signups that look automated: a filled-in honeypot, or a form sent
+/// back less than `min_fill_seconds` after its `form_token` was issued
+///
+/// A missing, forged or day-old token fails too, as does a token issued in
+/// the future.
+pub fn check_bot_signals(
+    honeypot: &str,
+    form_token: Option<&str>,
+    signer: &SessionSigner,
+    now_ms: i64,
+    min_fill_seconds: u64,
+) -> Result<(), AppError> {
+    if !honeypot.trim().is_empty() {
+        return Err(AppError::ValidationError("Signup could not be completed".to_string()));
+    }
+    if min_fill_seconds == 0 {
+        return Ok(());
+    }
+    let issued_ms = form_token
+        .and_then(|token| signer.verify_signed_token(token))
+        .and_then(|issued| issued.parse::<i64>().ok())
+        .filter(|issued_ms| (0..=FORM_TOKEN_MAX_AGE_HOURS * 3_600_000).contains(&(now_ms - issued_ms)))
+        .ok_or_else(|| AppError::ValidationError(
+            "This signup form has expired. Please reload the page and try again".to_string()
+        ))?;
+    if now_ms - issued_ms < min_fill_seconds as i64 * 1000 {
+        return Err(AppError::ValidationError(
+            "That was quick! Please take a moment to check the form and submit it again".to_string()
+        ));
+    }
+    Ok(())
+}
+
+/// Random token for a new invite link
+pub fn generate_invite_token() -> String {
+    use rand::Rng;
+    let token: [u8; 32] = rand::thread_rng().gen();
+    hex::encode(token)
+}
+
+/// Check that `invite` can be used to sign up with `email` at `now`
+pub fn check_invite(invite: Option<&Invite>, email: &str, now: NaiveDateTime) -> Result<(), AppError> {
+    let invalid = || AppError::ValidationError("This invite is invalid or has expired".to_string());
+    let invite = invite.ok_or_else(invalid)?;
+    if invite.used_at.is_some() || now >= invite.expires_at {
+        return Err(invalid());
+    }
+    if let Some(invited) = &invite.email {
+        if !invited.trim().eq_ignore_ascii_case(email.trim()) {
+            return Err(AppError::ValidationError("This invite was issued for a different email address".to_string()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::errors::error_message;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn invite(email: Option<&str>) -> Invite {
+        Invite {
+            id: 1,
+            token: "abc".to_string(),
+            email: email.map(str::to_string),
+            created_by: Some(1),
+            created_at: at(0),
+            expires_at: at(12),
+            used_at: None,
+            used_by: None,
+        }
+    }
+
+    #[test]
+    fn test_invite_only_wins_over_closed() {
+        assert_eq!(SignupMode::from_flags(true, false), SignupMode::Open);
+        assert_eq!(SignupMode::from_flags(false, false), SignupMode::Closed);
+        assert_eq!(SignupMode::from_flags(false, true), SignupMode::InviteOnly);
+        assert_eq!(SignupMode::from_flags(true, true), SignupMode::InviteOnly);
+    }
+
+    #[test]
+    fn test_bot_signals() {
+        const ISSUED_MS: i64 = 1_700_000_000_000;
+        let signer = form_signer("secret");
+        let token = issue_form_token(&signer, ISSUED_MS).unwrap();
+        let check = |honeypot: &str, token: Option<&str>, now_ms: i64| check_bot_signals(honeypot, token, &signer, now_ms, 3);
+
+        assert!(check("", Some(&token), ISSUED_MS + 5_000).is_ok());
+        assert!(check("http://spam.example", Some(&token), ISSUED_MS + 5_000).is_err());
+        assert!(error_message(check("", Some(&token), ISSUED_MS + 800)).starts_with("That was quick!"));
+        assert!(error_message(check("", None, ISSUED_MS + 5_000)).contains("expired"));
+        // Too old, or issued in the future
+        assert!(error_message(check("", Some(&token), ISSUED_MS + 25 * 3_600_000)).contains("expired"));
+        assert!(error_message(check("", Some(&token), ISSUED_MS - 5_000)).contains("expired"));
+        // A threshold of 0 only keeps the honeypot
+        assert!(check_bot_signals("", None, &signer, ISSUED_MS, 0).is_ok());
+        assert!(validate_min_fill_seconds("61").is_err());
+    }
+
+    #[test]
+    fn test_form_token_cannot_be_forged() {
+        const ISSUED_MS: i64 = 1_700_000_000_000;
+        let signer = form_signer("secret");
+        let token = issue_form_token(&signer, ISSUED_MS).unwrap();
+        let (_, signature) = token.split_once('.').unwrap();
+        // Backdating the timestamp breaks the signature
+        let backdated = format!("{}.{}", ISSUED_MS - 60_000, signature);
+        assert!(check_bot_signals("", Some(&backdated), &signer, ISSUED_MS + 1_000, 3).is_err());
+        // Tokens from another key, e.g. a session token, aren't accepted
+        let other = issue_form_token(&SessionSigner::new("secret"), ISSUED_MS).unwrap();
+        assert!(check_bot_signals("", Some(&other), &signer, ISSUED_MS + 5_000, 3).is_err());
+    }
+
+    #[test]
+    fn test_check_invite() {
+        assert!(check_invite(Some(&invite(None)), "a@example.com", at(11)).is_ok());
+        assert!(check_invite(Some(&invite(Some("A@Example.com"))), "a@example.com", at(11)).is_ok());
+        assert!(check_invite(Some(&invite(Some("a@example.com"))), "b@example.com", at(11)).is_err());
+        assert!(check_invite(Some(&invite(None)), "a@example.com", at(12)).is_err());
+        assert!(check_invite(None, "a@example.com", at(11)).is_err());
+
+        let used = Invite { used_at: Some(at(1)), used_by: Some(2), ..invite(None) };
+        assert!(check_invite(Some(&used), "a@example.com", at(11)).is_err());
+    }
+}
@@ -0,0 +1,257 @@
+//! XML sitemaps of public content
+//!
+//! A sitemap file may list at most `MAX_URLS_PER_SITEMAP` URLs. A site that
+//! fits gets everything (the home page, the post list, published posts and
+//! published pages) in `/sitemap.xml`. A bigger one gets a sitemap index
+//! there instead, pointing at numbered child sitemaps of posts
+//! (`/sitemap-posts-1.xml`, ...) and pages (`/sitemap-pages-1.xml`, ...,
+//! which also carry the home page and post list). Each child loads only its
+//! own window of rows. URLs are canonical paths on the public site URL.
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use crate::models::{Page, Post};
+use crate::services::canonical_urls::{canonical_path, public_base_url, TrailingSlash};
+use crate::services::feed::xml_escape;
+
+/// Sitemap protocol limit on URLs per file
+pub const MAX_URLS_PER_SITEMAP: i64 = 50_000;
+/// Listed besides posts and pages, at the start of the (first) pages sitemap
+const STATIC_PATHS: [&str; 2] = ["/", "/posts"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SitemapKind {
+    Posts,
+    Pages,
+}
+
+impl SitemapKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Posts => "posts",
+            Self::Pages => "pages",
+        }
+    }
+}
+
+/// How the site's URLs are split into files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SitemapLayout {
+    /// Everything in `/sitemap.xml`
+    Single,
+    /// `/sitemap.xml` is an index of this many post and page sitemaps
+    Index { post_sitemaps: i64, page_sitemaps: i64 },
+}
+
+impl SitemapLayout {
+    /// Layout for a site with this many published posts and pages
+    pub fn for_counts(post_count: i64, page_count: i64, per_sitemap: i64) -> Self {
+        let page_urls = page_count + STATIC_PATHS.len() as i64;
+        if post_count + page_urls <= per_sitemap {
+            return Self::Single;
+        }
+        Self::Index {
+            post_sitemaps: (post_count + per_sitemap - 1) / per_sitemap,
+            page_sitemaps: (page_urls + per_sitemap - 1) / per_sitemap,
+        }
+    }
+
+    /// Number of child sitemaps of a kind; none for a single sitemap
+    pub fn children(self, kind: SitemapKind) -> i64 {
+        match (self, kind) {
+            (Self::Single, _) => 0,
+            (Self::Index { post_sitemaps, .. }, SitemapKind::Posts) => post_sitemaps,
+            (Self::Index { page_sitemaps, .. }, SitemapKind::Pages) => page_sitemaps,
+        }
+    }
+}
+
+/// Path of a child sitemap, numbered from 1
+pub fn child_path(kind: SitemapKind, number: i64) -> String {
+    format!("/sitemap-{}-{}.xml", kind.as_str(), number)
+}
+
+/// The number in a child sitemap's file name, from its `N.xml` tail
+pub fn parse_child_number(tail: &str) -> Option<i64> {
+    tail.strip_suffix(".xml")?.parse::<i64>().ok().filter(|n| *n >= 1)
+}
+
+/// Rows of a kind (offset, limit) that child sitemap `number` lists. The
+/// static paths take the first slots of the first pages sitemap.
+pub fn child_window(kind: SitemapKind, number: i64, per_sitemap: i64) -> (i64, i64) {
+    let start = (number - 1) * per_sitemap;
+    match kind {
+        SitemapKind::Posts => (start, per_sitemap),
+        SitemapKind::Pages if number == 1 => (0, per_sitemap - STATIC_PATHS.len() as i64),
+        SitemapKind::Pages => (start - STATIC_PATHS.len() as i64, per_sitemap),
+    }
+}
+
+/// One `<url>` of a sitemap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitemapEntry {
+    pub loc: String,
+    pub last_modified: Option<NaiveDateTime>,
+}
+
+fn w3c_date(at: NaiveDateTime) -> String {
+    at.format("%Y-%m-%d").to_string()
+}
+
+/// A `<urlset>` document
+pub fn render_urlset(entries: &[SitemapEntry]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for entry in entries {
+        xml.push_str(&format!("<url><loc>{}</loc>", xml_escape(&entry.loc)));
+        if let Some(at) = entry.last_modified {
+            xml.push_str(&format!("<lastmod>{}</lastmod>", w3c_date(at)));
+        }
+        xml.push_str("</url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// A `<sitemapindex>` document pointing at these sitemap URLs
+pub fn render_index(sitemaps: &[String]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for loc in sitemaps {
+        xml.push_str(&format!("<sitemap><loc>{}</loc></sitemap>\n", xml_escape(loc)));
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+/// Absolute canonical URLs on the public site
+struct SiteUrls {
+    base: String,
+    trailing_slash: TrailingSlash,
+}
+
+impl SiteUrls {
+    fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        Ok(Self { base: public_base_url(conn)?, trailing_slash: TrailingSlash::load(conn)? })
+    }
+
+    fn content(&self, path: &str) -> String {
+        format!("{}{}", self.base, canonical_path(path, self.trailing_slash))
+    }
+
+    /// Sitemap files keep their exact names
+    fn file(&self, path: &str) -> String {
+        format!("{}{}", self.base, path)
+    }
+}
+
+fn entries(conn: &mut PgConnection, urls: &SiteUrls, kind: SitemapKind, offset: i64, limit: i64, with_static: bool) -> QueryResult<Vec<SitemapEntry>> {
+    let mut entries = Vec::new();
+    if with_static {
+        entries.extend(STATIC_PATHS.iter().map(|path| SitemapEntry { loc: urls.content(path), last_modified: None }));
+    }
+    match kind {
+        SitemapKind::Posts => entries.extend(Post::list_published_for_sitemap(conn, limit, offset)?
+            .into_iter()
+            .map(|(id, last_modified)| SitemapEntry { loc: urls.content(&format!("/post/{}", id)), last_modified })),
+        SitemapKind::Pages => entries.extend(Page::list_published_for_sitemap(conn, limit, offset)?
+            .into_iter()
+            .map(|(slug, last_modified)| SitemapEntry { loc: urls.content(&format!("/page/{}", slug)), last_modified })),
+    }
+    Ok(entries)
+}
+
+fn layout(conn: &mut PgConnection) -> QueryResult<SitemapLayout> {
+    Ok(SitemapLayout::for_counts(Post::count_published(conn)?, Page::count_published(conn)?, MAX_URLS_PER_SITEMAP))
+}
+
+/// `/sitemap.xml`: every URL, or the index of child sitemaps on a large site
+pub fn root_document(conn: &mut PgConnection) -> QueryResult<String> {
+    let urls = SiteUrls::load(conn)?;
+    let layout = layout(conn)?;
+    if layout == SitemapLayout::Single {
+        let mut all = entries(conn, &urls, SitemapKind::Pages, 0, MAX_URLS_PER_SITEMAP, true)?;
+        all.extend(entries(conn, &urls, SitemapKind::Posts, 0, MAX_URLS_PER_SITEMAP, false)?);
+        return Ok(render_urlset(&all));
+    }
+    let sitemaps: Vec<String> = [SitemapKind::Pages, SitemapKind::Posts].into_iter()
+        .flat_map(|kind| (1..=layout.children(kind)).map(move |number| child_path(kind, number)))
+        .map(|path| urls.file(&path))
+        .collect();
+    Ok(render_index(&sitemaps))
+}
+
+/// A child sitemap, or `None` if the site has no such file
+pub fn child_document(conn: &mut PgConnection, kind: SitemapKind, number: i64) -> QueryResult<Option<String>> {
+    if number > layout(conn)?.children(kind) {
+        return Ok(None);
+    }
+    let urls = SiteUrls::load(conn)?;
+    let (offset, limit) = child_window(kind, number, MAX_URLS_PER_SITEMAP);
+    let with_static = kind == SitemapKind::Pages && number == 1;
+    Ok(Some(render_urlset(&entries(conn, &urls, kind, offset, limit, with_static)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_at_the_boundary() {
+        // Two static URLs plus posts and pages exactly fill one file
+        assert_eq!(SitemapLayout::for_counts(6, 2, 10), SitemapLayout::Single);
+        assert_eq!(SitemapLayout::for_counts(0, 0, 10), SitemapLayout::Single);
+
+        // One more URL needs an index
+        let layout = SitemapLayout::for_counts(7, 2, 10);
+        assert_eq!(layout, SitemapLayout::Index { post_sitemaps: 1, page_sitemaps: 1 });
+        assert_eq!(SitemapLayout::for_counts(21, 9, 10), SitemapLayout::Index { post_sitemaps: 3, page_sitemaps: 2 });
+        assert_eq!(SitemapLayout::for_counts(0, 20, 10), SitemapLayout::Index { post_sitemaps: 0, page_sitemaps: 3 });
+        assert_eq!(SitemapLayout::Single.children(SitemapKind::Posts), 0);
+        assert_eq!(layout.children(SitemapKind::Pages), 1);
+    }
+
+    #[test]
+    fn test_child_windows_cover_every_row_once() {
+        let per = 10;
+        for (kind, rows, static_urls) in [(SitemapKind::Posts, 21, 0), (SitemapKind::Pages, 9, 2), (SitemapKind::Pages, 28, 2)] {
+            let layout = match kind {
+                SitemapKind::Posts => SitemapLayout::for_counts(rows, 0, per),
+                SitemapKind::Pages => SitemapLayout::for_counts(per, rows, per),
+            };
+            let mut next = 0;
+            for number in 1..=layout.children(kind) {
+                let (offset, limit) = child_window(kind, number, per);
+                assert_eq!(offset, next);
+                let listed = limit.min(rows - offset) + if number == 1 { static_urls } else { 0 };
+                assert!(listed <= per);
+                next = offset + limit;
+            }
+            assert!(next >= rows, "{:?} with {} rows", kind, rows);
+        }
+    }
+
+    #[test]
+    fn test_child_paths() {
+        assert_eq!(child_path(SitemapKind::Posts, 2), "/sitemap-posts-2.xml");
+        assert_eq!(parse_child_number("2.xml"), Some(2));
+        assert_eq!(parse_child_number("0.xml"), None);
+        assert_eq!(parse_child_number("2"), None);
+        assert_eq!(parse_child_number("two.xml"), None);
+    }
+
+    #[test]
+    fn test_documents() {
+        let at = NaiveDateTime::parse_from_str("2025-08-15 09:30:00", "%Y-%m-%d %H:%M:%S").ok();
+        let xml = render_urlset(&[
+            SitemapEntry { loc: "https://example.com/".to_string(), last_modified: None },
+            SitemapEntry { loc: "https://example.com/page/a&b".to_string(), last_modified: at },
+        ]);
+        assert!(xml.contains("<url><loc>https://example.com/</loc></url>"));
+        assert!(xml.contains("<url><loc>https://example.com/page/a&amp;b</loc><lastmod>2025-08-15</lastmod></url>"));
+
+        let index = render_index(&["https://example.com/sitemap-posts-1.xml".to_string()]);
+        assert!(index.contains("<sitemapindex"));
+        assert!(index.contains("<sitemap><loc>https://example.com/sitemap-posts-1.xml</loc></sitemap>"));
+    }
+}
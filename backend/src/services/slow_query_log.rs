@@ -0,0 +1,139 @@
+//! Slow query log
+//!
+//! Every pooled connection carries a small instrumentation hook that times
+//! each statement and logs the ones taking at least the
+//! `slow_query_threshold_ms` setting as a warning, with their SQL (bind
+//! values are left out, so passwords and tokens never reach the log). A
+//! threshold of 0 turns the log off. The threshold is shared by all
+//! connections and changes take effect immediately, so the log can be
+//! switched on in production while chasing a problem and off again after.
+//! When nothing is slow the cost is one clock read per statement.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use diesel::connection::{Instrumentation, InstrumentationEvent};
+use diesel::prelude::*;
+use diesel::r2d2::{CustomizeConnection, Error as PoolError};
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const SLOW_QUERY_THRESHOLD_SETTING: &str = "slow_query_threshold_ms";
+/// Longest threshold accepted; anything slower than this is surely worth a log line
+pub const MAX_SLOW_QUERY_THRESHOLD_MS: u64 = 60_000;
+
+/// Normalize a threshold setting value, in milliseconds
+pub fn validate_threshold(value: &str) -> Result<u64, AppError> {
+    value.trim().parse::<u64>()
+        .ok()
+        .filter(|ms| *ms <= MAX_SLOW_QUERY_THRESHOLD_MS)
+        .ok_or_else(|| AppError::ValidationError(format!(
+            "{} must be a whole number of milliseconds from 0 (off) to {}",
+            SLOW_QUERY_THRESHOLD_SETTING, MAX_SLOW_QUERY_THRESHOLD_MS
+        )))
+}
+
+/// Whether a statement that took `elapsed` should be logged; 0 means off
+fn is_slow(elapsed: Duration, threshold_ms: u64) -> bool {
+    threshold_ms > 0 && elapsed >= Duration::from_millis(threshold_ms)
+}
+
+/// The current threshold, shared between the settings and every connection
+#[derive(Debug, Clone, Default)]
+pub struct SlowQueryThreshold(Arc<AtomicU64>);
+
+impl SlowQueryThreshold {
+    pub fn millis(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set_millis(&self, ms: u64) {
+        self.0.store(ms, Ordering::Relaxed);
+    }
+
+    /// Apply the stored setting; a missing or invalid value turns the log off
+    pub fn load(&self, conn: &mut PgConnection) -> QueryResult<()> {
+        let value = Setting::find_by_key(conn, SLOW_QUERY_THRESHOLD_SETTING)?.and_then(|s| s.setting_value);
+        let ms = match value.as_deref().map(validate_threshold) {
+            Some(Ok(ms)) => ms,
+            Some(Err(e)) => {
+                warn!("Ignoring setting {}: {}", SLOW_QUERY_THRESHOLD_SETTING, e);
+                0
+            }
+            None => 0,
+        };
+        self.set_millis(ms);
+        Ok(())
+    }
+}
+
+/// Times the statements of one connection
+struct QueryTimer {
+    threshold: SlowQueryThreshold,
+    started: Option<Instant>,
+}
+
+impl Instrumentation for QueryTimer {
+    fn on_connection_event(&mut self, event: InstrumentationEvent<'_>) {
+        match event {
+            InstrumentationEvent::StartQuery { .. } => {
+                self.started = (self.threshold.millis() > 0).then(Instant::now);
+            }
+            InstrumentationEvent::FinishQuery { query, error, .. } => {
+                let Some(started) = self.started.take() else { return };
+                let elapsed = started.elapsed();
+                if is_slow(elapsed, self.threshold.millis()) {
+                    warn!(
+                        duration_ms = elapsed.as_millis() as u64,
+                        failed = error.is_some(),
+                        "Slow query: {}", query
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Installs the timer on each connection the pool opens
+#[derive(Debug)]
+pub struct SlowQueryLog(pub SlowQueryThreshold);
+
+impl CustomizeConnection<PgConnection, PoolError> for SlowQueryLog {
+    fn on_acquire(&self, conn: &mut PgConnection) -> Result<(), PoolError> {
+        conn.set_instrumentation(QueryTimer { threshold: self.0.clone(), started: None });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_comparison() {
+        assert!(is_slow(Duration::from_millis(250), 200));
+        assert!(is_slow(Duration::from_millis(200), 200));
+        assert!(!is_slow(Duration::from_millis(199), 200));
+        assert!(!is_slow(Duration::from_secs(30), 0));
+    }
+
+    #[test]
+    fn test_validate_threshold() {
+        assert_eq!(validate_threshold(" 250 ").unwrap(), 250);
+        assert_eq!(validate_threshold("0").unwrap(), 0);
+        assert!(validate_threshold("-5").is_err());
+        assert!(validate_threshold("fast").is_err());
+        assert!(validate_threshold(&(MAX_SLOW_QUERY_THRESHOLD_MS + 1).to_string()).is_err());
+    }
+
+    #[test]
+    fn test_threshold_is_shared() {
+        let threshold = SlowQueryThreshold::default();
+        let connection_copy = threshold.clone();
+        assert_eq!(connection_copy.millis(), 0);
+        threshold.set_millis(500);
+        assert_eq!(connection_copy.millis(), 500);
+    }
+}
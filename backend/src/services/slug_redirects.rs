@@ -0,0 +1,178 @@
+//! Redirects from old slugs to renamed content
+//!
+//! When a page's slug changes, the old slug is recorded against the page so
+//! requests for it can be answered with a 301 to wherever the page lives
+//! now. Redirects point at the resource rather than at the next slug, so a
+//! page renamed several times never needs a chain of hops. Each resource
+//! keeps at most `MAX_REDIRECTS_PER_RESOURCE` of its most recent old slugs,
+//! and redirects stop applying after `REDIRECT_LIFETIME_DAYS`.
+
+use chrono::{Duration, NaiveDateTime};
+use diesel::prelude::*;
+use crate::models::{NewSlugRedirect, Page, SlugRedirect};
+
+pub const PAGE_RESOURCE: &str = "page";
+pub const MAX_REDIRECTS_PER_RESOURCE: usize = 10;
+pub const REDIRECT_LIFETIME_DAYS: i64 = 365;
+
+/// Outcome of looking up content by slug
+#[derive(Debug)]
+pub enum SlugLookup<T> {
+    Found(T),
+    /// The content now lives at this slug
+    Redirect(String),
+    NotFound,
+}
+
+pub fn is_expired(redirect: &SlugRedirect, now: NaiveDateTime) -> bool {
+    redirect.created_at + Duration::days(REDIRECT_LIFETIME_DAYS) <= now
+}
+
+/// IDs of a resource's redirects (newest first) that are expired or past
+/// the per-resource cap
+pub fn redirects_to_prune(redirects: &[SlugRedirect], now: NaiveDateTime) -> Vec<i32> {
+    redirects.iter()
+        .enumerate()
+        .filter(|(index, redirect)| *index >= MAX_REDIRECTS_PER_RESOURCE || is_expired(redirect, now))
+        .map(|(_, redirect)| redirect.id)
+        .collect()
+}
+
+/// Decide what a request for a page slug gets: the page living at that slug
+/// wins; otherwise a live redirect leads to its page if that is published
+pub fn resolve_page_lookup(
+    live: Option<Page>,
+    redirect: Option<&SlugRedirect>,
+    target: Option<Page>,
+    now: NaiveDateTime,
+) -> SlugLookup<Page> {
+    if let Some(page) = live {
+        return if page.status == "published" { SlugLookup::Found(page) } else { SlugLookup::NotFound };
+    }
+    match (redirect, target) {
+        (Some(redirect), Some(page))
+            if !is_expired(redirect, now) && page.id == redirect.resource_id && page.status == "published" =>
+        {
+            SlugLookup::Redirect(page.slug)
+        }
+        _ => SlugLookup::NotFound,
+    }
+}
+
+/// Remember `old_slug` for a renamed resource and trim its older redirects
+pub fn record_rename(
+    conn: &mut PgConnection,
+    resource_type: &str,
+    resource_id: i32,
+    old_slug: &str,
+    new_slug: &str,
+    now: NaiveDateTime,
+) -> QueryResult<()> {
+    if old_slug == new_slug {
+        return Ok(());
+    }
+    // Moving back to an earlier slug makes its redirect pointless
+    SlugRedirect::delete_slug(conn, resource_type, new_slug)?;
+    SlugRedirect::record(conn, NewSlugRedirect {
+        resource_type: resource_type.to_string(),
+        resource_id,
+        old_slug: old_slug.to_string(),
+        created_at: now,
+    })?;
+
+    let prune = redirects_to_prune(&SlugRedirect::list_for_resource(conn, resource_type, resource_id)?, now);
+    if !prune.is_empty() {
+        SlugRedirect::delete_ids(conn, &prune)?;
+    }
+    Ok(())
+}
+
+/// Look up a published page by its current or a previous slug
+pub fn lookup_page(conn: &mut PgConnection, slug: &str, now: NaiveDateTime) -> QueryResult<SlugLookup<Page>> {
+    let live = Page::find_by_slug(conn, slug)?;
+    if live.is_some() {
+        return Ok(resolve_page_lookup(live, None, None, now));
+    }
+    let redirect = SlugRedirect::find(conn, PAGE_RESOURCE, slug)?;
+    let target = match redirect {
+        Some(ref redirect) => Page::find_by_id(conn, redirect.resource_id)?,
+        None => None,
+    };
+    Ok(resolve_page_lookup(None, redirect.as_ref(), target, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn page(id: i32, slug: &str, status: &str) -> Page {
+        Page {
+            id,
+            title: "About".to_string(),
+            content: String::new(),
+            user_id: None,
+            created_at: None,
+            updated_at: None,
+            slug: slug.to_string(),
+            status: status.to_string(),
+        }
+    }
+
+    fn redirect(id: i32, resource_id: i32, old_slug: &str, created_at: &str) -> SlugRedirect {
+        SlugRedirect {
+            id,
+            resource_type: PAGE_RESOURCE.to_string(),
+            resource_id,
+            old_slug: old_slug.to_string(),
+            created_at: at(created_at),
+        }
+    }
+
+    #[test]
+    fn test_renamed_page_resolves_via_old_slug() {
+        let now = at("2025-08-15 12:00:00");
+        let old = redirect(1, 7, "about", "2025-08-01 00:00:00");
+        assert!(matches!(
+            resolve_page_lookup(None, Some(&old), Some(page(7, "about-us", "published")), now),
+            SlugLookup::Redirect(slug) if slug == "about-us"
+        ));
+    }
+
+    #[test]
+    fn test_live_slug_wins_over_redirect() {
+        let now = at("2025-08-15 12:00:00");
+        let old = redirect(1, 7, "about", "2025-08-01 00:00:00");
+        let live = page(9, "about", "published");
+        assert!(matches!(
+            resolve_page_lookup(Some(live), Some(&old), Some(page(7, "about-us", "published")), now),
+            SlugLookup::Found(page) if page.id == 9
+        ));
+        assert!(matches!(resolve_page_lookup(Some(page(9, "about", "draft")), None, None, now), SlugLookup::NotFound));
+    }
+
+    #[test]
+    fn test_redirect_needs_a_published_live_target() {
+        let now = at("2025-08-15 12:00:00");
+        let old = redirect(1, 7, "about", "2025-08-01 00:00:00");
+        assert!(matches!(resolve_page_lookup(None, Some(&old), Some(page(7, "about-us", "draft")), now), SlugLookup::NotFound));
+        assert!(matches!(resolve_page_lookup(None, Some(&old), None, now), SlugLookup::NotFound));
+
+        let expired = redirect(1, 7, "about", "2024-08-01 00:00:00");
+        assert!(matches!(resolve_page_lookup(None, Some(&expired), Some(page(7, "about-us", "published")), now), SlugLookup::NotFound));
+    }
+
+    #[test]
+    fn test_prune_expired_and_over_cap() {
+        let now = at("2025-08-15 12:00:00");
+        let mut redirects: Vec<SlugRedirect> = (1..=MAX_REDIRECTS_PER_RESOURCE as i32 + 2)
+            .map(|id| redirect(id, 7, &format!("old-{}", id), "2025-08-01 00:00:00"))
+            .collect();
+        redirects[1].created_at = at("2024-01-01 00:00:00");
+
+        assert_eq!(redirects_to_prune(&redirects, now), vec![2, 11, 12]);
+    }
+}
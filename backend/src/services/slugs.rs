@@ -0,0 +1,91 @@
+//! URL slug generation
+//!
+//! Builds URL-safe slugs from titles and resolves collisions by appending a
+//! numeric suffix (`about`, `about-2`, `about-3`, ...).
+
+/// Fallback used when a title contains no characters usable in a slug
+const FALLBACK_SLUG: &str = "page";
+
+/// Normalize arbitrary text into a lowercase, dash-separated slug.
+///
+/// Runs of anything other than ASCII letters and digits become a single
+/// dash, and leading/trailing dashes are trimmed.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for ch in text.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Slugify a title, falling back to a generic slug if nothing usable remains
+pub fn slug_from_title(title: &str) -> String {
+    let slug = slugify(title);
+    if slug.is_empty() { FALLBACK_SLUG.to_string() } else { slug }
+}
+
+/// Find the first free slug starting from `base`, trying `base-2`, `base-3`,
+/// and so on. `is_taken` reports whether a candidate is already in use.
+pub fn unique_slug<E>(base: &str, mut is_taken: impl FnMut(&str) -> Result<bool, E>) -> Result<String, E> {
+    if !is_taken(base)? {
+        return Ok(base.to_string());
+    }
+
+    let mut suffix = 2;
+    loop {
+        let candidate = format!("{}-{}", base, suffix);
+        if !is_taken(&candidate)? {
+            return Ok(candidate);
+        }
+        suffix += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn create_page_slug(existing: &mut HashSet<String>, title: &str) -> String {
+        let slug = unique_slug::<()>(&slug_from_title(title), |candidate| Ok(existing.contains(candidate))).unwrap();
+        existing.insert(slug.clone());
+        slug
+    }
+
+    #[test]
+    fn test_slugify_normalizes_text() {
+        assert_eq!(slugify("  Hello, World!  "), "hello-world");
+        assert_eq!(slugify("Rust -- and  Yew"), "rust-and-yew");
+        assert_eq!(slugify("Already-a-slug"), "already-a-slug");
+    }
+
+    #[test]
+    fn test_slug_from_title_falls_back_when_empty() {
+        assert_eq!(slug_from_title("!!!"), "page");
+        assert_eq!(slug_from_title(""), "page");
+    }
+
+    #[test]
+    fn test_pages_with_same_title_get_distinct_slugs() {
+        let mut existing = HashSet::new();
+
+        let first = create_page_slug(&mut existing, "About Us");
+        let second = create_page_slug(&mut existing, "About Us");
+        let third = create_page_slug(&mut existing, "About Us");
+
+        assert_eq!(first, "about-us");
+        assert_eq!(second, "about-us-2");
+        assert_eq!(third, "about-us-3");
+    }
+
+    #[test]
+    fn test_unique_slug_skips_taken_suffixes() {
+        let existing: HashSet<String> = ["news", "news-2", "news-3"].iter().map(|s| s.to_string()).collect();
+        let slug = unique_slug::<()>("news", |candidate| Ok(existing.contains(candidate))).unwrap();
+        assert_eq!(slug, "news-4");
+    }
+}
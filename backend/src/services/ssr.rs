@@ -0,0 +1,272 @@
+//! Server-rendered public posts and pages, built with the `ssr` feature
+//!
+//! The public site is a single-page app, so crawlers and link previews that
+//! don't run WebAssembly would only see an empty shell. For `/post/:id` and
+//! `/page/:slug` the server fills the built `index.html` with the page's
+//! title, description, canonical URL, Open Graph tags and JSON-LD, and puts
+//! the header, content and footer in an `#ssr-content` element. The app
+//! removes that element when it mounts and renders the page as usual, so the
+//! SPA works the same with or without this.
+
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use serde_json::Value;
+use crate::services::feed::{excerpt, render_html};
+use crate::services::text_direction::TextDirection;
+
+/// Element holding the server-rendered markup; the app removes it on mount
+pub const SSR_CONTENT_ID: &str = "ssr-content";
+
+/// What a server-rendered page shows
+#[derive(Debug, Clone, PartialEq)]
+pub struct SsrDocument {
+    pub title: String,
+    /// Plain text summary for the description and Open Graph tags
+    pub description: String,
+    /// Absolute URL of the page, when the site URL is known
+    pub canonical_url: Option<String>,
+    /// Open Graph type, `article` or `website`
+    pub og_type: &'static str,
+    /// JSON-LD for the page, if any
+    pub structured_data: Option<Value>,
+    /// Sanitized HTML of the main content
+    pub content_html: String,
+}
+
+/// The site around the page
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SsrSite {
+    pub name: String,
+    /// Inline styles of the active header and footer templates
+    pub header_style: String,
+    pub footer_style: String,
+    /// Header logo image, when the header shows one instead of the title text
+    pub logo: Option<SsrLogo>,
+    pub favicon_url: Option<String>,
+    pub text_direction: TextDirection,
+}
+
+/// The header's logo image
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SsrLogo {
+    pub url: String,
+    /// Inline style from the header template's `logo_size`
+    pub style: String,
+    /// Whether the title text follows the image
+    pub beside_title: bool,
+}
+
+/// Text-bearing blocks of page builder content, as Markdown
+fn page_builder_markdown(components: &[Value]) -> String {
+    let mut blocks = Vec::new();
+    for component in components {
+        let content = component["content"].as_str().unwrap_or_default().trim();
+        if content.is_empty() {
+            continue;
+        }
+        match component["component_type"].as_str() {
+            Some("Heading") => blocks.push(format!("## {}", content)),
+            Some("Subheading") => blocks.push(format!("### {}", content)),
+            Some("Quote") => blocks.push(format!("> {}", content)),
+            Some("Text") | Some("Card") | Some("Hero") => blocks.push(content.to_string()),
+            _ => {}
+        }
+    }
+    blocks.join("\n\n")
+}
+
+/// A page's description and content HTML; page builder content keeps its
+/// text blocks, anything else is read as Markdown
+pub fn page_document(content: &str) -> (String, String) {
+    let markdown = match serde_json::from_str::<Vec<Value>>(content.trim()) {
+        Ok(components) => page_builder_markdown(&components),
+        Err(_) => content.to_string(),
+    };
+    (excerpt(&markdown), render_html(&markdown))
+}
+
+/// JSON for a `<script>` element; `</` can't end it early
+fn script_json(value: &Value) -> String {
+    value.to_string().replace("</", "<\\/")
+}
+
+fn head_tags(document: &SsrDocument, site: &SsrSite) -> String {
+    let attr = |value: &str| encode_double_quoted_attribute(value).into_owned();
+    let mut tags = vec![
+        format!("<meta name=\"description\" content=\"{}\">", attr(&document.description)),
+        format!("<meta property=\"og:title\" content=\"{}\">", attr(&document.title)),
+        format!("<meta property=\"og:description\" content=\"{}\">", attr(&document.description)),
+        format!("<meta property=\"og:type\" content=\"{}\">", document.og_type),
+        format!("<meta property=\"og:site_name\" content=\"{}\">", attr(&site.name)),
+        "<meta name=\"twitter:card\" content=\"summary\">".to_string(),
+    ];
+    if let Some(url) = &site.favicon_url {
+        tags.push(format!("<link rel=\"icon\" href=\"{}\">", attr(url)));
+    }
+    if let Some(url) = &document.canonical_url {
+        tags.push(format!("<link rel=\"canonical\" href=\"{}\">", attr(url)));
+        tags.push(format!("<meta property=\"og:url\" content=\"{}\">", attr(url)));
+    }
+    if let Some(data) = &document.structured_data {
+        tags.push(format!("<script type=\"application/ld+json\">{}</script>", script_json(data)));
+    }
+    tags.join("\n    ")
+}
+
+/// The header's title link: the site name, the logo, or both
+fn site_title(site: &SsrSite) -> String {
+    let attr = |value: &str| encode_double_quoted_attribute(value).into_owned();
+    let name = encode_text(&site.name);
+    match &site.logo {
+        None => format!("<a class=\"site-title\" href=\"/\">{}</a>", name),
+        Some(logo) => format!(
+            "<a class=\"site-title site-title-logo\" href=\"/\"><img class=\"site-logo\" src=\"{}\" alt=\"{}\" style=\"{}\">{}</a>",
+            attr(&logo.url),
+            attr(&site.name),
+            attr(&logo.style),
+            if logo.beside_title { format!("<span>{}</span>", name) } else { String::new() },
+        ),
+    }
+}
+
+fn body_markup(document: &SsrDocument, site: &SsrSite) -> String {
+    let attr = |value: &str| encode_double_quoted_attribute(value).into_owned();
+    format!(
+        "<div id=\"{id}\" class=\"public-site\" dir=\"{dir}\">\
+         <header class=\"site-header\" style=\"{header_style}\">{site_title}</header>\
+         <main class=\"main-content\"><article><h1>{title}</h1>{content}</article></main>\
+         <footer class=\"site-footer\" style=\"{footer_style}\"><p>{name}</p></footer>\
+         </div>",
+        id = SSR_CONTENT_ID,
+        dir = site.text_direction.as_str(),
+        header_style = attr(&site.header_style),
+        site_title = site_title(site),
+        footer_style = attr(&site.footer_style),
+        name = encode_text(&site.name),
+        title = encode_text(&document.title),
+        content = document.content_html,
+    )
+}
+
+/// The shell with the response's CSP nonce in its `csp-nonce` meta tag,
+/// which the app's injected `<style>` elements need
+pub fn with_csp_nonce(shell: &str, csp_nonce: &str) -> String {
+    shell.replacen(
+        "<meta name=\"csp-nonce\" content=\"\">",
+        &format!("<meta name=\"csp-nonce\" content=\"{}\">", encode_double_quoted_attribute(csp_nonce)),
+        1,
+    )
+}
+
+/// Fill the app shell (the built `index.html`) with a page's head tags and
+/// markup. A shell without `<title>`, `</head>` or `<body>` is filled as far
+/// as it can be.
+pub fn render(shell: &str, document: &SsrDocument, site: &SsrSite, csp_nonce: &str) -> String {
+    let mut html = shell.to_string();
+
+    let title = format!("<title>{}</title>", encode_text(&format!("{} | {}", document.title, site.name)));
+    match (html.find("<title>"), html.find("</title>")) {
+        (Some(start), Some(end)) if start < end => html.replace_range(start..end + "</title>".len(), &title),
+        _ => if let Some(at) = html.find("</head>") {
+            html.insert_str(at, &format!("{}\n", title));
+        },
+    }
+    if let Some(at) = html.find("</head>") {
+        html.insert_str(at, &format!("    {}\n", head_tags(document, site)));
+    }
+
+    html = with_csp_nonce(&html, csp_nonce);
+
+    if let Some(start) = html.find("<body") {
+        if let Some(end) = html[start..].find('>') {
+            html.insert_str(start + end + 1, &format!("\n    {}", body_markup(document, site)));
+        }
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SHELL: &str = "<!DOCTYPE html>\n<html>\n<head>\n<title>My Rust CMS</title>\n<meta name=\"csp-nonce\" content=\"\">\n</head>\n<body>\n<div id=\"root\"></div>\n</body>\n</html>";
+
+    fn document() -> SsrDocument {
+        SsrDocument {
+            title: "Rust & <Yew>".to_string(),
+            description: "A \"quoted\" summary".to_string(),
+            canonical_url: Some("https://example.com/post/7".to_string()),
+            og_type: "article",
+            structured_data: Some(json!({"@type": "Article", "headline": "</script><script>alert(1)"})),
+            content_html: "<p>Hello</p>".to_string(),
+        }
+    }
+
+    fn site() -> SsrSite {
+        SsrSite { name: "Blog".to_string(), header_style: "height: 110px".to_string(), ..SsrSite::default() }
+    }
+
+    #[test]
+    fn test_head_is_filled_and_escaped() {
+        let html = render(SHELL, &document(), &site(), "abc123");
+        assert!(html.contains("<title>Rust &amp; &lt;Yew&gt; | Blog</title>"));
+        assert!(!html.contains("My Rust CMS"));
+        assert!(html.contains("<meta name=\"description\" content=\"A &quot;quoted&quot; summary\">"));
+        assert!(html.contains("<link rel=\"canonical\" href=\"https://example.com/post/7\">"));
+        assert!(html.contains("<meta property=\"og:type\" content=\"article\">"));
+        assert!(html.contains("<meta name=\"csp-nonce\" content=\"abc123\">"));
+        assert!(html.contains("<\\/script><script>alert(1)"));
+        assert!(html.find("application/ld+json").unwrap() < html.find("</head>").unwrap());
+    }
+
+    #[test]
+    fn test_body_precedes_the_app_root() {
+        let html = render(SHELL, &document(), &site(), "");
+        let content = html.find("id=\"ssr-content\"").unwrap();
+        assert!(html.find("<body>").unwrap() < content && content < html.find("id=\"root\"").unwrap());
+        assert!(html.contains("<h1>Rust &amp; &lt;Yew&gt;</h1><p>Hello</p>"));
+        assert!(html.contains("<header class=\"site-header\" style=\"height: 110px\">"));
+        assert!(html.contains("class=\"public-site\" dir=\"ltr\">"));
+        let rtl = SsrSite { text_direction: TextDirection::Rtl, ..site() };
+        assert!(render(SHELL, &document(), &rtl, "").contains("class=\"public-site\" dir=\"rtl\">"));
+
+        // No canonical URL without a site URL, and a bare shell still gets its tags
+        let bare = render("<head></head><body></body>", &SsrDocument { canonical_url: None, ..document() }, &site(), "");
+        assert!(!bare.contains("rel=\"canonical\""));
+        assert!(!bare.contains("rel=\"icon\""));
+        assert!(bare.starts_with("<head><title>"));
+        assert!(bare.contains("<body>\n    <div id=\"ssr-content\""));
+    }
+
+    #[test]
+    fn test_logo_and_favicon() {
+        let branded = SsrSite {
+            logo: Some(SsrLogo { url: "/uploads/logo.png".to_string(), style: "font-size: 2rem".to_string(), beside_title: false }),
+            favicon_url: Some("/uploads/icon.png".to_string()),
+            ..site()
+        };
+        let html = render(SHELL, &document(), &branded, "");
+        assert!(html.contains("<link rel=\"icon\" href=\"/uploads/icon.png\">"));
+        assert!(html.contains("<a class=\"site-title site-title-logo\" href=\"/\"><img class=\"site-logo\" src=\"/uploads/logo.png\" alt=\"Blog\" style=\"font-size: 2rem\"></a>"));
+
+        let beside = SsrSite { logo: Some(SsrLogo { beside_title: true, ..branded.logo.clone().unwrap() }), ..branded };
+        assert!(render(SHELL, &document(), &beside, "").contains("style=\"font-size: 2rem\"><span>Blog</span></a>"));
+        assert!(render(SHELL, &document(), &site(), "").contains("<a class=\"site-title\" href=\"/\">Blog</a>"));
+    }
+
+    #[test]
+    fn test_page_content() {
+        let builder = json!([
+            {"component_type": "Heading", "content": "Welcome"},
+            {"component_type": "Text", "content": "Some **bold** text"},
+            {"component_type": "Image", "content": "/uploads/a.png"},
+            {"component_type": "Spacer", "content": ""}
+        ]).to_string();
+        let (description, html) = page_document(&builder);
+        assert_eq!(html, "<h2>Welcome</h2>\n<p>Some <strong>bold</strong> text</p>\n");
+        assert_eq!(description, "Welcome Some bold text");
+
+        let (_, markdown) = page_document("Plain <script>alert(1)</script> *page*");
+        assert_eq!(markdown, "<p>Plain  <em>page</em></p>\n");
+    }
+}
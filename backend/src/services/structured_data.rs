@@ -0,0 +1,194 @@
+//! schema.org structured data for public posts
+//!
+//! A published post carries a JSON-LD `Article` that the public post view
+//! places in a `<script type="application/ld+json">` for rich search
+//! results. Posts have no featured image field, so the first image in the
+//! body stands in for one. The publisher is the site itself, named by the
+//! `site_title` and `site_url` settings.
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde_json::{json, Map, Value};
+use crate::models::{PostWithRelations, Setting};
+use crate::services::canonical_urls::{canonical_path, TrailingSlash};
+use crate::services::feed::excerpt;
+use crate::services::image_alt::first_markdown_image;
+
+/// Longest headline search engines display
+pub const MAX_HEADLINE_CHARS: usize = 110;
+
+/// The site as publisher of its posts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Publisher {
+    pub name: String,
+    /// Absolute site URL without a trailing slash; empty when unset
+    pub url: String,
+    pub trailing_slash: TrailingSlash,
+}
+
+impl Publisher {
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut setting = |key: &str| -> QueryResult<String> {
+            Ok(Setting::find_by_key(conn, key)?
+                .and_then(|s| s.setting_value)
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default())
+        };
+        let name = setting("site_title")?;
+        let url = setting("site_url")?;
+        Ok(Self {
+            name: if name.is_empty() { "My Rust CMS".to_string() } else { name },
+            url: url.trim_end_matches('/').to_string(),
+            trailing_slash: TrailingSlash::load(conn)?,
+        })
+    }
+
+    /// `path` on the site, when the site URL is known
+    pub fn absolute(&self, path: &str) -> Option<String> {
+        if path.starts_with("https://") || path.starts_with("http://") {
+            return Some(path.to_string());
+        }
+        if self.url.is_empty() || !path.starts_with('/') || path.starts_with("//") {
+            return None;
+        }
+        Some(format!("{}{}", self.url, path))
+    }
+}
+
+fn iso_date(at: NaiveDateTime) -> String {
+    at.and_utc().to_rfc3339()
+}
+
+fn headline(title: &str) -> String {
+    let title = title.trim();
+    if title.chars().count() <= MAX_HEADLINE_CHARS {
+        return title.to_string();
+    }
+    let cut: String = title.chars().take(MAX_HEADLINE_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// The JSON-LD `Article` for a post
+pub fn article(post: &PostWithRelations, publisher: &Publisher) -> Value {
+    let mut article = Map::new();
+    article.insert("@context".to_string(), json!("https://schema.org"));
+    article.insert("@type".to_string(), json!("Article"));
+    article.insert("headline".to_string(), json!(headline(&post.title)));
+
+    let description = excerpt(&post.content);
+    if !description.is_empty() {
+        article.insert("description".to_string(), json!(description));
+    }
+    let published = post.published_at.or(post.created_at);
+    if let Some(published) = published {
+        article.insert("datePublished".to_string(), json!(iso_date(published)));
+    }
+    if let Some(modified) = post.updated_at.or(published) {
+        article.insert("dateModified".to_string(), json!(iso_date(modified)));
+    }
+    article.insert("author".to_string(), json!({
+        "@type": "Person",
+        "name": post.author_username.as_deref().unwrap_or("Admin"),
+    }));
+    if let Some(image) = first_markdown_image(&post.content).and_then(|src| publisher.absolute(&src)) {
+        article.insert("image".to_string(), json!([image]));
+    }
+    if let Some(section) = &post.category_name {
+        article.insert("articleSection".to_string(), json!(section));
+    }
+
+    let mut organization = json!({ "@type": "Organization", "name": publisher.name });
+    if !publisher.url.is_empty() {
+        organization["url"] = json!(publisher.url);
+    }
+    article.insert("publisher".to_string(), organization);
+
+    if let Some(url) = publisher.absolute(&canonical_path(&format!("/post/{}", post.id), publisher.trailing_slash)) {
+        article.insert("mainEntityOfPage".to_string(), json!({ "@type": "WebPage", "@id": url }));
+        article.insert("url".to_string(), json!(url));
+    }
+    Value::Object(article)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32) -> Option<NaiveDateTime> {
+        NaiveDate::from_ymd_opt(2024, 5, day).and_then(|d| d.and_hms_opt(12, 0, 0))
+    }
+
+    fn post(content: &str) -> PostWithRelations {
+        PostWithRelations {
+            id: 7,
+            title: "Hello World".to_string(),
+            content: content.to_string(),
+            category_id: Some(1),
+            user_id: Some(1),
+            created_at: at(1),
+            updated_at: at(3),
+            status: "published".to_string(),
+            published_at: at(2),
+            category_name: Some("General".to_string()),
+            author_username: Some("alice".to_string()),
+        }
+    }
+
+    fn publisher() -> Publisher {
+        Publisher {
+            name: "Rust Notes".to_string(),
+            url: "https://example.com".to_string(),
+            trailing_slash: TrailingSlash::Remove,
+        }
+    }
+
+    #[test]
+    fn test_article_structure() {
+        let value = article(&post("Intro text.\n\n![Diagram](/uploads/diagram.png)"), &publisher());
+        assert_eq!(value, json!({
+            "@context": "https://schema.org",
+            "@type": "Article",
+            "headline": "Hello World",
+            "description": "Intro text. Diagram",
+            "datePublished": "2024-05-02T12:00:00+00:00",
+            "dateModified": "2024-05-03T12:00:00+00:00",
+            "author": { "@type": "Person", "name": "alice" },
+            "image": ["https://example.com/uploads/diagram.png"],
+            "articleSection": "General",
+            "publisher": { "@type": "Organization", "name": "Rust Notes", "url": "https://example.com" },
+            "mainEntityOfPage": { "@type": "WebPage", "@id": "https://example.com/post/7" },
+            "url": "https://example.com/post/7",
+        }));
+        // Survives a round trip through the text placed in the script tag
+        let text = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&text).unwrap(), value);
+    }
+
+    #[test]
+    fn test_optional_fields_are_omitted() {
+        let mut draftish = post("Just words");
+        draftish.published_at = None;
+        draftish.updated_at = None;
+        draftish.category_name = None;
+        let value = article(&draftish, &Publisher { url: String::new(), ..publisher() });
+
+        assert_eq!(value["datePublished"], "2024-05-01T12:00:00+00:00");
+        assert_eq!(value["dateModified"], value["datePublished"]);
+        assert_eq!(value["publisher"], json!({ "@type": "Organization", "name": "Rust Notes" }));
+        for key in ["image", "articleSection", "mainEntityOfPage", "url"] {
+            assert!(value.get(key).is_none(), "{} should be omitted", key);
+        }
+    }
+
+    #[test]
+    fn test_image_urls_and_headline_length() {
+        let mut long = post("<p><img src=\"https://cdn.example.net/a.jpg\" alt=\"A\"></p> ![B](/b.png)");
+        long.title = "x".repeat(200);
+        let value = article(&long, &Publisher { trailing_slash: TrailingSlash::Add, ..publisher() });
+
+        assert_eq!(value["image"], json!(["https://cdn.example.net/a.jpg"]));
+        assert_eq!(value["headline"].as_str().unwrap().chars().count(), MAX_HEADLINE_CHARS);
+        assert_eq!(value["url"], "https://example.com/post/7/");
+    }
+}
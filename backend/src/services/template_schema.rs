@@ -0,0 +1,330 @@
+//! Allowed `template_data` keys per component type
+//!
+//! `template_data` is free-form JSON read by the public layout, so a
+//! misspelled key (`backgroud`) or a value of the wrong type silently does
+//! nothing. Each known component type lists its keys and their value kinds;
+//! saving a template checks its data against that list. A wrong value kind
+//! is always refused. Unknown keys, and types without a schema, are either
+//! reported back as warnings or refused, depending on
+//! `component_template_unknown_keys`. The schema is served to the template
+//! editor so it can offer the right fields.
+
+use std::collections::BTreeMap;
+use diesel::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const UNKNOWN_KEYS_SETTING: &str = "component_template_unknown_keys";
+
+/// Kind of value a key takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    /// A string, usually a CSS value
+    Text,
+    Boolean,
+    Number,
+    /// A number or a CSS string, e.g. `z_index`
+    NumberOrText,
+    /// A list of strings
+    List,
+}
+
+impl FieldKind {
+    fn accepts(self, value: &Value) -> bool {
+        match self {
+            FieldKind::Text => value.is_string(),
+            FieldKind::Boolean => value.is_boolean(),
+            FieldKind::Number => value.is_number(),
+            FieldKind::NumberOrText => value.is_number() || value.is_string(),
+            FieldKind::List => value.as_array().is_some_and(|items| items.iter().all(Value::is_string)),
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            FieldKind::Text => "a string",
+            FieldKind::Boolean => "true or false",
+            FieldKind::Number => "a number",
+            FieldKind::NumberOrText => "a number or a string",
+            FieldKind::List => "a list of strings",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Field {
+    pub key: &'static str,
+    pub kind: FieldKind,
+}
+
+const fn field(key: &'static str, kind: FieldKind) -> Field {
+    Field { key, kind }
+}
+
+use FieldKind::{Boolean, List, Number, NumberOrText, Text};
+
+/// Box styling every visual component accepts
+const COMMON_FIELDS: &[Field] = &[
+    field("background", Text),
+    field("background_color", Text),
+    field("text_color", Text),
+    field("position", Text),
+    field("height", Text),
+    field("padding", Text),
+    field("margin", Text),
+    field("border", Text),
+    field("box_shadow", Text),
+    field("z_index", NumberOrText),
+];
+
+const HEADER_FIELDS: &[Field] = &[
+    field("text_hover_color", Text),
+    field("nav_hover_color", Text),
+    field("nav_underline_color", Text),
+    field("nav_underline_thickness", Text),
+    field("nav_underline_animation", Text),
+    field("navigation_layout", Text),
+    field("container_width", Text),
+    field("logo_type", Text),
+    field("logo_size", Text),
+    field("mobile_menu", Text),
+    field("mobile_breakpoint", Text),
+    field("hover_effect", Text),
+    field("neon_glow", Text),
+    field("button_primary_bg", Text),
+    field("button_primary_text", Text),
+    field("button_primary_hover_bg", Text),
+    field("badge_bg", Text),
+    field("badge_text", Text),
+];
+
+const FOOTER_FIELDS: &[Field] = &[
+    field("style", Text),
+    field("text_muted", Text),
+    field("navigation_layout", Text),
+    field("container_width", Text),
+    field("copyright_position", Text),
+    field("copyright_text", Text),
+    field("additional_text", Text),
+    field("link_spacing", Text),
+    field("separator", Text),
+    field("text_glow", Text),
+];
+
+const SIDEBAR_FIELDS: &[Field] = &[
+    field("width", Text),
+    field("sticky", Boolean),
+    field("mobile_display", Text),
+    field("mobile_breakpoint", Text),
+    field("sections", List),
+    field("border_image", Text),
+];
+
+const MODAL_FIELDS: &[Field] = &[
+    field("backdrop", Text),
+    field("backdrop_opacity", Number),
+    field("backdrop_gradient", Text),
+    field("animation", Text),
+    field("max_width", Text),
+    field("closeOnOutside", Boolean),
+];
+
+/// Post card styling, shared by the posts list and the main container
+const CARD_FIELDS: &[Field] = &[
+    field("card_background", Text),
+    field("card_radius", Text),
+    field("card_shadow", Text),
+    field("title_color", Text),
+    field("meta_color", Text),
+    field("link_color", Text),
+    field("grid_gap", Text),
+];
+
+const MAIN_CONTAINER_FIELDS: &[Field] = &[
+    field("width_type", Text),
+    field("max_width", Text),
+    field("grid_system", Text),
+    field("responsive", Boolean),
+    field("background_type", Text),
+    field("gradient_from", Text),
+    field("gradient_to", Text),
+    field("gradient_angle", Text),
+    field("gradient_animate", Boolean),
+    field("background_animation", Text),
+];
+
+const COMMENTS_FIELDS: &[Field] = &[
+    field("enabled", Boolean),
+    field("per_page", Number),
+    field("avatar_size", Number),
+    field("show_auth_prompt", Boolean),
+    field("moderation", Boolean),
+];
+
+/// Field groups making up each component type's schema
+fn field_groups(component_type: &str) -> Option<&'static [&'static [Field]]> {
+    Some(match component_type {
+        "header" => &[COMMON_FIELDS, HEADER_FIELDS],
+        "footer" => &[COMMON_FIELDS, FOOTER_FIELDS],
+        "sidebar" => &[COMMON_FIELDS, SIDEBAR_FIELDS],
+        "modal" => &[COMMON_FIELDS, MODAL_FIELDS],
+        "main_container" => &[COMMON_FIELDS, MAIN_CONTAINER_FIELDS, CARD_FIELDS],
+        "posts_list" => &[COMMON_FIELDS, CARD_FIELDS],
+        "hero" => &[COMMON_FIELDS],
+        // Seeded with a capital letter by its migration
+        "Comments" => &[COMMENTS_FIELDS],
+        _ => return None,
+    })
+}
+
+pub const COMPONENT_TYPES: [&str; 8] = ["header", "footer", "sidebar", "modal", "main_container", "posts_list", "hero", "Comments"];
+
+/// Keys a component type accepts, or `None` if it has no schema
+pub fn fields_for(component_type: &str) -> Option<Vec<Field>> {
+    field_groups(component_type).map(|groups| groups.iter().flat_map(|group| group.iter().copied()).collect())
+}
+
+/// The whole schema, keyed by component type
+pub fn schema() -> BTreeMap<&'static str, Vec<Field>> {
+    COMPONENT_TYPES.iter()
+        .filter_map(|component_type| Some((*component_type, fields_for(component_type)?)))
+        .collect()
+}
+
+/// What happens to keys a component type doesn't know
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownKeys {
+    /// Save the template and report the keys back
+    #[default]
+    Warn,
+    Reject,
+}
+
+impl UnknownKeys {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim().to_lowercase().as_str() {
+            "warn" => Ok(Self::Warn),
+            "reject" => Ok(Self::Reject),
+            _ => Err(AppError::ValidationError(format!("{} must be warn or reject", UNKNOWN_KEYS_SETTING))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Warn => "warn",
+            Self::Reject => "reject",
+        }
+    }
+
+    /// Current policy from settings; a missing or invalid value warns
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let Some(value) = Setting::find_by_key(conn, UNKNOWN_KEYS_SETTING)?.and_then(|s| s.setting_value) else {
+            return Ok(Self::default());
+        };
+        Ok(Self::parse(&value).unwrap_or_else(|e| {
+            warn!("Ignoring setting {}: {}", UNKNOWN_KEYS_SETTING, e);
+            Self::default()
+        }))
+    }
+}
+
+/// Check `template_data` against its component type's schema, returning
+/// warnings for unknown keys when they are allowed through
+pub fn validate_template_data(component_type: &str, data: &Value, unknown_keys: UnknownKeys) -> Result<Vec<String>, AppError> {
+    let Some(object) = data.as_object() else {
+        return Err(AppError::ValidationError("template_data must be a JSON object".to_string()));
+    };
+    let Some(fields) = fields_for(component_type) else {
+        let message = format!("Component type '{}' has no template data schema", component_type);
+        return match unknown_keys {
+            UnknownKeys::Reject => Err(AppError::ValidationError(message)),
+            UnknownKeys::Warn => Ok(vec![message]),
+        };
+    };
+
+    let mut unknown = Vec::new();
+    for (key, value) in object {
+        match fields.iter().find(|f| f.key == key) {
+            Some(f) if !f.kind.accepts(value) => {
+                return Err(AppError::ValidationError(format!(
+                    "template_data.{} must be {}", key, f.kind.describe()
+                )));
+            }
+            Some(_) => {}
+            None => unknown.push(key.as_str()),
+        }
+    }
+    if unknown.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let message = format!("Unknown {} template_data keys: {}", component_type, unknown.join(", "));
+    match unknown_keys {
+        UnknownKeys::Reject => Err(AppError::ValidationError(message)),
+        UnknownKeys::Warn => Ok(vec![message]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_template_data() {
+        let header = json!({"background": "#000", "height": "88px", "z_index": 10, "nav_hover_color": "#a5b4fc"});
+        assert!(validate_template_data("header", &header, UnknownKeys::Reject).unwrap().is_empty());
+
+        let sidebar = json!({"sticky": true, "sections": ["navigation", "archives"], "z_index": "auto"});
+        assert!(validate_template_data("sidebar", &sidebar, UnknownKeys::Reject).unwrap().is_empty());
+        assert!(validate_template_data("footer", &json!({}), UnknownKeys::Reject).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_wrong_value_kinds_are_refused() {
+        for (component_type, data) in [
+            ("sidebar", json!({"sticky": "yes"})),
+            ("header", json!({"height": 80})),
+            ("sidebar", json!({"sections": ["navigation", 3]})),
+            ("Comments", json!({"per_page": "20"})),
+        ] {
+            assert!(validate_template_data(component_type, &data, UnknownKeys::Warn).is_err(), "{}", data);
+        }
+        assert!(validate_template_data("header", &json!(["background"]), UnknownKeys::Warn).is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_warn_or_reject() {
+        let typo = json!({"backgroud": "#000", "padding": "1rem"});
+        let warnings = validate_template_data("header", &typo, UnknownKeys::Warn).unwrap();
+        assert_eq!(warnings, vec!["Unknown header template_data keys: backgroud".to_string()]);
+        assert!(matches!(
+            validate_template_data("header", &typo, UnknownKeys::Reject),
+            Err(AppError::ValidationError(message)) if message.contains("backgroud")
+        ));
+
+        assert_eq!(validate_template_data("banner", &json!({}), UnknownKeys::Warn).unwrap().len(), 1);
+        assert!(validate_template_data("banner", &json!({}), UnknownKeys::Reject).is_err());
+    }
+
+    #[test]
+    fn test_built_in_presets_match_the_schema() {
+        for (name, layout) in crate::services::theme_presets::built_in_presets() {
+            for component in layout["component_templates"].as_array().into_iter().flatten() {
+                let component_type = component["component_type"].as_str().unwrap();
+                assert_eq!(
+                    validate_template_data(component_type, &component["template_data"], UnknownKeys::Reject).ok(),
+                    Some(Vec::new()),
+                    "{} preset, {} template", name, component_type
+                );
+            }
+        }
+        assert_eq!(UnknownKeys::parse(" Reject ").unwrap(), UnknownKeys::Reject);
+        assert!(UnknownKeys::parse("ignore").is_err());
+    }
+}
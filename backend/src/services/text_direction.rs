@@ -0,0 +1,70 @@
+//! Reading direction of the site
+//!
+//! `text_direction` sets the `dir` attribute on the public and admin roots:
+//! `ltr`, `rtl` for Arabic, Hebrew and other right-to-left content, or `auto`
+//! to let the browser pick from the first strongly directional text.
+
+use diesel::prelude::*;
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const TEXT_DIRECTION_SETTING: &str = "text_direction";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    #[default]
+    Ltr,
+    Rtl,
+    Auto,
+}
+
+impl TextDirection {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "ltr" => Ok(Self::Ltr),
+            "rtl" => Ok(Self::Rtl),
+            "auto" => Ok(Self::Auto),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown text direction '{}'; use ltr, rtl or auto", other
+            ))),
+        }
+    }
+
+    /// The `dir` attribute value
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ltr => "ltr",
+            Self::Rtl => "rtl",
+            Self::Auto => "auto",
+        }
+    }
+
+    /// The site's direction; a missing or invalid setting means left to right
+    #[cfg_attr(not(feature = "ssr"), allow(dead_code))]
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let value = Setting::find_by_key(conn, TEXT_DIRECTION_SETTING)?.and_then(|s| s.setting_value);
+        Ok(match value.as_deref().map(Self::parse) {
+            Some(Ok(direction)) => direction,
+            Some(Err(e)) => {
+                warn!("Ignoring setting {}: {}", TEXT_DIRECTION_SETTING, e);
+                Self::default()
+            }
+            None => Self::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(TextDirection::parse("rtl").unwrap(), TextDirection::Rtl);
+        assert_eq!(TextDirection::parse(" AUTO ").unwrap().as_str(), "auto");
+        assert_eq!(TextDirection::parse("ltr").unwrap(), TextDirection::default());
+        assert!(TextDirection::parse("right").is_err());
+        assert!(TextDirection::parse("").is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! Built-in master templates ("theme presets")
+//!
+//! Each preset is a `templates` row whose layout holds `menu_areas`,
+//! `component_templates` and `container_settings`, in the shape
+//! `apply_template_layout` writes to the live configuration. Presets are
+//! seeded by name at startup, so new ones appear on existing installs
+//! without touching templates users already have.
+
+/// Name of the preset that "Reset to Default" restores
+pub const DEFAULT_TEMPLATE_NAME: &str = "Default";
+
+/// All built-in presets as (name, layout), in the order they are seeded
+pub fn built_in_presets() -> Vec<(&'static str, serde_json::Value)> {
+    vec![
+        (DEFAULT_TEMPLATE_NAME, default_layout()),
+        ("Acid Mode", acid_layout()),
+        ("Minimal", minimal_layout()),
+        ("Dark", dark_layout()),
+        ("Magazine", magazine_layout()),
+    ]
+}
+
+/// Light theme matching the stock component template defaults
+fn default_layout() -> serde_json::Value {
+    serde_json::json!({
+        "menu_areas": [
+            {"area_name": "header", "display_name": "Header Menu", "is_active": true, "settings": {"layout": "horizontal", "background": "#ffffff"}},
+            {"area_name": "footer", "display_name": "Footer Menu", "is_active": true, "settings": {"style": "simple", "background": "#111111", "text_color": "#cccccc"}},
+            {"area_name": "floating", "display_name": "Floating Menu", "is_active": false, "settings": {"position": "fixed-right"}}
+        ],
+        "component_templates": [
+            {"component_type": "header", "template_data": {"position": "sticky", "height": "110px", "background_color": "#000000", "text_color": "#ffffff", "text_hover_color": "#f7fafc", "nav_hover_color": "#f7fafc", "nav_underline_color": "#ffffff", "nav_underline_thickness": "2px", "nav_underline_animation": "none", "navigation_layout": "horizontal", "logo_type": "text", "logo_size": "1.85rem", "mobile_menu": "hamburger", "mobile_breakpoint": "768px"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "contained", "max_width": "1200px", "is_active": true},
+            {"component_type": "footer", "template_data": {"style": "simple", "padding": "3rem 0", "navigation_layout": "horizontal", "copyright_position": "center", "copyright_text": "© {year} {site_title}", "additional_text": "Built with Rust & Yew"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "full", "is_active": true},
+            {"component_type": "sidebar", "template_data": {"position": "right", "width": "300px", "sticky": true, "mobile_display": "hidden", "mobile_breakpoint": "768px", "sections": ["navigation", "recent_posts"]}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "fixed", "max_width": "300px", "is_active": false},
+            {"component_type": "modal", "template_data": {"backdrop": "blur", "position": "center", "animation": "fade", "max_width": "600px", "z_index": 1000}, "breakpoints": {"mobile": "95%", "tablet": "80%", "desktop": "600px"}, "width_setting": "responsive", "max_width": "600px", "is_active": true},
+            {"component_type": "main_container", "template_data": {"width_type": "fixed", "max_width": "1200px", "padding": "1rem", "grid_system": "css_grid", "responsive": true}, "breakpoints": {"mobile": "100%", "tablet": "90%", "desktop": "1200px"}, "width_setting": "fixed", "max_width": "1200px", "is_active": true}
+        ],
+        "container_settings": {
+            "background_type": "none",
+            "background_color": "#ffffff",
+            "gradient_from": "#ffffff",
+            "gradient_to": "#ffffff",
+            "gradient_angle": "180deg",
+            "overlay_color": "#000000",
+            "overlay_opacity": "0.3",
+            "border_radius": "0px",
+            "border_width": "0px",
+            "border_color": "#000000",
+            "box_shadow": "none",
+            "animation": "none",
+            "width_type": "fixed",
+            "max_width": "1200px",
+            "horizontal_padding": "1rem"
+        }
+    })
+}
+
+/// Neon gradients and animations
+fn acid_layout() -> serde_json::Value {
+    serde_json::json!({
+        "menu_areas": [
+            {"area_name": "header", "display_name": "Header Menu", "is_active": true, "settings": {"layout": "centered", "background": "linear-gradient(135deg, #ff00cc 0%, #3333ff 100%)", "text_color": "#ffffff"}},
+            {"area_name": "footer", "display_name": "Footer Menu", "is_active": true, "settings": {"style": "multi-column", "background": "linear-gradient(180deg, #111111 0%, #000000 100%)", "text_color": "#66ffcc"}},
+            {"area_name": "floating", "display_name": "Floating Menu", "is_active": true, "settings": {"position": "fixed-right"}}
+        ],
+        "component_templates": [
+            {"component_type": "header", "template_data": {"position": "sticky", "height": "88px", "background": "linear-gradient(90deg, #ff0066, #ffcc00, #33ff99)", "navigation_layout": "split", "logo_type": "icon", "logo_size": "2rem", "hover_effect": "scale", "neon_glow": "0 0 20px #33ff99", "text_color": "#ffffff", "text_hover_color": "#e2e8f0", "nav_hover_color": "#a5b4fc", "nav_underline_color": "linear-gradient(90deg, #ff0066, #ffcc00, #33ff99)", "nav_underline_thickness": "3px", "nav_underline_animation": "underlineShimmer 3s linear infinite"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1400px"}, "width_setting": "fluid", "is_active": true},
+            {"component_type": "footer", "template_data": {"style": "multi-column", "padding": "4rem 0", "navigation_layout": "grid", "link_spacing": "1rem", "separator": "dot", "background": "linear-gradient(45deg, #111111, #222244)", "text_glow": "0 0 10px #66ffcc", "text_color": "#ffffff"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1400px"}, "width_setting": "full", "is_active": true},
+            {"component_type": "sidebar", "template_data": {"position": "both", "width": "320px", "sticky": true, "mobile_display": "drawer", "sections": ["navigation", "recent_posts", "categories", "archives"], "background": "linear-gradient(180deg, rgba(255,0,102,0.1), rgba(51,255,153,0.1))", "border": "2px solid", "border_image": "linear-gradient(90deg, #ff0066, #33ff99) 1"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1400px"}, "width_setting": "fixed", "max_width": "320px", "is_active": true},
+            {"component_type": "modal", "template_data": {"backdrop": "blur", "position": "center", "animation": "scale", "max_width": "800px", "z_index": 1200, "backdrop_opacity": 70, "backdrop_gradient": "radial-gradient(circle at center, rgba(255,0,102,0.4), rgba(0,0,0,0.8))"}, "breakpoints": {"mobile": "95%", "tablet": "85%", "desktop": "800px"}, "width_setting": "responsive", "max_width": "800px", "is_active": true},
+            {"component_type": "main_container", "template_data": {"width_type": "hybrid", "max_width": "1280px", "padding": "1.5rem", "responsive": true, "background_type": "gradient", "gradient_from": "#0f0f3d", "gradient_to": "#000000", "gradient_angle": "135deg", "gradient_animate": true, "background_animation": "gradientShift 20s ease infinite", "card_background": "#0f1629", "grid_gap": "24px", "card_radius": "12px", "card_shadow": "0 2px 12px rgba(0,0,0,0.35)", "title_color": "#e2e8f0", "meta_color": "#94a3b8", "link_color": "#22d3ee"}, "breakpoints": {"mobile": "100%", "tablet": "95%", "desktop": "1280px"}, "width_setting": "hybrid", "max_width": "1280px", "is_active": true}
+        ],
+        "container_settings": {
+            "background_type": "gradient",
+            "background_color": "#0b0b0b",
+            "gradient_from": "#0f0f3d",
+            "gradient_to": "#000000",
+            "gradient_angle": "135deg",
+            "overlay_color": "#00ffcc",
+            "overlay_opacity": "0.12",
+            "border_radius": "16px",
+            "border_width": "2px",
+            "border_color": "#33ff99",
+            "box_shadow": "0 10px 40px rgba(51,255,153,0.25)",
+            "animation": "fade-in",
+            "width_type": "hybrid",
+            "max_width": "1280px",
+            "horizontal_padding": "2rem"
+        }
+    })
+}
+
+/// Narrow single column with plain typography and no chrome
+fn minimal_layout() -> serde_json::Value {
+    serde_json::json!({
+        "menu_areas": [
+            {"area_name": "header", "display_name": "Header Menu", "is_active": true, "settings": {"layout": "horizontal", "background": "#ffffff", "text_color": "#111111"}},
+            {"area_name": "footer", "display_name": "Footer Menu", "is_active": true, "settings": {"style": "simple", "background": "#ffffff", "text_color": "#6b7280"}},
+            {"area_name": "floating", "display_name": "Floating Menu", "is_active": false, "settings": {"position": "fixed-right"}}
+        ],
+        "component_templates": [
+            {"component_type": "header", "template_data": {"position": "static", "height": "72px", "background_color": "#ffffff", "text_color": "#111111", "text_hover_color": "#4b5563", "nav_hover_color": "#4b5563", "nav_underline_color": "#111111", "nav_underline_thickness": "1px", "nav_underline_animation": "none", "navigation_layout": "horizontal", "logo_type": "text", "logo_size": "1.4rem", "mobile_menu": "hamburger", "mobile_breakpoint": "768px"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "contained", "max_width": "760px", "is_active": true},
+            {"component_type": "footer", "template_data": {"style": "simple", "padding": "2rem 0", "navigation_layout": "horizontal", "copyright_position": "center", "copyright_text": "© {year} {site_title}", "additional_text": "", "text_color": "#6b7280"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "contained", "max_width": "760px", "is_active": true},
+            {"component_type": "sidebar", "template_data": {"position": "right", "width": "260px", "sticky": false, "mobile_display": "hidden", "mobile_breakpoint": "768px", "sections": ["navigation"]}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "fixed", "max_width": "260px", "is_active": false},
+            {"component_type": "modal", "template_data": {"backdrop": "dim", "position": "center", "animation": "fade", "max_width": "560px", "z_index": 1000}, "breakpoints": {"mobile": "95%", "tablet": "80%", "desktop": "560px"}, "width_setting": "responsive", "max_width": "560px", "is_active": true},
+            {"component_type": "main_container", "template_data": {"width_type": "fixed", "max_width": "760px", "padding": "1.5rem", "grid_system": "flexbox", "responsive": true}, "breakpoints": {"mobile": "100%", "tablet": "90%", "desktop": "760px"}, "width_setting": "fixed", "max_width": "760px", "is_active": true}
+        ],
+        "container_settings": {
+            "background_type": "color",
+            "background_color": "#ffffff",
+            "gradient_from": "#ffffff",
+            "gradient_to": "#ffffff",
+            "gradient_angle": "180deg",
+            "overlay_color": "#000000",
+            "overlay_opacity": "0",
+            "border_radius": "0px",
+            "border_width": "0px",
+            "border_color": "#e5e7eb",
+            "box_shadow": "none",
+            "animation": "none",
+            "width_type": "fixed",
+            "max_width": "760px",
+            "horizontal_padding": "1.5rem"
+        }
+    })
+}
+
+/// Dark slate backgrounds with light text
+fn dark_layout() -> serde_json::Value {
+    serde_json::json!({
+        "menu_areas": [
+            {"area_name": "header", "display_name": "Header Menu", "is_active": true, "settings": {"layout": "horizontal", "background": "#0f172a", "text_color": "#e2e8f0"}},
+            {"area_name": "footer", "display_name": "Footer Menu", "is_active": true, "settings": {"style": "simple", "background": "#020617", "text_color": "#94a3b8"}},
+            {"area_name": "floating", "display_name": "Floating Menu", "is_active": false, "settings": {"position": "fixed-right"}}
+        ],
+        "component_templates": [
+            {"component_type": "header", "template_data": {"position": "sticky", "height": "80px", "background_color": "#0f172a", "text_color": "#e2e8f0", "text_hover_color": "#ffffff", "nav_hover_color": "#38bdf8", "nav_underline_color": "#38bdf8", "nav_underline_thickness": "2px", "nav_underline_animation": "none", "navigation_layout": "horizontal", "logo_type": "text", "logo_size": "1.6rem", "mobile_menu": "hamburger", "mobile_breakpoint": "768px"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "contained", "max_width": "1200px", "is_active": true},
+            {"component_type": "footer", "template_data": {"style": "simple", "padding": "3rem 0", "navigation_layout": "horizontal", "copyright_position": "center", "copyright_text": "© {year} {site_title}", "additional_text": "Built with Rust & Yew", "background": "#020617", "text_color": "#94a3b8"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "full", "is_active": true},
+            {"component_type": "sidebar", "template_data": {"position": "right", "width": "300px", "sticky": true, "mobile_display": "hidden", "mobile_breakpoint": "768px", "sections": ["navigation", "recent_posts"], "background": "#1e293b"}, "breakpoints": {"mobile": "768px", "tablet": "1024px", "desktop": "1200px"}, "width_setting": "fixed", "max_width": "300px", "is_active": false},
+            {"component_type": "modal", "template_data": {"backdrop": "blur", "position": "center", "animation": "fade", "max_width": "600px", "z_index": 1000, "backdrop_opacity": 70}, "breakpoints": {"mobile": "95%", "tablet": "80%", "desktop": "600px"}, "width_setting": "responsive", "max_width": "600px", "is_active": true},
+            {"component_type": "main_container", "template_data": {"width_type": "fixed", "max_width": "1200px", "padding": "1.5rem", "grid_system": "css_grid", "responsive": true, "card_background": "#1e293b", "grid_gap": "24px", "card_radius": "10px", "card_shadow": "0 2px 10px rgba(0,0,0,0.4)", "title_color": "#f1f5f9", "meta_color": "#94a3b8", "link_color": "#38bdf8"}, "breakpoints": {"mobile": "100%", "tablet": "90%", "desktop": "1200px"}, "width_setting": "fixed", "max_width": "1200px", "is_active": true}
+        ],
+        "container_settings": {
+            "background_type": "color",
+            "background_color": "#0f172a",
+            "gradient_from": "#0f172a",
+            "gradient_to": "#020617",
+            "gradient_angle": "180deg",
+            "overlay_color": "#000000",
+            "overlay_opacity": "0",
+            "border_radius": "0px",
+            "border_width": "0px",
+            "border_color": "#1e293b",
+            "box_shadow": "none",
+            "animation": "none",
+            "width_type": "fixed",
+            "max_width": "1200px",
+            "horizontal_padding": "1.5rem"
+        }
+    })
+}
+
+/// Wide layout with a centered masthead and an active sidebar
+fn magazine_layout() -> serde_json::Value {
+    serde_json::json!({
+        "menu_areas": [
+            {"area_name": "header", "display_name": "Header Menu", "is_active": true, "settings": {"layout": "centered", "background": "#ffffff", "text_color": "#111111"}},
+            {"area_name": "footer", "display_name": "Footer Menu", "is_active": true, "settings": {"style": "multi-column", "background": "#1a1a1a", "text_color": "#d4d4d4"}},
+            {"area_name": "floating", "display_name": "Floating Menu", "is_active": false, "settings": {"position": "fixed-right"}}
+        ],
+        "component_templates": [
+            {"component_type": "header", "template_data": {"position": "sticky", "height": "120px", "background_color": "#ffffff", "text_color": "#111111", "text_hover_color": "#b91c1c", "nav_hover_color": "#b91c1c", "nav_underline_color": "#b91c1c", "nav_underline_thickness": "3px", "nav_underline_animation": "none", "navigation_layout": "centered", "logo_type": "text", "logo_size": "2.4rem", "mobile_menu": "hamburger", "mobile_breakpoint": "900px"}, "breakpoints": {"mobile": "900px", "tablet": "1100px", "desktop": "1320px"}, "width_setting": "contained", "max_width": "1320px", "is_active": true},
+            {"component_type": "footer", "template_data": {"style": "multi-column", "padding": "4rem 0", "navigation_layout": "grid", "link_spacing": "0.75rem", "copyright_position": "left", "copyright_text": "© {year} {site_title}", "additional_text": "Built with Rust & Yew", "background": "#1a1a1a", "text_color": "#d4d4d4"}, "breakpoints": {"mobile": "900px", "tablet": "1100px", "desktop": "1320px"}, "width_setting": "full", "is_active": true},
+            {"component_type": "sidebar", "template_data": {"position": "right", "width": "320px", "sticky": true, "mobile_display": "bottom", "mobile_breakpoint": "900px", "sections": ["recent_posts", "categories", "archives"]}, "breakpoints": {"mobile": "900px", "tablet": "1100px", "desktop": "1320px"}, "width_setting": "fixed", "max_width": "320px", "is_active": true},
+            {"component_type": "modal", "template_data": {"backdrop": "dim", "position": "center", "animation": "fade", "max_width": "720px", "z_index": 1000}, "breakpoints": {"mobile": "95%", "tablet": "85%", "desktop": "720px"}, "width_setting": "responsive", "max_width": "720px", "is_active": true},
+            {"component_type": "main_container", "template_data": {"width_type": "fixed", "max_width": "1320px", "padding": "2rem", "grid_system": "css_grid", "responsive": true, "card_background": "#ffffff", "grid_gap": "32px", "card_radius": "0px", "card_shadow": "none", "title_color": "#111111", "meta_color": "#6b7280", "link_color": "#b91c1c"}, "breakpoints": {"mobile": "100%", "tablet": "95%", "desktop": "1320px"}, "width_setting": "fixed", "max_width": "1320px", "is_active": true}
+        ],
+        "container_settings": {
+            "background_type": "color",
+            "background_color": "#faf9f7",
+            "gradient_from": "#faf9f7",
+            "gradient_to": "#faf9f7",
+            "gradient_angle": "180deg",
+            "overlay_color": "#000000",
+            "overlay_opacity": "0",
+            "border_radius": "0px",
+            "border_width": "0px",
+            "border_color": "#e5e5e5",
+            "box_shadow": "none",
+            "animation": "none",
+            "width_type": "fixed",
+            "max_width": "1320px",
+            "horizontal_padding": "2rem"
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn keys(value: &serde_json::Value, list: &str, key: &str) -> BTreeSet<String> {
+        value[list].as_array().unwrap().iter()
+            .map(|item| item[key].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_preset_names_are_unique() {
+        let names: BTreeSet<_> = built_in_presets().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names.len(), built_in_presets().len());
+        assert!(names.contains(DEFAULT_TEMPLATE_NAME));
+    }
+
+    #[test]
+    fn test_presets_cover_the_same_areas_components_and_settings() {
+        // Applying any preset must overwrite everything the previous one set
+        let default = default_layout();
+        let container_keys: BTreeSet<_> = default["container_settings"].as_object().unwrap().keys().cloned().collect();
+        for (name, layout) in built_in_presets() {
+            assert_eq!(keys(&layout, "menu_areas", "area_name"), keys(&default, "menu_areas", "area_name"), "{}", name);
+            assert_eq!(keys(&layout, "component_templates", "component_type"), keys(&default, "component_templates", "component_type"), "{}", name);
+            let preset_keys: BTreeSet<_> = layout["container_settings"].as_object().unwrap().keys().cloned().collect();
+            assert_eq!(preset_keys, container_keys, "{}", name);
+        }
+    }
+}
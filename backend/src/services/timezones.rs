@@ -0,0 +1,87 @@
+//! Site time zone for displaying timestamps
+//!
+//! Timestamps are always stored and sent over the API in UTC. The `timezone`
+//! setting only changes how they are shown: an IANA zone name renders every
+//! date in that zone, and `viewer` renders in each visitor's own zone (which
+//! only the browser knows, so server-rendered strings fall back to UTC).
+//! The frontend's `local_time` module uses the same formats.
+
+use chrono::NaiveDateTime;
+use chrono_tz::Tz;
+use diesel::prelude::*;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const TIMEZONE_SETTING: &str = "timezone";
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+/// Setting value meaning "each visitor's browser time zone"
+pub const VIEWER_TIMEZONE: &str = "viewer";
+
+/// Display format for a date and time, e.g. "Aug 10, 2025 at 2:30 PM CEST"
+pub const DATETIME_FORMAT: &str = "%b %-d, %Y at %-I:%M %p %Z";
+
+/// Validate a `timezone` setting value, returning the value to store
+pub fn validate_timezone_setting(value: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value == VIEWER_TIMEZONE {
+        return Ok(value.to_string());
+    }
+    value.parse::<Tz>()
+        .map(|tz| tz.name().to_string())
+        .map_err(|_| AppError::ValidationError(format!(
+            "Unknown time zone '{}'; use an IANA name such as Europe/Berlin, or '{}'",
+            value, VIEWER_TIMEZONE
+        )))
+}
+
+/// Zone to render server-side strings in; `viewer` and unknown names use UTC
+pub fn display_zone(setting: &str) -> Tz {
+    setting.parse::<Tz>().unwrap_or(Tz::UTC)
+}
+
+/// Format a stored UTC timestamp for display in the given zone
+pub fn format_in_zone(utc: NaiveDateTime, zone: Tz) -> String {
+    utc.and_utc().with_timezone(&zone).format(DATETIME_FORMAT).to_string()
+}
+
+/// The configured site time zone setting, defaulting to UTC
+pub fn load_site_timezone(conn: &mut PgConnection) -> Result<String, diesel::result::Error> {
+    Ok(Setting::find_by_key(conn, TIMEZONE_SETTING)?
+        .and_then(|setting| setting.setting_value)
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_validate_timezone_setting() {
+        assert_eq!(validate_timezone_setting(" Europe/Berlin ").unwrap(), "Europe/Berlin");
+        assert_eq!(validate_timezone_setting("UTC").unwrap(), "UTC");
+        assert_eq!(validate_timezone_setting("viewer").unwrap(), "viewer");
+        assert!(validate_timezone_setting("Mars/Olympus_Mons").is_err());
+        assert!(validate_timezone_setting("").is_err());
+    }
+
+    #[test]
+    fn test_format_in_zone_applies_offset_and_dst() {
+        let summer = at("2025-08-10 12:30:00");
+        let winter = at("2025-01-10 12:30:00");
+        assert_eq!(format_in_zone(summer, Tz::UTC), "Aug 10, 2025 at 12:30 PM UTC");
+        assert_eq!(format_in_zone(summer, display_zone("Europe/Berlin")), "Aug 10, 2025 at 2:30 PM CEST");
+        assert_eq!(format_in_zone(winter, display_zone("Europe/Berlin")), "Jan 10, 2025 at 1:30 PM CET");
+    }
+
+    #[test]
+    fn test_viewer_and_unknown_zones_render_in_utc() {
+        let utc = at("2025-08-10 23:05:00");
+        assert_eq!(format_in_zone(utc, display_zone(VIEWER_TIMEZONE)), "Aug 10, 2025 at 11:05 PM UTC");
+        assert_eq!(format_in_zone(utc, display_zone("nonsense")), "Aug 10, 2025 at 11:05 PM UTC");
+    }
+}
@@ -0,0 +1,124 @@
+//! Friendly errors for usernames and emails that are already in use
+//!
+//! Signup and user creation check availability up front so the usual case
+//! gets a field-level validation error. Two requests racing for the same
+//! name can both pass that check, so the unique constraint violation from
+//! the insert is mapped to the same error instead of a database error.
+
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use crate::middleware::errors::AppError;
+use crate::models::User;
+
+pub const USERNAME_TAKEN: &str = "Username already taken";
+pub const EMAIL_TAKEN: &str = "Email already taken";
+
+fn taken(field: &str) -> AppError {
+    let message = if field == "email" { EMAIL_TAKEN } else { USERNAME_TAKEN };
+    AppError::FieldValidationError {
+        field: field.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Which field a users table unique constraint guards
+fn field_for_constraint(constraint: &str) -> Option<&'static str> {
+    match constraint {
+        "users_username_key" => Some("username"),
+        "users_email_key" => Some("email"),
+        _ => None,
+    }
+}
+
+/// Reject a username or email that belongs to another account
+/// (`except_user_id` lets a user keep their own)
+pub fn ensure_available(
+    conn: &mut PgConnection,
+    username: Option<&str>,
+    email: Option<&str>,
+    except_user_id: Option<i32>,
+) -> Result<(), AppError> {
+    let other = |user: &User| Some(user.id) != except_user_id;
+    if let Some(username) = username {
+        if User::find_by_username(conn, username)?.is_some_and(|user| other(&user)) {
+            return Err(taken("username"));
+        }
+    }
+    if let Some(email) = email {
+        if User::find_by_email(conn, email)?.is_some_and(|user| other(&user)) {
+            return Err(taken("email"));
+        }
+    }
+    Ok(())
+}
+
+/// Map a unique violation on the username or email column to the same error
+/// `ensure_available` gives; anything else converts as usual
+pub fn map_unique_violation(err: DieselError) -> AppError {
+    if let DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info) = &err {
+        if let Some(field) = info.constraint_name().and_then(field_for_constraint) {
+            return taken(field);
+        }
+    }
+    err.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::result::DatabaseErrorInformation;
+
+    struct Violation(&'static str);
+
+    impl DatabaseErrorInformation for Violation {
+        fn message(&self) -> &str {
+            "duplicate key value violates unique constraint"
+        }
+        fn details(&self) -> Option<&str> {
+            None
+        }
+        fn hint(&self) -> Option<&str> {
+            None
+        }
+        fn table_name(&self) -> Option<&str> {
+            Some("users")
+        }
+        fn column_name(&self) -> Option<&str> {
+            None
+        }
+        fn constraint_name(&self) -> Option<&str> {
+            Some(self.0)
+        }
+        fn statement_position(&self) -> Option<i32> {
+            None
+        }
+    }
+
+    fn unique_violation(constraint: &'static str) -> DieselError {
+        DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, Box::new(Violation(constraint)))
+    }
+
+    #[test]
+    fn test_duplicate_username_and_email_are_validation_errors() {
+        match map_unique_violation(unique_violation("users_username_key")) {
+            AppError::FieldValidationError { field, message } => {
+                assert_eq!(field, "username");
+                assert_eq!(message, USERNAME_TAKEN);
+            }
+            other => panic!("expected a field error, got {:?}", other),
+        }
+        match map_unique_violation(unique_violation("users_email_key")) {
+            AppError::FieldValidationError { field, message } => {
+                assert_eq!(field, "email");
+                assert_eq!(message, EMAIL_TAKEN);
+            }
+            other => panic!("expected a field error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_other_errors_convert_as_usual() {
+        assert!(matches!(map_unique_violation(unique_violation("posts_slug_key")), AppError::DatabaseError(_)));
+        assert!(matches!(map_unique_violation(DieselError::NotFound), AppError::NotFound(_)));
+    }
+}
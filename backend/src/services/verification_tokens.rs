@@ -0,0 +1,102 @@
+//! Email verification token lifetime
+//!
+//! A token is valid for `VERIFICATION_TOKEN_HOURS` after it is issued and is
+//! cleared once used. Tokens are stored as `email_verification_token`; a used
+//! token is left as an empty string, which never matches a presented token.
+//! Used and expired tokens are purged by `services::data_retention`.
+
+use chrono::{Duration, NaiveDateTime};
+use crate::middleware::errors::AppError;
+
+/// How long a verification link stays valid
+pub const VERIFICATION_TOKEN_HOURS: i64 = 24;
+
+/// Minimum time between verification emails for one account
+pub const RESEND_COOLDOWN_MINUTES: i64 = 5;
+
+/// Whether an account may be sent a new verification email
+///
+/// The current token was issued a fixed time before `expires_at`, so the
+/// cooldown runs from there; an account without a token can always resend.
+pub fn resend_allowed(expires_at: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+    expires_at.is_none_or(|expires_at| {
+        let issued_at = expires_at - Duration::hours(VERIFICATION_TOKEN_HOURS);
+        now >= issued_at + Duration::minutes(RESEND_COOLDOWN_MINUTES)
+    })
+}
+
+/// Check a presented token against the one stored for the account it matched
+///
+/// Expiry gets its own message so the client can offer to resend the email;
+/// anything else is reported as an invalid token.
+pub fn check_verification_token(
+    stored: Option<&str>,
+    expires_at: Option<NaiveDateTime>,
+    presented: &str,
+    now: NaiveDateTime,
+) -> Result<(), AppError> {
+    let invalid = || AppError::ValidationError("Invalid verification token".to_string());
+    match stored {
+        Some(stored) if !presented.is_empty() && stored == presented => {}
+        _ => return Err(invalid()),
+    }
+    match expires_at {
+        Some(expires_at) if now > expires_at => {
+            Err(AppError::ValidationError("Verification token has expired".to_string()))
+        }
+        Some(_) => Ok(()),
+        None => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::errors::error_message;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_valid_token() {
+        assert!(check_verification_token(Some("abc123"), Some(at(12)), "abc123", at(11)).is_ok());
+    }
+
+    #[test]
+    fn test_expired_token_is_reported_as_expired() {
+        assert_eq!(
+            error_message(check_verification_token(Some("abc123"), Some(at(12)), "abc123", at(13))),
+            "Verification token has expired"
+        );
+        assert_eq!(
+            error_message(check_verification_token(Some("abc123"), Some(at(12)), "other", at(11))),
+            "Invalid verification token"
+        );
+    }
+
+    #[test]
+    fn test_used_token_cannot_be_reused() {
+        // verify_email clears the stored token and expiry after a success
+        assert_eq!(
+            error_message(check_verification_token(Some(""), None, "abc123", at(11))),
+            "Invalid verification token"
+        );
+        assert_eq!(
+            error_message(check_verification_token(Some(""), None, "", at(11))),
+            "Invalid verification token"
+        );
+        assert!(check_verification_token(None, None, "abc123", at(11)).is_err());
+    }
+
+    #[test]
+    fn test_resend_cooldown() {
+        let issued_at = at(10);
+        let expires_at = Some(issued_at + Duration::hours(VERIFICATION_TOKEN_HOURS));
+        assert!(!resend_allowed(expires_at, issued_at));
+        assert!(!resend_allowed(expires_at, issued_at + Duration::minutes(RESEND_COOLDOWN_MINUTES) - Duration::seconds(1)));
+        assert!(resend_allowed(expires_at, issued_at + Duration::minutes(RESEND_COOLDOWN_MINUTES)));
+        assert!(resend_allowed(expires_at, at(12)));
+        assert!(resend_allowed(None, issued_at));
+    }
+}
@@ -0,0 +1,70 @@
+//! Scheduled visibility for navigation items
+//!
+//! An item is shown from `visible_from` (inclusive) until `visible_until`
+//! (exclusive). A missing bound is open-ended, so an item with neither is
+//! always visible. Times are UTC, matching the rest of the schema.
+
+use chrono::NaiveDateTime;
+use crate::middleware::errors::AppError;
+
+/// Whether a window contains `now`
+pub fn is_visible_at(visible_from: Option<NaiveDateTime>, visible_until: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+    visible_from.is_none_or(|from| from <= now) && visible_until.is_none_or(|until| now < until)
+}
+
+/// Reject windows that can never be open
+pub fn validate_window(visible_from: Option<NaiveDateTime>, visible_until: Option<NaiveDateTime>) -> Result<(), AppError> {
+    match (visible_from, visible_until) {
+        (Some(from), Some(until)) if until <= from => Err(AppError::ValidationError(
+            "Visible until must be later than visible from".to_string()
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_unbounded_window_is_always_visible() {
+        assert!(is_visible_at(None, None, at("2025-01-01 00:00:00")));
+    }
+
+    #[test]
+    fn test_window_boundaries() {
+        let from = at("2025-11-28 00:00:00");
+        let until = at("2025-12-02 00:00:00");
+        let second = Duration::seconds(1);
+
+        assert!(!is_visible_at(Some(from), Some(until), from - second));
+        assert!(is_visible_at(Some(from), Some(until), from));
+        assert!(is_visible_at(Some(from), Some(until), until - second));
+        assert!(!is_visible_at(Some(from), Some(until), until));
+    }
+
+    #[test]
+    fn test_single_bounds() {
+        let now = at("2025-06-15 12:00:00");
+        assert!(is_visible_at(Some(now), None, now));
+        assert!(!is_visible_at(Some(now + Duration::minutes(1)), None, now));
+        // An end date in the past hides the item immediately
+        assert!(!is_visible_at(None, Some(now - Duration::minutes(1)), now));
+        assert!(is_visible_at(None, Some(now + Duration::minutes(1)), now));
+    }
+
+    #[test]
+    fn test_validate_window() {
+        let from = at("2025-06-15 12:00:00");
+        assert!(validate_window(None, None).is_ok());
+        assert!(validate_window(Some(from), None).is_ok());
+        assert!(validate_window(Some(from), Some(from + Duration::hours(1))).is_ok());
+        assert!(validate_window(Some(from), Some(from)).is_err());
+        assert!(validate_window(Some(from), Some(from - Duration::hours(1))).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+//! Admin-configurable word filter for comments
+//!
+//! `comment_blocked_words` holds comma- or newline-separated words or short
+//! phrases. Matching ignores case and only counts whole words, so blocking
+//! `ass` leaves `class` and `assessment` alone. Depending on
+//! `comment_word_filter_mode` a comment containing a blocked word is either
+//! rejected with the word named, or posted with the word masked as `***`.
+
+use diesel::prelude::*;
+use tracing::warn;
+use crate::middleware::errors::AppError;
+use crate::models::Setting;
+
+pub const COMMENT_BLOCKED_WORDS_SETTING: &str = "comment_blocked_words";
+pub const COMMENT_WORD_FILTER_MODE_SETTING: &str = "comment_word_filter_mode";
+
+const MAX_WORDS: usize = 500;
+const MAX_WORD_LENGTH: usize = 100;
+
+/// What happens to a comment containing a blocked word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordFilterMode {
+    #[default]
+    Reject,
+    Mask,
+}
+
+impl WordFilterMode {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim().to_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "mask" => Ok(Self::Mask),
+            _ => Err(AppError::ValidationError(format!(
+                "{} must be reject or mask", COMMENT_WORD_FILTER_MODE_SETTING
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reject => "reject",
+            Self::Mask => "mask",
+        }
+    }
+}
+
+// Case folding one char at a time keeps match positions in step with the text
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Parse and normalize a blocked word list, rejecting overlong entries
+pub fn parse_word_list(value: &str) -> Result<Vec<String>, AppError> {
+    let mut words: Vec<String> = Vec::new();
+    for entry in value.split([',', '\n']) {
+        let word: String = entry.split_whitespace().collect::<Vec<_>>().join(" ").chars().map(fold).collect();
+        if word.is_empty() || words.contains(&word) {
+            continue;
+        }
+        if word.chars().count() > MAX_WORD_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Blocked words can be at most {} characters long", MAX_WORD_LENGTH
+            )));
+        }
+        words.push(word);
+    }
+    if words.len() > MAX_WORDS {
+        return Err(AppError::ValidationError(format!(
+            "The blocked word list can hold at most {} entries", MAX_WORDS
+        )));
+    }
+    Ok(words)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordFilter {
+    pub words: Vec<String>,
+    pub mode: WordFilterMode,
+}
+
+impl WordFilter {
+    /// Current filter from settings; an invalid stored value is ignored
+    pub fn load(conn: &mut PgConnection) -> QueryResult<Self> {
+        let mut filter = Self::default();
+        if let Some(value) = Setting::find_by_key(conn, COMMENT_BLOCKED_WORDS_SETTING)?.and_then(|s| s.setting_value) {
+            match parse_word_list(&value) {
+                Ok(words) => filter.words = words,
+                Err(e) => warn!("Ignoring setting {}: {}", COMMENT_BLOCKED_WORDS_SETTING, e),
+            }
+        }
+        if let Some(value) = Setting::find_by_key(conn, COMMENT_WORD_FILTER_MODE_SETTING)?.and_then(|s| s.setting_value) {
+            match WordFilterMode::parse(&value) {
+                Ok(mode) => filter.mode = mode,
+                Err(e) => warn!("Ignoring setting {}: {}", COMMENT_WORD_FILTER_MODE_SETTING, e),
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Char ranges of whole-word matches, with the blocked word each matched
+    fn find_matches(&self, chars: &[char]) -> Vec<(usize, usize, &str)> {
+        let folded: Vec<char> = chars.iter().copied().map(fold).collect();
+        let mut matches = Vec::new();
+        for word in &self.words {
+            let word_chars: Vec<char> = word.chars().collect();
+            if word_chars.len() > folded.len() {
+                continue;
+            }
+            for start in 0..=folded.len() - word_chars.len() {
+                let end = start + word_chars.len();
+                let bounded = (start == 0 || !is_word_char(folded[start - 1]))
+                    && (end == folded.len() || !is_word_char(folded[end]));
+                if bounded && folded[start..end] == word_chars[..] {
+                    matches.push((start, end, word.as_str()));
+                }
+            }
+        }
+        matches
+    }
+
+    /// Check a comment, returning it with blocked words masked in mask mode
+    pub fn apply(&self, content: &str) -> Result<String, AppError> {
+        let mut chars: Vec<char> = content.chars().collect();
+        let matches = self.find_matches(&chars);
+        let Some(&(_, _, first)) = matches.iter().min_by_key(|(start, _, _)| *start) else {
+            return Ok(content.to_string());
+        };
+        match self.mode {
+            WordFilterMode::Reject => Err(AppError::ValidationError(format!(
+                "Comment contains a blocked word: \"{}\"", first
+            ))),
+            WordFilterMode::Mask => {
+                for (start, end, _) in matches {
+                    for c in &mut chars[start..end] {
+                        if !c.is_whitespace() {
+                            *c = '*';
+                        }
+                    }
+                }
+                Ok(chars.into_iter().collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(words: &str, mode: WordFilterMode) -> WordFilter {
+        WordFilter { words: parse_word_list(words).unwrap(), mode }
+    }
+
+    #[test]
+    fn test_masking() {
+        let filter = filter("darn, heck\nfoo bar", WordFilterMode::Mask);
+        assert_eq!(filter.apply("Darn it, what the HECK!").unwrap(), "**** it, what the ****!");
+        assert_eq!(filter.apply("say foo bar twice: FOO BAR").unwrap(), "say *** *** twice: *** ***");
+        assert_eq!(filter.apply("nothing to see").unwrap(), "nothing to see");
+    }
+
+    #[test]
+    fn test_blocking_names_the_word() {
+        let filter = filter("heck, darn", WordFilterMode::Reject);
+        assert!(matches!(
+            filter.apply("well darn, and heck"),
+            Err(AppError::ValidationError(message)) if message == "Comment contains a blocked word: \"darn\""
+        ));
+        assert!(filter.apply("all good here").is_ok());
+    }
+
+    #[test]
+    fn test_no_false_positives_inside_words() {
+        let filter = filter("ass, hell", WordFilterMode::Reject);
+        for clean in ["A class assessment", "Hello, shellfish", "passed the Hellespont", "hell_raiser"] {
+            assert_eq!(filter.apply(clean).unwrap(), clean);
+        }
+        assert!(filter.apply("What the hell?").is_err());
+        assert!(filter.apply("(ASS)").is_err());
+    }
+
+    #[test]
+    fn test_parse_word_list() {
+        assert_eq!(parse_word_list(" Heck ,\n\nfoo   BAR, heck").unwrap(), vec!["heck", "foo bar"]);
+        assert!(parse_word_list(&"x".repeat(MAX_WORD_LENGTH + 1)).is_err());
+        assert_eq!(WordFilterMode::parse(" Mask ").unwrap(), WordFilterMode::Mask);
+        assert!(WordFilterMode::parse("hide").is_err());
+    }
+}
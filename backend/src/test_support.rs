@@ -0,0 +1,42 @@
+//! Fixtures for tests that run against a scratch database:
+//! `TEST_DATABASE_URL=postgres://... cargo test -- --ignored`
+//!
+//! Tests that create or check sessions hold `lock_sessions` while they run,
+//! since a global session reset expires every session in the database.
+
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+use crate::database::{establish_connection_pool, DbPool};
+use crate::models::{NewUser, User};
+
+static SESSIONS: Mutex<()> = Mutex::const_new(());
+
+/// Run session tests one at a time
+pub async fn lock_sessions() -> MutexGuard<'static, ()> {
+    SESSIONS.lock().await
+}
+
+/// A pool on `TEST_DATABASE_URL`, with migrations applied
+pub fn db_pool() -> Arc<DbPool> {
+    let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+    Arc::new(establish_connection_pool(&url, Default::default()).unwrap())
+}
+
+/// A pool on `TEST_DATABASE_URL` and a new active, verified user with
+/// this role, named `prefix` plus a random suffix
+pub fn db_user(prefix: &str, role: &str) -> (Arc<DbPool>, User) {
+    let pool = db_pool();
+    let mut conn = pool.get().unwrap();
+    let user = User::create(&mut conn, NewUser {
+        username: format!("{}_{}", prefix, Uuid::new_v4().simple()),
+        password: "unused".to_string(),
+        email: None,
+        role: role.to_string(),
+        status: "active".to_string(),
+        email_verified: Some(true),
+        email_verification_token: None,
+        email_verification_expires_at: None,
+    }).unwrap();
+    (pool, user)
+}
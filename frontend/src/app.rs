@@ -1,5 +1,6 @@
 use yew::prelude::*;
-use crate::services::auth_context::{use_auth, logout_and_update_context};
+use crate::services::auth_context::{use_auth, logout_and_update_context, login_url, take_return_path};
+use crate::services::canonical::canonical_path;
 use crate::pages::public::{PublicRouter, PublicPage};
 use crate::components::admin::sidebar::AdminTab;
 use web_sys::window;
@@ -11,6 +12,7 @@ pub enum AppView {
     Login,
     Signup,
     VerifyEmail,
+    ChangePassword,
     Admin(AdminTab),
 }
 
@@ -21,6 +23,7 @@ pub enum AppRoute {
     Login,
     Signup,
     VerifyEmail,
+    ChangePassword,
 }
 
 // Helper function to convert AdminTab to URL path
@@ -29,15 +32,18 @@ fn admin_tab_to_path(tab: &AdminTab) -> String {
         AdminTab::Dashboard => "/admin".to_string(),
         AdminTab::Posts => "/admin/posts".to_string(),
         AdminTab::PostCreate => "/admin/posts/create".to_string(),
+        AdminTab::PostCalendar => "/admin/posts/calendar".to_string(),
         AdminTab::Pages => "/admin/pages".to_string(),
         AdminTab::Media => "/admin/media".to_string(),
         AdminTab::Users => "/admin/users".to_string(),
         AdminTab::Comments => "/admin/comments".to_string(),
+        AdminTab::Sessions => "/admin/sessions".to_string(),
         AdminTab::Navigation => "/admin/navigation".to_string(),
         AdminTab::Templates => "/admin/templates".to_string(),
         AdminTab::Analytics => "/admin/analytics".to_string(),
         AdminTab::SystemSettings => "/admin/settings".to_string(),
         AdminTab::DesignSystem => "/admin/design".to_string(),
+        AdminTab::Profile => "/admin/profile".to_string(),
     }
 }
 
@@ -48,15 +54,18 @@ fn path_to_admin_tab(path: &str) -> Option<AdminTab> {
         "/admin/dashboard" => Some(AdminTab::Dashboard),
         "/admin/posts" => Some(AdminTab::Posts),
         "/admin/posts/create" => Some(AdminTab::PostCreate),
+        "/admin/posts/calendar" => Some(AdminTab::PostCalendar),
         "/admin/pages" => Some(AdminTab::Pages),
         "/admin/media" => Some(AdminTab::Media),
         "/admin/users" | "/admin/users/create" => Some(AdminTab::Users),
         "/admin/comments" => Some(AdminTab::Comments),
+        "/admin/sessions" => Some(AdminTab::Sessions),
         "/admin/navigation" => Some(AdminTab::Navigation),
         "/admin/templates" => Some(AdminTab::Templates),
         "/admin/analytics" => Some(AdminTab::Analytics),
         "/admin/settings" => Some(AdminTab::SystemSettings),
         "/admin/design" => Some(AdminTab::DesignSystem),
+        "/admin/profile" => Some(AdminTab::Profile),
         _ => None,
     }
 }
@@ -87,9 +96,13 @@ fn parse_current_url() -> AppRoute {
             if location == "/verify-email" {
                 return AppRoute::VerifyEmail;
             }
+            if location == "/change-password" {
+                return AppRoute::ChangePassword;
+            }
             
-            // Handle public routes
-            let public_page = match location.as_str() {
+            // Handle public routes, whichever trailing slash they were
+            // reached with; the public layout corrects the address bar
+            let public_page = match canonical_path(&location, false).as_str() {
                 "/" => PublicPage::Home,
                 "/posts" => PublicPage::Posts,
                 path if path.starts_with("/post/") => {
@@ -134,10 +147,58 @@ pub fn app() -> Html {
         AppRoute::Login => AppView::Login,
         AppRoute::Signup => AppView::Signup,
         AppRoute::VerifyEmail => AppView::VerifyEmail,
+        AppRoute::ChangePassword => AppView::ChangePassword,
     };
 
     // TODO: Add browser back/forward navigation support later
 
+    // Keep users an admin flagged for a password change out of the admin area
+    // until they've picked a new one
+    {
+        let current_route = current_route.clone();
+        let must_change_password = auth.user.as_ref().is_some_and(|user| user.must_change_password);
+        let in_admin = matches!(current_route.deref(), AppRoute::Admin(_));
+        use_effect_with_deps(move |(must_change_password, in_admin)| {
+            if *must_change_password && *in_admin {
+                if let Some(window) = window() {
+                    if let Ok(history) = window.history() {
+                        if let Err(e) = history.push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some("/change-password")) {
+                            web_sys::console::warn_1(&format!("Failed to update change password URL: {:?}", e).into());
+                        }
+                    }
+                }
+                current_route.set(AppRoute::ChangePassword);
+            }
+            || ()
+        }, (must_change_password, in_admin));
+    }
+
+    // Signed-out visitors opening an admin link sign in first, then land on it
+    {
+        let current_route = current_route.clone();
+        let signed_out = !auth.loading && !auth.is_authenticated;
+        let in_admin = matches!(current_route.deref(), AppRoute::Admin(_));
+        use_effect_with_deps(move |(signed_out, in_admin)| {
+            if *signed_out && *in_admin {
+                if let Some(window) = window() {
+                    let location = window.location();
+                    let return_path = format!(
+                        "{}{}",
+                        location.pathname().unwrap_or_default(),
+                        location.search().unwrap_or_default()
+                    );
+                    if let Ok(history) = window.history() {
+                        if let Err(e) = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&login_url(&return_path, false))) {
+                            web_sys::console::warn_1(&format!("Failed to update login URL: {:?}", e).into());
+                        }
+                    }
+                }
+                current_route.set(AppRoute::Login);
+            }
+            || ()
+        }, (signed_out, in_admin));
+    }
+
     let switch_to_admin = {
         let current_route = current_route.clone();
         let auth = auth.clone();
@@ -160,10 +221,11 @@ pub fn app() -> Html {
                 
                 current_route.set(new_route);
             } else {
-                // Update URL to login
+                // Update URL to login, coming back to the requested tab afterwards
+                let url = login_url(&admin_tab_to_path(&tab.unwrap_or(AdminTab::Dashboard)), false);
                 if let Some(window) = window() {
                     if let Ok(history) = window.history() {
-                        if let Err(e) = history.push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some("/login")) {
+                        if let Err(e) = history.push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url)) {
                             web_sys::console::warn_1(&format!("Failed to update login URL: {:?}", e).into());
                         }
                     }
@@ -204,18 +266,33 @@ pub fn app() -> Html {
     let on_login_success = {
         let current_route = current_route.clone();
         Callback::from(move |_| {
-            let new_route = AppRoute::Admin(AdminTab::Dashboard);
+            // Return to the deep link or expired session that led here, otherwise the dashboard
+            let return_path = take_return_path();
             
             // Update URL
             if let Some(window) = window() {
                 if let Ok(history) = window.history() {
-                    if let Err(e) = history.push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some("/admin")) {
+                    if let Err(e) = history.push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&return_path)) {
                         web_sys::console::warn_1(&format!("Failed to update admin URL after login: {:?}", e).into());
                     }
                 }
             }
             
-            current_route.set(new_route);
+            current_route.set(parse_current_url());
+        })
+    };
+
+    let on_password_changed = {
+        let current_route = current_route.clone();
+        Callback::from(move |_| {
+            if let Some(window) = window() {
+                if let Ok(history) = window.history() {
+                    if let Err(e) = history.push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some("/admin")) {
+                        web_sys::console::warn_1(&format!("Failed to update admin URL after password change: {:?}", e).into());
+                    }
+                }
+            }
+            current_route.set(AppRoute::Admin(AdminTab::Dashboard));
         })
     };
 
@@ -336,6 +413,11 @@ pub fn app() -> Html {
                             <crate::pages::auth::VerifyEmailPage />
                         </div>
                     },
+                    AppView::ChangePassword => html! {
+                        <crate::components::auth_guard::AuthGuard>
+                            <crate::pages::auth::ChangePasswordPage on_success={on_password_changed} />
+                        </crate::components::auth_guard::AuthGuard>
+                    },
                     AppView::Admin(admin_tab) => html! {
                         <crate::components::AdminGuard>
                             <crate::pages::admin::Admin 
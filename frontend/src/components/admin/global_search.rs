@@ -0,0 +1,208 @@
+use gloo_timers::callback::Timeout;
+use wasm_bindgen::{closure::Closure, JsCast};
+use yew::prelude::*;
+use crate::services::api_service::{search, SearchResult};
+
+/// Wait this long after the last keystroke before searching
+const SEARCH_DEBOUNCE_MS: u32 = 250;
+const MIN_QUERY_LENGTH: usize = 2;
+
+#[derive(Properties, PartialEq)]
+pub struct GlobalSearchProps {
+    pub on_select: Callback<SearchResult>,
+}
+
+/// Whether a key press is going into something the user is typing in
+fn is_typing_target(e: &web_sys::KeyboardEvent) -> bool {
+    e.target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+        .is_some_and(|el| {
+            matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT") || el.is_content_editable()
+        })
+}
+
+fn kind_label(kind: &str) -> &'static str {
+    match kind {
+        "post" => "Post",
+        "page" => "Page",
+        "comment" => "Comment",
+        _ => "Result",
+    }
+}
+
+/// Search box for the admin header; press `/` anywhere to focus it
+#[function_component(GlobalSearch)]
+pub fn global_search(props: &GlobalSearchProps) -> Html {
+    let input_ref = use_node_ref();
+    let query = use_state(String::new);
+    let results = use_state(Vec::<SearchResult>::new);
+    let error = use_state(|| None::<String>);
+    let open = use_state(|| false);
+    let pending = use_mut_ref(|| None::<Timeout>);
+    // Only the newest search may update the results
+    let latest_search = use_mut_ref(|| 0u32);
+
+    // `/` focuses the search box unless the user is typing elsewhere
+    {
+        let input_ref = input_ref.clone();
+        use_effect_with_deps(move |_| {
+            let on_keydown = Closure::<dyn Fn(web_sys::KeyboardEvent)>::new(move |e: web_sys::KeyboardEvent| {
+                if e.key() == "/" && !e.ctrl_key() && !e.meta_key() && !e.alt_key() && !is_typing_target(&e) {
+                    if let Some(input) = input_ref.cast::<web_sys::HtmlInputElement>() {
+                        e.prevent_default();
+                        let _ = input.focus();
+                        input.select();
+                    }
+                }
+            });
+            let document = web_sys::window().and_then(|window| window.document());
+            if let Some(document) = &document {
+                let _ = document.add_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref());
+            }
+            move || {
+                if let Some(document) = document {
+                    let _ = document.remove_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref());
+                }
+            }
+        }, ());
+    }
+
+    let on_input = {
+        let query = query.clone();
+        let results = results.clone();
+        let error = error.clone();
+        let open = open.clone();
+        let pending = pending.clone();
+        let latest_search = latest_search.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let value = input.value();
+            query.set(value.clone());
+            open.set(true);
+
+            *latest_search.borrow_mut() += 1;
+            let search_id = *latest_search.borrow();
+            if value.trim().chars().count() < MIN_QUERY_LENGTH {
+                pending.borrow_mut().take();
+                results.set(Vec::new());
+                error.set(None);
+                return;
+            }
+
+            let results = results.clone();
+            let error = error.clone();
+            let latest_search = latest_search.clone();
+            *pending.borrow_mut() = Some(Timeout::new(SEARCH_DEBOUNCE_MS, move || {
+                wasm_bindgen_futures::spawn_local(async move {
+                    let outcome = search(value.trim()).await;
+                    if *latest_search.borrow() != search_id {
+                        return;
+                    }
+                    match outcome {
+                        Ok(response) => {
+                            results.set(response.results);
+                            error.set(None);
+                        }
+                        Err(e) => error.set(Some(e.to_string())),
+                    }
+                });
+            }));
+        })
+    };
+
+    let select = {
+        let on_select = props.on_select.clone();
+        let query = query.clone();
+        let results = results.clone();
+        let open = open.clone();
+        Callback::from(move |result: SearchResult| {
+            query.set(String::new());
+            results.set(Vec::new());
+            open.set(false);
+            on_select.emit(result);
+        })
+    };
+
+    let on_keydown = {
+        let results = results.clone();
+        let open = open.clone();
+        let input_ref = input_ref.clone();
+        let select = select.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "Escape" => {
+                open.set(false);
+                if let Some(input) = input_ref.cast::<web_sys::HtmlInputElement>() {
+                    let _ = input.blur();
+                }
+            }
+            "Enter" => {
+                if let Some(first) = results.first() {
+                    e.prevent_default();
+                    select.emit(first.clone());
+                }
+            }
+            _ => {}
+        })
+    };
+
+    let on_focus = {
+        let open = open.clone();
+        Callback::from(move |_: FocusEvent| open.set(true))
+    };
+
+    // Let a click on a result land before the list closes
+    let on_blur = {
+        let open = open.clone();
+        Callback::from(move |_: FocusEvent| {
+            let open = open.clone();
+            Timeout::new(150, move || open.set(false)).forget();
+        })
+    };
+
+    let searching = query.trim().chars().count() >= MIN_QUERY_LENGTH;
+
+    html! {
+        <div class="global-search">
+            <input
+                ref={input_ref}
+                type="search"
+                class="global-search-input"
+                placeholder="Search posts, pages, comments  ( / )"
+                aria-label="Search the site"
+                value={(*query).clone()}
+                oninput={on_input}
+                onkeydown={on_keydown}
+                onfocus={on_focus}
+                onblur={on_blur}
+            />
+            if *open && searching {
+                <ul class="global-search-results" role="listbox">
+                    if let Some(ref message) = *error {
+                        <li class="global-search-empty">{message}</li>
+                    } else if results.is_empty() {
+                        <li class="global-search-empty">{"No matches"}</li>
+                    } else {
+                        {for results.iter().map(|result| {
+                            let onclick = {
+                                let select = select.clone();
+                                let result = result.clone();
+                                Callback::from(move |_: MouseEvent| select.emit(result.clone()))
+                            };
+                            html! {
+                                <li class="global-search-result" role="option" onclick={onclick}>
+                                    <span class={classes!("global-search-kind", format!("kind-{}", result.kind))}>
+                                        {kind_label(&result.kind)}
+                                    </span>
+                                    <div class="global-search-text">
+                                        <strong>{&result.title}</strong>
+                                        <small>{&result.snippet}</small>
+                                    </div>
+                                </li>
+                            }
+                        })}
+                    }
+                </ul>
+            }
+        </div>
+    }
+}
@@ -1,11 +1,14 @@
 use yew::prelude::*;
 use crate::services::auth_service::User;
-use crate::services::api_service::get_settings;
+use crate::services::api_service::{get_settings, SearchResult};
+use super::global_search::GlobalSearch;
 
 #[derive(Properties, PartialEq)]
 pub struct AdminHeaderProps {
     pub on_public_click: Callback<()>,
     pub on_logout: Callback<()>,
+    pub on_profile_click: Callback<()>,
+    pub on_search_select: Callback<SearchResult>,
     pub current_user: Option<User>,
 }
 
@@ -36,6 +39,7 @@ pub fn admin_header(props: &AdminHeaderProps) -> Html {
         <header class="admin-header">
             <div class="admin-header-content">
                 <h1 class="admin-title">{(*site_title).clone()}</h1>
+                <GlobalSearch on_select={props.on_search_select.clone()} />
                 <div class="admin-header-actions">
                     <button class="btn btn-secondary" onclick={let callback = props.on_public_click.clone(); Callback::from(move |_| callback.emit(()))}>
                         <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor" style="margin-right: 8px;">
@@ -44,12 +48,12 @@ pub fn admin_header(props: &AdminHeaderProps) -> Html {
                         {"View Site"}
                     </button>
                     if let Some(ref user) = props.current_user {
-                        <span class="admin-user">
+                        <button class="btn btn-secondary admin-user" title="My account" onclick={let callback = props.on_profile_click.clone(); Callback::from(move |_| callback.emit(()))}>
                             <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor" style="margin-right: 8px;">
                                 <path d="M12 12c2.21 0 4-1.79 4-4s-1.79-4-4-4-4 1.79-4 4 1.79 4 4 4zm0 2c-2.67 0-8 1.34-8 4v2h16v-2c0-2.66-5.33-4-8-4z"/>
                             </svg>
                             {&user.username}
-                        </span>
+                        </button>
                     }
                     <button class="btn btn-secondary" onclick={let callback = props.on_logout.clone(); Callback::from(move |_| callback.emit(()))}>
                         <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor" style="margin-right: 8px;">
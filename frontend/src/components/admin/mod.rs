@@ -1,5 +1,6 @@
 pub mod header;
 pub mod sidebar;
+pub mod global_search;
 
 pub use header::AdminHeader;
 pub use sidebar::AdminSidebar; 
\ No newline at end of file
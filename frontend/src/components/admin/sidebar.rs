@@ -5,15 +5,56 @@ pub enum AdminTab {
     Dashboard,
     Posts,
     PostCreate,
+    PostCalendar,
     Pages,
     Media,
     Users,
     Comments,
+    Sessions,
     Navigation,
     Templates,
     Analytics,
     SystemSettings,
     DesignSystem,
+    Profile,
+}
+
+impl AdminTab {
+    /// Permission the backend requires for this tab's endpoints, matching
+    /// `services::permissions` there; `None` for tabs every admin user gets
+    pub fn required_permission(&self) -> Option<&'static str> {
+        match self {
+            AdminTab::Dashboard
+            | AdminTab::Posts
+            | AdminTab::PostCreate
+            | AdminTab::PostCalendar
+            | AdminTab::Pages
+            | AdminTab::Media
+            | AdminTab::Comments => Some("manage_content"),
+            AdminTab::Users | AdminTab::Sessions => Some("manage_users"),
+            AdminTab::Analytics => Some("view_analytics"),
+            AdminTab::Navigation
+            | AdminTab::Templates
+            | AdminTab::SystemSettings
+            | AdminTab::DesignSystem => Some("manage_site"),
+            AdminTab::Profile => None,
+        }
+    }
+
+    pub fn is_allowed(&self, permissions: &[String]) -> bool {
+        self.required_permission()
+            .is_none_or(|required| permissions.iter().any(|p| p == required))
+    }
+
+    /// Where a role lands on opening the admin area: editors go straight
+    /// to their posts, admins to the dashboard
+    pub fn default_for(role: &str, permissions: &[String]) -> AdminTab {
+        let preferred = match role {
+            "editor" => AdminTab::Posts,
+            _ => AdminTab::Dashboard,
+        };
+        if preferred.is_allowed(permissions) { preferred } else { AdminTab::Profile }
+    }
 }
 
 #[derive(Properties, PartialEq)]
@@ -21,10 +62,14 @@ pub struct AdminSidebarProps {
     pub on_tab_click: Callback<AdminTab>,
     pub active_tab: AdminTab,
     pub on_public_click: Callback<()>,
+    /// Permissions of the signed-in user; tabs needing others are hidden
+    pub permissions: Vec<String>,
 }
 
 #[function_component(AdminSidebar)]
 pub fn admin_sidebar(props: &AdminSidebarProps) -> Html {
+    let allowed = |tab: &AdminTab| tab.is_allowed(&props.permissions);
+
     let on_dashboard_click = {
         let on_tab_click = props.on_tab_click.clone();
         Callback::from(move |_| on_tab_click.emit(AdminTab::Dashboard))
@@ -55,6 +100,11 @@ pub fn admin_sidebar(props: &AdminSidebarProps) -> Html {
         Callback::from(move |_| on_tab_click.emit(AdminTab::Comments))
     };
 
+    let on_sessions_click = {
+        let on_tab_click = props.on_tab_click.clone();
+        Callback::from(move |_| on_tab_click.emit(AdminTab::Sessions))
+    };
+
     let on_navigation_click = {
         let on_tab_click = props.on_tab_click.clone();
         Callback::from(move |_| on_tab_click.emit(AdminTab::Navigation))
@@ -122,149 +172,186 @@ pub fn admin_sidebar(props: &AdminSidebarProps) -> Html {
             <div class="sidebar-section">
                 <h3 class="section-title">{"Admin Panel"}</h3>
                 <ul class="admin-nav">
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::Dashboard { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_dashboard_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M3 13h8V3H3v10zm0 8h8v-6H3v6zm10 0h8V11h-8v10zm0-18v6h8V3h-8z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Dashboard"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::Posts { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_posts_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M19 3H5c-1.1 0-2 .9-2 2v14c0 1.1.9 2 2 2h14c1.1 0 2-.9 2-2V5c0-1.1-.9-2-2-2zm-5 14H7v-2h7v2zm3-4H7v-2h10v2zm0-4H7V7h10v2z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Posts"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::Pages { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_pages_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M14 2H6c-1.1 0-1.99.9-1.99 2L4 20c0 1.1.89 2 2 2h12c1.1 0 2-.9 2-2V8l-6-6zm2 16H8v-2h8v2zm0-4H8v-2h8v2zm-3-5V3.5L18.5 9H13z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Pages"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::Media { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_media_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M21 19V5c0-1.1-.9-2-2-2H5c-1.1 0-2 .9-2 2v14c0 1.1.9 2 2 2h14c1.1 0 2-.9 2-2zM8.5 13.5l2.5 3.01L14.5 12l4.5 6H5l3.5-4.5z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Media"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::Users { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_users_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M16 4c0-1.11.89-2 2-2s2 .89 2 2-.89 2-2 2-2-.89-2-2zm4 18v-6h2.5l-2.54-7.63A1.5 1.5 0 0 0 18.54 8H17c-.8 0-1.54.37-2.01 1l-1.7 2.26V16h-1.5v6h6zM12.5 11.5c.83 0 1.5-.67 1.5-1.5s-.67-1.5-1.5-1.5S11 9.17 11 10s.67 1.5 1.5 1.5zM5.5 6c1.11 0 2-.89 2-2s-.89-2-2-2-2 .89-2 2 .89 2 2 2zm2 16v-7H9V9c0-1.1-.9-2-2-2H4c-1.1 0-2 .9-2 2v6h1.5v7h4z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Users"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::Comments { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_comments_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M21.99 4c0-1.1-.89-2-2-2H4c-1.1 0-2 .9-2 2v12c0 1.1.9 2 2 2h14l4 4-.01-18zM18 14H6v-2h12v2zm0-3H6V9h12v2zm0-3H6V6h12v2z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Comments"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::Navigation { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_navigation_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M3 18h18v-2H3v2zm0-5h18v-2H3v2zm0-7v2h18V6H3z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Navigation"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::Templates { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_templates_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M14 2H6c-1.1 0-1.99.9-1.99 2L4 20c0 1.1.89 2 2 2h12c1.1 0 2-.9 2-2V8l-6-6zm2 16H8v-2h8v2zm0-4H8v-2h8v2zm-3-5V3.5L18.5 9H13z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Templates"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::Analytics { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_analytics_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M19 3H5c-1.1 0-2 .9-2 2v14c0 1.1.9 2 2 2h14c1.1 0 2-.9 2-2V5c0-1.1-.9-2-2-2zM9 17H7v-7h2v7zm4 0h-2V7h2v10zm4 0h-2v-4h2v4z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Analytics"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::DesignSystem { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_design_system_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M12,3c-4.97,0-9,4.03-9,9s4.03,9,9,9s9-4.03,9-9c0-0.46-0.04-0.92-0.1-1.36c-0.98,1.37-2.58,2.26-4.4,2.26 c-2.98,0-5.4-2.42-5.4-5.4c0-1.81,0.89-3.42,2.26-4.4C12.92,3.04,12.46,3,12,3z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Design System"}</span>
-                        </button>
-                    </li>
-                    <li>
-                        <button 
-                            class={if props.active_tab == AdminTab::SystemSettings { "admin-nav-link active" } else { "admin-nav-link" }}
-                            onclick={on_system_settings_click}
-                        >
-                            <span class="nav-icon">
-                                <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
-                                    <path d="M19.14,12.94c0.04-0.3,0.06-0.61,0.06-0.94c0-0.32-0.02-0.64-0.07-0.94l2.03-1.58c0.18-0.14,0.23-0.41,0.12-0.61 l-1.92-3.32c-0.12-0.22-0.37-0.29-0.59-0.22l-2.39,0.96c-0.5-0.38-1.03-0.7-1.62-0.94L14.4,2.81c-0.04-0.24-0.24-0.41-0.48-0.41 h-3.84c-0.24,0-0.43,0.17-0.47,0.41L9.25,5.35C8.66,5.59,8.12,5.92,7.63,6.29L5.24,5.33c-0.22-0.08-0.47,0-0.59,0.22L2.74,8.87 C2.62,9.08,2.66,9.34,2.86,9.48l2.03,1.58C4.84,11.36,4.8,11.69,4.8,12s0.02,0.64,0.07,0.94l-2.03,1.58 c-0.18,0.14-0.23,0.41-0.12,0.61l1.92,3.32c0.12,0.22,0.37,0.29,0.59,0.22l2.39-0.96c0.5,0.38,1.03,0.7,1.62,0.94l0.36,2.54 c0.05,0.24,0.24,0.41,0.48,0.41h3.84c0.24,0,0.44-0.17,0.47-0.41l0.36-2.54c0.59-0.24,1.13-0.56,1.62-0.94l2.39,0.96 c0.22,0.08,0.47,0,0.59-0.22l1.92-3.32c0.12-0.22,0.07-0.47-0.12-0.61L19.14,12.94z M12,15.6c-1.98,0-3.6-1.62-3.6-3.6 s1.62-3.6,3.6-3.6s3.6,1.62,3.6,3.6S13.98,15.6,12,15.6z"/>
-                                </svg>
-                            </span>
-                            <span class="nav-text">{"Settings"}</span>
-                        </button>
-                    </li>
+                    if allowed(&AdminTab::Dashboard) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Dashboard { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_dashboard_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M3 13h8V3H3v10zm0 8h8v-6H3v6zm10 0h8V11h-8v10zm0-18v6h8V3h-8z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Dashboard"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::Posts) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Posts { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_posts_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M19 3H5c-1.1 0-2 .9-2 2v14c0 1.1.9 2 2 2h14c1.1 0 2-.9 2-2V5c0-1.1-.9-2-2-2zm-5 14H7v-2h7v2zm3-4H7v-2h10v2zm0-4H7V7h10v2z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Posts"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::Pages) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Pages { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_pages_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M14 2H6c-1.1 0-1.99.9-1.99 2L4 20c0 1.1.89 2 2 2h12c1.1 0 2-.9 2-2V8l-6-6zm2 16H8v-2h8v2zm0-4H8v-2h8v2zm-3-5V3.5L18.5 9H13z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Pages"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::Media) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Media { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_media_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M21 19V5c0-1.1-.9-2-2-2H5c-1.1 0-2 .9-2 2v14c0 1.1.9 2 2 2h14c1.1 0 2-.9 2-2zM8.5 13.5l2.5 3.01L14.5 12l4.5 6H5l3.5-4.5z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Media"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::Users) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Users { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_users_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M16 4c0-1.11.89-2 2-2s2 .89 2 2-.89 2-2 2-2-.89-2-2zm4 18v-6h2.5l-2.54-7.63A1.5 1.5 0 0 0 18.54 8H17c-.8 0-1.54.37-2.01 1l-1.7 2.26V16h-1.5v6h6zM12.5 11.5c.83 0 1.5-.67 1.5-1.5s-.67-1.5-1.5-1.5S11 9.17 11 10s.67 1.5 1.5 1.5zM5.5 6c1.11 0 2-.89 2-2s-.89-2-2-2-2 .89-2 2 .89 2 2 2zm2 16v-7H9V9c0-1.1-.9-2-2-2H4c-1.1 0-2 .9-2 2v6h1.5v7h4z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Users"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::Comments) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Comments { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_comments_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M21.99 4c0-1.1-.89-2-2-2H4c-1.1 0-2 .9-2 2v12c0 1.1.9 2 2 2h14l4 4-.01-18zM18 14H6v-2h12v2zm0-3H6V9h12v2zm0-3H6V6h12v2z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Comments"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::Sessions) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Sessions { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_sessions_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M18 8h-1V6c0-2.76-2.24-5-5-5S7 3.24 7 6v2H6c-1.1 0-2 .9-2 2v10c0 1.1.9 2 2 2h12c1.1 0 2-.9 2-2V10c0-1.1-.9-2-2-2zm-6 9c-1.1 0-2-.9-2-2s.9-2 2-2 2 .9 2 2-.9 2-2 2zm3.1-9H8.9V6c0-1.71 1.39-3.1 3.1-3.1 1.71 0 3.1 1.39 3.1 3.1v2z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Sessions"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::Navigation) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Navigation { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_navigation_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M3 18h18v-2H3v2zm0-5h18v-2H3v2zm0-7v2h18V6H3z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Navigation"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::Templates) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Templates { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_templates_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M14 2H6c-1.1 0-1.99.9-1.99 2L4 20c0 1.1.89 2 2 2h12c1.1 0 2-.9 2-2V8l-6-6zm2 16H8v-2h8v2zm0-4H8v-2h8v2zm-3-5V3.5L18.5 9H13z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Templates"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::Analytics) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::Analytics { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_analytics_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M19 3H5c-1.1 0-2 .9-2 2v14c0 1.1.9 2 2 2h14c1.1 0 2-.9 2-2V5c0-1.1-.9-2-2-2zM9 17H7v-7h2v7zm4 0h-2V7h2v10zm4 0h-2v-4h2v4z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Analytics"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::DesignSystem) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::DesignSystem { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_design_system_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M12,3c-4.97,0-9,4.03-9,9s4.03,9,9,9s9-4.03,9-9c0-0.46-0.04-0.92-0.1-1.36c-0.98,1.37-2.58,2.26-4.4,2.26 c-2.98,0-5.4-2.42-5.4-5.4c0-1.81,0.89-3.42,2.26-4.4C12.92,3.04,12.46,3,12,3z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Design System"}</span>
+                            </button>
+                        </li>
+                    }
+                    if allowed(&AdminTab::SystemSettings) {
+                        <li>
+                            <button 
+                                class={if props.active_tab == AdminTab::SystemSettings { "admin-nav-link active" } else { "admin-nav-link" }}
+                                onclick={on_system_settings_click}
+                            >
+                                <span class="nav-icon">
+                                    <svg width="16" height="16" viewBox="0 0 24 24" fill="currentColor">
+                                        <path d="M19.14,12.94c0.04-0.3,0.06-0.61,0.06-0.94c0-0.32-0.02-0.64-0.07-0.94l2.03-1.58c0.18-0.14,0.23-0.41,0.12-0.61 l-1.92-3.32c-0.12-0.22-0.37-0.29-0.59-0.22l-2.39,0.96c-0.5-0.38-1.03-0.7-1.62-0.94L14.4,2.81c-0.04-0.24-0.24-0.41-0.48-0.41 h-3.84c-0.24,0-0.43,0.17-0.47,0.41L9.25,5.35C8.66,5.59,8.12,5.92,7.63,6.29L5.24,5.33c-0.22-0.08-0.47,0-0.59,0.22L2.74,8.87 C2.62,9.08,2.66,9.34,2.86,9.48l2.03,1.58C4.84,11.36,4.8,11.69,4.8,12s0.02,0.64,0.07,0.94l-2.03,1.58 c-0.18,0.14-0.23,0.41-0.12,0.61l1.92,3.32c0.12,0.22,0.37,0.29,0.59,0.22l2.39-0.96c0.5,0.38,1.03,0.7,1.62,0.94l0.36,2.54 c0.05,0.24,0.24,0.41,0.48,0.41h3.84c0.24,0,0.44-0.17,0.47-0.41l0.36-2.54c0.59-0.24,1.13-0.56,1.62-0.94l2.39,0.96 c0.22,0.08,0.47,0,0.59-0.22l1.92-3.32c0.12-0.22,0.07-0.47-0.12-0.61L19.14,12.94z M12,15.6c-1.98,0-3.6-1.62-3.6-3.6 s1.62-3.6,3.6-3.6s3.6,1.62,3.6,3.6S13.98,15.6,12,15.6z"/>
+                                    </svg>
+                                </span>
+                                <span class="nav-text">{"Settings"}</span>
+                            </button>
+                        </li>
+                    }
                 </ul>
             </div>
         </nav>
@@ -22,7 +22,8 @@ pub fn auth_guard(props: &AuthGuardProps) -> Html {
     }
 
     let is_authorized = match &auth.user {
-        Some(user) if require_admin => user.role == "admin" && user.status == "active",
+        // Any role with admin-area permissions; tabs check the specific ones
+        Some(user) if require_admin => !user.permissions.is_empty() && user.status == "active",
         Some(user) => user.status == "active",
         None => false,
     };
@@ -0,0 +1,141 @@
+use gloo_timers::callback::Interval;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use yew::prelude::*;
+use crate::services::api_service::get_public_settings;
+
+const SCRIPT_ID: &str = "captcha-provider-script";
+/// How often to check whether the provider script has finished loading
+const LOAD_POLL_MS: u32 = 200;
+
+/// The configured provider and its public site key
+#[derive(Clone, PartialEq)]
+pub struct CaptchaSettings {
+    /// `recaptcha` or `hcaptcha`
+    pub provider: String,
+    pub site_key: String,
+}
+
+impl CaptchaSettings {
+    fn script_url(&self) -> &'static str {
+        match self.provider.as_str() {
+            "hcaptcha" => "https://js.hcaptcha.com/1/api.js?render=explicit",
+            _ => "https://www.google.com/recaptcha/api.js?render=explicit",
+        }
+    }
+
+    /// The global object the provider script defines
+    fn global_name(&self) -> &'static str {
+        match self.provider.as_str() {
+            "hcaptcha" => "hcaptcha",
+            _ => "grecaptcha",
+        }
+    }
+}
+
+/// CAPTCHA settings from the public site settings; `None` when it is off
+pub async fn load_captcha_settings() -> Option<CaptchaSettings> {
+    let settings = get_public_settings(Some("site")).await.ok()?;
+    let value = |key: &str| {
+        settings.iter()
+            .find(|setting| setting.setting_key == key)
+            .and_then(|setting| setting.setting_value.clone())
+            .unwrap_or_default()
+    };
+    let provider = value("captcha_provider");
+    let site_key = value("captcha_site_key");
+    (matches!(provider.as_str(), "recaptcha" | "hcaptcha") && !site_key.trim().is_empty())
+        .then_some(CaptchaSettings { provider, site_key })
+}
+
+/// Failed logins before the login form needs a CAPTCHA
+pub async fn load_login_failure_threshold() -> u32 {
+    get_public_settings(Some("site")).await.ok()
+        .and_then(|settings| settings.into_iter().find(|setting| setting.setting_key == "captcha_login_failures"))
+        .and_then(|setting| setting.setting_value)
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(3)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CaptchaProps {
+    pub settings: CaptchaSettings,
+    /// The solved token, or `None` once it expires
+    pub on_token: Callback<Option<String>>,
+}
+
+fn add_script(document: &web_sys::Document, src: &str) {
+    if document.get_element_by_id(SCRIPT_ID).is_some() {
+        return;
+    }
+    if let (Ok(script), Some(head)) = (document.create_element("script"), document.head()) {
+        script.set_id(SCRIPT_ID);
+        let _ = script.set_attribute("src", src);
+        let _ = script.set_attribute("async", "");
+        let _ = head.append_child(&script);
+    }
+}
+
+/// The provider's `render` function, once its script has loaded
+fn render_function(global_name: &str) -> Option<(JsValue, js_sys::Function)> {
+    let window = web_sys::window()?;
+    let global = js_sys::Reflect::get(&window, &JsValue::from_str(global_name)).ok()?;
+    let render = js_sys::Reflect::get(&global, &JsValue::from_str("render")).ok()?;
+    render.dyn_into::<js_sys::Function>().ok().map(|render| (global, render))
+}
+
+/// reCAPTCHA or hCaptcha checkbox widget. Tokens are single use, so give
+/// the component a new `key` after each submission to get a fresh widget.
+#[function_component(Captcha)]
+pub fn captcha(props: &CaptchaProps) -> Html {
+    let container_ref = use_node_ref();
+
+    {
+        let container_ref = container_ref.clone();
+        let settings = props.settings.clone();
+        let on_token = props.on_token.clone();
+        use_effect_with_deps(move |_| {
+            if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+                add_script(&document, settings.script_url());
+            }
+
+            let solved = {
+                let on_token = on_token.clone();
+                Closure::<dyn Fn(String)>::new(move |token: String| on_token.emit(Some(token)))
+            };
+            let expired = Closure::<dyn Fn()>::new(move || on_token.emit(None));
+            let options = js_sys::Object::new();
+            let _ = js_sys::Reflect::set(&options, &"sitekey".into(), &settings.site_key.clone().into());
+            let _ = js_sys::Reflect::set(&options, &"callback".into(), solved.as_ref());
+            let _ = js_sys::Reflect::set(&options, &"expired-callback".into(), expired.as_ref());
+
+            let poll = Interval::new(LOAD_POLL_MS, {
+                let rendered = std::cell::Cell::new(false);
+                move || {
+                    if rendered.get() {
+                        return;
+                    }
+                    let (Some((global, render)), Some(container)) =
+                        (render_function(settings.global_name()), container_ref.cast::<web_sys::Element>())
+                    else {
+                        return;
+                    };
+                    rendered.set(true);
+                    if let Err(e) = render.call2(&global, &container, &options) {
+                        log::error!("Failed to render CAPTCHA: {:?}", e);
+                    }
+                }
+            });
+
+            // The widget calls back for as long as it is on the page
+            move || {
+                drop(poll);
+                drop(solved);
+                drop(expired);
+            }
+        }, props.settings.clone());
+    }
+
+    html! {
+        <div class="captcha-widget" ref={container_ref}></div>
+    }
+}
@@ -0,0 +1,150 @@
+use yew::prelude::*;
+use crate::services::auth_service::{change_password, AuthError};
+use crate::services::auth_context::{use_auth, refresh_user_context};
+
+#[derive(Properties, PartialEq)]
+pub struct ChangePasswordFormProps {
+    pub on_success: Callback<()>,
+}
+
+/// Current password, new password and confirmation, with an option to sign
+/// out the user's other sessions
+#[function_component(ChangePasswordForm)]
+pub fn change_password_form(props: &ChangePasswordFormProps) -> Html {
+    let auth = use_auth();
+    let current_password = use_state(String::new);
+    let new_password = use_state(String::new);
+    let confirm_password = use_state(String::new);
+    let logout_other_sessions = use_state(|| true);
+    let error = use_state(|| None::<String>);
+    let saving = use_state(|| false);
+
+    let on_input = |field: &UseStateHandle<String>| {
+        let field = field.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            field.set(input.value());
+        })
+    };
+
+    let on_logout_others_change = {
+        let logout_other_sessions = logout_other_sessions.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            logout_other_sessions.set(input.checked());
+        })
+    };
+
+    let on_submit = {
+        let current_password = current_password.clone();
+        let new_password = new_password.clone();
+        let confirm_password = confirm_password.clone();
+        let logout_other_sessions = logout_other_sessions.clone();
+        let error = error.clone();
+        let saving = saving.clone();
+        let auth = auth.clone();
+        let on_success = props.on_success.clone();
+
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            if *new_password != *confirm_password {
+                error.set(Some("The new passwords don't match".to_string()));
+                return;
+            }
+
+            let current = (*current_password).clone();
+            let new = (*new_password).clone();
+            let logout_others = *logout_other_sessions;
+            let current_password = current_password.clone();
+            let new_password = new_password.clone();
+            let confirm_password = confirm_password.clone();
+            let error = error.clone();
+            let saving = saving.clone();
+            let auth = auth.clone();
+            let on_success = on_success.clone();
+
+            error.set(None);
+            saving.set(true);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match change_password(&current, &new, logout_others).await {
+                    Ok(()) => {
+                        current_password.set(String::new());
+                        new_password.set(String::new());
+                        confirm_password.set(String::new());
+                        // Pick up a cleared "must change password" flag
+                        refresh_user_context(&auth).await;
+                        on_success.emit(());
+                    }
+                    Err(AuthError::ServerError(msg)) => error.set(Some(msg)),
+                    Err(e) => error.set(Some(format!("Could not change password: {}", e))),
+                }
+                saving.set(false);
+            });
+        })
+    };
+
+    html! {
+        <form class="auth-form" onsubmit={on_submit}>
+            if let Some(ref error_msg) = *error {
+                <div class="error-message">{"Error: "}{error_msg}</div>
+            }
+
+            <div class="form-group">
+                <label for="current-password">{"Current Password"}</label>
+                <input
+                    type="password"
+                    id="current-password"
+                    autocomplete="current-password"
+                    value={(*current_password).clone()}
+                    oninput={on_input(&current_password)}
+                    required=true
+                />
+            </div>
+
+            <div class="form-group">
+                <label for="new-password">{"New Password"}</label>
+                <input
+                    type="password"
+                    id="new-password"
+                    autocomplete="new-password"
+                    value={(*new_password).clone()}
+                    oninput={on_input(&new_password)}
+                    required=true
+                />
+            </div>
+
+            <div class="form-group">
+                <label for="confirm-password">{"Confirm New Password"}</label>
+                <input
+                    type="password"
+                    id="confirm-password"
+                    autocomplete="new-password"
+                    value={(*confirm_password).clone()}
+                    oninput={on_input(&confirm_password)}
+                    required=true
+                />
+            </div>
+
+            <div class="form-group">
+                <label>
+                    <input
+                        type="checkbox"
+                        checked={*logout_other_sessions}
+                        onchange={on_logout_others_change}
+                    />
+                    {" Sign out my other sessions"}
+                </label>
+            </div>
+
+            <button type="submit" class="btn btn-primary" disabled={*saving}>
+                if *saving {
+                    {"Saving..."}
+                } else {
+                    {"Change Password"}
+                }
+            </button>
+        </form>
+    }
+}
@@ -1,32 +1,136 @@
 use yew::prelude::*;
-use crate::services::api_service::CommentWithGravatar;
+use web_sys::HtmlTextAreaElement;
+use crate::services::api_service::{CommentWithGravatar, update_comment_content, delete_comment};
+use crate::components::RelativeTime;
 
 #[derive(Properties, PartialEq)]
 pub struct CommentItemProps {
     pub comment: CommentWithGravatar,
+    /// Whether the viewer may edit/delete this comment (admins/moderators)
+    #[prop_or_default]
+    pub can_moderate: bool,
+    /// Set while the comment is still being submitted; it cannot be edited or
+    /// deleted until the server has confirmed it
+    #[prop_or_default]
+    pub pending: bool,
+    /// Emitted with the updated comment after a successful edit
+    #[prop_or_default]
+    pub on_update: Callback<CommentWithGravatar>,
+    /// Emitted with the comment id after a successful delete
+    #[prop_or_default]
+    pub on_delete: Callback<i32>,
 }
 
 #[function_component(CommentItem)]
 pub fn comment_item(props: &CommentItemProps) -> Html {
+    let editing = use_state(|| false);
+    let edit_text = use_state(String::new);
+    let busy = use_state(|| false);
+    let error = use_state(|| None::<String>);
+
     let comment = &props.comment;
     let author_name = comment.author_username.as_ref()
         .map(|s| s.as_str())
         .unwrap_or("Anonymous");
-    
-    // Format the timestamp
-    let formatted_time = comment.created_at.as_ref()
-        .and_then(|time_str| {
-            chrono::NaiveDateTime::parse_from_str(time_str, "%Y-%m-%d %H:%M:%S")
-                .ok()
-                .map(|dt| dt.format("%b %d, %Y at %I:%M %p").to_string())
+
+
+    let on_start_edit = {
+        let editing = editing.clone();
+        let edit_text = edit_text.clone();
+        let error = error.clone();
+        let content = comment.content.clone();
+        Callback::from(move |_| {
+            edit_text.set(content.clone());
+            error.set(None);
+            editing.set(true);
+        })
+    };
+
+    let on_cancel_edit = {
+        let editing = editing.clone();
+        Callback::from(move |_| editing.set(false))
+    };
+
+    let on_edit_input = {
+        let edit_text = edit_text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            edit_text.set(input.value());
+        })
+    };
+
+    let on_save_edit = {
+        let editing = editing.clone();
+        let edit_text = edit_text.clone();
+        let busy = busy.clone();
+        let error = error.clone();
+        let comment = comment.clone();
+        let on_update = props.on_update.clone();
+        Callback::from(move |_| {
+            let text = edit_text.trim().to_string();
+            if text.is_empty() {
+                error.set(Some("Comment cannot be empty".to_string()));
+                return;
+            }
+
+            let editing = editing.clone();
+            let busy = busy.clone();
+            let error = error.clone();
+            let comment = comment.clone();
+            let on_update = on_update.clone();
+            busy.set(true);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match update_comment_content(comment.id, &text).await {
+                    Ok(updated_at) => {
+                        on_update.emit(CommentWithGravatar {
+                            content: text,
+                            updated_at: updated_at.or(comment.updated_at.clone()),
+                            ..comment
+                        });
+                        editing.set(false);
+                    }
+                    Err(e) => {
+                        error.set(Some(format!("Failed to update comment: {}", e)));
+                    }
+                }
+                busy.set(false);
+            });
+        })
+    };
+
+    let on_delete_click = {
+        let busy = busy.clone();
+        let error = error.clone();
+        let comment_id = comment.id;
+        let on_delete = props.on_delete.clone();
+        Callback::from(move |_| {
+            if !web_sys::window().unwrap().confirm_with_message("Delete this comment?").unwrap_or(false) {
+                return;
+            }
+
+            let busy = busy.clone();
+            let error = error.clone();
+            let on_delete = on_delete.clone();
+            busy.set(true);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match delete_comment(comment_id).await {
+                    Ok(_) => on_delete.emit(comment_id),
+                    Err(e) => {
+                        error.set(Some(format!("Failed to delete comment: {}", e)));
+                        busy.set(false);
+                    }
+                }
+            });
         })
-        .unwrap_or_else(|| "Just now".to_string());
+    };
 
     html! {
-        <div class="comment-bubble">
+        <div class={classes!("comment-bubble", props.pending.then_some("comment-pending"))}>
             <div class="comment-avatar">
-                <img 
-                    src={comment.gravatar_url.clone()} 
+                <img
+                    src={comment.gravatar_url.clone()}
                     alt={format!("{}'s avatar", author_name)}
                     class="avatar-image"
                 />
@@ -34,12 +138,64 @@ pub fn comment_item(props: &CommentItemProps) -> Html {
             <div class="comment-content">
                 <div class="comment-header">
                     <span class="comment-author">{author_name}</span>
-                    <span class="comment-time">{formatted_time}</span>
-                </div>
-                <div class="comment-text">
-                    {comment.content.clone()}
+                    {
+                        if props.pending {
+                            html! { <span class="comment-time comment-sending">{"Sending..."}</span> }
+                        } else {
+                            match comment.created_at.clone() {
+                                Some(created_at) => html! { <RelativeTime class="comment-time" value={created_at} /> },
+                                None => html! { <span class="comment-time">{"Just now"}</span> },
+                            }
+                        }
+                    }
+                    {
+                        if props.can_moderate && !props.pending && !*editing {
+                            html! {
+                                <span class="comment-actions">
+                                    <button class="btn-link" onclick={on_start_edit} disabled={*busy}>{"Edit"}</button>
+                                    <button class="btn-link btn-danger" onclick={on_delete_click} disabled={*busy}>{"Delete"}</button>
+                                </span>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
                 </div>
+                {
+                    if *editing {
+                        html! {
+                            <div class="comment-edit">
+                                <textarea
+                                    class="comment-textarea"
+                                    rows="3"
+                                    value={(*edit_text).clone()}
+                                    oninput={on_edit_input}
+                                    disabled={*busy}
+                                />
+                                <div class="comment-form-actions">
+                                    <button class="btn btn-secondary" onclick={on_cancel_edit} disabled={*busy}>{"Cancel"}</button>
+                                    <button class="btn btn-primary" onclick={on_save_edit} disabled={*busy}>
+                                        {if *busy { "Saving..." } else { "Save" }}
+                                    </button>
+                                </div>
+                            </div>
+                        }
+                    } else {
+                        html! {
+                            <div class="comment-text">
+                                {comment.content.clone()}
+                            </div>
+                        }
+                    }
+                }
+                {
+                    if let Some(message) = (*error).clone() {
+                        html! { <div class="comment-error">{message}</div> }
+                    } else {
+                        html! {}
+                    }
+                }
             </div>
         </div>
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,13 @@
 use yew::prelude::*;
+use std::collections::HashSet;
+use std::rc::Rc;
 use web_sys::HtmlTextAreaElement;
 use crate::components::comment_item::CommentItem;
-use crate::services::api_service::{CommentWithGravatar, PublicCommentRequest, get_post_comments, get_page_comments, create_public_comment};
+use crate::services::api_service::{ApiError, CommentLimits, CommentWithGravatar, PublicCommentRequest, get_post_comments, get_page_comments, get_comment_limits, create_public_comment};
 use crate::services::auth_service::{get_current_user, User};
+use crate::components::captcha::{Captcha, CaptchaSettings, load_captcha_settings};
 use crate::components::simple_notification::SimpleNotification;
+use crate::components::{RateLimitNotice, FALLBACK_RETRY_AFTER_SECS};
 
 #[derive(Properties, PartialEq)]
 pub struct CommentsSectionProps {
@@ -19,24 +23,142 @@ pub enum NotificationType {
     Info,
 }
 
+/// Comments currently shown, kept in display order and addressed by id so
+/// individual comments can be updated or removed without refetching.
+/// Optimistically added comments carry a temporary (negative) id and stay in
+/// `pending` until the server confirms or rejects them.
+#[derive(Clone, PartialEq, Default)]
+pub struct CommentList {
+    pub items: Vec<CommentWithGravatar>,
+    pub pending: HashSet<i32>,
+    /// New comments go at the top instead of the bottom
+    pub newest_first: bool,
+}
+
+impl CommentList {
+    pub fn is_pending(&self, id: i32) -> bool {
+        self.pending.contains(&id)
+    }
+}
+
+pub enum CommentListAction {
+    Set { comments: Vec<CommentWithGravatar>, newest_first: bool },
+    AddPending(CommentWithGravatar),
+    Confirm { temp_id: i32, comment: CommentWithGravatar },
+    Rollback(i32),
+    Update(CommentWithGravatar),
+    Remove(i32),
+}
+
+impl Reducible for CommentList {
+    type Action = CommentListAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut items = self.items.clone();
+        let mut pending = self.pending.clone();
+        let mut newest_first = self.newest_first;
+        match action {
+            CommentListAction::Set { comments, newest_first: newest } => {
+                // Keep in-flight comments visible across a reload
+                let in_flight: Vec<_> = items.into_iter().filter(|c| pending.contains(&c.id)).collect();
+                newest_first = newest;
+                if newest_first {
+                    items = in_flight;
+                    items.extend(comments);
+                } else {
+                    items = comments;
+                    items.extend(in_flight);
+                }
+            }
+            CommentListAction::AddPending(comment) => {
+                pending.insert(comment.id);
+                if newest_first {
+                    items.insert(0, comment);
+                } else {
+                    items.push(comment);
+                }
+            }
+            CommentListAction::Confirm { temp_id, comment } => {
+                pending.remove(&temp_id);
+                if let Some(existing) = items.iter_mut().find(|c| c.id == temp_id) {
+                    *existing = comment;
+                } else if newest_first {
+                    items.insert(0, comment);
+                } else {
+                    items.push(comment);
+                }
+            }
+            CommentListAction::Rollback(temp_id) => {
+                pending.remove(&temp_id);
+                items.retain(|c| c.id != temp_id);
+            }
+            CommentListAction::Update(comment) => {
+                if !pending.contains(&comment.id) {
+                    if let Some(existing) = items.iter_mut().find(|c| c.id == comment.id) {
+                        *existing = comment;
+                    }
+                }
+            }
+            CommentListAction::Remove(id) => {
+                if !pending.contains(&id) {
+                    items.retain(|c| c.id != id);
+                }
+            }
+        }
+        Rc::new(CommentList { items, pending, newest_first })
+    }
+}
+
+/// Count links the same way the server does: `http://`, `https://` and bare `www.`
+fn count_links(content: &str) -> usize {
+    let lower = content.to_lowercase();
+    let schemes = lower.matches("http://").count() + lower.matches("https://").count();
+    let bare_www = lower.match_indices("www.")
+        .filter(|(index, _)| !lower[..*index].ends_with("://"))
+        .count();
+    schemes + bare_www
+}
+
+fn is_staff(user: &User) -> bool {
+    user.role == "admin" || user.role == "editor"
+}
+
+/// Whether the site requires a verified email this user doesn't have yet
+fn needs_verification(limits: &CommentLimits, user: &User) -> bool {
+    limits.require_verified_email && user.email_verified == Some(false) && !is_staff(user)
+}
+
 #[function_component(CommentsSection)]
 pub fn comments_section(props: &CommentsSectionProps) -> Html {
-    let comments = use_state(Vec::<CommentWithGravatar>::new);
+    let comments = use_reducer(CommentList::default);
     let loading = use_state(|| true);
     let current_user = use_state(|| None::<User>);
     let comment_text = use_state(String::new);
-    let submitting = use_state(|| false);
+    let comment_limits = use_state(CommentLimits::default);
+    // The reader's choice of order; `None` follows the site default
+    let sort = use_state(|| None::<String>);
     let notification = use_state(|| None::<(String, NotificationType)>);
     let show_login_form = use_state(|| false);
     let show_signup_form = use_state(|| false);
+    let captcha_settings = use_state(|| None::<CaptchaSettings>);
+    let captcha_token = use_state(|| None::<String>);
+    // Bumped after each comment so a fresh widget replaces the used token
+    let captcha_key = use_state(|| 0u32);
+    // Seconds the server asked us to wait after a 429, and a key per refusal
+    let rate_limited = use_state(|| None::<u32>);
+    let rate_limit_key = use_state(|| 0u32);
     
     let comment_ref = use_node_ref();
+    // Source of temporary ids for optimistically added comments
+    let next_temp_id = use_mut_ref(|| 0i32);
 
-    // Load comments and current user on mount
+    // Load comments, comment limits and current user on mount
     {
         let comments = comments.clone();
         let loading = loading.clone();
         let current_user = current_user.clone();
+        let comment_limits = comment_limits.clone();
+        let captcha_settings = captcha_settings.clone();
         let post_id = props.post_id;
         let page_id = props.page_id;
 
@@ -44,20 +166,27 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
             let comments = comments.clone();
             let loading = loading.clone();
             let current_user = current_user.clone();
+            let comment_limits = comment_limits.clone();
+            let captcha_settings = captcha_settings.clone();
 
             wasm_bindgen_futures::spawn_local(async move {
-                // Load comments for either post or page
+                // Fall back to the default limits; the server still enforces its own
+                let limits = get_comment_limits().await.unwrap_or_default();
+                let newest_first = limits.default_sort == "newest";
+                comment_limits.set(limits);
+
+                // Load comments for either post or page, in the site's default order
                 let result = if let Some(post_id) = post_id {
-                    get_post_comments(post_id).await
+                    get_post_comments(post_id, None).await
                 } else if let Some(page_id) = page_id {
-                    get_page_comments(page_id).await
+                    get_page_comments(page_id, None).await
                 } else {
                     Ok(vec![]) // No ID provided, return empty
                 };
 
                 match result {
                     Ok(fetched_comments) => {
-                        comments.set(fetched_comments);
+                        comments.dispatch(CommentListAction::Set { comments: fetched_comments, newest_first });
                     }
                     Err(_) => {
                         // Handle error silently or show notification
@@ -67,6 +196,10 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
                 // Check if user is logged in
                 match get_current_user().await {
                     Ok(user) => {
+                        // Staff comment without a CAPTCHA
+                        if !is_staff(&user) {
+                            captcha_settings.set(load_captcha_settings().await);
+                        }
                         current_user.set(Some(user));
                     }
                     Err(_) => {
@@ -80,6 +213,40 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
         }, ());
     }
 
+    // Reload the thread when the reader picks another order
+    {
+        let comments = comments.clone();
+        let post_id = props.post_id;
+        let page_id = props.page_id;
+
+        use_effect_with_deps(move |sort: &Option<String>| {
+            if let Some(sort) = sort.clone() {
+                wasm_bindgen_futures::spawn_local(async move {
+                    let result = if let Some(post_id) = post_id {
+                        get_post_comments(post_id, Some(&sort)).await
+                    } else if let Some(page_id) = page_id {
+                        get_page_comments(page_id, Some(&sort)).await
+                    } else {
+                        Ok(vec![])
+                    };
+                    if let Ok(fetched_comments) = result {
+                        comments.dispatch(CommentListAction::Set { comments: fetched_comments, newest_first: sort == "newest" });
+                    }
+                });
+            }
+            || ()
+        }, (*sort).clone());
+    }
+
+    let on_sort_change = {
+        let sort = sort.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+            sort.set(Some(select.value()));
+        })
+    };
+    let current_sort = (*sort).clone().unwrap_or_else(|| comment_limits.default_sort.clone());
+
     let clear_notification = {
         let notification = notification.clone();
         Callback::from(move |_| {
@@ -95,6 +262,11 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
         })
     };
 
+    let comment_length = comment_text.trim().chars().count();
+    let comment_links = count_links(&comment_text);
+    let over_length = comment_length > comment_limits.max_length;
+    let over_links = comment_links > comment_limits.max_links;
+
     // Extract props fields to avoid lifetime issues
     let current_post_id = props.post_id;
     let current_page_id = props.page_id;
@@ -103,60 +275,136 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
         let comment_text = comment_text.clone();
         let current_user = current_user.clone();
         let comments = comments.clone();
-        let submitting = submitting.clone();
         let notification = notification.clone();
         let comment_ref = comment_ref.clone();
+        let next_temp_id = next_temp_id.clone();
+        let captcha_enabled = captcha_settings.is_some();
+        let captcha_token = captcha_token.clone();
+        let captcha_key = captcha_key.clone();
+        let rate_limited = rate_limited.clone();
+        let rate_limit_key = rate_limit_key.clone();
+        let gravatar = comment_limits.gravatar.clone();
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
 
+            if rate_limited.is_some() {
+                return;
+            }
             let text = (*comment_text).clone();
             if text.trim().is_empty() {
                 notification.set(Some(("Please enter a comment".to_string(), NotificationType::Error)));
                 return;
             }
+            if over_length || over_links {
+                notification.set(Some(("Your comment is over the length or link limit".to_string(), NotificationType::Error)));
+                return;
+            }
+            if captcha_enabled && captcha_token.is_none() {
+                notification.set(Some(("Please complete the CAPTCHA".to_string(), NotificationType::Error)));
+                return;
+            }
 
             if let Some(user) = (*current_user).clone() {
                 let comment_request = PublicCommentRequest {
                     content: text.trim().to_string(),
                     post_id: current_post_id,
                     page_id: current_page_id,
-                    user_id: user.id,
+                    captcha_token: (*captcha_token).clone(),
+                };
+                if captcha_enabled {
+                    captcha_token.set(None);
+                    captcha_key.set(*captcha_key + 1);
+                }
+
+                // Show the comment right away under a temporary id
+                let temp_id = {
+                    let mut next = next_temp_id.borrow_mut();
+                    *next -= 1;
+                    *next
                 };
+                comments.dispatch(CommentListAction::AddPending(CommentWithGravatar {
+                    id: temp_id,
+                    post_id: current_post_id,
+                    page_id: current_page_id,
+                    user_id: Some(user.id),
+                    content: comment_request.content.clone(),
+                    created_at: None,
+                    updated_at: None,
+                    author_username: Some(user.username.clone()),
+                    author_email: Some(user.email.clone()),
+                    gravatar_url: gravatar.url(&user.email, 80),
+                    status: String::new(),
+                }));
+
+                comment_text.set(String::new());
+                if let Some(textarea) = comment_ref.cast::<HtmlTextAreaElement>() {
+                    textarea.set_value("");
+                }
 
                 let comment_text = comment_text.clone();
                 let comments = comments.clone();
-                let submitting = submitting.clone();
                 let notification = notification.clone();
                 let comment_ref = comment_ref.clone();
-
-                submitting.set(true);
+                let rate_limited = rate_limited.clone();
+                let rate_limit_key = rate_limit_key.clone();
 
                 wasm_bindgen_futures::spawn_local(async move {
                     match create_public_comment(&comment_request).await {
+                        Ok(new_comment) if new_comment.status == "pending" => {
+                            // Held for moderation, so it isn't part of the thread yet
+                            comments.dispatch(CommentListAction::Rollback(temp_id));
+                            notification.set(Some(("Thanks! Your comment will appear once a moderator approves it.".to_string(), NotificationType::Info)));
+                        }
                         Ok(new_comment) => {
-                            let mut current_comments = (*comments).clone();
-                            current_comments.push(new_comment);
-                            comments.set(current_comments);
-                            comment_text.set(String::new());
-                            
-                            // Clear the textarea
-                            if let Some(textarea) = comment_ref.cast::<HtmlTextAreaElement>() {
-                                textarea.set_value("");
-                            }
-
+                            comments.dispatch(CommentListAction::Confirm { temp_id, comment: new_comment });
                             notification.set(Some(("Comment posted successfully!".to_string(), NotificationType::Success)));
                         }
                         Err(e) => {
-                            notification.set(Some((format!("Failed to post comment: {}", e), NotificationType::Error)));
+                            comments.dispatch(CommentListAction::Rollback(temp_id));
+
+                            // Give the text back so it isn't lost
+                            comment_text.set(comment_request.content.clone());
+                            if let Some(textarea) = comment_ref.cast::<HtmlTextAreaElement>() {
+                                textarea.set_value(&comment_request.content);
+                            }
+
+                            if let ApiError::RateLimited { retry_after, .. } = e {
+                                rate_limited.set(Some(retry_after.unwrap_or(FALLBACK_RETRY_AFTER_SECS)));
+                                rate_limit_key.set(*rate_limit_key + 1);
+                            } else {
+                                notification.set(Some((format!("Failed to post comment: {}", e), NotificationType::Error)));
+                            }
                         }
                     }
-                    submitting.set(false);
                 });
             }
         })
     };
 
+    let on_captcha_token = {
+        let captcha_token = captcha_token.clone();
+        Callback::from(move |token: Option<String>| captcha_token.set(token))
+    };
+
+    let on_comment_updated = {
+        let comments = comments.clone();
+        Callback::from(move |comment: CommentWithGravatar| {
+            comments.dispatch(CommentListAction::Update(comment));
+        })
+    };
+
+    let on_comment_deleted = {
+        let comments = comments.clone();
+        let notification = notification.clone();
+        Callback::from(move |comment_id: i32| {
+            comments.dispatch(CommentListAction::Remove(comment_id));
+            notification.set(Some(("Comment deleted".to_string(), NotificationType::Info)));
+        })
+    };
+
+    let can_moderate = current_user.as_ref().is_some_and(|u| u.permissions.iter().any(|p| p == "manage_content"));
+
     let toggle_login = {
         let show_login_form = show_login_form.clone();
         let show_signup_form = show_signup_form.clone();
@@ -186,7 +434,14 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
     html! {
         <div class="comments-section">
             <div class="comments-header">
-                <h3>{"Comments"} <span class="comment-count">{format!("({})", comments.len())}</span></h3>
+                <h3>{"Comments"} <span class="comment-count">{format!("({})", comments.items.len())}</span></h3>
+                <label class="comment-sort">
+                    {"Sort by "}
+                    <select onchange={on_sort_change}>
+                        <option value="oldest" selected={current_sort == "oldest"}>{"Oldest first"}</option>
+                        <option value="newest" selected={current_sort == "newest"}>{"Newest first"}</option>
+                    </select>
+                </label>
             </div>
 
             {
@@ -211,15 +466,12 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
             // Comment form
             <div class="comment-form-container">
                 {
-                    if let Some(user) = (*current_user).clone() {
+                    if let Some(user) = (*current_user).clone().filter(|user| !needs_verification(&comment_limits, user)) {
                         html! {
                             <form class="comment-form" onsubmit={submit_comment}>
                                 <div class="comment-form-header">
                                     <img 
-                                        src={format!("https://www.gravatar.com/avatar/{}?s=40&d=identicon&r=pg", 
-                                            md5::compute(user.email.to_lowercase().as_bytes()).iter()
-                                                .map(|b| format!("{:02x}", b)).collect::<String>()
-                                        )}
+                                        src={comment_limits.gravatar.url(&user.email, 40)}
                                         alt="Your avatar"
                                         class="comment-form-avatar"
                                     />
@@ -232,21 +484,46 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
                                         oninput={on_comment_change}
                                         placeholder="Share your thoughts..."
                                         rows="4"
-                                        disabled={*submitting}
                                         class="comment-textarea"
                                     />
                                 </div>
+                                if let Some(settings) = (*captcha_settings).clone() {
+                                    <Captcha key={*captcha_key} settings={settings} on_token={on_captcha_token} />
+                                }
+                                if let Some(retry_after) = *rate_limited {
+                                    <RateLimitNotice
+                                        key={*rate_limit_key}
+                                        retry_after={retry_after}
+                                        on_expire={let rate_limited = rate_limited.clone(); Callback::from(move |_| rate_limited.set(None))}
+                                    />
+                                }
                                 <div class="comment-form-actions">
+                                    <span class="comment-limits">
+                                        <span class={classes!("comment-limit-count", over_length.then_some("over-limit"))}>
+                                            {format!("{}/{}", comment_length, comment_limits.max_length)}
+                                        </span>
+                                        <span class={classes!("comment-limit-count", over_links.then_some("over-limit"))}>
+                                            {format!("{}/{} links", comment_links, comment_limits.max_links)}
+                                        </span>
+                                    </span>
                                     <button 
                                         type="submit" 
                                         class="btn btn-primary"
-                                        disabled={*submitting || comment_text.trim().is_empty()}
+                                        disabled={comment_text.trim().is_empty() || over_length || over_links || rate_limited.is_some()}
                                     >
-                                        {if *submitting { "Posting..." } else { "Post Comment" }}
+                                        {"Post Comment"}
                                     </button>
                                 </div>
                             </form>
                         }
+                    } else if current_user.is_some() {
+                        html! {
+                            <div class="comment-auth-prompt">
+                                <div class="auth-prompt-content">
+                                    <p>{"Verify your email address to join the conversation. Check your inbox for the verification link."}</p>
+                                </div>
+                            </div>
+                        }
                     } else {
                         html! {
                             <div class="comment-auth-prompt">
@@ -276,7 +553,7 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
             // Comments list
             <div class="comments-list">
                 {
-                    if comments.is_empty() {
+                    if comments.items.is_empty() {
                         html! {
                             <div class="no-comments">
                                 <p>{"No comments yet. Be the first to share your thoughts!"}</p>
@@ -285,9 +562,16 @@ pub fn comments_section(props: &CommentsSectionProps) -> Html {
                     } else {
                         html! {
                             <>
-                                {for comments.iter().map(|comment| {
+                                {for comments.items.iter().map(|comment| {
                                     html! {
-                                        <CommentItem comment={comment.clone()} />
+                                        <CommentItem
+                                            key={comment.id}
+                                            comment={comment.clone()}
+                                            pending={comments.is_pending(comment.id)}
+                                            can_moderate={can_moderate}
+                                            on_update={on_comment_updated.clone()}
+                                            on_delete={on_comment_deleted.clone()}
+                                        />
                                     }
                                 })}
                             </>
@@ -0,0 +1,111 @@
+use yew::prelude::*;
+use web_sys::HtmlInputElement;
+use crate::components::use_focus_trap;
+
+#[derive(Properties, PartialEq)]
+pub struct ConfirmDialogProps {
+    pub title: String,
+    pub message: String,
+    #[prop_or_else(|| "Confirm".to_string())]
+    pub confirm_label: String,
+    /// Style the confirm button as a destructive action
+    #[prop_or(true)]
+    pub danger: bool,
+    /// When set, the confirm button stays disabled until this exact text is typed
+    #[prop_or_default]
+    pub require_text: Option<String>,
+    pub on_confirm: Callback<()>,
+    pub on_cancel: Callback<()>,
+}
+
+/// Modal confirmation for destructive actions
+///
+/// Render it only while a confirmation is pending; cancelling via the
+/// button, the backdrop or Escape all call `on_cancel`. Focus stays in the
+/// dialog while it is open and returns to the opening control afterwards.
+#[function_component(ConfirmDialog)]
+pub fn confirm_dialog(props: &ConfirmDialogProps) -> Html {
+    let typed = use_state(String::new);
+    let dialog_ref = use_node_ref();
+
+    // High-risk actions start in the text field, the rest on Cancel
+    use_focus_trap(dialog_ref.clone(), true, props.on_cancel.clone());
+
+    let confirmed = props.require_text.as_ref().is_none_or(|text| *typed == *text);
+
+    let on_input = {
+        let typed = typed.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            typed.set(input.value());
+        })
+    };
+
+    let on_confirm = {
+        let on_confirm = props.on_confirm.clone();
+        Callback::from(move |_: MouseEvent| {
+            if confirmed {
+                on_confirm.emit(());
+            }
+        })
+    };
+
+    let on_cancel = {
+        let on_cancel = props.on_cancel.clone();
+        Callback::from(move |_: MouseEvent| on_cancel.emit(()))
+    };
+
+    // Enter confirms, except on a focused button, which Enter presses
+    let on_keydown = {
+        let on_confirm = props.on_confirm.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            let on_button = e.target_dyn_into::<web_sys::Element>().is_some_and(|target| target.tag_name() == "BUTTON");
+            if e.key() == "Enter" && confirmed && !on_button {
+                e.prevent_default();
+                on_confirm.emit(());
+            }
+        })
+    };
+
+    // Clicks inside the dialog must not reach the backdrop
+    let stop_propagation = Callback::from(|e: MouseEvent| e.stop_propagation());
+
+    html! {
+        <div class="confirm-dialog-overlay" onclick={on_cancel.clone()}>
+            <div
+                ref={dialog_ref}
+                class="confirm-dialog"
+                tabindex="-1"
+                role="alertdialog"
+                aria-modal="true"
+                onclick={stop_propagation}
+                onkeydown={on_keydown}
+            >
+                <h3 class="confirm-dialog-title">{&props.title}</h3>
+                <p class="confirm-dialog-message">{&props.message}</p>
+                if let Some(ref text) = props.require_text {
+                    <label class="confirm-dialog-prompt">
+                        {"Type "}<strong>{text}</strong>{" to confirm"}
+                        <input
+                            type="text"
+                            class="form-input"
+                            placeholder={text.clone()}
+                            value={(*typed).clone()}
+                            oninput={on_input}
+                        />
+                    </label>
+                }
+                <div class="confirm-dialog-actions">
+                    <button class="btn btn-secondary" onclick={on_cancel}>{"Cancel"}</button>
+                    <button
+                        class={classes!("btn", if props.danger { "btn-danger" } else { "btn-primary" })}
+                        onclick={on_confirm}
+                        disabled={!confirmed}
+                    >
+                        {&props.confirm_label}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
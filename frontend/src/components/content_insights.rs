@@ -0,0 +1,155 @@
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use yew::prelude::*;
+
+/// Average adult silent reading speed used for the reading time estimate
+const WORDS_PER_MINUTE: usize = 200;
+/// Title lengths search results show in full
+const TITLE_RANGE: (usize, usize) = (30, 60);
+/// Description lengths search results show in full
+const DESCRIPTION_RANGE: (usize, usize) = (70, 160);
+
+#[derive(Clone, Copy, PartialEq)]
+enum HintLevel {
+    Good,
+    Warning,
+    Problem,
+}
+
+impl HintLevel {
+    fn class(self) -> &'static str {
+        match self {
+            HintLevel::Good => "hint-good",
+            HintLevel::Warning => "hint-warning",
+            HintLevel::Problem => "hint-problem",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            HintLevel::Good => "Good",
+            HintLevel::Warning => "Could be better",
+            HintLevel::Problem => "Needs attention",
+        }
+    }
+}
+
+struct Hint {
+    level: HintLevel,
+    name: &'static str,
+    message: String,
+}
+
+/// What the counters and hints are worked out from
+struct ContentStats {
+    words: usize,
+    characters: usize,
+    /// Text of the first paragraph, which stands in for the description
+    opening: String,
+    has_image: bool,
+}
+
+fn content_stats(markdown: &str) -> ContentStats {
+    let mut text = String::new();
+    let mut opening = String::new();
+    let mut in_first_paragraph = false;
+    let mut seen_paragraph = false;
+    let mut has_image = false;
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Paragraph) if !seen_paragraph => in_first_paragraph = true,
+            Event::End(TagEnd::Paragraph) if in_first_paragraph => {
+                in_first_paragraph = false;
+                seen_paragraph = true;
+                text.push(' ');
+            }
+            Event::Start(Tag::Image { .. }) => has_image = true,
+            Event::Text(t) | Event::Code(t) => {
+                text.push_str(&t);
+                if in_first_paragraph {
+                    opening.push_str(&t);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                text.push(' ');
+                if in_first_paragraph {
+                    opening.push(' ');
+                }
+            }
+            Event::End(_) => text.push(' '),
+            // Raw HTML images count as an image too
+            Event::Html(html) | Event::InlineHtml(html) => has_image |= html.contains("<img"),
+            _ => {}
+        }
+    }
+
+    ContentStats {
+        words: text.split_whitespace().count(),
+        characters: text.split_whitespace().collect::<Vec<_>>().join(" ").chars().count(),
+        opening: opening.split_whitespace().collect::<Vec<_>>().join(" "),
+        has_image,
+    }
+}
+
+fn length_hint(name: &'static str, length: usize, (min, max): (usize, usize), subject: &str) -> Hint {
+    let (level, message) = if length == 0 {
+        (HintLevel::Problem, format!("No {} yet", subject))
+    } else if length < min {
+        (HintLevel::Warning, format!("{} characters; aim for {}–{}", length, min, max))
+    } else if length <= max {
+        (HintLevel::Good, format!("{} characters", length))
+    } else if length <= max + max / 5 {
+        (HintLevel::Warning, format!("{} characters; search results may cut it after {}", length, max))
+    } else {
+        (HintLevel::Problem, format!("{} characters; search results cut it after {}", length, max))
+    };
+    Hint { level, name, message }
+}
+
+fn seo_hints(title: &str, stats: &ContentStats) -> Vec<Hint> {
+    vec![
+        length_hint("Title length", title.trim().chars().count(), TITLE_RANGE, "title"),
+        // There is no separate description field; search engines and the
+        // feed excerpt use the opening text
+        length_hint("Description (first paragraph)", stats.opening.chars().count(), DESCRIPTION_RANGE, "opening paragraph"),
+        if stats.has_image {
+            Hint { level: HintLevel::Good, name: "Image", message: "The post has an image to show when shared".to_string() }
+        } else {
+            Hint { level: HintLevel::Warning, name: "Image", message: "Add an image so shares and previews have a picture".to_string() }
+        },
+    ]
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ContentInsightsProps {
+    pub title: String,
+    pub content: String,
+}
+
+/// Live word count, reading time and advisory SEO hints for the post editor.
+/// Hints never block saving.
+#[function_component(ContentInsights)]
+pub fn content_insights(props: &ContentInsightsProps) -> Html {
+    let stats = use_memo(|content| content_stats(content), props.content.clone());
+    let hints = seo_hints(&props.title, &stats);
+    let minutes = stats.words.div_ceil(WORDS_PER_MINUTE).max(1);
+
+    html! {
+        <div class="content-insights">
+            <div class="content-counters">
+                <span>{format!("{} {}", stats.words, if stats.words == 1 { "word" } else { "words" })}</span>
+                <span>{format!("{} characters", stats.characters)}</span>
+                <span>{format!("{} min read", minutes)}</span>
+            </div>
+            <ul class="seo-hints">
+                { for hints.into_iter().map(|hint| html! {
+                    <li class={classes!("seo-hint", hint.level.class())}>
+                        <span class="seo-hint-dot" role="img" title={hint.level.label()} aria-label={hint.level.label()}></span>
+                        <strong>{hint.name}</strong>
+                        {": "}{hint.message}
+                    </li>
+                }) }
+            </ul>
+        </div>
+    }
+}
@@ -0,0 +1,96 @@
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::HtmlElement;
+use yew::prelude::*;
+
+/// Elements Tab can reach inside a trapped container
+const FOCUSABLE: &str = "a[href], button:not([disabled]), input:not([disabled]):not([type=\"hidden\"]), \
+    select:not([disabled]), textarea:not([disabled]), [tabindex]:not([tabindex=\"-1\"])";
+
+fn focusable_in(container: &HtmlElement) -> Vec<HtmlElement> {
+    let Ok(nodes) = container.query_selector_all(FOCUSABLE) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|i| nodes.item(i))
+        .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+        // Skip anything hidden with display: none
+        .filter(|element| element.offset_width() > 0 || element.offset_height() > 0)
+        .collect()
+}
+
+/// Keep keyboard focus inside a modal or panel while it is open
+///
+/// While `active`, focus moves to the first control in `container` (or the
+/// container itself, which should have `tabindex="-1"`), Tab and Shift+Tab
+/// wrap around inside it, and Escape calls `on_escape`. When it closes,
+/// focus goes back to whatever had it before, usually the button that
+/// opened it.
+#[hook]
+pub fn use_focus_trap(container: NodeRef, active: bool, on_escape: Callback<()>) {
+    // The latest callback, so a new one each render doesn't rebind the listener
+    let on_escape_ref = use_mut_ref(|| on_escape.clone());
+    *on_escape_ref.borrow_mut() = on_escape;
+
+    use_effect_with_deps(move |active| {
+        let mut teardown: Option<Box<dyn FnOnce()>> = None;
+        let document = web_sys::window().and_then(|window| window.document());
+        if let (true, Some(document)) = (*active, document) {
+            let previously_focused = document.active_element()
+                .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+
+            if let Some(element) = container.cast::<HtmlElement>() {
+                match focusable_in(&element).first() {
+                    Some(first) => { let _ = first.focus(); }
+                    None => { let _ = element.focus(); }
+                }
+            }
+
+            let on_keydown = {
+                let container = container.clone();
+                Closure::<dyn Fn(KeyboardEvent)>::new(move |e: KeyboardEvent| {
+                    let Some(element) = container.cast::<HtmlElement>() else { return };
+                    match e.key().as_str() {
+                        "Escape" => {
+                            e.prevent_default();
+                            on_escape_ref.borrow().emit(());
+                        }
+                        "Tab" => {
+                            let focusable = focusable_in(&element);
+                            let (Some(first), Some(last)) = (focusable.first(), focusable.last()) else {
+                                e.prevent_default();
+                                let _ = element.focus();
+                                return;
+                            };
+                            let current = web_sys::window()
+                                .and_then(|window| window.document())
+                                .and_then(|document| document.active_element());
+                            let inside = current.as_ref().is_some_and(|current| element.contains(Some(current)));
+                            let is = |target: &HtmlElement| current.as_ref() == Some(target.as_ref());
+                            if e.shift_key() && (!inside || is(first) || is(&element)) {
+                                e.prevent_default();
+                                let _ = last.focus();
+                            } else if !e.shift_key() && (!inside || is(last)) {
+                                e.prevent_default();
+                                let _ = first.focus();
+                            }
+                        }
+                        _ => {}
+                    }
+                })
+            };
+            let _ = document.add_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref());
+
+            teardown = Some(Box::new(move || {
+                let _ = document.remove_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref());
+                if let Some(previous) = previously_focused.filter(|previous| previous.is_connected()) {
+                    let _ = previous.focus();
+                }
+            }));
+        }
+        move || {
+            if let Some(teardown) = teardown {
+                teardown();
+            }
+        }
+    }, active);
+}
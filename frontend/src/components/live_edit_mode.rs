@@ -6,6 +6,7 @@ use serde_json::json;
 
 use crate::services::navigation_service::{ComponentTemplate, update_component_template};
 use crate::services::api_service::{SettingData, update_settings};
+use crate::components::use_focus_trap;
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct LiveEditModeProps {
@@ -263,6 +264,17 @@ pub fn live_edit_mode(props: &LiveEditModeProps) -> Html {
         })
     };
 
+    // Keep keyboard focus in the panel while one area is being edited
+    let panel_ref = use_node_ref();
+    {
+        let selected_scope = selected_scope.clone();
+        use_focus_trap(
+            panel_ref.clone(),
+            props.enabled && selected_scope.is_some(),
+            Callback::from(move |_| selected_scope.set(None)),
+        );
+    }
+
     if !props.enabled { return html!{}; }
 
     html! {
@@ -309,10 +321,10 @@ pub fn live_edit_mode(props: &LiveEditModeProps) -> Html {
                         }),
                     };
                     html!{
-                        <div class="live-edit-panel" style="position: fixed; top: 40px; right: 8px; background: white; padding: 12px; border-radius: 8px; box-shadow: 0 6px 24px rgba(0,0,0,0.2); min-width: 260px;">
+                        <div ref={panel_ref.clone()} class="live-edit-panel" role="dialog" aria-label={format!("Edit {}", title)} tabindex="-1" style="position: fixed; top: 40px; right: 8px; background: white; padding: 12px; border-radius: 8px; box-shadow: 0 6px 24px rgba(0,0,0,0.2); min-width: 260px;">
                             <div style="display:flex; justify-content: space-between; align-items: center; margin-bottom: 8px;">
                                 <strong>{format!("Edit {}", title)}</strong>
-                                <button onclick={on_close_panel.clone()}>{"×"}</button>
+                                <button type="button" aria-label="Close the editor" onclick={on_close_panel.clone()}>{"×"}</button>
                             </div>
                             <div style="display: grid; gap: 6px;">{body}</div>
                             <div style="display:flex; justify-content: flex-end; gap: 8px; margin-top: 10px;">
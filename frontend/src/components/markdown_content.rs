@@ -0,0 +1,229 @@
+use yew::prelude::*;
+use gloo_timers::callback::Timeout;
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, HeadingLevel, Parser, Tag, TagEnd};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+
+/// How long the copy confirmation stays on the button
+const COPY_FEEDBACK_MS: u32 = 2000;
+
+/// Deepest heading level listed in the table of contents
+const TOC_MAX_LEVEL: HeadingLevel = HeadingLevel::H3;
+
+/// A heading in the table of contents, linking to its anchor id
+#[derive(Clone, PartialEq, Debug)]
+pub struct TocEntry {
+    pub level: u8,
+    pub id: String,
+    pub title: String,
+}
+
+/// Lowercase ASCII slug for a heading anchor, e.g. "Getting Started!" -> "getting-started"
+fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for ch in text.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() { "section".to_string() } else { slug.to_string() }
+}
+
+/// Parse markdown, giving every heading a slugified id unique within the
+/// document, and collect the headings for the table of contents
+fn parse_with_heading_ids(content: &str) -> (Vec<Event<'_>>, Vec<TocEntry>) {
+    let mut events: Vec<Event> = Parser::new(content).collect();
+    let mut toc = Vec::new();
+    let mut used_ids = std::collections::HashSet::new();
+
+    for start in 0..events.len() {
+        let Event::Start(Tag::Heading { level, .. }) = &events[start] else { continue };
+        let level = *level;
+        let title: String = events[start + 1..].iter()
+            .take_while(|event| !matches!(event, Event::End(TagEnd::Heading(_))))
+            .filter_map(|event| match event {
+                Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+                _ => None,
+            })
+            .collect();
+
+        let base = slugify(&title);
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while !used_ids.insert(id.clone()) {
+            id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+
+        if let Event::Start(Tag::Heading { id: heading_id, .. }) = &mut events[start] {
+            *heading_id = Some(CowStr::from(id.clone()));
+        }
+        if level <= TOC_MAX_LEVEL {
+            toc.push(TocEntry { level: level as u8, id, title });
+        }
+    }
+    (events, toc)
+}
+
+/// Headings of a markdown document, with the anchor ids `MarkdownContent` gives them
+pub fn table_of_contents(content: &str) -> Vec<TocEntry> {
+    parse_with_heading_ids(content).1
+}
+
+/// A piece of rendered markdown: plain HTML, or a code block that gets its
+/// own component so it can carry a copy button
+enum Segment {
+    Html(String),
+    Code { language: Option<String>, code: String },
+}
+
+/// Split markdown into HTML runs and top-level fenced or indented code blocks
+///
+/// Code blocks nested in lists or quotes stay in the HTML, since cutting
+/// there would leave unbalanced markup on either side.
+fn segments(content: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut pending: Vec<Event> = Vec::new();
+    let mut depth = 0usize;
+    let mut code: Option<(Option<String>, String)> = None;
+
+    let flush = |pending: &mut Vec<Event>, segments: &mut Vec<Segment>| {
+        if !pending.is_empty() {
+            let mut html_output = String::new();
+            html::push_html(&mut html_output, pending.drain(..));
+            segments.push(Segment::Html(html_output));
+        }
+    };
+
+    for event in parse_with_heading_ids(content).0 {
+        if let Some((_, ref mut text)) = code {
+            match event {
+                Event::Text(chunk) => text.push_str(&chunk),
+                Event::End(TagEnd::CodeBlock) => {
+                    let (language, code) = code.take().unwrap_or_default();
+                    segments.push(Segment::Code { language, code });
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) if depth == 0 => {
+                flush(&mut pending, &mut segments);
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().map(str::to_string),
+                    CodeBlockKind::Indented => None,
+                };
+                code = Some((language, String::new()));
+            }
+            Event::Start(_) => {
+                depth += 1;
+                pending.push(event);
+            }
+            Event::End(_) => {
+                depth = depth.saturating_sub(1);
+                pending.push(event);
+            }
+            _ => pending.push(event),
+        }
+    }
+    flush(&mut pending, &mut segments);
+    segments
+}
+
+/// Write `text` to the clipboard; fails when the API is unavailable (e.g. on
+/// plain HTTP) or permission is denied
+async fn copy_to_clipboard(text: &str) -> Result<(), JsValue> {
+    let navigator = web_sys::window().ok_or(JsValue::NULL)?.navigator();
+    let clipboard = js_sys::Reflect::get(&navigator, &JsValue::from_str("clipboard"))?;
+    if clipboard.is_undefined() {
+        return Err(JsValue::from_str("Clipboard API unavailable"));
+    }
+    JsFuture::from(navigator.clipboard().write_text(text)).await.map(|_| ())
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CodeBlockProps {
+    pub code: String,
+    #[prop_or_default]
+    pub language: Option<String>,
+}
+
+/// A code block with a button that copies its raw text
+#[function_component(CodeBlock)]
+pub fn code_block(props: &CodeBlockProps) -> Html {
+    // Some(true) after a successful copy, Some(false) after a failure
+    let copied = use_state(|| None::<bool>);
+
+    {
+        let status = *copied;
+        let copied = copied.clone();
+        use_effect_with_deps(move |status: &Option<bool>| {
+            let timeout = status.map(|_| Timeout::new(COPY_FEEDBACK_MS, move || copied.set(None)));
+            move || drop(timeout)
+        }, status);
+    }
+
+    let on_copy = {
+        let copied = copied.clone();
+        let code = props.code.clone();
+        Callback::from(move |_: MouseEvent| {
+            let copied = copied.clone();
+            let code = code.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = copy_to_clipboard(&code).await;
+                if let Err(e) = &result {
+                    web_sys::console::warn_1(&format!("Copy to clipboard failed: {:?}", e).into());
+                }
+                copied.set(Some(result.is_ok()));
+            });
+        })
+    };
+
+    let label = match *copied {
+        Some(true) => "Copied!",
+        Some(false) => "Copy failed",
+        None => "Copy",
+    };
+    let code_class = props.language.as_ref().map(|language| format!("language-{}", language));
+
+    html! {
+        <div class="code-block">
+            <button
+                type="button"
+                class={classes!("code-copy-btn", copied.map(|ok| if ok { "copied" } else { "failed" }))}
+                onclick={on_copy}
+                aria-label="Copy code to clipboard"
+            >
+                {label}
+            </button>
+            <pre><code class={code_class}>{&props.code}</code></pre>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MarkdownContentProps {
+    pub content: String,
+}
+
+/// Rendered markdown with copy buttons on its code blocks
+#[function_component(MarkdownContent)]
+pub fn markdown_content(props: &MarkdownContentProps) -> Html {
+    let segments = use_memo(|content: &String| segments(content), props.content.clone());
+
+    html! {
+        <>
+            {for segments.iter().map(|segment| match segment {
+                Segment::Html(html_output) => Html::from_html_unchecked(html_output.clone().into()),
+                Segment::Code { language, code } => html! {
+                    <CodeBlock code={code.clone()} language={language.clone()} />
+                },
+            })}
+        </>
+    }
+}
@@ -1,6 +1,13 @@
 use yew::prelude::*;
-use web_sys::HtmlTextAreaElement;
+use gloo_timers::callback::Timeout;
+use wasm_bindgen::JsCast;
+use web_sys::{ClipboardEvent, File, FileList, HtmlTextAreaElement};
 use pulldown_cmark::{Parser, html};
+use crate::components::MediaPicker;
+use crate::services::api_service::{upload_media, MediaItem};
+
+/// Delay after the last keystroke before the preview re-renders
+const PREVIEW_DEBOUNCE_MS: u32 = 250;
 
 #[derive(Properties, PartialEq)]
 pub struct MarkdownEditorProps {
@@ -10,10 +17,149 @@ pub struct MarkdownEditorProps {
     pub rows: Option<u32>,
 }
 
+/// A toolbar edit applied to the textarea's current selection
+#[derive(Clone)]
+enum Format {
+    /// Surround the selection, or a placeholder when nothing is selected
+    Wrap { before: String, after: String, placeholder: &'static str },
+    /// Prefix the line the selection starts on
+    LinePrefix(&'static str),
+    /// Replace the selection, leaving the cursor after the new text
+    Insert(String),
+}
+
+impl Format {
+    fn wrap(before: &str, after: &str, placeholder: &'static str) -> Self {
+        Self::Wrap { before: before.to_string(), after: after.to_string(), placeholder }
+    }
+}
+
+/// Result of a toolbar edit: the new text and the range to select, in chars
+struct Edit {
+    value: String,
+    select: (usize, usize),
+}
+
+fn render_markdown(source: &str) -> String {
+    let mut html_output = String::new();
+    html::push_html(&mut html_output, Parser::new(source));
+    html_output
+}
+
+/// Byte offset of the `chars`-th character, clamped to the end of `s`
+fn byte_index(s: &str, chars: usize) -> usize {
+    s.char_indices().nth(chars).map(|(i, _)| i).unwrap_or(s.len())
+}
+
+/// Convert a textarea selection offset (UTF-16 code units) to a char offset
+fn utf16_to_char_offset(s: &str, utf16: u32) -> usize {
+    let mut units = 0;
+    for (chars, c) in s.chars().enumerate() {
+        if units >= utf16 as usize {
+            return chars;
+        }
+        units += c.len_utf16();
+    }
+    s.chars().count()
+}
+
+fn char_to_utf16_offset(s: &str, chars: usize) -> u32 {
+    s.chars().take(chars).map(|c| c.len_utf16() as u32).sum()
+}
+
+/// Apply a toolbar format to `value` with `start..end` selected (char offsets)
+fn apply_format(value: &str, start: usize, end: usize, format: Format) -> Edit {
+    let (start, end) = (start.min(end), start.max(end));
+    let (start_byte, end_byte) = (byte_index(value, start), byte_index(value, end));
+    match format {
+        Format::Wrap { before, after, placeholder } => {
+            let selected = &value[start_byte..end_byte];
+            let inner = if selected.is_empty() { placeholder } else { selected };
+            let mut new_value = String::with_capacity(value.len() + before.len() + after.len() + inner.len());
+            new_value.push_str(&value[..start_byte]);
+            new_value.push_str(&before);
+            new_value.push_str(inner);
+            new_value.push_str(&after);
+            new_value.push_str(&value[end_byte..]);
+            let inner_start = start + before.chars().count();
+            Edit { value: new_value, select: (inner_start, inner_start + inner.chars().count()) }
+        }
+        Format::LinePrefix(prefix) => {
+            let line_start = value[..start_byte].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let mut new_value = value.to_string();
+            new_value.insert_str(line_start, prefix);
+            let shift = prefix.chars().count();
+            Edit { value: new_value, select: (start + shift, end + shift) }
+        }
+        Format::Insert(text) => {
+            let new_value = format!("{}{}{}", &value[..start_byte], text, &value[end_byte..]);
+            let cursor = start + text.chars().count();
+            Edit { value: new_value, select: (cursor, cursor) }
+        }
+    }
+}
+
+/// Image files from a paste or drop, in order
+fn image_files(files: Option<FileList>) -> Vec<File> {
+    let Some(files) = files else { return Vec::new() };
+    (0..files.length())
+        .filter_map(|i| files.get(i))
+        .filter(|file| file.type_().starts_with("image/"))
+        .collect()
+}
+
+/// Alt text suggestion from a file name, e.g. "team-photo.png" -> "team photo"
+fn alt_from_file_name(name: &str) -> String {
+    let stem = name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name);
+    stem.replace(['-', '_'], " ").trim().to_string()
+}
+
+/// Swap `placeholder` for `replacement` in the textarea, keeping the
+/// cursor where the user left it
+fn replace_placeholder(textarea_ref: &NodeRef, on_change: &Callback<String>, placeholder: &str, replacement: &str) {
+    let Some(textarea) = textarea_ref.cast::<HtmlTextAreaElement>() else { return };
+    let value = textarea.value();
+    let Some(at) = value.find(placeholder) else { return };
+
+    let placeholder_at = value[..at].encode_utf16().count() as u32;
+    let placeholder_len = placeholder.encode_utf16().count() as i64;
+    let delta = replacement.encode_utf16().count() as i64 - placeholder_len;
+    let shift = |pos: u32| {
+        if pos as i64 >= placeholder_at as i64 + placeholder_len { (pos as i64 + delta) as u32 } else { pos.min(placeholder_at) }
+    };
+    let start = textarea.selection_start().ok().flatten().unwrap_or(0);
+    let end = textarea.selection_end().ok().flatten().unwrap_or(0);
+
+    let new_value = value.replacen(placeholder, replacement, 1);
+    textarea.set_value(&new_value);
+    let _ = textarea.set_selection_start(Some(shift(start)));
+    let _ = textarea.set_selection_end(Some(shift(end)));
+    on_change.emit(new_value);
+}
+
 #[function_component(MarkdownEditor)]
 pub fn markdown_editor(props: &MarkdownEditorProps) -> Html {
     let show_preview = use_state(|| false);
+    let show_media_picker = use_state(|| false);
     let textarea_ref = use_node_ref();
+    let preview_source = use_state(|| props.value.clone());
+    let file_drag_over = use_state(|| false);
+    let next_upload_id = use_mut_ref(|| 0u32);
+
+    // Re-render the preview once typing pauses; dropping the pending
+    // timeout on each change restarts the wait
+    {
+        let preview_source = preview_source.clone();
+        use_effect_with_deps(move |(value, show_preview): &(String, bool)| {
+            let timeout = show_preview.then(|| {
+                let value = value.clone();
+                Timeout::new(PREVIEW_DEBOUNCE_MS, move || preview_source.set(value))
+            });
+            move || drop(timeout)
+        }, (props.value.clone(), *show_preview));
+    }
+
+    let preview_html = use_memo(|source: &String| render_markdown(source), (*preview_source).clone());
 
     let on_input = {
         let on_change = props.on_change.clone();
@@ -25,145 +171,192 @@ pub fn markdown_editor(props: &MarkdownEditorProps) -> Html {
 
     let toggle_preview = {
         let show_preview = show_preview.clone();
+        let preview_source = preview_source.clone();
+        let value = props.value.clone();
         Callback::from(move |_| {
+            // Opening shows the current text straight away
+            if !*show_preview {
+                preview_source.set(value.clone());
+            }
             show_preview.set(!*show_preview);
         })
     };
 
-    let insert_text = {
+    // Edit the textarea directly so the selection survives, then report the
+    // new text; the textarea stays the source of truth
+    let apply = {
         let textarea_ref = textarea_ref.clone();
         let on_change = props.on_change.clone();
-        let value = props.value.clone();
-        
-        Callback::from(move |text: String| {
-            if let Some(textarea) = textarea_ref.cast::<HtmlTextAreaElement>() {
-                let start = textarea.selection_start().unwrap_or(Some(0)).unwrap_or(0) as usize;
-                let end = textarea.selection_end().unwrap_or(Some(0)).unwrap_or(0) as usize;
-                
-                let mut new_value = value.clone();
-                new_value.replace_range(start..end, &text);
-                
-                on_change.emit(new_value);
-                
-                // Set cursor position after inserted text
-                let new_pos = (start + text.len()) as u32;
-                let _ = textarea.set_selection_start(Some(new_pos));
-                let _ = textarea.set_selection_end(Some(new_pos));
-                let _ = textarea.focus();
-            }
+        Callback::from(move |format: Format| {
+            let Some(textarea) = textarea_ref.cast::<HtmlTextAreaElement>() else { return };
+            let value = textarea.value();
+            let start = utf16_to_char_offset(&value, textarea.selection_start().ok().flatten().unwrap_or(0));
+            let end = utf16_to_char_offset(&value, textarea.selection_end().ok().flatten().unwrap_or(0));
+
+            let edit = apply_format(&value, start, end, format);
+            textarea.set_value(&edit.value);
+            let _ = textarea.set_selection_start(Some(char_to_utf16_offset(&edit.value, edit.select.0)));
+            let _ = textarea.set_selection_end(Some(char_to_utf16_offset(&edit.value, edit.select.1)));
+            let _ = textarea.focus();
+            on_change.emit(edit.value);
         })
     };
 
-    let on_bold = {
-        let insert_text = insert_text.clone();
-        Callback::from(move |_| {
-            insert_text.emit("**bold text**".to_string());
-        })
+    let format_button = |format: Format, title: &'static str, label: Html| {
+        let apply = apply.clone();
+        html! {
+            <button type="button" class="toolbar-btn" onclick={Callback::from(move |_| apply.emit(format.clone()))} {title}>
+                {label}
+            </button>
+        }
     };
 
-    let on_italic = {
-        let insert_text = insert_text.clone();
-        Callback::from(move |_| {
-            insert_text.emit("*italic text*".to_string());
-        })
+    let open_media_picker = {
+        let show_media_picker = show_media_picker.clone();
+        Callback::from(move |_| show_media_picker.set(true))
     };
 
-    let on_header = {
-        let insert_text = insert_text.clone();
-        Callback::from(move |_| {
-            insert_text.emit("## Header".to_string());
+    let close_media_picker = {
+        let show_media_picker = show_media_picker.clone();
+        Callback::from(move |_| show_media_picker.set(false))
+    };
+
+    // Insert a placeholder per image at the cursor, then upload them in turn,
+    // swapping each placeholder for the final markdown or an error marker
+    let upload_images = {
+        let apply = apply.clone();
+        let textarea_ref = textarea_ref.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |files: Vec<File>| {
+            let uploads: Vec<(String, File)> = files.into_iter().map(|file| {
+                let mut next_id = next_upload_id.borrow_mut();
+                *next_id += 1;
+                (format!("![Uploading {}…](#upload-{})", file.name(), *next_id), file)
+            }).collect();
+            let placeholders: Vec<&str> = uploads.iter().map(|(placeholder, _)| placeholder.as_str()).collect();
+            apply.emit(Format::Insert(placeholders.join("\n")));
+
+            let textarea_ref = textarea_ref.clone();
+            let on_change = on_change.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                for (placeholder, file) in uploads {
+                    let replacement = match upload_media(&file).await {
+                        Ok(media) => format!("![{}]({})", alt_from_file_name(&file.name()), media.url),
+                        Err(e) => format!("[Upload failed: {} ({})]", file.name(), e),
+                    };
+                    replace_placeholder(&textarea_ref, &on_change, &placeholder, &replacement);
+                }
+            });
         })
     };
 
-    let on_link = {
-        let insert_text = insert_text.clone();
-        Callback::from(move |_| {
-            insert_text.emit("[link text](https://example.com)".to_string());
+    let on_paste = {
+        let upload_images = upload_images.clone();
+        Callback::from(move |e: Event| {
+            let Some(e) = e.dyn_ref::<ClipboardEvent>() else { return };
+            let files = image_files(e.clipboard_data().and_then(|data| data.files()));
+            // Plain text pastes keep the browser's default behaviour
+            if !files.is_empty() {
+                e.prevent_default();
+                upload_images.emit(files);
+            }
         })
     };
 
-    let on_list = {
-        let insert_text = insert_text.clone();
-        Callback::from(move |_| {
-            insert_text.emit("- List item".to_string());
+    let on_dragover = {
+        let file_drag_over = file_drag_over.clone();
+        Callback::from(move |e: DragEvent| {
+            let has_files = e.data_transfer()
+                .is_some_and(|data| data.types().includes(&"Files".into(), 0));
+            if has_files {
+                e.prevent_default();
+                if !*file_drag_over {
+                    file_drag_over.set(true);
+                }
+            }
         })
     };
 
-    let on_code = {
-        let insert_text = insert_text.clone();
-        Callback::from(move |_| {
-            insert_text.emit("`code`".to_string());
+    let on_dragleave = {
+        let file_drag_over = file_drag_over.clone();
+        Callback::from(move |_: DragEvent| file_drag_over.set(false))
+    };
+
+    let on_drop = {
+        let file_drag_over = file_drag_over.clone();
+        let upload_images = upload_images.clone();
+        Callback::from(move |e: DragEvent| {
+            file_drag_over.set(false);
+            let files = image_files(e.data_transfer().and_then(|data| data.files()));
+            if !files.is_empty() {
+                e.prevent_default();
+                upload_images.emit(files);
+            }
         })
     };
 
-    // Convert markdown to HTML for preview
-    let html_content = if *show_preview {
-        let parser = Parser::new(&props.value);
-        let mut html_output = String::new();
-        html::push_html(&mut html_output, parser);
-        html_output
-    } else {
-        String::new()
+    // The selected text (or a placeholder, left selected) becomes the alt text
+    let on_media_select = {
+        let show_media_picker = show_media_picker.clone();
+        let apply = apply.clone();
+        Callback::from(move |media: MediaItem| {
+            show_media_picker.set(false);
+            apply.emit(Format::wrap("![", &format!("]({})", media.url), "alt text"));
+        })
     };
 
     html! {
         <div class="markdown-editor">
             <div class="markdown-toolbar">
                 <div class="toolbar-group">
-                    <button type="button" class="toolbar-btn" onclick={on_bold} title="Bold">
-                        <strong>{"B"}</strong>
-                    </button>
-                    <button type="button" class="toolbar-btn" onclick={on_italic} title="Italic">
-                        <em>{"I"}</em>
-                    </button>
-                    <button type="button" class="toolbar-btn" onclick={on_header} title="Header">
-                        {"H"}
-                    </button>
+                    {format_button(Format::wrap("**", "**", "bold text"), "Bold", html! { <strong>{"B"}</strong> })}
+                    {format_button(Format::wrap("*", "*", "italic text"), "Italic", html! { <em>{"I"}</em> })}
+                    {format_button(Format::LinePrefix("## "), "Heading", html! { {"H"} })}
                 </div>
-                
+
                 <div class="toolbar-group">
-                    <button type="button" class="toolbar-btn" onclick={on_link} title="Link">
-                        {"🔗"}
-                    </button>
-                    <button type="button" class="toolbar-btn" onclick={on_list} title="List">
-                        {"• List"}
-                    </button>
-                    <button type="button" class="toolbar-btn" onclick={on_code} title="Code">
-                        {"<>"}
+                    {format_button(Format::wrap("[", "](https://example.com)", "link text"), "Link", html! { {"🔗"} })}
+                    {format_button(Format::LinePrefix("- "), "List", html! { {"• List"} })}
+                    {format_button(Format::wrap("`", "`", "code"), "Code", html! { {"<>"} })}
+                    <button type="button" class="toolbar-btn" onclick={open_media_picker} title="Insert image from media library">
+                        {"🖼 Image"}
                     </button>
                 </div>
-                
+
                 <div class="toolbar-group">
-                    <button 
-                        type="button" 
+                    <button
+                        type="button"
                         class={if *show_preview { "toolbar-btn active" } else { "toolbar-btn" }}
                         onclick={toggle_preview}
-                        title="Toggle Preview"
+                        title="Toggle live preview"
                     >
-                        {if *show_preview { "📝 Edit" } else { "👁 Preview" }}
+                        {if *show_preview { "Hide Preview" } else { "👁 Preview" }}
                     </button>
                 </div>
             </div>
 
-            <div class="markdown-content">
+            <div class={classes!("markdown-content", show_preview.then_some("split"))}>
+                <textarea
+                    ref={textarea_ref}
+                    class={classes!("markdown-textarea", file_drag_over.then_some("drop-target"))}
+                    value={props.value.clone()}
+                    oninput={on_input}
+                    onpaste={on_paste}
+                    ondragover={on_dragover}
+                    ondragleave={on_dragleave}
+                    ondrop={on_drop}
+                    placeholder={props.placeholder.clone().unwrap_or_else(|| "Write your content in Markdown...".to_string())}
+                    rows={props.rows.unwrap_or(15).to_string()}
+                />
                 if *show_preview {
                     <div class="markdown-preview">
                         <div class="preview-content">
-                            {Html::from_html_unchecked(html_content.into())}
+                            {Html::from_html_unchecked((*preview_html).clone().into())}
                         </div>
                     </div>
-                } else {
-                    <textarea
-                        ref={textarea_ref}
-                        class="markdown-textarea"
-                        value={props.value.clone()}
-                        oninput={on_input}
-                        placeholder={props.placeholder.clone().unwrap_or_else(|| "Write your content in Markdown...".to_string())}
-                        rows={props.rows.unwrap_or(15).to_string()}
-                    />
                 }
             </div>
-            
+
             <div class="markdown-help">
                 <details>
                     <summary>{"Markdown Help"}</summary>
@@ -174,12 +367,20 @@ pub fn markdown_editor(props: &MarkdownEditorProps) -> Html {
                             <li><code>{"*italic*"}</code>{" for "}<em>{"italic"}</em></li>
                             <li><code>{"# Header 1"}</code>{" for headers"}</li>
                             <li><code>{"[text](url)"}</code>{" for links"}</li>
+                            <li><code>{"![alt text](url)"}</code>{" for images (or paste or drop an image to upload it)"}</li>
                             <li><code>{"- item"}</code>{" for lists"}</li>
                             <li><code>{"`code`"}</code>{" for inline code"}</li>
                         </ul>
                     </div>
                 </details>
             </div>
+
+            <MediaPicker
+                show={*show_media_picker}
+                filter_images_only={true}
+                on_select={on_media_select}
+                on_close={close_media_picker}
+            />
         </div>
     }
-}
\ No newline at end of file
+}
@@ -2,6 +2,7 @@ use yew::prelude::*;
 use crate::services::api_service::{get_media, MediaItem};
 use web_sys::MouseEvent;
 use wasm_bindgen::JsCast;
+use crate::components::use_focus_trap;
 
 #[derive(Properties, PartialEq)]
 pub struct MediaPickerProps {
@@ -92,10 +93,15 @@ pub fn media_picker(props: &MediaPickerProps) -> Html {
         })
     };
 
+    let modal_ref = use_node_ref();
+    use_focus_trap(modal_ref.clone(), props.show, props.on_close.clone());
+
     if !props.show {
         return html! {};
     }
 
+    let title = if props.filter_images_only { "Select Image" } else { "Select Video" };
+
     html! {
         <div class="media-picker-backdrop" onclick={backdrop_click} style="
             position: fixed;
@@ -110,7 +116,7 @@ pub fn media_picker(props: &MediaPickerProps) -> Html {
             justify-content: center;
             padding: 20px;
         ">
-            <div class="media-picker-modal" style="
+            <div ref={modal_ref} class="media-picker-modal" role="dialog" aria-modal="true" aria-label={title} tabindex="-1" style="
                 background: white;
                 border-radius: 12px;
                 box-shadow: 0 25px 50px rgba(0, 0, 0, 0.25);
@@ -129,9 +135,11 @@ pub fn media_picker(props: &MediaPickerProps) -> Html {
                     justify-content: space-between;
                 ">
                     <h3 style="margin: 0; font-size: 18px; font-weight: 600; color: #333;">
-                        {if props.filter_images_only { "Select Image" } else { "Select Video" }}
+                        {title}
                     </h3>
                     <button 
+                        type="button"
+                        aria-label="Close"
                         onclick={let on_close = props.on_close.clone(); Callback::from(move |_| on_close.emit(()))}
                         style="
                             background: none;
@@ -231,12 +239,27 @@ pub fn media_picker(props: &MediaPickerProps) -> Html {
                                 {for filtered_items.iter().map(|item| {
                                     let item_clone = item.clone();
                                     let on_select = props.on_select.clone();
+                                    // Items are picked with Enter or Space as well as a click
+                                    let on_keydown = {
+                                        let item = item.clone();
+                                        let on_select = on_select.clone();
+                                        Callback::from(move |e: KeyboardEvent| {
+                                            if e.key() == "Enter" || e.key() == " " {
+                                                e.prevent_default();
+                                                on_select.emit(item.clone());
+                                            }
+                                        })
+                                    };
                                     
                                     html! {
                                         <div 
                                             key={item.id.unwrap_or(0)}
                                             class="media-item" 
+                                            role="button"
+                                            tabindex="0"
+                                            aria-label={item.name.clone()}
                                             onclick={Callback::from(move |_| on_select.emit(item_clone.clone()))}
+                                            onkeydown={on_keydown}
                                             style="
                                                 border: 1px solid #e1e5e9;
                                                 border-radius: 8px;
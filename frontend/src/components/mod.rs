@@ -2,18 +2,27 @@ pub mod header;
 pub mod sidebar;
 pub mod notification;
 pub mod simple_notification;
+pub mod confirm_dialog;
 mod public_layout;
 mod media_picker;
 mod live_edit_mode;
 // mod hamburger_menu; // Temporarily removed
 pub mod admin;
 pub mod auth_guard;
+pub mod change_password_form;
 pub mod posts_list_widget;
 pub mod markdown_editor;
 pub mod page_builder;
 pub mod performance_monitor;
 pub mod comment_item;
 pub mod comments_section;
+pub mod relative_time;
+pub mod markdown_content;
+pub mod table_of_contents;
+pub mod captcha;
+pub mod focus_trap;
+pub mod content_insights;
+pub mod rate_limit_notice;
 
 // Export essential components that are used across the app
 pub use sidebar::ActiveTab;
@@ -26,3 +35,9 @@ pub use performance_monitor::PerformanceMonitor;
 pub use media_picker::MediaPicker;
 // pub use comment_item::CommentItem; // Used internally by CommentsSection
 pub use comments_section::CommentsSection;
+pub use confirm_dialog::ConfirmDialog;
+pub use relative_time::RelativeTime;
+pub use markdown_content::MarkdownContent;
+pub use table_of_contents::TableOfContents;
+pub use focus_trap::use_focus_trap;
+pub use rate_limit_notice::{RateLimitNotice, FALLBACK_RETRY_AFTER_SECS};
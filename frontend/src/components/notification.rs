@@ -1,7 +1,8 @@
 use yew::prelude::*;
+use gloo_timers::callback::Timeout;
 
 #[derive(Clone, PartialEq)]
-#[allow(dead_code)]
+#[allow(dead_code)] // Warning is not raised anywhere yet
 pub enum NotificationType {
     Success,
     Error,
@@ -10,7 +11,6 @@ pub enum NotificationType {
 }
 
 #[derive(Clone, PartialEq)]
-#[allow(dead_code)]
 pub struct Notification {
     pub id: String,
     pub message: String,
@@ -19,7 +19,6 @@ pub struct Notification {
 }
 
 #[derive(Properties, PartialEq)]
-#[allow(dead_code)]
 pub struct NotificationProps {
     pub notification: Notification,
     pub on_close: Callback<String>,
@@ -40,6 +39,16 @@ pub fn notification_item(props: &NotificationProps) -> Html {
         Callback::from(move |_| on_close.emit(notification_id.clone()))
     };
 
+    // Auto-dismiss once mounted; dropping the timeout on unmount cancels it
+    {
+        let notification_id = props.notification.id.clone();
+        let on_close = props.on_close.clone();
+        use_effect_with_deps(move |duration: &Option<u32>| {
+            let timeout = duration.map(|ms| Timeout::new(ms, move || on_close.emit(notification_id)));
+            move || drop(timeout)
+        }, props.notification.duration);
+    }
+
     html! {
         <div class={classes!("notification", notification_type_class)}>
             <div class="notification-content">
@@ -51,7 +60,6 @@ pub fn notification_item(props: &NotificationProps) -> Html {
 }
 
 #[derive(Properties, PartialEq)]
-#[allow(dead_code)]
 pub struct NotificationContainerProps {
     pub notifications: Vec<Notification>,
     pub on_close: Callback<String>,
@@ -64,6 +72,7 @@ pub fn notification_container(props: &NotificationContainerProps) -> Html {
             {props.notifications.iter().map(|notification| {
                 html! {
                     <NotificationItem
+                        key={notification.id.clone()}
                         notification={notification.clone()}
                         on_close={props.on_close.clone()}
                     />
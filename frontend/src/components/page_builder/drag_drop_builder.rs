@@ -4,7 +4,7 @@ use wasm_bindgen::JsCast;
 use serde::{Deserialize, Serialize};
 use crate::components::markdown_editor::MarkdownEditor;
 use crate::components::MediaPicker;
-use crate::services::api_service::MediaItem;
+use crate::services::api_service::{get_categories, Category, MediaItem};
 use crate::services::navigation_service::{get_component_templates, get_all_component_templates_admin, ComponentTemplate};
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -164,6 +164,9 @@ pub struct ComponentProperties {
     pub posts_list_show_date: bool,
     pub posts_list_show_excerpt: bool,
     pub posts_list_show_view_all: bool,
+    /// Only posts of this category; `None` for all categories
+    pub posts_list_category_id: Option<i32>,
+    pub posts_list_layout: String, // "grid", "list"
     
     // Comments specific properties
     pub comments_enabled: bool,
@@ -397,6 +400,8 @@ impl Default for ComponentProperties {
             posts_list_show_date: true,
             posts_list_show_excerpt: true,
             posts_list_show_view_all: true,
+            posts_list_category_id: None,
+            posts_list_layout: "grid".to_string(),
             
             // Form specific
             form_action: "/submit".to_string(),
@@ -732,6 +737,20 @@ pub fn drag_drop_page_builder(props: &DragDropPageBuilderProps) -> Html {
 
     // Cache component templates for default config lookups (e.g., Sidebar)
     let component_templates = use_state(Vec::<ComponentTemplate>::new);
+    // Categories a Posts List block can be limited to
+    let categories = use_state(Vec::<Category>::new);
+
+    {
+        let categories = categories.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Ok(loaded) = get_categories().await {
+                    categories.set(loaded);
+                }
+            });
+            || ()
+        }, ());
+    }
 
     {
         let component_templates = component_templates.clone();
@@ -1169,6 +1188,8 @@ pub fn drag_drop_page_builder(props: &DragDropPageBuilderProps) -> Html {
                             component.properties.posts_list_excerpt_length = length;
                         }
                     },
+                    "posts_list_category_id" => component.properties.posts_list_category_id = property_value.parse::<i32>().ok(),
+                    "posts_list_layout" => component.properties.posts_list_layout = property_value,
                     _ => {}
                 }
             }
@@ -3478,6 +3499,45 @@ pub fn drag_drop_page_builder(props: &DragDropPageBuilderProps) -> Html {
                                                         <h4 class="section-title">{"Posts List Properties"}</h4>
                                                         
                                                         // Layout & Display Options
+                                                        <div class="property-group">
+                                                            <label>{"Category"}</label>
+                                                            <select 
+                                                                onchange={{
+                                                                    let on_property_update = on_property_update.clone();
+                                                                    let component_id = component.id.clone();
+                                                                    Callback::from(move |e: Event| {
+                                                                        let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                                        on_property_update.emit((component_id.clone(), "posts_list_category_id".to_string(), target.value()));
+                                                                    })
+                                                                }}
+                                                            >
+                                                                <option value="" selected={component.properties.posts_list_category_id.is_none()}>{"All Categories"}</option>
+                                                                {for categories.iter().map(|category| html! {
+                                                                    <option value={category.id.to_string()} selected={component.properties.posts_list_category_id == Some(category.id)}>
+                                                                        {&category.name}
+                                                                    </option>
+                                                                })}
+                                                            </select>
+                                                        </div>
+                                                        
+                                                        <div class="property-group">
+                                                            <label>{"Layout"}</label>
+                                                            <select 
+                                                                value={component.properties.posts_list_layout.clone()}
+                                                                onchange={{
+                                                                    let on_property_update = on_property_update.clone();
+                                                                    let component_id = component.id.clone();
+                                                                    Callback::from(move |e: Event| {
+                                                                        let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                                        on_property_update.emit((component_id.clone(), "posts_list_layout".to_string(), target.value()));
+                                                                    })
+                                                                }}
+                                                            >
+                                                                <option value="grid">{"Grid"}</option>
+                                                                <option value="list">{"List (one post per row)"}</option>
+                                                            </select>
+                                                        </div>
+                                                        
                                                         <div class="property-group">
                                                             <label>{"Grid Columns"}</label>
                                                             <select 
@@ -4750,7 +4810,12 @@ fn render_component_content(component: &PageComponent) -> Html {
                         <span style="font-size: 48px; display: block; margin-bottom: 12px;">{"📄"}</span>
                         <h3 style="margin: 0 0 8px 0; font-size: 18px; color: var(--public-text-primary, #333);">{"Posts List Component"}</h3>
                         <p style="margin: 0; font-size: 14px; line-height: 1.5;">
-                            {"This will display your latest blog posts in a grid layout. "}
+                            {format!(
+                                "This will display your {} latest {}posts in a {} layout. ",
+                                component.properties.posts_list_count,
+                                if component.properties.posts_list_category_id.is_some() { "category " } else { "" },
+                                if component.properties.posts_list_layout == "list" { "list" } else { "grid" },
+                            )}
                             {"The actual posts from your CMS will appear here when published on your site."}
                         </p>
                     </div>
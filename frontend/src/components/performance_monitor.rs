@@ -1,7 +1,92 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
 use yew::prelude::*;
 use crate::services::api_service::{get_performance_metrics, PerformanceMetrics, BackendMetrics, FrontendMetrics, SystemMetrics};
 use crate::services::performance_service::get_performance_service;
 
+/// How often the monitor polls while live updates are on
+const POLL_INTERVAL_MS: u32 = 5000;
+/// Samples kept for the trend charts (10 minutes at the poll interval)
+const HISTORY_LENGTH: usize = 120;
+
+/// One polled reading of the values charted over time
+#[derive(Clone, PartialEq)]
+pub struct MetricsSample {
+    pub p50_request_time: f64,
+    pub p95_request_time: f64,
+    pub p99_request_time: f64,
+    pub db_connection_pool_active: u32,
+    pub memory_usage_mb: f64,
+}
+
+impl From<&BackendMetrics> for MetricsSample {
+    fn from(metrics: &BackendMetrics) -> Self {
+        Self {
+            p50_request_time: metrics.p50_request_time,
+            p95_request_time: metrics.p95_request_time,
+            p99_request_time: metrics.p99_request_time,
+            db_connection_pool_active: metrics.db_connection_pool_active,
+            memory_usage_mb: metrics.memory_usage_mb,
+        }
+    }
+}
+
+type MetricsHistory = Rc<RefCell<VecDeque<MetricsSample>>>;
+
+/// Fetch backend metrics, merge in client-side tracking and record a sample
+async fn load_metrics(
+    performance_data: UseStateHandle<Option<PerformanceMetrics>>,
+    history: MetricsHistory,
+    error: UseStateHandle<Option<String>>,
+    last_updated: UseStateHandle<String>,
+) {
+    match get_performance_metrics().await {
+        Ok(mut metrics) => {
+            // Merge with frontend tracking data if available
+            if let Some(perf_service) = get_performance_service() {
+                let _ = perf_service.update_all_metrics();
+                let frontend_metrics = perf_service.get_metrics();
+                let network_metrics = perf_service.get_network_metrics();
+                let avg_render_time = perf_service.get_avg_component_render_time();
+                
+                // Update frontend metrics with real tracked data
+                metrics.frontend_metrics.page_load_time = frontend_metrics.page_load_time;
+                metrics.frontend_metrics.time_to_interactive = frontend_metrics.time_to_interactive;
+                metrics.frontend_metrics.first_contentful_paint = frontend_metrics.first_contentful_paint;
+                metrics.frontend_metrics.largest_contentful_paint = frontend_metrics.largest_contentful_paint;
+                metrics.frontend_metrics.cumulative_layout_shift = frontend_metrics.cumulative_layout_shift;
+                metrics.frontend_metrics.dom_nodes_count = frontend_metrics.dom_nodes_count;
+                metrics.frontend_metrics.memory_usage_js_mb = frontend_metrics.memory_usage;
+                metrics.frontend_metrics.wasm_bundle_size_kb = frontend_metrics.wasm_bundle_size;
+                metrics.frontend_metrics.network_request_avg_time = network_metrics.avg_request_time;
+                metrics.frontend_metrics.component_render_avg_time = avg_render_time;
+            }
+
+            {
+                let mut history = history.borrow_mut();
+                if history.len() == HISTORY_LENGTH {
+                    history.pop_front();
+                }
+                history.push_back(MetricsSample::from(&metrics.backend_metrics));
+            }
+            
+            performance_data.set(Some(metrics));
+            let now = js_sys::Date::new_0();
+            last_updated.set(format!("{:02}:{:02}:{:02}", 
+                now.get_hours(),
+                now.get_minutes(), 
+                now.get_seconds()
+            ));
+            error.set(None);
+        }
+        Err(e) => {
+            // Keep the last good reading on screen and report the failure
+            error.set(Some(format!("Failed to load performance metrics: {}", e)));
+        }
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct PerformanceMonitorProps {
     pub show_real_time: bool,
@@ -10,14 +95,15 @@ pub struct PerformanceMonitorProps {
 #[function_component(PerformanceMonitor)]
 pub fn performance_monitor(props: &PerformanceMonitorProps) -> Html {
     let performance_data = use_state(|| None::<PerformanceMetrics>);
+    let history = use_mut_ref(|| VecDeque::<MetricsSample>::with_capacity(HISTORY_LENGTH));
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
     let last_updated = use_state(|| "Never".to_string());
-    let _show_real_time = props.show_real_time;
 
     // Load performance data
     {
         let performance_data = performance_data.clone();
+        let history = history.clone();
         let loading = loading.clone();
         let error = error.clone();
         let last_updated = last_updated.clone();
@@ -25,306 +111,60 @@ pub fn performance_monitor(props: &PerformanceMonitorProps) -> Html {
         use_effect_with_deps(move |_| {
             wasm_bindgen_futures::spawn_local(async move {
                 loading.set(true);
-                error.set(None);
-
-                match get_performance_metrics().await {
-                    Ok(mut metrics) => {
-                        // Merge with frontend tracking data if available
-                        if let Some(perf_service) = get_performance_service() {
-                            let _ = perf_service.update_all_metrics();
-                            let frontend_metrics = perf_service.get_metrics();
-                            let network_metrics = perf_service.get_network_metrics();
-                            let avg_render_time = perf_service.get_avg_component_render_time();
-                            
-                            // Update frontend metrics with real tracked data
-                            metrics.frontend_metrics.page_load_time = frontend_metrics.page_load_time;
-                            metrics.frontend_metrics.time_to_interactive = frontend_metrics.time_to_interactive;
-                            metrics.frontend_metrics.first_contentful_paint = frontend_metrics.first_contentful_paint;
-                            metrics.frontend_metrics.largest_contentful_paint = frontend_metrics.largest_contentful_paint;
-                            metrics.frontend_metrics.cumulative_layout_shift = frontend_metrics.cumulative_layout_shift;
-                            metrics.frontend_metrics.dom_nodes_count = frontend_metrics.dom_nodes_count;
-                            metrics.frontend_metrics.memory_usage_js_mb = frontend_metrics.memory_usage;
-                            metrics.frontend_metrics.wasm_bundle_size_kb = frontend_metrics.wasm_bundle_size;
-                            metrics.frontend_metrics.network_request_avg_time = network_metrics.avg_request_time;
-                            metrics.frontend_metrics.component_render_avg_time = avg_render_time;
-                        }
-                        
-                        performance_data.set(Some(metrics));
-                        let now = js_sys::Date::new_0();
-                        last_updated.set(format!("{:02}:{:02}:{:02}", 
-                            now.get_hours(),
-                            now.get_minutes(), 
-                            now.get_seconds()
-                        ));
-                    }
-                    Err(_e) => {
-                        // Show mock data when API is unavailable (for demo purposes)
-                        use crate::services::api_service::{PerformanceMetrics, BackendMetrics, FrontendMetrics, SystemMetrics};
-                        
-                        let mock_metrics = PerformanceMetrics {
-                            backend_metrics: BackendMetrics {
-                                avg_request_time: 120.5,
-                                max_request_time: 450.2,
-                                min_request_time: 45.1,
-                                total_requests: 15420,
-                                error_rate: 1.2,
-                                db_query_avg_time: 35.8,
-                                db_connection_pool_active: 8,
-                                db_connection_pool_idle: 12,
-                                memory_usage_mb: 256.7,
-                                active_sessions: 42,
-                                session_avg_duration: 1800.0,
-                            },
-                            frontend_metrics: FrontendMetrics {
-                                wasm_bundle_size_kb: 1250.4,
-                                page_load_time: 2.1,
-                                time_to_interactive: 2.8,
-                                first_contentful_paint: 1.2,
-                                largest_contentful_paint: 2.4,
-                                cumulative_layout_shift: 0.08,
-                                network_request_avg_time: 180.5,
-                                component_render_avg_time: 12.3,
-                                dom_nodes_count: 1850,
-                                memory_usage_js_mb: 45.6,
-                            },
-                            system_metrics: SystemMetrics {
-                                cpu_usage_percent: 15.3,
-                                memory_total_mb: 16384.0,
-                                memory_available_mb: 8192.0,
-                                disk_usage_percent: 62.8,
-                                network_io_bytes_per_sec: 1024.0,
-                                uptime_seconds: 345600, // 4 days
-                            },
-                        };
-                        
-                        performance_data.set(Some(mock_metrics));
-                        let now = js_sys::Date::new_0();
-                        last_updated.set(format!("{:02}:{:02}:{:02}", 
-                            now.get_hours(),
-                            now.get_minutes(), 
-                            now.get_seconds()
-                        ));
-                        
-                        // Clear any previous errors since we're showing mock data
-                        error.set(None);
-                    }
-                }
-
+                load_metrics(performance_data, history, error, last_updated).await;
                 loading.set(false);
             });
             || ()
         }, ());
     }
 
-    // Real-time updates (always enabled for now)
+    // Real-time updates
     {
         let performance_data = performance_data.clone();
-        let last_updated = last_updated.clone();
+        let history = history.clone();
         let error = error.clone();
+        let last_updated = last_updated.clone();
 
-        use_effect_with_deps(move |_| {
-            let interval = gloo_timers::callback::Interval::new(5000, move || {
-                let performance_data = performance_data.clone();
-                let last_updated = last_updated.clone();
-                let error = error.clone();
-
-                wasm_bindgen_futures::spawn_local(async move {
-                    match get_performance_metrics().await {
-                        Ok(mut metrics) => {
-                            // Merge with frontend tracking data if available
-                            if let Some(perf_service) = get_performance_service() {
-                                let _ = perf_service.update_all_metrics();
-                                let frontend_metrics = perf_service.get_metrics();
-                                let network_metrics = perf_service.get_network_metrics();
-                                let avg_render_time = perf_service.get_avg_component_render_time();
-                                
-                                // Update frontend metrics with real tracked data
-                                metrics.frontend_metrics.page_load_time = frontend_metrics.page_load_time;
-                                metrics.frontend_metrics.time_to_interactive = frontend_metrics.time_to_interactive;
-                                metrics.frontend_metrics.first_contentful_paint = frontend_metrics.first_contentful_paint;
-                                metrics.frontend_metrics.largest_contentful_paint = frontend_metrics.largest_contentful_paint;
-                                metrics.frontend_metrics.cumulative_layout_shift = frontend_metrics.cumulative_layout_shift;
-                                metrics.frontend_metrics.dom_nodes_count = frontend_metrics.dom_nodes_count;
-                                metrics.frontend_metrics.memory_usage_js_mb = frontend_metrics.memory_usage;
-                                metrics.frontend_metrics.wasm_bundle_size_kb = frontend_metrics.wasm_bundle_size;
-                                metrics.frontend_metrics.network_request_avg_time = network_metrics.avg_request_time;
-                                metrics.frontend_metrics.component_render_avg_time = avg_render_time;
-                            }
-                            
-                            performance_data.set(Some(metrics));
-                            let now = js_sys::Date::new_0();
-                            last_updated.set(format!("{:02}:{:02}:{:02}", 
-                                now.get_hours(),
-                                now.get_minutes(), 
-                                now.get_seconds()
-                            ));
-                            error.set(None);
-                        }
-                        Err(_e) => {
-                            // Show mock data for real-time updates when API is unavailable
-                            use crate::services::api_service::{PerformanceMetrics, BackendMetrics, FrontendMetrics, SystemMetrics};
-                            
-                            // Generate slightly varying mock data for real-time effect
-                            let cpu_variation = (js_sys::Math::random() * 10.0) - 5.0;
-                            let memory_variation = (js_sys::Math::random() * 100.0) - 50.0;
-                            
-                            let mock_metrics = PerformanceMetrics {
-                                backend_metrics: BackendMetrics {
-                                    avg_request_time: 120.5 + (js_sys::Math::random() * 20.0) - 10.0,
-                                    max_request_time: 450.2,
-                                    min_request_time: 45.1,
-                                    total_requests: 15420 + (js_sys::Math::random() * 10.0) as u64,
-                                    error_rate: 1.2,
-                                    db_query_avg_time: 35.8 + (js_sys::Math::random() * 5.0) - 2.5,
-                                    db_connection_pool_active: 8,
-                                    db_connection_pool_idle: 12,
-                                    memory_usage_mb: 256.7 + memory_variation,
-                                    active_sessions: 42,
-                                    session_avg_duration: 1800.0,
-                                },
-                                frontend_metrics: FrontendMetrics {
-                                    wasm_bundle_size_kb: 1250.4,
-                                    page_load_time: 2.1,
-                                    time_to_interactive: 2.8,
-                                    first_contentful_paint: 1.2,
-                                    largest_contentful_paint: 2.4,
-                                    cumulative_layout_shift: 0.08,
-                                    network_request_avg_time: 180.5 + (js_sys::Math::random() * 50.0) - 25.0,
-                                    component_render_avg_time: 12.3,
-                                    dom_nodes_count: 1850,
-                                    memory_usage_js_mb: 45.6,
-                                },
-                                system_metrics: SystemMetrics {
-                                    cpu_usage_percent: 15.3 + cpu_variation,
-                                    memory_total_mb: 16384.0,
-                                    memory_available_mb: 8192.0 + memory_variation,
-                                    disk_usage_percent: 62.8,
-                                    network_io_bytes_per_sec: 1024.0 + (js_sys::Math::random() * 512.0),
-                                    uptime_seconds: 345600, // 4 days
-                                },
-                            };
-                            
-                            performance_data.set(Some(mock_metrics));
-                            let now = js_sys::Date::new_0();
-                            last_updated.set(format!("{:02}:{:02}:{:02}", 
-                                now.get_hours(),
-                                now.get_minutes(), 
-                                now.get_seconds()
-                            ));
-                            error.set(None);
-                        }
-                    }
-                });
+        use_effect_with_deps(move |show_real_time| {
+            let interval = show_real_time.then(|| {
+                gloo_timers::callback::Interval::new(POLL_INTERVAL_MS, move || {
+                    wasm_bindgen_futures::spawn_local(load_metrics(
+                        performance_data.clone(),
+                        history.clone(),
+                        error.clone(),
+                        last_updated.clone(),
+                    ));
+                })
             });
 
             move || drop(interval)
-        }, ());
+        }, props.show_real_time);
     }
 
     let refresh_data = {
         let performance_data = performance_data.clone();
+        let history = history.clone();
         let loading = loading.clone();
         let error = error.clone();
         let last_updated = last_updated.clone();
         
         Callback::from(move |_| {
             let performance_data = performance_data.clone();
+            let history = history.clone();
             let loading = loading.clone();
             let error = error.clone();
             let last_updated = last_updated.clone();
             
             loading.set(true);
-            error.set(None);
-            
             wasm_bindgen_futures::spawn_local(async move {
-                match get_performance_metrics().await {
-                    Ok(mut metrics) => {
-                        // Merge with frontend tracking data if available
-                        if let Some(perf_service) = get_performance_service() {
-                            let _ = perf_service.update_all_metrics();
-                            let frontend_metrics = perf_service.get_metrics();
-                            let network_metrics = perf_service.get_network_metrics();
-                            let avg_render_time = perf_service.get_avg_component_render_time();
-                            
-                            // Update frontend metrics with real tracked data
-                            metrics.frontend_metrics.page_load_time = frontend_metrics.page_load_time;
-                            metrics.frontend_metrics.time_to_interactive = frontend_metrics.time_to_interactive;
-                            metrics.frontend_metrics.first_contentful_paint = frontend_metrics.first_contentful_paint;
-                            metrics.frontend_metrics.largest_contentful_paint = frontend_metrics.largest_contentful_paint;
-                            metrics.frontend_metrics.cumulative_layout_shift = frontend_metrics.cumulative_layout_shift;
-                            metrics.frontend_metrics.dom_nodes_count = frontend_metrics.dom_nodes_count;
-                            metrics.frontend_metrics.memory_usage_js_mb = frontend_metrics.memory_usage;
-                            metrics.frontend_metrics.wasm_bundle_size_kb = frontend_metrics.wasm_bundle_size;
-                            metrics.frontend_metrics.network_request_avg_time = network_metrics.avg_request_time;
-                            metrics.frontend_metrics.component_render_avg_time = avg_render_time;
-                        }
-                        
-                        performance_data.set(Some(metrics));
-                        let now = js_sys::Date::new_0();
-                        last_updated.set(format!("{:02}:{:02}:{:02}", 
-                            now.get_hours(),
-                            now.get_minutes(), 
-                            now.get_seconds()
-                        ));
-                    }
-                    Err(_e) => {
-                        // Show mock data when API is unavailable (for demo purposes)
-                        use crate::services::api_service::{PerformanceMetrics, BackendMetrics, FrontendMetrics, SystemMetrics};
-                        
-                        let mock_metrics = PerformanceMetrics {
-                            backend_metrics: BackendMetrics {
-                                avg_request_time: 120.5,
-                                max_request_time: 450.2,
-                                min_request_time: 45.1,
-                                total_requests: 15420,
-                                error_rate: 1.2,
-                                db_query_avg_time: 35.8,
-                                db_connection_pool_active: 8,
-                                db_connection_pool_idle: 12,
-                                memory_usage_mb: 256.7,
-                                active_sessions: 42,
-                                session_avg_duration: 1800.0,
-                            },
-                            frontend_metrics: FrontendMetrics {
-                                wasm_bundle_size_kb: 1250.4,
-                                page_load_time: 2.1,
-                                time_to_interactive: 2.8,
-                                first_contentful_paint: 1.2,
-                                largest_contentful_paint: 2.4,
-                                cumulative_layout_shift: 0.08,
-                                network_request_avg_time: 180.5,
-                                component_render_avg_time: 12.3,
-                                dom_nodes_count: 1850,
-                                memory_usage_js_mb: 45.6,
-                            },
-                            system_metrics: SystemMetrics {
-                                cpu_usage_percent: 15.3,
-                                memory_total_mb: 16384.0,
-                                memory_available_mb: 8192.0,
-                                disk_usage_percent: 62.8,
-                                network_io_bytes_per_sec: 1024.0,
-                                uptime_seconds: 345600, // 4 days
-                            },
-                        };
-                        
-                        performance_data.set(Some(mock_metrics));
-                        let now = js_sys::Date::new_0();
-                        last_updated.set(format!("{:02}:{:02}:{:02}", 
-                            now.get_hours(),
-                            now.get_minutes(), 
-                            now.get_seconds()
-                        ));
-                        
-                        // Clear any previous errors since we're showing mock data
-                        error.set(None);
-                    }
-                }
-                
+                load_metrics(performance_data, history, error, last_updated).await;
                 loading.set(false);
             });
         })
     };
 
+    let samples: Vec<MetricsSample> = history.borrow().iter().cloned().collect();
+
     if *loading {
         html! {
             <div class="performance-monitor">
@@ -360,6 +200,7 @@ pub fn performance_monitor(props: &PerformanceMonitorProps) -> Html {
                     html! {
                         <div class="performance-grid">
                             <BackendMetricsComponent metrics={metrics.backend_metrics.clone()} />
+                            <MetricsTrends samples={samples} />
                             <FrontendMetricsComponent metrics={metrics.frontend_metrics.clone()} />
                             <SystemMetricsComponent metrics={metrics.system_metrics.clone()} />
                         </div>
@@ -396,6 +237,13 @@ pub fn backend_metrics_component(props: &BackendMetricsProps) -> Html {
                     status={if metrics.avg_request_time < 100.0 { "good" } else if metrics.avg_request_time < 500.0 { "warning" } else { "critical" }}
                 />
                 
+                <MetricCard
+                    title="Latency Percentiles"
+                    value={format!("{:.1}ms", metrics.p95_request_time)}
+                    subtitle={format!("p50: {:.1}ms, p95: {:.1}ms, p99: {:.1}ms", metrics.p50_request_time, metrics.p95_request_time, metrics.p99_request_time)}
+                    status={if metrics.p95_request_time < 250.0 { "good" } else if metrics.p95_request_time < 1000.0 { "warning" } else { "critical" }}
+                />
+                
                 <MetricCard
                     title="Total Requests"
                     value={metrics.total_requests.to_string()}
@@ -406,14 +254,21 @@ pub fn backend_metrics_component(props: &BackendMetricsProps) -> Html {
                 <MetricCard
                     title="Database Query Time"
                     value={format!("{:.2}ms", metrics.db_query_avg_time)}
-                    subtitle="Average query time"
+                    subtitle={format!("Average query time, p95: {:.1}ms", metrics.db_query_p95_time)}
                     status={if metrics.db_query_avg_time < 50.0 { "good" } else if metrics.db_query_avg_time < 200.0 { "warning" } else { "critical" }}
                 />
                 
                 <MetricCard
                     title="DB Connections"
                     value={format!("{}/{}", metrics.db_connection_pool_active, metrics.db_connection_pool_active + metrics.db_connection_pool_idle)}
-                    subtitle="Active/Total connections"
+                    subtitle={format!("Active/Open connections, pool max {}", metrics.db_connection_pool_max)}
+                    status={if metrics.db_connection_pool_max == 0 || metrics.db_connection_pool_active * 4 < metrics.db_connection_pool_max * 3 { "good" } else if metrics.db_connection_pool_active < metrics.db_connection_pool_max { "warning" } else { "critical" }}
+                />
+                
+                <MetricCard
+                    title="Cache Hit Rate"
+                    value={metrics.cache_hit_rate.map(|rate| format!("{:.1}%", rate)).unwrap_or_else(|| "N/A".to_string())}
+                    subtitle={if metrics.cache_hit_rate.is_some() { "Bootstrap requests served from cache" } else { "Not reported by the server" }}
                     status="info"
                 />
                 
@@ -435,6 +290,108 @@ pub fn backend_metrics_component(props: &BackendMetricsProps) -> Html {
     }
 }
 
+#[derive(Properties, PartialEq)]
+pub struct MetricsTrendsProps {
+    pub samples: Vec<MetricsSample>,
+}
+
+#[function_component(MetricsTrends)]
+pub fn metrics_trends(props: &MetricsTrendsProps) -> Html {
+    let samples = &props.samples;
+    let series = |value: fn(&MetricsSample) -> f64| samples.iter().map(value).collect::<Vec<f64>>();
+    
+    html! {
+        <div class="metrics-section metrics-trends">
+            <h3>{"Trends"}</h3>
+            if samples.len() < 2 {
+                <p class="metric-subtitle">{"Collecting samples..."}</p>
+            } else {
+                <div class="metrics-grid">
+                    <TrendChart
+                        title="Request Latency"
+                        unit="ms"
+                        series={vec![
+                            TrendSeries { label: "p50".to_string(), class: "series-p50".to_string(), values: series(|s| s.p50_request_time) },
+                            TrendSeries { label: "p95".to_string(), class: "series-p95".to_string(), values: series(|s| s.p95_request_time) },
+                            TrendSeries { label: "p99".to_string(), class: "series-p99".to_string(), values: series(|s| s.p99_request_time) },
+                        ]}
+                    />
+                    <TrendChart
+                        title="Active DB Connections"
+                        unit=""
+                        series={vec![
+                            TrendSeries { label: "active".to_string(), class: "series-p50".to_string(), values: series(|s| s.db_connection_pool_active as f64) },
+                        ]}
+                    />
+                    <TrendChart
+                        title="Backend Memory"
+                        unit="MB"
+                        series={vec![
+                            TrendSeries { label: "resident".to_string(), class: "series-p50".to_string(), values: series(|s| s.memory_usage_mb) },
+                        ]}
+                    />
+                </div>
+            }
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TrendSeries {
+    pub label: String,
+    pub class: String,
+    pub values: Vec<f64>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TrendChartProps {
+    pub title: String,
+    pub unit: String,
+    pub series: Vec<TrendSeries>,
+}
+
+/// Line chart of recent samples, scaled to the largest value shown
+#[function_component(TrendChart)]
+pub fn trend_chart(props: &TrendChartProps) -> Html {
+    const WIDTH: f64 = 300.0;
+    const HEIGHT: f64 = 80.0;
+
+    let peak = props.series.iter()
+        .flat_map(|s| s.values.iter().copied())
+        .fold(0.0_f64, f64::max);
+    let scale = if peak > 0.0 { peak } else { 1.0 };
+
+    let points = |values: &[f64]| {
+        let step = WIDTH / (HISTORY_LENGTH - 1) as f64;
+        let offset = WIDTH - step * (values.len().saturating_sub(1)) as f64;
+        values.iter().enumerate()
+            .map(|(i, value)| format!("{:.1},{:.1}", offset + step * i as f64, HEIGHT - (value / scale) * HEIGHT))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    
+    html! {
+        <div class="metric-card trend-chart">
+            <div class="metric-header">
+                <h4>{&props.title}</h4>
+                <span class="metric-subtitle">{format!("peak {:.1}{}", peak, props.unit)}</span>
+            </div>
+            <svg viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)} preserveAspectRatio="none" class="trend-chart-plot">
+                { for props.series.iter().map(|s| html! {
+                    <polyline class={classes!("trend-line", s.class.clone())} points={points(&s.values)} />
+                }) }
+            </svg>
+            <div class="trend-legend">
+                { for props.series.iter().map(|s| html! {
+                    <span class={classes!("trend-legend-item", s.class.clone())}>
+                        {format!("{}: {:.1}{}", s.label, s.values.last().copied().unwrap_or(0.0), props.unit)}
+                    </span>
+                }) }
+            </div>
+        </div>
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct FrontendMetricsProps {
     pub metrics: FrontendMetrics,
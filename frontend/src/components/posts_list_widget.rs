@@ -1,15 +1,7 @@
 use yew::prelude::*;
-use crate::services::api_service::{get_posts, Post as PostData};
+use crate::services::api_service::{get_posts, get_recent_posts, get_public_settings, Post as PostData};
 use crate::pages::public::PublicPage;
-
-fn format_date(date_str: &str) -> String {
-    // Try to parse and format the date nicely
-    if let Ok(naive_datetime) = chrono::NaiveDateTime::parse_from_str(date_str, "%Y-%m-%d %H:%M:%S") {
-        naive_datetime.format("%B %d, %Y").to_string()
-    } else {
-        date_str.to_string()
-    }
-}
+use crate::components::RelativeTime;
 
 fn truncate_content(content: &str, max_length: usize) -> String {
     if content.len() <= max_length {
@@ -30,8 +22,12 @@ pub struct PostsListWidgetProps {
     pub limit: usize,
     #[prop_or(false)]
     pub show_full_list: bool,
-    #[prop_or(200)]
-    pub excerpt_length: usize,
+    /// Only posts of this category
+    #[prop_or_default]
+    pub category_id: Option<i32>,
+    /// Overrides the site's excerpt length for this list
+    #[prop_or_default]
+    pub excerpt_length: Option<usize>,
     pub on_navigate: Option<Callback<crate::pages::public::PublicPage>>,
 }
 
@@ -40,15 +36,38 @@ pub fn posts_list_widget(props: &PostsListWidgetProps) -> Html {
     let posts = use_state(Vec::new);
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
+    let read_more_text = use_state(|| "Read Article".to_string());
+
+    {
+        let read_more_text = read_more_text.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let label = get_public_settings(Some("site")).await.ok()
+                    .and_then(|settings| settings.into_iter().find(|setting| setting.setting_key == "read_more_text"))
+                    .and_then(|setting| setting.setting_value)
+                    .filter(|value| !value.trim().is_empty());
+                if let Some(label) = label {
+                    read_more_text.set(label);
+                }
+            });
+            || ()
+        }, ());
+    }
 
     {
         let posts = posts.clone();
         let loading = loading.clone();
         let error = error.clone();
 
-        use_effect_with_deps(move |_| {
+        use_effect_with_deps(move |(show_full_list, limit, category_id)| {
+            // One post past the limit tells whether there are more to see
+            let request = (!*show_full_list).then_some((*category_id, *limit + 1));
             wasm_bindgen_futures::spawn_local(async move {
-                match get_posts().await {
+                let result = match request {
+                    Some((category_id, limit)) => get_recent_posts(category_id, limit).await,
+                    None => get_posts().await,
+                };
+                match result {
                     Ok(fetched_posts) => {
                         posts.set(fetched_posts);
                         loading.set(false);
@@ -60,7 +79,7 @@ pub fn posts_list_widget(props: &PostsListWidgetProps) -> Html {
                 }
             });
             || ()
-        }, ());
+        }, (props.show_full_list, props.limit, props.category_id));
     }
 
     let posts_to_show = if props.show_full_list {
@@ -87,17 +106,28 @@ pub fn posts_list_widget(props: &PostsListWidgetProps) -> Html {
                 </div>
             } else if posts_to_show.is_empty() {
                 <div class="no-posts" style="text-align: center; padding: 2rem; color: var(--text-light);">
-                    <h3 style="margin-bottom: 1rem;">{"No posts published yet"}</h3>
+                    <h3 style="margin-bottom: 1rem;">
+                        {if props.category_id.is_some() { "No posts in this category yet" } else { "No posts published yet" }}
+                    </h3>
                     <p>{"Check back later for new content."}</p>
                 </div>
             } else {
                 <div class="posts-grid">
                     {posts_to_show.iter().map(|post| {
-                        let formatted_date = post.created_at.as_deref()
-                            .map(format_date)
-                            .unwrap_or_else(|| "Recent".to_string());
-                        
-                        let excerpt = truncate_content(&post.content, props.excerpt_length);
+                        let formatted_date = match post.created_at.clone() {
+                            Some(created_at) => html! { <RelativeTime value={created_at} date_only=true /> },
+                            None => html! { {"Recent"} },
+                        };
+
+                        // The server cuts excerpts to the site's length
+                        let excerpt = match (props.excerpt_length, &post.excerpt) {
+                            (None, Some(excerpt)) => excerpt.clone(),
+                            (length, _) => truncate_content(&post.content, length.unwrap_or(200)),
+                        };
+                        let comments = post.comment_count.map(|count| match count {
+                            1 => "1 comment".to_string(),
+                            count => format!("{} comments", count),
+                        });
                         
                         let post_id = post.id.unwrap_or(0);
                         let on_click = if let Some(ref on_navigate) = props.on_navigate {
@@ -115,15 +145,19 @@ pub fn posts_list_widget(props: &PostsListWidgetProps) -> Html {
                                 <h2>{&post.title}</h2>
                                 <p class="post-meta">
                                     {"By "}{&post.author}{" • "}{formatted_date}
+                                    if let Some(comments) = comments {
+                                        {" • "}
+                                        <span class="post-comment-count">{comments}</span>
+                                    }
                                 </p>
                                 <p class="post-excerpt">{excerpt}</p>
                                 if let Some(click_handler) = on_click {
                                     <a href={format!("/post/{}", post_id)} class="read-more" onclick={click_handler}>
-                                        {"Read Article"}
+                                        {(*read_more_text).clone()}
                                     </a>
                                 } else {
                                     <a href={format!("/post/{}", post_id)} class="read-more">
-                                        {"Read Article"}
+                                        {(*read_more_text).clone()}
                                     </a>
                                 }
                             </article>
@@ -131,7 +165,8 @@ pub fn posts_list_widget(props: &PostsListWidgetProps) -> Html {
                     }).collect::<Html>()}
                 </div>
             }
-            if !props.show_full_list && posts.len() > props.limit {
+            // The full list isn't filtered by category, so only offer it for all posts
+            if !props.show_full_list && props.category_id.is_none() && posts.len() > props.limit {
                 <div class="view-all" style="text-align: center; margin-top: 2rem;">
                     <a href="/posts" class="view-all-link" style="padding: 0.75rem 1.5rem; background: var(--primary-color); color: white; text-decoration: none; border-radius: 0.5rem; font-weight: 500;">
                         {"View All Posts"}
@@ -1,12 +1,15 @@
 use yew::prelude::*;
-use crate::services::navigation_service::{get_navigation_by_area, get_component_templates, ComponentTemplate};
-use crate::services::api_service::get_public_settings;
+use crate::services::navigation_service::ComponentTemplate;
+use crate::services::api_service::{get_public_bootstrap, Branding};
 use std::collections::HashMap;
 use crate::pages::public::PublicPage;
 use crate::pages::admin::design_system::{PublicColorScheme, apply_public_css_variables};
 use wasm_bindgen::JsCast;
 use crate::services::auth_context::use_auth;
 use crate::components::LiveEditMode;
+use crate::services::component_style::{active_component_style, active_header_logo, global_style_vars, HeaderLogo};
+use crate::services::canonical::{apply_canonical, CANONICAL_TRAILING_SLASH_SETTING};
+use crate::services::text_direction_context::use_text_direction;
 
 #[derive(Properties, PartialEq)]
 pub struct PublicLayoutProps {
@@ -25,11 +28,16 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
     let component_templates = use_state(Vec::<ComponentTemplate>::new);
     let loading = use_state(|| true);
     let admin_button_visible = use_state(|| true); // Default to true until loaded
+    let powered_by_visible = use_state(|| true);
     let site_title = use_state(|| "My Rust CMS".to_string());
+    let branding = use_state(Branding::default);
+    // (site URL, trailing slash wanted) once the settings are in
+    let canonical_settings = use_state(|| None::<(String, bool)>);
     let acid_mode = use_state(|| false);
     let site_style = use_state(|| String::new());
     let inner_container_style = use_state(|| String::new());
     let live_edit_enabled = use_state(|| false);
+    let text_direction = use_text_direction();
 
     // Load navigation items, component templates, and admin button setting
     {
@@ -38,7 +46,10 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
         let component_templates = component_templates.clone();
         let loading = loading.clone();
         let admin_button_visible = admin_button_visible.clone();
+        let powered_by_visible = powered_by_visible.clone();
         let site_title = site_title.clone();
+        let branding = branding.clone();
+        let canonical_settings = canonical_settings.clone();
         let acid_mode = acid_mode.clone();
         let site_style = site_style.clone();
         let inner_container_style = inner_container_style.clone();
@@ -46,16 +57,25 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
         use_effect_with_deps(move |_| {
             web_sys::console::log_1(&"PublicLayout: Starting to fetch navigation items, templates, and settings".into());
             wasm_bindgen_futures::spawn_local(async move {
-                // Load header and footer navigation items
-                let header_nav_result = get_navigation_by_area("header").await;
-                let footer_nav_result = get_navigation_by_area("footer").await;
-                
-                // Load component templates
-                let templates_result = get_component_templates().await;
-                
-                // Load site and container settings
-                let settings_result = get_public_settings(Some("site")).await;
-                let container_settings_result = get_public_settings(Some("container")).await;
+                // Navigation, component templates and site and container
+                // settings all arrive in one request
+                let (header_nav_result, footer_nav_result, templates_result, settings_result, container_settings_result) =
+                    match get_public_bootstrap().await {
+                        Ok(bootstrap) => {
+                            branding.set(bootstrap.branding);
+                            (
+                                Ok(bootstrap.header_navigation),
+                                Ok(bootstrap.footer_navigation),
+                                Ok(bootstrap.component_templates),
+                                Ok(bootstrap.site_settings),
+                                Ok(bootstrap.container_settings),
+                            )
+                        }
+                        Err(e) => {
+                            let e = e.to_string();
+                            (Err(e.clone()), Err(e.clone()), Err(e.clone()), Err(e.clone()), Err(e))
+                        }
+                    };
                 
                 match header_nav_result {
                     Ok(items) => {
@@ -99,6 +119,22 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
                             }
                         }
                         
+                        if let Some(value) = settings.iter()
+                            .find(|s| s.setting_key == "footer_powered_by_visible")
+                            .and_then(|s| s.setting_value.as_ref())
+                        {
+                            powered_by_visible.set(value.trim() != "false");
+                        }
+                        
+                        let setting_value = |key: &str| settings.iter()
+                            .find(|s| s.setting_key == key)
+                            .and_then(|s| s.setting_value.clone())
+                            .unwrap_or_default();
+                        canonical_settings.set(Some((
+                            setting_value("site_url"),
+                            setting_value(CANONICAL_TRAILING_SLASH_SETTING).trim() == "add",
+                        )));
+
                         // Find site title setting
                         if let Some(setting) = settings.iter().find(|s| s.setting_key == "site_title") {
                             if let Some(ref value) = setting.setting_value {
@@ -350,6 +386,26 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
         }, ());
     }
 
+    // Renders follow navigation, so re-check the canonical link after each
+    {
+        let canonical_settings = canonical_settings.clone();
+        use_effect(move || {
+            if let Some((ref site_url, add_slash)) = *canonical_settings {
+                apply_canonical(site_url, add_slash);
+            }
+            || ()
+        });
+    }
+
+    // The favicon is a link in the document head
+    {
+        let favicon_url = branding.favicon_url.clone();
+        use_effect_with_deps(move |favicon_url| {
+            apply_favicon(favicon_url.as_deref());
+            || ()
+        }, favicon_url);
+    }
+
     // Apply default public theme on component mount
     {
         use_effect_with_deps(move |_| {
@@ -400,238 +456,52 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
         }
     };
 
-    // Helper function to get template styles (safe subset for public UI)
+    // Template styles for the header and footer (safe subset for public UI)
     let get_component_style = {
         let component_templates = component_templates.clone();
         move |component_type: &str| -> String {
-            if let Some(template) = component_templates.iter()
-                .find(|t| t.component_type == component_type && t.is_active) {
-                let mut styles = Vec::new();
-                
-                if let Some(height) = template.template_data.get("height").and_then(|v| v.as_str()) {
-                    if component_type == "header" {
-                        let mut h = height.to_string();
-                        if let Some(stripped) = h.strip_suffix("px") {
-                            if let Ok(px) = stripped.trim().parse::<i32>() {
-                                if px < 110 { h = "110px".to_string(); }
-                            }
-                        }
-                        styles.push(format!("height: {}", h));
-                    } else {
-                        styles.push(format!("height: {}", height));
-                    }
-                } else if component_type == "header" {
-                    styles.push("height: 110px".to_string());
-                }
-                
-                // Only allow position overrides for non-header components to avoid layout breaks
-                if component_type != "header" {
-                    if let Some(position) = template.template_data.get("position").and_then(|v| v.as_str()) {
-                        styles.push(format!("position: {}", position));
-                    }
-                }
-                
-                // Support both background (gradients/images) and background_color
-                if component_type == "header" {
-                    // For header, set background directly; coerce white to black per default theme requirement
-                    if let Some(mut bg) = template.template_data.get("background").and_then(|v| v.as_str()) {
-                        if bg.trim().eq_ignore_ascii_case("#ffffff") { bg = "#000000"; }
-                        styles.push(format!("background: {}", bg));
-                    } else if let Some(mut bg) = template.template_data.get("background_color").and_then(|v| v.as_str()) {
-                        if bg.trim().eq_ignore_ascii_case("#ffffff") { bg = "#000000"; }
-                        styles.push(format!("background-color: {}", bg));
-                    } else {
-                        // Fallback when no background provided
-                        styles.push("background-color: #000000".to_string());
-                    }
-                } else if component_type == "footer" {
-                    if let Some(bg) = template.template_data.get("background").and_then(|v| v.as_str()) {
-                        styles.push(format!("--public-footer-bg: {}", bg));
-                    } else if let Some(bg) = template.template_data.get("background_color").and_then(|v| v.as_str()) {
-                        styles.push(format!("--public-footer-bg: {}", bg));
-                    }
-                } else {
-                    if let Some(background) = template.template_data.get("background").and_then(|v| v.as_str()) {
-                        styles.push(format!("background: {}", background));
-                    } else if let Some(background) = template.template_data.get("background_color").and_then(|v| v.as_str()) {
-                        styles.push(format!("background-color: {}", background));
-                    }
-                }
-                
-                // Optional text color overrides via CSS variables for header/footer
-                if component_type == "header" {
-                    if let Some(text_color) = template.template_data.get("text_color").and_then(|v| v.as_str()) {
-                        styles.push(format!("--header-text: {}", text_color));
-                    } else {
-                        // Default to white for readability on black header
-                        styles.push("--header-text: #ffffff".to_string());
-                    }
-                    if let Some(text_hover) = template.template_data.get("text_hover_color").and_then(|v| v.as_str()) {
-                        styles.push(format!("--header-text-hover: {}", text_hover));
-                    } else {
-                        styles.push("--header-text-hover: #f7fafc".to_string());
-                    }
-                    if let Some(nav_hover) = template.template_data.get("nav_hover_color").and_then(|v| v.as_str()) {
-                        styles.push(format!("--nav-hover-color: {}", nav_hover));
-                    }
-                    if let Some(nav_underline) = template.template_data.get("nav_underline_color").and_then(|v| v.as_str()) {
-                        styles.push(format!("--nav-underline-color: {}", nav_underline));
-                    }
-                    if let Some(thickness) = template.template_data.get("nav_underline_thickness").and_then(|v| v.as_str()) {
-                        styles.push(format!("--nav-underline-thickness: {}", thickness));
-                    }
-                    if let Some(anim) = template.template_data.get("nav_underline_animation").and_then(|v| v.as_str()) {
-                        styles.push(format!("--nav-underline-animation: {}", anim));
-                    }
-                }
-                if component_type == "footer" {
-                    if let Some(text_color) = template.template_data.get("text_color").and_then(|v| v.as_str()) {
-                        styles.push(format!("--footer-text: {}", text_color));
-                    }
-                    if let Some(text_muted) = template.template_data.get("text_muted").and_then(|v| v.as_str()) {
-                        styles.push(format!("--footer-text-muted: {}", text_muted));
-                    }
-                    if let Some(bg) = template.template_data.get("background").and_then(|v| v.as_str()) {
-                        styles.push(format!("--footer-background: {}", bg));
-                    }
-                }
-
-                if let Some(z_index_val) = template.template_data.get("z_index") {
-                    if let Some(z) = z_index_val.as_i64() {
-                        styles.push(format!("z-index: {}", z));
-                    } else if let Some(z) = z_index_val.as_str() {
-                        styles.push(format!("z-index: {}", z));
-                    }
-                }
-                
-                if let Some(padding) = template.template_data.get("padding").and_then(|v| v.as_str()) {
-                    styles.push(format!("padding: {}", padding));
-                }
-                
-                if let Some(margin) = template.template_data.get("margin").and_then(|v| v.as_str()) {
-                    styles.push(format!("margin: {}", margin));
-                }
-                
-                if let Some(border) = template.template_data.get("border").and_then(|v| v.as_str()) {
-                    styles.push(format!("border: {}", border));
-                }
-                
-                if let Some(box_shadow) = template.template_data.get("box_shadow").and_then(|v| v.as_str()) {
-                    styles.push(format!("box-shadow: {}", box_shadow));
-                }
-                
-                let style_string = styles.join("; ");
-                if !style_string.is_empty() {
-                    web_sys::console::log_1(&format!("Applying {} template styles: {}", component_type, style_string).into());
-                }
-                style_string
-            } else {
-                String::new()
+            let style_string = active_component_style(&component_templates, component_type);
+            if !style_string.is_empty() {
+                web_sys::console::log_1(&format!("Applying {} template styles: {}", component_type, style_string).into());
             }
+            style_string
         }
     };
 
-    // Global style variables derived from specific component templates (e.g., posts_list)
-    let global_style_vars = {
-        let component_templates = component_templates.clone();
-        move || -> String {
-            let mut vars: Vec<String> = Vec::new();
-            if let Some(posts_tpl) = component_templates.iter().find(|t| t.component_type == "posts_list" && t.is_active) {
-                if let Some(bg) = posts_tpl.template_data.get("card_background").and_then(|v| v.as_str()) {
-                    vars.push(format!("--posts-card-bg: {}", bg));
-                }
-                if let Some(radius) = posts_tpl.template_data.get("card_radius").and_then(|v| v.as_str()) {
-                    vars.push(format!("--posts-card-radius: {}", radius));
-                }
-                if let Some(shadow) = posts_tpl.template_data.get("card_shadow").and_then(|v| v.as_str()) {
-                    vars.push(format!("--posts-card-shadow: {}", shadow));
-                }
-                if let Some(title_color) = posts_tpl.template_data.get("title_color").and_then(|v| v.as_str()) {
-                    vars.push(format!("--posts-title-color: {}", title_color));
-                }
-                if let Some(meta_color) = posts_tpl.template_data.get("meta_color").and_then(|v| v.as_str()) {
-                    vars.push(format!("--posts-meta-color: {}", meta_color));
-                }
-                if let Some(link_color) = posts_tpl.template_data.get("link_color").and_then(|v| v.as_str()) {
-                    vars.push(format!("--posts-link-color: {}", link_color));
-                }
-                if let Some(grid_gap) = posts_tpl.template_data.get("grid_gap").and_then(|v| v.as_str()) {
-                    vars.push(format!("--posts-grid-gap: {}", grid_gap));
-                }
-            }
-            // Hero variables (background/text)
-            if let Some(hero_tpl) = component_templates.iter().find(|t| t.component_type == "hero" && t.is_active) {
-                if let Some(bg) = hero_tpl.template_data.get("background").and_then(|v| v.as_str()) {
-                    vars.push(format!("--hero-bg: {}", bg));
-                }
-                if let Some(color) = hero_tpl.template_data.get("text_color").and_then(|v| v.as_str()) {
-                    vars.push(format!("--hero-text: {}", color));
-                }
-            }
-            // Buttons
-            if let Some(btn_tpl) = component_templates.iter().find(|t| t.component_type == "header" && t.is_active) {
-                if let Some(bg) = btn_tpl.template_data.get("button_primary_bg").and_then(|v| v.as_str()) {
-                    vars.push(format!("--button-primary-bg: {}", bg));
-                }
-                if let Some(text) = btn_tpl.template_data.get("button_primary_text").and_then(|v| v.as_str()) {
-                    vars.push(format!("--button-primary-text: {}", text));
-                }
-                if let Some(hover_bg) = btn_tpl.template_data.get("button_primary_hover_bg").and_then(|v| v.as_str()) {
-                    vars.push(format!("--button-primary-hover-bg: {}", hover_bg));
-                }
-            }
-            // Badges
-            if let Some(badge_tpl) = component_templates.iter().find(|t| t.component_type == "header" && t.is_active) {
-                if let Some(bg) = badge_tpl.template_data.get("badge_bg").and_then(|v| v.as_str()) {
-                    vars.push(format!("--badge-bg: {}", bg));
-                }
-                if let Some(text) = badge_tpl.template_data.get("badge_text").and_then(|v| v.as_str()) {
-                    vars.push(format!("--badge-text: {}", text));
-                }
-            }
-            // Background animation
-            if let Some(site_tpl) = component_templates.iter().find(|t| t.component_type == "main_container" && t.is_active) {
-                if let Some(anim) = site_tpl.template_data.get("background_animation").and_then(|v| v.as_str()) {
-                    vars.push(format!("--bg-animation: {}", anim));
-                }
-            }
-            vars.join("; ")
-        }
-    };
-
+    // Links to public pages are handled in the app; anything else, and
+    // clicks meant to open a new tab or window, go to the browser
     let on_nav_item_click = {
         let on_navigate = props.on_navigate.clone();
         Callback::from(move |e: MouseEvent| {
-            e.prevent_default();
-            if let Some(on_navigate) = &on_navigate {
-                if let Some(target) = e.target_dyn_into::<web_sys::HtmlElement>() {
-                    if let Some(url) = target.get_attribute("data-url") {
-                        let page = match url.as_str() {
-                            "/" => PublicPage::Home,
-                            "/posts" => PublicPage::Posts,
-                            url if url.starts_with("/post/") => {
-                                if let Ok(id) = url.trim_start_matches("/post/").parse::<i32>() {
-                                    PublicPage::Post(id)
-                                } else {
-                                    return;
-                                }
-                            }
-                            url if url.starts_with("/page/") => {
-                                let slug = url.trim_start_matches("/page/");
-                                PublicPage::Page(slug.to_string())
-                            }
-                            _ => return,
-                        };
-                        on_navigate.emit(page);
-                    }
-                }
+            if e.button() != 0 || e.ctrl_key() || e.meta_key() || e.shift_key() || e.alt_key() {
+                return;
+            }
+            let Some(on_navigate) = &on_navigate else { return };
+            let page = e.current_target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                .and_then(|target| target.get_attribute("data-url"))
+                .and_then(|url| public_page_for_url(&url));
+            if let Some(page) = page {
+                e.prevent_default();
+                on_navigate.emit(page);
             }
         })
     };
 
+    // Links already follow on Enter; Space should activate them too, as it
+    // does buttons, instead of scrolling the page
+    let on_nav_item_keydown = Callback::from(|e: KeyboardEvent| {
+        if e.key() == " " {
+            e.prevent_default();
+            if let Some(target) = e.current_target().and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok()) {
+                target.click();
+            }
+        }
+    });
+
     html! {
-        <div class={if *acid_mode { "public-site acid-mode" } else { "public-site" }} style={format!("{}{}{}; position: relative; z-index: 1",
-            global_style_vars(),
+        <div class={if *acid_mode { "public-site acid-mode" } else { "public-site" }} dir={text_direction.as_str()} style={format!("{}{}{}; position: relative; z-index: 1",
+            global_style_vars(&component_templates),
             if !(*site_style).is_empty() { "; " } else { "" },
             (*site_style).clone()
         )}>
@@ -639,8 +509,20 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
                 html! {
                     <header id="site-header" class="site-header" style={get_component_style("header")}>
                         <div class="container">
-                            <h1 class="site-title">{(*site_title).clone()}</h1>
-                            <nav class="site-nav">
+                            {{
+                                let (header_logo, logo_style) = active_header_logo(&component_templates, branding.logo_url.is_some());
+                                let logo = || html! {
+                                    <img class="site-logo" src={branding.logo_url.clone()} alt={(*site_title).clone()} style={logo_style.clone()} />
+                                };
+                                match header_logo {
+                                    HeaderLogo::Title => html! { <h1 class="site-title">{(*site_title).clone()}</h1> },
+                                    HeaderLogo::Logo => html! { <h1 class="site-title site-title-logo">{logo()}</h1> },
+                                    HeaderLogo::LogoAndTitle => html! {
+                                        <h1 class="site-title site-title-logo">{logo()}<span>{(*site_title).clone()}</span></h1>
+                                    },
+                                }
+                            }}
+                            <nav class="site-nav" aria-label="Main navigation">
                                 if !*loading {
                                     {{
                                         let items: Vec<_> = header_navigation_items.iter().filter(|item| item.is_active).collect();
@@ -650,10 +532,12 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
                                             let is_active = props.current_page == item.url.trim_start_matches('/');
                                             html! {
                                                 <a 
-                                                    href="#" 
+                                                    href={item.url.clone()}
                                                     class={if is_active { "nav-link active" } else { "nav-link" }}
+                                                    aria-current={is_active.then_some("page")}
                                                     data-url={item.url.clone()}
                                                     onclick={on_nav_item_click.clone()}
+                                                    onkeydown={on_nav_item_keydown.clone()}
                                                 >
                                                     {&item.title}
                                                 </a>
@@ -664,7 +548,7 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
                                 
                                 {if *admin_button_visible {
                                     html! {
-                                        <button class="nav-button admin-button" onclick={on_admin_click}>
+                                        <button type="button" class="nav-button admin-button" aria-label="Open the admin dashboard" onclick={on_admin_click}>
                                             {"Admin"}
                                         </button>
                                     }
@@ -759,14 +643,15 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
                         <div class="container">
                             {if !footer_navigation_items.is_empty() {
                                 html! {
-                                    <nav class="footer-nav">
+                                    <nav class="footer-nav" aria-label="Footer navigation">
                                         {footer_navigation_items.iter().filter(|item| item.is_active).map(|item| {
                                             html! {
                                                 <a 
-                                                    href="#" 
+                                                    href={item.url.clone()}
                                                     class="footer-nav-link"
                                                     data-url={item.url.clone()}
                                                     onclick={on_nav_item_click.clone()}
+                                                    onkeydown={on_nav_item_keydown.clone()}
                                                 >
                                                     {&item.title}
                                                 </a>
@@ -777,7 +662,31 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
                             } else {
                                 html! {}
                             }}
-                            <p class="footer-copyright">{"© 2024 My Rust CMS. Built with Rust and Yew."}</p>
+                            {{
+                                // Texts come from the footer template; an empty
+                                // string there hides the line
+                                let footer_data = component_templates.iter()
+                                    .find(|t| t.component_type == "footer" && t.is_active)
+                                    .map(|t| t.template_data.clone())
+                                    .unwrap_or_default();
+                                let text = |key: &str, default: &str| footer_data.get(key)
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or(default)
+                                    .trim()
+                                    .to_string();
+                                let copyright = text("copyright_text", DEFAULT_COPYRIGHT_TEXT);
+                                let powered_by = text("additional_text", DEFAULT_POWERED_BY_TEXT);
+                                html! {
+                                    <>
+                                        if !copyright.is_empty() {
+                                            <p class="footer-copyright">{footer_text(&copyright, &site_title)}</p>
+                                        }
+                                        if *powered_by_visible && !powered_by.is_empty() {
+                                            <p class="footer-powered-by">{footer_text(&powered_by, &site_title)}</p>
+                                        }
+                                    </>
+                                }
+                            }}
                         </div>
                     </footer>
                 }
@@ -791,7 +700,12 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
                 };
                 html!{
                     <>
-                        <button onclick={on_toggle} style="position: fixed; bottom: 16px; right: 16px; z-index: 9999; padding: 10px 14px; border-radius: 8px; border: 1px solid rgba(0,0,0,0.1); background: #111; color: #fff; opacity: 0.9; pointer-events: auto;">{
+                        <button
+                            type="button"
+                            onclick={on_toggle}
+                            aria-label={if *live_edit_enabled { "Turn off live edit mode" } else { "Turn on live edit mode" }}
+                            aria-pressed={live_edit_enabled.to_string()}
+                            style="position: fixed; bottom: 16px; right: 16px; z-index: 9999; padding: 10px 14px; border-radius: 8px; border: 1px solid rgba(0,0,0,0.1); background: #111; color: #fff; opacity: 0.9; pointer-events: auto;">{
                             if *live_edit_enabled { "Disable Live Edit" } else { "Enable Live Edit" }
                         }</button>
                         <LiveEditMode
@@ -812,4 +726,71 @@ pub fn public_layout(props: &PublicLayoutProps) -> Html {
             } else { html!{} }}
         </div>
     }
-} 
\ No newline at end of file
+} 
+
+/// Footer texts used when the footer template doesn't set them
+/// Point the document's `<link rel="icon">` at `url`, or remove it
+fn apply_favicon(url: Option<&str>) {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else { return };
+    let existing = document.query_selector("link[rel=\"icon\"]").ok().flatten();
+    match (url, existing) {
+        (Some(url), Some(link)) => {
+            let _ = link.set_attribute("href", url);
+        }
+        (Some(url), None) => {
+            if let (Ok(link), Some(head)) = (document.create_element("link"), document.head()) {
+                let _ = link.set_attribute("rel", "icon");
+                let _ = link.set_attribute("href", url);
+                let _ = head.append_child(&link);
+            }
+        }
+        (None, Some(link)) => link.remove(),
+        (None, None) => {}
+    }
+}
+
+const DEFAULT_COPYRIGHT_TEXT: &str = "© {year} {site_title}";
+const DEFAULT_POWERED_BY_TEXT: &str = "Built with Rust and Yew";
+
+/// Footer text with `{year}` and `{site_title}` filled in. `[label](url)`
+/// becomes a link to a site-relative, http(s) or mailto URL; everything else
+/// is shown as text, so template text can never inject markup.
+fn footer_text(template: &str, site_title: &str) -> Html {
+    let year = js_sys::Date::new_0().get_full_year().to_string();
+    let text = template.replace("{year}", &year).replace("{site_title}", site_title);
+
+    let mut parts: Vec<Html> = Vec::new();
+    let mut rest = text.as_str();
+    while let Some(open) = rest.find('[') {
+        let link = rest[open + 1..].split_once("](").and_then(|(label, after)| {
+            let (url, tail) = after.split_once(')')?;
+            let safe = url.starts_with('/') || url.starts_with("https://") || url.starts_with("http://") || url.starts_with("mailto:");
+            (safe && !label.contains('[')).then_some((label, url, tail))
+        });
+        match link {
+            Some((label, url, tail)) => {
+                parts.push(html! { {&rest[..open]} });
+                parts.push(html! { <a href={url.to_string()}>{label}</a> });
+                rest = tail;
+            }
+            None => {
+                parts.push(html! { {&rest[..=open]} });
+                rest = &rest[open + 1..];
+            }
+        }
+    }
+    parts.push(html! { {rest} });
+    parts.into_iter().collect()
+}
+
+/// The public page a site-relative navigation URL leads to, if the app
+/// renders it itself
+fn public_page_for_url(url: &str) -> Option<PublicPage> {
+    match url {
+        "/" => Some(PublicPage::Home),
+        "/posts" => Some(PublicPage::Posts),
+        url if url.starts_with("/post/") => url.trim_start_matches("/post/").parse::<i32>().ok().map(PublicPage::Post),
+        url if url.starts_with("/page/") => Some(PublicPage::Page(url.trim_start_matches("/page/").to_string())),
+        _ => None,
+    }
+}
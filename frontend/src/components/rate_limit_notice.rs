@@ -0,0 +1,54 @@
+use gloo_timers::callback::Interval;
+use yew::prelude::*;
+
+/// Wait to suggest when a 429 doesn't say how long
+pub const FALLBACK_RETRY_AFTER_SECS: u32 = 30;
+
+#[derive(Properties, PartialEq)]
+pub struct RateLimitNoticeProps {
+    /// Seconds to wait, from the response's `Retry-After` header
+    pub retry_after: u32,
+    /// Called once the wait is over, so the form can be submitted again
+    pub on_expire: Callback<()>,
+}
+
+/// "Please slow down" message with a countdown, shown after a 429
+///
+/// Give it a new `key` for each refusal so the countdown restarts even when
+/// two refusals ask for the same wait.
+#[function_component(RateLimitNotice)]
+pub fn rate_limit_notice(props: &RateLimitNoticeProps) -> Html {
+    let remaining = use_state(|| props.retry_after);
+
+    {
+        let remaining = remaining.clone();
+        let on_expire = props.on_expire.clone();
+        use_effect_with_deps(move |retry_after| {
+            let retry_after = *retry_after;
+            remaining.set(retry_after);
+            let started = js_sys::Date::now();
+            let tick = Interval::new(1_000, move || {
+                let elapsed = ((js_sys::Date::now() - started) / 1_000.0) as u32;
+                let left = retry_after.saturating_sub(elapsed);
+                remaining.set(left);
+                if left == 0 {
+                    on_expire.emit(());
+                }
+            });
+            move || drop(tick)
+        }, props.retry_after);
+    }
+
+    let seconds = *remaining;
+    html! {
+        <div class="rate-limit-notice" role="status" aria-live="polite">
+            <strong>{"Please slow down."}</strong>
+            {" "}
+            if seconds > 0 {
+                {format!("You can try again in {} {}.", seconds, if seconds == 1 { "second" } else { "seconds" })}
+            } else {
+                {"You can try again now."}
+            }
+        </div>
+    }
+}
@@ -0,0 +1,47 @@
+use yew::prelude::*;
+use gloo_timers::callback::Interval;
+use crate::services::local_time::{parse_api_timestamp, relative_time, utc_now};
+use crate::services::timezone_context::use_display_timezone;
+
+/// How often relative labels are recomputed
+const REFRESH_INTERVAL_MS: u32 = 60_000;
+
+#[derive(Properties, PartialEq)]
+pub struct RelativeTimeProps {
+    /// UTC timestamp as returned by the API
+    pub value: String,
+    /// Fall back to a date without time of day once too old for relative text
+    #[prop_or_default]
+    pub date_only: bool,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// A timestamp shown as "3 hours ago", with the absolute time on hover
+///
+/// The label refreshes every minute; times older than a month (or in the
+/// future) render as absolute dates in the site time zone.
+#[function_component(RelativeTime)]
+pub fn relative_time_label(props: &RelativeTimeProps) -> Html {
+    let timezone = use_display_timezone();
+    let now = use_state(utc_now);
+
+    {
+        let now = now.clone();
+        use_effect_with_deps(move |_| {
+            let interval = Interval::new(REFRESH_INTERVAL_MS, move || now.set(utc_now()));
+            move || drop(interval)
+        }, ());
+    }
+
+    let absolute = timezone.datetime(&props.value);
+    let parsed = parse_api_timestamp(&props.value);
+    let label = parsed
+        .and_then(|then| relative_time(then, *now))
+        .unwrap_or_else(|| if props.date_only { timezone.date(&props.value) } else { absolute.clone() });
+    let datetime = parsed.map(|then| then.to_rfc3339());
+
+    html! {
+        <time class={props.class.clone()} datetime={datetime} title={absolute}>{label}</time>
+    }
+}
@@ -0,0 +1,89 @@
+use yew::prelude::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+use crate::components::markdown_content::TocEntry;
+
+/// Posts with fewer headings than this don't get a table of contents
+pub const MIN_TOC_ENTRIES: usize = 3;
+/// A section counts as current once its heading is this close to the top
+const SCROLL_SPY_OFFSET_PX: f64 = 96.0;
+
+/// Id of the last heading scrolled past; none while above the first heading
+fn current_section(entries: &[TocEntry]) -> Option<String> {
+    let document = web_sys::window()?.document()?;
+    entries.iter()
+        .rev()
+        .find(|entry| {
+            document.get_element_by_id(&entry.id)
+                .is_some_and(|heading| heading.get_bounding_client_rect().top() <= SCROLL_SPY_OFFSET_PX)
+        })
+        .map(|entry| entry.id.clone())
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TableOfContentsProps {
+    pub entries: Vec<TocEntry>,
+}
+
+/// Sticky list of a post's headings that highlights the section in view
+#[function_component(TableOfContents)]
+pub fn table_of_contents(props: &TableOfContentsProps) -> Html {
+    // Only re-renders when the current section actually changes
+    let active = use_state_eq(|| None::<String>);
+
+    // Scroll spy: track the current section while the page scrolls
+    {
+        let active = active.clone();
+        use_effect_with_deps(move |entries: &Vec<TocEntry>| {
+            let entries = entries.clone();
+            // The handle captured here goes stale, so compare in use_state_eq
+            let update = move || active.set(current_section(&entries));
+            update();
+
+            let on_scroll = Closure::<dyn Fn()>::new(update);
+            let window = web_sys::window();
+            if let Some(window) = &window {
+                let _ = window.add_event_listener_with_callback("scroll", on_scroll.as_ref().unchecked_ref());
+            }
+            move || {
+                if let Some(window) = window {
+                    let _ = window.remove_event_listener_with_callback("scroll", on_scroll.as_ref().unchecked_ref());
+                }
+            }
+        }, props.entries.clone());
+    }
+
+    let top_level = props.entries.iter().map(|entry| entry.level).min().unwrap_or(1);
+
+    html! {
+        <nav class="table-of-contents" aria-label="Table of contents">
+            <h2 class="toc-title">{"Contents"}</h2>
+            <ul>
+                {for props.entries.iter().map(|entry| {
+                    // Scroll in place rather than changing the URL hash
+                    let onclick = {
+                        let active = active.clone();
+                        let id = entry.id.clone();
+                        Callback::from(move |e: MouseEvent| {
+                            e.prevent_default();
+                            let heading = web_sys::window()
+                                .and_then(|window| window.document())
+                                .and_then(|document| document.get_element_by_id(&id));
+                            if let Some(heading) = heading {
+                                heading.scroll_into_view();
+                                active.set(Some(id.clone()));
+                            }
+                        })
+                    };
+                    let is_active = active.as_deref() == Some(entry.id.as_str());
+                    html! {
+                        <li class={classes!(format!("toc-level-{}", entry.level - top_level + 1), is_active.then_some("active"))}>
+                            <a href={format!("#{}", entry.id)} {onclick} aria-current={is_active.then_some("location")}>
+                                {&entry.title}
+                            </a>
+                        </li>
+                    }
+                })}
+            </ul>
+        </nav>
+    }
+}
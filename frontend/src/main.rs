@@ -4,14 +4,20 @@ mod pages;
 mod services;
 
 use app::App;
-use services::{performance_service::init_performance_service, auth_context::AuthProvider};
+use services::{performance_service::init_performance_service, auth_context::AuthProvider, toast_context::ToastProvider, timezone_context::TimezoneProvider, text_direction_context::TextDirectionProvider};
 use yew::prelude::*;
 
 #[function_component(Root)]
 fn root() -> Html {
     html! {
         <AuthProvider>
-            <App />
+            <ToastProvider>
+                <TimezoneProvider>
+                    <TextDirectionProvider>
+                        <App />
+                    </TextDirectionProvider>
+                </TimezoneProvider>
+            </ToastProvider>
         </AuthProvider>
     }
 }
@@ -21,6 +27,12 @@ fn main() {
     if let Err(e) = init_performance_service() {
         web_sys::console::warn_1(&format!("Failed to initialize performance service: {:?}", e).into());
     }
-    
+
+    // Markup the server rendered for crawlers (the backend's `ssr` feature);
+    // the app renders the page itself
+    if let Some(ssr_content) = gloo::utils::document().get_element_by_id("ssr-content") {
+        ssr_content.remove();
+    }
+
     yew::Renderer::<Root>::new().render();
 }
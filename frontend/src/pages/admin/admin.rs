@@ -1,11 +1,13 @@
 use yew::prelude::*;
 use crate::components::admin::{AdminSidebar, AdminHeader};
 use crate::components::admin::sidebar::AdminTab;
-use crate::pages::admin::{dashboard::AdminDashboard, post_list::PostList, post_editor::PostEditor, page_builder::PageBuilder, media_library::MediaLibrary, enhanced_user_management::EnhancedUserManagement, comment_moderation::CommentModeration, navigation_manager::NavigationManager, template_manager::TemplateManager, analytics::Analytics, system_settings::SystemSettings, design_system::DesignSystemPage};
+use crate::pages::admin::{dashboard::AdminDashboard, post_list::PostList, post_editor::PostEditor, post_calendar::PostCalendar, page_builder::PageBuilder, media_library::MediaLibrary, enhanced_user_management::EnhancedUserManagement, comment_moderation::CommentModeration, navigation_manager::NavigationManager, template_manager::TemplateManager, analytics::Analytics, system_settings::SystemSettings, design_system::DesignSystemPage, session_management::SessionManagement, profile::Profile};
 use crate::services::migrate_pages::create_essential_pages;
-use crate::pages::admin::design_system::{AdminColorScheme, apply_admin_css_variables};
+use crate::pages::admin::design_system::{apply_admin_css_variables, load_active_admin_scheme};
 use crate::services::navigation_service::get_component_templates;
-use crate::services::api_service::get_settings;
+use crate::services::api_service::{get_settings, SearchResult};
+use crate::services::csp::tag_with_nonce;
+use crate::services::text_direction_context::use_text_direction;
 use crate::services::auth_service::User;
 
 #[derive(Properties, PartialEq)]
@@ -20,6 +22,31 @@ pub struct AdminProps {
 #[function_component(Admin)]
 pub fn admin(props: &AdminProps) -> Html {
     let on_tab_change = props.on_tab_change.clone();
+    let role = props.current_user.as_ref().map(|user| user.role.clone()).unwrap_or_default();
+    let permissions = props.current_user.as_ref().map(|user| user.permissions.clone()).unwrap_or_default();
+    let default_tab = AdminTab::default_for(&role, &permissions);
+    let allowed = props.current_tab.is_allowed(&permissions);
+    // Never mount a tab the role can't use, even for the frame before the redirect
+    let shown_tab = if allowed { props.current_tab.clone() } else { default_tab.clone() };
+    let text_direction = use_text_direction();
+
+    // Opening the admin area lands on the role's default tab, and a tab the
+    // role can't use (e.g. a bookmarked link) falls back to it
+    {
+        let on_tab_change = on_tab_change.clone();
+        let default_tab = default_tab.clone();
+        let opened_bare = web_sys::window()
+            .and_then(|window| window.location().pathname().ok())
+            .is_some_and(|path| path == "/admin" || path == "/admin/");
+        let landing = use_mut_ref(|| opened_bare);
+        use_effect_with_deps(move |(current_tab, allowed)| {
+            let landing = std::mem::replace(&mut *landing.borrow_mut(), false);
+            if (!*allowed || landing) && *current_tab != default_tab {
+                on_tab_change.emit(default_tab);
+            }
+            || ()
+        }, (props.current_tab.clone(), allowed));
+    }
 
     // Load saved admin theme from database on component mount and cleanup on unmount
     use_effect_with_deps(|_| {
@@ -49,6 +76,7 @@ pub fn admin(props: &AdminProps) -> Html {
                         if let Some(head) = document.head() {
                             if let Ok(style_el) = document.create_element("style") {
                                 style_el.set_id("container-settings-overrides");
+                                tag_with_nonce(&style_el);
                                 let css_text = format!(":root {{\n    {}\n}}", vars.join("\n    "));
                                 style_el.set_text_content(Some(&css_text));
                                 let _ = head.append_child(&style_el);
@@ -66,73 +94,10 @@ pub fn admin(props: &AdminProps) -> Html {
             // Fetch component templates to ensure defaults are in effect for preview areas
             let _ = get_component_templates().await;
 
-            // Try to load the saved theme from database
-            match get_settings(Some("theme")).await {
-                Ok(theme_settings) => {
-                    if !theme_settings.is_empty() {
-                        let mut current_theme = "Light Preset".to_string();
-                        let mut found_current_theme = false;
-                        let mut saved_admin_schemes: std::collections::HashMap<String, AdminColorScheme> = std::collections::HashMap::new();
-                        
-                        // Process theme settings to find the current theme
-                        for setting in theme_settings {
-                            match setting.setting_key.as_str() {
-                                "theme_current_admin" => {
-                                    if let Some(theme_name) = setting.setting_value {
-                                        current_theme = theme_name;
-                                        found_current_theme = true;
-                                        log::info!("🎨 Admin loading saved theme: {}", current_theme);
-                                    }
-                                },
-                                key if key.starts_with("theme_admin_") => {
-                                    let theme_name = key.strip_prefix("theme_admin_").unwrap_or("");
-                                    if let Some(theme_json) = setting.setting_value {
-                                        if let Ok(scheme) = serde_json::from_str::<AdminColorScheme>(&theme_json) {
-                                            saved_admin_schemes.insert(theme_name.to_string(), scheme);
-                                        }
-                                    }
-                                },
-                                _ => {}
-                            }
-                        }
-                        
-                        // Apply the saved theme if found
-                        if found_current_theme {
-                            let scheme = match current_theme.as_str() {
-                                "Dark Preset" => AdminColorScheme::dark_mode(),
-                                "Light Preset" => AdminColorScheme::default(),
-                                custom_name => {
-                                    saved_admin_schemes.get(custom_name)
-                                        .cloned()
-                                        .unwrap_or_else(|| {
-                                            log::warn!("🎨 Custom theme '{}' not found in admin, using light preset", custom_name);
-                                            AdminColorScheme::default()
-                                        })
-                                }
-                            };
-                            apply_admin_css_variables(&scheme);
-                            log::info!("✅ Applied saved admin theme: {}", current_theme);
-                        } else {
-                            // No current theme setting found, use default
-                            let default_scheme = AdminColorScheme::default();
-                            apply_admin_css_variables(&default_scheme);
-                            log::info!("🎨 No saved theme found, using default light theme");
-                        }
-                    } else {
-                        // No theme settings at all, use default
-                        let default_scheme = AdminColorScheme::default();
-                        apply_admin_css_variables(&default_scheme);
-                        log::info!("🎨 No theme settings in database, using default light theme");
-                    }
-                },
-                Err(err) => {
-                    // Database error, use default but don't override if CSS variables already set
-                    log::error!("❌ Failed to load admin theme settings: {:?}", err);
-                    let default_scheme = AdminColorScheme::default();
-                    apply_admin_css_variables(&default_scheme);
-                    log::info!("🎨 Using default theme due to database error");
-                }
-            }
+            // Apply the active admin color scheme, or the light preset if it is missing
+            let (theme_name, scheme) = load_active_admin_scheme().await;
+            apply_admin_css_variables(&scheme);
+            log::info!("✅ Applied admin theme: {}", theme_name);
         });
         
         // Cleanup function to restore body styles when leaving admin
@@ -149,20 +114,35 @@ pub fn admin(props: &AdminProps) -> Html {
     }, ());
 
     html! {
-        <div class="admin-layout">
+        <div class="admin-layout" dir={text_direction.as_str()}>
             <AdminHeader 
                 on_public_click={props.on_public_click.clone()}
                 on_logout={props.on_logout.clone()}
+                on_profile_click={{
+                    let on_tab_change = on_tab_change.clone();
+                    Callback::from(move |_| on_tab_change.emit(AdminTab::Profile))
+                }}
+                on_search_select={{
+                    let on_tab_change = on_tab_change.clone();
+                    Callback::from(move |result: SearchResult| {
+                        if result.kind == "comment" {
+                            on_tab_change.emit(AdminTab::Comments);
+                        } else if let Some(window) = web_sys::window() {
+                            let _ = window.open_with_url_and_target(&result.url, "_blank");
+                        }
+                    })
+                }}
                 current_user={props.current_user.clone()}
             />
             <div class="admin-content">
                 <AdminSidebar 
                     on_tab_click={on_tab_change.clone()} 
-                    active_tab={props.current_tab.clone()} 
+                    active_tab={shown_tab.clone()} 
+                    permissions={permissions.clone()}
                     on_public_click={props.on_public_click.clone()}
                 />
                 <main class="admin-main">
-                    {match props.current_tab {
+                    {match shown_tab {
                         AdminTab::Dashboard => html! { <AdminDashboard on_navigate={on_tab_change.clone()} /> },
                         AdminTab::Posts => html! { <PostList on_navigate={on_tab_change.clone()} /> },
                         AdminTab::PostCreate => {
@@ -176,15 +156,18 @@ pub fn admin(props: &AdminProps) -> Html {
                             };
                             html! { <PostEditor post={None} on_save={on_save} on_cancel={on_cancel} /> }
                         },
+                        AdminTab::PostCalendar => html! { <PostCalendar on_navigate={on_tab_change.clone()} /> },
                         AdminTab::Pages => html! { <PageBuilder /> },
                         AdminTab::Media => html! { <MediaLibrary /> },
                         AdminTab::Users => html! { <EnhancedUserManagement /> },
                         AdminTab::Comments => html! { <CommentModeration /> },
+                        AdminTab::Sessions => html! { <SessionManagement /> },
                         AdminTab::Navigation => html! { <NavigationManager /> },
                         AdminTab::Templates => html! { <TemplateManager /> },
                         AdminTab::Analytics => html! { <Analytics /> },
                         AdminTab::DesignSystem => html! { <DesignSystemPage /> },
                         AdminTab::SystemSettings => html! { <SystemSettings /> },
+                        AdminTab::Profile => html! { <Profile /> },
                     }}
                 </main>
             </div>
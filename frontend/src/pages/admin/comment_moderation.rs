@@ -1,18 +1,44 @@
 use yew::prelude::*;
-use crate::services::api_service::{get_comments_with_relations, delete_comment, CommentWithRelations};
+use crate::services::api_service::{get_comments_with_relations, delete_comment, bulk_moderate_comments, BulkModerationReport, CommentWithRelations};
 use wasm_bindgen::JsCast;
+use crate::services::timezone_context::use_display_timezone;
 
 #[derive(Clone, PartialEq)]
 pub enum CommentFilter {
     All,
+    Status(&'static str),
 }
 
+impl CommentFilter {
+    fn matches(&self, comment: &CommentWithRelations) -> bool {
+        match self {
+            CommentFilter::All => true,
+            CommentFilter::Status(status) => comment.status == *status,
+        }
+    }
+}
+
+const FILTERS: [(&str, &str); 5] = [("all", "All"), ("pending", "Pending"), ("approved", "Approved"), ("rejected", "Rejected"), ("spam", "Spam")];
+
+/// Bulk actions with their button labels and classes
+const BULK_ACTIONS: [(&str, &str, &str); 4] = [
+    ("approve", "Approve", "btn btn-secondary"),
+    ("reject", "Reject", "btn btn-secondary"),
+    ("spam", "Mark as Spam", "btn btn-secondary"),
+    ("delete", "Delete", "btn btn-danger"),
+];
+
 #[function_component(CommentModeration)]
 pub fn comment_moderation() -> Html {
     let comments = use_state(Vec::<CommentWithRelations>::new);
+    let timezone = use_display_timezone();
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
     let selected_comments = use_state(|| std::collections::HashSet::<i32>::new());
+    let filter = use_state(|| CommentFilter::All);
+    let bulk_report = use_state(|| None::<BulkModerationReport>);
+    let bulk_busy = use_state(|| false);
+    let visible: Vec<CommentWithRelations> = (*comments).iter().filter(|c| filter.matches(c)).cloned().collect();
 
     // Load comments
     {
@@ -51,13 +77,13 @@ pub fn comment_moderation() -> Html {
     };
 
     let on_select_all = {
-        let comments = comments.clone();
+        let visible = visible.clone();
         let selected_comments = selected_comments.clone();
         Callback::from(move |e: Event| {
             let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
             let mut current = std::collections::HashSet::<i32>::new();
             if target.checked() {
-                for comment in (*comments).iter() {
+                for comment in visible.iter() {
                     if let Some(id) = comment.id {
                         current.insert(id);
                     }
@@ -92,17 +118,70 @@ pub fn comment_moderation() -> Html {
         })
     };
 
-    let on_bulk_delete = {
+    let on_filter_change = {
+        let filter = filter.clone();
+        let selected_comments = selected_comments.clone();
+        Callback::from(move |e: Event| {
+            let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+            let value = FILTERS.iter().map(|(value, _)| *value).find(|value| *value == target.value()).unwrap_or("all");
+            filter.set(if value == "all" { CommentFilter::All } else { CommentFilter::Status(value) });
+            selected_comments.set(std::collections::HashSet::new());
+        })
+    };
+
+    let on_bulk_action = {
+        let comments = comments.clone();
         let selected_comments = selected_comments.clone();
-        let on_delete_comment = on_delete_comment.clone();
-        Callback::from(move |_| {
-            let selected = (*selected_comments).clone();
-            for comment_id in selected {
-                on_delete_comment.emit(comment_id);
+        let bulk_report = bulk_report.clone();
+        let bulk_busy = bulk_busy.clone();
+        let error = error.clone();
+        Callback::from(move |action: &'static str| {
+            let mut ids: Vec<i32> = (*selected_comments).iter().copied().collect();
+            ids.sort_unstable();
+            if action == "delete" {
+                let message = format!("Permanently delete {} comments?", ids.len());
+                if !web_sys::window().and_then(|w| w.confirm_with_message(&message).ok()).unwrap_or(false) {
+                    return;
+                }
             }
+            let comments = comments.clone();
+            let selected_comments = selected_comments.clone();
+            let bulk_report = bulk_report.clone();
+            let bulk_busy = bulk_busy.clone();
+            let error = error.clone();
+            bulk_busy.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                match bulk_moderate_comments(action, &ids).await {
+                    Ok(report) => {
+                        // Deleted comments and ones found to be gone leave the
+                        // list; the rest take their new status
+                        let new_comments: Vec<CommentWithRelations> = (*comments)
+                            .iter()
+                            .filter_map(|comment| {
+                                let Some(result) = report.results.iter().find(|r| Some(r.id) == comment.id) else {
+                                    return Some(comment.clone());
+                                };
+                                result.status.clone().map(|status| CommentWithRelations { status, ..comment.clone() })
+                            })
+                            .collect();
+                        comments.set(new_comments);
+                        selected_comments.set(std::collections::HashSet::new());
+                        bulk_report.set(Some(report));
+                    }
+                    Err(e) => {
+                        error.set(Some(format!("Bulk {} failed: {}", action, e)));
+                    }
+                }
+                bulk_busy.set(false);
+            });
         })
     };
 
+    let on_dismiss_report = {
+        let bulk_report = bulk_report.clone();
+        Callback::from(move |_| bulk_report.set(None))
+    };
+
     if *loading {
         html! {
             <div class="comment-moderation">
@@ -123,10 +202,49 @@ pub fn comment_moderation() -> Html {
                     <div class="error-message">{"Error: "}{error_msg}</div>
                 }
 
+                if let Some(ref report) = *bulk_report {
+                    <div class="bulk-report">
+                        <div class="bulk-report-header">
+                            <strong>{format!("{}: {} succeeded, {} failed", report.action, report.succeeded, report.failed)}</strong>
+                            <button class="btn btn-secondary" onclick={on_dismiss_report}>{"Dismiss"}</button>
+                        </div>
+                        if report.failed > 0 {
+                            <ul>
+                                {for report.results.iter().filter(|result| !result.success).map(|result| html! {
+                                    <li class="bulk-failed">
+                                        {format!("Comment #{}: {}", result.id, result.error.as_deref().unwrap_or("failed"))}
+                                    </li>
+                                })}
+                            </ul>
+                        }
+                    </div>
+                }
+
+                <div class="comment-filters">
+                    <label for="comment-status-filter">{"Show: "}</label>
+                    <select id="comment-status-filter" onchange={on_filter_change}>
+                        {for FILTERS.iter().map(|(value, label)| {
+                            let selected = match *filter {
+                                CommentFilter::All => *value == "all",
+                                CommentFilter::Status(status) => *value == status,
+                            };
+                            html! { <option value={*value} {selected}>{*label}</option> }
+                        })}
+                    </select>
+                </div>
+
                 if !(*selected_comments).is_empty() {
                     <div class="bulk-actions">
                         <span>{"Selected: "}{(*selected_comments).len()}{" comments"}</span>
-                        <button class="btn btn-danger" onclick={on_bulk_delete}>{"Delete Selected"}</button>
+                        {for BULK_ACTIONS.iter().map(|(action, label, class)| {
+                            let on_bulk_action = on_bulk_action.clone();
+                            let action = *action;
+                            html! {
+                                <button class={*class} disabled={*bulk_busy} onclick={Callback::from(move |_| on_bulk_action.emit(action))}>
+                                    {*label}
+                                </button>
+                            }
+                        })}
                     </div>
                 }
 
@@ -138,18 +256,19 @@ pub fn comment_moderation() -> Html {
                                     <input 
                                         type="checkbox" 
                                         onchange={on_select_all}
-                                        checked={(*selected_comments).len() == (*comments).len() && !(*comments).is_empty()}
+                                        checked={(*selected_comments).len() == visible.len() && !visible.is_empty()}
                                     />
                                 </th>
                                 <th>{"Author"}</th>
                                 <th>{"Comment"}</th>
+                                <th>{"Status"}</th>
                                 <th>{"Post ID"}</th>
                                 <th>{"Created"}</th>
                                 <th>{"Actions"}</th>
                             </tr>
                         </thead>
                         <tbody>
-                            {(*comments).iter().map(|comment| {
+                            {visible.iter().map(|comment| {
                                 let comment_id = comment.id.unwrap_or(0);
                                 let is_selected = (*selected_comments).contains(&comment_id);
                                 
@@ -179,8 +298,9 @@ pub fn comment_moderation() -> Html {
                                         </td>
                                         <td>{comment.author_username.as_ref().unwrap_or(&"Anonymous".to_string())}</td>
                                         <td class="comment-content">{&comment.content}</td>
+                                        <td><span class={classes!("status-badge", comment.status.clone())}>{&comment.status}</span></td>
                                         <td>{comment.post_id.map(|id| id.to_string()).unwrap_or_else(|| "N/A".to_string())}</td>
-                                        <td>{comment.created_at.as_ref().unwrap_or(&"N/A".to_string())}</td>
+                                        <td>{comment.created_at.as_deref().map(|date| timezone.datetime(date)).unwrap_or_else(|| "N/A".to_string())}</td>
                                         <td class="actions">
                                             <button class="btn btn-small btn-danger" onclick={on_delete}>{"Delete"}</button>
                                         </td>
@@ -191,7 +311,7 @@ pub fn comment_moderation() -> Html {
                     </table>
                 </div>
 
-                if (*comments).is_empty() {
+                if visible.is_empty() {
                     <div class="empty-state">
                         <p>{"No comments found."}</p>
                     </div>
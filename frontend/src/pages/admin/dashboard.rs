@@ -2,6 +2,7 @@ use yew::prelude::*;
 use crate::services::api_service::{get_posts, get_media, get_comments, get_pages, Post, MediaItem};
 use crate::services::migrate_pages::create_essential_pages;
 use crate::components::admin::sidebar::AdminTab;
+use crate::services::timezone_context::use_display_timezone;
 
 #[derive(Properties, PartialEq)]
 pub struct AdminDashboardProps {
@@ -31,6 +32,7 @@ pub fn admin_dashboard(props: &AdminDashboardProps) -> Html {
     });
     
     let recent_posts = use_state(Vec::<Post>::new);
+    let timezone = use_display_timezone();
     let recent_media = use_state(Vec::<MediaItem>::new);
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
@@ -230,7 +232,7 @@ pub fn admin_dashboard(props: &AdminDashboardProps) -> Html {
                                                     <div class="post-meta">
                                                         <span class="status-badge status-badge-{post.status.clone()}">{&post.status}</span>
                                                         <span class="post-date">
-                                                            {post.created_at.as_ref().unwrap_or(&"Unknown date".to_string())}
+                                                            {post.created_at.as_deref().map(|date| timezone.datetime(date)).unwrap_or_else(|| "Unknown date".to_string())}
                                                         </span>
                                                     </div>
                                                 </div>
@@ -272,7 +274,7 @@ pub fn admin_dashboard(props: &AdminDashboardProps) -> Html {
                                                             html! {}
                                                         }}
                                                         <span class="media-date">
-                                                            {media.created_at.as_ref().unwrap_or(&"Unknown date".to_string())}
+                                                            {media.created_at.as_deref().map(|date| timezone.datetime(date)).unwrap_or_else(|| "Unknown date".to_string())}
                                                         </span>
                                                     </p>
                                                 </div>
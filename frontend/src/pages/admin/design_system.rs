@@ -1,7 +1,13 @@
 use yew::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlInputElement, HtmlSelectElement};
-use crate::services::api_service::{get_settings, update_settings, SettingData};
+use crate::services::api_service::{
+    ColorSchemeSummary, list_color_schemes, get_color_scheme, save_color_scheme,
+    rename_color_scheme, delete_color_scheme, activate_color_scheme,
+};
+use crate::services::toast_context::use_toast;
+use crate::services::csp::tag_with_nonce;
+use crate::services::color_contrast::{check_contrast, AA_NORMAL_TEXT};
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct AdminColorScheme {
@@ -565,110 +571,148 @@ impl Default for PublicColorScheme {
     }
 }
 
+impl PublicColorScheme {
+    // Dark public theme with light text on slate backgrounds
+    pub fn dark_mode() -> Self {
+        Self {
+            name: "Public Dark Theme".to_string(),
+            // Dark theme text hierarchy
+            text_primary: "#f8fafc".to_string(),
+            text_secondary: "#e2e8f0".to_string(),
+            text_meta: "#cbd5e1".to_string(),
+            text_light: "#94a3b8".to_string(),
+            text_muted: "#64748b".to_string(),
+            
+            // Dark theme link colors
+            link_primary: "#60a5fa".to_string(),
+            link_hover: "#93c5fd".to_string(),
+            link_visited: "#a78bfa".to_string(),
+            link_active: "#3b82f6".to_string(),
+            
+            // Dark theme heading hierarchy
+            heading_h1: "#f8fafc".to_string(),
+            heading_h2: "#f1f5f9".to_string(),
+            heading_h3: "#e2e8f0".to_string(),
+            heading_h4: "#cbd5e1".to_string(),
+            heading_h5: "#94a3b8".to_string(),
+            heading_h6: "#64748b".to_string(),
+            
+            // Dark theme context-specific text
+            header_text: "#f8fafc".to_string(),
+            header_text_hover: "#cbd5e1".to_string(),
+            footer_text: "#e2e8f0".to_string(),
+            footer_text_muted: "#94a3b8".to_string(),
+            
+            // Dark theme semantic colors
+            success: "#10b981".to_string(),
+            warning: "#f59e0b".to_string(),
+            danger: "#ef4444".to_string(),
+            info: "#06b6d4".to_string(),
+            
+            // Dark theme layout colors
+            border_light: "#334155".to_string(),
+            background_light: "#1e293b".to_string(),
+            header_bg: "#1e293b".to_string(),
+            footer_bg: "#1e293b".to_string(),
+            hero_bg: "#0f172a".to_string(),
+            card_shadow: "rgba(0, 0, 0, 0.3)".to_string(),
+        }
+    }
+}
+
+/// Admin colors for a built-in preset or saved custom scheme, falling back
+/// to the light preset if the scheme is missing or unreadable
+pub async fn admin_scheme_named(name: &str) -> AdminColorScheme {
+    match name {
+        "Light Preset" => return AdminColorScheme::default(),
+        "Dark Preset" => return AdminColorScheme::dark_mode(),
+        _ => {}
+    }
+    let stored = get_color_scheme(name).await.ok()
+        .and_then(|stored| stored.admin)
+        .and_then(|admin| serde_json::from_value::<AdminColorScheme>(admin).ok());
+    stored.unwrap_or_else(|| {
+        log::warn!("Custom admin theme '{}' not found, falling back to light preset", name);
+        AdminColorScheme::default()
+    })
+}
+
+/// Public colors for a built-in preset or saved custom scheme, falling back
+/// to the default public theme
+pub async fn public_scheme_named(name: &str) -> PublicColorScheme {
+    match name {
+        "Light Preset" => return PublicColorScheme::default(),
+        "Dark Preset" => return PublicColorScheme::dark_mode(),
+        _ => {}
+    }
+    let stored = get_color_scheme(name).await.ok()
+        .and_then(|stored| stored.public)
+        .and_then(|public| serde_json::from_value::<PublicColorScheme>(public).ok());
+    stored.unwrap_or_else(|| {
+        log::warn!("Custom public theme '{}' not found, falling back to default", name);
+        PublicColorScheme::default()
+    })
+}
+
+/// Name and colors of the active admin scheme, defaulting to the light preset
+/// when none is set or the settings can't be loaded
+pub async fn load_active_admin_scheme() -> (String, AdminColorScheme) {
+    match list_color_schemes().await {
+        Ok(list) => {
+            let scheme = admin_scheme_named(&list.current_admin).await;
+            (list.current_admin, scheme)
+        }
+        Err(err) => {
+            log::error!("Failed to load admin theme settings: {}", err);
+            ("Light Preset".to_string(), AdminColorScheme::default())
+        }
+    }
+}
+
 #[function_component(DesignSystemPage)]
 pub fn design_system_page() -> Html {
     let admin_scheme = use_state(|| AdminColorScheme::default());
     let public_scheme = use_state(|| PublicColorScheme::default());
     let current_tab = use_state(|| "admin".to_string());
-    let saved_themes = use_state(|| vec![
-        "Light Preset".to_string(),
-        "Dark Preset".to_string(),
-    ]);
+    let color_schemes = use_state(Vec::<ColorSchemeSummary>::new);
     let selected_preset = use_state(|| "Light Preset".to_string());
     let theme_name_input = use_state(|| String::new());
+    let toast = use_toast();
 
-    // Load theme from database on component mount
+    // Load the scheme list and the active admin scheme on mount
     {
         let admin_scheme = admin_scheme.clone();
         let selected_preset = selected_preset.clone();
-        let saved_themes = saved_themes.clone();
-        
+        let color_schemes = color_schemes.clone();
         use_effect_with_deps(move |_| {
             wasm_bindgen_futures::spawn_local(async move {
-                // Load current theme and saved themes from database
-                match get_settings(Some("theme")).await {
-                    Ok(theme_settings) => {
-                        if theme_settings.is_empty() {
-                            // No theme settings found - use defaults but don't force apply
-                            log::info!("No theme settings found in database, using defaults without forcing application");
-                            return;
-                        }
-                        
-                        let mut current_theme = "Light Preset".to_string();
-                        let mut custom_themes = vec!["Light Preset".to_string(), "Dark Preset".to_string()];
-                        let mut found_current_theme = false;
-                        
-                        let mut saved_admin_schemes: std::collections::HashMap<String, AdminColorScheme> = std::collections::HashMap::new();
-                        
-                        // Process theme settings
-                        for setting in theme_settings {
-                            match setting.setting_key.as_str() {
-                                "theme_current_admin" => {
-                                    if let Some(theme_name) = setting.setting_value {
-                                        current_theme = theme_name;
-                                        found_current_theme = true;
-                                        log::info!("Loaded current admin theme from database: {}", current_theme);
-                                    }
-                                },
-                                "theme_custom_list" => {
-                                    if let Some(themes_json) = setting.setting_value {
-                                        if let Ok(themes) = serde_json::from_str::<Vec<String>>(&themes_json) {
-                                            custom_themes = themes;
-                                            log::info!("Loaded {} custom themes from database", custom_themes.len());
-                                        }
-                                    }
-                                },
-                                key if key.starts_with("theme_admin_") => {
-                                    let theme_name = key.strip_prefix("theme_admin_").unwrap_or("");
-                                    if let Some(theme_json) = setting.setting_value {
-                                        if let Ok(scheme) = serde_json::from_str::<AdminColorScheme>(&theme_json) {
-                                            saved_admin_schemes.insert(theme_name.to_string(), scheme);
-                                            log::info!("Loaded custom admin theme: {}", theme_name);
-                                        }
-                                    }
-                                },
-                                _ => {}
-                            }
-                        }
-                        
-                        // Only apply theme if we found a current theme setting
-                        if found_current_theme {
-                            let scheme = match current_theme.as_str() {
-                                "Dark Preset" => AdminColorScheme::dark_mode(),
-                                "Light Preset" => AdminColorScheme::default(),
-                                custom_name => {
-                                    // Try to load custom theme, fallback to light
-                                    saved_admin_schemes.get(custom_name)
-                                        .cloned()
-                                        .unwrap_or_else(|| {
-                                            log::warn!("Custom theme '{}' not found, falling back to light preset", custom_name);
-                                            AdminColorScheme::default()
-                                        })
-                                }
-                            };
-                            
-                            selected_preset.set(current_theme.clone());
-                            saved_themes.set(custom_themes);
-                            admin_scheme.set(scheme.clone());
-                            apply_admin_css_variables(&scheme);
-                            log::info!("Successfully applied theme: {}", current_theme);
-                        } else {
-                            // Have theme data but no current theme setting - just update the lists
-                            saved_themes.set(custom_themes);
-                            log::info!("Updated theme lists but preserved current theme");
-                        }
-                    },
-                    Err(err) => {
-                        // Log the error but don't forcefully apply defaults
-                        log::error!("Failed to load theme settings from database: {:?}", err);
-                        log::info!("Preserving current theme due to database error");
+                match list_color_schemes().await {
+                    Ok(list) => {
+                        let scheme = admin_scheme_named(&list.current_admin).await;
+                        selected_preset.set(list.current_admin);
+                        color_schemes.set(list.schemes);
+                        admin_scheme.set(scheme);
                     }
+                    Err(err) => log::error!("Failed to load color schemes: {}", err),
                 }
             });
             || ()
         }, ());
     }
 
+    let refresh_schemes = {
+        let color_schemes = color_schemes.clone();
+        Callback::from(move |_: ()| {
+            let color_schemes = color_schemes.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match list_color_schemes().await {
+                    Ok(list) => color_schemes.set(list.schemes),
+                    Err(err) => log::error!("Failed to load color schemes: {}", err),
+                }
+            });
+        })
+    };
+
     let switch_tab = {
         let current_tab = current_tab.clone();
         Callback::from(move |tab: String| {
@@ -691,6 +735,8 @@ pub fn design_system_page() -> Html {
                 "surface" => scheme.surface = value,
                 "text_primary" => scheme.text_primary = value,
                 "text_secondary" => scheme.text_secondary = value,
+                "text_muted" => scheme.text_muted = value,
+                "text_link" => scheme.text_link = value,
                 "border" => scheme.border = value,
                 
                 // Header styling
@@ -719,6 +765,21 @@ pub fn design_system_page() -> Html {
                 "nav_link_public_hover_bg" => scheme.nav_link_public_hover_bg = value,
                 "nav_link_public_hover_text" => scheme.nav_link_public_hover_text = value,
                 
+                // Text on forms, buttons, tables and status badges
+                "form_label" => scheme.form_label = value,
+                "form_placeholder" => scheme.form_placeholder = value,
+                "btn_primary_text" => scheme.btn_primary_text = value,
+                "btn_secondary_text" => scheme.btn_secondary_text = value,
+                "btn_danger_text" => scheme.btn_danger_text = value,
+                "btn_success_text" => scheme.btn_success_text = value,
+                "table_header_text" => scheme.table_header_text = value,
+                "status_published_text" => scheme.status_published_text = value,
+                "status_draft_text" => scheme.status_draft_text = value,
+                "status_pending_text" => scheme.status_pending_text = value,
+                "status_approved_text" => scheme.status_approved_text = value,
+                "status_active_text" => scheme.status_active_text = value,
+                "status_inactive_text" => scheme.status_inactive_text = value,
+                
                 // General layout
                 "card_bg" => scheme.card_bg = value,
                 "shadow_color" => scheme.shadow_color = value,
@@ -809,213 +870,76 @@ pub fn design_system_page() -> Html {
         })
     };
 
-    // Removed unused preset callbacks - functionality moved to on_preset_change
-
-    // Removed unused preset callbacks - functionality moved to on_preset_change
-
+    // Selecting a scheme previews it; on the admin tab it also becomes the
+    // active admin scheme
     let on_preset_change = {
         let selected_preset = selected_preset.clone();
         let admin_scheme = admin_scheme.clone();
         let public_scheme = public_scheme.clone();
         let current_tab = current_tab.clone();
+        let color_schemes = color_schemes.clone();
+        let toast = toast.clone();
         Callback::from(move |event: web_sys::Event| {
             let input = event.target().unwrap().dyn_into::<HtmlSelectElement>().unwrap();
             let preset_name = input.value();
             selected_preset.set(preset_name.clone());
-            
-            // Load the preset based on current tab and selection
-            let current_tab_val = (*current_tab).clone();
-            if current_tab_val == "admin" {
-                if preset_name == "Dark Preset" {
-                    let scheme = AdminColorScheme::dark_mode();
-                    admin_scheme.set(scheme.clone());
-                    apply_admin_css_variables(&scheme);
-                    
-                    // Save current theme to database
-                    let preset_name_clone = preset_name.clone();
-                    wasm_bindgen_futures::spawn_local(async move {
-                        let settings_data = vec![
-                            SettingData {
-                                key: "theme_current_admin".to_string(),
-                                value: preset_name_clone,
-                                setting_type: "theme".to_string(),
-                                description: Some("Current active admin theme".to_string()),
-                            }
-                        ];
-                        let _ = update_settings(settings_data).await;
-                    });
-                } else if preset_name == "Light Preset" {
-                    let scheme = AdminColorScheme::default(); // Light mode is now default
-                    admin_scheme.set(scheme.clone());
+
+            let is_admin_tab = *current_tab == "admin";
+            let admin_scheme = admin_scheme.clone();
+            let public_scheme = public_scheme.clone();
+            let color_schemes = color_schemes.clone();
+            let toast = toast.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if is_admin_tab {
+                    let scheme = admin_scheme_named(&preset_name).await;
                     apply_admin_css_variables(&scheme);
-                    
-                    // Save current theme to database
-                    let preset_name_clone = preset_name.clone();
-                    wasm_bindgen_futures::spawn_local(async move {
-                        let settings_data = vec![
-                            SettingData {
-                                key: "theme_current_admin".to_string(),
-                                value: preset_name_clone,
-                                setting_type: "theme".to_string(),
-                                description: Some("Current active admin theme".to_string()),
-                            }
-                        ];
-                        let _ = update_settings(settings_data).await;
-                    });
+                    admin_scheme.set(scheme);
+                    match activate_color_scheme(&preset_name).await {
+                        Ok(list) => color_schemes.set(list.schemes),
+                        Err(err) => toast.error(format!("Failed to activate '{}': {}", preset_name, err)),
+                    }
                 } else {
-                    // Handle custom admin themes
-                    let preset_name_clone = preset_name.clone();
-                    let admin_scheme_clone = admin_scheme.clone();
-                    
-                    wasm_bindgen_futures::spawn_local(async move {
-                        // Load custom theme from database
-                        match get_settings(Some("theme")).await {
-                            Ok(theme_settings) => {
-                                let theme_key = format!("theme_admin_{}", preset_name_clone);
-                                for setting in theme_settings {
-                                    if setting.setting_key == theme_key {
-                                        if let Some(theme_json) = setting.setting_value {
-                                            if let Ok(scheme) = serde_json::from_str::<AdminColorScheme>(&theme_json) {
-                                                admin_scheme_clone.set(scheme.clone());
-                                                apply_admin_css_variables(&scheme);
-                                                break;
-                                            }
-                                        }
-                                    }
-                                }
-                                
-                                // Save current theme selection
-                                let settings_data = vec![
-                                    SettingData {
-                                        key: "theme_current_admin".to_string(),
-                                        value: preset_name_clone.clone(),
-                                        setting_type: "theme".to_string(),
-                                        description: Some("Current active admin theme".to_string()),
-                                    }
-                                ];
-                                let _ = update_settings(settings_data).await;
-                            },
-                            Err(_) => {
-                                // Fallback to light theme if custom theme can't be loaded
-                                let fallback_scheme = AdminColorScheme::default();
-                                admin_scheme_clone.set(fallback_scheme.clone());
-                                apply_admin_css_variables(&fallback_scheme);
-                            }
-                        }
-                    });
-                }
-            } else if current_tab_val == "public" {
-                if preset_name == "Dark Preset" {
-                    let scheme = PublicColorScheme {
-                        name: "Public Dark Theme".to_string(),
-                        // Dark theme text hierarchy
-                        text_primary: "#f8fafc".to_string(),
-                        text_secondary: "#e2e8f0".to_string(),
-                        text_meta: "#cbd5e1".to_string(),
-                        text_light: "#94a3b8".to_string(),
-                        text_muted: "#64748b".to_string(),
-                        
-                        // Dark theme link colors
-                        link_primary: "#60a5fa".to_string(),
-                        link_hover: "#93c5fd".to_string(),
-                        link_visited: "#a78bfa".to_string(),
-                        link_active: "#3b82f6".to_string(),
-                        
-                        // Dark theme heading hierarchy
-                        heading_h1: "#f8fafc".to_string(),
-                        heading_h2: "#f1f5f9".to_string(),
-                        heading_h3: "#e2e8f0".to_string(),
-                        heading_h4: "#cbd5e1".to_string(),
-                        heading_h5: "#94a3b8".to_string(),
-                        heading_h6: "#64748b".to_string(),
-                        
-                        // Dark theme context-specific text
-                        header_text: "#f8fafc".to_string(),
-                        header_text_hover: "#cbd5e1".to_string(),
-                        footer_text: "#e2e8f0".to_string(),
-                        footer_text_muted: "#94a3b8".to_string(),
-                        
-                        // Dark theme semantic colors
-                        success: "#10b981".to_string(),
-                        warning: "#f59e0b".to_string(),
-                        danger: "#ef4444".to_string(),
-                        info: "#06b6d4".to_string(),
-                        
-                        // Dark theme layout colors
-                        border_light: "#334155".to_string(),
-                        background_light: "#1e293b".to_string(),
-                        header_bg: "#1e293b".to_string(),
-                        footer_bg: "#1e293b".to_string(),
-                        hero_bg: "#0f172a".to_string(),
-                        card_shadow: "rgba(0, 0, 0, 0.3)".to_string(),
-                    };
-                    public_scheme.set(scheme.clone());
-                    apply_public_css_variables(&scheme);
-                } else if preset_name == "Light Preset" {
-                    let scheme = PublicColorScheme::default();
-                    public_scheme.set(scheme.clone());
+                    let scheme = public_scheme_named(&preset_name).await;
                     apply_public_css_variables(&scheme);
+                    public_scheme.set(scheme);
                 }
-            }
+            });
         })
     };
 
+    // Save the current tab's colors under the entered name, creating or
+    // updating that custom scheme
     let save_theme = {
         let theme_name_input = theme_name_input.clone();
-        let saved_themes = saved_themes.clone();
         let current_tab = current_tab.clone();
         let admin_scheme = admin_scheme.clone();
         let public_scheme = public_scheme.clone();
+        let refresh_schemes = refresh_schemes.clone();
+        let toast = toast.clone();
         Callback::from(move |_: MouseEvent| {
-            let theme_name = (*theme_name_input).clone();
-            if !theme_name.is_empty() {
-                let mut themes = (*saved_themes).clone();
-                if !themes.contains(&theme_name) {
-                    themes.push(theme_name.clone());
-                    saved_themes.set(themes.clone());
-                    
-                    // Save custom theme list and theme data to database
-                    let current_tab_val = (*current_tab).clone();
-                    let admin_scheme_clone = (*admin_scheme).clone();
-                    let public_scheme_clone = (*public_scheme).clone();
-                    
-                    wasm_bindgen_futures::spawn_local(async move {
-                        let mut settings_data = vec![
-                            // Save updated custom themes list
-                            SettingData {
-                                key: "theme_custom_list".to_string(),
-                                value: serde_json::to_string(&themes).unwrap_or_default(),
-                                setting_type: "theme".to_string(),
-                                description: Some("List of custom saved themes".to_string()),
-                            }
-                        ];
-                        
-                        // Save the actual theme data based on current tab
-                        match current_tab_val.as_str() {
-                            "admin" => {
-                                settings_data.push(SettingData {
-                                    key: format!("theme_admin_{}", theme_name),
-                                    value: serde_json::to_string(&admin_scheme_clone).unwrap_or_default(),
-                                    setting_type: "theme".to_string(),
-                                    description: Some(format!("Admin theme data for {}", theme_name)),
-                                });
-                            },
-                            "public" => {
-                                settings_data.push(SettingData {
-                                    key: format!("theme_public_{}", theme_name),
-                                    value: serde_json::to_string(&public_scheme_clone).unwrap_or_default(),
-                                    setting_type: "theme".to_string(),
-                                    description: Some(format!("Public theme data for {}", theme_name)),
-                                });
-                            },
-                            _ => {}
-                        }
-                        
-                        let _ = update_settings(settings_data).await;
-                    });
-                }
-                theme_name_input.set(String::new());
+            let theme_name = theme_name_input.trim().to_string();
+            if theme_name.is_empty() {
+                return;
             }
+            let (admin, public) = match current_tab.as_str() {
+                "admin" => (serde_json::to_value(&*admin_scheme).ok(), None),
+                "public" => (None, serde_json::to_value(&*public_scheme).ok()),
+                _ => return,
+            };
+
+            let theme_name_input = theme_name_input.clone();
+            let refresh_schemes = refresh_schemes.clone();
+            let toast = toast.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match save_color_scheme(&theme_name, admin, public).await {
+                    Ok(saved) => {
+                        toast.success(format!("Saved color scheme '{}'", saved.name));
+                        theme_name_input.set(String::new());
+                        refresh_schemes.emit(());
+                    }
+                    Err(err) => toast.error(format!("Failed to save color scheme: {}", err)),
+                }
+            });
         })
     };
 
@@ -1074,10 +998,10 @@ pub fn design_system_page() -> Html {
                                         <div class="theme-controls">
                                                                         <div class="preset-controls">
                                 <select class="preset-dropdown" onchange={on_preset_change.clone()}>
-                                    {for (*saved_themes).iter().map(|theme| {
-                                        let is_selected = *selected_preset == *theme;
+                                    {for color_schemes.iter().filter(|scheme| scheme.has_admin).map(|scheme| {
+                                        let is_selected = *selected_preset == scheme.name;
                                         html! {
-                                            <option value={theme.clone()} selected={is_selected}>{theme.clone()}</option>
+                                            <option value={scheme.name.clone()} selected={is_selected}>{scheme.name.clone()}</option>
                                         }
                                     })}
                                 </select>
@@ -1382,6 +1306,11 @@ pub fn design_system_page() -> Html {
                                         </div>
                                     </div>
 
+                                    <ColorSchemeManager
+                                        schemes={(*color_schemes).clone()}
+                                        on_changed={refresh_schemes.clone()}
+                                    />
+
                                     <div class="apply-actions">
                                         <button class="save-controls-button save-theme-button" onclick={apply_admin_theme}>
                                             {"Apply Admin Theme"}
@@ -1392,6 +1321,10 @@ pub fn design_system_page() -> Html {
                                 <div class="color-preview">
                                     <h3>{"Admin Preview"}</h3>
                                     <AdminPreview scheme={(*admin_scheme).clone()} />
+                                    <ContrastReport
+                                        pairs={admin_contrast_pairs(&admin_scheme)}
+                                        on_apply={update_admin_color.clone()}
+                                    />
                                 </div>
                             </div>
                         </div>
@@ -1405,10 +1338,10 @@ pub fn design_system_page() -> Html {
                                         <div class="theme-controls">
                                                                         <div class="preset-controls">
                                 <select class="preset-dropdown" onchange={on_preset_change.clone()}>
-                                    {for (*saved_themes).iter().map(|theme| {
-                                        let is_selected = *selected_preset == *theme;
+                                    {for color_schemes.iter().filter(|scheme| scheme.has_public).map(|scheme| {
+                                        let is_selected = *selected_preset == scheme.name;
                                         html! {
-                                            <option value={theme.clone()} selected={is_selected}>{theme.clone()}</option>
+                                            <option value={scheme.name.clone()} selected={is_selected}>{scheme.name.clone()}</option>
                                         }
                                     })}
                                 </select>
@@ -1619,6 +1552,11 @@ pub fn design_system_page() -> Html {
                                         </div>
                                     </div>
 
+                                    <ColorSchemeManager
+                                        schemes={(*color_schemes).clone()}
+                                        on_changed={refresh_schemes.clone()}
+                                    />
+
                                     <div class="apply-actions">
                                         <button class="save-controls-button save-theme-button" onclick={apply_public_theme}>
                                             {"Apply Public Theme"}
@@ -1629,6 +1567,10 @@ pub fn design_system_page() -> Html {
                                 <div class="color-preview-row">
                                     <h3>{"Public Preview"}</h3>
                                     <PublicPreview scheme={(*public_scheme).clone()} />
+                                    <ContrastReport
+                                        pairs={public_contrast_pairs(&public_scheme)}
+                                        on_apply={update_public_color.clone()}
+                                    />
                                     
                                     <div class="public-text-preview">
                                         <h3>{"Text System Overview"}</h3>
@@ -1978,6 +1920,257 @@ fn public_color_input(props: &PublicColorInputProps) -> Html {
     }
 }
 
+#[derive(Properties, PartialEq)]
+struct ColorSchemeManagerProps {
+    schemes: Vec<ColorSchemeSummary>,
+    on_changed: Callback<()>,
+}
+
+/// Rename and delete saved custom schemes. The active admin scheme can't be
+/// deleted until another one is selected.
+#[function_component(ColorSchemeManager)]
+fn color_scheme_manager(props: &ColorSchemeManagerProps) -> Html {
+    let toast = use_toast();
+    let custom: Vec<&ColorSchemeSummary> = props.schemes.iter().filter(|scheme| !scheme.built_in).collect();
+    if custom.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="color-scheme-manager">
+            <h4>{"Saved Schemes"}</h4>
+            <ul>
+                {for custom.into_iter().map(|scheme| {
+                    let on_rename = {
+                        let name = scheme.name.clone();
+                        let on_changed = props.on_changed.clone();
+                        let toast = toast.clone();
+                        Callback::from(move |_: MouseEvent| {
+                            let new_name = web_sys::window()
+                                .and_then(|w| w.prompt_with_message_and_default("Rename color scheme to:", &name).ok().flatten())
+                                .map(|new_name| new_name.trim().to_string())
+                                .filter(|new_name| !new_name.is_empty() && *new_name != name);
+                            let Some(new_name) = new_name else { return };
+
+                            let name = name.clone();
+                            let on_changed = on_changed.clone();
+                            let toast = toast.clone();
+                            wasm_bindgen_futures::spawn_local(async move {
+                                match rename_color_scheme(&name, &new_name).await {
+                                    Ok(renamed) => {
+                                        toast.success(format!("Renamed '{}' to '{}'", name, renamed.name));
+                                        on_changed.emit(());
+                                    }
+                                    Err(err) => toast.error(format!("Failed to rename '{}': {}", name, err)),
+                                }
+                            });
+                        })
+                    };
+                    let on_delete = {
+                        let name = scheme.name.clone();
+                        let on_changed = props.on_changed.clone();
+                        let toast = toast.clone();
+                        Callback::from(move |_: MouseEvent| {
+                            let confirmed = web_sys::window()
+                                .and_then(|w| w.confirm_with_message(&format!("Delete the color scheme '{}'?", name)).ok())
+                                .unwrap_or(false);
+                            if !confirmed {
+                                return;
+                            }
+
+                            let name = name.clone();
+                            let on_changed = on_changed.clone();
+                            let toast = toast.clone();
+                            wasm_bindgen_futures::spawn_local(async move {
+                                match delete_color_scheme(&name).await {
+                                    Ok(()) => {
+                                        toast.success(format!("Deleted '{}'", name));
+                                        on_changed.emit(());
+                                    }
+                                    Err(err) => toast.error(format!("Failed to delete '{}': {}", name, err)),
+                                }
+                            });
+                        })
+                    };
+
+                    html! {
+                        <li class="color-scheme-row">
+                            <span class="color-scheme-name">{&scheme.name}</span>
+                            {scheme.has_admin.then(|| html! { <span class="scheme-tag">{"Admin"}</span> })}
+                            {scheme.has_public.then(|| html! { <span class="scheme-tag">{"Public"}</span> })}
+                            {scheme.active.then(|| html! { <span class="scheme-tag active">{"Active"}</span> })}
+                            <button type="button" class="scheme-action" onclick={on_rename}>{"Rename"}</button>
+                            <button
+                                type="button"
+                                class="scheme-action danger"
+                                onclick={on_delete}
+                                disabled={scheme.active}
+                                title={if scheme.active { "Select another admin scheme before deleting this one" } else { "Delete this scheme" }}
+                            >
+                                {"Delete"}
+                            </button>
+                        </li>
+                    }
+                })}
+            </ul>
+        </div>
+    }
+}
+
+/// A text color and the background it's drawn on, checked for readability
+#[derive(Clone, PartialEq)]
+struct ContrastPair {
+    label: &'static str,
+    /// Scheme property holding the text color, used to apply a suggestion
+    property: &'static str,
+    foreground: String,
+    background: String,
+    /// Opaque surface under a translucent background
+    base: String,
+}
+
+impl ContrastPair {
+    fn new(label: &'static str, property: &'static str, foreground: &str, background: &str, base: &str) -> Self {
+        Self {
+            label,
+            property,
+            foreground: foreground.to_string(),
+            background: background.to_string(),
+            base: base.to_string(),
+        }
+    }
+}
+
+fn admin_contrast_pairs(scheme: &AdminColorScheme) -> Vec<ContrastPair> {
+    let s = scheme;
+    vec![
+        ContrastPair::new("Text on page background", "text_primary", &s.text_primary, &s.background, &s.background),
+        ContrastPair::new("Text on cards", "text_primary", &s.text_primary, &s.surface, &s.background),
+        ContrastPair::new("Secondary text on cards", "text_secondary", &s.text_secondary, &s.surface, &s.background),
+        ContrastPair::new("Muted text on cards", "text_muted", &s.text_muted, &s.surface, &s.background),
+        ContrastPair::new("Links on cards", "text_link", &s.text_link, &s.surface, &s.background),
+        ContrastPair::new("Header text", "header_text_color", &s.header_text_color, &s.header_gradient, &s.background),
+        ContrastPair::new("Sidebar links", "nav_link_text_color", &s.nav_link_text_color, &s.sidebar_bg, &s.background),
+        ContrastPair::new("Form labels", "form_label", &s.form_label, &s.surface, &s.background),
+        ContrastPair::new("Form placeholders", "form_placeholder", &s.form_placeholder, &s.form_bg, &s.surface),
+        ContrastPair::new("Primary buttons", "btn_primary_text", &s.btn_primary_text, &s.btn_primary_bg, &s.surface),
+        ContrastPair::new("Secondary buttons", "btn_secondary_text", &s.btn_secondary_text, &s.btn_secondary_bg, &s.surface),
+        ContrastPair::new("Danger buttons", "btn_danger_text", &s.btn_danger_text, &s.btn_danger_bg, &s.surface),
+        ContrastPair::new("Success buttons", "btn_success_text", &s.btn_success_text, &s.btn_success_bg, &s.surface),
+        ContrastPair::new("Table headers", "table_header_text", &s.table_header_text, &s.table_header_bg, &s.surface),
+        ContrastPair::new("Published badge", "status_published_text", &s.status_published_text, &s.status_published_bg, &s.surface),
+        ContrastPair::new("Draft badge", "status_draft_text", &s.status_draft_text, &s.status_draft_bg, &s.surface),
+        ContrastPair::new("Pending badge", "status_pending_text", &s.status_pending_text, &s.status_pending_bg, &s.surface),
+        ContrastPair::new("Approved badge", "status_approved_text", &s.status_approved_text, &s.status_approved_bg, &s.surface),
+        ContrastPair::new("Active badge", "status_active_text", &s.status_active_text, &s.status_active_bg, &s.surface),
+        ContrastPair::new("Inactive badge", "status_inactive_text", &s.status_inactive_text, &s.status_inactive_bg, &s.surface),
+    ]
+}
+
+fn public_contrast_pairs(scheme: &PublicColorScheme) -> Vec<ContrastPair> {
+    let s = scheme;
+    let page = &s.background_light;
+    vec![
+        ContrastPair::new("Primary text", "text_primary", &s.text_primary, page, page),
+        ContrastPair::new("Body text", "text_secondary", &s.text_secondary, page, page),
+        ContrastPair::new("Metadata", "text_meta", &s.text_meta, page, page),
+        ContrastPair::new("Captions", "text_light", &s.text_light, page, page),
+        ContrastPair::new("Links", "link_primary", &s.link_primary, page, page),
+        ContrastPair::new("Visited links", "link_visited", &s.link_visited, page, page),
+        ContrastPair::new("Heading 1", "heading_h1", &s.heading_h1, page, page),
+        ContrastPair::new("Heading 2", "heading_h2", &s.heading_h2, page, page),
+        ContrastPair::new("Heading 3", "heading_h3", &s.heading_h3, page, page),
+        ContrastPair::new("Heading 4", "heading_h4", &s.heading_h4, page, page),
+        ContrastPair::new("Heading 5", "heading_h5", &s.heading_h5, page, page),
+        ContrastPair::new("Heading 6", "heading_h6", &s.heading_h6, page, page),
+        ContrastPair::new("Success messages", "success", &s.success, page, page),
+        ContrastPair::new("Warning messages", "warning", &s.warning, page, page),
+        ContrastPair::new("Error messages", "danger", &s.danger, page, page),
+        ContrastPair::new("Info messages", "info", &s.info, page, page),
+        ContrastPair::new("Header text", "header_text", &s.header_text, &s.header_bg, page),
+        ContrastPair::new("Header hover text", "header_text_hover", &s.header_text_hover, &s.header_bg, page),
+        ContrastPair::new("Footer text", "footer_text", &s.footer_text, &s.footer_bg, page),
+        ContrastPair::new("Footer muted text", "footer_text_muted", &s.footer_text_muted, &s.footer_bg, page),
+    ]
+}
+
+#[derive(Properties, PartialEq)]
+struct ContrastReportProps {
+    pairs: Vec<ContrastPair>,
+    on_apply: Callback<(String, String)>,
+}
+
+/// WCAG AA contrast results for a scheme, failures first, each with a
+/// one-click fix that nudges the text color until it passes
+#[function_component(ContrastReport)]
+fn contrast_report(props: &ContrastReportProps) -> Html {
+    let mut results: Vec<_> = props.pairs.iter()
+        .map(|pair| (pair, check_contrast(&pair.foreground, &pair.background, &pair.base)))
+        .collect();
+    // Failing pairs first, then ones that couldn't be checked
+    results.sort_by_key(|(_, result)| match result {
+        Some(result) if result.ratio < AA_NORMAL_TEXT => 0,
+        None => 1,
+        Some(_) => 2,
+    });
+    let failing = results.iter().filter(|(_, result)| result.as_ref().is_some_and(|r| r.ratio < AA_NORMAL_TEXT)).count();
+
+    html! {
+        <div class="contrast-report">
+            <h4>{"Contrast Check"}</h4>
+            <p class={classes!("contrast-summary", (failing > 0).then_some("has-failures"))}>
+                {if failing == 0 {
+                    format!("All checked text meets WCAG AA ({}:1)", AA_NORMAL_TEXT)
+                } else {
+                    format!("{} of {} text colors fall below WCAG AA ({}:1)", failing, results.len(), AA_NORMAL_TEXT)
+                }}
+            </p>
+            <ul class="contrast-list">
+                {for results.iter().map(|(pair, result)| {
+                    let swatch_style = format!("color: {}; background: {};", pair.foreground, pair.background);
+                    let status = match result {
+                        None => html! { <span class="contrast-badge unknown">{"Not checked"}</span> },
+                        Some(result) if result.ratio >= AA_NORMAL_TEXT => html! {
+                            <span class="contrast-badge pass">{format!("{:.2}:1 AA", result.ratio)}</span>
+                        },
+                        Some(result) => {
+                            let fix = result.suggestion.clone().map(|suggested| {
+                                let on_apply = props.on_apply.clone();
+                                let property = pair.property.to_string();
+                                let value = suggested.clone();
+                                html! {
+                                    <button
+                                        type="button"
+                                        class="contrast-fix"
+                                        title={format!("Set {} to {}", pair.property, suggested)}
+                                        onclick={Callback::from(move |_| on_apply.emit((property.clone(), value.clone())))}
+                                    >
+                                        <span class="contrast-fix-swatch" style={format!("background: {};", suggested)}></span>
+                                        {format!("Use {}", suggested)}
+                                    </button>
+                                }
+                            });
+                            html! {
+                                <>
+                                    <span class="contrast-badge fail">{format!("{:.2}:1", result.ratio)}</span>
+                                    {fix.unwrap_or_default()}
+                                </>
+                            }
+                        }
+                    };
+                    html! {
+                        <li class="contrast-item">
+                            <span class="contrast-swatch" style={swatch_style}>{"Aa"}</span>
+                            <span class="contrast-label">{pair.label}</span>
+                            {status}
+                        </li>
+                    }
+                })}
+            </ul>
+        </div>
+    }
+}
+
 #[derive(Properties, PartialEq)]
 struct AdminPreviewProps {
     scheme: AdminColorScheme,
@@ -2231,6 +2424,7 @@ pub fn apply_admin_css_variables(scheme: &AdminColorScheme) {
             if let Some(head) = document.head() {
                 if let Ok(style_element) = document.create_element("style") {
                     style_element.set_id("admin-theme-overrides");
+                    tag_with_nonce(&style_element);
                     
                     // Comprehensive CSS variables for the entire admin design system
                     // Include container_* variables so template manager container settings flow into theme
@@ -2522,6 +2716,7 @@ pub fn apply_public_css_variables(scheme: &PublicColorScheme) {
             if let Some(head) = document.head() {
                 if let Ok(style_element) = document.create_element("style") {
                     style_element.set_id("public-theme-overrides");
+                    tag_with_nonce(&style_element);
                     // Only refresh CSS variables - let the CSS cascade handle the rest
                     let css_overrides = format!(r#"
                         /* Refresh public theme CSS variables only */
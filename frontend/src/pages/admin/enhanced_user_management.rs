@@ -1,9 +1,11 @@
 use yew::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{window, InputEvent};
-use crate::services::user_service::{get_users, create_user, promote_user, delete_user, CreateUserRequest};
-use crate::services::auth_service::{User, AuthError};
-use crate::components::simple_notification::SimpleNotification;
+use crate::services::user_service::{get_users, create_user, update_user, promote_user, delete_user, CreateUserRequest, UpdateUserRequest};
+use crate::services::api_service::ApiError;
+use crate::services::auth_service::User;
+use crate::components::{ConfirmDialog, RelativeTime};
+use crate::services::toast_context::use_toast;
 
 #[derive(Clone, PartialEq)]
 pub enum UserManagementView {
@@ -11,13 +13,6 @@ pub enum UserManagementView {
     Create,
 }
 
-#[derive(Clone, PartialEq)]
-pub enum NotificationType {
-    Success,
-    Error,
-    Info,
-}
-
 #[derive(Clone, PartialEq)]
 pub struct UserForm {
     pub username: String,
@@ -60,22 +55,18 @@ pub fn enhanced_user_management() -> Html {
     let current_view = use_state(|| initial_view);
     let user_form = use_state(UserForm::default);
     let form_loading = use_state(|| false);
-    let notification = use_state(|| None::<(String, NotificationType)>);
-
-    let clear_notification = {
-        let notification = notification.clone();
-        Callback::from(move |_| {
-            notification.set(None);
-        })
-    };
+    let toast = use_toast();
+    // User awaiting delete confirmation: (id, username)
+    let pending_delete = use_state(|| None::<(i32, String)>);
 
     // Load users
     {
         let users = users.clone();
         let loading = loading.clone();
-        let notification = notification.clone();
+        let toast = toast.clone();
 
-        use_effect(move || {
+        // Load once on mount; toasts re-render this component via the context
+        use_effect_with_deps(move |_| {
             wasm_bindgen_futures::spawn_local(async move {
                 match get_users().await {
                     Ok(fetched_users) => {
@@ -83,27 +74,27 @@ pub fn enhanced_user_management() -> Html {
                         loading.set(false);
                     }
                     Err(e) => {
-                        notification.set(Some((format!("Failed to load users: {}", e), NotificationType::Error)));
+                        toast.error(format!("Failed to load users: {}", e));
                         loading.set(false);
                     }
                 }
             });
-        });
+        }, ());
     }
 
     let reload_users = {
         let users = users.clone();
-        let notification = notification.clone();
+        let toast = toast.clone();
         Callback::from(move |_| {
             let users = users.clone();
-            let notification = notification.clone();
+            let toast = toast.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 match get_users().await {
                     Ok(fetched_users) => {
                         users.set(fetched_users);
                     }
                     Err(e) => {
-                        notification.set(Some((format!("Failed to reload users: {}", e), NotificationType::Error)));
+                        toast.error(format!("Failed to reload users: {}", e));
                     }
                 }
             });
@@ -127,23 +118,65 @@ pub fn enhanced_user_management() -> Html {
     };
 
     let on_promote_user = {
-        let notification = notification.clone();
+        let toast = toast.clone();
         let reload_users = reload_users.clone();
         Callback::from(move |(user_id, new_role): (i32, String)| {
-            let notification = notification.clone();
+            let toast = toast.clone();
             let reload_users = reload_users.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 match promote_user(user_id, &new_role).await {
                     Ok(_) => {
                         let action = if new_role == "editor" { "promoted to" } else { "demoted to" };
-                        notification.set(Some((format!("User {} {} role", action, new_role), NotificationType::Success)));
+                        toast.success(format!("User {} {} role", action, new_role));
+                        reload_users.emit(());
+                    }
+                    Err(ApiError::NotFound(_)) => {
+                        toast.error("That user no longer exists");
                         reload_users.emit(());
                     }
-                    Err(AuthError::ServerError(msg)) => {
-                        notification.set(Some((msg, NotificationType::Error)));
+                    Err(ApiError::Forbidden(message)) | Err(ApiError::Validation { message, .. }) => {
+                        toast.error(message);
                     }
+                    // The session has expired and handle_unauthorized is already redirecting
+                    Err(ApiError::Unauthorized(_)) => {}
                     Err(e) => {
-                        notification.set(Some((format!("Failed to change user role: {}", e), NotificationType::Error)));
+                        toast.error(format!("Failed to change user role: {}", e));
+                    }
+                }
+            });
+        })
+    };
+
+    let on_require_password_change = {
+        let toast = toast.clone();
+        let reload_users = reload_users.clone();
+        Callback::from(move |(user_id, username): (i32, String)| {
+            let toast = toast.clone();
+            let reload_users = reload_users.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let update = UpdateUserRequest {
+                    username: None,
+                    email: None,
+                    password: None,
+                    role: None,
+                    status: None,
+                    must_change_password: Some(true),
+                };
+                match update_user(user_id, &update).await {
+                    Ok(_) => {
+                        toast.success(format!("{} will have to change their password at next sign-in", username));
+                        reload_users.emit(());
+                    }
+                    Err(ApiError::NotFound(_)) => {
+                        toast.error("That user no longer exists");
+                        reload_users.emit(());
+                    }
+                    Err(ApiError::Forbidden(message)) | Err(ApiError::Validation { message, .. }) => {
+                        toast.error(message);
+                    }
+                    Err(ApiError::Unauthorized(_)) => {}
+                    Err(e) => {
+                        toast.error(format!("Failed to require a password change: {}", e));
                     }
                 }
             });
@@ -151,40 +184,61 @@ pub fn enhanced_user_management() -> Html {
     };
 
     let on_delete_user = {
-        let notification = notification.clone();
+        let toast = toast.clone();
         let reload_users = reload_users.clone();
         Callback::from(move |user_id: i32| {
-            let notification = notification.clone();
+            let toast = toast.clone();
             let reload_users = reload_users.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 match delete_user(user_id).await {
                     Ok(_) => {
-                        notification.set(Some(("User deleted successfully".to_string(), NotificationType::Success)));
+                        toast.success("User deleted successfully");
+                        reload_users.emit(());
+                    }
+                    Err(ApiError::NotFound(_)) => {
+                        toast.error("That user no longer exists");
                         reload_users.emit(());
                     }
-                    Err(AuthError::ServerError(msg)) => {
-                        notification.set(Some((msg, NotificationType::Error)));
+                    Err(ApiError::Forbidden(message)) | Err(ApiError::Validation { message, .. }) => {
+                        toast.error(message);
                     }
+                    Err(ApiError::Unauthorized(_)) => {}
                     Err(e) => {
-                        notification.set(Some((format!("Failed to delete user: {}", e), NotificationType::Error)));
+                        toast.error(format!("Failed to delete user: {}", e));
                     }
                 }
             });
         })
     };
 
+    let on_confirm_delete = {
+        let pending_delete = pending_delete.clone();
+        let on_delete_user = on_delete_user.clone();
+        Callback::from(move |_| {
+            if let Some((user_id, _)) = (*pending_delete).clone() {
+                pending_delete.set(None);
+                on_delete_user.emit(user_id);
+            }
+        })
+    };
+
+    let on_cancel_delete = {
+        let pending_delete = pending_delete.clone();
+        Callback::from(move |_| pending_delete.set(None))
+    };
+
     let on_save_user = {
         let current_view = current_view.clone();
         let user_form = user_form.clone();
         let form_loading = form_loading.clone();
-        let notification = notification.clone();
+        let toast = toast.clone();
         let reload_users = reload_users.clone();
 
         Callback::from(move |_| {
             let current_view = current_view.clone();
             let user_form = (*user_form).clone();
             let form_loading = form_loading.clone();
-            let notification = notification.clone();
+            let toast = toast.clone();
             let reload_users = reload_users.clone();
 
             form_loading.set(true);
@@ -199,7 +253,7 @@ pub fn enhanced_user_management() -> Html {
 
                 match create_user(&create_request).await {
                     Ok(_) => {
-                        notification.set(Some(("User created successfully".to_string(), NotificationType::Success)));
+                        toast.success("User created successfully");
                         current_view.set(UserManagementView::List);
                         reload_users.emit(());
                         
@@ -210,11 +264,16 @@ pub fn enhanced_user_management() -> Html {
                             }
                         }
                     }
-                    Err(AuthError::ServerError(msg)) => {
-                        notification.set(Some((msg, NotificationType::Error)));
+                    // Name the rejected field, e.g. a taken username or a short password
+                    Err(ApiError::Validation { field: Some(field), message }) => {
+                        toast.error(format!("{}: {}", field, message));
                     }
+                    Err(ApiError::Validation { field: None, message }) | Err(ApiError::Forbidden(message)) => {
+                        toast.error(message);
+                    }
+                    Err(ApiError::Unauthorized(_)) => {}
                     Err(e) => {
-                        notification.set(Some((format!("Failed to create user: {}", e), NotificationType::Error)));
+                        toast.error(format!("Failed to create user: {}", e));
                     }
                 }
                 form_loading.set(false);
@@ -303,23 +362,15 @@ pub fn enhanced_user_management() -> Html {
                             </div>
                         </div>
 
-                        {
-                            if let Some((message, notification_type)) = (*notification).clone() {
-                                let class = match notification_type {
-                                    NotificationType::Success => "notification-success",
-                                    NotificationType::Error => "notification-error",
-                                    NotificationType::Info => "notification-info",
-                                };
-                                html! {
-                                    <SimpleNotification 
-                                        message={message} 
-                                        notification_type={class} 
-                                        on_close={clear_notification.clone()}
-                                    />
-                                }
-                            } else {
-                                html! {}
-                            }
+
+                        if let Some((_, ref username)) = *pending_delete {
+                            <ConfirmDialog
+                                title="Delete user?"
+                                message={format!("{} will be permanently deleted. This cannot be undone.", username)}
+                                confirm_label="Delete User"
+                                on_confirm={on_confirm_delete.clone()}
+                                on_cancel={on_cancel_delete.clone()}
+                            />
                         }
 
                         <div class="admin-table-container">
@@ -331,6 +382,7 @@ pub fn enhanced_user_management() -> Html {
                                         <th>{"Role"}</th>
                                         <th>{"Status"}</th>
                                         <th>{"Email Verified"}</th>
+                                        <th>{"Last Login"}</th>
                                         <th>{"Actions"}</th>
                                     </tr>
                                 </thead>
@@ -348,13 +400,16 @@ pub fn enhanced_user_management() -> Html {
                                             Callback::from(move |_| on_promote_user.emit((user_id, "user".to_string())))
                                         };
 
+                                        let on_require_change = {
+                                            let on_require_password_change = on_require_password_change.clone();
+                                            let username = user.username.clone();
+                                            Callback::from(move |_| on_require_password_change.emit((user_id, username.clone())))
+                                        };
+
                                         let on_delete = {
-                                            let on_delete_user = on_delete_user.clone();
-                                            Callback::from(move |_| {
-                                                if web_sys::window().unwrap().confirm_with_message("Are you sure you want to delete this user?").unwrap() {
-                                                    on_delete_user.emit(user_id);
-                                                }
-                                            })
+                                            let pending_delete = pending_delete.clone();
+                                            let username = user.username.clone();
+                                            Callback::from(move |_| pending_delete.set(Some((user_id, username.clone()))))
                                         };
 
                                         let status_class = match user.status.as_str() {
@@ -381,6 +436,12 @@ pub fn enhanced_user_management() -> Html {
                                                         {if email_verified { "✓ Verified" } else { "✗ Unverified" }}
                                                     </span>
                                                 </td>
+                                                <td>
+                                                    {match user.last_login_at.clone() {
+                                                        Some(last_login_at) => html! { <RelativeTime value={last_login_at} /> },
+                                                        None => html! { {"Never"} },
+                                                    }}
+                                                </td>
                                                 <td class="actions">
                                                     {
                                                         if user.role == "user" {
@@ -409,6 +470,23 @@ pub fn enhanced_user_management() -> Html {
                                                             }
                                                         }
                                                     }
+                                                    {
+                                                        if user.must_change_password {
+                                                            html! {
+                                                                <span class="admin-label">{"Password change pending"}</span>
+                                                            }
+                                                        } else {
+                                                            html! {
+                                                                <button 
+                                                                    class="btn btn-small btn-secondary" 
+                                                                    onclick={on_require_change}
+                                                                    title="Make this user choose a new password at next sign-in"
+                                                                >
+                                                                    {"Require Password Change"}
+                                                                </button>
+                                                            }
+                                                        }
+                                                    }
                                                     {
                                                         if user.role != "admin" {
                                                             html! {
@@ -454,24 +532,6 @@ pub fn enhanced_user_management() -> Html {
                         <p>{"Create a new user account. They will be automatically verified."}</p>
                     </div>
 
-                    {
-                        if let Some((message, notification_type)) = (*notification).clone() {
-                            let class = match notification_type {
-                                NotificationType::Success => "notification-success",
-                                NotificationType::Error => "notification-error",
-                                NotificationType::Info => "notification-info",
-                            };
-                            html! {
-                                <SimpleNotification 
-                                    message={message} 
-                                    notification_type={class} 
-                                    on_close={clear_notification.clone()}
-                                />
-                            }
-                        } else {
-                            html! {}
-                        }
-                    }
 
                     <div class="user-form">
                         <div class="form-group">
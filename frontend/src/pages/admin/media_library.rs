@@ -1,9 +1,9 @@
+use std::collections::HashSet;
 use yew::prelude::*;
-use crate::services::api_service::{get_media, delete_media, MediaItem};
-use web_sys::{File, HtmlInputElement, DragEvent, FileList, InputEvent, MouseEvent};
+use crate::services::api_service::{get_media, upload_media, delete_media, bulk_delete_media, cleanup_media, MediaItem, MediaCleanupSummary};
+use web_sys::{HtmlInputElement, DragEvent, FileList, InputEvent, MouseEvent};
 use wasm_bindgen::JsCast;
-use gloo_net::http::Request;
-use crate::services::auth_service::get_auth_token;
+use crate::components::ConfirmDialog;
 
 #[derive(Clone, PartialEq)]
 enum ViewMode {
@@ -43,48 +43,11 @@ impl MediaFilter {
     }
 }
 
-async fn upload_file(file: &File) -> Result<MediaItem, String> {
-    use web_sys::console;
-    
-    console::log_1(&format!("🚀 Uploading: {} ({} bytes)", file.name(), file.size()).into());
-    
-    let form_data = web_sys::FormData::new().unwrap();
-    form_data.append_with_blob("file", &file).unwrap();
-    
-    // Attach Authorization header (admin routes require auth)
-    let token = get_auth_token().map_err(|_| "Not authenticated".to_string())?;
-    let response = Request::post("http://localhost:8081/api/media/upload")
-        .header("Authorization", &format!("Bearer {}", token))
-        .body(form_data)
-        .map_err(|e| format!("Failed to create request: {}", e))?
-        .send()
-        .await
-        .map_err(|e| format!("Network error: {}", e))?;
-    
-    console::log_1(&format!("📡 Response status: {}", response.status()).into());
-    
-    if response.status() == 201 {
-        let result: serde_json::Value = response.json().await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
-        
-        if result["success"].as_bool().unwrap_or(false) {
-            let media_data = &result["media"];
-            Ok(MediaItem {
-                id: media_data["id"].as_i64().map(|id| id as i32),
-                name: media_data["name"].as_str().unwrap_or("").to_string(),
-                type_: media_data["type_"].as_str().unwrap_or("").to_string(),
-                size: Some(media_data["size"].as_str().unwrap_or("").to_string()),
-                url: media_data["url"].as_str().unwrap_or("").to_string(),
-                created_at: media_data["created_at"].as_str().map(|s| s.to_string()),
-                user_id: None,
-            })
-        } else {
-            Err(result["message"].as_str().unwrap_or("Upload failed").to_string())
-        }
-    } else {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        Err(format!("HTTP {} - {}", response.status(), error_text))
-    }
+/// Destructive actions waiting on the confirmation dialog
+#[derive(Clone, Copy, PartialEq)]
+enum PendingAction {
+    BulkDelete,
+    Cleanup,
 }
 
 #[function_component(MediaLibrary)]
@@ -102,6 +65,10 @@ pub fn media_library() -> Html {
     let show_lightbox = use_state(|| false);
     let lightbox_image_url = use_state(String::new);
     let lightbox_image_name = use_state(String::new);
+    let selected = use_state(HashSet::<i32>::new);
+    let cleanup_report = use_state(|| None::<MediaCleanupSummary>);
+    let cleanup_running = use_state(|| false);
+    let pending_action = use_state(|| None::<PendingAction>);
 
     // Load media on mount
     {
@@ -182,7 +149,7 @@ pub fn media_library() -> Html {
                 
                 for i in 0..files.length() {
                     if let Some(file) = files.get(i) {
-                        match upload_file(&file).await {
+                        match upload_media(&file).await {
                             Ok(new_media) => {
                                 successful_uploads.push(new_media);
                                 
@@ -279,6 +246,94 @@ pub fn media_library() -> Html {
         })
     };
 
+    let on_toggle_select = {
+        let selected = selected.clone();
+        Callback::from(move |media_id: i32| {
+            let mut current = (*selected).clone();
+            if !current.remove(&media_id) {
+                current.insert(media_id);
+            }
+            selected.set(current);
+        })
+    };
+
+    let on_clear_selection = {
+        let selected = selected.clone();
+        Callback::from(move |_: MouseEvent| selected.set(HashSet::new()))
+    };
+
+    let on_bulk_delete = {
+        let media_items = media_items.clone();
+        let selected = selected.clone();
+        let error = error.clone();
+        Callback::from(move |_: ()| {
+            let ids: Vec<i32> = selected.iter().copied().collect();
+            if ids.is_empty() {
+                return;
+            }
+
+            let media_items = media_items.clone();
+            let selected = selected.clone();
+            let error = error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match bulk_delete_media(&ids).await {
+                    Ok(summary) => {
+                        let mut current_media = (*media_items).clone();
+                        current_media.retain(|item| !item.id.map(|id| ids.contains(&id)).unwrap_or(false));
+                        media_items.set(current_media);
+                        selected.set(HashSet::new());
+                        if !summary.file_errors.is_empty() {
+                            error.set(Some(format!("Deleted {} items, but some files could not be removed: {}", summary.deleted, summary.file_errors.join("; "))));
+                        }
+                    }
+                    Err(e) => {
+                        error.set(Some(format!("Failed to delete selected media: {}", e)));
+                    }
+                }
+            });
+        })
+    };
+
+    let request_confirmation = {
+        let pending_action = pending_action.clone();
+        move |action: PendingAction| {
+            let pending_action = pending_action.clone();
+            Callback::from(move |_: MouseEvent| pending_action.set(Some(action)))
+        }
+    };
+
+    // Storage cleanup: scan first (dry run), then apply after confirmation
+    let run_cleanup = {
+        let media_items = media_items.clone();
+        let cleanup_report = cleanup_report.clone();
+        let cleanup_running = cleanup_running.clone();
+        let error = error.clone();
+        Callback::from(move |dry_run: bool| {
+            let media_items = media_items.clone();
+            let cleanup_report = cleanup_report.clone();
+            let cleanup_running = cleanup_running.clone();
+            let error = error.clone();
+            cleanup_running.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                match cleanup_media(dry_run).await {
+                    Ok(summary) => {
+                        if !summary.dry_run {
+                            let removed: HashSet<i32> = summary.missing_files.iter().map(|m| m.id).collect();
+                            let mut current_media = (*media_items).clone();
+                            current_media.retain(|item| !item.id.map(|id| removed.contains(&id)).unwrap_or(false));
+                            media_items.set(current_media);
+                        }
+                        cleanup_report.set(Some(summary));
+                    }
+                    Err(e) => {
+                        error.set(Some(format!("Media cleanup failed: {}", e)));
+                    }
+                }
+                cleanup_running.set(false);
+            });
+        })
+    };
+
     // Lightbox handlers
     let open_lightbox = {
         let show_lightbox = show_lightbox.clone();
@@ -430,6 +485,20 @@ pub fn media_library() -> Html {
                     />
                 </div>
                 <div class="controls-right">
+                    if !selected.is_empty() {
+                        <div class="bulk-actions">
+                            <span class="selection-count">{format!("{} selected", selected.len())}</span>
+                            <button class="btn btn-secondary" onclick={on_clear_selection}>{"Clear"}</button>
+                            <button class="btn btn-danger" onclick={request_confirmation(PendingAction::BulkDelete)}>{"Delete Selected"}</button>
+                        </div>
+                    }
+                    <button
+                        class="btn btn-secondary"
+                        disabled={*cleanup_running}
+                        onclick={let run_cleanup = run_cleanup.clone(); Callback::from(move |_| run_cleanup.emit(true))}
+                    >
+                        {if *cleanup_running { "Scanning..." } else { "Scan Storage" }}
+                    </button>
                     <div class="view-toggle">
                         <button 
                             class={classes!("view-btn", if matches!(*view_mode, ViewMode::Grid) { Some("active") } else { None })}
@@ -447,6 +516,54 @@ pub fn media_library() -> Html {
                 </div>
             </div>
 
+            // Storage cleanup report
+            if let Some(report) = (*cleanup_report).clone() {
+                <div class="media-cleanup-report">
+                    if report.dry_run {
+                        <p>
+                            {format!(
+                                "Found {} orphaned file(s) ({:.1} KB) and {} media entr{} with missing files.",
+                                report.orphaned_files.len(),
+                                report.orphaned_bytes as f64 / 1024.0,
+                                report.missing_files.len(),
+                                if report.missing_files.len() == 1 { "y" } else { "ies" },
+                            )}
+                        </p>
+                        if !report.orphaned_files.is_empty() {
+                            <details>
+                                <summary>{"Orphaned files"}</summary>
+                                <ul>{ for report.orphaned_files.iter().map(|name| html! { <li>{name}</li> }) }</ul>
+                            </details>
+                        }
+                        if !report.missing_files.is_empty() {
+                            <details>
+                                <summary>{"Entries with missing files"}</summary>
+                                <ul>{ for report.missing_files.iter().map(|m| html! { <li>{format!("{} ({})", m.file_name, m.url)}</li> }) }</ul>
+                            </details>
+                        }
+                    } else {
+                        <p>{format!("Removed {} orphaned file(s) and {} media entr{} with missing files.", report.files_removed, report.rows_removed, if report.rows_removed == 1 { "y" } else { "ies" })}</p>
+                    }
+                    if !report.errors.is_empty() {
+                        <p class="error-text">{report.errors.join("; ")}</p>
+                    }
+                    <div class="cleanup-actions">
+                        if report.dry_run && (!report.orphaned_files.is_empty() || !report.missing_files.is_empty()) {
+                            <button
+                                class="btn btn-danger"
+                                disabled={*cleanup_running}
+                                onclick={request_confirmation(PendingAction::Cleanup)}
+                            >
+                                {"Clean Up"}
+                            </button>
+                        }
+                        <button class="btn btn-secondary" onclick={let cleanup_report = cleanup_report.clone(); Callback::from(move |_| cleanup_report.set(None))}>
+                            {"Dismiss"}
+                        </button>
+                    </div>
+                </div>
+            }
+
             // Content Area
             <div class="media-content">
                 {if *loading {
@@ -491,12 +608,26 @@ pub fn media_library() -> Html {
                                     })
                                 };
 
+                                let is_selected = item.id.map(|id| selected.contains(&id)).unwrap_or(false);
+                                let on_select = {
+                                    let on_toggle_select = on_toggle_select.clone();
+                                    let item_id = item.id.unwrap_or(0);
+                                    Callback::from(move |_: Event| on_toggle_select.emit(item_id))
+                                };
+
                                 let (media_icon, media_class) = get_media_icon_and_class(&item.type_);
                                 let file_extension = item.name.split('.').last().unwrap_or("").to_uppercase();
 
                                 html! {
-                                    <div class={classes!("media-card", Some(media_class))}>
+                                    <div class={classes!("media-card", Some(media_class), is_selected.then_some("selected"))}>
                                         <div class="media-preview">
+                                            <input
+                                                type="checkbox"
+                                                class="media-select"
+                                                title="Select"
+                                                checked={is_selected}
+                                                onchange={on_select}
+                                            />
                                             {if item.type_.starts_with("image") && !item.url.is_empty() {
                                                 html! { <img src={format!("http://localhost:8081{}", item.url)} alt={item.name.clone()} /> }
                                             } else {
@@ -536,6 +667,39 @@ pub fn media_library() -> Html {
                 }}
             </div>
 
+            if let Some(action) = *pending_action {
+                <ConfirmDialog
+                    title={match action {
+                        PendingAction::BulkDelete => "Delete selected media?".to_string(),
+                        PendingAction::Cleanup => "Clean up media storage?".to_string(),
+                    }}
+                    message={match action {
+                        PendingAction::BulkDelete => format!("{} selected file(s) will be permanently deleted. This cannot be undone.", selected.len()),
+                        PendingAction::Cleanup => "Orphaned files and media entries whose files are missing will be removed. This cannot be undone.".to_string(),
+                    }}
+                    confirm_label={match action {
+                        PendingAction::BulkDelete => "Delete Files".to_string(),
+                        PendingAction::Cleanup => "Clean Up".to_string(),
+                    }}
+                    on_confirm={{
+                        let pending_action = pending_action.clone();
+                        let on_bulk_delete = on_bulk_delete.clone();
+                        let run_cleanup = run_cleanup.clone();
+                        Callback::from(move |_| {
+                            pending_action.set(None);
+                            match action {
+                                PendingAction::BulkDelete => on_bulk_delete.emit(()),
+                                PendingAction::Cleanup => run_cleanup.emit(false),
+                            }
+                        })
+                    }}
+                    on_cancel={{
+                        let pending_action = pending_action.clone();
+                        Callback::from(move |_| pending_action.set(None))
+                    }}
+                />
+            }
+
             // Lightbox Modal
             {if *show_lightbox {
                 html! {
@@ -2,6 +2,7 @@ pub mod admin;
 pub mod dashboard;
 pub mod post_list;
 pub mod post_editor;
+pub mod post_calendar;
 pub mod user_management;
 pub mod enhanced_user_management;
 pub mod comment_moderation;
@@ -12,6 +13,8 @@ pub mod navigation_manager;
 pub mod template_manager;
 pub mod analytics;
 pub mod design_system;
+pub mod session_management;
+pub mod profile;
 
 // Keeping all admin page exports available for future use
 #[allow(unused_imports)]
@@ -37,4 +40,6 @@ pub use navigation_manager::NavigationManager;
 #[allow(unused_imports)]
 pub use analytics::Analytics;
 #[allow(unused_imports)]
-pub use design_system::DesignSystemPage; 
\ No newline at end of file
+pub use design_system::DesignSystemPage;
+#[allow(unused_imports)]
+pub use session_management::SessionManagement; 
\ No newline at end of file
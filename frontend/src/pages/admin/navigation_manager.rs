@@ -1,8 +1,40 @@
 use yew::prelude::*;
 use wasm_bindgen::JsCast;
-use crate::services::navigation_service::{NavigationItem, get_navigation_by_area, get_navigation_items, create_navigation_item, update_navigation_item, delete_navigation_item};
+use crate::services::navigation_service::{NavigationItem, get_admin_navigation_by_area, get_navigation_items, create_navigation_item, update_navigation_item, delete_navigation_item};
 use crate::services::page_service::get_pages;
 use crate::services::api_service::get_posts;
+use crate::services::local_time::{local_input_to_utc, utc_to_local_input};
+
+/// Short description of an item's schedule and whether it is currently shown
+fn schedule_label(item: &NavigationItem) -> Option<String> {
+    if item.visible_from.is_none() && item.visible_until.is_none() {
+        return None;
+    }
+    let parse = |value: &Option<String>| value.as_ref()
+        .map(|v| js_sys::Date::new(&wasm_bindgen::JsValue::from_str(v)).get_time());
+    let now = js_sys::Date::now();
+    let state = match (parse(&item.visible_from), parse(&item.visible_until)) {
+        (_, Some(until)) if now >= until => "Expired",
+        (Some(from), _) if now < from => "Scheduled",
+        _ => "Live",
+    };
+    let local = |value: &Option<String>| utc_to_local_input(value).replace('T', " ");
+    Some(match (&item.visible_from, &item.visible_until) {
+        (Some(_), Some(_)) => format!("{}: {} – {}", state, local(&item.visible_from), local(&item.visible_until)),
+        (Some(_), None) => format!("{}: from {}", state, local(&item.visible_from)),
+        _ => format!("{}: until {}", state, local(&item.visible_until)),
+    })
+}
+
+/// Values submitted from the inline edit form
+#[derive(Clone, PartialEq)]
+pub struct NavigationItemEdit {
+    pub id: i32,
+    pub title: String,
+    pub url: String,
+    pub visible_from: Option<String>,
+    pub visible_until: Option<String>,
+}
 
 #[function_component(NavigationManager)]
 pub fn navigation_manager() -> Html {
@@ -16,6 +48,8 @@ pub fn navigation_manager() -> Html {
     let new_item_url = use_state(String::new);
     let new_item_type = use_state(|| "custom".to_string());
     let new_item_target = use_state(String::new);
+    let new_item_visible_from = use_state(String::new);
+    let new_item_visible_until = use_state(String::new);
     let editing_item = use_state(|| None::<i32>);
     
     // Custom menu creation states
@@ -40,7 +74,7 @@ pub fn navigation_manager() -> Html {
             let area = menu_area.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 loading.set(true);
-                match get_navigation_by_area(&area).await {
+                match get_admin_navigation_by_area(&area).await {
                     Ok(items) => {
                         navigation_items.set(items);
                         loading.set(false);
@@ -228,6 +262,8 @@ pub fn navigation_manager() -> Html {
         let new_item_url = new_item_url.clone();
         let new_item_type = new_item_type.clone();
         let new_item_target = new_item_target.clone();
+        let new_item_visible_from = new_item_visible_from.clone();
+        let new_item_visible_until = new_item_visible_until.clone();
         let current_menu_area = current_menu_area.clone();
         let error = error.clone();
         
@@ -254,6 +290,8 @@ pub fn navigation_manager() -> Html {
                     target: Some("_self".to_string()),
                     mobile_visible: true,
                     description: None,
+                    visible_from: local_input_to_utc(&new_item_visible_from),
+                    visible_until: local_input_to_utc(&new_item_visible_until),
                     children: None,
                 };
                 
@@ -263,6 +301,8 @@ pub fn navigation_manager() -> Html {
                 let new_item_url_clone = new_item_url.clone();
                 let new_item_type_clone = new_item_type.clone();
                 let new_item_target_clone = new_item_target.clone();
+                let new_item_visible_from_clone = new_item_visible_from.clone();
+                let new_item_visible_until_clone = new_item_visible_until.clone();
                 
                 wasm_bindgen_futures::spawn_local(async move {
                     match create_navigation_item(&new_item).await {
@@ -275,6 +315,8 @@ pub fn navigation_manager() -> Html {
                             new_item_url_clone.set(String::new());
                             new_item_type_clone.set("custom".to_string());
                             new_item_target_clone.set(String::new());
+                            new_item_visible_from_clone.set(String::new());
+                            new_item_visible_until_clone.set(String::new());
                         }
                         Err(e) => {
                             error_clone.set(Some(format!("Failed to create navigation item: {:?}", e)));
@@ -318,7 +360,8 @@ pub fn navigation_manager() -> Html {
         let navigation_items = navigation_items.clone();
         let editing_item = editing_item.clone();
         let error = error.clone();
-        Callback::from(move |(id, title, url): (i32, String, String)| {
+        Callback::from(move |edit: NavigationItemEdit| {
+            let NavigationItemEdit { id, title, url, visible_from, visible_until } = edit;
             let navigation_items_clone = navigation_items.clone();
             let editing_item_clone = editing_item.clone();
             let error_clone = error.clone();
@@ -341,6 +384,8 @@ pub fn navigation_manager() -> Html {
                     target: Some("_self".to_string()),
                     mobile_visible: true,
                     description: None,
+                    visible_from: visible_from.clone(),
+                    visible_until: visible_until.clone(),
                     children: None,
                 };
                 
@@ -350,6 +395,8 @@ pub fn navigation_manager() -> Html {
                         if let Some(item) = items.iter_mut().find(|item| item.id == id) {
                             item.title = title;
                             item.url = url;
+                            item.visible_from = visible_from;
+                            item.visible_until = visible_until;
                             navigation_items_clone.set(items);
                         }
                         editing_item_clone.set(None);
@@ -401,6 +448,22 @@ pub fn navigation_manager() -> Html {
         })
     };
 
+    let on_visible_from_input = {
+        let new_item_visible_from = new_item_visible_from.clone();
+        Callback::from(move |e: InputEvent| {
+            let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+            new_item_visible_from.set(target.value());
+        })
+    };
+
+    let on_visible_until_input = {
+        let new_item_visible_until = new_item_visible_until.clone();
+        Callback::from(move |e: InputEvent| {
+            let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+            new_item_visible_until.set(target.value());
+        })
+    };
+
     let on_menu_area_change = {
         let current_menu_area = current_menu_area.clone();
         Callback::from(move |e: Event| {
@@ -628,6 +691,24 @@ pub fn navigation_manager() -> Html {
                                 }
                             </div>
                         }
+                        <div class="form-group">
+                            <label for="new-visible-from">{"Show From (optional)"}</label>
+                            <input 
+                                id="new-visible-from"
+                                type="datetime-local" 
+                                value={(*new_item_visible_from).clone()}
+                                oninput={on_visible_from_input}
+                            />
+                        </div>
+                        <div class="form-group">
+                            <label for="new-visible-until">{"Show Until (optional)"}</label>
+                            <input 
+                                id="new-visible-until"
+                                type="datetime-local" 
+                                value={(*new_item_visible_until).clone()}
+                                oninput={on_visible_until_input}
+                            />
+                        </div>
                         <div class="form-group">
                             <button 
                                 class="btn btn-primary" 
@@ -659,6 +740,8 @@ pub fn navigation_manager() -> Html {
                                             id={item.id}
                                             title={item.title.clone()}
                                             url={item.url.clone()}
+                                            visible_from={item.visible_from.clone()}
+                                            visible_until={item.visible_until.clone()}
                                             on_save={save_edit.clone()}
                                             on_cancel={cancel_edit.clone()}
                                         />
@@ -669,6 +752,9 @@ pub fn navigation_manager() -> Html {
                                             <div class="item-info">
                                                 <h4>{&item.title}</h4>
                                                 <p class="item-url">{&item.url}</p>
+                                                if let Some(schedule) = schedule_label(item) {
+                                                    <p class="item-schedule">{schedule}</p>
+                                                }
                                                 <p class="item-menu-area">
                                                     {"Menu: "} 
                                                     <span class="menu-area-badge">
@@ -720,7 +806,9 @@ pub struct NavigationItemFormProps {
     pub id: i32,
     pub title: String,
     pub url: String,
-    pub on_save: Callback<(i32, String, String)>,
+    pub visible_from: Option<String>,
+    pub visible_until: Option<String>,
+    pub on_save: Callback<NavigationItemEdit>,
     pub on_cancel: Callback<()>,
 }
 
@@ -728,6 +816,8 @@ pub struct NavigationItemFormProps {
 pub fn navigation_item_form(props: &NavigationItemFormProps) -> Html {
     let title = use_state(|| props.title.clone());
     let url = use_state(|| props.url.clone());
+    let visible_from = use_state(|| utc_to_local_input(&props.visible_from));
+    let visible_until = use_state(|| utc_to_local_input(&props.visible_until));
     let id = props.id; // Clone the id to avoid lifetime issues
 
     let on_title_input = {
@@ -746,12 +836,36 @@ pub fn navigation_item_form(props: &NavigationItemFormProps) -> Html {
         })
     };
 
+    let on_visible_from_input = {
+        let visible_from = visible_from.clone();
+        Callback::from(move |e: InputEvent| {
+            let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+            visible_from.set(target.value());
+        })
+    };
+
+    let on_visible_until_input = {
+        let visible_until = visible_until.clone();
+        Callback::from(move |e: InputEvent| {
+            let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+            visible_until.set(target.value());
+        })
+    };
+
     let on_save = {
         let on_save = props.on_save.clone();
         let title = title.clone();
         let url = url.clone();
+        let visible_from = visible_from.clone();
+        let visible_until = visible_until.clone();
         Callback::from(move |_| {
-            on_save.emit((id, (*title).clone(), (*url).clone()));
+            on_save.emit(NavigationItemEdit {
+                id,
+                title: (*title).clone(),
+                url: (*url).clone(),
+                visible_from: local_input_to_utc(&visible_from),
+                visible_until: local_input_to_utc(&visible_until),
+            });
         })
     };
 
@@ -779,6 +893,22 @@ pub fn navigation_item_form(props: &NavigationItemFormProps) -> Html {
                         oninput={on_url_input}
                     />
                 </div>
+                <div class="form-group">
+                    <input 
+                        type="datetime-local" 
+                        title="Show from"
+                        value={(*visible_from).clone()}
+                        oninput={on_visible_from_input}
+                    />
+                </div>
+                <div class="form-group">
+                    <input 
+                        type="datetime-local" 
+                        title="Show until"
+                        value={(*visible_until).clone()}
+                        oninput={on_visible_until_input}
+                    />
+                </div>
                 <div class="form-group">
                     <button class="btn btn-small btn-primary" onclick={on_save}>
                         {"Save"}
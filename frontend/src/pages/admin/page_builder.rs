@@ -1,11 +1,15 @@
 use yew::prelude::*;
 use crate::components::page_builder::{DragDropPageBuilder, PageComponent};
-use crate::services::api_service::{get_pages, create_page, update_page, delete_page, PageItem};
+use crate::services::api_service::{get_pages_page, create_page, update_page, delete_page, PageItem, PagedPages};
+use crate::services::page_service::get_page_by_slug;
+use crate::services::preview_service::{open_public_view, PublicContent};
 use crate::pages::public::{PublicPage, render_component_content_public_with_navigation};
 use crate::services::migrate_pages::create_essential_pages;
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlInputElement, HtmlSelectElement, Event, MouseEvent};
 
+const PAGES_PER_PAGE: i64 = 20;
+
 #[function_component(PageBuilder)]
 pub fn page_builder() -> Html {
     let current_page = use_state(|| None::<PageItem>);
@@ -14,32 +18,43 @@ pub fn page_builder() -> Html {
     let page_components = use_state(Vec::new);
     
     let pages = use_state(Vec::new);
+    let list_page = use_state(|| 1i64);
+    let list_info = use_state(|| None::<PagedPages>);
+    let essentials_missing = use_state(|| false);
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
     let saving = use_state(|| false);
     let show_preview = use_state(|| false);
     
-    // Check if essential pages exist
-    let show_init_button = use_memo(
-        |pages| {
-            let has_home = pages.iter().any(|p: &PageItem| p.slug == "home");
-            let has_posts = pages.iter().any(|p: &PageItem| p.slug == "posts");
-            !(has_home && has_posts)
-        },
-        pages.clone()
-    );
+    // Check if essential pages exist; they may not be on the listed page,
+    // so look them up by slug directly
+    {
+        let essentials_missing = essentials_missing.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let has_home = get_page_by_slug("home").await.is_ok();
+                let has_posts = get_page_by_slug("posts").await.is_ok();
+                essentials_missing.set(!(has_home && has_posts));
+            });
+            || ()
+        }, ());
+    }
+    let show_init_button = *essentials_missing;
 
-    // Load pages on mount
+    // Load the current page of pages whenever it changes
     {
         let pages = pages.clone();
+        let list_info = list_info.clone();
         let loading = loading.clone();
         let error = error.clone();
         
-        use_effect_with_deps(move |_| {
+        use_effect_with_deps(move |list_page: &i64| {
+            let list_page = *list_page;
             wasm_bindgen_futures::spawn_local(async move {
-                match get_pages().await {
-                    Ok(page_list) => {
-                        pages.set(page_list);
+                match get_pages_page(list_page, PAGES_PER_PAGE).await {
+                    Ok(paged) => {
+                        pages.set(paged.pages.clone());
+                        list_info.set(Some(paged));
                         loading.set(false);
                     }
                     Err(e) => {
@@ -49,9 +64,21 @@ pub fn page_builder() -> Html {
                 }
             });
             || ()
-        }, ());
+        }, *list_page);
     }
 
+    let total_list_pages = list_info.as_ref().map(|info| info.total_pages).unwrap_or(0);
+
+    let on_prev_list_page = {
+        let list_page = list_page.clone();
+        Callback::from(move |_: MouseEvent| list_page.set((*list_page - 1).max(1)))
+    };
+
+    let on_next_list_page = {
+        let list_page = list_page.clone();
+        Callback::from(move |_: MouseEvent| list_page.set((*list_page + 1).min(total_list_pages.max(1))))
+    };
+
     // Load page for editing
     let load_page = {
         let current_page = current_page.clone();
@@ -284,11 +311,13 @@ pub fn page_builder() -> Html {
         let pages = pages.clone();
         let loading = loading.clone();
         let error = error.clone();
+        let essentials_missing = essentials_missing.clone();
         
         Callback::from(move |_| {
             let pages = pages.clone();
             let loading = loading.clone();
             let error = error.clone();
+            let essentials_missing = essentials_missing.clone();
             
             loading.set(true);
             
@@ -298,6 +327,7 @@ pub fn page_builder() -> Html {
                         let mut current_pages = (*pages).clone();
                         current_pages.extend(created_pages.iter().cloned());
                         pages.set(current_pages);
+                        essentials_missing.set(false);
                         loading.set(false);
                         
                         if created_pages.len() > 0 {
@@ -315,6 +345,18 @@ pub fn page_builder() -> Html {
         })
     };
 
+    // Opens the saved page on the public site; drafts get a signed preview link
+    let public_view = current_page.as_ref().and_then(|page| {
+        let is_published = page.status == "published";
+        let content = PublicContent::Page { id: page.id?, slug: page.slug.clone(), status: page.status.clone() };
+        let error = error.clone();
+        let callback = Callback::from(move |_: MouseEvent| {
+            let error = error.clone();
+            open_public_view(content.clone(), move |message| error.set(Some(message)));
+        });
+        Some((callback, is_published))
+    });
+
     html! {
         <div class="enhanced-page-builder">
             <div class="page-builder-header">
@@ -361,6 +403,17 @@ pub fn page_builder() -> Html {
                                 }
                             })}
                         </select>
+                        if let Some(ref info) = *list_info {
+                            if info.total_pages > 1 {
+                                <div class="pagination">
+                                    <button class="btn btn-secondary" onclick={on_prev_list_page} disabled={info.page <= 1}>{"Previous"}</button>
+                                    <span class="pagination-info">
+                                        {format!("Page {} of {} ({} pages)", info.page, info.total_pages, info.total)}
+                                    </span>
+                                    <button class="btn btn-secondary" onclick={on_next_list_page} disabled={info.page >= info.total_pages}>{"Next"}</button>
+                                </div>
+                            }
+                        }
                     </div>
                     <div class="form-group">
                         <label>{"Page Title"}</label>
@@ -391,7 +444,7 @@ pub fn page_builder() -> Html {
                     >
                         {"New Page"}
                     </button>
-                    {if *show_init_button {
+                    {if show_init_button {
                         html! {
                             <button
                                 class="btn btn-primary"
@@ -411,6 +464,11 @@ pub fn page_builder() -> Html {
                     >
                         {if *show_preview { "Edit" } else { "Preview" }}
                     </button>
+                    if let Some((on_view_public, is_published)) = public_view {
+                        <button class="btn btn-secondary" onclick={on_view_public}>
+                            {if is_published { "View Live ↗" } else { "Open Preview Link ↗" }}
+                        </button>
+                    }
                     <button
                         class="btn btn-primary"
                         onclick={save_page}
@@ -0,0 +1,231 @@
+use yew::prelude::*;
+use crate::services::api_service::{get_post_calendar, get_admin_post, update_post, CalendarPost};
+use crate::services::local_time::{parse_utc, local_time_label, local_day_start, move_to_local_day};
+use crate::services::toast_context::use_toast;
+use crate::components::admin::sidebar::AdminTab;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// Month shown by the calendar; `month` is 0-based like JS `Date`
+#[derive(Clone, Copy, PartialEq)]
+struct CalendarMonth {
+    year: i32,
+    month: i32,
+}
+
+impl CalendarMonth {
+    fn current() -> Self {
+        let now = js_sys::Date::new_0();
+        Self { year: now.get_full_year() as i32, month: now.get_month() as i32 }
+    }
+
+    fn offset(self, months: i32) -> Self {
+        let index = self.year * 12 + self.month + months;
+        Self { year: index.div_euclid(12), month: index.rem_euclid(12) }
+    }
+
+    fn days(self) -> i32 {
+        // Day 0 of the next month is the last day of this one
+        local_day_start(self.year, self.month + 1, 0).get_date() as i32
+    }
+
+    /// Weekday of the 1st, 0 = Sunday
+    fn first_weekday(self) -> i32 {
+        local_day_start(self.year, self.month, 1).get_day() as i32
+    }
+
+    /// UTC bounds of the month in the browser's time zone
+    fn utc_range(self) -> (String, String) {
+        let iso = |date: js_sys::Date| date.to_iso_string().as_string().unwrap_or_default();
+        (iso(local_day_start(self.year, self.month, 1)), iso(local_day_start(self.year, self.month + 1, 1)))
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PostCalendarProps {
+    #[prop_or_default]
+    pub on_navigate: Option<Callback<AdminTab>>,
+}
+
+/// Month view of published and scheduled posts by publish date
+///
+/// Dates are shown in the browser's time zone. Dropping a post on another
+/// day keeps its local time of day and saves the new date via `update_post`.
+#[function_component(PostCalendar)]
+pub fn post_calendar(props: &PostCalendarProps) -> Html {
+    let toast = use_toast();
+    let month = use_state(CalendarMonth::current);
+    let posts = use_state(Vec::<CalendarPost>::new);
+    let loading = use_state(|| true);
+    let reload_counter = use_state(|| 0u32);
+    let dragging = use_state(|| None::<i32>);
+    let drop_target = use_state(|| None::<i32>);
+
+    // Reload when the month changes or after a reschedule
+    {
+        let posts = posts.clone();
+        let loading = loading.clone();
+        let toast = toast.clone();
+        use_effect_with_deps(move |(month, _): &(CalendarMonth, u32)| {
+            let (from, to) = month.utc_range();
+            loading.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_post_calendar(&from, &to).await {
+                    Ok(fetched) => posts.set(fetched),
+                    Err(e) => toast.error(format!("Failed to load calendar: {}", e)),
+                }
+                loading.set(false);
+            });
+            || ()
+        }, (*month, *reload_counter));
+    }
+
+    let change_month = |months: i32| {
+        let month = month.clone();
+        Callback::from(move |_: MouseEvent| month.set(month.offset(months)))
+    };
+    let on_today = {
+        let month = month.clone();
+        Callback::from(move |_: MouseEvent| month.set(CalendarMonth::current()))
+    };
+
+    let on_back = props.on_navigate.clone().map(|on_navigate| {
+        Callback::from(move |_: MouseEvent| on_navigate.emit(AdminTab::Posts))
+    });
+
+    let reschedule = {
+        let posts = posts.clone();
+        let reload_counter = reload_counter.clone();
+        let toast = toast.clone();
+        let month = *month;
+        Callback::from(move |(post_id, day): (i32, i32)| {
+            let Some(entry) = posts.iter().find(|p| p.id == post_id).cloned() else { return };
+            let Some(published_at) = move_to_local_day(&entry.published_at, month.year, month.month, day) else { return };
+            if published_at == entry.published_at {
+                return;
+            }
+            let reload_counter = reload_counter.clone();
+            let toast = toast.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = async {
+                    let mut post = get_admin_post(post_id).await?;
+                    post.published_at = Some(published_at);
+                    update_post(post_id, &post).await
+                }.await;
+                match result {
+                    Ok(_) => toast.success(format!("Moved \"{}\" to {} {}", entry.title, MONTH_NAMES[month.month as usize], day)),
+                    Err(e) => toast.error(format!("Failed to reschedule post: {}", e)),
+                }
+                reload_counter.set(*reload_counter + 1);
+            });
+        })
+    };
+
+    let days = month.days();
+    let leading_blanks = month.first_weekday();
+
+    let day_cell = |day: i32| {
+        let day_posts: Vec<(CalendarPost, js_sys::Date)> = posts.iter()
+            .filter_map(|post| parse_utc(&post.published_at).map(|date| (post.clone(), date)))
+            .filter(|(_, date)| date.get_date() as i32 == day)
+            .collect();
+
+        let on_dragover = {
+            let drop_target = drop_target.clone();
+            Callback::from(move |e: DragEvent| {
+                // Allow dropping here
+                e.prevent_default();
+                if *drop_target != Some(day) {
+                    drop_target.set(Some(day));
+                }
+            })
+        };
+        let on_drop = {
+            let dragging = dragging.clone();
+            let drop_target = drop_target.clone();
+            let reschedule = reschedule.clone();
+            Callback::from(move |e: DragEvent| {
+                e.prevent_default();
+                if let Some(post_id) = *dragging {
+                    reschedule.emit((post_id, day));
+                }
+                dragging.set(None);
+                drop_target.set(None);
+            })
+        };
+
+        html! {
+            <div
+                class={classes!("calendar-day", (*drop_target == Some(day)).then_some("drop-target"))}
+                ondragover={on_dragover}
+                ondrop={on_drop}
+            >
+                <span class="calendar-day-number">{day}</span>
+                {for day_posts.into_iter().map(|(post, date)| {
+                    let on_dragstart = {
+                        let dragging = dragging.clone();
+                        let post_id = post.id;
+                        Callback::from(move |_: DragEvent| dragging.set(Some(post_id)))
+                    };
+                    let on_dragend = {
+                        let dragging = dragging.clone();
+                        let drop_target = drop_target.clone();
+                        Callback::from(move |_: DragEvent| {
+                            dragging.set(None);
+                            drop_target.set(None);
+                        })
+                    };
+                    html! {
+                        <div
+                            class={classes!("calendar-post", post.status.clone())}
+                            draggable="true"
+                            ondragstart={on_dragstart}
+                            ondragend={on_dragend}
+                            title={format!("{} ({})", post.title, post.status)}
+                        >
+                            <span class="calendar-post-time">{local_time_label(&date)}</span>
+                            <span class="calendar-post-title">{&post.title}</span>
+                        </div>
+                    }
+                })}
+            </div>
+        }
+    };
+
+    html! {
+        <div class="post-calendar">
+            <div class="page-header">
+                <div>
+                    <h1>{"Publishing Calendar"}</h1>
+                    <p>{"Published and scheduled posts by publish date. Drag a post to another day to reschedule it."}</p>
+                </div>
+                <div class="header-actions">
+                    if let Some(on_back) = on_back {
+                        <button class="btn btn-secondary" onclick={on_back}>{"Back to Posts"}</button>
+                    }
+                </div>
+            </div>
+
+            <div class="calendar-toolbar">
+                <button class="btn btn-secondary" onclick={change_month(-1)}>{"‹ Prev"}</button>
+                <h2 class="calendar-title">{format!("{} {}", MONTH_NAMES[month.month as usize], month.year)}</h2>
+                <button class="btn btn-secondary" onclick={change_month(1)}>{"Next ›"}</button>
+                <button class="btn btn-secondary" onclick={on_today}>{"Today"}</button>
+                if *loading {
+                    <span class="calendar-loading">{"Loading..."}</span>
+                }
+                <span class="calendar-timezone">{"Times shown in your local time zone"}</span>
+            </div>
+
+            <div class="calendar-grid">
+                {for WEEKDAY_NAMES.iter().map(|name| html! { <div class="calendar-weekday">{name}</div> })}
+                {for (0..leading_blanks).map(|_| html! { <div class="calendar-day empty"></div> })}
+                {for (1..=days).map(day_cell)}
+            </div>
+        </div>
+    }
+}
@@ -1,6 +1,10 @@
 use yew::prelude::*;
-use crate::services::api_service::{create_post, update_post, Post};
+use wasm_bindgen::{JsCast, JsValue};
+use crate::services::api_service::{create_post, update_post, get_categories, export_post_markdown, Category, Post, SharePreviewLink};
 use crate::components::markdown_editor::MarkdownEditor;
+use crate::components::content_insights::ContentInsights;
+use crate::services::preview_service::{open_public_view, share_post_preview, PublicContent};
+use crate::services::local_time::{local_input_to_utc, utc_to_local_input};
 
 #[derive(Properties, PartialEq)]
 pub struct PostEditorProps {
@@ -9,14 +13,53 @@ pub struct PostEditorProps {
     pub on_cancel: Callback<()>,
 }
 
+/// Save `text` through the browser's download prompt
+fn download_text(filename: &str, mime: &str, text: &str) -> Result<(), JsValue> {
+    let document = web_sys::window().and_then(|window| window.document()).ok_or("no document")?;
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&js_sys::Array::of1(&JsValue::from_str(text)), &options)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+    let link: web_sys::HtmlElement = document.create_element("a")?.dyn_into()?;
+    link.set_attribute("href", &url)?;
+    link.set_attribute("download", filename)?;
+    link.click();
+    web_sys::Url::revoke_object_url(&url)
+}
+
 #[function_component(PostEditor)]
 pub fn post_editor(props: &PostEditorProps) -> Html {
     let title = use_state(|| props.post.as_ref().map(|p| p.title.clone()).unwrap_or_default());
     let content = use_state(|| props.post.as_ref().map(|p| p.content.clone()).unwrap_or_default());
     let author = use_state(|| props.post.as_ref().map(|p| p.author.clone()).unwrap_or_default());
     let status = use_state(|| props.post.as_ref().map(|p| p.status.clone()).unwrap_or_else(|| "draft".to_string()));
+    let category_id = use_state(|| props.post.as_ref().and_then(|p| p.category_id));
+    // `datetime-local` value in the browser's time zone
+    let publish_at = use_state(|| utc_to_local_input(&props.post.as_ref().and_then(|p| p.published_at.clone())));
+    let categories = use_state(Vec::<Category>::new);
     let loading = use_state(|| false);
     let error = use_state(|| None::<String>);
+    // Full URL and details of the last share link issued for this draft
+    let share_link = use_state(|| None::<(String, SharePreviewLink)>);
+
+    // Load available categories; new posts default to "General"
+    {
+        let categories = categories.clone();
+        let category_id = category_id.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Ok(fetched) = get_categories().await {
+                    if category_id.is_none() {
+                        if let Some(general) = fetched.iter().find(|c| c.name == "General") {
+                            category_id.set(Some(general.id));
+                        }
+                    }
+                    categories.set(fetched);
+                }
+            });
+            || ()
+        }, ());
+    }
 
     let on_title_change = {
         let title = title.clone();
@@ -50,6 +93,22 @@ pub fn post_editor(props: &PostEditorProps) -> Html {
         })
     };
 
+    let on_publish_at_change = {
+        let publish_at = publish_at.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            publish_at.set(input.value());
+        })
+    };
+
+    let on_category_change = {
+        let category_id = category_id.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            category_id.set(input.value().parse::<i32>().ok());
+        })
+    };
+
     let post_id = props.post.as_ref().and_then(|p| p.id);
     let post_created_at = props.post.as_ref().and_then(|p| p.created_at.clone());
     
@@ -58,6 +117,8 @@ pub fn post_editor(props: &PostEditorProps) -> Html {
         let content = content.clone();
         let author = author.clone();
         let status = status.clone();
+        let category_id = category_id.clone();
+        let publish_at = publish_at.clone();
         let loading = loading.clone();
         let error = error.clone();
         let on_save = props.on_save.clone();
@@ -69,6 +130,10 @@ pub fn post_editor(props: &PostEditorProps) -> Html {
                 error.set(Some("Please fill in all required fields".to_string()));
                 return;
             }
+            if *status == "scheduled" && local_input_to_utc(&publish_at).is_none() {
+                error.set(Some("Choose a publish date for scheduled posts".to_string()));
+                return;
+            }
 
             let post = Post {
                 id: post_id,
@@ -76,8 +141,13 @@ pub fn post_editor(props: &PostEditorProps) -> Html {
                 content: (*content).clone(),
                 author: (*author).clone(),
                 status: (*status).clone(),
-                category_id: None,
+                category_id: *category_id,
+                category_name: None,
                 created_at: post_created_at.clone(),
+                published_at: local_input_to_utc(&publish_at),
+                comment_count: None,
+                excerpt: None,
+                structured_data: None,
             };
 
             let loading = loading.clone();
@@ -112,6 +182,54 @@ pub fn post_editor(props: &PostEditorProps) -> Html {
         Callback::from(move |_| on_cancel.emit(()))
     };
 
+    // Opens the saved version; drafts get a signed preview link
+    let on_view_public = props.post.as_ref().and_then(|post| {
+        let content = PublicContent::Post { id: post.id?, status: post.status.clone() };
+        let error = error.clone();
+        Some(Callback::from(move |_: MouseEvent| {
+            let error = error.clone();
+            open_public_view(content.clone(), move |message| error.set(Some(message)));
+        }))
+    });
+
+    // Saved drafts and scheduled posts can be shared with reviewers
+    let on_share_preview = props.post.as_ref()
+        .filter(|post| matches!(post.status.as_str(), "draft" | "scheduled"))
+        .and_then(|post| post.id)
+        .map(|id| {
+            let error = error.clone();
+            let share_link = share_link.clone();
+            Callback::from(move |_: MouseEvent| {
+                let error = error.clone();
+                let share_link = share_link.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match share_post_preview(id).await {
+                        Ok(link) => share_link.set(Some(link)),
+                        Err(e) => error.set(Some(format!("Failed to create share link: {}", e))),
+                    }
+                });
+            })
+        });
+
+    // Exports the saved version, so unsaved edits aren't included
+    let on_export_markdown = post_id.map(|id| {
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let error = error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = export_post_markdown(id).await
+                    .map_err(|e| e.to_string())
+                    .and_then(|(filename, markdown)| {
+                        download_text(&filename, "text/markdown", &markdown)
+                            .map_err(|_| "the browser blocked the download".to_string())
+                    });
+                if let Err(message) = result {
+                    error.set(Some(format!("Failed to export post: {}", message)));
+                }
+            });
+        })
+    });
+
     html! {
         <div class="post-editor modern-editor">
             <div class="page-header">
@@ -124,6 +242,24 @@ pub fn post_editor(props: &PostEditorProps) -> Html {
                         <span class="btn-icon">{"✕"}</span>
                         {"Cancel"}
                     </button>
+                    if let Some(on_view_public) = on_view_public {
+                        <button class="btn btn-outline-secondary" onclick={on_view_public}>
+                            <span class="btn-icon">{"↗"}</span>
+                            {if props.post.as_ref().map(|p| p.status == "published").unwrap_or(false) { "View Post" } else { "Preview" }}
+                        </button>
+                    }
+                    if let Some(on_share_preview) = on_share_preview {
+                        <button class="btn btn-outline-secondary" onclick={on_share_preview} title="Create a time-limited link to the saved draft for reviewers without an account">
+                            <span class="btn-icon">{"🔗"}</span>
+                            {"Share Preview"}
+                        </button>
+                    }
+                    if let Some(on_export_markdown) = on_export_markdown {
+                        <button class="btn btn-outline-secondary" onclick={on_export_markdown} title="Download the saved post as Markdown with front matter">
+                            <span class="btn-icon">{"⬇"}</span>
+                            {"Download as Markdown"}
+                        </button>
+                    }
                     <button 
                         class={classes!("btn", "btn-primary", if *loading { "loading" } else { "" })} 
                         onclick={on_save} 
@@ -141,6 +277,26 @@ pub fn post_editor(props: &PostEditorProps) -> Html {
             </div>
 
             <div class="editor-content">
+                if let Some((ref url, ref link)) = *share_link {
+                    <div class="share-link-panel">
+                        <label for="share-link-url">{"Share link"}</label>
+                        <input
+                            type="text"
+                            id="share-link-url"
+                            readonly=true
+                            value={url.clone()}
+                            onfocus={Callback::from(|e: FocusEvent| {
+                                e.target_unchecked_into::<web_sys::HtmlInputElement>().select();
+                            })}
+                        />
+                        <p>
+                            {format!(
+                                "Anyone with this link can view the saved draft until {} UTC, up to {} times. It stops working once the post is published.",
+                                link.expires_at, link.max_views
+                            )}
+                        </p>
+                    </div>
+                }
                 if let Some(ref error_msg) = *error {
                     <div class="error-alert">
                         <span class="error-icon">{"⚠️"}</span>
@@ -201,10 +357,49 @@ pub fn post_editor(props: &PostEditorProps) -> Html {
                                         <select id="post-status" class="form-select" value={(*status).clone()} oninput={on_status_change}>
                                             <option value="draft">{"📝 Draft"}</option>
                                             <option value="published">{"🌐 Published"}</option>
+                                            <option value="scheduled">{"🕒 Scheduled"}</option>
+                                            <option value="trash">{"🗑️ Trash"}</option>
                                         </select>
                                     </div>
                                     <small class="form-hint">{"Control post visibility"}</small>
                                 </div>
+
+                                if *status == "scheduled" || *status == "published" {
+                                    <div class="form-group publish-date-group">
+                                        <label for="post-publish-at" class="form-label">
+                                            {"Publish Date"}
+                                            if *status == "scheduled" {
+                                                <span class="required-indicator">{"*"}</span>
+                                            }
+                                        </label>
+                                        <input
+                                            type="datetime-local"
+                                            id="post-publish-at"
+                                            class="form-input"
+                                            value={(*publish_at).clone()}
+                                            oninput={on_publish_at_change}
+                                        />
+                                        <small class="form-hint">{"In your local time zone; left empty, publishing uses the current time"}</small>
+                                    </div>
+                                }
+
+                                <div class="form-group category-group">
+                                    <label for="post-category" class="form-label">{"Category"}</label>
+                                    <div class="select-wrapper">
+                                        <select id="post-category" class="form-select" onchange={on_category_change}>
+                                            <option value="" selected={category_id.is_none()}>{"Default (General)"}</option>
+                                            { for categories.iter().map(|category| html! {
+                                                <option
+                                                    value={category.id.to_string()}
+                                                    selected={*category_id == Some(category.id)}
+                                                >
+                                                    {&category.name}
+                                                </option>
+                                            }) }
+                                        </select>
+                                    </div>
+                                    <small class="form-hint">{"Group related posts together"}</small>
+                                </div>
                             </div>
                         </div>
                     </div>
@@ -240,6 +435,7 @@ pub fn post_editor(props: &PostEditorProps) -> Html {
                                     <a href="https://www.markdownguide.org/basic-syntax/" target="_blank" rel="noopener">{"Markdown syntax"}</a>
                                     {" for rich formatting - preview available after saving"}
                                 </small>
+                                <ContentInsights title={(*title).clone()} content={(*content).clone()} />
                             </div>
                         </div>
                     </div>
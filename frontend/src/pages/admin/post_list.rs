@@ -1,13 +1,34 @@
 use yew::prelude::*;
-use crate::services::api_service::{get_posts, delete_post, Post};
+use web_sys::HtmlInputElement;
+use crate::services::api_service::{get_admin_posts, delete_post, duplicate_post, import_markdown_posts, Post, AdminPostsPage, PostListQuery, PostStatusCounts, ImportReport};
 use crate::components::admin::sidebar::AdminTab;
+use crate::services::timezone_context::use_display_timezone;
+
+/// Status tabs shown above the post table: (query value, label)
+const STATUS_TABS: &[(&str, &str)] = &[
+    ("", "All"),
+    ("published", "Published"),
+    ("draft", "Draft"),
+    ("scheduled", "Scheduled"),
+    ("trash", "Trash"),
+];
+
+fn status_count(counts: &PostStatusCounts, status: &str) -> i64 {
+    match status {
+        "published" => counts.published,
+        "draft" => counts.draft,
+        "scheduled" => counts.scheduled,
+        "trash" => counts.trash,
+        _ => counts.all,
+    }
+}
 
 #[derive(Clone, PartialEq)]
 #[allow(dead_code)]
 pub enum PostListView {
     List,
     Create,
-    Edit(Post),
+    Edit(Box<Post>),
 }
 
 #[derive(Properties, PartialEq)]
@@ -18,21 +39,30 @@ pub struct PostListProps {
 
 #[function_component(PostList)]
 pub fn post_list(props: &PostListProps) -> Html {
-    let posts = use_state(Vec::<Post>::new);
+    let listing = use_state(|| None::<AdminPostsPage>);
+    let timezone = use_display_timezone();
+    let query = use_state(|| PostListQuery { page: 1, ..Default::default() });
+    let reload_counter = use_state(|| 0u32);
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
     let current_view = use_state(|| PostListView::List);
+    let importing = use_state(|| false);
+    let import_report = use_state(|| None::<ImportReport>);
 
+    // Reload whenever the tab, search, or page changes, or after an edit
     {
-        let posts = posts.clone();
+        let listing = listing.clone();
         let loading = loading.clone();
         let error = error.clone();
 
-        use_effect_with_deps(move |_| {
+        use_effect_with_deps(move |(query, _): &(PostListQuery, u32)| {
+            let query = query.clone();
+            loading.set(true);
             wasm_bindgen_futures::spawn_local(async move {
-                match get_posts().await {
-                    Ok(fetched_posts) => {
-                        posts.set(fetched_posts);
+                match get_admin_posts(&query).await {
+                    Ok(page) => {
+                        listing.set(Some(page));
+                        error.set(None);
                         loading.set(false);
                     }
                     Err(e) => {
@@ -42,23 +72,23 @@ pub fn post_list(props: &PostListProps) -> Html {
                 }
             });
             || ()
-        }, ());
+        }, ((*query).clone(), *reload_counter));
     }
 
+    let reload = {
+        let reload_counter = reload_counter.clone();
+        move || reload_counter.set(*reload_counter + 1)
+    };
+
     let on_delete_post = {
-        let posts = posts.clone();
+        let reload = reload.clone();
         let error = error.clone();
         Callback::from(move |post_id: i32| {
-            let posts = posts.clone();
+            let reload = reload.clone();
             let error = error.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 match delete_post(post_id).await {
-                    Ok(_) => {
-                        // Remove the deleted post from the list
-                        let mut current_posts = (*posts).clone();
-                        current_posts.retain(|post| post.id != Some(post_id));
-                        posts.set(current_posts);
-                    }
+                    Ok(_) => reload(),
                     Err(e) => {
                         error.set(Some(format!("Failed to delete post: {}", e)));
                     }
@@ -67,6 +97,64 @@ pub fn post_list(props: &PostListProps) -> Html {
         })
     };
 
+    let on_duplicate_post = {
+        let reload = reload.clone();
+        let error = error.clone();
+        let current_view = current_view.clone();
+        Callback::from(move |post_id: i32| {
+            let reload = reload.clone();
+            let error = error.clone();
+            let current_view = current_view.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match duplicate_post(post_id).await {
+                    Ok(new_post) => {
+                        // Refresh the list and open the copy in the editor
+                        reload();
+                        current_view.set(PostListView::Edit(Box::new(new_post)));
+                    }
+                    Err(e) => {
+                        error.set(Some(format!("Failed to duplicate post: {}", e)));
+                    }
+                }
+            });
+        })
+    };
+
+    let on_import_files = {
+        let reload = reload.clone();
+        let error = error.clone();
+        let importing = importing.clone();
+        let import_report = import_report.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let Some(files) = input.files().filter(|files| files.length() > 0) else { return };
+            let reload = reload.clone();
+            let error = error.clone();
+            let importing = importing.clone();
+            let import_report = import_report.clone();
+            importing.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                match import_markdown_posts(&files).await {
+                    Ok(report) => {
+                        if report.imported > 0 {
+                            reload();
+                        }
+                        import_report.set(Some(report));
+                    }
+                    Err(e) => error.set(Some(format!("Failed to import posts: {}", e))),
+                }
+                importing.set(false);
+            });
+            // Let the same files be picked again after fixing them
+            input.set_value("");
+        })
+    };
+
+    let on_dismiss_import = {
+        let import_report = import_report.clone();
+        Callback::from(move |_| import_report.set(None))
+    };
+
     let on_create_post = {
         let on_navigate = props.on_navigate.clone();
         Callback::from(move |_| {
@@ -78,29 +166,55 @@ pub fn post_list(props: &PostListProps) -> Html {
 
     let on_edit_post = {
         let current_view = current_view.clone();
-        Callback::from(move |post: Post| current_view.set(PostListView::Edit(post)))
+        Callback::from(move |post: Post| current_view.set(PostListView::Edit(Box::new(post))))
     };
 
     let on_save_post = {
-        let posts = posts.clone();
+        let reload = reload.clone();
         let current_view = current_view.clone();
-        Callback::from(move |saved_post: Post| {
-            let mut current_posts: Vec<Post> = (*posts).clone();
-            if let Some(existing_index) = current_posts.iter().position(|p| p.id == saved_post.id) {
-                current_posts[existing_index] = saved_post;
-            } else {
-                current_posts.push(saved_post);
-            }
-            posts.set(current_posts);
+        Callback::from(move |_saved_post: Post| {
+            reload();
             current_view.set(PostListView::List);
         })
     };
 
+    // Switching tabs or searching starts again from the first page
+    let on_search_change = {
+        let query = query.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query.set(PostListQuery { page: 1, search: input.value(), ..(*query).clone() });
+        })
+    };
+
+    let total_pages = listing.as_ref().map(|l| l.total_pages).unwrap_or(0);
+
+    let on_prev_page = {
+        let query = query.clone();
+        Callback::from(move |_| {
+            query.set(PostListQuery { page: (query.page - 1).max(1), ..(*query).clone() });
+        })
+    };
+
+    let on_next_page = {
+        let query = query.clone();
+        Callback::from(move |_| {
+            query.set(PostListQuery { page: (query.page + 1).min(total_pages.max(1)), ..(*query).clone() });
+        })
+    };
+
+    let posts: Vec<Post> = listing.as_ref().map(|l| l.posts.clone()).unwrap_or_default();
+    let counts = listing.as_ref().map(|l| l.counts.clone()).unwrap_or_default();
+
     let on_cancel_edit = {
         let current_view = current_view.clone();
         Callback::from(move |_| current_view.set(PostListView::List))
     };
 
+    let on_calendar = props.on_navigate.clone().map(|on_navigate| {
+        Callback::from(move |_: MouseEvent| on_navigate.emit(AdminTab::PostCalendar))
+    });
+
     match *current_view {
         PostListView::List => {
             let on_create_post_clone = on_create_post.clone();
@@ -112,14 +226,85 @@ pub fn post_list(props: &PostListProps) -> Html {
                             <p>{"Manage and organize your content"}</p>
                         </div>
                         <div class="header-actions">
+                            if let Some(on_calendar) = on_calendar.clone() {
+                                <button class="btn btn-secondary" onclick={on_calendar}>{"Calendar"}</button>
+                            }
+                            <label class={classes!("btn", "btn-secondary", importing.then_some("disabled"))} for="markdown-import">
+                                {if *importing { "Importing..." } else { "Import Markdown" }}
+                            </label>
+                            <input
+                                type="file"
+                                id="markdown-import"
+                                multiple=true
+                                style="display: none;"
+                                accept=".md,.markdown,.txt,text/markdown"
+                                disabled={*importing}
+                                onchange={on_import_files}
+                            />
                             <button class="btn btn-primary" onclick={on_create_post}>{"Add New Post"}</button>
                         </div>
                     </div>
 
-                    if *loading {
+                    if let Some(ref report) = *import_report {
+                        <div class="import-report">
+                            <div class="import-report-header">
+                                <strong>{format!("Imported {} of {} files as drafts", report.imported, report.imported + report.failed)}</strong>
+                                <button class="btn btn-secondary" onclick={on_dismiss_import}>{"Dismiss"}</button>
+                            </div>
+                            <ul>
+                                {for report.results.iter().map(|result| html! {
+                                    <li class={if result.success { "import-ok" } else { "import-failed" }}>
+                                        <span class="import-file">{&result.file}</span>
+                                        {" — "}
+                                        if result.success {
+                                            {format!("created \"{}\"", result.title.as_deref().unwrap_or(""))}
+                                        } else {
+                                            {result.error.as_deref().unwrap_or("failed")}
+                                        }
+                                        {for result.warnings.iter().map(|warning| html! {
+                                            <div class="import-warning">{warning}</div>
+                                        })}
+                                    </li>
+                                })}
+                            </ul>
+                        </div>
+                    }
+
+                    <div class="post-status-tabs">
+                        {for STATUS_TABS.iter().map(|(status, label)| {
+                            let on_select = {
+                                let query = query.clone();
+                                let status = status.to_string();
+                                Callback::from(move |_| {
+                                    query.set(PostListQuery { page: 1, status: status.clone(), ..(*query).clone() });
+                                })
+                            };
+                            html! {
+                                <button
+                                    class={classes!("status-tab", (query.status == *status).then_some("active"))}
+                                    onclick={on_select}
+                                >
+                                    {format!("{} ({})", label, status_count(&counts, status))}
+                                </button>
+                            }
+                        })}
+                        <input
+                            type="search"
+                            class="form-input post-search"
+                            placeholder="Search posts"
+                            value={query.search.clone()}
+                            onchange={on_search_change}
+                        />
+                    </div>
+
+                    if *loading && listing.is_none() {
                         <div class="loading">{"Loading posts..."}</div>
                     } else if let Some(ref error_msg) = *error {
                         <div class="error">{"Error loading posts: "}{error_msg}</div>
+                    } else if posts.is_empty() && (!query.status.is_empty() || !query.search.trim().is_empty()) {
+                        <div class="empty-state">
+                            <p>{"No posts match this view."}</p>
+                        </div>
                     } else if posts.is_empty() {
                         <div class="empty-state">
                             <h3>{"No posts yet"}</h3>
@@ -146,6 +331,12 @@ pub fn post_list(props: &PostListProps) -> Html {
                                         Callback::from(move |_| on_delete_post.emit(post_id))
                                     };
 
+                                    let on_duplicate = {
+                                        let on_duplicate_post = on_duplicate_post.clone();
+                                        let post_id = post.id.unwrap_or(0);
+                                        Callback::from(move |_| on_duplicate_post.emit(post_id))
+                                    };
+
                                     let on_edit = {
                                         let on_edit_post = on_edit_post.clone();
                                         let post = post.clone();
@@ -161,9 +352,10 @@ pub fn post_list(props: &PostListProps) -> Html {
                                                     {&post.status}
                                                 </span>
                                             </td>
-                                            <td>{post.created_at.as_deref().unwrap_or("Unknown")}</td>
+                                            <td>{post.created_at.as_deref().map(|date| timezone.datetime(date)).unwrap_or_else(|| "Unknown".to_string())}</td>
                                             <td class="actions">
                                                 <button class="btn btn-secondary" onclick={on_edit}>{"Edit"}</button>
+                                                <button class="btn btn-secondary" onclick={on_duplicate}>{"Duplicate"}</button>
                                                 <button class="btn btn-danger" onclick={on_delete}>{"Delete"}</button>
                                             </td>
                                         </tr>
@@ -172,6 +364,16 @@ pub fn post_list(props: &PostListProps) -> Html {
                             </tbody>
                         </table>
                     </div>
+
+                    if let Some(ref page) = *listing {
+                        <div class="pagination">
+                            <button class="btn btn-secondary" onclick={on_prev_page} disabled={page.page <= 1 || *loading}>{"Previous"}</button>
+                            <span class="pagination-info">
+                                {format!("Page {} of {} ({} posts)", page.page, page.total_pages.max(1), page.total)}
+                            </span>
+                            <button class="btn btn-secondary" onclick={on_next_page} disabled={page.page >= page.total_pages || *loading}>{"Next"}</button>
+                        </div>
+                    }
                 }
             </div>
             }
@@ -185,7 +387,7 @@ pub fn post_list(props: &PostListProps) -> Html {
         },
         PostListView::Edit(ref post) => html! {
             <crate::pages::admin::PostEditor
-                post={Some(post.clone())}
+                post={Some((**post).clone())}
                 on_save={on_save_post}
                 on_cancel={on_cancel_edit}
             />
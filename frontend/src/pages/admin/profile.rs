@@ -0,0 +1,152 @@
+use yew::prelude::*;
+use crate::components::change_password_form::ChangePasswordForm;
+use crate::components::RelativeTime;
+use crate::services::auth_context::{use_auth, AuthAction};
+use crate::services::auth_service::{update_profile, AuthError, UpdateProfileRequest};
+use crate::services::toast_context::use_toast;
+
+/// The signed-in user's own account: details and password change
+#[function_component(Profile)]
+pub fn profile() -> Html {
+    let auth = use_auth();
+    let toast = use_toast();
+    let user = auth.user.clone();
+
+    let username = use_state(|| user.as_ref().map(|u| u.username.clone()).unwrap_or_default());
+    let email = use_state(|| user.as_ref().map(|u| u.email.clone()).unwrap_or_default());
+    let bio = use_state(|| user.as_ref().and_then(|u| u.bio.clone()).unwrap_or_default());
+    let saving = use_state(|| false);
+
+    let on_input = |field: &UseStateHandle<String>| {
+        let field = field.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            field.set(input.value());
+        })
+    };
+
+    let on_bio_input = {
+        let bio = bio.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlTextAreaElement = e.target_unchecked_into();
+            bio.set(input.value());
+        })
+    };
+
+    let on_save = {
+        let username = username.clone();
+        let email = email.clone();
+        let bio = bio.clone();
+        let saving = saving.clone();
+        let auth = auth.clone();
+        let toast = toast.clone();
+
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let request = UpdateProfileRequest {
+                username: (*username).clone(),
+                email: (*email).clone(),
+                bio: (*bio).clone(),
+            };
+            let email = email.clone();
+            let saving = saving.clone();
+            let auth = auth.clone();
+            let toast = toast.clone();
+
+            saving.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                match update_profile(&request).await {
+                    Ok(updated) => {
+                        if let Some(ref pending) = updated.pending_email {
+                            toast.success(format!("Profile saved. Check {} for a link to confirm your new address", pending));
+                        } else {
+                            toast.success("Profile saved");
+                        }
+                        // The current address stays in the form until the new one is confirmed
+                        email.set(updated.email.clone());
+                        auth.dispatch(AuthAction::SetUser(Box::new(updated)));
+                    }
+                    Err(AuthError::ServerError(msg)) => toast.error(msg),
+                    Err(e) => toast.error(format!("Failed to save profile: {}", e)),
+                }
+                saving.set(false);
+            });
+        })
+    };
+
+    let on_password_changed = {
+        let toast = toast.clone();
+        Callback::from(move |_| {
+            toast.success("Your password has been changed");
+        })
+    };
+
+    html! {
+        <div class="profile-page">
+            <div class="page-header">
+                <div>
+                    <h1>{"My Account"}</h1>
+                    <p>{"Your account details and password"}</p>
+                </div>
+            </div>
+
+            // The latest login is this session's own, so show the one before it
+            if let Some(previous_login_at) = user.as_ref().and_then(|u| u.previous_login_at.clone()) {
+                <p class="help-text">{"Last login: "}<RelativeTime value={previous_login_at} /></p>
+            }
+
+            <div class="settings-section">
+                <h2>{"Profile"}</h2>
+                if let Some(ref pending) = user.as_ref().and_then(|u| u.pending_email.clone()) {
+                    <div class="info-message">
+                        {format!("Waiting for you to confirm {}. Until then, your current address stays in use.", pending)}
+                    </div>
+                }
+                <form class="auth-form" onsubmit={on_save}>
+                    <div class="form-group">
+                        <label for="profile-username">{"Username"}</label>
+                        <input
+                            type="text"
+                            id="profile-username"
+                            value={(*username).clone()}
+                            oninput={on_input(&username)}
+                            required=true
+                        />
+                    </div>
+
+                    <div class="form-group">
+                        <label for="profile-email">{"Email"}</label>
+                        <input
+                            type="email"
+                            id="profile-email"
+                            value={(*email).clone()}
+                            oninput={on_input(&email)}
+                            required=true
+                        />
+                    </div>
+
+                    <div class="form-group">
+                        <label for="profile-bio">{"Bio"}</label>
+                        <textarea
+                            id="profile-bio"
+                            rows="4"
+                            maxlength="500"
+                            value={(*bio).clone()}
+                            oninput={on_bio_input}
+                        />
+                    </div>
+
+                    <button type="submit" class="btn btn-primary" disabled={*saving}>
+                        {if *saving { "Saving..." } else { "Save Profile" }}
+                    </button>
+                </form>
+            </div>
+
+            <div class="settings-section">
+                <h2>{"Change Password"}</h2>
+                <ChangePasswordForm on_success={on_password_changed} />
+            </div>
+        </div>
+    }
+}
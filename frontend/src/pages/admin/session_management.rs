@@ -0,0 +1,348 @@
+use yew::prelude::*;
+use web_sys::HtmlInputElement;
+use crate::services::api_service::{get_my_sessions, get_admin_sessions, force_logout_all_sessions, SessionInfo, AdminSessionsPage, SessionListQuery};
+use crate::services::auth_service::clear_auth;
+use crate::components::ConfirmDialog;
+use crate::services::local_time::DisplayTimezone;
+use crate::services::timezone_context::use_display_timezone;
+
+/// Text the admin must type before a global session reset is allowed
+const FORCE_LOGOUT_CONFIRMATION: &str = "LOGOUT ALL";
+
+fn format_optional_timestamp(timezone: DisplayTimezone, value: &Option<String>) -> String {
+    value.as_deref().map(|value| timezone.datetime(value)).unwrap_or_else(|| "Unknown".to_string())
+}
+
+fn is_expired(expires_at: &Option<String>) -> bool {
+    let now = chrono::DateTime::from_timestamp_millis(js_sys::Date::now() as i64).map(|dt| dt.naive_utc());
+    match (expires_at.as_deref(), now) {
+        (Some(value), Some(now)) => chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+            .map(|expires| expires <= now)
+            .unwrap_or(false),
+        _ => true,
+    }
+}
+
+#[function_component(SessionManagement)]
+pub fn session_management() -> Html {
+    let sessions = use_state(Vec::<SessionInfo>::new);
+    let timezone = use_display_timezone();
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+
+    // System-wide listing
+    let query = use_state(|| SessionListQuery { page: 1, ..Default::default() });
+    let all_sessions = use_state(|| None::<AdminSessionsPage>);
+    let all_loading = use_state(|| true);
+    let all_error = use_state(|| None::<String>);
+    let reload_counter = use_state(|| 0u32);
+
+    // Global session reset
+    let confirming_reset = use_state(|| false);
+    let keep_own_session = use_state(|| true);
+    let resetting = use_state(|| false);
+    let reset_message = use_state(|| None::<String>);
+
+    {
+        let sessions = sessions.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_my_sessions().await {
+                    Ok(fetched_sessions) => {
+                        sessions.set(fetched_sessions);
+                    }
+                    Err(e) => {
+                        error.set(Some(e.to_string()));
+                    }
+                }
+                loading.set(false);
+            });
+            || ()
+        }, ());
+    }
+
+    // Reload the system-wide listing whenever the filters or page change
+    {
+        let all_sessions = all_sessions.clone();
+        let all_loading = all_loading.clone();
+        let all_error = all_error.clone();
+
+        use_effect_with_deps(move |(query, _): &(SessionListQuery, u32)| {
+            let query = query.clone();
+            all_loading.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_admin_sessions(&query).await {
+                    Ok(page) => {
+                        all_sessions.set(Some(page));
+                        all_error.set(None);
+                    }
+                    Err(e) => {
+                        all_error.set(Some(e.to_string()));
+                    }
+                }
+                all_loading.set(false);
+            });
+            || ()
+        }, ((*query).clone(), *reload_counter));
+    }
+
+    // Changing any filter starts again from the first page
+    let update_filter = |apply: fn(&mut SessionListQuery, String)| {
+        let query = query.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut next = (*query).clone();
+            apply(&mut next, input.value());
+            next.page = 1;
+            query.set(next);
+        })
+    };
+    let on_username_change = update_filter(|q, v| q.username = v);
+    let on_status_change = update_filter(|q, v| q.status = v);
+    let on_from_change = update_filter(|q, v| q.from = v);
+    let on_to_change = update_filter(|q, v| q.to = v);
+
+    let total_pages = all_sessions.as_ref().map(|p| p.total_pages).unwrap_or(0);
+
+    let on_prev_page = {
+        let query = query.clone();
+        Callback::from(move |_| {
+            let mut next = (*query).clone();
+            next.page = (next.page - 1).max(1);
+            query.set(next);
+        })
+    };
+
+    let on_next_page = {
+        let query = query.clone();
+        Callback::from(move |_| {
+            let mut next = (*query).clone();
+            next.page = (next.page + 1).min(total_pages.max(1));
+            query.set(next);
+        })
+    };
+
+    let on_request_reset = {
+        let confirming_reset = confirming_reset.clone();
+        Callback::from(move |_| confirming_reset.set(true))
+    };
+
+    let on_cancel_reset = {
+        let confirming_reset = confirming_reset.clone();
+        Callback::from(move |_| confirming_reset.set(false))
+    };
+
+    let on_keep_own_change = {
+        let keep_own_session = keep_own_session.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            keep_own_session.set(input.checked());
+        })
+    };
+
+    let on_force_logout_all = {
+        let confirming_reset = confirming_reset.clone();
+        let keep_own_session = keep_own_session.clone();
+        let resetting = resetting.clone();
+        let reset_message = reset_message.clone();
+        let reload_counter = reload_counter.clone();
+        Callback::from(move |_| {
+            confirming_reset.set(false);
+
+            let keep_own = *keep_own_session;
+            let resetting = resetting.clone();
+            let reset_message = reset_message.clone();
+            let reload_counter = reload_counter.clone();
+            resetting.set(true);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match force_logout_all_sessions(keep_own).await {
+                    Ok(_) if !keep_own => {
+                        // Our own session is gone too; return to the public site
+                        clear_auth();
+                        if let Some(window) = web_sys::window() {
+                            let _ = window.location().set_href("/");
+                        }
+                    }
+                    Ok(count) => {
+                        reset_message.set(Some(format!("Logged out {} sessions", count)));
+                        reload_counter.set(*reload_counter + 1);
+                    }
+                    Err(e) => {
+                        reset_message.set(Some(format!("Failed to log out sessions: {}", e)));
+                    }
+                }
+                resetting.set(false);
+            });
+        })
+    };
+
+    html! {
+        <div class="session-management">
+            <div class="page-header">
+                <div>
+                    <h1>{"Sessions"}</h1>
+                    <p>{"Monitor who is signed in across the site"}</p>
+                </div>
+            </div>
+
+            <div class="session-section">
+                <h2>{"All Sessions"}</h2>
+                if let Some(ref page) = *all_sessions {
+                    <p class="session-summary">
+                        {format!("{} active of {} total sessions", page.active_sessions, page.total_sessions)}
+                    </p>
+                }
+
+                <div class="session-filters">
+                    <input
+                        type="search"
+                        class="form-input"
+                        placeholder="Search by username"
+                        value={query.username.clone()}
+                        onchange={on_username_change}
+                    />
+                    <select class="form-select" onchange={on_status_change}>
+                        <option value="" selected={query.status.is_empty()}>{"All statuses"}</option>
+                        <option value="active" selected={query.status == "active"}>{"Active"}</option>
+                        <option value="expired" selected={query.status == "expired"}>{"Expired"}</option>
+                    </select>
+                    <label>
+                        {"From "}
+                        <input type="date" class="form-input" value={query.from.clone()} onchange={on_from_change} />
+                    </label>
+                    <label>
+                        {"To "}
+                        <input type="date" class="form-input" value={query.to.clone()} onchange={on_to_change} />
+                    </label>
+                </div>
+
+                if let Some(ref error_msg) = *all_error {
+                    <div class="error">{"Error loading sessions: "}{error_msg}</div>
+                } else if *all_loading && all_sessions.is_none() {
+                    <div class="loading">{"Loading sessions..."}</div>
+                } else if let Some(ref page) = *all_sessions {
+                    if page.sessions.is_empty() {
+                        <div class="empty-state">
+                            <p>{"No sessions match these filters."}</p>
+                        </div>
+                    } else {
+                        <div class="admin-table-container">
+                            <table>
+                                <thead>
+                                    <tr>
+                                        <th>{"User"}</th>
+                                        <th>{"Signed In"}</th>
+                                        <th>{"Last Activity"}</th>
+                                        <th>{"Expires"}</th>
+                                        <th>{"IP Address"}</th>
+                                        <th>{"Status"}</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {page.sessions.iter().map(|session| {
+                                        let expired = is_expired(&session.expires_at);
+                                        html! {
+                                            <tr key={session.id}>
+                                                <td>{session.username.clone().unwrap_or_else(|| "Unknown".to_string())}</td>
+                                                <td>{format_optional_timestamp(timezone, &session.created_at)}</td>
+                                                <td>{format_optional_timestamp(timezone, &session.last_seen_at)}</td>
+                                                <td>{format_optional_timestamp(timezone, &session.expires_at)}</td>
+                                                <td>{session.ip_address.clone().unwrap_or_else(|| "Unknown".to_string())}</td>
+                                                <td>
+                                                    <span class={classes!("status-badge", if expired { "draft" } else { "published" })}>
+                                                        {if expired { "Expired" } else { "Active" }}
+                                                    </span>
+                                                </td>
+                                            </tr>
+                                        }
+                                    }).collect::<Html>()}
+                                </tbody>
+                            </table>
+                        </div>
+                    }
+
+                    <div class="pagination">
+                        <button class="btn btn-secondary" onclick={on_prev_page} disabled={page.page <= 1 || *all_loading}>{"Previous"}</button>
+                        <span class="pagination-info">
+                            {format!("Page {} of {} ({} sessions)", page.page, page.total_pages.max(1), page.total)}
+                        </span>
+                        <button class="btn btn-secondary" onclick={on_next_page} disabled={page.page >= page.total_pages || *all_loading}>{"Next"}</button>
+                    </div>
+                }
+            </div>
+
+            <div class="session-section danger-zone">
+                <h2>{"Log Out Everyone"}</h2>
+                <p>
+                    {"Immediately ends every session on the site. Use this during a security incident."}
+                </p>
+                <div class="session-filters">
+                    <label>
+                        <input type="checkbox" checked={*keep_own_session} onchange={on_keep_own_change} />
+                        {" Keep my current session"}
+                    </label>
+                    <button class="btn btn-danger" onclick={on_request_reset} disabled={*resetting}>
+                        {if *resetting { "Logging out..." } else { "Log Out All Sessions" }}
+                    </button>
+                </div>
+                if let Some(ref message) = *reset_message {
+                    <div class="session-summary">{message}</div>
+                }
+                if *confirming_reset {
+                    <ConfirmDialog
+                        title="Log out everyone?"
+                        message={if *keep_own_session {
+                            "Every other session on the site will end immediately. Your current session is kept.".to_string()
+                        } else {
+                            "Every session on the site will end immediately, including yours.".to_string()
+                        }}
+                        confirm_label="Log Out All Sessions"
+                        require_text={FORCE_LOGOUT_CONFIRMATION.to_string()}
+                        on_confirm={on_force_logout_all}
+                        on_cancel={on_cancel_reset}
+                    />
+                }
+            </div>
+
+            <div class="session-section">
+                <h2>{"Your Sessions"}</h2>
+                if *loading {
+                    <div class="loading">{"Loading sessions..."}</div>
+                } else if let Some(ref error_msg) = *error {
+                    <div class="error">{"Error loading sessions: "}{error_msg}</div>
+                } else if sessions.is_empty() {
+                    <div class="empty-state">
+                        <p>{"No active sessions."}</p>
+                    </div>
+                } else {
+                    <div class="admin-table-container">
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>{"Signed In"}</th>
+                                    <th>{"Last Activity"}</th>
+                                    <th>{"Expires"}</th>
+                                    <th>{"IP Address"}</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {sessions.iter().map(|session| html! {
+                                    <tr key={session.id}>
+                                        <td>{timezone.datetime(&session.created_at)}</td>
+                                        <td>{format_optional_timestamp(timezone, &session.last_seen_at)}</td>
+                                        <td>{timezone.datetime(&session.expires_at)}</td>
+                                        <td>{session.ip_address.clone().unwrap_or_else(|| "Unknown".to_string())}</td>
+                                    </tr>
+                                }).collect::<Html>()}
+                            </tbody>
+                        </table>
+                    </div>
+                }
+            </div>
+        </div>
+    }
+}
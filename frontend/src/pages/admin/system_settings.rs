@@ -2,19 +2,94 @@ use yew::prelude::*;
 use wasm_bindgen::JsCast;
 use crate::services::api_service::{
     get_system_info, SystemInfo, get_backups, get_data_snapshot, create_backup,
-    BackupInfo, DataSnapshot, BackupRequest, get_settings, Setting, update_settings, SettingData
+    BackupInfo, DataSnapshot, BackupRequest, get_settings, get_admin_settings, Setting, update_settings, SettingData,
+    create_invite, get_media, MediaItem
 };
+use crate::components::MediaPicker;
+use crate::services::toast_context::use_toast;
+use crate::services::timezone_context::TimezoneContext;
+use crate::services::text_direction_context::{TextDirection, TextDirectionContext, TEXT_DIRECTION_SETTING};
+use crate::services::local_time::DisplayTimezone;
+use crate::services::page_service::{get_pages, Page};
+
+/// Gravatar's built-in fallback images, as (`d` value, label)
+const GRAVATAR_DEFAULTS: [(&str, &str); 8] = [
+    ("mp", "Mystery person"),
+    ("identicon", "Identicon (geometric pattern)"),
+    ("monsterid", "Monster"),
+    ("wavatar", "Wavatar (generated face)"),
+    ("retro", "Retro (8-bit)"),
+    ("robohash", "Robot"),
+    ("blank", "Blank"),
+    ("404", "None (broken image)"),
+];
+
+/// Gravatar ratings, as (`r` value, label)
+const GRAVATAR_RATINGS: [(&str, &str); 4] = [
+    ("g", "G: suitable for all audiences"),
+    ("pg", "PG: may be mildly offensive"),
+    ("r", "R: may contain harsh language or violence"),
+    ("x", "X: may be explicit"),
+];
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct SiteSettings {
     pub site_title: String,
     pub site_description: String,
+    /// Media id of the header logo; empty shows the site title
+    pub site_logo_media_id: String,
+    /// Media id of the favicon; empty for none
+    pub favicon_media_id: String,
     pub site_url: String,
+    pub canonical_trailing_slash: String,
     pub admin_email: String,
     pub posts_per_page: i32,
     pub allow_comments: bool,
     pub moderate_comments: bool,
     pub admin_button_visible: bool,
+    pub default_header_navigation: bool,
+    pub footer_powered_by_visible: bool,
+    pub enforce_image_alt: bool,
+    pub allow_public_signup: bool,
+    pub signup_invite_only: bool,
+    pub signup_allowed_email_domains: String,
+    pub signup_blocked_email_domains: String,
+    pub signup_min_fill_seconds: i32,
+    pub comment_max_length: i32,
+    pub comment_max_links: i32,
+    pub comment_rate_per_minute: i32,
+    pub comment_rate_per_hour: i32,
+    pub comments_require_verified_email: bool,
+    pub comments_hold_first_for_approval: bool,
+    /// Comma-separated words or phrases filtered out of comments
+    pub comment_blocked_words: String,
+    /// `reject` or `mask`
+    pub comment_word_filter_mode: String,
+    /// `oldest` or `newest`
+    pub comments_default_sort: String,
+    /// Gravatar image for commenters without one (`d`)
+    pub gravatar_default: String,
+    /// Most explicit Gravatar rating shown (`r`)
+    pub gravatar_rating: String,
+    /// `posts` or `page`
+    pub homepage_mode: String,
+    /// ID of the page shown at `/` in `page` mode; empty when unset
+    pub homepage_page_id: String,
+    /// Newest posts in the RSS feed, 1 to 100
+    pub feed_item_count: i32,
+    pub feed_full_content: bool,
+    /// Characters of text in post list excerpts, 1 to 2000
+    pub excerpt_length: i32,
+    /// Label of the link from an excerpt to its post
+    pub read_more_text: String,
+    /// `none`, `recaptcha` or `hcaptcha`
+    pub captcha_provider: String,
+    pub captcha_site_key: String,
+    /// Only sent when filled in; the stored secret is never loaded back
+    pub captcha_secret_key: String,
+    pub captcha_login_failures: i32,
+    pub timezone: String,
+    pub text_direction: String,
     pub theme: String,
 }
 
@@ -34,24 +109,91 @@ pub fn system_settings() -> Html {
     // State management for all tabs
     let active_tab = use_state(|| "site".to_string());
     let loading = use_state(|| false);
-    let error_message = use_state(|| None::<String>);
+    let toast = use_toast();
+    let display_timezone = use_context::<TimezoneContext>();
+    let text_direction = use_context::<TextDirectionContext>();
     let system_info = use_state(|| None::<SystemInfo>);
     let backups_list = use_state(|| None::<Vec<BackupInfo>>);
     let data_snapshot = use_state(|| None::<DataSnapshot>);
     let settings_list = use_state(|| None::<Vec<Setting>>);
+    // Published pages that can be picked as the homepage
+    let homepage_choices = use_state(Vec::<Page>::new);
     let saving = use_state(|| false);
-    let save_message = use_state(|| None::<String>);
+    let invite_email = use_state(String::new);
+    let invite_link = use_state(|| None::<String>);
+    // Images in the media library, for the logo and favicon previews
+    let media_images = use_state(Vec::<MediaItem>::new);
+    // Setting key the media picker is choosing an image for
+    let branding_picker = use_state(|| None::<&'static str>);
+
+    let on_create_invite = {
+        let invite_email = invite_email.clone();
+        let invite_link = invite_link.clone();
+        let toast = toast.clone();
+        Callback::from(move |_: MouseEvent| {
+            let email = Some(invite_email.trim().to_string()).filter(|email| !email.is_empty());
+            let invite_email = invite_email.clone();
+            let invite_link = invite_link.clone();
+            let toast = toast.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match create_invite(email).await {
+                    Ok(invite) => {
+                        let origin = web_sys::window()
+                            .and_then(|window| window.location().origin().ok())
+                            .unwrap_or_default();
+                        invite_link.set(Some(format!("{}/signup?invite={}", origin, invite.token)));
+                        invite_email.set(String::new());
+                    }
+                    Err(e) => toast.error(format!("Unable to create invite: {}", e)),
+                }
+            });
+        })
+    };
     
     // Site settings state
     let site_settings = use_state(|| SiteSettings {
         site_title: "My Rust CMS".to_string(),
+        site_logo_media_id: String::new(),
+        favicon_media_id: String::new(),
         site_description: "A modern content management system built with Rust".to_string(),
         site_url: "http://localhost:8080".to_string(),
+        canonical_trailing_slash: "remove".to_string(),
         admin_email: "admin@example.com".to_string(),
         posts_per_page: 10,
         allow_comments: true,
         moderate_comments: true,
         admin_button_visible: true,
+        default_header_navigation: true,
+        footer_powered_by_visible: true,
+        enforce_image_alt: false,
+        allow_public_signup: true,
+        signup_invite_only: false,
+        signup_allowed_email_domains: String::new(),
+        signup_blocked_email_domains: String::new(),
+        signup_min_fill_seconds: 3,
+        comment_max_length: 2000,
+        comment_max_links: 3,
+        comment_rate_per_minute: 3,
+        comment_rate_per_hour: 20,
+        comments_require_verified_email: false,
+        comments_hold_first_for_approval: false,
+        comment_blocked_words: String::new(),
+        comment_word_filter_mode: "reject".to_string(),
+        comments_default_sort: "oldest".to_string(),
+        gravatar_default: "identicon".to_string(),
+        gravatar_rating: "pg".to_string(),
+        homepage_mode: "posts".to_string(),
+        homepage_page_id: String::new(),
+        feed_item_count: 20,
+        feed_full_content: false,
+        excerpt_length: 200,
+        read_more_text: "Read Article".to_string(),
+        captcha_provider: "none".to_string(),
+        captcha_site_key: String::new(),
+        captcha_secret_key: String::new(),
+        captcha_login_failures: 3,
+        timezone: "UTC".to_string(),
+        text_direction: TextDirection::default().as_str().to_string(),
         theme: "Modern".to_string(),
     });
     
@@ -72,15 +214,14 @@ pub fn system_settings() -> Html {
     // API Callbacks for each tab
     let load_system_info = {
         let loading = loading.clone();
-        let error_message = error_message.clone();
+        let toast = toast.clone();
         let system_info = system_info.clone();
         
         Callback::from(move |_| {
             let loading = loading.clone();
-            let error_message = error_message.clone();
+            let toast = toast.clone();
             let system_info = system_info.clone();
             
-            error_message.set(None);
             loading.set(true);
             
             wasm_bindgen_futures::spawn_local(async move {
@@ -90,7 +231,7 @@ pub fn system_settings() -> Html {
                         web_sys::console::log_1(&"✅ System info loaded successfully".into());
                     },
                     Err(e) => {
-                        error_message.set(Some(format!("Unable to load system info: {}", e)));
+                        toast.error(format!("Unable to load system info: {}", e));
                         web_sys::console::warn_1(&format!("⚠️ System info API error: {}", e).into());
                     }
                 }
@@ -101,15 +242,14 @@ pub fn system_settings() -> Html {
 
     let load_backups = {
         let loading = loading.clone();
-        let error_message = error_message.clone();
+        let toast = toast.clone();
         let backups_list = backups_list.clone();
         
         Callback::from(move |_| {
             let loading = loading.clone();
-            let error_message = error_message.clone();
+            let toast = toast.clone();
             let backups_list = backups_list.clone();
             
-            error_message.set(None);
             loading.set(true);
             
             wasm_bindgen_futures::spawn_local(async move {
@@ -119,7 +259,7 @@ pub fn system_settings() -> Html {
                         web_sys::console::log_1(&"✅ Backups loaded successfully".into());
                     },
                     Err(e) => {
-                        error_message.set(Some(format!("Unable to load backups: {}", e)));
+                        toast.error(format!("Unable to load backups: {}", e));
                         web_sys::console::warn_1(&format!("⚠️ Backups API error: {}", e).into());
                     }
                 }
@@ -130,15 +270,14 @@ pub fn system_settings() -> Html {
 
     let load_data_snapshot = {
         let loading = loading.clone();
-        let error_message = error_message.clone();
+        let toast = toast.clone();
         let data_snapshot = data_snapshot.clone();
         
         Callback::from(move |_| {
             let loading = loading.clone();
-            let error_message = error_message.clone();
+            let toast = toast.clone();
             let data_snapshot = data_snapshot.clone();
             
-            error_message.set(None);
             loading.set(true);
             
             wasm_bindgen_futures::spawn_local(async move {
@@ -148,7 +287,7 @@ pub fn system_settings() -> Html {
                         web_sys::console::log_1(&"✅ Data snapshot loaded successfully".into());
                     },
                     Err(e) => {
-                        error_message.set(Some(format!("Unable to load data snapshot: {}", e)));
+                        toast.error(format!("Unable to load data snapshot: {}", e));
                         web_sys::console::warn_1(&format!("⚠️ Data snapshot API error: {}", e).into());
                     }
                 }
@@ -159,15 +298,14 @@ pub fn system_settings() -> Html {
 
     let create_backup_handler = {
         let loading = loading.clone();
-        let error_message = error_message.clone();
+        let toast = toast.clone();
         let backups_list = backups_list.clone();
         
         Callback::from(move |_| {
             let loading = loading.clone();
-            let error_message = error_message.clone();
+            let toast = toast.clone();
             let backups_list = backups_list.clone();
             
-            error_message.set(None);
             loading.set(true);
             
             wasm_bindgen_futures::spawn_local(async move {
@@ -178,6 +316,7 @@ pub fn system_settings() -> Html {
                 
                 match create_backup(backup_request).await {
                     Ok(_) => {
+                        toast.success("Backup created successfully");
                         web_sys::console::log_1(&"✅ Backup created successfully".into());
                         // Refresh backups list
                         match get_backups().await {
@@ -186,7 +325,7 @@ pub fn system_settings() -> Html {
                         }
                     },
                     Err(e) => {
-                        error_message.set(Some(format!("Failed to create backup: {}", e)));
+                        toast.error(format!("Failed to create backup: {}", e));
                         web_sys::console::warn_1(&format!("⚠️ Backup creation error: {}", e).into());
                     }
                 }
@@ -197,15 +336,14 @@ pub fn system_settings() -> Html {
 
     let load_settings = {
         let loading = loading.clone();
-        let error_message = error_message.clone();
+        let toast = toast.clone();
         let settings_list = settings_list.clone();
         
         Callback::from(move |_| {
             let loading = loading.clone();
-            let error_message = error_message.clone();
+            let toast = toast.clone();
             let settings_list = settings_list.clone();
             
-            error_message.set(None);
             loading.set(true);
             
             wasm_bindgen_futures::spawn_local(async move {
@@ -215,7 +353,7 @@ pub fn system_settings() -> Html {
                         web_sys::console::log_1(&"✅ Settings loaded successfully".into());
                     },
                     Err(e) => {
-                        error_message.set(Some(format!("Unable to load settings: {}", e)));
+                        toast.error(format!("Unable to load settings: {}", e));
                         web_sys::console::warn_1(&format!("⚠️ Settings API error: {}", e).into());
                     }
                 }
@@ -224,31 +362,210 @@ pub fn system_settings() -> Html {
         })
     };
 
+    {
+        let homepage_choices = homepage_choices.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Ok(pages) = get_pages().await {
+                    homepage_choices.set(pages);
+                }
+            });
+            || ()
+        }, ());
+    }
+
+    {
+        let media_images = media_images.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Ok(media) = get_media().await {
+                    media_images.set(media.into_iter().filter(|item| item.type_.starts_with("image")).collect());
+                }
+            });
+            || ()
+        }, ());
+    }
+
+    let on_branding_image_selected = {
+        let site_settings = site_settings.clone();
+        let media_images = media_images.clone();
+        let branding_picker = branding_picker.clone();
+        Callback::from(move |item: MediaItem| {
+            let Some(id) = item.id else { return };
+            let mut settings = (*site_settings).clone();
+            match *branding_picker {
+                Some("site_logo_media_id") => settings.site_logo_media_id = id.to_string(),
+                Some("favicon_media_id") => settings.favicon_media_id = id.to_string(),
+                _ => return,
+            }
+            site_settings.set(settings);
+            if !media_images.iter().any(|known| known.id == Some(id)) {
+                let mut images = (*media_images).clone();
+                images.push(item);
+                media_images.set(images);
+            }
+            branding_picker.set(None);
+        })
+    };
+
+    let close_branding_picker = {
+        let branding_picker = branding_picker.clone();
+        Callback::from(move |_| branding_picker.set(None))
+    };
+
+    // Preview, choose and remove buttons for the logo or favicon setting
+    let branding_image_field = |key: &'static str, label: &str, help: &str| {
+        let media_id = match key {
+            "site_logo_media_id" => site_settings.site_logo_media_id.clone(),
+            _ => site_settings.favicon_media_id.clone(),
+        };
+        let preview = media_images.iter()
+            .find(|item| item.id.map(|id| id.to_string()) == Some(media_id.clone()))
+            .map(|item| item.url.clone());
+        let on_choose = {
+            let branding_picker = branding_picker.clone();
+            Callback::from(move |_: MouseEvent| branding_picker.set(Some(key)))
+        };
+        let on_remove = {
+            let site_settings = site_settings.clone();
+            Callback::from(move |_: MouseEvent| {
+                let mut settings = (*site_settings).clone();
+                match key {
+                    "site_logo_media_id" => settings.site_logo_media_id.clear(),
+                    _ => settings.favicon_media_id.clear(),
+                }
+                site_settings.set(settings);
+            })
+        };
+        html! {
+            <div class="form-group branding-image-field">
+                <label>{label}</label>
+                if let Some(url) = preview {
+                    <img class="branding-image-preview" src={url} alt={format!("{} preview", label)} />
+                } else if !media_id.is_empty() {
+                    <small class="form-help">{format!("Media {} (not in the media library any more)", media_id)}</small>
+                }
+                <div class="branding-image-actions">
+                    <button type="button" class="btn btn-secondary" onclick={on_choose}>{"Choose Image"}</button>
+                    if !media_id.is_empty() {
+                        <button type="button" class="btn btn-secondary" onclick={on_remove}>{"Remove"}</button>
+                    }
+                </div>
+                <small class="form-help">{help.to_string()}</small>
+            </div>
+        }
+    };
+
+    // Load stored site settings so saving doesn't overwrite them with defaults
+    {
+        let site_settings = site_settings.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Ok(mut settings) = get_settings(Some("site")).await {
+                    // Kept off the public endpoint, like the comment word filter and
+                    // the signup email domain lists
+                    if let Ok(system_settings) = get_admin_settings(Some("system")).await {
+                        settings.extend(system_settings);
+                    }
+                    let mut site_config = (*site_settings).clone();
+                    for setting in settings {
+                        let value = setting.setting_value.unwrap_or_default();
+                        match setting.setting_key.as_str() {
+                            "site_title" => site_config.site_title = value,
+                            "site_description" => site_config.site_description = value,
+                            "site_logo_media_id" => site_config.site_logo_media_id = value,
+                            "favicon_media_id" => site_config.favicon_media_id = value,
+                            "site_url" => site_config.site_url = value,
+                            "canonical_trailing_slash" => site_config.canonical_trailing_slash = value,
+                            "admin_email" => site_config.admin_email = value,
+                            "posts_per_page" => site_config.posts_per_page = value.parse().unwrap_or(site_config.posts_per_page),
+                            "allow_comments" => site_config.allow_comments = value == "true",
+                            "moderate_comments" => site_config.moderate_comments = value == "true",
+                            "admin_button_visible" => site_config.admin_button_visible = value == "true",
+                            "default_header_navigation" => site_config.default_header_navigation = value == "true",
+                            "footer_powered_by_visible" => site_config.footer_powered_by_visible = value == "true",
+                            "enforce_image_alt" => site_config.enforce_image_alt = value == "true",
+                            "allow_public_signup" => site_config.allow_public_signup = value == "true",
+                            "signup_invite_only" => site_config.signup_invite_only = value == "true",
+                            "signup_allowed_email_domains" => site_config.signup_allowed_email_domains = value,
+                            "signup_blocked_email_domains" => site_config.signup_blocked_email_domains = value,
+                            "signup_min_fill_seconds" => site_config.signup_min_fill_seconds = value.parse().unwrap_or(site_config.signup_min_fill_seconds),
+                            "comment_max_length" => site_config.comment_max_length = value.parse().unwrap_or(site_config.comment_max_length),
+                            "comment_max_links" => site_config.comment_max_links = value.parse().unwrap_or(site_config.comment_max_links),
+                            "comment_rate_per_minute" => site_config.comment_rate_per_minute = value.parse().unwrap_or(site_config.comment_rate_per_minute),
+                            "comment_rate_per_hour" => site_config.comment_rate_per_hour = value.parse().unwrap_or(site_config.comment_rate_per_hour),
+                            "comments_require_verified_email" => site_config.comments_require_verified_email = value == "true",
+                            "comments_hold_first_for_approval" => site_config.comments_hold_first_for_approval = value == "true",
+                            "comment_blocked_words" => site_config.comment_blocked_words = value,
+                            "comment_word_filter_mode" => site_config.comment_word_filter_mode = value,
+                            "comments_default_sort" => site_config.comments_default_sort = value,
+                            "gravatar_default" => site_config.gravatar_default = value,
+                            "gravatar_rating" => site_config.gravatar_rating = value,
+                            "homepage_mode" => site_config.homepage_mode = value,
+                            "homepage_page_id" => site_config.homepage_page_id = value,
+                            "feed_item_count" => site_config.feed_item_count = value.parse().unwrap_or(site_config.feed_item_count),
+                            "feed_full_content" => site_config.feed_full_content = value == "true",
+                            "excerpt_length" => site_config.excerpt_length = value.parse().unwrap_or(site_config.excerpt_length),
+                            "read_more_text" => site_config.read_more_text = value,
+                            "captcha_provider" => site_config.captcha_provider = value,
+                            "captcha_site_key" => site_config.captcha_site_key = value,
+                            "captcha_login_failures" => site_config.captcha_login_failures = value.parse().unwrap_or(site_config.captcha_login_failures),
+                            "timezone" => site_config.timezone = value,
+                            TEXT_DIRECTION_SETTING => site_config.text_direction = value,
+                            "theme" => site_config.theme = value,
+                            _ => {}
+                        }
+                    }
+                    site_settings.set(site_config);
+                }
+            });
+            || ()
+        }, ());
+    }
+
     // Site settings save callback
     let save_site_settings = {
         let site_settings = site_settings.clone();
         let saving = saving.clone();
-        let save_message = save_message.clone();
+        let toast = toast.clone();
+        let display_timezone = display_timezone.clone();
+        let text_direction = text_direction.clone();
         
         Callback::from(move |_| {
             let settings = (*site_settings).clone();
             let saving = saving.clone();
-            let save_message = save_message.clone();
+            let toast = toast.clone();
+            let display_timezone = display_timezone.clone();
+            let timezone = DisplayTimezone::from_setting(&settings.timezone);
+            let text_direction = text_direction.clone();
+            let direction = TextDirection::from_setting(&settings.text_direction);
             
             saving.set(true);
-            save_message.set(None);
             
             wasm_bindgen_futures::spawn_local(async move {
                 web_sys::console::log_1(&format!("Saving site settings: {:?}", settings).into());
                 
                 // Convert site settings to API format
-                let settings_data = vec![
+                let captcha_secret_key = settings.captcha_secret_key.trim().to_string();
+                let mut settings_data = vec![
                     SettingData {
                         key: "site_title".to_string(),
                         value: settings.site_title,
                         setting_type: "site".to_string(),
                         description: Some("Site title displayed in navigation".to_string()),
                     },
+                    SettingData {
+                        key: "site_logo_media_id".to_string(),
+                        value: settings.site_logo_media_id,
+                        setting_type: "site".to_string(),
+                        description: Some("Media id of the image shown in the header instead of the site title; empty for the title".to_string()),
+                    },
+                    SettingData {
+                        key: "favicon_media_id".to_string(),
+                        value: settings.favicon_media_id,
+                        setting_type: "site".to_string(),
+                        description: Some("Media id of the site's favicon; empty for none".to_string()),
+                    },
                     SettingData {
                         key: "site_description".to_string(),
                         value: settings.site_description,
@@ -261,6 +578,12 @@ pub fn system_settings() -> Html {
                         setting_type: "site".to_string(),
                         description: Some("Base URL of the site".to_string()),
                     },
+                    SettingData {
+                        key: "canonical_trailing_slash".to_string(),
+                        value: settings.canonical_trailing_slash.clone(),
+                        setting_type: "site".to_string(),
+                        description: Some("Whether public post and page URLs end in a slash: \"remove\" or \"add\"; other spellings redirect".to_string()),
+                    },
                     SettingData {
                         key: "admin_email".to_string(),
                         value: settings.admin_email,
@@ -291,6 +614,186 @@ pub fn system_settings() -> Html {
                         setting_type: "site".to_string(),
                         description: Some("Show admin button in public navigation".to_string()),
                     },
+                    SettingData {
+                        key: "default_header_navigation".to_string(),
+                        value: settings.default_header_navigation.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Show Home and published pages in the header when it has no menu items".to_string()),
+                    },
+                    SettingData {
+                        key: "footer_powered_by_visible".to_string(),
+                        value: settings.footer_powered_by_visible.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Show the footer template's additional (\"powered by\") line".to_string()),
+                    },
+                    SettingData {
+                        key: "enforce_image_alt".to_string(),
+                        value: settings.enforce_image_alt.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Require alt text on every image before a post or page can be published".to_string()),
+                    },
+                    SettingData {
+                        key: "allow_public_signup".to_string(),
+                        value: settings.allow_public_signup.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Let visitors create accounts from the signup page".to_string()),
+                    },
+                    SettingData {
+                        key: "signup_invite_only".to_string(),
+                        value: settings.signup_invite_only.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Require an admin-issued invite to sign up".to_string()),
+                    },
+                    SettingData {
+                        key: "signup_allowed_email_domains".to_string(),
+                        value: settings.signup_allowed_email_domains,
+                        setting_type: "system".to_string(),
+                        description: Some("Only these email domains can sign up (comma-separated; *.example.com matches subdomains; empty allows all)".to_string()),
+                    },
+                    SettingData {
+                        key: "signup_blocked_email_domains".to_string(),
+                        value: settings.signup_blocked_email_domains,
+                        setting_type: "system".to_string(),
+                        description: Some("Email domains that cannot sign up (comma-separated; *.example.com matches subdomains)".to_string()),
+                    },
+                    SettingData {
+                        key: "signup_min_fill_seconds".to_string(),
+                        value: settings.signup_min_fill_seconds.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Reject signups submitted faster than this many seconds after the form opened (0 disables)".to_string()),
+                    },
+                    SettingData {
+                        key: "comment_max_length".to_string(),
+                        value: settings.comment_max_length.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Maximum comment length in characters".to_string()),
+                    },
+                    SettingData {
+                        key: "comment_max_links".to_string(),
+                        value: settings.comment_max_links.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Maximum number of links allowed in a comment (0 disallows links)".to_string()),
+                    },
+                    SettingData {
+                        key: "comments_require_verified_email".to_string(),
+                        value: settings.comments_require_verified_email.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Only users with a verified email address can comment (admins and editors are exempt)".to_string()),
+                    },
+                    SettingData {
+                        key: "comments_hold_first_for_approval".to_string(),
+                        value: settings.comments_hold_first_for_approval.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Hold comments for moderation until the author has one approved comment (admins and editors are exempt)".to_string()),
+                    },
+                    SettingData {
+                        key: "comment_blocked_words".to_string(),
+                        value: settings.comment_blocked_words.clone(),
+                        setting_type: "system".to_string(),
+                        description: Some("Words or phrases not allowed in comments (comma-separated, whole words, case-insensitive)".to_string()),
+                    },
+                    SettingData {
+                        key: "comment_word_filter_mode".to_string(),
+                        value: settings.comment_word_filter_mode.clone(),
+                        setting_type: "system".to_string(),
+                        description: Some("What to do with comments containing a blocked word: reject or mask".to_string()),
+                    },
+                    SettingData {
+                        key: "comments_default_sort".to_string(),
+                        value: settings.comments_default_sort.clone(),
+                        setting_type: "site".to_string(),
+                        description: Some("Default order of comment threads: oldest or newest first".to_string()),
+                    },
+                    SettingData {
+                        key: "gravatar_default".to_string(),
+                        value: settings.gravatar_default.clone(),
+                        setting_type: "site".to_string(),
+                        description: Some("Gravatar image for commenters without one: mp, identicon, monsterid, wavatar, retro, robohash, blank or 404".to_string()),
+                    },
+                    SettingData {
+                        key: "gravatar_rating".to_string(),
+                        value: settings.gravatar_rating.clone(),
+                        setting_type: "site".to_string(),
+                        description: Some("Most explicit Gravatar rating to show: g, pg, r or x".to_string()),
+                    },
+                    SettingData {
+                        key: "comment_rate_per_minute".to_string(),
+                        value: settings.comment_rate_per_minute.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Maximum comments per minute from one user or IP address (0 disables)".to_string()),
+                    },
+                    SettingData {
+                        key: "comment_rate_per_hour".to_string(),
+                        value: settings.comment_rate_per_hour.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Maximum comments per hour from one user or IP address (0 disables)".to_string()),
+                    },
+                    SettingData {
+                        key: "homepage_mode".to_string(),
+                        value: settings.homepage_mode,
+                        setting_type: "site".to_string(),
+                        description: Some("What the public home shows: \"posts\" for the post list or \"page\" for a single page".to_string()),
+                    },
+                    SettingData {
+                        key: "homepage_page_id".to_string(),
+                        value: settings.homepage_page_id,
+                        setting_type: "site".to_string(),
+                        description: Some("ID of the published page shown as the home when the homepage mode is \"page\"".to_string()),
+                    },
+                    SettingData {
+                        key: "feed_item_count".to_string(),
+                        value: settings.feed_item_count.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Number of newest posts in the RSS feed".to_string()),
+                    },
+                    SettingData {
+                        key: "feed_full_content".to_string(),
+                        value: settings.feed_full_content.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Include each post's full content in the RSS feed, not just an excerpt".to_string()),
+                    },
+                    SettingData {
+                        key: "excerpt_length".to_string(),
+                        value: settings.excerpt_length.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Characters of text in post excerpts on post lists".to_string()),
+                    },
+                    SettingData {
+                        key: "read_more_text".to_string(),
+                        value: settings.read_more_text,
+                        setting_type: "site".to_string(),
+                        description: Some("Label of the link from a post excerpt to the full post".to_string()),
+                    },
+                    SettingData {
+                        key: "captcha_provider".to_string(),
+                        value: settings.captcha_provider,
+                        setting_type: "site".to_string(),
+                        description: Some("CAPTCHA provider for signup, login and comments: none, recaptcha or hcaptcha".to_string()),
+                    },
+                    SettingData {
+                        key: "captcha_site_key".to_string(),
+                        value: settings.captcha_site_key,
+                        setting_type: "site".to_string(),
+                        description: Some("Public site key from the CAPTCHA provider".to_string()),
+                    },
+                    SettingData {
+                        key: "captcha_login_failures".to_string(),
+                        value: settings.captcha_login_failures.to_string(),
+                        setting_type: "site".to_string(),
+                        description: Some("Failed logins from a username or IP address before login needs a CAPTCHA (0 always requires it)".to_string()),
+                    },
+                    SettingData {
+                        key: "timezone".to_string(),
+                        value: settings.timezone,
+                        setting_type: "site".to_string(),
+                        description: Some("Time zone for displayed dates (an IANA name like Europe/Berlin, or \"viewer\" for each visitor's own)".to_string()),
+                    },
+                    SettingData {
+                        key: TEXT_DIRECTION_SETTING.to_string(),
+                        value: settings.text_direction,
+                        setting_type: "site".to_string(),
+                        description: Some("Reading direction of the public site and admin: ltr, rtl or auto".to_string()),
+                    },
                     SettingData {
                         key: "theme".to_string(),
                         value: settings.theme,
@@ -298,24 +801,34 @@ pub fn system_settings() -> Html {
                         description: Some("Site theme".to_string()),
                     },
                 ];
+                // A system setting, so it is never returned by the public settings
+                if !captcha_secret_key.is_empty() {
+                    settings_data.push(SettingData {
+                        key: "captcha_secret_key".to_string(),
+                        value: captcha_secret_key,
+                        setting_type: "system".to_string(),
+                        description: Some("Secret key from the CAPTCHA provider, used to verify tokens".to_string()),
+                    });
+                }
                 
                 match update_settings(settings_data).await {
                     Ok(_) => {
                         saving.set(false);
-                        save_message.set(Some("Settings saved successfully!".to_string()));
+                        if let Some(display_timezone) = display_timezone {
+                            display_timezone.set(timezone);
+                        }
+                        if let Some(text_direction) = text_direction {
+                            text_direction.set(direction);
+                        }
+                        toast.success("Settings saved successfully!");
                         web_sys::console::log_1(&"Settings saved successfully".into());
                     }
                     Err(e) => {
                         saving.set(false);
-                        save_message.set(Some(format!("Error saving settings: {}", e)));
+                        toast.error(format!("Error saving settings: {}", e));
                         web_sys::console::error_1(&format!("Failed to save settings: {}", e).into());
                     }
                 }
-                
-                // Clear message after 3 seconds
-                let save_message = save_message.clone();
-                gloo_timers::future::TimeoutFuture::new(3000).await;
-                save_message.set(None);
             });
         })
     };
@@ -324,15 +837,14 @@ pub fn system_settings() -> Html {
     let save_email_settings = {
         let email_settings = email_settings.clone();
         let saving = saving.clone();
-        let save_message = save_message.clone();
+        let toast = toast.clone();
         
         Callback::from(move |_| {
             let settings = (*email_settings).clone();
             let saving = saving.clone();
-            let save_message = save_message.clone();
+            let toast = toast.clone();
             
             saving.set(true);
-            save_message.set(None);
             
             wasm_bindgen_futures::spawn_local(async move {
                 web_sys::console::log_1(&format!("Saving email settings: {:?}", settings).into());
@@ -385,20 +897,15 @@ pub fn system_settings() -> Html {
                 match update_settings(settings_data).await {
                     Ok(_) => {
                         saving.set(false);
-                        save_message.set(Some("Email settings saved successfully!".to_string()));
+                        toast.success("Email settings saved successfully!");
                         web_sys::console::log_1(&"Email settings saved successfully".into());
                     }
                     Err(e) => {
                         saving.set(false);
-                        save_message.set(Some(format!("Error saving email settings: {}", e)));
+                        toast.error(format!("Error saving email settings: {}", e));
                         web_sys::console::error_1(&format!("Failed to save email settings: {}", e).into());
                     }
                 }
-                
-                // Clear message after 3 seconds
-                let save_message = save_message.clone();
-                gloo_timers::future::TimeoutFuture::new(3000).await;
-                save_message.set(None);
             });
         })
     };
@@ -406,13 +913,13 @@ pub fn system_settings() -> Html {
     // Function to load email settings
     let do_load_email_settings = {
         let loading = loading.clone();
-        let error_message = error_message.clone();
+        let toast = toast.clone();
         let email_settings = email_settings.clone();
         let email_settings_loaded = email_settings_loaded.clone();
         
         move || {
             let loading = loading.clone();
-            let error_message = error_message.clone();
+            let toast = toast.clone();
             let email_settings = email_settings.clone();
             let email_settings_loaded = email_settings_loaded.clone();
             
@@ -421,7 +928,6 @@ pub fn system_settings() -> Html {
                 return;
             }
             
-            error_message.set(None);
             loading.set(true);
             
             wasm_bindgen_futures::spawn_local(async move {
@@ -448,7 +954,7 @@ pub fn system_settings() -> Html {
                         web_sys::console::log_1(&"✅ Email settings loaded successfully".into());
                     },
                     Err(e) => {
-                        error_message.set(Some(format!("Unable to load email settings: {}", e)));
+                        toast.error(format!("Unable to load email settings: {}", e));
                         web_sys::console::warn_1(&format!("⚠️ Email settings API error: {}", e).into());
                     }
                 }
@@ -490,19 +996,6 @@ pub fn system_settings() -> Html {
                 <p>{"Configure your CMS settings and preferences"}</p>
             </div>
 
-            // Show any errors or save messages
-            if let Some(error) = error_message.as_ref() {
-                <div class="notification error">
-                    <span>{error}</span>
-                </div>
-            }
-            
-            if let Some(message) = save_message.as_ref() {
-                <div class="notification success">
-                    <span>{message}</span>
-                </div>
-            }
-
             <div class="settings-content">
                 <div class="settings-tabs">
                     <button 
@@ -592,6 +1085,23 @@ pub fn system_settings() -> Html {
                                             })}
                                             placeholder="https://example.com"
                                         />
+                                        <small class="form-help">{"Canonical links on public pages use this scheme and host"}</small>
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Trailing Slash"}</label>
+                                        <select 
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.canonical_trailing_slash = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        >
+                                            <option value="remove" selected={site_settings.canonical_trailing_slash != "add"}>{"Remove (/page/about)"}</option>
+                                            <option value="add" selected={site_settings.canonical_trailing_slash == "add"}>{"Add (/page/about/)"}</option>
+                                        </select>
+                                        <small class="form-help">{"Post and page URLs spelled the other way redirect to this form"}</small>
                                     </div>
                                     
                                     <div class="form-group">
@@ -610,6 +1120,18 @@ pub fn system_settings() -> Html {
                                     </div>
                                 </div>
 
+                                <h3>{"Logo & Favicon"}</h3>
+                                <div class="form-grid">
+                                    {branding_image_field("site_logo_media_id", "Site Logo", "Shown in the header instead of the site title. A header template with the icon logo type shows it beside the title.")}
+                                    {branding_image_field("favicon_media_id", "Favicon", "The icon browsers show in tabs and bookmarks. A small square PNG works best.")}
+                                </div>
+                                <MediaPicker
+                                    show={branding_picker.is_some()}
+                                    filter_images_only={true}
+                                    on_close={close_branding_picker.clone()}
+                                    on_select={on_branding_image_selected.clone()}
+                                />
+
                                 <h3>{"Content Settings"}</h3>
                                 <div class="form-grid">
                                     <div class="form-group">
@@ -649,57 +1171,620 @@ pub fn system_settings() -> Html {
                                     </div>
                                 </div>
 
-                                <h3>{"Comment Settings"}</h3>
                                 <div class="form-grid">
                                     <div class="form-group checkbox-group">
                                         <label>
                                             <input 
                                                 type="checkbox" 
-                                                checked={site_settings.allow_comments}
+                                                checked={site_settings.enforce_image_alt}
                                                 onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
                                                     let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
                                                     let mut settings = (*site_settings).clone();
-                                                    settings.allow_comments = target.checked();
+                                                    settings.enforce_image_alt = target.checked();
                                                     site_settings.set(settings);
                                                 })}
                                             />
-                                            {"Allow Comments"}
+                                            {"Require image alt text before publishing"}
                                         </label>
                                     </div>
-                                    
+                                </div>
+
+                                <h3>{"Signups"}</h3>
+                                <div class="form-grid">
                                     <div class="form-group checkbox-group">
                                         <label>
                                             <input 
                                                 type="checkbox" 
-                                                checked={site_settings.moderate_comments}
+                                                checked={site_settings.allow_public_signup}
                                                 onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
                                                     let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
                                                     let mut settings = (*site_settings).clone();
-                                                    settings.moderate_comments = target.checked();
+                                                    settings.allow_public_signup = target.checked();
                                                     site_settings.set(settings);
                                                 })}
                                             />
-                                            {"Moderate Comments"}
+                                            {"Allow public signup"}
+                                        </label>
+                                    </div>
+
+                                    <div class="form-group checkbox-group">
+                                        <label>
+                                            <input 
+                                                type="checkbox" 
+                                                checked={site_settings.signup_invite_only}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.signup_invite_only = target.checked();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                            {"Invite only (overrides the setting above)"}
                                         </label>
                                     </div>
                                 </div>
 
-                                <h3>{"Navigation Settings"}</h3>
+                                <div class="form-grid">
+                                    <div class="form-group">
+                                        <label>{"Allowed Email Domains"}</label>
+                                        <input 
+                                            type="text" 
+                                            placeholder="Empty allows all, e.g. example.com, *.example.com"
+                                            value={site_settings.signup_allowed_email_domains.clone()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.signup_allowed_email_domains = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        />
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Blocked Email Domains"}</label>
+                                        <input 
+                                            type="text" 
+                                            placeholder="e.g. mailinator.com, *.ru"
+                                            value={site_settings.signup_blocked_email_domains.clone()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.signup_blocked_email_domains = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        />
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Minimum Signup Form Time (seconds, 0 = off)"}</label>
+                                        <input 
+                                            type="number" 
+                                            value={site_settings.signup_min_fill_seconds.to_string()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                if let Ok(value) = target.value().parse::<i32>() {
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.signup_min_fill_seconds = value;
+                                                    site_settings.set(settings);
+                                                }
+                                            })}
+                                            min="0"
+                                            max="60"
+                                        />
+                                    </div>
+                                </div>
+
+                                <div class="form-grid">
+                                    <div class="form-group">
+                                        <label>{"Invite email (optional)"}</label>
+                                        <input 
+                                            type="email" 
+                                            placeholder="Leave blank for an invite anyone can use"
+                                            value={(*invite_email).clone()}
+                                            oninput={let invite_email = invite_email.clone(); Callback::from(move |e: InputEvent| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                invite_email.set(target.value());
+                                            })}
+                                        />
+                                    </div>
+                                    <div class="form-group">
+                                        <button type="button" class="btn btn-secondary" onclick={on_create_invite}>{"Create Invite Link"}</button>
+                                        if let Some(link) = &*invite_link {
+                                            <small class="help-text">{"Single use, valid for 7 days: "}<code>{link.clone()}</code></small>
+                                        }
+                                    </div>
+                                </div>
+
+                                <h3>{"Comment Settings"}</h3>
                                 <div class="form-grid">
                                     <div class="form-group checkbox-group">
                                         <label>
                                             <input 
                                                 type="checkbox" 
-                                                checked={site_settings.admin_button_visible}
+                                                checked={site_settings.allow_comments}
                                                 onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
                                                     let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
                                                     let mut settings = (*site_settings).clone();
-                                                    settings.admin_button_visible = target.checked();
+                                                    settings.allow_comments = target.checked();
                                                     site_settings.set(settings);
                                                 })}
                                             />
-                                            {"Show Admin Button in Public Navigation"}
+                                            {"Allow Comments"}
+                                        </label>
+                                    </div>
+                                    
+                                    <div class="form-group checkbox-group">
+                                        <label>
+                                            <input 
+                                                type="checkbox" 
+                                                checked={site_settings.moderate_comments}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.moderate_comments = target.checked();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                            {"Moderate Comments"}
+                                        </label>
+                                    </div>
+                                </div>
+
+                                <div class="form-grid">
+                                    <div class="form-group">
+                                        <label>{"Maximum Comment Length (characters)"}</label>
+                                        <input 
+                                            type="number" 
+                                            value={site_settings.comment_max_length.to_string()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                if let Ok(value) = target.value().parse::<i32>() {
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.comment_max_length = value;
+                                                    site_settings.set(settings);
+                                                }
+                                            })}
+                                            min="1"
+                                            max="20000"
+                                        />
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Maximum Links per Comment"}</label>
+                                        <input 
+                                            type="number" 
+                                            value={site_settings.comment_max_links.to_string()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                if let Ok(value) = target.value().parse::<i32>() {
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.comment_max_links = value;
+                                                    site_settings.set(settings);
+                                                }
+                                            })}
+                                            min="0"
+                                            max="50"
+                                        />
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Comments per Minute (per user or IP, 0 = unlimited)"}</label>
+                                        <input 
+                                            type="number" 
+                                            value={site_settings.comment_rate_per_minute.to_string()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                if let Ok(value) = target.value().parse::<i32>() {
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.comment_rate_per_minute = value;
+                                                    site_settings.set(settings);
+                                                }
+                                            })}
+                                            min="0"
+                                            max="100"
+                                        />
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Comments per Hour (per user or IP, 0 = unlimited)"}</label>
+                                        <input 
+                                            type="number" 
+                                            value={site_settings.comment_rate_per_hour.to_string()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                if let Ok(value) = target.value().parse::<i32>() {
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.comment_rate_per_hour = value;
+                                                    site_settings.set(settings);
+                                                }
+                                            })}
+                                            min="0"
+                                            max="1000"
+                                        />
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Blocked Words"}</label>
+                                        <textarea 
+                                            rows="3"
+                                            placeholder="Comma-separated words or phrases"
+                                            value={site_settings.comment_blocked_words.clone()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlTextAreaElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.comment_blocked_words = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        />
+                                        <small class="form-help">{"Matched as whole words regardless of case, so \"ass\" won't catch \"class\""}</small>
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Comments With Blocked Words"}</label>
+                                        <select 
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.comment_word_filter_mode = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        >
+                                            <option value="reject" selected={site_settings.comment_word_filter_mode != "mask"}>{"Reject the comment"}</option>
+                                            <option value="mask" selected={site_settings.comment_word_filter_mode == "mask"}>{"Post it with the words masked"}</option>
+                                        </select>
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Comment Order"}</label>
+                                        <select 
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.comments_default_sort = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        >
+                                            <option value="oldest" selected={site_settings.comments_default_sort != "newest"}>{"Oldest first"}</option>
+                                            <option value="newest" selected={site_settings.comments_default_sort == "newest"}>{"Newest first"}</option>
+                                        </select>
+                                        <small class="form-help">{"Readers can still switch the order on each thread"}</small>
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Default Avatar"}</label>
+                                        <select 
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.gravatar_default = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        >
+                                            { for GRAVATAR_DEFAULTS.iter().map(|(value, label)| html! {
+                                                <option value={*value} selected={site_settings.gravatar_default == *value}>{*label}</option>
+                                            }) }
+                                        </select>
+                                        <small class="form-help">{"Shown by Gravatar for commenters who haven't set up an avatar"}</small>
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Maximum Avatar Rating"}</label>
+                                        <select 
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.gravatar_rating = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        >
+                                            { for GRAVATAR_RATINGS.iter().map(|(value, label)| html! {
+                                                <option value={*value} selected={site_settings.gravatar_rating == *value}>{*label}</option>
+                                            }) }
+                                        </select>
+                                    </div>
+
+                                    <div class="form-group checkbox-group">
+                                        <label>
+                                            <input 
+                                                type="checkbox" 
+                                                checked={site_settings.comments_require_verified_email}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.comments_require_verified_email = target.checked();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                            {"Require a verified email to comment"}
+                                        </label>
+                                    </div>
+
+                                    <div class="form-group checkbox-group">
+                                        <label>
+                                            <input 
+                                                type="checkbox" 
+                                                checked={site_settings.comments_hold_first_for_approval}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.comments_hold_first_for_approval = target.checked();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                            {"Hold a user's first comment for approval"}
+                                        </label>
+                                    </div>
+                                </div>
+
+                                <h3>{"Homepage"}</h3>
+                                <div class="form-grid">
+                                    <div class="form-group">
+                                        <label>{"Home Shows"}</label>
+                                        <select 
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.homepage_mode = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        >
+                                            <option value="posts" selected={site_settings.homepage_mode != "page"}>{"Latest posts"}</option>
+                                            <option value="page" selected={site_settings.homepage_mode == "page"}>{"A static page"}</option>
+                                        </select>
+                                    </div>
+
+                                    if site_settings.homepage_mode == "page" {
+                                        <div class="form-group">
+                                            <label>{"Homepage"}</label>
+                                            <select 
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.homepage_page_id = target.value();
+                                                    site_settings.set(settings);
+                                                })}
+                                            >
+                                                <option value="" selected={site_settings.homepage_page_id.is_empty()}>{"Choose a page..."}</option>
+                                                {for homepage_choices.iter().filter_map(|page| page.id.map(|id| (id, page))).map(|(id, page)| html! {
+                                                    <option value={id.to_string()} selected={site_settings.homepage_page_id == id.to_string()}>{&page.title}</option>
+                                                })}
+                                            </select>
+                                            <small class="form-help">{"Only published pages can be the homepage. If it is unpublished later, the home falls back to the post list."}</small>
+                                        </div>
+                                    }
+                                </div>
+
+                                <h3>{"RSS Feed"}</h3>
+                                <div class="form-grid">
+                                    <div class="form-group">
+                                        <label>{"Posts in Feed"}</label>
+                                        <input 
+                                            type="number" 
+                                            value={site_settings.feed_item_count.to_string()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.feed_item_count = target.value().parse().unwrap_or(settings.feed_item_count);
+                                                site_settings.set(settings);
+                                            })}
+                                            min="1"
+                                            max="100"
+                                        />
+                                    </div>
+
+                                    <div class="form-group checkbox-group">
+                                        <label>
+                                            <input 
+                                                type="checkbox" 
+                                                checked={site_settings.feed_full_content}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.feed_full_content = target.checked();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                            {"Include full post content (otherwise an excerpt)"}
+                                        </label>
+                                    </div>
+                                </div>
+
+                                <h3>{"Post Excerpts"}</h3>
+                                <div class="form-grid">
+                                    <div class="form-group">
+                                        <label>{"Excerpt Length"}</label>
+                                        <input 
+                                            type="number" 
+                                            value={site_settings.excerpt_length.to_string()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.excerpt_length = target.value().parse().unwrap_or(settings.excerpt_length);
+                                                site_settings.set(settings);
+                                            })}
+                                            min="1"
+                                            max="2000"
+                                        />
+                                        <small class="form-help">{"Characters of text shown for each post in post lists, cut at a word. Applies to existing posts too."}</small>
+                                    </div>
+
+                                    <div class="form-group">
+                                        <label>{"Read More Link"}</label>
+                                        <input 
+                                            type="text" 
+                                            value={site_settings.read_more_text.clone()}
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.read_more_text = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                            maxlength="40"
+                                        />
+                                    </div>
+                                </div>
+
+                                <h3>{"CAPTCHA"}</h3>
+                                <div class="form-grid">
+                                    <div class="form-group">
+                                        <label>{"Provider"}</label>
+                                        <select 
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.captcha_provider = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        >
+                                            <option value="none" selected={!matches!(site_settings.captcha_provider.as_str(), "recaptcha" | "hcaptcha")}>{"None"}</option>
+                                            <option value="recaptcha" selected={site_settings.captcha_provider == "recaptcha"}>{"Google reCAPTCHA v2"}</option>
+                                            <option value="hcaptcha" selected={site_settings.captcha_provider == "hcaptcha"}>{"hCaptcha"}</option>
+                                        </select>
+                                        <small class="form-help">{"Signup and comments always need a CAPTCHA when one is set up; admins and editors comment without one."}</small>
+                                    </div>
+
+                                    if matches!(site_settings.captcha_provider.as_str(), "recaptcha" | "hcaptcha") {
+                                        <div class="form-group">
+                                            <label>{"Site Key"}</label>
+                                            <input 
+                                                type="text" 
+                                                value={site_settings.captcha_site_key.clone()}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.captcha_site_key = target.value();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                        </div>
+                                        <div class="form-group">
+                                            <label>{"Secret Key"}</label>
+                                            <input 
+                                                type="password" 
+                                                value={site_settings.captcha_secret_key.clone()}
+                                                placeholder="Leave blank to keep the current secret"
+                                                autocomplete="off"
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.captcha_secret_key = target.value();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                        </div>
+                                        <div class="form-group">
+                                            <label>{"Failed Logins Before CAPTCHA"}</label>
+                                            <input 
+                                                type="number" 
+                                                min="0"
+                                                value={site_settings.captcha_login_failures.to_string()}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.captcha_login_failures = target.value().parse().unwrap_or(settings.captcha_login_failures);
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                            <small class="form-help">{"Counted per username and per IP address over 15 minutes; 0 asks for a CAPTCHA on every login."}</small>
+                                        </div>
+                                    }
+                                </div>
+
+                                <h3>{"Date & Time"}</h3>
+                                <div class="form-grid">
+                                    <div class="form-group">
+                                        <label>{"Time Zone"}</label>
+                                        <input 
+                                            type="text" 
+                                            value={site_settings.timezone.clone()}
+                                            placeholder="UTC"
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.timezone = target.value().trim().to_string();
+                                                site_settings.set(settings);
+                                            })}
+                                        />
+                                        <small class="form-help">{"An IANA name such as Europe/Berlin or America/New_York, or \"viewer\" to show each visitor their own time zone. Dates are always stored in UTC."}</small>
+                                    </div>
+                                </div>
+
+                                <h3>{"Reading Direction"}</h3>
+                                <div class="form-grid">
+                                    <div class="form-group">
+                                        <label>{"Text Direction"}</label>
+                                        <select 
+                                            onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                let target = e.target().unwrap().unchecked_into::<web_sys::HtmlSelectElement>();
+                                                let mut settings = (*site_settings).clone();
+                                                settings.text_direction = target.value();
+                                                site_settings.set(settings);
+                                            })}
+                                        >
+                                            {for [TextDirection::Ltr, TextDirection::Rtl, TextDirection::Auto].into_iter().map(|direction| {
+                                                let label = match direction {
+                                                    TextDirection::Ltr => "Left to right",
+                                                    TextDirection::Rtl => "Right to left (Arabic, Hebrew, Persian…)",
+                                                    TextDirection::Auto => "Automatic, from the content",
+                                                };
+                                                html! {
+                                                    <option value={direction.as_str()} selected={TextDirection::from_setting(&site_settings.text_direction) == direction}>{label}</option>
+                                                }
+                                            })}
+                                        </select>
+                                        <small class="form-help">{"Sets the direction of the public site and the admin; right to left mirrors the layout."}</small>
+                                    </div>
+                                </div>
+
+                                <h3>{"Navigation Settings"}</h3>
+                                <div class="form-grid">
+                                    <div class="form-group checkbox-group">
+                                        <label>
+                                            <input 
+                                                type="checkbox" 
+                                                checked={site_settings.admin_button_visible}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.admin_button_visible = target.checked();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                            {"Show Admin Button in Public Navigation"}
+                                        </label>
+                                    </div>
+
+                                    <div class="form-group checkbox-group">
+                                        <label>
+                                            <input 
+                                                type="checkbox" 
+                                                checked={site_settings.default_header_navigation}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.default_header_navigation = target.checked();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                            {"Show Home and Published Pages When the Header Menu Is Empty"}
+                                        </label>
+                                    </div>
+
+                                    <div class="form-group checkbox-group">
+                                        <label>
+                                            <input 
+                                                type="checkbox" 
+                                                checked={site_settings.footer_powered_by_visible}
+                                                onchange={let site_settings = site_settings.clone(); Callback::from(move |e: Event| {
+                                                    let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                                    let mut settings = (*site_settings).clone();
+                                                    settings.footer_powered_by_visible = target.checked();
+                                                    site_settings.set(settings);
+                                                })}
+                                            />
+                                            {"Show the \"Powered By\" Line in the Footer"}
                                         </label>
+                                        <small class="form-help">{"The line's text is the footer template's additional text, edited in the Template Manager."}</small>
                                     </div>
                                 </div>
 
@@ -887,6 +1972,7 @@ pub fn system_settings() -> Html {
                                                 <div class="info-item"><span class="label">{"Memory Usage:"}</span><span class="value">{&info.memory_usage}</span></div>
                                                 <div class="info-item"><span class="label">{"CPU Usage:"}</span><span class="value">{&info.cpu_usage}</span></div>
                                                 <div class="info-item"><span class="label">{"Disk Usage:"}</span><span class="value">{&info.disk_usage}</span></div>
+                                                <div class="info-item"><span class="label">{"Site Time:"}</span><span class="value">{&info.site_time}</span></div>
                                             </div>
                                             <div class="info-card">
                                                 <h4>{"Database Statistics"}</h4>
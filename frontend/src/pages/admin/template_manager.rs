@@ -1,7 +1,9 @@
 use yew::prelude::*;
 use wasm_bindgen::JsCast;
+use crate::services::toast_context::use_toast;
+use crate::services::component_style::{component_style, global_style_vars};
 use crate::services::navigation_service::{MenuArea, ComponentTemplate, NavigationItem, get_menu_areas, get_component_templates, get_all_component_templates_admin, update_menu_area, update_component_template, get_navigation_by_area, toggle_component_template};
-use crate::services::api_service::{SettingData, get_settings, update_settings, get_templates, Template};
+use crate::services::api_service::{SettingData, get_settings, update_settings, get_templates, reset_default_template, apply_template, Template};
 use serde_json::Value as JsonValue;
 use serde_json::json;
 use wasm_bindgen::JsValue;
@@ -112,6 +114,10 @@ pub fn template_manager() -> Html {
     let menu_areas = use_state(Vec::<MenuArea>::new);
 
     let component_templates = use_state(Vec::<ComponentTemplate>::new);
+    // Templates as of the last render, so a toggle answered after other
+    // changes applies on top of them rather than on its stale snapshot
+    let latest_templates = use_mut_ref(Vec::<ComponentTemplate>::new);
+    *latest_templates.borrow_mut() = (*component_templates).clone();
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
     let templates = use_state(Vec::<Template>::new);
@@ -302,81 +308,68 @@ pub fn template_manager() -> Html {
         }, ());
     }
 
-    // Apply selected master template
+    // Apply the selected master template on the server, then reload
     let apply_selected_template = {
-        let selected_template_id_state = selected_template_id.clone();
+        let selected_template_id = selected_template_id.clone();
         let templates_state = templates.clone();
-        let selected_template_id_setter = selected_template_id.clone();
         let menu_areas = menu_areas.clone();
         let component_templates = component_templates.clone();
         let error = error.clone();
         let notify_apply = notify_message.clone();
         Callback::from(move |_| {
-            if let Some(template_id) = *selected_template_id_state {
-                if let Some(selected) = (*templates_state).iter().find(|t| t.id == template_id) {
-                    // Parse layout JSON: expected keys: menu_areas: [], component_templates: [], container_settings: {}
-                    if let Ok(layout_json) = serde_json::from_str::<JsonValue>(&selected.layout) {
-                        // Apply menu areas
-                        if let Some(areas) = layout_json.get("menu_areas").and_then(|v| v.as_array()) {
-                            for area in areas {
-                                if let (Some(area_name), Some(is_active)) = (area.get("area_name").and_then(|v| v.as_str()), area.get("is_active").and_then(|v| v.as_bool())) {
-                                    if let Some(existing) = (*menu_areas).iter().find(|a| a.area_name == area_name) {
-                                        let mut updated = existing.clone();
-                                        updated.is_active = is_active;
-                                        if let Some(settings) = area.get("settings").cloned() { updated.settings = settings; }
-                                        if let Some(mobile) = area.get("mobile_behavior").and_then(|v| v.as_str()) { updated.mobile_behavior = Some(mobile.to_string()); }
-                                        if let Some(icon) = area.get("hamburger_icon").and_then(|v| v.as_str()) { updated.hamburger_icon = Some(icon.to_string()); }
-                                        let name = area_name.to_string();
-                                        wasm_bindgen_futures::spawn_local(async move {
-                                            let _ = update_menu_area(&name, &updated).await;
-                                        });
-                                    }
-                                }
-                            }
-                        }
-
-                        // Apply component templates by component_type
-                        if let Some(components) = layout_json.get("component_templates").and_then(|v| v.as_array()) {
-                            for comp in components {
-                                if let Some(component_type) = comp.get("component_type").and_then(|v| v.as_str()) {
-                                    if let Some(existing) = (*component_templates).iter().find(|t| t.component_type == component_type) {
-                                        let mut updated = existing.clone();
-                                        if let Some(data) = comp.get("template_data").cloned() { updated.template_data = data; }
-                                        if let Some(bp) = comp.get("breakpoints").cloned() { updated.breakpoints = bp; }
-                                        if let Some(width) = comp.get("width_setting").and_then(|v| v.as_str()) { updated.width_setting = Some(width.to_string()); }
-                                        if let Some(max_w) = comp.get("max_width").and_then(|v| v.as_str()) { updated.max_width = Some(max_w.to_string()); }
-                                        if let Some(is_active) = comp.get("is_active").and_then(|v| v.as_bool()) { updated.is_active = is_active; }
-                                        let id = updated.id;
-                                        wasm_bindgen_futures::spawn_local(async move {
-                                            let _ = update_component_template(id, &updated).await;
-                                        });
-                                    }
-                                }
-                            }
-                        }
+            let Some(template_id) = *selected_template_id else { return };
+            let Some(name) = (*templates_state).iter().find(|t| t.id == template_id).map(|t| t.name.clone()) else { return };
 
-                        // Apply container settings via system settings API
-                        if let Some(container) = layout_json.get("container_settings").and_then(|v| v.as_object()) {
-                            let mut settings_data: Vec<SettingData> = Vec::new();
-                            for (key, value) in container.iter() {
-                                // Store all container settings under container_ prefix
-                                let setting_key = format!("container_{}", key);
-                                let setting_value = if value.is_string() { value.as_str().unwrap_or("").to_string() } else { value.to_string() };
-                                settings_data.push(SettingData { key: setting_key, value: setting_value, setting_type: "container".to_string(), description: None });
-                            }
-                            wasm_bindgen_futures::spawn_local(async move {
-                                let _ = update_settings(settings_data).await;
-                            });
-                        }
-                        // Update selected dropdown to reflect applied template
-                        selected_template_id_setter.set(Some(selected.id));
-                        // Show success notification
-                        notify_apply.set(Some((format!("Applied template: {}", selected.name), "success".to_string())));
-                    } else {
-                        error.set(Some("Invalid template layout JSON".to_string()))
+            let menu_areas = menu_areas.clone();
+            let component_templates = component_templates.clone();
+            let error = error.clone();
+            let notify_apply = notify_apply.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match apply_template(template_id).await {
+                    Ok(()) => {
+                        if let Ok(areas) = get_menu_areas().await { menu_areas.set(areas); }
+                        if let Ok(components) = get_all_component_templates_admin().await { component_templates.set(components); }
+                        notify_apply.set(Some((format!("Applied template: {}", name), "success".to_string())));
                     }
+                    Err(e) => error.set(Some(format!("Failed to apply template: {}", e))),
                 }
+            });
+        })
+    };
+
+    // Restore the shipped Default template on the server, then reload
+    let reset_to_default = {
+        let templates_state = templates.clone();
+        let selected_template_id = selected_template_id.clone();
+        let menu_areas = menu_areas.clone();
+        let component_templates = component_templates.clone();
+        let error = error.clone();
+        let notify_reset = notify_message.clone();
+        Callback::from(move |_| {
+            let confirmed = web_sys::window()
+                .and_then(|w| w.confirm_with_message("Reset the theme to the Default template? All custom menu area, component template, and container styling will be overwritten.").ok())
+                .unwrap_or(false);
+            if !confirmed {
+                return;
             }
+
+            let default_id = (*templates_state).iter().find(|t| t.name == "Default").map(|t| t.id);
+            let selected_template_id = selected_template_id.clone();
+            let menu_areas = menu_areas.clone();
+            let component_templates = component_templates.clone();
+            let error = error.clone();
+            let notify_reset = notify_reset.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match reset_default_template().await {
+                    Ok(()) => {
+                        if let Ok(areas) = get_menu_areas().await { menu_areas.set(areas); }
+                        if let Ok(components) = get_all_component_templates_admin().await { component_templates.set(components); }
+                        if default_id.is_some() { selected_template_id.set(default_id); }
+                        notify_reset.set(Some(("Theme reset to the Default template".to_string(), "success".to_string())));
+                    }
+                    Err(e) => error.set(Some(format!("Failed to reset theme: {}", e))),
+                }
+            });
         })
     };
 
@@ -571,6 +564,7 @@ pub fn template_manager() -> Html {
                 </button>
                 <button class="btn-secondary" onclick={{ &open_import_modal }}>{"Import Template"}</button>
                 <button class="btn-secondary" onclick={{ &export_current_configuration }}>{"Export Template"}</button>
+                <button class="btn-danger" onclick={{ &reset_to_default }}>{"Reset to Default"}</button>
             </div>
 
             {if *show_import_modal {
@@ -740,13 +734,17 @@ pub fn template_manager() -> Html {
                                 on_modify={Callback::noop()}
                                 on_template_toggled={{
                                     let component_templates = component_templates.clone();
-                                    Callback::from(move |updated_template: ComponentTemplate| {
-                                        // Update the component template in the local state
-                                        let mut templates = (*component_templates).clone();
-                                        if let Some(index) = templates.iter().position(|t| t.id == updated_template.id) {
-                                            templates[index] = updated_template;
-                                            component_templates.set(templates);
+                                    let latest_templates = latest_templates.clone();
+                                    Callback::from(move |changed: Vec<ComponentTemplate>| {
+                                        // Replace the changed templates in the local state
+                                        let mut templates = latest_templates.borrow().clone();
+                                        for updated_template in changed {
+                                            if let Some(index) = templates.iter().position(|t| t.id == updated_template.id) {
+                                                templates[index] = updated_template;
+                                            }
                                         }
+                                        *latest_templates.borrow_mut() = templates.clone();
+                                        component_templates.set(templates);
                                     })
                                 }}
                             /> 
@@ -1137,11 +1135,30 @@ fn render_component_tags(component_type: &str) -> Html {
 pub struct ComponentTemplatesViewProps {
     pub component_templates: Vec<ComponentTemplate>,
     pub on_modify: Callback<(String, String)>, // (component_id, property)
-    pub on_template_toggled: Callback<ComponentTemplate>, // Called when template is toggled
+    pub on_template_toggled: Callback<Vec<ComponentTemplate>>, // Templates changed by a toggle
+}
+
+/// Templates of `changed`'s type with `changed` applied. One template per
+/// type is active, as the server enforces, so activating one deactivates
+/// the rest.
+fn with_toggled_template(templates: &[ComponentTemplate], changed: &ComponentTemplate) -> Vec<ComponentTemplate> {
+    templates.iter()
+        .filter(|t| t.component_type == changed.component_type)
+        .map(|t| {
+            if t.id == changed.id {
+                changed.clone()
+            } else if changed.is_active {
+                ComponentTemplate { is_active: false, ..t.clone() }
+            } else {
+                t.clone()
+            }
+        })
+        .collect()
 }
 
 #[function_component(ComponentTemplatesView)]
 pub fn component_templates_view(props: &ComponentTemplatesViewProps) -> Html {
+    let toast = use_toast();
     let editing_component = use_state(|| None::<String>);
     let editing_template = use_state(|| None::<ComponentTemplate>);
     let saving = use_state(|| false);
@@ -1273,6 +1290,18 @@ pub fn component_templates_view(props: &ComponentTemplatesViewProps) -> Html {
         })
     };
 
+    // The live preview is styled the way the public layout styles the site,
+    // with the template being edited standing in for the active one
+    let (preview_style, preview_vars) = match (*editing_template).as_ref() {
+        Some(template) => {
+            let templates: Vec<ComponentTemplate> = std::iter::once(ComponentTemplate { is_active: true, ..template.clone() })
+                .chain(props.component_templates.iter().cloned())
+                .collect();
+            (component_style(&template.component_type, &template.template_data), global_style_vars(&templates))
+        }
+        None => (String::new(), String::new()),
+    };
+
     html! {
         <div class="component-templates-section">
             <h2>{"Component Templates"}</h2>
@@ -1609,12 +1638,49 @@ pub fn component_templates_view(props: &ComponentTemplatesViewProps) -> Html {
                                                     </div>
                                                     <div class="property-item">
                                                         <label>{"Copyright Text"}</label>
-                                                        <input type="text" value="© 2024 My Rust CMS" class="property-input" />
+                                                        <input 
+                                                            type="text" 
+                                                            class="property-input"
+                                                            value={{
+                                                                (*editing_template)
+                                                                    .as_ref()
+                                                                    .and_then(|t| t.template_data.get("copyright_text").and_then(|v| v.as_str()))
+                                                                    .unwrap_or("© {year} {site_title}")
+                                                                    .to_string()
+                                                            }}
+                                                            onchange={{
+                                                                let update_template_data = update_template_data.clone();
+                                                                Callback::from(move |e: Event| {
+                                                                    if let Some(target) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                                                                        update_template_data.emit(("copyright_text".to_string(), serde_json::Value::String(target.value())));
+                                                                    }
+                                                                })
+                                                            }}
+                                                        />
                                                     </div>
                                                     <div class="property-item">
-                                                        <label>{"Additional Text"}</label>
-                                                        <input type="text" value="Built with Rust & Yew" class="property-input" />
+                                                        <label>{"Additional (Powered By) Text"}</label>
+                                                        <input 
+                                                            type="text" 
+                                                            class="property-input"
+                                                            value={{
+                                                                (*editing_template)
+                                                                    .as_ref()
+                                                                    .and_then(|t| t.template_data.get("additional_text").and_then(|v| v.as_str()))
+                                                                    .unwrap_or("Built with Rust and Yew")
+                                                                    .to_string()
+                                                            }}
+                                                            onchange={{
+                                                                let update_template_data = update_template_data.clone();
+                                                                Callback::from(move |e: Event| {
+                                                                    if let Some(target) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                                                                        update_template_data.emit(("additional_text".to_string(), serde_json::Value::String(target.value())));
+                                                                    }
+                                                                })
+                                                            }}
+                                                        />
                                                     </div>
+                                                    <small class="form-help">{"{year} and {site_title} are filled in; [label](url) makes a link. Leave a line empty to hide it. The powered-by line can also be hidden under System Settings."}</small>
                                                 </div>
                                             </>
                                         },
@@ -1892,10 +1958,10 @@ pub fn component_templates_view(props: &ComponentTemplatesViewProps) -> Html {
                                 
                                 <div class="editor-preview">
                                     <h4>{"Live Preview"}</h4>
-                                    <div class="preview-container">
+                                    <div class="preview-container" style={preview_vars.clone()}>
                                         {match component_id.as_str() {
                                             "header" => html! {
-                                                <div class="preview-header">
+                                                <div class="preview-header" style={preview_style.clone()}>
                                                     <div class="preview-nav">
                                                         <span class="preview-logo">{"🏠 My Site"}</span>
                                                         <div class="preview-menu">
@@ -1923,7 +1989,7 @@ pub fn component_templates_view(props: &ComponentTemplatesViewProps) -> Html {
                                                 </div>
                                             },
                                             "footer" => html! {
-                                                <div class="preview-footer">
+                                                <div class="preview-footer" style={preview_style.clone()}>
                                                     <div class="preview-footer-content">
                                                         <div class="preview-footer-nav">
                                                             {
@@ -1946,10 +2012,33 @@ pub fn component_templates_view(props: &ComponentTemplatesViewProps) -> Html {
                                                                 }
                                                             }
                                                         </div>
-                                                        <p class="preview-footer-text">{"© 2024 My Rust CMS - Built with Rust & Yew"}</p>
+                                                        <p class="preview-footer-text">{{
+                                                            let text = |key: &str, default: &str| (*editing_template)
+                                                                .as_ref()
+                                                                .and_then(|t| t.template_data.get(key).and_then(|v| v.as_str()))
+                                                                .unwrap_or(default)
+                                                                .trim()
+                                                                .to_string();
+                                                            [text("copyright_text", "© {year} {site_title}"), text("additional_text", "Built with Rust and Yew")]
+                                                                .into_iter()
+                                                                .filter(|line| !line.is_empty())
+                                                                .collect::<Vec<_>>()
+                                                                .join(" - ")
+                                                        }}</p>
                                                     </div>
                                                 </div>
                                             },
+                                            "main_container" | "posts_list" => html! {
+                                                <div class="preview-main-container">
+                                                    {for ["First post", "Second post"].into_iter().map(|title| html! {
+                                                        <div class="preview-post-card">
+                                                            <span class="preview-post-title">{title}</span>
+                                                            <span class="preview-post-meta">{"Posted today"}</span>
+                                                            <span class="preview-post-link">{"Read more"}</span>
+                                                        </div>
+                                                    })}
+                                                </div>
+                                            },
                                             _ => html! {
                                                 <div class="preview-placeholder">
                                                     <p>{format!("{} Component Preview", component_id)}</p>
@@ -1996,6 +2085,8 @@ pub fn component_templates_view(props: &ComponentTemplatesViewProps) -> Html {
                     let editing_component = editing_component.clone();
                     let editing_template = editing_template.clone();
                     let on_template_toggled = props.on_template_toggled.clone();
+                    let all_templates = props.component_templates.clone();
+                    let toast = toast.clone();
                     
                     html! {
                         <div class={format!("component-card {}", if template.is_default { "primary" } else { "secondary" })}>
@@ -2034,18 +2125,31 @@ pub fn component_templates_view(props: &ComponentTemplatesViewProps) -> Html {
                                                         id={format!("toggle-{}", template.id)}
                                                         checked={template.is_active}
                                                         onchange={{
-                                                            let template_id = template.id;
+                                                            let template = template.clone();
                                                             let on_template_toggled = on_template_toggled.clone();
                                                             Callback::from(move |_| {
+                                                                // Show the new state right away, then settle on the server's answer
+                                                                let previous: Vec<ComponentTemplate> = all_templates.iter()
+                                                                    .filter(|t| t.component_type == template.component_type)
+                                                                    .cloned()
+                                                                    .collect();
+                                                                let flipped = ComponentTemplate { is_active: !template.is_active, ..template.clone() };
+                                                                on_template_toggled.emit(with_toggled_template(&previous, &flipped));
+
+                                                                let template_id = template.id;
+                                                                let template_name = template.name.clone();
                                                                 let on_template_toggled = on_template_toggled.clone();
+                                                                let toast = toast.clone();
                                                                 wasm_bindgen_futures::spawn_local(async move {
                                                                     match toggle_component_template(template_id).await {
                                                                         Ok(updated_template) => {
-                                                                            on_template_toggled.emit(updated_template);
+                                                                            on_template_toggled.emit(with_toggled_template(&previous, &updated_template));
                                                                             log::info!("✅ Toggled component template {}", template_id);
                                                                         }
                                                                         Err(e) => {
                                                                             log::error!("❌ Failed to toggle component template: {:?}", e);
+                                                                            on_template_toggled.emit(previous);
+                                                                            toast.error(format!("Failed to toggle {}: {:?}", template_name, e));
                                                                         }
                                                                     }
                                                                 });
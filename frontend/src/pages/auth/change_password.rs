@@ -0,0 +1,31 @@
+use yew::prelude::*;
+use crate::components::change_password_form::ChangePasswordForm;
+use crate::services::auth_context::use_auth;
+
+#[derive(Properties, PartialEq)]
+pub struct ChangePasswordProps {
+    pub on_success: Callback<()>,
+}
+
+#[function_component(ChangePasswordPage)]
+pub fn change_password_page(props: &ChangePasswordProps) -> Html {
+    let auth = use_auth();
+    let required = auth.user.as_ref().is_some_and(|user| user.must_change_password);
+
+    html! {
+        <div class="auth-page">
+            <div class="auth-container">
+                <div class="auth-header">
+                    <h1>{"Change Password"}</h1>
+                    <p>{"Choose a new password for your account"}</p>
+                </div>
+
+                if required {
+                    <div class="info-message">{"An administrator has asked you to change your password before continuing."}</div>
+                }
+
+                <ChangePasswordForm on_success={props.on_success.clone()} />
+            </div>
+        </div>
+    }
+}
@@ -1,6 +1,8 @@
 use yew::prelude::*;
 use crate::services::auth_service::{LoginCredentials, AuthError};
-use crate::services::auth_context::{use_auth, login_and_update_context};
+use crate::services::auth_context::{use_auth, login_and_update_context, remember_return_path, session_expired_from_location};
+use crate::components::captcha::{Captcha, CaptchaSettings, load_captcha_settings, load_login_failure_threshold};
+use crate::components::{RateLimitNotice, FALLBACK_RETRY_AFTER_SECS};
 
 #[derive(Properties, PartialEq)]
 pub struct LoginProps {
@@ -13,6 +15,40 @@ pub fn login_page(props: &LoginProps) -> Html {
     let username = use_state(String::new);
     let password = use_state(String::new);
     let error = use_state(|| None::<String>);
+    let session_expired = use_state(session_expired_from_location);
+    let captcha_settings = use_state(|| None::<CaptchaSettings>);
+    let failure_threshold = use_state(|| 3u32);
+    // Failed attempts in this form; the server keeps its own count
+    let failures = use_state(|| 0u32);
+    let captcha_demanded = use_state(|| false);
+    let captcha_token = use_state(|| None::<String>);
+    // Bumped after each attempt so a fresh widget replaces the used token
+    let captcha_key = use_state(|| 0u32);
+    // Seconds the server asked us to wait after a 429, and a key per refusal
+    let rate_limited = use_state(|| None::<u32>);
+    let rate_limit_key = use_state(|| 0u32);
+
+    {
+        let captcha_settings = captcha_settings.clone();
+        let failure_threshold = failure_threshold.clone();
+        use_effect_with_deps(move |_| {
+            remember_return_path();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Some(settings) = load_captcha_settings().await {
+                    failure_threshold.set(load_login_failure_threshold().await);
+                    captcha_settings.set(Some(settings));
+                }
+            });
+            || ()
+        }, ());
+    }
+
+    let show_captcha = captcha_settings.is_some() && (*captcha_demanded || *failures >= *failure_threshold);
+
+    let on_captcha_token = {
+        let captcha_token = captcha_token.clone();
+        Callback::from(move |token: Option<String>| captcha_token.set(token))
+    };
 
     let on_username_change = {
         let username = username.clone();
@@ -34,27 +70,51 @@ pub fn login_page(props: &LoginProps) -> Html {
         let username = username.clone();
         let password = password.clone();
         let error = error.clone();
+        let session_expired = session_expired.clone();
         let auth = auth.clone();
         let on_login_success = props.on_login_success.clone();
+        let failures = failures.clone();
+        let captcha_demanded = captcha_demanded.clone();
+        let captcha_token = captcha_token.clone();
+        let captcha_key = captcha_key.clone();
+        let rate_limited = rate_limited.clone();
+        let rate_limit_key = rate_limit_key.clone();
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
+
+            if rate_limited.is_some() {
+                return;
+            }
             
             if username.is_empty() || password.is_empty() {
                 error.set(Some("Please fill in all fields".to_string()));
                 return;
             }
 
+            if show_captcha && captcha_token.is_none() {
+                error.set(Some("Please complete the CAPTCHA".to_string()));
+                return;
+            }
+
             let credentials = LoginCredentials {
                 username: (*username).clone(),
                 password: (*password).clone(),
+                captcha_token: (*captcha_token).clone().filter(|_| show_captcha),
             };
 
             let error = error.clone();
             let auth = auth.clone();
             let on_login_success = on_login_success.clone();
+            let failures = failures.clone();
+            let captcha_demanded = captcha_demanded.clone();
+            let captcha_token = captcha_token.clone();
+            let captcha_key = captcha_key.clone();
+            let rate_limited = rate_limited.clone();
+            let rate_limit_key = rate_limit_key.clone();
 
             error.set(None);
+            session_expired.set(false);
 
             wasm_bindgen_futures::spawn_local(async move {
                 match login_and_update_context(&auth, &credentials).await {
@@ -63,10 +123,26 @@ pub fn login_page(props: &LoginProps) -> Html {
                     }
                     Err(e) => {
                         let error_msg = match e {
-                            AuthError::InvalidCredentials => "Invalid username or password".to_string(),
-                            _ => format!("Login failed: {}", e),
+                            AuthError::InvalidCredentials => {
+                                failures.set(*failures + 1);
+                                Some("Invalid username or password".to_string())
+                            }
+                            AuthError::CaptchaRequired(message) => {
+                                captcha_demanded.set(true);
+                                Some(message)
+                            }
+                            AuthError::RateLimited { retry_after, .. } => {
+                                rate_limited.set(Some(retry_after.unwrap_or(FALLBACK_RETRY_AFTER_SECS)));
+                                rate_limit_key.set(*rate_limit_key + 1);
+                                None
+                            }
+                            _ => Some(format!("Login failed: {}", e)),
                         };
-                        error.set(Some(error_msg));
+                        error.set(error_msg);
+                        if show_captcha {
+                            captcha_token.set(None);
+                            captcha_key.set(*captcha_key + 1);
+                        }
                     }
                 }
             });
@@ -81,10 +157,22 @@ pub fn login_page(props: &LoginProps) -> Html {
                     <p>{"Sign in to access the admin panel"}</p>
                 </div>
 
+                if *session_expired {
+                    <div class="info-message">{"Your session has expired. Please sign in again."}</div>
+                }
+
                 if let Some(ref error_msg) = *error {
                     <div class="error-message">{"Error: "}{error_msg}</div>
                 }
 
+                if let Some(retry_after) = *rate_limited {
+                    <RateLimitNotice
+                        key={*rate_limit_key}
+                        retry_after={retry_after}
+                        on_expire={let rate_limited = rate_limited.clone(); Callback::from(move |_| rate_limited.set(None))}
+                    />
+                }
+
                 <form class="auth-form" onsubmit={on_submit}>
                     <div class="form-group">
                         <label for="username">{"Username"}</label>
@@ -110,10 +198,16 @@ pub fn login_page(props: &LoginProps) -> Html {
                         />
                     </div>
 
+                    if let (true, Some(settings)) = (show_captcha, (*captcha_settings).clone()) {
+                        <div class="form-group">
+                            <Captcha key={*captcha_key} settings={settings} on_token={on_captcha_token} />
+                        </div>
+                    }
+
                     <button 
                         type="submit" 
                         class="btn btn-primary" 
-                        disabled={auth.loading}
+                        disabled={auth.loading || rate_limited.is_some()}
                     >
                         if auth.loading {
                             {"Signing in..."}
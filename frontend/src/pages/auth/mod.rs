@@ -1,7 +1,9 @@
+pub mod change_password;
 pub mod login;
 pub mod signup;
 pub mod verify_email;
 
+pub use change_password::ChangePasswordPage;
 pub use login::Login;
 pub use signup::SignupPage;
 pub use verify_email::VerifyEmailPage; 
\ No newline at end of file
@@ -1,7 +1,9 @@
 use yew::prelude::*;
 use web_sys::HtmlInputElement;
-use crate::services::auth_service::{signup, SignupCredentials, AuthError};
+use crate::services::auth_service::{signup, get_signup_policy, SignupCredentials, AuthError};
 use crate::components::simple_notification::SimpleNotification;
+use crate::components::captcha::{Captcha, CaptchaSettings, load_captcha_settings};
+use crate::components::{RateLimitNotice, FALLBACK_RETRY_AFTER_SECS};
 
 #[derive(Clone, PartialEq)]
 pub enum NotificationType {
@@ -10,15 +12,57 @@ pub enum NotificationType {
     Info,
 }
 
+/// Invite token from the `invite` query parameter of the signup link
+fn invite_from_url() -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search.trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("invite="))
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+}
+
 #[function_component]
 pub fn SignupPage() -> Html {
     let username_ref = use_node_ref();
     let email_ref = use_node_ref();
     let password_ref = use_node_ref();
     let confirm_password_ref = use_node_ref();
+    let website_ref = use_node_ref();
+    // Signed by the server when it handed out the form, so it can tell
+    // people from instant bot submissions
+    let form_token = use_state(|| None::<String>);
     
     let notification = use_state(|| None::<(String, NotificationType)>);
     let is_loading = use_state(|| false);
+    // Server-side error for one field: (field, message)
+    let field_error = use_state(|| None::<(String, String)>);
+    let invite_token = use_state(invite_from_url);
+    // `open`, `invite_only` or `closed`; assume open until the server says otherwise
+    let signup_mode = use_state(|| "open".to_string());
+    let captcha_settings = use_state(|| None::<CaptchaSettings>);
+    let captcha_token = use_state(|| None::<String>);
+    // Bumped after each attempt so a fresh widget replaces the used token
+    let captcha_key = use_state(|| 0u32);
+    // Seconds the server asked us to wait after a 429, and a key per refusal
+    let rate_limited = use_state(|| None::<u32>);
+    let rate_limit_key = use_state(|| 0u32);
+
+    {
+        let signup_mode = signup_mode.clone();
+        let form_token = form_token.clone();
+        let captcha_settings = captcha_settings.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Ok(policy) = get_signup_policy().await {
+                    signup_mode.set(policy.mode);
+                    form_token.set(policy.form_token);
+                }
+                captcha_settings.set(load_captcha_settings().await);
+            });
+            || ()
+        }, ());
+    }
 
     let clear_notification = {
         let notification = notification.clone();
@@ -34,9 +78,22 @@ pub fn SignupPage() -> Html {
         let confirm_password_ref = confirm_password_ref.clone();
         let notification = notification.clone();
         let is_loading = is_loading.clone();
+        let field_error = field_error.clone();
+        let invite_token = invite_token.clone();
+        let website_ref = website_ref.clone();
+        let form_token = form_token.clone();
+        let captcha_enabled = captcha_settings.is_some();
+        let captcha_token = captcha_token.clone();
+        let captcha_key = captcha_key.clone();
+        let rate_limited = rate_limited.clone();
+        let rate_limit_key = rate_limit_key.clone();
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
+
+            if rate_limited.is_some() {
+                return;
+            }
             
             let username_input = username_ref.cast::<HtmlInputElement>().unwrap();
             let email_input = email_ref.cast::<HtmlInputElement>().unwrap();
@@ -69,16 +126,31 @@ pub fn SignupPage() -> Html {
                 return;
             }
 
+            if captcha_enabled && captcha_token.is_none() {
+                notification.set(Some(("Please complete the CAPTCHA".to_string(), NotificationType::Error)));
+                return;
+            }
+
             let credentials = SignupCredentials {
                 username: username.clone(),
                 email: email.clone(),
                 password: password.clone(),
+                invite_token: (*invite_token).clone(),
+                website: website_ref.cast::<HtmlInputElement>().map(|input| input.value()).unwrap_or_default(),
+                form_token: (*form_token).clone(),
+                captcha_token: (*captcha_token).clone(),
             };
 
             let notification = notification.clone();
             let is_loading = is_loading.clone();
+            let field_error = field_error.clone();
+            let captcha_token = captcha_token.clone();
+            let captcha_key = captcha_key.clone();
+            let rate_limited = rate_limited.clone();
+            let rate_limit_key = rate_limit_key.clone();
             
             is_loading.set(true);
+            field_error.set(None);
             
             wasm_bindgen_futures::spawn_local(async move {
                 match signup(&credentials).await {
@@ -94,18 +166,47 @@ pub fn SignupPage() -> Html {
                         password_input.set_value("");
                         confirm_password_input.set_value("");
                     }
-                    Err(AuthError::ServerError(msg)) => {
+                    Err(AuthError::FieldError { field, message }) => {
+                        field_error.set(Some((field, message)));
+                    }
+                    Err(AuthError::RateLimited { retry_after, .. }) => {
+                        rate_limited.set(Some(retry_after.unwrap_or(FALLBACK_RETRY_AFTER_SECS)));
+                        rate_limit_key.set(*rate_limit_key + 1);
+                    }
+                    Err(AuthError::ServerError(msg)) | Err(AuthError::CaptchaRequired(msg)) => {
                         notification.set(Some((msg, NotificationType::Error)));
                     }
                     Err(e) => {
                         notification.set(Some((format!("Signup failed: {}", e), NotificationType::Error)));
                     }
                 }
+                if captcha_enabled {
+                    captcha_token.set(None);
+                    captcha_key.set(*captcha_key + 1);
+                }
                 is_loading.set(false);
             });
         })
     };
 
+    let on_captcha_token = {
+        let captcha_token = captcha_token.clone();
+        Callback::from(move |token: Option<String>| captcha_token.set(token))
+    };
+
+    let error_for = |field: &str| -> Html {
+        match &*field_error {
+            Some((name, message)) if name == field => html! { <div class="field-error">{message}</div> },
+            _ => html! {},
+        }
+    };
+
+    let closed_message = match signup_mode.as_str() {
+        "closed" => Some("Signups are currently closed."),
+        "invite_only" if invite_token.is_none() => Some("Signups are by invitation only. Use the link from your invite to create an account."),
+        _ => None,
+    };
+
     html! {
         <div class="auth-page">
             <div class="auth-container">
@@ -134,68 +235,109 @@ pub fn SignupPage() -> Html {
                     }
                 }
 
-                <form onsubmit={on_submit} class="auth-form">
-                    <div class="form-group">
-                        <label for="username">{"Username"}</label>
-                        <input
-                            ref={username_ref}
-                            type="text"
-                            id="username"
-                            name="username"
-                            placeholder="Enter your username"
-                            required=true
-                            disabled={*is_loading}
-                        />
-                    </div>
-
-                    <div class="form-group">
-                        <label for="email">{"Email"}</label>
-                        <input
-                            ref={email_ref}
-                            type="email"
-                            id="email"
-                            name="email"
-                            placeholder="Enter your email address"
-                            required=true
-                            disabled={*is_loading}
-                        />
-                    </div>
-
-                    <div class="form-group">
-                        <label for="password">{"Password"}</label>
-                        <input
-                            ref={password_ref}
-                            type="password"
-                            id="password"
-                            name="password"
-                            placeholder="Enter your password"
-                            required=true
-                            disabled={*is_loading}
-                        />
-                        <small class="help-text">{"Password must be at least 6 characters long"}</small>
-                    </div>
-
-                    <div class="form-group">
-                        <label for="confirm-password">{"Confirm Password"}</label>
-                        <input
-                            ref={confirm_password_ref}
-                            type="password"
-                            id="confirm-password"
-                            name="confirm-password"
-                            placeholder="Confirm your password"
-                            required=true
-                            disabled={*is_loading}
-                        />
-                    </div>
-
-                    <button 
-                        type="submit" 
-                        class="btn btn-primary btn-full-width"
-                        disabled={*is_loading}
-                    >
-                        {if *is_loading { "Creating Account..." } else { "Create Account" }}
-                    </button>
-                </form>
+                {
+                    if let Some(message) = closed_message {
+                        html! {
+                            <div class="signup-closed">
+                                <p>{message}</p>
+                            </div>
+                        }
+                    } else {
+                        html! {
+                        <form onsubmit={on_submit} class="auth-form">
+                            <div class="form-group">
+                                <label for="username">{"Username"}</label>
+                                <input
+                                    ref={username_ref}
+                                    type="text"
+                                    id="username"
+                                    name="username"
+                                    placeholder="Enter your username"
+                                    required=true
+                                    disabled={*is_loading}
+                                />
+                                {error_for("username")}
+                            </div>
+
+                            <div class="form-group">
+                                <label for="email">{"Email"}</label>
+                                <input
+                                    ref={email_ref}
+                                    type="email"
+                                    id="email"
+                                    name="email"
+                                    placeholder="Enter your email address"
+                                    required=true
+                                    disabled={*is_loading}
+                                />
+                                {error_for("email")}
+                            </div>
+
+                            <div class="form-group">
+                                <label for="password">{"Password"}</label>
+                                <input
+                                    ref={password_ref}
+                                    type="password"
+                                    id="password"
+                                    name="password"
+                                    placeholder="Enter your password"
+                                    required=true
+                                    disabled={*is_loading}
+                                />
+                                <small class="help-text">{"Password must be at least 6 characters long"}</small>
+                            </div>
+
+                            <div class="form-group">
+                                <label for="confirm-password">{"Confirm Password"}</label>
+                                <input
+                                    ref={confirm_password_ref}
+                                    type="password"
+                                    id="confirm-password"
+                                    name="confirm-password"
+                                    placeholder="Confirm your password"
+                                    required=true
+                                    disabled={*is_loading}
+                                />
+                            </div>
+
+                            // Honeypot: off-screen and hidden from assistive tech, so only bots fill it in
+                            <div class="hp-field" aria-hidden="true">
+                                <label for="website">{"Website"}</label>
+                                <input
+                                    ref={website_ref}
+                                    type="text"
+                                    id="website"
+                                    name="website"
+                                    tabindex="-1"
+                                    autocomplete="off"
+                                />
+                            </div>
+
+                            if let Some(settings) = (*captcha_settings).clone() {
+                                <div class="form-group">
+                                    <Captcha key={*captcha_key} settings={settings} on_token={on_captcha_token.clone()} />
+                                </div>
+                            }
+
+                            if let Some(retry_after) = *rate_limited {
+                                <RateLimitNotice
+                                    key={*rate_limit_key}
+                                    retry_after={retry_after}
+                                    on_expire={let rate_limited = rate_limited.clone(); Callback::from(move |_| rate_limited.set(None))}
+                                />
+                            }
+
+                            <button 
+                                type="submit" 
+                                class="btn btn-primary btn-full-width"
+                                disabled={*is_loading || rate_limited.is_some()}
+                            >
+                                {if *is_loading { "Creating Account..." } else { "Create Account" }}
+                            </button>
+                        </form>
+                        }
+                    }
+                }
 
                 <div class="auth-footer">
                     <p>
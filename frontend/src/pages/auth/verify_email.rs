@@ -1,6 +1,6 @@
 use yew::prelude::*;
 use web_sys::window;
-use crate::services::auth_service::{verify_email, AuthError};
+use crate::services::auth_service::{verify_email, resend_verification, AuthError};
 // Notification not used in this component
 use std::collections::HashMap;
 
@@ -15,6 +15,8 @@ pub enum NotificationType {
 pub enum VerificationState {
     Loading,
     Success,
+    /// The link was valid but too old; the user can ask for a new one
+    Expired,
     Error(String),
 }
 
@@ -45,12 +47,16 @@ pub fn VerifyEmailPage() -> Html {
         None
     });
 
+    let resend_email_ref = use_node_ref();
+    let resending = use_state(|| false);
+    let resend_message = use_state(|| None::<String>);
+
     // Verify email on component mount
     {
         let verification_state = verification_state.clone();
         let token = token.clone();
         
-        use_effect(move || {
+        use_effect_with_deps(move |_| {
             if let Some(token) = (*token).clone() {
                 let verification_state = verification_state.clone();
                 wasm_bindgen_futures::spawn_local(async move {
@@ -58,6 +64,9 @@ pub fn VerifyEmailPage() -> Html {
                         Ok(_) => {
                             verification_state.set(VerificationState::Success);
                         }
+                        Err(AuthError::ServerError(msg)) if msg.contains("Verification token has expired") => {
+                            verification_state.set(VerificationState::Expired);
+                        }
                         Err(AuthError::ServerError(msg)) => {
                             verification_state.set(VerificationState::Error(msg));
                         }
@@ -69,9 +78,38 @@ pub fn VerifyEmailPage() -> Html {
             } else {
                 verification_state.set(VerificationState::Error("No verification token provided".to_string()));
             }
-        });
+            || ()
+        }, ());
     }
 
+    let on_resend = {
+        let resend_email_ref = resend_email_ref.clone();
+        let resending = resending.clone();
+        let resend_message = resend_message.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let Some(input) = resend_email_ref.cast::<web_sys::HtmlInputElement>() else {
+                return;
+            };
+            let email = input.value().trim().to_string();
+            if email.is_empty() {
+                return;
+            }
+
+            let resending = resending.clone();
+            let resend_message = resend_message.clone();
+            resending.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let message = match resend_verification(&email).await {
+                    Ok(message) => message,
+                    Err(e) => format!("Couldn't send a new link: {}", e),
+                };
+                resend_message.set(Some(message));
+                resending.set(false);
+            });
+        })
+    };
+
     html! {
         <div class="auth-page">
             <div class="auth-container">
@@ -99,6 +137,42 @@ pub fn VerifyEmailPage() -> Html {
                                     </div>
                                 </div>
                             },
+                            VerificationState::Expired => html! {
+                                <div class="error-state">
+                                    <div class="error-icon">{"✗"}</div>
+                                    <h2>{"Verification Link Expired"}</h2>
+                                    <p>{"This link is no longer valid. Enter the email address you signed up with and we'll send you a new one."}</p>
+                                    {
+                                        if let Some(message) = &*resend_message {
+                                            html! { <p class="help-text">{message.clone()}</p> }
+                                        } else {
+                                            html! {
+                                                <form onsubmit={on_resend} class="auth-form">
+                                                    <div class="form-group">
+                                                        <label for="resend-email">{"Email"}</label>
+                                                        <input
+                                                            ref={resend_email_ref.clone()}
+                                                            type="email"
+                                                            id="resend-email"
+                                                            name="email"
+                                                            placeholder="Enter your email address"
+                                                            required=true
+                                                            disabled={*resending}
+                                                        />
+                                                    </div>
+                                                    <button
+                                                        type="submit"
+                                                        class="btn btn-primary btn-full-width"
+                                                        disabled={*resending}
+                                                    >
+                                                        {if *resending { "Sending..." } else { "Resend Verification Email" }}
+                                                    </button>
+                                                </form>
+                                            }
+                                        }
+                                    }
+                                </div>
+                            },
                             VerificationState::Error(message) => html! {
                                 <div class="error-state">
                                     <div class="error-icon">{"✗"}</div>
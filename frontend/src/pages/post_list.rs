@@ -76,7 +76,12 @@ pub fn post_list() -> Html {
                     author: (*author).clone(),
                     status: (*status).clone(),
                     category_id: None,
+                    category_name: None,
                     created_at: None,
+                    published_at: None,
+                    comment_count: None,
+                    excerpt: None,
+                    structured_data: None,
                 };
 
                 match create_post(&new_post).await {
@@ -139,7 +144,12 @@ pub fn post_list() -> Html {
                         author: (*author).clone(),
                         status: (*status).clone(),
                         category_id: post.category_id,
+                        category_name: post.category_name,
                         created_at: post.created_at,
+                        published_at: post.published_at,
+                        comment_count: None,
+                        excerpt: None,
+                        structured_data: None,
                     };
 
                     if let Some(id) = post.id {
@@ -1,4 +1,5 @@
 use yew::prelude::*;
+use crate::services::timezone_context::use_display_timezone;
 use crate::services::api_service::{get_post, Post as PostData};
 
 #[derive(Properties, PartialEq)]
@@ -7,16 +8,6 @@ pub struct PostProps {
     pub id: String,
 }
 
-#[allow(dead_code)]
-fn format_date(date_str: &str) -> String {
-    // Try to parse and format the date nicely
-    if let Ok(naive_datetime) = chrono::NaiveDateTime::parse_from_str(date_str, "%Y-%m-%d %H:%M:%S") {
-        naive_datetime.format("%B %d, %Y at %I:%M %p").to_string()
-    } else {
-        date_str.to_string()
-    }
-}
-
 #[allow(dead_code)]
 fn format_content_as_markdown(content: &str) -> Html {
     // For now, we'll just handle basic paragraph breaks
@@ -38,6 +29,7 @@ fn format_content_as_markdown(content: &str) -> Html {
 #[function_component(Post)]
 pub fn post(props: &PostProps) -> Html {
     let post_data = use_state(|| None::<PostData>);
+    let timezone = use_display_timezone();
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
 
@@ -114,7 +106,7 @@ pub fn post(props: &PostProps) -> Html {
                                 <p class="post-meta">
                                     {"By "}{&post.author}
                                     {if let Some(ref date) = post.created_at {
-                                        html! { <>{" • "}{format_date(date)}</> }
+                                        html! { <>{" • "}{timezone.datetime(date)}</> }
                                     } else {
                                         html! {}
                                     }}
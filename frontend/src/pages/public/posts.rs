@@ -1,16 +1,7 @@
 use yew::prelude::*;
+use crate::components::RelativeTime;
 use crate::services::api_service::get_posts;
 
-#[allow(dead_code)]
-fn format_date(date_str: &str) -> String {
-    // Try to parse and format the date nicely
-    if let Ok(naive_datetime) = chrono::NaiveDateTime::parse_from_str(date_str, "%Y-%m-%d %H:%M:%S") {
-        naive_datetime.format("%B %d, %Y").to_string()
-    } else {
-        date_str.to_string()
-    }
-}
-
 #[allow(dead_code)]
 fn truncate_content(content: &str, max_length: usize) -> String {
     if content.len() <= max_length {
@@ -102,10 +93,11 @@ pub fn posts() -> Html {
                     } else {
                         <div class="posts-grid">
                             {posts.iter().map(|post| {
-                                let formatted_date = post.created_at.as_deref()
-                                    .map(format_date)
-                                    .unwrap_or_else(|| "Recent".to_string());
-                                
+                                let formatted_date = match post.created_at.clone() {
+                                    Some(created_at) => html! { <RelativeTime value={created_at} date_only=true /> },
+                                    None => html! { {"Recent"} },
+                                };
+
                                 let excerpt = truncate_content(&post.content, 200);
                                 
                                 html! {
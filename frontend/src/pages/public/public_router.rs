@@ -1,9 +1,15 @@
 use yew::prelude::*;
-use crate::components::{PublicLayout, PostsListWidget, CommentsSection};
-use crate::services::page_service::{get_page_by_slug, Page};
+use crate::components::{PublicLayout, PostsListWidget, CommentsSection, MarkdownContent, TableOfContents};
+use crate::components::markdown_content::table_of_contents;
+use crate::components::table_of_contents::MIN_TOC_ENTRIES;
+use crate::services::page_service::{get_homepage, get_page_by_slug, get_page_preview, Page};
+use crate::services::preview_service::{preview_token_from_location, share_token_from_location};
 use crate::components::page_builder::{PageComponent, ComponentType};
 use crate::services::default_pages::{get_default_home_page_components, get_default_posts_page_components};
 use crate::services::navigation_service::check_comments_enabled;
+use crate::services::api_service::ApiError;
+use crate::services::timezone_context::use_display_timezone;
+use crate::services::csp::style_nonce;
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum PublicPage {
@@ -82,6 +88,14 @@ fn home_content(_props: &HomeContentProps) -> Html {
 
         use_effect_with_deps(move |_| {
             wasm_bindgen_futures::spawn_local(async move {
+                // A page chosen in the settings replaces the post list home
+                if let Ok(homepage) = get_homepage().await {
+                    if let Some(home_page) = homepage.page.filter(|_| homepage.mode == "page") {
+                        page.set(Some(home_page));
+                        loading.set(false);
+                        return;
+                    }
+                }
                 match get_page_by_slug("home").await {
                     Ok(fetched_page) => {
                         page.set(Some(fetched_page));
@@ -198,19 +212,34 @@ struct PostContentProps {
 #[function_component(PostContent)]
 fn post_content(props: &PostContentProps) -> Html {
     let post = use_state(|| None::<crate::services::api_service::Post>);
+    let timezone = use_display_timezone();
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
+    let preview_token = use_memo(|_| preview_token_from_location(), props.post_id);
+    let share_token = use_memo(|_| share_token_from_location(), props.post_id);
+    let toc = use_memo(
+        |content: &String| table_of_contents(content),
+        post.as_ref().map(|p| p.content.clone()).unwrap_or_default(),
+    );
+    let show_toc = toc.len() >= MIN_TOC_ENTRIES;
 
     {
         let post = post.clone();
         let loading = loading.clone();
         let error = error.clone();
         let post_id = props.post_id;
+        let preview_token = (*preview_token).clone();
+        let share_token = (*share_token).clone();
 
         use_effect_with_deps(move |_| {
             wasm_bindgen_futures::spawn_local(async move {
                 web_sys::console::log_1(&format!("PostContent: Loading post with ID = {}", post_id).into());
-                match crate::services::api_service::get_post(post_id).await {
+                let result = match (&share_token, &preview_token) {
+                    (Some(token), _) => crate::services::api_service::get_shared_post_preview(post_id, token).await,
+                    (None, Some(token)) => crate::services::api_service::get_post_preview(post_id, token).await,
+                    (None, None) => crate::services::api_service::get_post(post_id).await,
+                };
+                match result {
                     Ok(fetched_post) => {
                         web_sys::console::log_1(&format!("PostContent: Post loaded successfully: {:?}", fetched_post.title).into());
                         post.set(Some(fetched_post));
@@ -218,11 +247,13 @@ fn post_content(props: &PostContentProps) -> Html {
                     }
                     Err(e) => {
                         web_sys::console::log_1(&format!("PostContent: Error loading post: {:?}", e).into());
-                        let error_message = match e.to_string().as_str() {
-                            msg if msg.contains("404") || msg.contains("Not Found") => {
+                        let error_message = match e {
+                            // An expired or used up share link says so
+                            ApiError::NotFound(message) if share_token.is_some() => message,
+                            ApiError::NotFound(_) => {
                                 format!("Post not found. The post with ID {} may have been deleted or doesn't exist.", post_id)
                             }
-                            msg if msg.contains("NetworkError") => {
+                            ApiError::Network(_) => {
                                 "Unable to connect to the server. Please check your internet connection and try again.".to_string()
                             }
                             _ => format!("Failed to load post: {}", e)
@@ -236,6 +267,26 @@ fn post_content(props: &PostContentProps) -> Html {
         }, post_id);
     }
 
+    // Search engines read the post's JSON-LD from the document head
+    {
+        let structured_data = post.as_ref().and_then(|p| p.structured_data.clone());
+        use_effect_with_deps(move |structured_data| {
+            let script = structured_data.as_ref().and_then(|data| {
+                let document = web_sys::window()?.document()?;
+                let script = document.create_element("script").ok()?;
+                script.set_attribute("type", "application/ld+json").ok()?;
+                script.set_text_content(Some(&data.to_string()));
+                document.head()?.append_child(&script).ok()?;
+                Some(script)
+            });
+            move || {
+                if let Some(script) = script {
+                    script.remove();
+                }
+            }
+        }, structured_data);
+    }
+
     html! {
         <div class="post-detail">
             if *loading {
@@ -244,16 +295,26 @@ fn post_content(props: &PostContentProps) -> Html {
                 <div class="error">{"Error loading post: "}{error_msg}</div>
             } else if let Some(ref post_data) = *post {
                 <>
+                    if preview_token.is_some() && post_data.status != "published" {
+                        <div class="preview-banner">{"Preview — this post is not published yet"}</div>
+                    }
                     <h1>{post_data.title.clone()}</h1>
                     <div class="post-meta">
                         <span class="post-author">{"By "}{post_data.author.clone()}</span>
                         if let Some(ref created_at) = post_data.created_at {
-                            <span class="post-date">{" • "}{created_at.clone()}</span>
+                            <span class="post-date">{" • "}{timezone.datetime(created_at)}</span>
                         }
                         <span class="post-status">{" • "}{post_data.status.clone()}</span>
                     </div>
-                    <div class="post-content">
-                        <p>{post_data.content.clone()}</p>
+                    <div class={classes!("post-body", show_toc.then_some("with-toc"))}>
+                        <div class="post-content">
+                            <MarkdownContent content={post_data.content.clone()} />
+                        </div>
+                        if show_toc {
+                            <aside class="post-toc">
+                                <TableOfContents entries={(*toc).clone()} />
+                            </aside>
+                        }
                     </div>
                     
                     // Add comments section for posts (if enabled)
@@ -275,8 +336,10 @@ struct PageContentProps {
 #[function_component(PageContent)]
 fn page_content(props: &PageContentProps) -> Html {
     let page = use_state(|| None::<Page>);
+    let timezone = use_display_timezone();
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
+    let preview_token = use_memo(|_| preview_token_from_location(), props.slug.clone());
 
     {
         let page = page.clone();
@@ -284,13 +347,25 @@ fn page_content(props: &PageContentProps) -> Html {
         let error = error.clone();
         let slug = props.slug.clone();
         let slug_for_deps = slug.clone();
+        let preview_token = (*preview_token).clone();
 
         use_effect_with_deps(move |_| {
             wasm_bindgen_futures::spawn_local(async move {
                 web_sys::console::log_1(&format!("PageContent: Loading page with slug = {}", slug).into());
-                match get_page_by_slug(&slug).await {
+                let result = match preview_token {
+                    Some(ref token) => get_page_preview(&slug, token).await,
+                    None => get_page_by_slug(&slug).await,
+                };
+                match result {
                     Ok(fetched_page) => {
                         web_sys::console::log_1(&format!("PageContent: Page loaded successfully: {:?}", fetched_page.title).into());
+                        // An old slug was redirected; show the current one in the address bar
+                        if fetched_page.slug != slug {
+                            if let Some(history) = web_sys::window().and_then(|w| w.history().ok()) {
+                                let url = format!("/page/{}", fetched_page.slug);
+                                let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+                            }
+                        }
                         page.set(Some(fetched_page));
                         loading.set(false);
                     }
@@ -313,11 +388,14 @@ fn page_content(props: &PageContentProps) -> Html {
                 <div class="error">{"Error loading page: "}{error_msg}</div>
             } else if let Some(ref page_data) = *page {
                 <>
+                    if preview_token.is_some() && page_data.status != "published" {
+                        <div class="preview-banner">{"Preview — this page is not published yet"}</div>
+                    }
                     <h1>{page_data.title.clone()}</h1>
                     <div class="page-meta">
                         <span class="page-status">{page_data.status.clone()}</span>
                         if let Some(ref created_at) = page_data.created_at {
-                            <span class="page-date">{" • "}{created_at.clone()}</span>
+                            <span class="page-date">{" • "}{timezone.datetime(created_at)}</span>
                         }
                     </div>
                     <div class="page-content">
@@ -991,20 +1069,23 @@ pub fn render_component_content_public_with_context(component: &PageComponent, o
             }
         }
         ComponentType::PostsList => {
-            // Parse properties for PostsList configuration
-            let posts_to_show = component.properties.container_max_width.parse::<usize>().unwrap_or(6);
-            let show_full = component.properties.container_max_width == "all" || posts_to_show >= 100;
-            let excerpt_length = component.properties.divider_margin.parse::<usize>().unwrap_or(200);
-            let _grid_columns = component.properties.gallery_columns;
+            // Posts are fetched when the page renders: the newest
+            // `posts_list_count`, of one category if the block names one
+            let properties = &component.properties;
+            let posts_to_show = properties.posts_list_count.max(1) as usize;
+            let columns = match properties.posts_list_layout.as_str() {
+                "list" => 1,
+                _ => properties.posts_list_columns.clamp(1, 4),
+            };
             
             // Create custom styling based on component properties
-            let card_bg = &component.properties.button_text;
-            let card_radius = &component.properties.button_size;
-            let grid_gap = &component.properties.button_url;
-            let title_color = &component.properties.button_icon;
-            let meta_color = &component.properties.form_action;
-            let link_color = &component.properties.form_method;
-            let shadow_type = &component.properties.button_variant;
+            let card_bg = &properties.posts_list_card_background;
+            let card_radius = &properties.posts_list_card_radius;
+            let grid_gap = &properties.posts_list_grid_gap;
+            let title_color = &properties.posts_list_title_color;
+            let meta_color = &properties.posts_list_meta_color;
+            let link_color = &properties.posts_list_link_color;
+            let shadow_type = &properties.posts_list_card_shadow;
             
             // Generate shadow CSS based on selection
             let card_shadow = match shadow_type.as_str() {
@@ -1017,9 +1098,11 @@ pub fn render_component_content_public_with_context(component: &PageComponent, o
             
             // Create dynamic styling for the posts grid
             let posts_grid_style = format!(
-                "display: grid; grid-template-columns: repeat(auto-fit, minmax(280px, 1fr)); gap: {}; max-width: 1200px; margin: 0 auto;",
-                grid_gap
+                "display: grid; grid-template-columns: repeat({}, minmax(0, 1fr)); gap: {}; max-width: 1200px; margin: 0 auto;",
+                columns, grid_gap
             );
+            // Scopes the styles to this block, so two lists on a page keep their own
+            let scope = format!("[data-posts-list=\"{}\"]", component.id.replace(['"', '\\'], ""));
             
             let post_card_style = format!(
                 "background: {}; border-radius: {}; box-shadow: {}; transition: transform 0.2s ease, box-shadow 0.2s ease; overflow: hidden;",
@@ -1027,34 +1110,38 @@ pub fn render_component_content_public_with_context(component: &PageComponent, o
             );
             
             html! {
-                <div class="component posts-list-component" style={format_component_styles(&component.styles)}>
-                    <style>
+                <div class="component posts-list-component" data-posts-list={component.id.clone()} style={format_component_styles(&component.styles)}>
+                    <style nonce={style_nonce()}>
                         {format!(r#"
-                            .posts-list-widget .posts-grid {{
+                            {scope} .posts-list-widget .posts-grid {{
                                 {}
                             }}
-                            .posts-list-widget .post-card {{
+                            {scope} .posts-list-widget .post-card {{
                                 {}
                             }}
-                            .posts-list-widget .post-card h2 {{
+                            {scope} .posts-list-widget .post-card h2 {{
                                 color: {} !important;
                             }}
-                            .posts-list-widget .post-meta {{
+                            {scope} .posts-list-widget .post-meta {{
                                 color: {} !important;
                             }}
-                            .posts-list-widget .read-more {{
+                            {scope} .posts-list-widget .read-more {{
                                 color: {} !important;
                             }}
-                            .posts-list-widget .post-card:hover {{
+                            {scope} .posts-list-widget .post-card:hover {{
                                 transform: translateY(-2px);
                                 box-shadow: 0 8px 25px rgba(0,0,0,0.15);
                             }}
-                        "#, posts_grid_style, post_card_style, title_color, meta_color, link_color)}
+                            @media (max-width: 768px) {{
+                                {scope} .posts-list-widget .posts-grid {{
+                                    grid-template-columns: 1fr;
+                                }}
+                            }}
+                        "#, posts_grid_style, post_card_style, title_color, meta_color, link_color, scope = scope)}
                     </style>
                     <PostsListWidget 
-                        show_full_list={show_full} 
                         limit={posts_to_show} 
-                        excerpt_length={excerpt_length}
+                        category_id={properties.posts_list_category_id}
                         on_navigate={on_navigate.clone()}
                     />
                 </div>
@@ -1204,10 +1291,7 @@ fn render_nested_content(content: &str, on_navigate: Option<Callback<PublicPage>
 
 // Helper function to render markdown content
 pub fn render_markdown_content(content: &str) -> Html {
-    let parser = pulldown_cmark::Parser::new(content);
-    let mut html_output = String::new();
-    pulldown_cmark::html::push_html(&mut html_output, parser);
-    Html::from_html_unchecked(html_output.into())
+    html! { <MarkdownContent content={content.to_string()} /> }
 }
 
 // Helper function to format component styles
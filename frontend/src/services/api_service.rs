@@ -3,24 +3,111 @@
 use gloo_net::http::Request;
 use serde::{Deserialize, Serialize};
 use crate::services::auth_service::get_auth_token;
+use crate::services::auth_context::handle_unauthorized;
+use crate::services::navigation_service::{ComponentTemplate, NavigationItem};
 
 const API_BASE_URL: &str = "http://localhost:8081/api";
 
 // Helper function to create authenticated requests
-fn create_authenticated_request(method: &str, url: &str) -> Result<gloo_net::http::RequestBuilder, ApiServiceError> {
-    let token = get_auth_token().map_err(|_| ApiServiceError::ServerError("Not authenticated".to_string()))?;
+pub(crate) fn create_authenticated_request(method: &str, url: &str) -> Result<gloo_net::http::RequestBuilder, ApiError> {
+    let token = get_auth_token().map_err(|_| ApiError::Unauthorized("Not authenticated".to_string()))?;
     
     let request_builder = match method {
         "GET" => Request::get(url),
         "POST" => Request::post(url),
         "PUT" => Request::put(url),
         "DELETE" => Request::delete(url),
-        _ => return Err(ApiServiceError::ServerError("Invalid HTTP method".to_string())),
+        _ => return Err(ApiError::Server("Invalid HTTP method".to_string())),
     };
     
     Ok(request_builder.header("Authorization", &format!("Bearer {}", token)))
 }
 
+/// Backoff settings for retrying idempotent requests
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first; 1 disables retries
+    pub max_attempts: u32,
+    pub initial_delay_ms: u32,
+    pub max_delay_ms: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay_ms: 300,
+            max_delay_ms: 3000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the given retry (0-based), doubling each time up to the cap
+    fn delay_ms(&self, retry: u32) -> u32 {
+        self.initial_delay_ms
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay_ms)
+    }
+}
+
+// Network failures and 5xx responses are usually transient (e.g. a backend
+// restart); 4xx responses will not change on retry
+fn is_retryable_status(status: u16) -> bool {
+    (500..600).contains(&status)
+}
+
+/// Send a request, retrying network errors and 5xx responses with exponential backoff
+///
+/// `build` is called once per attempt since a request can only be sent once.
+/// Only use this for idempotent requests, or creates sent with an
+/// `Idempotency-Key`; other mutations are sent without retries. The last
+/// response is returned as-is when attempts run out, so callers keep
+/// handling non-2xx statuses themselves.
+pub async fn send_with_retry<F>(build: F, policy: RetryPolicy) -> Result<gloo_net::http::Response, ApiError>
+where
+    F: Fn() -> Result<Request, ApiError>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        let result = build()?.send().await;
+        let retryable = match &result {
+            Ok(response) => is_retryable_status(response.status()),
+            Err(_) => true,
+        };
+        if !retryable || attempt >= max_attempts {
+            return result.map_err(|e| ApiError::Network(e.to_string()));
+        }
+        gloo_timers::future::TimeoutFuture::new(policy.delay_ms(attempt - 1)).await;
+        attempt += 1;
+    }
+}
+
+// GET requests are idempotent, so they retry with the default policy
+async fn get_with_retry<F>(build: F) -> Result<gloo_net::http::Response, ApiError>
+where
+    F: Fn() -> Result<gloo_net::http::RequestBuilder, ApiError>,
+{
+    send_with_retry(
+        || build()?.build().map_err(|e| ApiError::Network(e.to_string())),
+        RetryPolicy::default(),
+    ).await
+}
+
+/// Send a create request with retries, all attempts carrying one new
+/// `Idempotency-Key` so a retry after a lost response can't create twice
+async fn create_with_retry<T: Serialize + ?Sized>(url: &str, body: &T) -> Result<gloo_net::http::Response, ApiError> {
+    let idempotency_key = uuid::Uuid::new_v4().to_string();
+    send_with_retry(
+        || create_authenticated_request("POST", url)?
+            .header("Idempotency-Key", &idempotency_key)
+            .json(body)
+            .map_err(|e| ApiError::Parse(e.to_string())),
+        RetryPolicy::default(),
+    ).await
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct Post {
     pub id: Option<i32>,
@@ -29,7 +116,21 @@ pub struct Post {
     pub author: String,
     pub status: String,
     pub category_id: Option<i32>,
+    #[serde(default)]
+    pub category_name: Option<String>,
     pub created_at: Option<String>,
+    /// RFC 3339 UTC publish date; the target date for scheduled posts
+    #[serde(default)]
+    pub published_at: Option<String>,
+    /// Only sent with the public post list
+    #[serde(default, skip_serializing)]
+    pub comment_count: Option<i64>,
+    /// Teaser cut to the site's excerpt length; only sent with the public post list
+    #[serde(default, skip_serializing)]
+    pub excerpt: Option<String>,
+    /// JSON-LD `Article`, only sent with a single public post
+    #[serde(default, skip_serializing)]
+    pub structured_data: Option<serde_json::Value>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
@@ -63,6 +164,12 @@ pub struct CommentWithRelations {
     pub post_title: Option<String>,
     pub page_id: Option<i32>,
     pub page_title: Option<String>,
+    #[serde(default = "default_comment_status")]
+    pub status: String,
+}
+
+fn default_comment_status() -> String {
+    "approved".to_string()
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
@@ -77,6 +184,9 @@ pub struct CommentWithGravatar {
     pub author_username: Option<String>,
     pub author_email: Option<String>,
     pub gravatar_url: String,
+    /// `pending` when a new comment is held for moderation
+    #[serde(default)]
+    pub status: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
@@ -84,7 +194,80 @@ pub struct PublicCommentRequest {
     pub content: String,
     pub post_id: Option<i32>,
     pub page_id: Option<i32>,
-    pub user_id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captcha_token: Option<String>,
+}
+
+/// One hit from the sitewide search
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchResult {
+    /// `post`, `page` or `comment`
+    pub kind: String,
+    pub id: i32,
+    pub title: String,
+    pub snippet: String,
+    /// Public path of the result, or of what a comment was left on
+    pub url: String,
+    pub score: u32,
+    pub date: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchResponse {
+    pub query: String,
+    pub results: Vec<SearchResult>,
+}
+
+/// Comment limits enforced by the server
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct CommentLimits {
+    pub max_length: usize,
+    pub max_links: usize,
+    /// Users with an unverified email can't comment (admins and editors can)
+    #[serde(default)]
+    pub require_verified_email: bool,
+    /// Thread order when the reader hasn't picked one: `oldest` or `newest`
+    #[serde(default = "default_comment_sort")]
+    pub default_sort: String,
+    #[serde(default)]
+    pub gravatar: GravatarSettings,
+}
+
+fn default_comment_sort() -> String {
+    "oldest".to_string()
+}
+
+impl Default for CommentLimits {
+    fn default() -> Self {
+        Self {
+            max_length: 2000,
+            max_links: 3,
+            require_verified_email: false,
+            default_sort: default_comment_sort(),
+            gravatar: GravatarSettings::default(),
+        }
+    }
+}
+
+/// The site's Gravatar fallback image (`d`) and maximum rating (`r`)
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct GravatarSettings {
+    pub default_image: String,
+    pub rating: String,
+}
+
+impl Default for GravatarSettings {
+    fn default() -> Self {
+        Self { default_image: "identicon".to_string(), rating: "pg".to_string() }
+    }
+}
+
+impl GravatarSettings {
+    /// Avatar URL for an email, built the same way as the server's
+    pub fn url(&self, email: &str, size: u32) -> String {
+        let hash = format!("{:x}", md5::compute(email.trim().to_lowercase().as_bytes()));
+        format!("https://www.gravatar.com/avatar/{}?s={}&d={}&r={}", hash, size, self.default_image, self.rating)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
@@ -113,7 +296,6 @@ pub struct PageItem {
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
-#[allow(dead_code)]
 pub struct Category {
     pub id: i32,
     pub name: String,
@@ -140,11 +322,24 @@ pub struct BackendMetrics {
     pub avg_request_time: f64,
     pub max_request_time: f64,
     pub min_request_time: f64,
+    #[serde(default)]
+    pub p50_request_time: f64,
+    #[serde(default)]
+    pub p95_request_time: f64,
+    #[serde(default)]
+    pub p99_request_time: f64,
     pub total_requests: u64,
     pub error_rate: f64,
     pub db_query_avg_time: f64,
+    #[serde(default)]
+    pub db_query_p95_time: f64,
     pub db_connection_pool_active: u32,
     pub db_connection_pool_idle: u32,
+    #[serde(default)]
+    pub db_connection_pool_max: u32,
+    /// Percentage of response cache lookups served from the cache
+    #[serde(default)]
+    pub cache_hit_rate: Option<f64>,
     pub memory_usage_mb: f64,
     pub active_sessions: u32,
     pub session_avg_duration: f64,
@@ -183,493 +378,1323 @@ pub struct Template {
     pub layout: String,
 }
 
-pub async fn get_templates() -> Result<Vec<Template>, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/templates", API_BASE_URL))?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_templates() -> Result<Vec<Template>, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/templates", API_BASE_URL))).await?;
 
     if response.status() == 200 {
         let templates: Vec<Template> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(templates)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Restore the shipped Default master template, discarding theme customizations
+pub async fn reset_default_template() -> Result<(), ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/templates/reset-default", API_BASE_URL))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        Ok(())
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Apply a master template (a built-in preset or a saved template) to the site theme
+pub async fn apply_template(id: i32) -> Result<(), ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/templates/{}/apply", API_BASE_URL, id))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        Ok(())
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// A single-use signup invite
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Invite {
+    pub id: i32,
+    pub token: String,
+    pub email: Option<String>,
+    pub expires_at: String,
+    pub used_at: Option<String>,
+}
+
+/// Create a signup invite, optionally restricted to one email address
+pub async fn create_invite(email: Option<String>) -> Result<Invite, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/admin/invites", API_BASE_URL))?
+        .json(&serde_json::json!({ "email": email }))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 201 {
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// A design-system color scheme as listed by the backend
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColorSchemeSummary {
+    pub name: String,
+    pub built_in: bool,
+    pub has_admin: bool,
+    pub has_public: bool,
+    pub active: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColorSchemeList {
+    pub schemes: Vec<ColorSchemeSummary>,
+    pub current_admin: String,
+}
+
+/// A custom scheme's stored admin and public colors
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StoredColorScheme {
+    pub name: String,
+    pub admin: Option<serde_json::Value>,
+    pub public: Option<serde_json::Value>,
+}
+
+fn color_scheme_url(name: &str) -> String {
+    format!("{}/color-schemes/{}", API_BASE_URL, String::from(js_sys::encode_uri_component(name)))
+}
+
+pub async fn list_color_schemes() -> Result<ColorSchemeList, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/color-schemes", API_BASE_URL))).await?;
+
+    if response.status() == 200 {
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+pub async fn get_color_scheme(name: &str) -> Result<StoredColorScheme, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &color_scheme_url(name))).await?;
+
+    if response.status() == 200 {
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
     }
 }
 
-#[derive(Debug)]
-pub enum ApiServiceError {
-    NetworkError(String),
-    ParseError(String),
-    ServerError(String),
+/// Create or update a custom scheme; a `None` half keeps what is stored
+pub async fn save_color_scheme(
+    name: &str,
+    admin: Option<serde_json::Value>,
+    public: Option<serde_json::Value>,
+) -> Result<StoredColorScheme, ApiError> {
+    let response = create_authenticated_request("PUT", &color_scheme_url(name))?
+        .json(&serde_json::json!({ "admin": admin, "public": public }))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+pub async fn rename_color_scheme(name: &str, new_name: &str) -> Result<StoredColorScheme, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/rename", color_scheme_url(name)))?
+        .json(&serde_json::json!({ "new_name": new_name }))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
 }
 
-impl std::fmt::Display for ApiServiceError {
+/// Delete a custom scheme; the backend refuses while it is the active one
+pub async fn delete_color_scheme(name: &str) -> Result<(), ApiError> {
+    let response = create_authenticated_request("DELETE", &color_scheme_url(name))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        Ok(())
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Make a scheme the active admin scheme, returning the updated list
+pub async fn activate_color_scheme(name: &str) -> Result<ColorSchemeList, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/activate", color_scheme_url(name)))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Errors returned by api_service calls
+///
+/// Failed responses are mapped from the backend's standard error body
+/// (`{ code, message, details }`), so callers can branch on the kind of
+/// failure instead of matching message strings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    /// No response was received
+    Network(String),
+    /// A request or response body could not be (de)serialized
+    Parse(String),
+    /// 401: missing, invalid or expired credentials
+    Unauthorized(String),
+    /// 403: authenticated but not allowed
+    Forbidden(String),
+    /// 404
+    NotFound(String),
+    /// 400, 409 or 422, with the offending field when the server names one
+    Validation { field: Option<String>, message: String },
+    /// 429, with the server's `Retry-After` in seconds when it sent one
+    RateLimited { message: String, retry_after: Option<u32> },
+    /// Any other failure status
+    Server(String),
+}
+
+impl std::fmt::Display for ApiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ApiServiceError::NetworkError(msg) => write!(f, "Network error: {}", msg),
-            ApiServiceError::ParseError(msg) => write!(f, "Parse error: {}", msg),
-            ApiServiceError::ServerError(msg) => write!(f, "Server error: {}", msg),
+            ApiError::Network(msg) => write!(f, "Network error: {}", msg),
+            ApiError::Parse(msg) => write!(f, "Parse error: {}", msg),
+            ApiError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
+            ApiError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
+            ApiError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            ApiError::Validation { field: Some(field), message } => write!(f, "{}: {}", field, message),
+            ApiError::Validation { field: None, message } => write!(f, "{}", message),
+            ApiError::RateLimited { message, .. } => write!(f, "{}", message),
+            ApiError::Server(msg) => write!(f, "Server error: {}", msg),
         }
     }
 }
 
+// Shape of the backend's AppError response body
+#[derive(Deserialize, Default)]
+struct ErrorBody {
+    message: Option<String>,
+    details: Option<serde_json::Value>,
+}
+
+impl ApiError {
+    /// Build an error from a failed response, reading the error body if present
+    ///
+    /// A 401 goes through `handle_unauthorized`, so every admin screen handles
+    /// an expired session the same way.
+    pub async fn from_response(response: gloo_net::http::Response) -> Self {
+        let status = response.status();
+        let retry_after = retry_after(&response);
+        let body = response.json::<ErrorBody>().await.unwrap_or_default();
+        let error = match Self::from_parts(status, body) {
+            ApiError::RateLimited { message, .. } => ApiError::RateLimited { message, retry_after },
+            error => error,
+        };
+        if matches!(error, ApiError::Unauthorized(_)) {
+            handle_unauthorized();
+        }
+        error
+    }
+
+    fn from_parts(status: u16, body: ErrorBody) -> Self {
+        let field = body.details.as_ref()
+            .and_then(|details| details.get("field"))
+            .and_then(|field| field.as_str())
+            .map(str::to_string);
+        let message = body.message.unwrap_or_else(|| format!("HTTP {}", status));
+        match status {
+            401 => ApiError::Unauthorized(message),
+            403 => ApiError::Forbidden(message),
+            404 => ApiError::NotFound(message),
+            400 | 409 | 422 => ApiError::Validation { field, message },
+            429 => ApiError::RateLimited { message, retry_after: None },
+            _ => ApiError::Server(message),
+        }
+    }
+}
+
+/// Seconds to wait from a response's `Retry-After` header, which holds
+/// either a number of seconds or an HTTP date
+pub fn retry_after(response: &gloo_net::http::Response) -> Option<u32> {
+    let value = response.headers().get("retry-after")?;
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u32>() {
+        return Some(seconds);
+    }
+    let at = js_sys::Date::parse(value);
+    (!at.is_nan()).then(|| ((at - js_sys::Date::now()) / 1_000.0).ceil().max(0.0) as u32)
+}
+
 // Posts API
-pub async fn get_posts() -> Result<Vec<Post>, ApiServiceError> {
-    let response = Request::get(&format!("{}/posts", API_BASE_URL))
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_posts() -> Result<Vec<Post>, ApiError> {
+    let response = get_with_retry(|| Ok(Request::get(&format!("{}/posts", API_BASE_URL)))).await?;
 
     if response.status() == 200 {
         let posts: Vec<Post> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(posts)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn get_post(id: i32) -> Result<Post, ApiServiceError> {
-    let response = Request::get(&format!("{}/posts/{}", API_BASE_URL, id))
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+/// The newest `limit` published posts, optionally of one category
+pub async fn get_recent_posts(category_id: Option<i32>, limit: usize) -> Result<Vec<Post>, ApiError> {
+    let mut url = format!("{}/posts?limit={}", API_BASE_URL, limit);
+    if let Some(category_id) = category_id {
+        url.push_str(&format!("&category_id={}", category_id));
+    }
+    let response = get_with_retry(|| Ok(Request::get(&url))).await?;
+
+    if response.status() == 200 {
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+pub async fn get_post(id: i32) -> Result<Post, ApiError> {
+    let response = get_with_retry(|| Ok(Request::get(&format!("{}/posts/{}", API_BASE_URL, id)))).await?;
 
     if response.status() == 200 {
         let post: Post = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(post)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn create_post(post: &Post) -> Result<Post, ApiServiceError> {
-    let response = create_authenticated_request("POST", &format!("{}/posts", API_BASE_URL))?
-        .json(post)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
+/// Fetch a post in any status using a signed preview token
+pub async fn get_post_preview(id: i32, token: &str) -> Result<Post, ApiError> {
+    let response = get_with_retry(|| Ok(
+        Request::get(&format!("{}/preview/posts/{}", API_BASE_URL, id)).query([("token", token)])
+    )).await?;
+
+    if response.status() == 200 {
+        let post: Post = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(post)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Fetch a draft post using a share link's token; the view is counted
+pub async fn get_shared_post_preview(id: i32, token: &str) -> Result<Post, ApiError> {
+    let response = get_with_retry(|| Ok(
+        Request::get(&format!("{}/preview/posts/{}/shared", API_BASE_URL, id)).query([("token", token)])
+    )).await?;
+
+    if response.status() == 200 {
+        let post: Post = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(post)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct PreviewLink {
+    pub token: String,
+    pub expires_at: String,
+    /// Public path with the token attached, e.g. `/post/12?preview=...`
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct SharePreviewLink {
+    pub token: String,
+    /// UTC, `YYYY-MM-DD HH:MM:SS`
+    pub expires_at: String,
+    pub max_views: i32,
+    /// Public path with the token attached, e.g. `/post/12?share=...`
+    pub path: String,
+}
+
+/// Issue a share link that shows a draft post to reviewers without an account
+pub async fn create_share_preview(post_id: i32) -> Result<SharePreviewLink, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/posts/{}/share-preview", API_BASE_URL, post_id))?
+        .json(&serde_json::json!({}))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 201 {
+        let link: SharePreviewLink = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(link)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Issue a signed preview link for an unpublished post or page
+pub async fn create_preview_link(content_type: &str, id: i32) -> Result<PreviewLink, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/preview-links", API_BASE_URL))?
+        .json(&serde_json::json!({ "content_type": content_type, "id": id }))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        let link: PreviewLink = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(link)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+pub async fn create_post(post: &Post) -> Result<Post, ApiError> {
+    let response = create_with_retry(&format!("{}/posts", API_BASE_URL), post).await?;
 
     if response.status() == 201 {
         let created_post: Post = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(created_post)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn update_post(id: i32, post: &Post) -> Result<Post, ApiServiceError> {
+/// Number of posts per status tab; `all` excludes trashed posts
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PostStatusCounts {
+    pub all: i64,
+    pub published: i64,
+    pub draft: i64,
+    pub scheduled: i64,
+    pub trash: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct AdminPostsPage {
+    pub posts: Vec<Post>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+    pub counts: PostStatusCounts,
+}
+
+/// Filters for the admin post listing; empty values are ignored
+#[derive(Clone, PartialEq, Default)]
+pub struct PostListQuery {
+    pub page: i64,
+    pub status: String,
+    pub search: String,
+}
+
+pub async fn get_admin_posts(query: &PostListQuery) -> Result<AdminPostsPage, ApiError> {
+    let page = query.page.max(1).to_string();
+    let params = [
+        ("page", page.as_str()),
+        ("status", query.status.as_str()),
+        ("search", query.search.trim()),
+    ];
+
+    let response = get_with_retry(|| Ok(
+        create_authenticated_request("GET", &format!("{}/admin/posts", API_BASE_URL))?
+            .query(params.into_iter().filter(|(_, value)| !value.is_empty()))
+    )).await?;
+
+    if response.status() == 200 {
+        let posts_page: AdminPostsPage = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(posts_page)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Fetch a post in any status (admin)
+pub async fn get_admin_post(id: i32) -> Result<Post, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/admin/posts/{}", API_BASE_URL, id))).await?;
+
+    if response.status() == 200 {
+        let post: Post = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(post)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// A post plotted on the publishing calendar
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct CalendarPost {
+    pub id: i32,
+    pub title: String,
+    pub status: String,
+    /// RFC 3339 UTC
+    pub published_at: String,
+}
+
+/// Fetch dated posts with a publish date in `[from, to)` (RFC 3339 bounds)
+pub async fn get_post_calendar(from: &str, to: &str) -> Result<Vec<CalendarPost>, ApiError> {
+    let response = get_with_retry(|| Ok(
+        create_authenticated_request("GET", &format!("{}/posts/calendar", API_BASE_URL))?
+            .query([("from", from), ("to", to)])
+    )).await?;
+
+    if response.status() == 200 {
+        let posts: Vec<CalendarPost> = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(posts)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+pub async fn update_post(id: i32, post: &Post) -> Result<Post, ApiError> {
     let response = create_authenticated_request("PUT", &format!("{}/posts/{}", API_BASE_URL, id))?
         .json(post)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
         let updated_post: Post = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(updated_post)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+pub async fn duplicate_post(id: i32) -> Result<Post, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/posts/{}/duplicate", API_BASE_URL, id))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 201 {
+        let duplicated_post: Post = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(duplicated_post)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Download a post as a Markdown file: (file name, contents)
+pub async fn export_post_markdown(id: i32) -> Result<(String, String), ApiError> {
+    let response = create_authenticated_request("GET", &format!("{}/posts/{}/export.md", API_BASE_URL, id))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        let filename = response.headers().get("content-disposition")
+            .and_then(|value| value.split("filename=\"").nth(1).map(|rest| rest.trim_end_matches('"').to_string()))
+            .unwrap_or_else(|| format!("post-{}.md", id));
+        let markdown = response.text().await.map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok((filename, markdown))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Outcome of importing one Markdown file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedFile {
+    pub file: String,
+    pub success: bool,
+    pub post_id: Option<i32>,
+    pub title: Option<String>,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub failed: usize,
+    pub results: Vec<ImportedFile>,
+}
+
+/// Import Markdown files as draft posts; each file succeeds or fails on its own
+pub async fn import_markdown_posts(files: &web_sys::FileList) -> Result<ImportReport, ApiError> {
+    let form_data = web_sys::FormData::new()
+        .map_err(|_| ApiError::Server("Failed to create upload form".to_string()))?;
+    for file in (0..files.length()).filter_map(|i| files.get(i)) {
+        form_data.append_with_blob_and_filename("files", &file, &file.name())
+            .map_err(|_| ApiError::Server("Failed to attach file to upload".to_string()))?;
+    }
+
+    let response = create_authenticated_request("POST", &format!("{}/posts/import", API_BASE_URL))?
+        .body(form_data)
+        .map_err(|e| ApiError::Server(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn delete_post(id: i32) -> Result<(), ApiServiceError> {
+pub async fn delete_post(id: i32) -> Result<(), ApiError> {
     let response = create_authenticated_request("DELETE", &format!("{}/posts/{}", API_BASE_URL, id))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
         Ok(())
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
 // Users API
-pub async fn get_users() -> Result<Vec<User>, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/users", API_BASE_URL))?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct PagedUsers {
+    pub users: Vec<User>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+}
+
+/// Fetch one page of users (admin)
+pub async fn get_users_page(page: i64, per_page: i64) -> Result<PagedUsers, ApiError> {
+    let response = get_with_retry(|| Ok(
+        create_authenticated_request("GET", &format!("{}/users", API_BASE_URL))?
+            .query([("page", page.to_string()), ("per_page", per_page.to_string())])
+    )).await?;
 
     if response.status() == 200 {
-        let users: Vec<User> = response
+        let paged: PagedUsers = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
-        Ok(users)
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(paged)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Fetch every user by walking the paginated listing
+pub async fn get_users() -> Result<Vec<User>, ApiError> {
+    let mut users = Vec::new();
+    let mut page = 1;
+    loop {
+        let paged = get_users_page(page, 100).await?;
+        users.extend(paged.users);
+        if page >= paged.total_pages {
+            return Ok(users);
+        }
+        page += 1;
     }
 }
 
-pub async fn create_user(user: &User) -> Result<User, ApiServiceError> {
+pub async fn create_user(user: &User) -> Result<User, ApiError> {
     let response = Request::post(&format!("{}/users", API_BASE_URL))
         .json(user)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 201 {
         let created_user: User = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(created_user)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn update_user(id: i32, user: &User) -> Result<User, ApiServiceError> {
+pub async fn update_user(id: i32, user: &User) -> Result<User, ApiError> {
     let response = Request::put(&format!("{}/users/{}", API_BASE_URL, id))
         .json(user)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
         let updated_user: User = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(updated_user)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn delete_user(id: i32) -> Result<(), ApiServiceError> {
+pub async fn delete_user(id: i32) -> Result<(), ApiError> {
     let response = Request::delete(&format!("{}/users/{}", API_BASE_URL, id))
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 204 {
         Ok(())
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
 // Comments API
-pub async fn get_comments() -> Result<Vec<Comment>, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/comments", API_BASE_URL))?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_comments() -> Result<Vec<Comment>, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/comments", API_BASE_URL))).await?;
 
     if response.status() == 200 {
         let comments: Vec<Comment> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(comments)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn get_comments_with_relations() -> Result<Vec<CommentWithRelations>, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/comments", API_BASE_URL))?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_comments_with_relations() -> Result<Vec<CommentWithRelations>, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/comments", API_BASE_URL))).await?;
 
     if response.status() == 200 {
         let comments: Vec<CommentWithRelations> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(comments)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
 #[allow(dead_code)]
-pub async fn create_comment(comment: &Comment) -> Result<Comment, ApiServiceError> {
+pub async fn create_comment(comment: &Comment) -> Result<Comment, ApiError> {
     let response = Request::post(&format!("{}/comments", API_BASE_URL))
         .json(comment)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 201 {
         let created_comment: Comment = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(created_comment)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn update_comment(id: i32, comment: &Comment) -> Result<Comment, ApiServiceError> {
+pub async fn update_comment(id: i32, comment: &Comment) -> Result<Comment, ApiError> {
     let response = Request::put(&format!("{}/comments/{}", API_BASE_URL, id))
         .json(comment)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
         let updated_comment: Comment = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(updated_comment)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn delete_comment(id: i32) -> Result<(), ApiServiceError> {
-    let response = Request::delete(&format!("{}/comments/{}", API_BASE_URL, id))
+pub async fn delete_comment(id: i32) -> Result<(), ApiError> {
+    let response = create_authenticated_request("DELETE", &format!("{}/comments/{}", API_BASE_URL, id))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
-    if response.status() == 204 {
+    if response.ok() {
         Ok(())
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-// Public Comments API
-pub async fn get_post_comments(post_id: i32) -> Result<Vec<CommentWithGravatar>, ApiServiceError> {
-    let response = Request::get(&format!("{}/comments/public?post_id={}", API_BASE_URL, post_id))
+/// Outcome of moderating one comment in a bulk request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModeratedComment {
+    pub id: i32,
+    pub success: bool,
+    pub status: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulkModerationReport {
+    pub action: String,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<ModeratedComment>,
+}
+
+/// Apply `action` (approve, reject, spam or delete) to many comments in one request
+pub async fn bulk_moderate_comments(action: &str, ids: &[i32]) -> Result<BulkModerationReport, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/comments/bulk", API_BASE_URL))?
+        .json(&serde_json::json!({ "action": action, "ids": ids }))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.ok() {
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+// Edit a comment's content in place, returning the server's updated_at timestamp
+pub async fn update_comment_content(id: i32, content: &str) -> Result<Option<String>, ApiError> {
+    let response = create_authenticated_request("PUT", &format!("{}/comments/{}", API_BASE_URL, id))?
+        .json(&serde_json::json!({ "content": content }))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        let updated: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(updated["updated_at"].as_str().map(|s| s.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+// Public Comments API
+
+/// `&sort=` for a comments request; none leaves the order to the site default
+fn comment_sort_param(sort: Option<&str>) -> String {
+    sort.map(|sort| format!("&sort={}", sort)).unwrap_or_default()
+}
+
+pub async fn get_post_comments(post_id: i32, sort: Option<&str>) -> Result<Vec<CommentWithGravatar>, ApiError> {
+    let response = get_with_retry(|| Ok(Request::get(&format!("{}/comments/public?post_id={}{}", API_BASE_URL, post_id, comment_sort_param(sort))))).await?;
 
     if response.status() == 200 {
         let comments: Vec<CommentWithGravatar> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(comments)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn get_page_comments(page_id: i32) -> Result<Vec<CommentWithGravatar>, ApiServiceError> {
-    let response = Request::get(&format!("{}/comments/public?page_id={}", API_BASE_URL, page_id))
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_page_comments(page_id: i32, sort: Option<&str>) -> Result<Vec<CommentWithGravatar>, ApiError> {
+    let response = get_with_retry(|| Ok(Request::get(&format!("{}/comments/public?page_id={}{}", API_BASE_URL, page_id, comment_sort_param(sort))))).await?;
 
     if response.status() == 200 {
         let comments: Vec<CommentWithGravatar> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(comments)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn create_public_comment(comment_request: &PublicCommentRequest) -> Result<CommentWithGravatar, ApiServiceError> {
-    let response = create_authenticated_request("POST", &format!("{}/comments/create", API_BASE_URL))?
-        .json(comment_request)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+/// Search published posts and pages, plus comments for admins
+pub async fn search(query: &str) -> Result<SearchResponse, ApiError> {
+    let url = format!("{}/search", API_BASE_URL);
+    let response = get_with_retry(|| {
+        let request = create_authenticated_request("GET", &url).unwrap_or_else(|_| Request::get(&url));
+        Ok(request.query([("q", query)]))
+    }).await?;
+
+    if response.status() == 200 {
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+pub async fn get_comment_limits() -> Result<CommentLimits, ApiError> {
+    let response = get_with_retry(|| Ok(Request::get(&format!("{}/comments/limits", API_BASE_URL)))).await?;
+
+    if response.status() == 200 {
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+pub async fn create_public_comment(comment_request: &PublicCommentRequest) -> Result<CommentWithGravatar, ApiError> {
+    let response = create_with_retry(&format!("{}/comments/create", API_BASE_URL), comment_request).await?;
 
     if response.status() == 201 {
         let created_comment: CommentWithGravatar = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(created_comment)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
 // Media API
-pub async fn get_media() -> Result<Vec<MediaItem>, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/media", API_BASE_URL))?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_media() -> Result<Vec<MediaItem>, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/media", API_BASE_URL))).await?;
 
     if response.status() == 200 {
         let media: Vec<MediaItem> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(media)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Upload a file to the media library as multipart form data
+pub async fn upload_media(file: &web_sys::File) -> Result<MediaItem, ApiError> {
+    let form_data = web_sys::FormData::new()
+        .map_err(|_| ApiError::Server("Failed to create upload form".to_string()))?;
+    form_data.append_with_blob("file", file)
+        .map_err(|_| ApiError::Server("Failed to attach file to upload".to_string()))?;
+
+    let response = create_authenticated_request("POST", &format!("{}/media/upload", API_BASE_URL))?
+        .body(form_data)
+        .map_err(|e| ApiError::Server(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() != 201 {
+        return Err(ApiError::from_response(response).await);
     }
+
+    let result: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| ApiError::Parse(e.to_string()))?;
+    if !result["success"].as_bool().unwrap_or(false) {
+        return Err(ApiError::Validation {
+            field: None,
+            message: result["message"].as_str().unwrap_or("Upload failed").to_string(),
+        });
+    }
+
+    let media_data = &result["media"];
+    Ok(MediaItem {
+        id: media_data["id"].as_i64().map(|id| id as i32),
+        name: media_data["name"].as_str().unwrap_or("").to_string(),
+        type_: media_data["type_"].as_str().unwrap_or("").to_string(),
+        size: Some(media_data["size"].as_str().unwrap_or("").to_string()),
+        url: media_data["url"].as_str().unwrap_or("").to_string(),
+        created_at: media_data["created_at"].as_str().map(|s| s.to_string()),
+        user_id: None,
+    })
 }
 
 #[allow(dead_code)]
-pub async fn create_media(media: &MediaItem) -> Result<MediaItem, ApiServiceError> {
+pub async fn create_media(media: &MediaItem) -> Result<MediaItem, ApiError> {
     let response = Request::post(&format!("{}/media", API_BASE_URL))
         .json(media)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 201 {
         let created_media: MediaItem = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(created_media)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn delete_media(id: i32) -> Result<(), ApiServiceError> {
+pub async fn delete_media(id: i32) -> Result<(), ApiError> {
     let response = create_authenticated_request("DELETE", &format!("{}/media/{}", API_BASE_URL, id))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 204 {
         Ok(())
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-// Stats API
-pub async fn get_stats() -> Result<Stats, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/stats", API_BASE_URL))?
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct BulkDeleteMediaSummary {
+    pub deleted: usize,
+    pub not_found: Vec<i32>,
+    pub files_removed: usize,
+    pub file_errors: Vec<String>,
+}
+
+pub async fn bulk_delete_media(ids: &[i32]) -> Result<BulkDeleteMediaSummary, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/media/bulk-delete", API_BASE_URL))?
+        .json(&serde_json::json!({ "ids": ids }))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct MissingMediaFile {
+    pub id: i32,
+    pub file_name: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct MediaCleanupSummary {
+    pub dry_run: bool,
+    pub orphaned_files: Vec<String>,
+    pub orphaned_bytes: u64,
+    pub missing_files: Vec<MissingMediaFile>,
+    pub files_removed: usize,
+    pub rows_removed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Scan for media out of sync with the upload directory; removes it unless `dry_run`
+pub async fn cleanup_media(dry_run: bool) -> Result<MediaCleanupSummary, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/system/media/cleanup", API_BASE_URL))?
+        .json(&serde_json::json!({ "dry_run": dry_run }))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+// Stats API
+pub async fn get_stats() -> Result<Stats, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/stats", API_BASE_URL))).await?;
 
     if response.status() == 200 {
         let stats: Stats = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(stats)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
 // Pages API
-pub async fn get_pages() -> Result<Vec<PageItem>, ApiServiceError> {
-    let request = create_authenticated_request("GET", &format!("{}/pages", API_BASE_URL))?;
-    let response = request.send().await.map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
-    
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct PagedPages {
+    pub pages: Vec<PageItem>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+}
+
+/// Fetch one page of pages in any status (admin)
+pub async fn get_pages_page(page: i64, per_page: i64) -> Result<PagedPages, ApiError> {
+    let response = get_with_retry(|| Ok(
+        create_authenticated_request("GET", &format!("{}/admin/pages", API_BASE_URL))?
+            .query([("page", page.to_string()), ("per_page", per_page.to_string())])
+    )).await?;
+
     if response.ok() {
-        let pages: Vec<PageItem> = response.json().await.map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
-        Ok(pages)
+        let paged: PagedPages = response.json().await.map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(paged)
     } else {
-        Err(ApiServiceError::NetworkError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn create_page(page: &PageItem) -> Result<PageItem, ApiServiceError> {
+/// Fetch every page in any status by walking the paginated admin listing
+pub async fn get_pages() -> Result<Vec<PageItem>, ApiError> {
+    let mut pages = Vec::new();
+    let mut page = 1;
+    loop {
+        let paged = get_pages_page(page, 100).await?;
+        pages.extend(paged.pages);
+        if page >= paged.total_pages {
+            return Ok(pages);
+        }
+        page += 1;
+    }
+}
+
+pub async fn create_page(page: &PageItem) -> Result<PageItem, ApiError> {
     let request = create_authenticated_request("POST", &format!("{}/pages", API_BASE_URL))?
         .json(page)
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
     
-    let response = request.send().await.map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+    let response = request.send().await.map_err(|e| ApiError::Network(e.to_string()))?;
     
     if response.ok() {
-        let created_page: PageItem = response.json().await.map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+        let created_page: PageItem = response.json().await.map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(created_page)
     } else {
-        Err(ApiServiceError::NetworkError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn update_page(id: i32, page: &PageItem) -> Result<PageItem, ApiServiceError> {
+pub async fn update_page(id: i32, page: &PageItem) -> Result<PageItem, ApiError> {
     let request = create_authenticated_request("PUT", &format!("{}/pages/{}", API_BASE_URL, id))?
         .json(page)
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
     
-    let response = request.send().await.map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+    let response = request.send().await.map_err(|e| ApiError::Network(e.to_string()))?;
     
     if response.ok() {
-        let updated_page: PageItem = response.json().await.map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+        let updated_page: PageItem = response.json().await.map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(updated_page)
     } else {
-        Err(ApiServiceError::NetworkError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn delete_page(id: i32) -> Result<(), ApiServiceError> {
+pub async fn delete_page(id: i32) -> Result<(), ApiError> {
     let request = create_authenticated_request("DELETE", &format!("{}/pages/{}", API_BASE_URL, id))?;
-    let response = request.send().await.map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+    let response = request.send().await.map_err(|e| ApiError::Network(e.to_string()))?;
     
     if response.ok() {
         Ok(())
     } else {
-        Err(ApiServiceError::NetworkError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-// Categories API
-#[allow(dead_code)]
-pub async fn get_categories() -> Result<Vec<Category>, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/categories", API_BASE_URL))?
+// Sessions API
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct SessionInfo {
+    pub id: i32,
+    pub user_id: i32,
+    pub created_at: String,
+    pub expires_at: String,
+    pub is_expired: bool,
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub last_seen_at: Option<String>,
+}
+
+pub async fn get_my_sessions() -> Result<Vec<SessionInfo>, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/auth/sessions", API_BASE_URL))).await?;
+
+    if response.status() == 200 {
+        let sessions: Vec<SessionInfo> = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(sessions)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Session row in the admin listing, joined with its owner
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct AdminSession {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub created_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub last_seen_at: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct AdminSessionsPage {
+    pub total_sessions: i64,
+    pub active_sessions: i64,
+    pub sessions: Vec<AdminSession>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+}
+
+/// Filters for the admin session listing; empty values are ignored
+#[derive(Clone, PartialEq, Default)]
+pub struct SessionListQuery {
+    pub page: i64,
+    pub username: String,
+    pub status: String,
+    pub from: String,
+    pub to: String,
+}
+
+pub async fn get_admin_sessions(query: &SessionListQuery) -> Result<AdminSessionsPage, ApiError> {
+    let page = query.page.max(1).to_string();
+    let params = [
+        ("page", page.as_str()),
+        ("username", query.username.trim()),
+        ("status", query.status.as_str()),
+        ("from", query.from.as_str()),
+        ("to", query.to.as_str()),
+    ];
+
+    let response = get_with_retry(|| Ok(
+        create_authenticated_request("GET", &format!("{}/admin/sessions", API_BASE_URL))?
+            .query(params.into_iter().filter(|(_, value)| !value.is_empty()))
+    )).await?;
+
+    if response.status() == 200 {
+        let sessions_page: AdminSessionsPage = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(sessions_page)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Expire every session site-wide, returning how many were affected
+pub async fn force_logout_all_sessions(keep_current_session: bool) -> Result<i64, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/admin/sessions/force-logout-all", API_BASE_URL))?
+        .json(&serde_json::json!({ "keep_current_session": keep_current_session }))
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        let result: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        Ok(result["sessions_expired"].as_i64().unwrap_or(0))
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+// Categories API
+pub async fn get_categories() -> Result<Vec<Category>, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/categories", API_BASE_URL))).await?;
 
     if response.status() == 200 {
         let categories: Vec<Category> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(categories)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
 // Performance Metrics API
-pub async fn get_performance_metrics() -> Result<PerformanceMetrics, ApiServiceError> {
-    let response = Request::get(&format!("{}/performance", API_BASE_URL))
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_performance_metrics() -> Result<PerformanceMetrics, ApiError> {
+    let response = get_with_retry(|| Ok(Request::get(&format!("{}/performance", API_BASE_URL)))).await?;
 
     if response.status() == 200 {
         let metrics: PerformanceMetrics = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(metrics)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
@@ -711,6 +1736,8 @@ pub struct SystemInfo {
     pub total_users: i64,
     pub total_media: i64,
     pub last_backup: Option<String>,
+    #[serde(default)]
+    pub site_time: String,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -748,30 +1775,46 @@ pub struct TableSnapshot {
 }
 
 // System Settings API
-pub async fn get_settings(setting_type: Option<&str>) -> Result<Vec<Setting>, ApiServiceError> {
+pub async fn get_settings(setting_type: Option<&str>) -> Result<Vec<Setting>, ApiError> {
     let url = match setting_type {
         Some(t) => format!("{}/public/system/settings?setting_type={}", API_BASE_URL, t),
         None => format!("{}/public/system/settings", API_BASE_URL),
     };
     // Admin-only: requires auth
-    let response = create_authenticated_request("GET", &url)?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+    let response = get_with_retry(|| create_authenticated_request("GET", &url)).await?;
 
     if response.status() == 200 {
         let settings: Vec<Setting> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(settings)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+/// Settings of any type, including `system` ones the public endpoint never
+/// returns (admin only)
+pub async fn get_admin_settings(setting_type: Option<&str>) -> Result<Vec<Setting>, ApiError> {
+    let url = match setting_type {
+        Some(t) => format!("{}/system/settings?setting_type={}", API_BASE_URL, t),
+        None => format!("{}/system/settings", API_BASE_URL),
+    };
+    let response = get_with_retry(|| create_authenticated_request("GET", &url)).await?;
+
+    if response.status() == 200 {
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::from_response(response).await)
     }
 }
 
 // Public settings fetcher (safe subset)
-pub async fn get_public_settings(setting_type: Option<&str>) -> Result<Vec<Setting>, ApiServiceError> {
+pub async fn get_public_settings(setting_type: Option<&str>) -> Result<Vec<Setting>, ApiError> {
     // First try the dedicated public endpoint
     let public_url = match setting_type {
         Some(t) => format!("{}/public/system/settings?setting_type={}", API_BASE_URL, t),
@@ -782,7 +1825,7 @@ pub async fn get_public_settings(setting_type: Option<&str>) -> Result<Vec<Setti
             let settings: Vec<Setting> = response
                 .json()
                 .await
-                .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+                .map_err(|e| ApiError::Parse(e.to_string()))?;
             return Ok(settings);
         }
     }
@@ -797,124 +1840,151 @@ pub async fn get_public_settings(setting_type: Option<&str>) -> Result<Vec<Setti
             .header("Authorization", &format!("Bearer {}", token))
             .send()
             .await
-            .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+            .map_err(|e| ApiError::Network(e.to_string()))?;
         if response.status() == 200 {
             let settings: Vec<Setting> = response
                 .json()
                 .await
-                .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+                .map_err(|e| ApiError::Parse(e.to_string()))?;
             return Ok(settings);
         }
     }
 
-    Err(ApiServiceError::ServerError("Failed to fetch settings".to_string()))
+    Err(ApiError::Server("Failed to fetch settings".to_string()))
+}
+
+/// Site logo and favicon URLs, when set
+#[derive(Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct Branding {
+    pub logo_url: Option<String>,
+    pub favicon_url: Option<String>,
 }
 
-pub async fn update_settings(settings: Vec<SettingData>) -> Result<Vec<Setting>, ApiServiceError> {
+/// Navigation, component templates and settings for the public layout
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct PublicBootstrap {
+    pub header_navigation: Vec<NavigationItem>,
+    pub footer_navigation: Vec<NavigationItem>,
+    pub component_templates: Vec<ComponentTemplate>,
+    pub site_settings: Vec<Setting>,
+    pub container_settings: Vec<Setting>,
+    #[serde(default)]
+    pub branding: Branding,
+}
+
+/// Everything the public layout loads on mount, in one request
+pub async fn get_public_bootstrap() -> Result<PublicBootstrap, ApiError> {
+    let response = Request::get(&format!("{}/public/bootstrap", API_BASE_URL))
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 200 {
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))
+    } else {
+        Err(ApiError::Server(format!("Failed to load site layout: HTTP {}", response.status())))
+    }
+}
+
+pub async fn update_settings(settings: Vec<SettingData>) -> Result<Vec<Setting>, ApiError> {
     let request_body = SettingsRequest { settings };
     
     let response = create_authenticated_request("PUT", &format!("{}/system/settings", API_BASE_URL))?
         .json(&request_body)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
         let updated_settings: Vec<Setting> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(updated_settings)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn get_system_info() -> Result<SystemInfo, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/system/info", API_BASE_URL))?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_system_info() -> Result<SystemInfo, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/system/info", API_BASE_URL))).await?;
 
     if response.status() == 200 {
         let system_info: SystemInfo = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(system_info)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn create_backup(backup_request: BackupRequest) -> Result<BackupInfo, ApiServiceError> {
+pub async fn create_backup(backup_request: BackupRequest) -> Result<BackupInfo, ApiError> {
     let response = create_authenticated_request("POST", &format!("{}/system/backup", API_BASE_URL))?
         .json(&backup_request)
-        .map_err(|e| ApiServiceError::ParseError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 || response.status() == 201 {
         let backup_info: BackupInfo = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(backup_info)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn get_backups() -> Result<Vec<BackupInfo>, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/system/backups", API_BASE_URL))?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_backups() -> Result<Vec<BackupInfo>, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/system/backups", API_BASE_URL))).await?;
 
     if response.status() == 200 {
         let backups: Vec<BackupInfo> = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(backups)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn get_data_snapshot() -> Result<DataSnapshot, ApiServiceError> {
-    let response = create_authenticated_request("GET", &format!("{}/system/snapshot", API_BASE_URL))?
-        .send()
-        .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+pub async fn get_data_snapshot() -> Result<DataSnapshot, ApiError> {
+    let response = get_with_retry(|| create_authenticated_request("GET", &format!("{}/system/snapshot", API_BASE_URL))).await?;
 
     if response.status() == 200 {
         let snapshot: DataSnapshot = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(snapshot)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
 
 #[allow(dead_code)]
-pub async fn restore_backup(backup_id: &str) -> Result<String, ApiServiceError> {
+pub async fn restore_backup(backup_id: &str) -> Result<String, ApiError> {
     let response = create_authenticated_request("POST", &format!("{}/system/backup/{}/restore", API_BASE_URL, backup_id))?
         .send()
         .await
-        .map_err(|e| ApiServiceError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
         let result: String = response
             .json()
             .await
-            .map_err(|e| ApiServiceError::ParseError(e.to_string()))?;
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
         Ok(result)
     } else {
-        Err(ApiServiceError::ServerError(format!("HTTP {}", response.status())))
+        Err(ApiError::from_response(response).await)
     }
 }
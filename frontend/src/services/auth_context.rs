@@ -1,6 +1,18 @@
 use yew::prelude::*;
 use std::rc::Rc;
-use crate::services::auth_service::{User, get_current_user, is_authenticated, clear_auth};
+use gloo_storage::{SessionStorage, Storage};
+use crate::services::auth_service::{User, AuthError, get_current_user, is_authenticated, clear_auth};
+
+const LOGIN_PATH: &str = "/login";
+const SESSION_EXPIRED_PARAM: &str = "expired";
+const RETURN_PATH_PARAM: &str = "next";
+/// Other names a return path is accepted under in a login URL
+const RETURN_PATH_ALIASES: [&str; 2] = ["return_to", "redirect"];
+/// Session storage key holding the return path while the visitor is away
+/// from the login page, e.g. signing up first
+const RETURN_PATH_STORAGE_KEY: &str = "login_return_path";
+/// Where a sign-in lands without a return path
+pub const DEFAULT_LANDING_PATH: &str = "/admin";
 
 #[derive(Clone, PartialEq)]
 pub struct AuthState {
@@ -20,7 +32,7 @@ impl Default for AuthState {
 }
 
 pub enum AuthAction {
-    SetUser(User),
+    SetUser(Box<User>),
     ClearUser,
     #[allow(dead_code)]
     SetLoading(bool),
@@ -33,7 +45,7 @@ impl Reducible for AuthState {
         match action {
             AuthAction::SetUser(user) => Rc::new(AuthState {
                 is_authenticated: true,
-                user: Some(user),
+                user: Some(*user),
                 loading: false,
             }),
             AuthAction::ClearUser => Rc::new(AuthState {
@@ -69,7 +81,11 @@ pub fn auth_provider(props: &AuthProviderProps) -> Html {
                 if is_authenticated() {
                     match get_current_user().await {
                         Ok(user) => {
-                            auth_state.dispatch(AuthAction::SetUser(user));
+                            auth_state.dispatch(AuthAction::SetUser(Box::new(user)));
+                        }
+                        Err(AuthError::InvalidCredentials) if on_admin_path() => {
+                            // Token expired while away; re-authenticate and come back
+                            handle_unauthorized();
                         }
                         Err(_) => {
                             // Invalid token, clear it
@@ -107,7 +123,7 @@ pub async fn login_and_update_context(
     
     match crate::services::auth_service::login(credentials).await {
         Ok(auth_response) => {
-            auth_context.dispatch(AuthAction::SetUser(auth_response.user));
+            auth_context.dispatch(AuthAction::SetUser(Box::new(auth_response.user)));
             Ok(())
         }
         Err(e) => {
@@ -133,12 +149,11 @@ pub async fn logout_and_update_context(
     }
 }
 
-#[allow(dead_code)]
 pub async fn refresh_user_context(auth_context: &AuthContext) {
     if is_authenticated() {
         match get_current_user().await {
             Ok(user) => {
-                auth_context.dispatch(AuthAction::SetUser(user));
+                auth_context.dispatch(AuthAction::SetUser(Box::new(user)));
             }
             Err(_) => {
                 clear_auth();
@@ -148,4 +163,92 @@ pub async fn refresh_user_context(auth_context: &AuthContext) {
     } else {
         auth_context.dispatch(AuthAction::ClearUser);
     }
-}
\ No newline at end of file
+}
+
+/// Clear a rejected session and send the browser to the login page
+///
+/// Called for any 401 from an authenticated API call. The login URL carries a
+/// "session expired" flag and the current location as the return path. On the
+/// login page itself this only clears the token, so a failed login can't loop.
+pub fn handle_unauthorized() {
+    clear_auth();
+    let Some(window) = web_sys::window() else { return };
+    let location = window.location();
+    let path = location.pathname().unwrap_or_default();
+    if path == LOGIN_PATH {
+        return;
+    }
+    let return_path = format!("{}{}", path, location.search().unwrap_or_default());
+    let _ = location.set_href(&login_url(&return_path, true));
+}
+
+/// Login page URL that comes back to `return_path` after signing in
+pub fn login_url(return_path: &str, session_expired: bool) -> String {
+    let mut url = LOGIN_PATH.to_string();
+    let mut separator = '?';
+    if session_expired {
+        url.push_str(&format!("?{}=1", SESSION_EXPIRED_PARAM));
+        separator = '&';
+    }
+    if is_safe_return_path(return_path) {
+        url.push_str(&format!(
+            "{}{}={}",
+            separator,
+            RETURN_PATH_PARAM,
+            String::from(js_sys::encode_uri_component(return_path))
+        ));
+    }
+    url
+}
+
+/// Whether the login page was reached because a session expired
+pub fn session_expired_from_location() -> bool {
+    login_query_param(SESSION_EXPIRED_PARAM).is_some()
+}
+
+/// Return path carried by the login URL, if it is a safe one
+fn return_path_from_location() -> Option<String> {
+    std::iter::once(RETURN_PATH_PARAM)
+        .chain(RETURN_PATH_ALIASES)
+        .find_map(login_query_param)
+        .filter(|path| is_safe_return_path(path))
+}
+
+/// Keep the login URL's return path for the rest of the browser session,
+/// so it survives a detour through the signup page
+pub fn remember_return_path() {
+    if let Some(path) = return_path_from_location() {
+        let _ = SessionStorage::set(RETURN_PATH_STORAGE_KEY, path);
+    }
+}
+
+/// Where to go after signing in: the login URL's return path, then a
+/// remembered one, then the dashboard. The remembered path is used up.
+pub fn take_return_path() -> String {
+    let stored = SessionStorage::get::<String>(RETURN_PATH_STORAGE_KEY).ok();
+    SessionStorage::delete(RETURN_PATH_STORAGE_KEY);
+    return_path_from_location()
+        .or(stored.filter(|path| is_safe_return_path(path)))
+        .unwrap_or_else(|| DEFAULT_LANDING_PATH.to_string())
+}
+
+fn on_admin_path() -> bool {
+    web_sys::window()
+        .and_then(|window| window.location().pathname().ok())
+        .is_some_and(|path| path.starts_with("/admin"))
+}
+
+fn login_query_param(name: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search.trim_start_matches('?')
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| js_sys::decode_uri_component(value).ok())
+        .map(String::from)
+        .filter(|value| !value.is_empty())
+}
+
+fn is_safe_return_path(path: &str) -> bool {
+    shared::return_path::is_safe_return_path(path, LOGIN_PATH)
+}
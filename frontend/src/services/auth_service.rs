@@ -1,6 +1,7 @@
 use gloo_net::http::Request;
 use serde::{Deserialize, Serialize};
 use gloo_storage::{LocalStorage, Storage};
+use crate::services::api_service::retry_after;
 
 const API_BASE_URL: &str = "http://localhost:8081/api";
 
@@ -8,6 +9,9 @@ const API_BASE_URL: &str = "http://localhost:8081/api";
 pub struct LoginCredentials {
     pub username: String,
     pub password: String,
+    /// Needed once a username or address has failed too many logins
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captcha_token: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
@@ -15,6 +19,15 @@ pub struct SignupCredentials {
     pub username: String,
     pub email: String,
     pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invite_token: Option<String>,
+    /// Honeypot; stays empty for people
+    pub website: String,
+    /// Token from the signup policy; the server times the form with it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captcha_token: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
@@ -22,6 +35,25 @@ pub struct VerifyEmailRequest {
     pub token: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResendVerificationRequest {
+    pub email: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+    pub logout_other_sessions: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct UpdateProfileRequest {
+    pub username: String,
+    pub email: String,
+    pub bio: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct User {
     pub id: i32,
@@ -30,6 +62,24 @@ pub struct User {
     pub role: String,
     pub status: String,
     pub email_verified: Option<bool>,
+    /// Set by an admin; the user is sent to the change-password page until
+    /// they pick a new one
+    #[serde(default)]
+    pub must_change_password: bool,
+    #[serde(default)]
+    pub bio: Option<String>,
+    /// New address waiting to be confirmed from the link emailed to it
+    #[serde(default)]
+    pub pending_email: Option<String>,
+    /// Last password login, as a UTC timestamp
+    #[serde(default)]
+    pub last_login_at: Option<String>,
+    /// The password login before `last_login_at`; only in the user's own profile
+    #[serde(default)]
+    pub previous_login_at: Option<String>,
+    /// Admin-area permissions granted by the role, e.g. "manage_content"
+    #[serde(default)]
+    pub permissions: Vec<String>,
     pub created_at: Option<String>,
 }
 
@@ -44,7 +94,13 @@ pub enum AuthError {
     NetworkError(String),
     ParseError(String),
     ServerError(String),
+    /// The server rejected one form field, e.g. a username that's taken
+    FieldError { field: String, message: String },
     InvalidCredentials,
+    /// The request needs a solved CAPTCHA, or its token was rejected
+    CaptchaRequired(String),
+    /// 429: too many attempts, with the server's `Retry-After` in seconds
+    RateLimited { message: String, retry_after: Option<u32> },
 }
 
 impl std::fmt::Display for AuthError {
@@ -53,11 +109,21 @@ impl std::fmt::Display for AuthError {
             AuthError::NetworkError(msg) => write!(f, "Network error: {}", msg),
             AuthError::ParseError(msg) => write!(f, "Parse error: {}", msg),
             AuthError::ServerError(msg) => write!(f, "Server error: {}", msg),
+            AuthError::FieldError { message, .. } => write!(f, "{}", message),
             AuthError::InvalidCredentials => write!(f, "Invalid credentials"),
+            AuthError::CaptchaRequired(msg) => write!(f, "{}", msg),
+            AuthError::RateLimited { message, .. } => write!(f, "{}", message),
         }
     }
 }
 
+async fn rate_limited(response: gloo_net::http::Response) -> AuthError {
+    let retry_after = retry_after(&response);
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    let message = body["message"].as_str().unwrap_or("Too many attempts").to_string();
+    AuthError::RateLimited { message, retry_after }
+}
+
 pub async fn login(credentials: &LoginCredentials) -> Result<AuthResponse, AuthError> {
     let response = Request::post(&format!("{}/auth/login", API_BASE_URL))
         .json(credentials)
@@ -79,6 +145,16 @@ pub async fn login(credentials: &LoginCredentials) -> Result<AuthResponse, AuthE
         Ok(auth_response)
     } else if response.status() == 401 {
         Err(AuthError::InvalidCredentials)
+    } else if response.status() == 429 {
+        Err(rate_limited(response).await)
+    } else if response.status() == 400 {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["message"].as_str().unwrap_or("Invalid input").to_string();
+        if body["code"] == "CAPTCHA_FAILED" {
+            Err(AuthError::CaptchaRequired(message))
+        } else {
+            Err(AuthError::ServerError(message))
+        }
     } else {
         Err(AuthError::ServerError(format!("HTTP {}", response.status())))
     }
@@ -157,12 +233,51 @@ pub async fn signup(credentials: &SignupCredentials) -> Result<serde_json::Value
         Ok(result)
     } else if response.status() == 409 {
         Err(AuthError::ServerError("Username or email already exists".to_string()))
+    } else if response.status() == 403 {
+        Err(AuthError::ServerError("Signups are closed on this site".to_string()))
+    } else if response.status() == 429 {
+        Err(rate_limited(response).await)
     } else if response.status() == 400 {
-        let error_text = response
-            .text()
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["message"].as_str().unwrap_or("Invalid input").to_string();
+        if body["code"] == "CAPTCHA_FAILED" {
+            return Err(AuthError::CaptchaRequired(message));
+        }
+        match body["details"]["field"].as_str() {
+            Some(field) => Err(AuthError::FieldError { field: field.to_string(), message }),
+            None => Err(AuthError::ServerError(message)),
+        }
+    } else {
+        Err(AuthError::ServerError(format!("HTTP {}", response.status())))
+    }
+}
+
+/// What the signup page needs before showing the form
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct SignupPolicy {
+    /// `open`, `invite_only` or `closed`
+    #[serde(default = "default_signup_mode")]
+    pub mode: String,
+    /// Signed time the form was handed out, sent back with the signup
+    #[serde(default)]
+    pub form_token: Option<String>,
+}
+
+fn default_signup_mode() -> String {
+    "open".to_string()
+}
+
+pub async fn get_signup_policy() -> Result<SignupPolicy, AuthError> {
+    let response = Request::get(&format!("{}/auth/signup-policy", API_BASE_URL))
+        .send()
+        .await
+        .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+
+    if response.status() == 200 {
+        response
+            .json()
             .await
-            .unwrap_or_else(|_| "Invalid input".to_string());
-        Err(AuthError::ServerError(error_text))
+            .map_err(|e| AuthError::ParseError(e.to_string()))
     } else {
         Err(AuthError::ServerError(format!("HTTP {}", response.status())))
     }
@@ -197,6 +312,93 @@ pub async fn verify_email(token: &str) -> Result<serde_json::Value, AuthError> {
     }
 }
 
+/// Ask for a new verification email. The server answers the same way whether
+/// or not the address has an unverified account.
+pub async fn resend_verification(email: &str) -> Result<String, AuthError> {
+    let request = ResendVerificationRequest {
+        email: email.to_string(),
+    };
+
+    let response = Request::post(&format!("{}/auth/resend-verification", API_BASE_URL))
+        .json(&request)
+        .map_err(|e| AuthError::NetworkError(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+
+    if response.status() == 200 {
+        let result: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AuthError::ParseError(e.to_string()))?;
+        Ok(result["message"].as_str().unwrap_or("A new verification email is on its way.").to_string())
+    } else {
+        Err(AuthError::ServerError(format!("HTTP {}", response.status())))
+    }
+}
+
+/// Update the signed-in user's username, email and bio. A changed email
+/// comes back as `pending_email` until it is confirmed.
+pub async fn update_profile(request: &UpdateProfileRequest) -> Result<User, AuthError> {
+    let token = get_auth_token()?;
+
+    let response = Request::put(&format!("{}/auth/me", API_BASE_URL))
+        .header("Authorization", &format!("Bearer {}", token))
+        .json(request)
+        .map_err(|e| AuthError::NetworkError(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+
+    if response.status() == 200 {
+        response
+            .json()
+            .await
+            .map_err(|e| AuthError::ParseError(e.to_string()))
+    } else if response.status() == 401 {
+        Err(AuthError::InvalidCredentials)
+    } else {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["message"].as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("HTTP {}", status));
+        Err(AuthError::ServerError(message))
+    }
+}
+
+/// Replace the signed-in user's password, optionally signing out their
+/// other sessions
+pub async fn change_password(current_password: &str, new_password: &str, logout_other_sessions: bool) -> Result<(), AuthError> {
+    let token = get_auth_token()?;
+    let request = ChangePasswordRequest {
+        current_password: current_password.to_string(),
+        new_password: new_password.to_string(),
+        logout_other_sessions,
+    };
+
+    let response = Request::post(&format!("{}/auth/change-password", API_BASE_URL))
+        .header("Authorization", &format!("Bearer {}", token))
+        .json(&request)
+        .map_err(|e| AuthError::NetworkError(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+
+    if response.status() == 200 {
+        Ok(())
+    } else if response.status() == 401 {
+        Err(AuthError::InvalidCredentials)
+    } else {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["message"].as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("HTTP {}", status));
+        Err(AuthError::ServerError(message))
+    }
+}
+
 #[allow(dead_code)]
 pub async fn refresh_session() -> Result<User, AuthError> {
     // For now, just verify the current token
@@ -0,0 +1,70 @@
+//! Canonical URLs of public pages
+//!
+//! Mirrors the backend's `canonical_urls` policy: the
+//! `canonical_trailing_slash` setting decides whether content paths end in
+//! a slash, and the canonical URL is that path on `site_url`. The public
+//! layout puts it in a `<link rel="canonical">` and tidies the address bar
+//! to match.
+
+use web_sys::window;
+
+pub const CANONICAL_TRAILING_SLASH_SETTING: &str = "canonical_trailing_slash";
+
+/// `path` with doubled slashes collapsed and the trailing slash added or
+/// removed; `/` stays as it is
+pub fn canonical_path(path: &str, add_slash: bool) -> String {
+    let mut collapsed = String::with_capacity(path.len() + 1);
+    for c in path.chars() {
+        if c != '/' || !collapsed.ends_with('/') {
+            collapsed.push(c);
+        }
+    }
+    let trimmed = collapsed.trim_end_matches('/');
+    match add_slash {
+        _ if trimmed.is_empty() => "/".to_string(),
+        true => format!("{}/", trimmed),
+        false => trimmed.to_string(),
+    }
+}
+
+/// Absolute canonical URL, or `None` while `site_url` isn't an http(s) URL
+pub fn canonical_url(site_url: &str, path: &str, add_slash: bool) -> Option<String> {
+    let site_url = site_url.trim().trim_end_matches('/');
+    let host = site_url.strip_prefix("https://").or_else(|| site_url.strip_prefix("http://"))?;
+    (!host.is_empty()).then(|| format!("{}{}", site_url, canonical_path(path, add_slash)))
+}
+
+/// Point the document's `<link rel="canonical">` at the current page, and
+/// swap a non-canonical path in the address bar for the canonical one
+/// without reloading. Query string and fragment are kept.
+pub fn apply_canonical(site_url: &str, add_slash: bool) {
+    let Some(window) = window() else { return };
+    let Some(document) = window.document() else { return };
+    let location = window.location();
+    let Ok(path) = location.pathname() else { return };
+
+    let canonical = canonical_path(&path, add_slash);
+    if canonical != path {
+        let rest = format!("{}{}", location.search().unwrap_or_default(), location.hash().unwrap_or_default());
+        if let Ok(history) = window.history() {
+            let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&format!("{}{}", canonical, rest)));
+        }
+    }
+
+    let existing = document.query_selector("link[rel=\"canonical\"]").ok().flatten();
+    match (canonical_url(site_url, &canonical, add_slash), existing) {
+        (Some(url), Some(link)) => {
+            let _ = link.set_attribute("href", &url);
+        }
+        (Some(url), None) => {
+            if let (Ok(link), Some(head)) = (document.create_element("link"), document.head()) {
+                let _ = link.set_attribute("rel", "canonical");
+                let _ = link.set_attribute("href", &url);
+                let _ = head.append_child(&link);
+            }
+        }
+        // A canonical URL can't be named without a site URL
+        (None, Some(link)) => link.remove(),
+        (None, None) => {}
+    }
+}
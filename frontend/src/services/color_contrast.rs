@@ -0,0 +1,142 @@
+// WCAG 2.x contrast checks for theme color pairs
+//
+// Colors come straight from the design system's CSS values, so backgrounds
+// may be translucent rgba() or gradients. Translucent colors are composited
+// over the surface they sit on, and a gradient is judged by its worst stop.
+
+/// WCAG AA minimum contrast for normal-size text
+pub const AA_NORMAL_TEXT: f64 = 4.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgba {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub a: f64,
+}
+
+impl Rgba {
+    const WHITE: Rgba = Rgba { r: 255.0, g: 255.0, b: 255.0, a: 1.0 };
+    const BLACK: Rgba = Rgba { r: 0.0, g: 0.0, b: 0.0, a: 1.0 };
+
+    /// Blend this color over an opaque backdrop
+    fn over(self, backdrop: Rgba) -> Rgba {
+        let mix = |top: f64, bottom: f64| top * self.a + bottom * (1.0 - self.a);
+        Rgba { r: mix(self.r, backdrop.r), g: mix(self.g, backdrop.g), b: mix(self.b, backdrop.b), a: 1.0 }
+    }
+
+    /// Move `amount` (0.0-1.0) of the way towards `target`
+    fn towards(self, target: Rgba, amount: f64) -> Rgba {
+        let mix = |from: f64, to: f64| from + (to - from) * amount;
+        Rgba { r: mix(self.r, target.r), g: mix(self.g, target.g), b: mix(self.b, target.b), a: self.a }
+    }
+
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r.round() as u8, self.g.round() as u8, self.b.round() as u8)
+    }
+
+    /// WCAG relative luminance
+    fn luminance(self) -> f64 {
+        let channel = |c: f64| {
+            let c = c / 255.0;
+            if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * channel(self.r) + 0.7152 * channel(self.g) + 0.0722 * channel(self.b)
+    }
+}
+
+/// Parse a `#rgb`, `#rrggbb`, `rgb()` or `rgba()` color
+pub fn parse_color(value: &str) -> Option<Rgba> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        let digits: Vec<u8> = hex.chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        let (r, g, b) = match digits.as_slice() {
+            [r, g, b] => (r * 17, g * 17, b * 17),
+            [r1, r2, g1, g2, b1, b2] => (r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2),
+            _ => return None,
+        };
+        return Some(Rgba { r: r as f64, g: g as f64, b: b as f64, a: 1.0 });
+    }
+
+    let args = value.strip_prefix("rgba(").or_else(|| value.strip_prefix("rgb("))?.strip_suffix(')')?;
+    let parts: Vec<f64> = args.split(',')
+        .map(|part| part.trim().parse::<f64>().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [r, g, b] => Some(Rgba { r: *r, g: *g, b: *b, a: 1.0 }),
+        [r, g, b, a] => Some(Rgba { r: *r, g: *g, b: *b, a: a.clamp(0.0, 1.0) }),
+        _ => None,
+    }
+}
+
+/// Every color in a CSS value: the color itself, or each stop of a gradient
+fn color_stops(value: &str) -> Vec<Rgba> {
+    if let Some(color) = parse_color(value) {
+        return vec![color];
+    }
+    let mut stops = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find(['#', 'r']) {
+        rest = &rest[start..];
+        let end = if rest.starts_with('#') {
+            rest.find(|c: char| !(c == '#' || c.is_ascii_hexdigit())).unwrap_or(rest.len())
+        } else if rest.starts_with("rgb") {
+            rest.find(')').map(|i| i + 1).unwrap_or(rest.len())
+        } else {
+            1
+        };
+        if let Some(color) = parse_color(&rest[..end]) {
+            stops.push(color);
+        }
+        rest = &rest[end..];
+    }
+    stops
+}
+
+/// Contrast ratio between two opaque colors, from 1.0 to 21.0
+pub fn contrast_ratio(a: Rgba, b: Rgba) -> f64 {
+    let (la, lb) = (a.luminance(), b.luminance());
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Outcome of checking one text color against its background
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastResult {
+    /// Lowest ratio across the background's color stops
+    pub ratio: f64,
+    /// Closest color to the original text color that passes AA, when it fails
+    pub suggestion: Option<String>,
+}
+
+/// Check `foreground` text on `background`, which sits on the opaque `base`
+/// surface. Returns None when either value isn't a color this can read.
+pub fn check_contrast(foreground: &str, background: &str, base: &str) -> Option<ContrastResult> {
+    let base = parse_color(base).map(|c| c.over(Rgba::WHITE)).unwrap_or(Rgba::WHITE);
+    let backgrounds: Vec<Rgba> = color_stops(background).into_iter().map(|c| c.over(base)).collect();
+    if backgrounds.is_empty() {
+        return None;
+    }
+    let foreground = parse_color(foreground)?;
+
+    let worst_ratio = |fg: Rgba| backgrounds.iter()
+        .map(|bg| contrast_ratio(fg.over(*bg), *bg))
+        .fold(f64::INFINITY, f64::min);
+
+    let ratio = worst_ratio(foreground);
+    if ratio >= AA_NORMAL_TEXT {
+        return Some(ContrastResult { ratio, suggestion: None });
+    }
+
+    // Darken or lighten in 1% steps and keep whichever passes with the smaller change
+    let opaque = Rgba { a: 1.0, ..foreground };
+    let suggestion = [Rgba::BLACK, Rgba::WHITE].iter()
+        .filter_map(|target| (1..=100)
+            .map(|step| (step, opaque.towards(*target, step as f64 / 100.0)))
+            .find(|(_, candidate)| worst_ratio(*candidate) >= AA_NORMAL_TEXT))
+        .min_by_key(|(step, _)| *step)
+        .map(|(_, color)| color.to_hex());
+
+    Some(ContrastResult { ratio, suggestion })
+}
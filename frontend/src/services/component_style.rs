@@ -0,0 +1,35 @@
+// Component template styles for the public layout and the live preview
+//
+// The derivation itself lives in the shared crate, where the backend can use
+// it too; this picks out the active templates.
+
+use crate::services::navigation_service::ComponentTemplate;
+pub use shared::component_style::{component_style, HeaderLogo};
+use shared::component_style::{global_declarations, header_logo, logo_style, to_style};
+
+fn active_template<'a>(templates: &'a [ComponentTemplate], component_type: &str) -> Option<&'a ComponentTemplate> {
+    templates.iter().find(|t| t.component_type == component_type && t.is_active)
+}
+
+/// Inline style for a component, from its active template if there is one
+pub fn active_component_style(templates: &[ComponentTemplate], component_type: &str) -> String {
+    active_template(templates, component_type)
+        .map(|template| component_style(component_type, &template.template_data))
+        .unwrap_or_default()
+}
+
+/// Site-wide CSS variables taken from the active templates
+pub fn global_style_vars(templates: &[ComponentTemplate]) -> String {
+    to_style(&global_declarations(|component_type| {
+        active_template(templates, component_type).map(|template| &template.template_data)
+    }))
+}
+
+/// What the header shows, and the logo image's inline style, from the active
+/// header template
+pub fn active_header_logo(templates: &[ComponentTemplate], has_logo: bool) -> (HeaderLogo, String) {
+    match active_template(templates, "header") {
+        Some(template) => (header_logo(&template.template_data, has_logo), logo_style(&template.template_data)),
+        None => (header_logo(&serde_json::Value::Null, has_logo), String::new()),
+    }
+}
@@ -0,0 +1,29 @@
+// Content-Security-Policy nonce for styles the frontend injects
+//
+// Under the production CSP, `<style>` elements are only applied when they
+// carry the page's nonce. The server that delivers index.html puts it in
+// `<meta name="csp-nonce" content="...">` (the backend sends it in the
+// `X-CSP-Nonce` header). Without a nonce, styles are injected untagged, which
+// is what the development policy expects.
+
+use web_sys::Element;
+
+/// The page's CSP nonce, if the shell provided one
+pub fn style_nonce() -> Option<String> {
+    let meta = web_sys::window()?
+        .document()?
+        .query_selector("meta[name=\"csp-nonce\"]")
+        .ok()
+        .flatten()?;
+    meta.get_attribute("content")
+        .map(|nonce| nonce.trim().to_string())
+        .filter(|nonce| !nonce.is_empty())
+}
+
+/// Tag an injected `<style>` element with the CSP nonce, before it is
+/// appended to the document
+pub fn tag_with_nonce(style: &Element) {
+    if let Some(nonce) = style_nonce() {
+        let _ = style.set_attribute("nonce", &nonce);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::components::page_builder::drag_drop_builder::{PageComponent, ComponentType, ComponentStyles, Position, ComponentProperties};
-use crate::services::api_service::{get_pages, create_page, PageItem, ApiServiceError};
+use crate::services::api_service::{get_pages, create_page, PageItem, ApiError};
 use std::collections::HashSet;
 
 pub fn get_default_home_page_components() -> Vec<PageComponent> {
@@ -107,7 +107,7 @@ pub fn get_default_post_page_components() -> Vec<PageComponent> {
 }
 
 #[allow(dead_code)]
-pub async fn initialize_essential_pages() -> Result<Vec<PageItem>, ApiServiceError> {
+pub async fn initialize_essential_pages() -> Result<Vec<PageItem>, ApiError> {
     let mut created_pages = Vec::new();
     
     // Check existing pages
@@ -0,0 +1,156 @@
+//! Conversions between the server's UTC timestamps and the browser's time zone
+//!
+//! The API exchanges UTC strings; inputs and calendars show local time, and
+//! displayed dates follow the site `timezone` setting via `DisplayTimezone`.
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+
+/// Setting value meaning "each visitor's browser time zone"
+pub const VIEWER_TIMEZONE: &str = "viewer";
+/// Date and time, e.g. "Aug 10, 2025 at 2:30 PM CEST"; matches the backend
+pub const DATETIME_FORMAT: &str = "%b %-d, %Y at %-I:%M %p %Z";
+/// Date only, e.g. "August 10, 2025"
+pub const DATE_FORMAT: &str = "%B %-d, %Y";
+
+/// Zone that displayed timestamps are rendered in
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DisplayTimezone {
+    Zone(Tz),
+    Viewer,
+}
+
+impl Default for DisplayTimezone {
+    fn default() -> Self {
+        Self::Zone(Tz::UTC)
+    }
+}
+
+impl DisplayTimezone {
+    /// Interpret a `timezone` setting value; unknown names fall back to UTC
+    pub fn from_setting(value: &str) -> Self {
+        let value = value.trim();
+        if value == VIEWER_TIMEZONE {
+            Self::Viewer
+        } else {
+            Self::Zone(value.parse().unwrap_or(Tz::UTC))
+        }
+    }
+
+    /// Format a UTC timestamp from the API, or return it unchanged if unparseable
+    pub fn format(&self, value: &str, format: &str) -> String {
+        let Some(utc) = parse_api_timestamp(value) else {
+            return value.to_string();
+        };
+        match self {
+            Self::Zone(tz) => utc.with_timezone(tz).format(format).to_string(),
+            Self::Viewer => utc.with_timezone(&Local).format(format).to_string(),
+        }
+    }
+
+    pub fn datetime(&self, value: &str) -> String {
+        self.format(value, DATETIME_FORMAT)
+    }
+
+    pub fn date(&self, value: &str) -> String {
+        self.format(value, DATE_FORMAT)
+    }
+}
+
+/// Parse an API timestamp: RFC 3339, or the naive UTC forms the backend
+/// uses for posts, comments and sessions
+pub fn parse_api_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"].iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|naive| Utc.from_utc_datetime(&naive))
+}
+
+/// Convert a `datetime-local` input value (browser time zone) to RFC 3339 UTC
+pub fn local_input_to_utc(value: &str) -> Option<String> {
+    if value.trim().is_empty() {
+        return None;
+    }
+    let date = Date::new(&JsValue::from_str(value));
+    if date.get_time().is_nan() {
+        return None;
+    }
+    date.to_iso_string().as_string()
+}
+
+/// Convert an RFC 3339 timestamp to a `datetime-local` input value
+pub fn utc_to_local_input(value: &Option<String>) -> String {
+    let Some(value) = value else {
+        return String::new();
+    };
+    let date = Date::new(&JsValue::from_str(value));
+    if date.get_time().is_nan() {
+        return String::new();
+    }
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}",
+        date.get_full_year(),
+        date.get_month() + 1,
+        date.get_date(),
+        date.get_hours(),
+        date.get_minutes()
+    )
+}
+
+/// Parse an RFC 3339 timestamp, returning `None` when it is invalid
+pub fn parse_utc(value: &str) -> Option<Date> {
+    let date = Date::new(&JsValue::from_str(value));
+    (!date.get_time().is_nan()).then_some(date)
+}
+
+/// Short local time of day, e.g. "09:30"
+pub fn local_time_label(date: &Date) -> String {
+    format!("{:02}:{:02}", date.get_hours(), date.get_minutes())
+}
+
+/// Local midnight at the start of the given day; `month` is 0-based and may
+/// overflow into the next year, as with the JS `Date` constructor
+pub fn local_day_start(year: i32, month: i32, day: i32) -> Date {
+    Date::new_with_year_month_day(year as u32, month, day)
+}
+
+/// Move a UTC timestamp to another local calendar day, keeping its local time
+pub fn move_to_local_day(value: &str, year: i32, month: i32, day: i32) -> Option<String> {
+    let date = parse_utc(value)?;
+    date.set_full_year_with_month_date(year as u32, month, day);
+    date.to_iso_string().as_string()
+}
+
+/// Age beyond which `relative_time` gives up and absolute dates are shown
+pub const RELATIVE_TIME_MAX_DAYS: i64 = 30;
+
+/// Describe `then` relative to `now`, e.g. "3 hours ago"
+///
+/// Returns `None` for future times and anything older than
+/// `RELATIVE_TIME_MAX_DAYS`, where an absolute date reads better.
+pub fn relative_time(then: DateTime<Utc>, now: DateTime<Utc>) -> Option<String> {
+    let elapsed = now.signed_duration_since(then);
+    // Allow a little clock skew between server and browser
+    if elapsed.num_seconds() < -60 || elapsed.num_days() > RELATIVE_TIME_MAX_DAYS {
+        return None;
+    }
+    let plural = |count: i64, unit: &str| {
+        format!("{} {}{} ago", count, unit, if count == 1 { "" } else { "s" })
+    };
+    Some(match elapsed.num_seconds() {
+        s if s < 45 => "just now".to_string(),
+        s if s < 3600 => plural((s / 60).max(1), "minute"),
+        s if s < 86_400 => plural(s / 3600, "hour"),
+        _ if elapsed.num_days() == 1 => "yesterday".to_string(),
+        _ => plural(elapsed.num_days(), "day"),
+    })
+}
+
+/// The browser's current time in UTC
+pub fn utc_now() -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(Date::now() as i64).unwrap_or_default()
+}
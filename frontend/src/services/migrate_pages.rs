@@ -1,5 +1,5 @@
 use crate::components::page_builder::drag_drop_builder::{PageComponent, ComponentType, ComponentStyles, Position, ComponentProperties};
-use crate::services::api_service::{create_page, get_pages, PageItem, ApiServiceError};
+use crate::services::api_service::{create_page, get_pages, PageItem, ApiError};
 use uuid::Uuid;
 
 // Helper function to create a component with default properties
@@ -187,6 +187,8 @@ fn default_properties() -> ComponentProperties {
         posts_list_show_date: true,
         posts_list_show_excerpt: true,
         posts_list_show_view_all: true,
+        posts_list_category_id: None,
+        posts_list_layout: "grid".to_string(),
         
         // Nested components for layout containers
         nested_components: vec![],
@@ -303,7 +305,7 @@ pub fn create_why_mrcms_page_components() -> Vec<PageComponent> {
     ]
 }
 
-pub async fn create_essential_pages() -> Result<Vec<PageItem>, ApiServiceError> {
+pub async fn create_essential_pages() -> Result<Vec<PageItem>, ApiError> {
     let mut created_pages = Vec::new();
     
     // Check if essential pages already exist
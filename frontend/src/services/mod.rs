@@ -3,8 +3,17 @@
 pub mod api_service;
 pub mod auth_service;
 pub mod auth_context;
+pub mod toast_context;
+pub mod timezone_context;
+pub mod text_direction_context;
 pub mod navigation_service;
+pub mod local_time;
+pub mod color_contrast;
+pub mod component_style;
+pub mod csp;
 pub mod page_service;
+pub mod preview_service;
+pub mod canonical;
 pub mod performance_service;
 pub mod sample_page_data;
 pub mod default_pages;
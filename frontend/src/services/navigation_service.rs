@@ -15,6 +15,11 @@ pub struct NavigationItem {
     pub target: Option<String>,
     pub mobile_visible: bool,
     pub description: Option<String>,
+    /// RFC 3339 UTC timestamps bounding when the item is shown publicly
+    #[serde(default)]
+    pub visible_from: Option<String>,
+    #[serde(default)]
+    pub visible_until: Option<String>,
     pub children: Option<Vec<NavigationItem>>,
 }
 
@@ -178,6 +183,29 @@ pub async fn get_navigation_by_area(area: &str) -> Result<Vec<NavigationItem>, N
     }
 }
 
+// Every item in an area, including ones outside their schedule (admin only)
+pub async fn get_admin_navigation_by_area(area: &str) -> Result<Vec<NavigationItem>, NavigationServiceError> {
+    let token = get_auth_token().map_err(|_| NavigationServiceError::NetworkError("Not authenticated".to_string()))?;
+    
+    match gloo_net::http::Request::get(&format!("http://localhost:8081/api/navigation/area/{}/all", area))
+        .header("Authorization", &format!("Bearer {}", token))
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if response.status() == 200 {
+                match response.json::<Vec<NavigationItem>>().await {
+                    Ok(items) => Ok(items),
+                    Err(e) => Err(NavigationServiceError::ParseError(e.to_string())),
+                }
+            } else {
+                Err(NavigationServiceError::NetworkError(format!("HTTP {}: {}", response.status(), response.status_text())))
+            }
+        }
+        Err(e) => Err(NavigationServiceError::NetworkError(e.to_string())),
+    }
+}
+
 #[allow(dead_code)]
 pub async fn get_menu_area(name: &str) -> Result<MenuArea, NavigationServiceError> {
     match gloo_net::http::Request::get(&format!("http://localhost:8081/api/menu-areas/{}", name))
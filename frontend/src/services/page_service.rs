@@ -20,14 +20,49 @@ pub enum PageServiceError {
     ParseError(String),
 }
 
-pub async fn get_pages() -> Result<Vec<Page>, PageServiceError> {
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PagedPages {
+    pub pages: Vec<Page>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+}
+
+/// What the public home shows, as resolved by the server
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Homepage {
+    /// `posts` or `page`
+    pub mode: String,
+    pub page: Option<Page>,
+}
+
+pub async fn get_homepage() -> Result<Homepage, PageServiceError> {
+    match gloo_net::http::Request::get("http://localhost:8081/api/homepage")
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if response.status() == 200 {
+                response.json::<Homepage>().await.map_err(|e| PageServiceError::ParseError(e.to_string()))
+            } else {
+                Err(PageServiceError::NetworkError(format!("HTTP {}: {}", response.status(), response.status_text())))
+            }
+        }
+        Err(e) => Err(PageServiceError::NetworkError(e.to_string())),
+    }
+}
+
+/// Fetch one page of published pages
+pub async fn get_published_pages(page: i64, per_page: i64) -> Result<PagedPages, PageServiceError> {
     match gloo_net::http::Request::get("http://localhost:8081/api/pages")
+        .query([("page", page.to_string()), ("per_page", per_page.to_string())])
         .send()
         .await
     {
         Ok(response) => {
             if response.status() == 200 {
-                match response.json::<Vec<Page>>().await {
+                match response.json::<PagedPages>().await {
                     Ok(pages) => Ok(pages),
                     Err(e) => Err(PageServiceError::ParseError(e.to_string())),
                 }
@@ -39,6 +74,20 @@ pub async fn get_pages() -> Result<Vec<Page>, PageServiceError> {
     }
 }
 
+/// Fetch every published page by walking the paginated listing
+pub async fn get_pages() -> Result<Vec<Page>, PageServiceError> {
+    let mut pages = Vec::new();
+    let mut page = 1;
+    loop {
+        let paged = get_published_pages(page, 100).await?;
+        pages.extend(paged.pages);
+        if page >= paged.total_pages {
+            return Ok(pages);
+        }
+        page += 1;
+    }
+}
+
 #[allow(dead_code)]
 pub async fn create_page(page: &Page) -> Result<Page, PageServiceError> {
     let token = get_auth_token().map_err(|_| PageServiceError::NetworkError("Not authenticated".to_string()))?;
@@ -107,6 +156,27 @@ pub async fn delete_page(id: i32) -> Result<(), PageServiceError> {
     }
 }
 
+/// Fetch a page in any status using a signed preview token
+pub async fn get_page_preview(slug: &str, token: &str) -> Result<Page, PageServiceError> {
+    match gloo_net::http::Request::get(&format!("http://localhost:8081/api/preview/pages/{}", slug))
+        .query([("token", token)])
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if response.status() == 200 {
+                match response.json::<Page>().await {
+                    Ok(page) => Ok(page),
+                    Err(e) => Err(PageServiceError::ParseError(e.to_string())),
+                }
+            } else {
+                Err(PageServiceError::NetworkError(format!("HTTP {}: {}", response.status(), response.status_text())))
+            }
+        }
+        Err(e) => Err(PageServiceError::NetworkError(e.to_string())),
+    }
+}
+
 pub async fn get_page_by_slug(slug: &str) -> Result<Page, PageServiceError> {
     match gloo_net::http::Request::get(&format!("http://localhost:8081/api/pages/slug/{}", slug))
         .send()
@@ -0,0 +1,117 @@
+//! Links from the admin to the public view of posts and pages
+//!
+//! Published content opens at its normal public URL. Anything else gets a
+//! signed, time-limited preview link from the backend instead.
+
+use crate::services::api_service::{create_preview_link, create_share_preview, get_public_settings, ApiError, SharePreviewLink};
+
+/// Query parameter carrying a preview token on public URLs
+pub const PREVIEW_PARAM: &str = "preview";
+/// Query parameter carrying a draft share token on public URLs
+pub const SHARE_PARAM: &str = "share";
+
+/// Content that can be opened on the public site
+#[derive(Clone, PartialEq)]
+pub enum PublicContent {
+    Post { id: i32, status: String },
+    Page { id: i32, slug: String, status: String },
+}
+
+impl PublicContent {
+    fn is_published(&self) -> bool {
+        match self {
+            PublicContent::Post { status, .. } | PublicContent::Page { status, .. } => status == "published",
+        }
+    }
+
+    fn public_path(&self) -> String {
+        match self {
+            PublicContent::Post { id, .. } => format!("/post/{}", id),
+            PublicContent::Page { slug, .. } => format!("/page/{}", slug),
+        }
+    }
+}
+
+/// Join the configured site URL and a path without doubling slashes
+pub fn public_url(site_url: &str, path: &str) -> String {
+    format!("{}/{}", site_url.trim().trim_end_matches('/'), path.trim_start_matches('/'))
+}
+
+/// Base URL of the public site: the `site_url` setting, or this origin
+async fn site_url() -> String {
+    let configured = get_public_settings(Some("site")).await
+        .ok()
+        .and_then(|settings| settings.into_iter().find(|s| s.setting_key == "site_url"))
+        .and_then(|s| s.setting_value)
+        .filter(|url| !url.trim().is_empty());
+
+    configured.unwrap_or_else(|| {
+        web_sys::window()
+            .and_then(|w| w.location().origin().ok())
+            .unwrap_or_default()
+    })
+}
+
+/// Resolve the public URL for `content`, issuing a preview link if it isn't published
+pub async fn public_view_url(content: &PublicContent) -> Result<String, ApiError> {
+    let path = if content.is_published() {
+        content.public_path()
+    } else {
+        let (content_type, id) = match content {
+            PublicContent::Post { id, .. } => ("post", *id),
+            PublicContent::Page { id, .. } => ("page", *id),
+        };
+        create_preview_link(content_type, id).await?.path
+    };
+    Ok(public_url(&site_url().await, &path))
+}
+
+/// Issue a share link for a draft post; returns its full URL and details
+pub async fn share_post_preview(post_id: i32) -> Result<(String, SharePreviewLink), ApiError> {
+    let link = create_share_preview(post_id).await?;
+    Ok((public_url(&site_url().await, &link.path), link))
+}
+
+/// Open the public view of `content` in a new tab.
+///
+/// The tab is opened immediately so popup blockers treat it as part of the
+/// click, then pointed at the URL once it has been resolved.
+pub fn open_public_view(content: PublicContent, on_error: impl Fn(String) + 'static) {
+    let Some(tab) = web_sys::window().and_then(|w| w.open_with_url_and_target("about:blank", "_blank").ok().flatten()) else {
+        on_error("Could not open a new tab".to_string());
+        return;
+    };
+
+    wasm_bindgen_futures::spawn_local(async move {
+        match public_view_url(&content).await {
+            Ok(url) => {
+                let _ = tab.location().set_href(&url);
+            }
+            Err(e) => {
+                let _ = tab.close();
+                on_error(format!("Failed to create preview link: {}", e));
+            }
+        }
+    });
+}
+
+fn token_from_location(param: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search.trim_start_matches('?')
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == param)
+        .and_then(|(_, value)| js_sys::decode_uri_component(value).ok())
+        .map(String::from)
+        .filter(|token| !token.is_empty())
+}
+
+/// Preview token from the current page's query string, if any
+pub fn preview_token_from_location() -> Option<String> {
+    token_from_location(PREVIEW_PARAM)
+}
+
+/// Draft share token from the current page's query string, if any
+pub fn share_token_from_location() -> Option<String> {
+    token_from_location(SHARE_PARAM)
+}
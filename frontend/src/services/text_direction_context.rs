@@ -0,0 +1,79 @@
+use yew::prelude::*;
+use crate::services::api_service::get_public_settings;
+
+pub const TEXT_DIRECTION_SETTING: &str = "text_direction";
+
+/// Reading direction, set as the `dir` attribute of the public and admin roots
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TextDirection {
+    #[default]
+    Ltr,
+    Rtl,
+    Auto,
+}
+
+impl TextDirection {
+    /// Interpret a `text_direction` setting value; unknown values mean ltr
+    pub fn from_setting(value: &str) -> Self {
+        match value.trim() {
+            "rtl" => Self::Rtl,
+            "auto" => Self::Auto,
+            _ => Self::Ltr,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ltr => "ltr",
+            Self::Rtl => "rtl",
+            Self::Auto => "auto",
+        }
+    }
+}
+
+/// Site reading direction; settable so a saved setting applies immediately
+pub type TextDirectionContext = UseStateHandle<TextDirection>;
+
+#[derive(Properties, PartialEq)]
+pub struct TextDirectionProviderProps {
+    pub children: Children,
+}
+
+/// Loads the site `text_direction` setting once and shares it with the app
+///
+/// Layouts render left to right until the setting arrives. The direction
+/// goes on the layout roots rather than `<body>`, whose classes the public
+/// layout and admin theme rewrite.
+#[function_component(TextDirectionProvider)]
+pub fn text_direction_provider(props: &TextDirectionProviderProps) -> Html {
+    let direction = use_state(TextDirection::default);
+
+    {
+        let direction = direction.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Ok(settings) = get_public_settings(Some("site")).await {
+                    if let Some(value) = settings.into_iter()
+                        .find(|s| s.setting_key == TEXT_DIRECTION_SETTING)
+                        .and_then(|s| s.setting_value)
+                    {
+                        direction.set(TextDirection::from_setting(&value));
+                    }
+                }
+            });
+            || ()
+        }, ());
+    }
+
+    html! {
+        <ContextProvider<TextDirectionContext> context={direction}>
+            {props.children.clone()}
+        </ContextProvider<TextDirectionContext>>
+    }
+}
+
+// Hook for the direction layouts should render in
+#[hook]
+pub fn use_text_direction() -> TextDirection {
+    use_context::<TextDirectionContext>().map(|direction| *direction).unwrap_or_default()
+}
@@ -0,0 +1,50 @@
+use yew::prelude::*;
+use crate::services::api_service::get_public_settings;
+use crate::services::local_time::DisplayTimezone;
+
+const TIMEZONE_SETTING: &str = "timezone";
+
+/// Site display time zone; settable so a saved setting applies immediately
+pub type TimezoneContext = UseStateHandle<DisplayTimezone>;
+
+#[derive(Properties, PartialEq)]
+pub struct TimezoneProviderProps {
+    pub children: Children,
+}
+
+/// Loads the site `timezone` setting once and shares it with the app
+///
+/// Dates render in UTC until the setting arrives.
+#[function_component(TimezoneProvider)]
+pub fn timezone_provider(props: &TimezoneProviderProps) -> Html {
+    let timezone = use_state(DisplayTimezone::default);
+
+    {
+        let timezone = timezone.clone();
+        use_effect_with_deps(move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Ok(settings) = get_public_settings(Some("site")).await {
+                    if let Some(value) = settings.into_iter()
+                        .find(|s| s.setting_key == TIMEZONE_SETTING)
+                        .and_then(|s| s.setting_value)
+                    {
+                        timezone.set(DisplayTimezone::from_setting(&value));
+                    }
+                }
+            });
+            || ()
+        }, ());
+    }
+
+    html! {
+        <ContextProvider<TimezoneContext> context={timezone}>
+            {props.children.clone()}
+        </ContextProvider<TimezoneContext>>
+    }
+}
+
+// Hook for the zone dates should be displayed in
+#[hook]
+pub fn use_display_timezone() -> DisplayTimezone {
+    use_context::<TimezoneContext>().map(|tz| *tz).unwrap_or_default()
+}
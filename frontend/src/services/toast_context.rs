@@ -0,0 +1,114 @@
+use yew::prelude::*;
+use std::rc::Rc;
+use crate::components::notification::{Notification, NotificationContainer, NotificationType};
+
+/// Toasts shown at once; later ones wait their turn in the queue
+const MAX_VISIBLE_TOASTS: usize = 4;
+const SUCCESS_DURATION_MS: u32 = 3000;
+const INFO_DURATION_MS: u32 = 4000;
+const ERROR_DURATION_MS: u32 = 8000;
+
+#[derive(Clone, PartialEq, Default)]
+pub struct ToastState {
+    pub toasts: Vec<Notification>,
+    next_id: u32,
+}
+
+pub enum ToastAction {
+    Push {
+        message: String,
+        notification_type: NotificationType,
+        duration: Option<u32>,
+    },
+    Dismiss(String),
+}
+
+impl Reducible for ToastState {
+    type Action = ToastAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = (*self).clone();
+        match action {
+            ToastAction::Push { message, notification_type, duration } => {
+                state.next_id += 1;
+                state.toasts.push(Notification {
+                    id: format!("toast-{}", state.next_id),
+                    message,
+                    notification_type,
+                    duration,
+                });
+            }
+            ToastAction::Dismiss(id) => {
+                state.toasts.retain(|toast| toast.id != id);
+            }
+        }
+        Rc::new(state)
+    }
+}
+
+pub type ToastContext = UseReducerHandle<ToastState>;
+
+/// Handle returned by `use_toast` for raising notifications
+#[derive(Clone)]
+pub struct Toaster {
+    context: ToastContext,
+}
+
+impl Toaster {
+    pub fn show(&self, message: impl Into<String>, notification_type: NotificationType, duration: Option<u32>) {
+        self.context.dispatch(ToastAction::Push {
+            message: message.into(),
+            notification_type,
+            duration,
+        });
+    }
+
+    pub fn success(&self, message: impl Into<String>) {
+        self.show(message, NotificationType::Success, Some(SUCCESS_DURATION_MS));
+    }
+
+    #[allow(dead_code)]
+    pub fn info(&self, message: impl Into<String>) {
+        self.show(message, NotificationType::Info, Some(INFO_DURATION_MS));
+    }
+
+    pub fn error(&self, message: impl Into<String>) {
+        self.show(message, NotificationType::Error, Some(ERROR_DURATION_MS));
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ToastProviderProps {
+    pub children: Children,
+}
+
+#[function_component(ToastProvider)]
+pub fn toast_provider(props: &ToastProviderProps) -> Html {
+    let toast_state = use_reducer(ToastState::default);
+
+    let on_close = {
+        let toast_state = toast_state.clone();
+        Callback::from(move |id: String| toast_state.dispatch(ToastAction::Dismiss(id)))
+    };
+
+    // Auto-dismiss timers start when a toast becomes visible, so queued
+    // toasts get their full display time
+    let visible: Vec<Notification> = toast_state.toasts.iter()
+        .take(MAX_VISIBLE_TOASTS)
+        .cloned()
+        .collect();
+
+    html! {
+        <ContextProvider<ToastContext> context={toast_state}>
+            {props.children.clone()}
+            <NotificationContainer notifications={visible} on_close={on_close} />
+        </ContextProvider<ToastContext>>
+    }
+}
+
+// Hook to raise toasts from any component under ToastProvider
+#[hook]
+pub fn use_toast() -> Toaster {
+    let context = use_context::<ToastContext>().expect("use_toast must be used within ToastProvider");
+    Toaster { context }
+}
@@ -1,8 +1,7 @@
-use gloo_net::http::Request;
 use serde::{Deserialize, Serialize};
-use gloo_storage::{LocalStorage, Storage};
 
-use crate::services::auth_service::{get_auth_token, AuthError, User};
+use crate::services::api_service::{create_authenticated_request, ApiError};
+use crate::services::auth_service::User;
 
 const API_BASE_URL: &str = "http://localhost:8081/api";
 
@@ -21,6 +20,7 @@ pub struct UpdateUserRequest {
     pub password: Option<String>,
     pub role: Option<String>,
     pub status: Option<String>,
+    pub must_change_password: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
@@ -28,152 +28,93 @@ pub struct PromoteUserRequest {
     pub role: String,
 }
 
-pub async fn get_users() -> Result<Vec<User>, AuthError> {
-    let token = get_auth_token()?;
-    
-    let response = Request::get(&format!("{}/users", API_BASE_URL))
-        .header("Authorization", &format!("Bearer {}", token))
-        .send()
-        .await
-        .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+#[derive(Deserialize)]
+struct PagedUsers {
+    users: Vec<User>,
+    total_pages: i64,
+}
 
-    if response.status() == 200 {
-        let users: Vec<User> = response
-            .json()
+/// Fetch every user by walking the paginated listing
+pub async fn get_users() -> Result<Vec<User>, ApiError> {
+    let mut users = Vec::new();
+    let mut page: i64 = 1;
+    loop {
+        let response = create_authenticated_request("GET", &format!("{}/users", API_BASE_URL))?
+            .query([("page", page.to_string()), ("per_page", "100".to_string())])
+            .send()
             .await
-            .map_err(|e| AuthError::ParseError(e.to_string()))?;
-        Ok(users)
-    } else if response.status() == 401 {
-        // Clear invalid token
-        LocalStorage::delete("auth_token");
-        Err(AuthError::InvalidCredentials)
-    } else {
-        Err(AuthError::ServerError(format!("HTTP {}", response.status())))
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        if response.status() != 200 {
+            return Err(ApiError::from_response(response).await);
+        }
+        let paged: PagedUsers = response.json().await.map_err(|e| ApiError::Parse(e.to_string()))?;
+        users.extend(paged.users);
+        if page >= paged.total_pages {
+            return Ok(users);
+        }
+        page += 1;
     }
 }
 
-pub async fn create_user(user_data: &CreateUserRequest) -> Result<serde_json::Value, AuthError> {
-    let token = get_auth_token()?;
-    
-    let response = Request::post(&format!("{}/users", API_BASE_URL))
-        .header("Authorization", &format!("Bearer {}", token))
+pub async fn create_user(user_data: &CreateUserRequest) -> Result<serde_json::Value, ApiError> {
+    let response = create_authenticated_request("POST", &format!("{}/users", API_BASE_URL))?
         .json(user_data)
-        .map_err(|e| AuthError::NetworkError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
-        let result: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| AuthError::ParseError(e.to_string()))?;
-        Ok(result)
-    } else if response.status() == 401 {
-        LocalStorage::delete("auth_token");
-        Err(AuthError::InvalidCredentials)
-    } else if response.status() == 409 {
-        Err(AuthError::ServerError("Username or email already exists".to_string()))
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
     } else {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to create user".to_string());
-        Err(AuthError::ServerError(error_text))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn update_user(user_id: i32, user_data: &UpdateUserRequest) -> Result<serde_json::Value, AuthError> {
-    let token = get_auth_token()?;
-    
-    let response = Request::put(&format!("{}/users/{}", API_BASE_URL, user_id))
-        .header("Authorization", &format!("Bearer {}", token))
+pub async fn update_user(user_id: i32, user_data: &UpdateUserRequest) -> Result<serde_json::Value, ApiError> {
+    let response = create_authenticated_request("PUT", &format!("{}/users/{}", API_BASE_URL, user_id))?
         .json(user_data)
-        .map_err(|e| AuthError::NetworkError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
-        let result: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| AuthError::ParseError(e.to_string()))?;
-        Ok(result)
-    } else if response.status() == 401 {
-        LocalStorage::delete("auth_token");
-        Err(AuthError::InvalidCredentials)
-    } else if response.status() == 404 {
-        Err(AuthError::ServerError("User not found".to_string()))
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
     } else {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to update user".to_string());
-        Err(AuthError::ServerError(error_text))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn promote_user(user_id: i32, role: &str) -> Result<serde_json::Value, AuthError> {
-    let token = get_auth_token()?;
-    
+pub async fn promote_user(user_id: i32, role: &str) -> Result<serde_json::Value, ApiError> {
     let promote_data = PromoteUserRequest {
         role: role.to_string(),
     };
     
-    let response = Request::put(&format!("{}/users/{}/promote", API_BASE_URL, user_id))
-        .header("Authorization", &format!("Bearer {}", token))
+    let response = create_authenticated_request("PUT", &format!("{}/users/{}/promote", API_BASE_URL, user_id))?
         .json(&promote_data)
-        .map_err(|e| AuthError::NetworkError(e.to_string()))?
+        .map_err(|e| ApiError::Parse(e.to_string()))?
         .send()
         .await
-        .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
-        let result: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| AuthError::ParseError(e.to_string()))?;
-        Ok(result)
-    } else if response.status() == 401 {
-        LocalStorage::delete("auth_token");
-        Err(AuthError::InvalidCredentials)
-    } else if response.status() == 404 {
-        Err(AuthError::ServerError("User not found".to_string()))
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
     } else {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to promote user".to_string());
-        Err(AuthError::ServerError(error_text))
+        Err(ApiError::from_response(response).await)
     }
 }
 
-pub async fn delete_user(user_id: i32) -> Result<serde_json::Value, AuthError> {
-    let token = get_auth_token()?;
-    
-    let response = Request::delete(&format!("{}/users/{}", API_BASE_URL, user_id))
-        .header("Authorization", &format!("Bearer {}", token))
+pub async fn delete_user(user_id: i32) -> Result<serde_json::Value, ApiError> {
+    let response = create_authenticated_request("DELETE", &format!("{}/users/{}", API_BASE_URL, user_id))?
         .send()
         .await
-        .map_err(|e| AuthError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if response.status() == 200 {
-        let result: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| AuthError::ParseError(e.to_string()))?;
-        Ok(result)
-    } else if response.status() == 401 {
-        LocalStorage::delete("auth_token");
-        Err(AuthError::InvalidCredentials)
-    } else if response.status() == 404 {
-        Err(AuthError::ServerError("User not found".to_string()))
+        response.json().await.map_err(|e| ApiError::Parse(e.to_string()))
     } else {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to delete user".to_string());
-        Err(AuthError::ServerError(error_text))
+        Err(ApiError::from_response(response).await)
     }
 }
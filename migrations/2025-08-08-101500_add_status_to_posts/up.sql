@@ -0,0 +1,4 @@
+-- Track publishing status on posts so drafts can be kept out of public listings
+ALTER TABLE posts ADD COLUMN status VARCHAR NOT NULL DEFAULT 'published';
+
+CREATE INDEX idx_posts_status ON posts(status);
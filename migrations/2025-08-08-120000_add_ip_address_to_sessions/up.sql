@@ -0,0 +1,2 @@
+-- Record the IP address a session was created from so it can optionally be bound to it
+ALTER TABLE sessions ADD COLUMN ip_address VARCHAR;
@@ -0,0 +1,2 @@
+-- Track when each session was last used so idle sessions can be expired
+ALTER TABLE sessions ADD COLUMN last_seen_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP;
@@ -0,0 +1,2 @@
+ALTER TABLE navigation DROP COLUMN IF EXISTS visible_until;
+ALTER TABLE navigation DROP COLUMN IF EXISTS visible_from;
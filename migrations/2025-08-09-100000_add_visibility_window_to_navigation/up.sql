@@ -0,0 +1,3 @@
+-- Optional schedule for campaign links; NULL bounds mean always visible
+ALTER TABLE navigation ADD COLUMN visible_from TIMESTAMP;
+ALTER TABLE navigation ADD COLUMN visible_until TIMESTAMP;
@@ -0,0 +1,4 @@
+-- Publish date for published posts and target date for scheduled ones (UTC)
+ALTER TABLE posts ADD COLUMN published_at TIMESTAMP;
+UPDATE posts SET published_at = COALESCE(created_at, NOW()) WHERE status = 'published';
+CREATE INDEX idx_posts_published_at ON posts(published_at);
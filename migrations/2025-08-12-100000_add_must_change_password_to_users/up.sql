@@ -0,0 +1,2 @@
+-- Admin-forced password change, checked at login and by the auth middleware
+ALTER TABLE users ADD COLUMN must_change_password BOOLEAN NOT NULL DEFAULT FALSE;
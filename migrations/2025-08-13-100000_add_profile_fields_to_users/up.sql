@@ -0,0 +1,4 @@
+-- Self-service profile: a short bio, and a new email address awaiting
+-- confirmation while the current one keeps working
+ALTER TABLE users ADD COLUMN bio TEXT;
+ALTER TABLE users ADD COLUMN pending_email VARCHAR;
@@ -0,0 +1,2 @@
+-- Set on each password login, not on session refresh
+ALTER TABLE users ADD COLUMN last_login_at TIMESTAMP;
@@ -0,0 +1,11 @@
+-- Old slugs of renamed content, so links to them keep working
+CREATE TABLE slug_redirects (
+    id SERIAL PRIMARY KEY,
+    resource_type VARCHAR NOT NULL,
+    resource_id INTEGER NOT NULL,
+    old_slug VARCHAR NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (resource_type, old_slug)
+);
+
+CREATE INDEX idx_slug_redirects_resource ON slug_redirects (resource_type, resource_id);
@@ -0,0 +1,4 @@
+-- Moderation state of a comment; only approved comments are shown publicly
+ALTER TABLE comments ADD COLUMN status VARCHAR NOT NULL DEFAULT 'approved';
+
+CREATE INDEX idx_comments_status ON comments(status);
@@ -0,0 +1,16 @@
+-- Results of create requests sent with an Idempotency-Key, so a retried
+-- request gets the original response instead of creating a duplicate
+CREATE TABLE idempotency_keys (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    idempotency_key VARCHAR NOT NULL,
+    -- Hash of the method, path and body the key was first used with
+    request_fingerprint VARCHAR NOT NULL,
+    -- Both NULL while the first request is still being handled
+    response_status INTEGER,
+    response_body TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, idempotency_key)
+);
+
+CREATE INDEX idx_idempotency_keys_created_at ON idempotency_keys (created_at);
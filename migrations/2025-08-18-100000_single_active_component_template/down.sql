@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_component_templates_one_active;
@@ -0,0 +1,18 @@
+-- The public layout shows one template per component type. Keep the
+-- default (then the most recently updated) active template of each type
+-- and deactivate the rest, then make sure no second one can be activated.
+WITH ranked AS (
+    SELECT id, ROW_NUMBER() OVER (
+        PARTITION BY component_type
+        ORDER BY is_default DESC, updated_at DESC NULLS LAST, id ASC
+    ) AS position
+    FROM component_templates
+    WHERE is_active
+)
+UPDATE component_templates
+SET is_active = false, updated_at = NOW()
+WHERE id IN (SELECT id FROM ranked WHERE position > 1);
+
+CREATE UNIQUE INDEX idx_component_templates_one_active
+    ON component_templates (component_type)
+    WHERE is_active;
@@ -0,0 +1,15 @@
+-- Links that show an unpublished post to reviewers without an account. The
+-- link carries a signed token whose id is stored here, so views can be
+-- counted and capped.
+CREATE TABLE post_share_links (
+    id SERIAL PRIMARY KEY,
+    post_id INTEGER NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    token_id VARCHAR NOT NULL UNIQUE,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMP NOT NULL,
+    max_views INTEGER NOT NULL,
+    view_count INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_post_share_links_expires_at ON post_share_links (expires_at);
@@ -0,0 +1,2 @@
+-- The login before the latest one, shown to the user as their last login
+ALTER TABLE users ADD COLUMN previous_login_at TIMESTAMP;
@@ -0,0 +1,2 @@
+UPDATE settings SET setting_type = 'site'
+WHERE setting_key IN ('comment_blocked_words', 'comment_word_filter_mode');
@@ -0,0 +1,4 @@
+-- The comment word filter was seeded as a public "site" setting, which put
+-- the blocklist in the public settings and bootstrap responses
+UPDATE settings SET setting_type = 'system'
+WHERE setting_key IN ('comment_blocked_words', 'comment_word_filter_mode');
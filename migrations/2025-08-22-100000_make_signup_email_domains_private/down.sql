@@ -0,0 +1,2 @@
+UPDATE settings SET setting_type = 'site'
+WHERE setting_key IN ('signup_allowed_email_domains', 'signup_blocked_email_domains');
@@ -0,0 +1,5 @@
+-- The signup email domain lists were seeded as public "site" settings,
+-- which put the allow and block lists in the public settings and bootstrap
+-- responses
+UPDATE settings SET setting_type = 'system'
+WHERE setting_key IN ('signup_allowed_email_domains', 'signup_blocked_email_domains');
@@ -0,0 +1,13 @@
+-- Admin actions that need a record beyond the application log, such as a
+-- global session reset
+CREATE TABLE audit_log (
+    id SERIAL PRIMARY KEY,
+    action VARCHAR NOT NULL,
+    -- NULL once the acting user is deleted; the username is kept
+    actor_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    actor_username VARCHAR NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_log_created_at ON audit_log (created_at);
@@ -0,0 +1,277 @@
+//! Inline styles derived from component template data
+//!
+//! The public layout, the template editor's live preview and server-side
+//! rendering all turn a template's `template_data` into CSS here, so they
+//! agree on what the site looks like. Only a safe subset of `template_data`
+//! becomes CSS. Results are lists of declarations (property or CSS variable
+//! and value), joined into a `style` attribute with [`to_style`].
+
+use serde_json::Value;
+
+/// A CSS property or custom property and its value
+pub type Declaration = (&'static str, String);
+
+/// The header never gets shorter than this, so the navigation always fits
+pub const MIN_HEADER_HEIGHT: &str = "110px";
+const MIN_HEADER_HEIGHT_PX: i32 = 110;
+
+fn text<'a>(data: &'a Value, key: &str) -> Option<&'a str> {
+    data.get(key).and_then(Value::as_str)
+}
+
+/// Header heights under the minimum are raised to it
+fn header_height(height: &str) -> &str {
+    match height.strip_suffix("px").and_then(|px| px.trim().parse::<i32>().ok()) {
+        Some(px) if px < MIN_HEADER_HEIGHT_PX => MIN_HEADER_HEIGHT,
+        _ => height,
+    }
+}
+
+/// The default theme keeps a dark header, so a white one is turned black
+fn header_background(background: &str) -> &str {
+    if background.trim().eq_ignore_ascii_case("#ffffff") { "#000000" } else { background }
+}
+
+/// Push `property: value` for each `template_data` key that is set
+fn push_keys(declarations: &mut Vec<Declaration>, data: &Value, keys: &[(&str, &'static str)]) {
+    for (key, property) in keys {
+        if let Some(value) = text(data, key) {
+            declarations.push((property, value.to_string()));
+        }
+    }
+}
+
+/// Declarations for a component of the given type with this template data
+pub fn component_declarations(component_type: &str, data: &Value) -> Vec<Declaration> {
+    let mut declarations = Vec::new();
+    let is_header = component_type == "header";
+    let is_footer = component_type == "footer";
+
+    match text(data, "height") {
+        Some(height) if is_header => declarations.push(("height", header_height(height).to_string())),
+        Some(height) => declarations.push(("height", height.to_string())),
+        None if is_header => declarations.push(("height", MIN_HEADER_HEIGHT.to_string())),
+        None => {}
+    }
+
+    // Only allow position overrides for non-header components to avoid layout breaks
+    if !is_header {
+        push_keys(&mut declarations, data, &[("position", "position")]);
+    }
+
+    // Support both background (gradients/images) and background_color
+    if is_header {
+        if let Some(bg) = text(data, "background") {
+            declarations.push(("background", header_background(bg).to_string()));
+        } else if let Some(bg) = text(data, "background_color") {
+            declarations.push(("background-color", header_background(bg).to_string()));
+        } else {
+            declarations.push(("background-color", "#000000".to_string()));
+        }
+    } else if is_footer {
+        if let Some(bg) = text(data, "background").or_else(|| text(data, "background_color")) {
+            declarations.push(("--public-footer-bg", bg.to_string()));
+        }
+    } else if let Some(background) = text(data, "background") {
+        declarations.push(("background", background.to_string()));
+    } else if let Some(background) = text(data, "background_color") {
+        declarations.push(("background-color", background.to_string()));
+    }
+
+    // Text colors reach the header and footer contents through CSS variables
+    if is_header {
+        // White text by default for readability on the black header
+        declarations.push(("--header-text", text(data, "text_color").unwrap_or("#ffffff").to_string()));
+        declarations.push(("--header-text-hover", text(data, "text_hover_color").unwrap_or("#f7fafc").to_string()));
+        push_keys(&mut declarations, data, &[
+            ("nav_hover_color", "--nav-hover-color"),
+            ("nav_underline_color", "--nav-underline-color"),
+            ("nav_underline_thickness", "--nav-underline-thickness"),
+            ("nav_underline_animation", "--nav-underline-animation"),
+        ]);
+    }
+    if is_footer {
+        push_keys(&mut declarations, data, &[
+            ("text_color", "--footer-text"),
+            ("text_muted", "--footer-text-muted"),
+            ("background", "--footer-background"),
+        ]);
+    }
+
+    match data.get("z_index") {
+        Some(Value::Number(z)) if z.is_i64() => declarations.push(("z-index", z.to_string())),
+        Some(Value::String(z)) => declarations.push(("z-index", z.clone())),
+        _ => {}
+    }
+
+    push_keys(&mut declarations, data, &[
+        ("padding", "padding"),
+        ("margin", "margin"),
+        ("border", "border"),
+        ("box_shadow", "box-shadow"),
+    ]);
+    declarations
+}
+
+/// Inline style for a component of the given type with this template data
+pub fn component_style(component_type: &str, data: &Value) -> String {
+    to_style(&component_declarations(component_type, data))
+}
+
+/// How the header shows the site's identity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderLogo {
+    /// The site title as text
+    Title,
+    /// The logo image in place of the title
+    Logo,
+    /// The logo image beside the title
+    LogoAndTitle,
+}
+
+/// What the header shows, given its template data and whether the site has
+/// a logo. A header whose `logo_type` is `icon` puts the logo beside the
+/// title; any other shows the logo alone. Without a logo it is the title.
+pub fn header_logo(data: &Value, has_logo: bool) -> HeaderLogo {
+    match (has_logo, text(data, "logo_type")) {
+        (false, _) => HeaderLogo::Title,
+        (true, Some("icon")) => HeaderLogo::LogoAndTitle,
+        (true, _) => HeaderLogo::Logo,
+    }
+}
+
+/// Inline style for the header's logo image: `logo_size` sets its font
+/// size, and the image is `2em` tall in CSS
+pub fn logo_style(data: &Value) -> String {
+    let mut declarations = Vec::new();
+    push_keys(&mut declarations, data, &[("logo_size", "font-size")]);
+    to_style(&declarations)
+}
+
+/// Template keys turned into site-wide CSS variables, by component type
+const GLOBAL_VARS: &[(&str, &[(&str, &str)])] = &[
+    ("posts_list", &[
+        ("card_background", "--posts-card-bg"),
+        ("card_radius", "--posts-card-radius"),
+        ("card_shadow", "--posts-card-shadow"),
+        ("title_color", "--posts-title-color"),
+        ("meta_color", "--posts-meta-color"),
+        ("link_color", "--posts-link-color"),
+        ("grid_gap", "--posts-grid-gap"),
+    ]),
+    ("hero", &[
+        ("background", "--hero-bg"),
+        ("text_color", "--hero-text"),
+    ]),
+    ("header", &[
+        ("button_primary_bg", "--button-primary-bg"),
+        ("button_primary_text", "--button-primary-text"),
+        ("button_primary_hover_bg", "--button-primary-hover-bg"),
+        ("badge_bg", "--badge-bg"),
+        ("badge_text", "--badge-text"),
+    ]),
+    ("main_container", &[
+        ("background_animation", "--bg-animation"),
+    ]),
+];
+
+/// Site-wide CSS variables (post cards, hero, buttons, badges and the
+/// background animation), given the active template data for a type
+pub fn global_declarations<'a>(active_data: impl Fn(&str) -> Option<&'a Value>) -> Vec<Declaration> {
+    let mut declarations = Vec::new();
+    for (component_type, keys) in GLOBAL_VARS {
+        if let Some(data) = active_data(component_type) {
+            push_keys(&mut declarations, data, keys);
+        }
+    }
+    declarations
+}
+
+/// Join declarations into a `style` attribute value
+pub fn to_style(declarations: &[Declaration]) -> String {
+    declarations.iter()
+        .map(|(property, value)| format!("{}: {}", property, value))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn value<'a>(declarations: &'a [Declaration], property: &str) -> Option<&'a str> {
+        declarations.iter().find(|(p, _)| *p == property).map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_header_height_and_background_coercion() {
+        let short_white = component_declarations("header", &json!({"height": "72px", "background_color": "#FFFFFF"}));
+        assert_eq!(value(&short_white, "height"), Some("110px"));
+        assert_eq!(value(&short_white, "background-color"), Some("#000000"));
+
+        let tall = component_declarations("header", &json!({"height": "140px", "background": "linear-gradient(#fff, #000)"}));
+        assert_eq!(value(&tall, "height"), Some("140px"));
+        assert_eq!(value(&tall, "background"), Some("linear-gradient(#fff, #000)"));
+        assert_eq!(value(&component_declarations("header", &json!({"height": "5rem"})), "height"), Some("5rem"));
+
+        let empty = component_declarations("header", &json!({"position": "fixed"}));
+        assert_eq!(value(&empty, "height"), Some(MIN_HEADER_HEIGHT));
+        assert_eq!(value(&empty, "background-color"), Some("#000000"));
+        assert_eq!(value(&empty, "--header-text"), Some("#ffffff"));
+        assert_eq!(value(&empty, "position"), None);
+    }
+
+    #[test]
+    fn test_footer_css_vars() {
+        let footer = component_declarations("footer", &json!({
+            "background": "#111827", "text_color": "#f9fafb", "text_muted": "#9ca3af", "position": "relative"
+        }));
+        assert_eq!(footer, vec![
+            ("position", "relative".to_string()),
+            ("--public-footer-bg", "#111827".to_string()),
+            ("--footer-text", "#f9fafb".to_string()),
+            ("--footer-text-muted", "#9ca3af".to_string()),
+            ("--footer-background", "#111827".to_string()),
+        ]);
+        let color_only = component_declarations("footer", &json!({"background_color": "#222"}));
+        assert_eq!(to_style(&color_only), "--public-footer-bg: #222");
+    }
+
+    #[test]
+    fn test_box_styles_and_z_index() {
+        let style = component_style("sidebar", &json!({
+            "background_color": "#eee", "z_index": 5, "padding": "1rem", "box_shadow": "none", "sticky": true
+        }));
+        assert_eq!(style, "background-color: #eee; z-index: 5; padding: 1rem; box-shadow: none");
+        assert_eq!(component_style("modal", &json!({"z_index": "auto"})), "z-index: auto");
+        assert_eq!(component_style("modal", &json!({"z_index": 1.5})), "");
+    }
+
+    #[test]
+    fn test_header_logo() {
+        let icon = json!({"logo_type": "icon", "logo_size": "2rem"});
+        assert_eq!(header_logo(&icon, true), HeaderLogo::LogoAndTitle);
+        assert_eq!(header_logo(&icon, false), HeaderLogo::Title);
+        assert_eq!(header_logo(&json!({"logo_type": "text"}), true), HeaderLogo::Logo);
+        assert_eq!(header_logo(&json!({}), true), HeaderLogo::Logo);
+        assert_eq!(logo_style(&icon), "font-size: 2rem");
+        assert_eq!(logo_style(&json!({})), "");
+    }
+
+    #[test]
+    fn test_posts_list_and_global_vars() {
+        let posts = json!({"card_background": "#fff", "card_radius": "12px", "grid_gap": "2rem", "unknown": "x"});
+        let header = json!({"button_primary_bg": "#4f46e5", "badge_text": "#fff"});
+        let vars = global_declarations(|component_type| match component_type {
+            "posts_list" => Some(&posts),
+            "header" => Some(&header),
+            _ => None,
+        });
+        assert_eq!(
+            to_style(&vars),
+            "--posts-card-bg: #fff; --posts-card-radius: 12px; --posts-grid-gap: 2rem; --button-primary-bg: #4f46e5; --badge-text: #fff"
+        );
+        assert!(global_declarations(|_| None).is_empty());
+    }
+}
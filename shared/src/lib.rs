@@ -0,0 +1,4 @@
+//! Code shared by the backend and the frontend
+
+pub mod component_style;
+pub mod return_path;